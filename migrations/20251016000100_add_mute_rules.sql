@@ -0,0 +1,12 @@
+-- Mute rules: keyword/regex filters applied to articles, globally or per feed
+
+CREATE TABLE mute_rules (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    feed_id UUID REFERENCES feeds(id) ON DELETE CASCADE,
+    pattern TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_mute_rules_user_id ON mute_rules(user_id);
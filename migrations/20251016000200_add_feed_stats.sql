@@ -0,0 +1,7 @@
+-- Feed activity statistics, derived from the entries exposed by the feed document
+
+ALTER TABLE feeds ADD COLUMN avg_articles_per_day DOUBLE PRECISION;
+ALTER TABLE feeds ADD COLUMN last_published_at TIMESTAMPTZ;
+ALTER TABLE feeds ADD COLUMN stats_updated_at TIMESTAMPTZ;
+
+CREATE INDEX idx_feeds_stats_updated_at ON feeds(stats_updated_at NULLS FIRST);
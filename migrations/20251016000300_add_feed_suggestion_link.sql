@@ -0,0 +1,6 @@
+-- Link feeds created from a suggestion back to the suggestion and its category
+
+ALTER TABLE feeds ADD COLUMN suggestion_id TEXT;
+ALTER TABLE feeds ADD COLUMN category_id TEXT;
+
+CREATE INDEX idx_feeds_category_id ON feeds(category_id) WHERE category_id IS NOT NULL;
@@ -0,0 +1,14 @@
+-- Store purchases validated against the app stores, so a purchase token can only
+-- unlock one account
+
+CREATE TABLE subscription_purchases (
+    purchase_token TEXT PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    platform TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_subscription_purchases_user_id ON subscription_purchases(user_id);
@@ -0,0 +1,8 @@
+-- Track the free trial explicitly instead of deriving it from created_at
+ALTER TABLE users ADD COLUMN trial_started_at TIMESTAMPTZ;
+ALTER TABLE users ADD COLUMN trial_ends_at TIMESTAMPTZ;
+
+-- Existing accounts keep the trial they implicitly had since signup
+UPDATE users
+SET trial_started_at = created_at,
+    trial_ends_at = created_at + INTERVAL '7 days';
@@ -0,0 +1,3 @@
+-- Profile details from the OAuth provider; display_name can be edited by the user
+ALTER TABLE users ADD COLUMN display_name TEXT;
+ALTER TABLE users ADD COLUMN avatar_url TEXT;
@@ -0,0 +1,2 @@
+-- Per-user exceptions to the plan limits (e.g. beta testers, press), set via the admin API
+ALTER TABLE users ADD COLUMN limit_overrides JSONB;
@@ -0,0 +1,31 @@
+-- Seed the per-language voice map from the single voice setting. The voice
+-- field itself is kept for older clients.
+UPDATE users
+SET settings = jsonb_set(
+    settings,
+    '{voices}',
+    jsonb_build_object(
+        CASE settings->>'voice'
+            WHEN 'Lucia' THEN 'es'
+            WHEN 'Sergio' THEN 'es'
+            WHEN 'Conchita' THEN 'es'
+            WHEN 'Matthew' THEN 'en'
+            WHEN 'Joanna' THEN 'en'
+            WHEN 'Amy' THEN 'en'
+            WHEN 'Celine' THEN 'fr'
+            WHEN 'Mathieu' THEN 'fr'
+            WHEN 'Hans' THEN 'de'
+            WHEN 'Marlene' THEN 'de'
+            WHEN 'Ricardo' THEN 'pt'
+            WHEN 'Ines' THEN 'pt'
+            WHEN 'Carla' THEN 'it'
+            WHEN 'Giorgio' THEN 'it'
+        END,
+        settings->>'voice'
+    )
+)
+WHERE NOT settings ? 'voices'
+  AND settings->>'voice' IN (
+      'Lucia', 'Sergio', 'Conchita', 'Matthew', 'Joanna', 'Amy', 'Celine',
+      'Mathieu', 'Hans', 'Marlene', 'Ricardo', 'Ines', 'Carla', 'Giorgio'
+  );
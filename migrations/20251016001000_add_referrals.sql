@@ -0,0 +1,15 @@
+-- Referral program: each user gets a shareable code, and both sides of a redeemed
+-- referral get extra characters until referral_bonus_until
+
+ALTER TABLE users ADD COLUMN referral_code TEXT UNIQUE;
+ALTER TABLE users ADD COLUMN referral_bonus_until TIMESTAMPTZ;
+
+CREATE TABLE referrals (
+    id UUID PRIMARY KEY,
+    referrer_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- A user can only ever be referred once
+    referee_id UUID NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_referrals_referrer_id ON referrals(referrer_id, created_at);
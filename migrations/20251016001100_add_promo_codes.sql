@@ -0,0 +1,20 @@
+-- Promo/gift codes that grant Pro for a number of days, created via the admin API
+
+CREATE TABLE promo_codes (
+    code TEXT PRIMARY KEY,
+    duration_days INTEGER NOT NULL,
+    -- NULL means the code can be redeemed any number of times
+    max_redemptions INTEGER,
+    redemption_count INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+-- Each user can redeem a given code once
+CREATE TABLE promo_code_redemptions (
+    code TEXT NOT NULL REFERENCES promo_codes(code) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    redeemed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (code, user_id)
+);
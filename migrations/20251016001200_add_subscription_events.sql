@@ -0,0 +1,15 @@
+-- Billing history: one row per change to a user's subscription, from store
+-- purchases, renewals, refunds and promo code redemptions
+
+CREATE TABLE subscription_events (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    -- NULL for events that didn't come from a store, e.g. promo codes
+    platform TEXT,
+    product_id TEXT,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_subscription_events_user_id ON subscription_events(user_id, created_at DESC);
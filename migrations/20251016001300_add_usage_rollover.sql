@@ -0,0 +1,4 @@
+-- Unused Pro quota carried into the day. NULL on days the user wasn't eligible
+-- for rollover, so a new Pro user starts without a balance.
+
+ALTER TABLE usage_tracking ADD COLUMN rollover_characters INTEGER;
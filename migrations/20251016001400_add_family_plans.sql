@@ -0,0 +1,16 @@
+-- Family plans: a Pro owner shares their limits with up to five members, who keep
+-- their own feeds and usage
+
+ALTER TABLE users ADD COLUMN family_owner_id UUID REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_users_family_owner_id ON users(family_owner_id);
+
+-- Pending invites; deleted once accepted. Re-inviting an email refreshes its invite.
+CREATE TABLE family_invites (
+    code TEXT PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    email TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (owner_id, email)
+);
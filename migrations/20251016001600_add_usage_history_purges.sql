@@ -0,0 +1,10 @@
+-- Record of self-service usage history purges. Only counts are kept, never the usage itself.
+
+CREATE TABLE usage_history_purges (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    deleted_days INTEGER NOT NULL,
+    purged_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_usage_history_purges_user_id ON usage_history_purges(user_id);
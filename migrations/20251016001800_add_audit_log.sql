@@ -0,0 +1,30 @@
+-- Append-only record of who changed what. Rows outlive the accounts they mention,
+-- so user_id has no foreign key.
+
+CREATE TABLE audit_log (
+    id UUID PRIMARY KEY,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    -- 'user', 'admin' or 'system'
+    actor_type TEXT NOT NULL,
+    -- User id, admin email or the name of the background process
+    actor TEXT NOT NULL,
+    action TEXT NOT NULL,
+    -- Account the change applies to
+    user_id UUID,
+    -- Changed record, e.g. a feed id or promo code
+    target TEXT,
+    details JSONB NOT NULL DEFAULT '{}'
+);
+
+CREATE INDEX idx_audit_log_occurred_at ON audit_log(occurred_at DESC);
+CREATE INDEX idx_audit_log_user_id ON audit_log(user_id, occurred_at DESC);
+
+CREATE FUNCTION reject_audit_log_changes() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION reject_audit_log_changes();
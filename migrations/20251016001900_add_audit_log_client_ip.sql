@@ -0,0 +1,3 @@
+-- Address the change was requested from; NULL for background jobs
+
+ALTER TABLE audit_log ADD COLUMN client_ip TEXT;
@@ -0,0 +1,10 @@
+-- Daily synthesis counters for admin statistics, one row per UTC day and provider.
+-- Audio served from the cache is counted under the 'cache' provider.
+
+CREATE TABLE synthesis_stats (
+    date DATE NOT NULL,
+    provider TEXT NOT NULL,
+    requests INTEGER NOT NULL,
+    characters BIGINT NOT NULL,
+    PRIMARY KEY (date, provider)
+);
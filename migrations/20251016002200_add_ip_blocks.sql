@@ -0,0 +1,18 @@
+-- Client addresses refused before any other work is done, added by admins or after
+-- repeated authentication failures
+
+CREATE TABLE ip_blocks (
+    id UUID PRIMARY KEY,
+    -- Single address or CIDR range, e.g. 203.0.113.7/32
+    network TEXT NOT NULL UNIQUE,
+    reason TEXT NOT NULL,
+    -- 'admin' or 'auth_failures'
+    source TEXT NOT NULL,
+    -- Admin email for manual blocks
+    created_by TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    -- NULL blocks until removed
+    expires_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_ip_blocks_expires_at ON ip_blocks(expires_at);
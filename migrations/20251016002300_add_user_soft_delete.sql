@@ -0,0 +1,6 @@
+-- Accounts past their deletion date are hidden first and only removed for good once
+-- this moment is older than the retention period; NULL means live
+ALTER TABLE users ADD COLUMN deleted_at TIMESTAMPTZ;
+
+CREATE INDEX idx_users_deleted_at ON users(deleted_at)
+    WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,19 @@
+-- Transactional outbox: domain events written in the same transaction as the change
+-- they describe, then handed to webhooks and the audit log by the dispatcher
+
+CREATE TABLE outbox_events (
+    id UUID PRIMARY KEY,
+    -- e.g. 'user_created'; the payload carries the same value under "type"
+    event_type TEXT NOT NULL,
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    -- Set once every handler took the event, or retries ran out
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_outbox_events_due ON outbox_events(next_attempt_at)
+    WHERE completed_at IS NULL;
@@ -0,0 +1,16 @@
+-- Product analytics sent by the apps in batches. Rows are only ever inserted.
+
+CREATE TABLE analytics_events (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- e.g. 'screen_view'; properties are validated against its schema on ingestion
+    name TEXT NOT NULL,
+    -- Groups the events of one app launch, as sent by the client
+    session_id TEXT,
+    properties JSONB NOT NULL,
+    -- Client clock when the event happened
+    occurred_at TIMESTAMPTZ NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_analytics_events_name_occurred_at ON analytics_events(name, occurred_at);
@@ -0,0 +1,9 @@
+-- Listening time actually produced each day, measured from the audio, and the
+-- provider and engine that last synthesized for the user that day. Earlier days
+-- only have the 1000-characters-a-minute estimate to go on.
+
+ALTER TABLE usage_tracking ADD COLUMN audio_seconds DOUBLE PRECISION NOT NULL DEFAULT 0;
+ALTER TABLE usage_tracking ADD COLUMN provider TEXT;
+ALTER TABLE usage_tracking ADD COLUMN engine TEXT;
+
+UPDATE usage_tracking SET audio_seconds = characters_used * 0.06;
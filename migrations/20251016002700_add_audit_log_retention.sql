@@ -0,0 +1,26 @@
+-- Let the maintenance job delete audit entries past AUDIT_LOG_RETENTION_DAYS. The log
+-- stays append-only otherwise: a delete only goes through for entries older than a
+-- cutoff in audit_log_purges, which the purge inserts and removes in its own
+-- transaction, so no other session ever sees one.
+CREATE TABLE audit_log_purges (
+    delete_before TIMESTAMPTZ NOT NULL
+);
+
+DROP TRIGGER audit_log_append_only ON audit_log;
+
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION reject_audit_log_changes();
+
+CREATE FUNCTION reject_audit_log_deletes() RETURNS trigger AS $$
+BEGIN
+    IF OLD.occurred_at < (SELECT MAX(delete_before) FROM audit_log_purges) THEN
+        RETURN OLD;
+    END IF;
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_expired_deletes_only
+    BEFORE DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION reject_audit_log_deletes();
@@ -0,0 +1,24 @@
+-- Feeds users propose for the suggestions list. Admins approve them into a category,
+-- after which they are served alongside the curated suggestions, or reject them.
+
+CREATE TABLE suggestion_submissions (
+    id UUID PRIMARY KEY,
+    -- NULL once the submitter's account is gone; approved suggestions stay listed
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    url TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    -- 'pending', 'approved' or 'rejected'
+    status TEXT NOT NULL,
+    -- Suggestion category, set on approval
+    category_id TEXT,
+    -- Told to the submitter on rejection
+    rejection_reason TEXT,
+    -- Admin email
+    reviewed_by TEXT,
+    reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_suggestion_submissions_status ON suggestion_submissions(status, created_at);
+CREATE INDEX idx_suggestion_submissions_user_id ON suggestion_submissions(user_id);
@@ -0,0 +1,14 @@
+-- Weekly listening summaries handled per user. A row is claimed before the summary is
+-- compiled, so instances running the job at the same time don't mail a week twice.
+
+CREATE TABLE weekly_summaries (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Monday the summarised week started on, in the user's timezone
+    week_start DATE NOT NULL,
+    claimed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (user_id, week_start)
+);
+
+-- Summaries total each user's playback events for the week
+CREATE INDEX idx_analytics_events_user_name_occurred_at
+    ON analytics_events(user_id, name, occurred_at);
@@ -0,0 +1,10 @@
+-- State values handed to the OAuth provider when a sign-in starts. The callback has to
+-- bring one back before it expires, and each is used once, so a callback nobody started
+-- here is turned away.
+
+CREATE TABLE oauth_states (
+    state TEXT PRIMARY KEY,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_oauth_states_expires_at ON oauth_states(expires_at);
@@ -0,0 +1,8 @@
+-- Refreshing replaces a token with a new one whose parent is the token it replaced. A
+-- replaced token presented again has been copied, and every token descended from it is
+-- revoked.
+
+ALTER TABLE refresh_tokens
+    ADD COLUMN parent_id UUID REFERENCES refresh_tokens(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_refresh_tokens_parent_id ON refresh_tokens(parent_id);
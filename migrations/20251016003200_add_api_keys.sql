@@ -0,0 +1,16 @@
+-- Long-lived keys users create for scripts, sent as `Authorization: ApiKey <key>`. Only
+-- a SHA-256 digest of each key is stored; the key itself is shown once, at creation.
+
+CREATE TABLE api_keys (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    -- Start of the key, so users can tell their keys apart
+    prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    -- Route groups the key may call, as a JSON array
+    scopes JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_api_keys_user_id ON api_keys(user_id);
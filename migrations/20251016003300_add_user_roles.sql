@@ -0,0 +1,4 @@
+-- Staff accounts. Admins reach the /admin API and dashboard; ADMIN_EMAILS still admits
+-- the accounts listed there, so the first admin can be named without touching the table.
+
+ALTER TABLE users ADD COLUMN role TEXT NOT NULL DEFAULT 'user';
@@ -0,0 +1,8 @@
+-- What signed in, so users can tell their sessions apart and sign one out. A refreshed
+-- token carries the session on: it keeps the sign-in time and records when the session
+-- was last refreshed and from where.
+
+ALTER TABLE refresh_tokens
+    ADD COLUMN last_used_at TIMESTAMPTZ,
+    ADD COLUMN user_agent TEXT,
+    ADD COLUMN client_ip TEXT;
@@ -0,0 +1,205 @@
+-- FeedTape schema for SQLite builds (the `sqlite` feature), matching the Postgres
+-- migrations up to 20251016001900. New Postgres migrations need a counterpart here.
+--
+-- UUIDs are stored as 16-byte blobs, timestamps and dates as RFC 3339 text and JSON
+-- as text, which is how sqlx encodes them for SQLite.
+
+CREATE TABLE users (
+    id BLOB PRIMARY KEY,
+    email TEXT NOT NULL UNIQUE,
+    oauth_provider TEXT NOT NULL,
+    oauth_provider_id TEXT NOT NULL,
+    settings TEXT NOT NULL,
+    subscription_tier TEXT NOT NULL,
+    subscription_status TEXT NOT NULL,
+    subscription_expires_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    trial_started_at TEXT,
+    trial_ends_at TEXT,
+    -- Accounts are purged once this moment passes; NULL means not scheduled
+    deletion_scheduled_at TEXT,
+    display_name TEXT,
+    avatar_url TEXT,
+    limit_overrides TEXT,
+    referral_code TEXT UNIQUE,
+    referral_bonus_until TEXT,
+    family_owner_id BLOB REFERENCES users(id) ON DELETE SET NULL,
+    UNIQUE(oauth_provider, oauth_provider_id)
+);
+
+CREATE INDEX idx_users_oauth ON users(oauth_provider, oauth_provider_id);
+CREATE INDEX idx_users_deletion_scheduled_at ON users(deletion_scheduled_at)
+    WHERE deletion_scheduled_at IS NOT NULL;
+CREATE INDEX idx_users_family_owner_id ON users(family_owner_id);
+
+CREATE TABLE feeds (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    title TEXT,
+    created_at TEXT NOT NULL,
+    avg_articles_per_day REAL,
+    last_published_at TEXT,
+    stats_updated_at TEXT,
+    suggestion_id TEXT,
+    category_id TEXT,
+    UNIQUE(user_id, url)
+);
+
+CREATE INDEX idx_feeds_user_id ON feeds(user_id);
+CREATE INDEX idx_feeds_stats_updated_at ON feeds(stats_updated_at);
+CREATE INDEX idx_feeds_category_id ON feeds(category_id) WHERE category_id IS NOT NULL;
+
+CREATE TABLE refresh_tokens (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token TEXT NOT NULL UNIQUE,
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    revoked BOOLEAN NOT NULL
+);
+
+CREATE INDEX idx_refresh_tokens_user_id ON refresh_tokens(user_id);
+
+CREATE TABLE usage_tracking (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    date TEXT NOT NULL,
+    characters_used INTEGER NOT NULL,
+    articles_synthesized INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    rollover_characters INTEGER,
+    UNIQUE(user_id, date)
+);
+
+CREATE TABLE mute_rules (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    feed_id BLOB REFERENCES feeds(id) ON DELETE CASCADE,
+    pattern TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_mute_rules_user_id ON mute_rules(user_id);
+
+CREATE TABLE subscription_purchases (
+    purchase_token TEXT PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    platform TEXT NOT NULL,
+    product_id TEXT NOT NULL,
+    expires_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_subscription_purchases_user_id ON subscription_purchases(user_id);
+
+CREATE TABLE referrals (
+    id BLOB PRIMARY KEY,
+    referrer_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    referee_id BLOB NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_referrals_referrer_id ON referrals(referrer_id, created_at);
+
+CREATE TABLE promo_codes (
+    code TEXT PRIMARY KEY,
+    duration_days INTEGER NOT NULL,
+    max_redemptions INTEGER,
+    redemption_count INTEGER NOT NULL DEFAULT 0,
+    expires_at TEXT,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE promo_code_redemptions (
+    code TEXT NOT NULL REFERENCES promo_codes(code) ON DELETE CASCADE,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    redeemed_at TEXT NOT NULL,
+    PRIMARY KEY (code, user_id)
+);
+
+CREATE TABLE subscription_events (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL,
+    platform TEXT,
+    product_id TEXT,
+    expires_at TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_subscription_events_user_id ON subscription_events(user_id, created_at DESC);
+
+CREATE TABLE family_invites (
+    code TEXT PRIMARY KEY,
+    owner_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    email TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (owner_id, email)
+);
+
+CREATE TABLE devices (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    platform TEXT NOT NULL,
+    token TEXT NOT NULL UNIQUE,
+    app_version TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_devices_user_id ON devices(user_id);
+
+CREATE TABLE usage_history_purges (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    deleted_days INTEGER NOT NULL,
+    purged_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_usage_history_purges_user_id ON usage_history_purges(user_id);
+
+CREATE TABLE idempotency_keys (
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    key TEXT NOT NULL,
+    request_hash TEXT NOT NULL,
+    status_code INTEGER,
+    content_type TEXT,
+    response_body BLOB,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, key)
+);
+
+CREATE INDEX idx_idempotency_keys_created_at ON idempotency_keys(created_at);
+
+-- Append-only; user_id has no foreign key so entries outlive the accounts they mention
+CREATE TABLE audit_log (
+    id BLOB PRIMARY KEY,
+    occurred_at TEXT NOT NULL,
+    actor_type TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    action TEXT NOT NULL,
+    user_id BLOB,
+    target TEXT,
+    details TEXT NOT NULL DEFAULT '{}',
+    client_ip TEXT
+);
+
+CREATE INDEX idx_audit_log_occurred_at ON audit_log(occurred_at DESC);
+CREATE INDEX idx_audit_log_user_id ON audit_log(user_id, occurred_at DESC);
+
+CREATE TRIGGER audit_log_no_update BEFORE UPDATE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
+
+CREATE TRIGGER audit_log_no_delete BEFORE DELETE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
@@ -0,0 +1,9 @@
+-- Daily synthesis counters per provider (see the Postgres migration)
+
+CREATE TABLE synthesis_stats (
+    date TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    requests INTEGER NOT NULL,
+    characters INTEGER NOT NULL,
+    PRIMARY KEY (date, provider)
+);
@@ -0,0 +1,13 @@
+-- Blocked client addresses (see the Postgres migration)
+
+CREATE TABLE ip_blocks (
+    id BLOB PRIMARY KEY,
+    network TEXT NOT NULL UNIQUE,
+    reason TEXT NOT NULL,
+    source TEXT NOT NULL,
+    created_by TEXT,
+    created_at TEXT NOT NULL,
+    expires_at TEXT
+);
+
+CREATE INDEX idx_ip_blocks_expires_at ON ip_blocks(expires_at);
@@ -0,0 +1,5 @@
+-- Soft-deleted accounts (see the Postgres migration)
+ALTER TABLE users ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_users_deleted_at ON users(deleted_at)
+    WHERE deleted_at IS NOT NULL;
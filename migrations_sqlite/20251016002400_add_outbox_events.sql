@@ -0,0 +1,17 @@
+-- Transactional outbox: domain events written in the same transaction as the change
+-- they describe, then handed to webhooks and the audit log by the dispatcher
+
+CREATE TABLE outbox_events (
+    id BLOB PRIMARY KEY,
+    event_type TEXT NOT NULL,
+    user_id BLOB REFERENCES users(id) ON DELETE CASCADE,
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TEXT NOT NULL,
+    last_error TEXT,
+    created_at TEXT NOT NULL,
+    completed_at TEXT
+);
+
+CREATE INDEX idx_outbox_events_due ON outbox_events(next_attempt_at)
+    WHERE completed_at IS NULL;
@@ -0,0 +1,13 @@
+-- Product analytics sent by the apps in batches. Rows are only ever inserted.
+
+CREATE TABLE analytics_events (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    session_id TEXT,
+    properties TEXT NOT NULL,
+    occurred_at TEXT NOT NULL,
+    received_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_analytics_events_name_occurred_at ON analytics_events(name, occurred_at);
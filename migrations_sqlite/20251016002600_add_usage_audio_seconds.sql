@@ -0,0 +1,6 @@
+-- Audio seconds, provider and engine per usage day (see the Postgres migration)
+ALTER TABLE usage_tracking ADD COLUMN audio_seconds REAL NOT NULL DEFAULT 0;
+ALTER TABLE usage_tracking ADD COLUMN provider TEXT;
+ALTER TABLE usage_tracking ADD COLUMN engine TEXT;
+
+UPDATE usage_tracking SET audio_seconds = characters_used * 0.06;
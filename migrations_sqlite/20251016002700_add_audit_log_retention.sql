@@ -0,0 +1,12 @@
+-- Deletes of audit entries older than a purge's cutoff (see the Postgres migration)
+CREATE TABLE audit_log_purges (
+    delete_before TEXT NOT NULL
+);
+
+DROP TRIGGER audit_log_no_delete;
+
+CREATE TRIGGER audit_log_no_delete BEFORE DELETE ON audit_log
+WHEN NOT EXISTS (SELECT 1 FROM audit_log_purges WHERE OLD.occurred_at < delete_before)
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
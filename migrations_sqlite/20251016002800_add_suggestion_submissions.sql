@@ -0,0 +1,18 @@
+-- User-submitted feed suggestions and their review (see the Postgres migration)
+
+CREATE TABLE suggestion_submissions (
+    id BLOB PRIMARY KEY,
+    user_id BLOB REFERENCES users(id) ON DELETE SET NULL,
+    url TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    status TEXT NOT NULL,
+    category_id TEXT,
+    rejection_reason TEXT,
+    reviewed_by TEXT,
+    reviewed_at TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_suggestion_submissions_status ON suggestion_submissions(status, created_at);
+CREATE INDEX idx_suggestion_submissions_user_id ON suggestion_submissions(user_id);
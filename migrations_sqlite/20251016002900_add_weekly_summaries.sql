@@ -0,0 +1,11 @@
+-- Weekly listening summaries handled per user (see the Postgres migration)
+
+CREATE TABLE weekly_summaries (
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    week_start TEXT NOT NULL,
+    claimed_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, week_start)
+);
+
+CREATE INDEX idx_analytics_events_user_name_occurred_at
+    ON analytics_events(user_id, name, occurred_at);
@@ -0,0 +1,8 @@
+-- Pending OAuth sign-in states (see the Postgres migration)
+
+CREATE TABLE oauth_states (
+    state TEXT PRIMARY KEY,
+    expires_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_oauth_states_expires_at ON oauth_states(expires_at);
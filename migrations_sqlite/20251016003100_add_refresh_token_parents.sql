@@ -0,0 +1,6 @@
+-- The token each refresh token replaced (see the Postgres migration)
+
+ALTER TABLE refresh_tokens
+    ADD COLUMN parent_id BLOB REFERENCES refresh_tokens(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_refresh_tokens_parent_id ON refresh_tokens(parent_id);
@@ -0,0 +1,13 @@
+-- API keys for programmatic clients (see the Postgres migration)
+
+CREATE TABLE api_keys (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_api_keys_user_id ON api_keys(user_id);
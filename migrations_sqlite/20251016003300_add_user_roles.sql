@@ -0,0 +1,2 @@
+-- User roles (see the Postgres migration)
+ALTER TABLE users ADD COLUMN role TEXT NOT NULL DEFAULT 'user';
@@ -0,0 +1,4 @@
+-- The client behind each refresh token (see the Postgres migration)
+ALTER TABLE refresh_tokens ADD COLUMN last_used_at TEXT;
+ALTER TABLE refresh_tokens ADD COLUMN user_agent TEXT;
+ALTER TABLE refresh_tokens ADD COLUMN client_ip TEXT;
@@ -0,0 +1,3 @@
+-- X-Request-Id of the request the change came from; NULL for background jobs
+
+ALTER TABLE audit_log ADD COLUMN request_id TEXT;
@@ -0,0 +1,164 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::admin::{
+    AdminStatsQuery, AdminStatsResponse, AdminUserListResponse, AdminUserResponse,
+    AdminUserUsageResponse, AuditLogResponse, CreatePromoCodeRequest, GrantProRequest,
+    ListAuditLogQuery, ListPromoCodesQuery, ListUsersQuery, PromoCodeListResponse,
+    PromoCodeResponse, SetRoleRequest,
+};
+use crate::domain::plan::LimitOverrides;
+use crate::domain::tts::{TtsMetrics, TtsMetricsSnapshot};
+use crate::{
+    domain::admin::{AdminService, AdminServiceApi},
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+pub struct AdminController {
+    admin_service: Arc<AdminService>,
+    tts_metrics: Arc<TtsMetrics>,
+}
+
+impl AdminController {
+    pub fn new(admin_service: Arc<AdminService>, tts_metrics: Arc<TtsMetrics>) -> Self {
+        Self {
+            admin_service,
+            tts_metrics,
+        }
+    }
+
+    /// GET /admin/users - List and search users
+    pub async fn list_users(
+        State(controller): State<Arc<AdminController>>,
+        Query(query): Query<ListUsersQuery>,
+    ) -> AppResult<Json<AdminUserListResponse>> {
+        let response = controller.admin_service.list_users(query).await?;
+        Ok(Json(response))
+    }
+
+    /// GET /admin/users/{userId} - Look up a user
+    pub async fn get_user(
+        State(controller): State<Arc<AdminController>>,
+        Path(user_id): Path<Uuid>,
+    ) -> AppResult<Json<AdminUserResponse>> {
+        let response = controller.admin_service.get_user(user_id).await?;
+        Ok(Json(response))
+    }
+
+    /// GET /admin/users/{userId}/usage - View a user's synthesis usage
+    pub async fn get_user_usage(
+        State(controller): State<Arc<AdminController>>,
+        Path(user_id): Path<Uuid>,
+    ) -> AppResult<Json<AdminUserUsageResponse>> {
+        let response = controller.admin_service.get_user_usage(user_id).await?;
+        Ok(Json(response))
+    }
+
+    /// POST /admin/users/{userId}/grant-pro - Grant Pro manually
+    pub async fn grant_pro(
+        State(controller): State<Arc<AdminController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(user_id): Path<Uuid>,
+        Json(request): Json<GrantProRequest>,
+    ) -> AppResult<Json<AdminUserResponse>> {
+        let response = controller
+            .admin_service
+            .grant_pro(&auth_user.email, user_id, request)
+            .await?;
+        Ok(Json(response))
+    }
+
+    /// POST /admin/users/{userId}/revoke-pro - Move a user back to Free
+    pub async fn revoke_pro(
+        State(controller): State<Arc<AdminController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(user_id): Path<Uuid>,
+    ) -> AppResult<Json<AdminUserResponse>> {
+        let response = controller
+            .admin_service
+            .revoke_pro(&auth_user.email, user_id)
+            .await?;
+        Ok(Json(response))
+    }
+
+    /// PUT /admin/users/{userId}/limit-overrides - Replace a user's limit overrides
+    pub async fn set_limit_overrides(
+        State(controller): State<Arc<AdminController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(user_id): Path<Uuid>,
+        Json(overrides): Json<LimitOverrides>,
+    ) -> AppResult<Json<AdminUserResponse>> {
+        let response = controller
+            .admin_service
+            .set_limit_overrides(&auth_user.email, user_id, overrides)
+            .await?;
+        Ok(Json(response))
+    }
+
+    /// PUT /admin/users/{userId}/role - Make a user an admin or a regular user
+    pub async fn set_role(
+        State(controller): State<Arc<AdminController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(user_id): Path<Uuid>,
+        Json(request): Json<SetRoleRequest>,
+    ) -> AppResult<Json<AdminUserResponse>> {
+        let response = controller
+            .admin_service
+            .set_role(&auth_user.email, user_id, request)
+            .await?;
+        Ok(Json(response))
+    }
+
+    /// GET /admin/promo-codes - List promo codes
+    pub async fn list_promo_codes(
+        State(controller): State<Arc<AdminController>>,
+        Query(query): Query<ListPromoCodesQuery>,
+    ) -> AppResult<Json<PromoCodeListResponse>> {
+        let response = controller.admin_service.list_promo_codes(query).await?;
+        Ok(Json(response))
+    }
+
+    /// POST /admin/promo-codes - Create a promo code
+    pub async fn create_promo_code(
+        State(controller): State<Arc<AdminController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<CreatePromoCodeRequest>,
+    ) -> AppResult<(StatusCode, Json<PromoCodeResponse>)> {
+        let response = controller
+            .admin_service
+            .create_promo_code(&auth_user.email, request)
+            .await?;
+        Ok((StatusCode::CREATED, Json(response)))
+    }
+
+    /// GET /admin/audit-log - List recorded changes
+    pub async fn list_audit_log(
+        State(controller): State<Arc<AdminController>>,
+        Query(query): Query<ListAuditLogQuery>,
+    ) -> AppResult<Json<AuditLogResponse>> {
+        let response = controller.admin_service.list_audit_log(query).await?;
+        Ok(Json(response))
+    }
+
+    /// GET /admin/stats - Service-wide totals and recent activity
+    pub async fn get_stats(
+        State(controller): State<Arc<AdminController>>,
+        Query(query): Query<AdminStatsQuery>,
+    ) -> AppResult<Json<AdminStatsResponse>> {
+        let response = controller.admin_service.get_stats(query).await?;
+        Ok(Json(response))
+    }
+
+    /// GET /admin/tts/metrics - Latency and errors of TTS provider calls on this instance
+    pub async fn get_tts_metrics(
+        State(controller): State<Arc<AdminController>>,
+    ) -> Json<TtsMetricsSnapshot> {
+        Json(controller.tts_metrics.snapshot())
+    }
+}
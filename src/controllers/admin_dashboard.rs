@@ -0,0 +1,367 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{Html, IntoResponse, Redirect, Response},
+    Extension,
+};
+use chrono::{NaiveDate, Utc};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::admin::{AdminStatsQuery, ListUsersQuery};
+use crate::infrastructure::auth::admin_session::{cleared_session_cookie, ADMIN_SIGN_IN_PATH};
+use crate::infrastructure::sanitizer::escape_html;
+use crate::{
+    domain::admin::{AdminService, AdminServiceApi},
+    domain::feed_suggestions::FeedSuggestionsService,
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+/// Days of activity charted on the overview
+const OVERVIEW_DAYS: i64 = 14;
+const CHART_HEIGHT: i64 = 120;
+const CHART_BAR_WIDTH: i64 = 18;
+
+/// Server-rendered pages over the admin API, for operating the service from a browser
+pub struct AdminDashboardController {
+    admin_service: Arc<AdminService>,
+    feed_suggestions_service: Arc<FeedSuggestionsService>,
+    secure_cookies: bool,
+}
+
+impl AdminDashboardController {
+    pub fn new(
+        admin_service: Arc<AdminService>,
+        feed_suggestions_service: Arc<FeedSuggestionsService>,
+        secure_cookies: bool,
+    ) -> Self {
+        Self {
+            admin_service,
+            feed_suggestions_service,
+            secure_cookies,
+        }
+    }
+
+    /// GET /admin - Totals, recent activity and user search
+    pub async fn overview(
+        State(controller): State<Arc<AdminDashboardController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Query(query): Query<ListUsersQuery>,
+    ) -> AppResult<Html<String>> {
+        let search = query.q.clone().unwrap_or_default();
+        let stats = controller
+            .admin_service
+            .get_stats(AdminStatsQuery {
+                days: Some(OVERVIEW_DAYS),
+            })
+            .await?;
+        let users = controller.admin_service.list_users(query).await?;
+
+        let characters_by_day: HashMap<NaiveDate, i64> = stats
+            .daily
+            .iter()
+            .map(|day| (day.date, day.characters))
+            .collect();
+        let chart: Vec<(NaiveDate, i64)> = stats
+            .since
+            .iter_days()
+            .take_while(|date| *date <= Utc::now().date_naive())
+            .map(|date| (date, characters_by_day.get(&date).copied().unwrap_or(0)))
+            .collect();
+
+        let mut body = String::new();
+        body.push_str("<section class=\"cards\">");
+        for (label, value) in [
+            ("Users", stats.users.total.to_string()),
+            ("Pro", stats.users.pro.to_string()),
+            ("Active", stats.users.active.to_string()),
+            ("Pending deletion", stats.users.pending_deletion.to_string()),
+            ("Feeds", stats.feeds.total.to_string()),
+            (
+                "TTS cache hit rate",
+                stats
+                    .cache
+                    .hit_rate
+                    .map(|rate| format!("{:.0}%", rate * 100.0))
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+        ] {
+            let _ = write!(
+                body,
+                "<div class=\"card\"><span>{}</span><strong>{}</strong></div>",
+                label, value
+            );
+        }
+        body.push_str("</section>");
+
+        let _ = write!(
+            body,
+            "<p>Active users and characters cover the last {} days.</p>\
+             <h2>Characters synthesized per day</h2>{}",
+            OVERVIEW_DAYS,
+            bar_chart(&chart)
+        );
+
+        let _ = write!(
+            body,
+            "<h2>Users</h2>\
+             <form method=\"get\" action=\"/admin\">\
+             <input type=\"search\" name=\"q\" value=\"{}\" placeholder=\"Email or name\">\
+             <button type=\"submit\">Search</button></form>",
+            escape_html(&search)
+        );
+        body.push_str(
+            "<table><tr><th>Email</th><th>Name</th><th>Tier</th><th>Status</th>\
+             <th>Joined</th></tr>",
+        );
+        for user in &users.items {
+            let _ = write!(
+                body,
+                "<tr><td><a href=\"/admin/dashboard/users/{}\">{}</a></td><td>{}</td>\
+                 <td>{}</td><td>{}</td><td>{}</td></tr>",
+                user.id,
+                escape_html(&user.email),
+                escape_html(user.display_name.as_deref().unwrap_or("")),
+                user.subscription_tier,
+                user.subscription_status,
+                user.created_at.format("%Y-%m-%d"),
+            );
+        }
+        body.push_str("</table>");
+        if let Some(cursor) = &users.next_cursor {
+            let _ = write!(
+                body,
+                "<p><a href=\"/admin?q={}&amp;cursor={}\">Next page</a></p>",
+                urlencoding::encode(&search),
+                urlencoding::encode(cursor)
+            );
+        }
+
+        Ok(page("Overview", &auth_user, &body))
+    }
+
+    /// GET /admin/dashboard/users/{userId} - A user's quota and daily usage
+    pub async fn user_usage(
+        State(controller): State<Arc<AdminDashboardController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(user_id): Path<Uuid>,
+    ) -> AppResult<Html<String>> {
+        let usage = controller.admin_service.get_user_usage(user_id).await?;
+        let chart: Vec<(NaiveDate, i64)> = usage
+            .history
+            .iter()
+            .rev()
+            .map(|day| (day.date, day.characters_used as i64))
+            .collect();
+
+        let mut body = String::new();
+        let _ = write!(
+            body,
+            "<p>User <code>{}</code></p>\
+             <section class=\"cards\">\
+             <div class=\"card\"><span>Used this {} period</span><strong>{} / {}</strong></div>\
+             <div class=\"card\"><span>Articles</span><strong>{}</strong></div>\
+             <div class=\"card\"><span>Resets</span><strong>{}</strong></div>\
+             </section>",
+            usage.user_id,
+            usage.period,
+            usage.characters_used,
+            usage.characters_limit,
+            usage.articles_synthesized,
+            usage.resets_at.format("%Y-%m-%d %H:%M UTC"),
+        );
+        body.push_str("<h2>Characters per day</h2>");
+        body.push_str(&bar_chart(&chart));
+        body.push_str("<table><tr><th>Date</th><th>Characters</th><th>Articles</th></tr>");
+        for day in &usage.history {
+            let _ = write!(
+                body,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                day.date, day.characters_used, day.articles_synthesized
+            );
+        }
+        body.push_str("</table>");
+
+        Ok(page("User usage", &auth_user, &body))
+    }
+
+    /// GET /admin/dashboard/suggestions - Curated feed suggestions and their link status
+    pub async fn suggestions(
+        State(controller): State<Arc<AdminDashboardController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Html<String>> {
+        let categories: HashMap<String, String> = controller
+            .feed_suggestions_service
+            .get_categories()
+            .into_iter()
+            .map(|category| (category.id, category.name))
+            .collect();
+        let suggestions = controller
+            .feed_suggestions_service
+            .get_suggestions_with_link_status();
+        let broken = suggestions.iter().filter(|(_, broken)| *broken).count();
+
+        let mut body = String::new();
+        let _ = write!(
+            body,
+            "<p>{} suggestions, {} hidden from users because their feed failed the last \
+             link check.</p>\
+             <form method=\"post\" action=\"/admin/dashboard/suggestions/check\">\
+             <button type=\"submit\">Check links now</button></form>",
+            suggestions.len(),
+            broken
+        );
+        body.push_str(
+            "<table><tr><th>Title</th><th>Category</th><th>Feed</th><th>Status</th></tr>",
+        );
+        for (suggestion, broken) in &suggestions {
+            let category = categories
+                .get(&suggestion.category_id)
+                .unwrap_or(&suggestion.category_id);
+            let _ = write!(
+                body,
+                "<tr><td>{}</td><td>{}</td><td><a href=\"{}\">{}</a></td><td>{}</td></tr>",
+                escape_html(&suggestion.title),
+                escape_html(category),
+                escape_html(&suggestion.url),
+                escape_html(&suggestion.url),
+                if *broken {
+                    "<span class=\"bad\">broken</span>"
+                } else {
+                    "ok"
+                },
+            );
+        }
+        body.push_str("</table>");
+
+        Ok(page("Feed suggestions", &auth_user, &body))
+    }
+
+    /// POST /admin/dashboard/suggestions/check - Run the suggestion link check now
+    pub async fn check_suggestion_links(
+        State(controller): State<Arc<AdminDashboardController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> Redirect {
+        let broken = controller
+            .feed_suggestions_service
+            .check_suggestion_links()
+            .await;
+        tracing::info!(
+            admin = %auth_user.email,
+            broken,
+            "Feed suggestion links checked from the dashboard"
+        );
+        Redirect::to("/admin/dashboard/suggestions")
+    }
+
+    /// GET /admin/sign-in - Start a dashboard session with GitHub
+    pub async fn sign_in() -> Html<String> {
+        Html(layout(
+            "Sign in",
+            "",
+            "<p>Sign in with the GitHub account of an admin.</p>\
+             <p><a class=\"button\" href=\"/auth/oauth/github?admin=true\">\
+             Sign in with GitHub</a></p>",
+        ))
+    }
+
+    /// POST /admin/sign-out - End the dashboard session
+    pub async fn sign_out(State(controller): State<Arc<AdminDashboardController>>) -> Response {
+        (
+            [(
+                header::SET_COOKIE,
+                cleared_session_cookie(controller.secure_cookies),
+            )],
+            Redirect::to(ADMIN_SIGN_IN_PATH),
+        )
+            .into_response()
+    }
+}
+
+/// A dashboard page with the navigation for a signed-in admin
+fn page(title: &str, auth_user: &AuthUser, body: &str) -> Html<String> {
+    let nav = format!(
+        "<nav><a href=\"/admin\">Overview</a>\
+         <a href=\"/admin/dashboard/suggestions\">Suggestions</a>\
+         <form method=\"post\" action=\"/admin/sign-out\">\
+         <span>{}</span><button type=\"submit\">Sign out</button></form></nav>",
+        escape_html(&auth_user.email)
+    );
+    Html(layout(title, &nav, body))
+}
+
+fn layout(title: &str, nav: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>{title} - FeedTape admin</title><style>{STYLE}</style></head>\
+         <body>{nav}<main><h1>{title}</h1>{body}</main></body></html>"
+    )
+}
+
+const STYLE: &str = "\
+body{font-family:system-ui,sans-serif;margin:0;color:#222}\
+nav{display:flex;gap:1rem;align-items:center;padding:.75rem 1.5rem;background:#222}\
+nav a,nav span{color:#fff;text-decoration:none}\
+nav form{margin-left:auto;display:flex;gap:.5rem}\
+main{padding:1rem 1.5rem;max-width:72rem}\
+.cards{display:flex;flex-wrap:wrap;gap:.75rem}\
+.card{border:1px solid #ddd;border-radius:6px;padding:.75rem 1rem;min-width:9rem}\
+.card span{display:block;color:#666;font-size:.85rem}.card strong{font-size:1.4rem}\
+table{border-collapse:collapse;margin-top:1rem;width:100%}\
+th,td{text-align:left;padding:.35rem .5rem;border-bottom:1px solid #eee}\
+svg rect{fill:#3b6ea5}.bad{color:#b00020}.button{padding:.5rem 1rem;border:1px solid #222}";
+
+/// Inline SVG with one bar per day, scaled to the busiest day
+fn bar_chart(points: &[(NaiveDate, i64)]) -> String {
+    let max = points.iter().map(|(_, value)| *value).max().unwrap_or(0);
+    let width = CHART_BAR_WIDTH * points.len() as i64;
+    let mut svg = format!(
+        "<svg width=\"{width}\" height=\"{CHART_HEIGHT}\" viewBox=\"0 0 {width} {CHART_HEIGHT}\" \
+         role=\"img\" aria-label=\"Bar chart\">"
+    );
+    for (index, (date, value)) in points.iter().enumerate() {
+        let height = if max > 0 {
+            value * CHART_HEIGHT / max
+        } else {
+            0
+        };
+        let _ = write!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"><title>{}: {}</title></rect>",
+            index as i64 * CHART_BAR_WIDTH + 1,
+            CHART_HEIGHT - height,
+            CHART_BAR_WIDTH - 2,
+            height,
+            date,
+            value
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_chart_scales_to_the_busiest_day() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let chart = bar_chart(&[(day, 50), (day.succ_opt().unwrap(), 100)]);
+
+        assert!(chart.contains("y=\"60\" width=\"16\" height=\"60\""));
+        assert!(chart.contains("y=\"0\" width=\"16\" height=\"120\""));
+        assert!(chart.contains("<title>2026-10-02: 100</title>"));
+    }
+
+    #[test]
+    fn test_bar_chart_without_usage_has_empty_bars() {
+        let day = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+
+        assert!(bar_chart(&[(day, 0)]).contains("height=\"0\""));
+    }
+}
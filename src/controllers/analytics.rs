@@ -0,0 +1,32 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use std::sync::Arc;
+
+use crate::domain::analytics::{IngestEventsRequest, IngestEventsResponse};
+use crate::{
+    domain::analytics::{AnalyticsService, AnalyticsServiceApi},
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+pub struct AnalyticsController {
+    analytics_service: Arc<AnalyticsService>,
+}
+
+impl AnalyticsController {
+    pub fn new(analytics_service: Arc<AnalyticsService>) -> Self {
+        Self { analytics_service }
+    }
+
+    /// POST /api/analytics/events - Ingest a batch of app analytics events
+    pub async fn ingest_events(
+        State(controller): State<Arc<AnalyticsController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<IngestEventsRequest>,
+    ) -> AppResult<(StatusCode, Json<IngestEventsResponse>)> {
+        let response = controller
+            .analytics_service
+            .ingest_events(auth_user.user_id, request)
+            .await?;
+        Ok((StatusCode::ACCEPTED, Json(response)))
+    }
+}
@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::api_key::{ApiKeyResponse, ApiKeysResponse, CreateApiKeyRequest};
+use crate::{
+    domain::api_key::{ApiKeyService, ApiKeyServiceApi},
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+pub struct ApiKeyController {
+    api_key_service: Arc<ApiKeyService>,
+}
+
+impl ApiKeyController {
+    pub fn new(api_key_service: Arc<ApiKeyService>) -> Self {
+        Self { api_key_service }
+    }
+
+    /// GET /api/me/api-keys - List the user's API keys, without the keys themselves
+    pub async fn list_api_keys(
+        State(controller): State<Arc<ApiKeyController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<ApiKeysResponse>> {
+        let api_keys = controller
+            .api_key_service
+            .list_api_keys(auth_user.user_id)
+            .await?;
+        Ok(Json(api_keys))
+    }
+
+    /// POST /api/me/api-keys - Create an API key for scripts
+    pub async fn create_api_key(
+        State(controller): State<Arc<ApiKeyController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<CreateApiKeyRequest>,
+    ) -> AppResult<(StatusCode, Json<ApiKeyResponse>)> {
+        let api_key = controller
+            .api_key_service
+            .create_api_key(auth_user.user_id, request)
+            .await?;
+        Ok((StatusCode::CREATED, Json(api_key)))
+    }
+
+    /// DELETE /api/me/api-keys/{apiKeyId} - Revoke an API key
+    pub async fn delete_api_key(
+        State(controller): State<Arc<ApiKeyController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(api_key_id): Path<Uuid>,
+    ) -> AppResult<StatusCode> {
+        controller
+            .api_key_service
+            .delete_api_key(auth_user.user_id, api_key_id)
+            .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
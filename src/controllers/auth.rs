@@ -1,13 +1,33 @@
-use axum::{extract::State, http::StatusCode, Extension, Json};
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Extension, Json,
+};
 use std::sync::Arc;
+use uuid::Uuid;
 
-use crate::domain::auth::{RefreshTokenRequest, TokenResponse};
+use crate::domain::auth::{RefreshTokenRequest, SessionClient, SessionsResponse, TokenResponse};
+use crate::infrastructure::http::ClientIp;
 use crate::{
     domain::auth::{AuthService, AuthServiceApi},
     error::AppResult,
     infrastructure::auth::AuthUser,
 };
 
+/// The client behind a request that signs in or refreshes a session
+pub fn session_client(
+    headers: &HeaderMap,
+    client_ip: Option<Extension<ClientIp>>,
+) -> SessionClient {
+    SessionClient::new(
+        headers
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok()),
+        client_ip.map(|Extension(ClientIp(ip))| ip),
+    )
+}
+
 pub struct AuthController {
     auth_service: Arc<AuthService>,
 }
@@ -20,11 +40,13 @@ impl AuthController {
     /// POST /auth/refresh - Refresh access token
     pub async fn refresh(
         State(controller): State<Arc<AuthController>>,
+        headers: HeaderMap,
+        client_ip: Option<Extension<ClientIp>>,
         Json(request): Json<RefreshTokenRequest>,
     ) -> AppResult<Json<TokenResponse>> {
         let response = controller
             .auth_service
-            .refresh_token(&request.refresh_token)
+            .refresh_token(&request.refresh_token, &session_client(&headers, client_ip))
             .await?;
         Ok(Json(response))
     }
@@ -52,4 +74,38 @@ impl AuthController {
             .await?;
         Ok(StatusCode::NO_CONTENT)
     }
+
+    /// GET /api/me/sessions - List the devices signed in to the account
+    pub async fn list_sessions(
+        State(controller): State<Arc<AuthController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<SessionsResponse>> {
+        let sessions = controller
+            .auth_service
+            .list_sessions(auth_user.user_id)
+            .await?;
+        Ok(Json(sessions))
+    }
+
+    /// DELETE /api/me/sessions/{sessionId} - Sign a device out
+    pub async fn revoke_session(
+        State(controller): State<Arc<AuthController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(session_id): Path<Uuid>,
+    ) -> AppResult<StatusCode> {
+        controller
+            .auth_service
+            .revoke_session(auth_user.user_id, session_id)
+            .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// GET /.well-known/jwks.json - Public keys that verify access tokens
+    pub async fn jwks(State(controller): State<Arc<AuthController>>) -> impl IntoResponse {
+        // Short enough for verifiers to pick up a rotation well within a token's lifetime
+        (
+            [(header::CACHE_CONTROL, "public, max-age=300")],
+            Json(controller.auth_service.jwks()),
+        )
+    }
 }
@@ -0,0 +1,98 @@
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::Response,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::{AppError, AppResult};
+
+/// Hex characters of the body digest kept in ETags
+const BODY_HASH_LENGTH: usize = 16;
+
+/// Serialize `body` as JSON with an ETag derived from its content, answering 304 Not
+/// Modified when If-None-Match already names it
+pub fn conditional_json<T: Serialize>(headers: &HeaderMap, body: &T) -> AppResult<Response> {
+    let bytes = to_json(body)?;
+    let etag = format!("\"{}\"", body_hash(&bytes));
+    conditional_response(headers, &etag, bytes)
+}
+
+/// Serialize a response body for hashing and sending
+pub fn to_json<T: Serialize>(body: &T) -> AppResult<Vec<u8>> {
+    serde_json::to_vec(body).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Short digest of a serialized body, identifying its content
+pub fn body_hash(bytes: &[u8]) -> String {
+    let mut hash = hex::encode(Sha256::digest(bytes));
+    hash.truncate(BODY_HASH_LENGTH);
+    hash
+}
+
+/// 200 with `bytes` as the JSON body, or an empty 304 when the client's copy is current.
+/// `etag` must already be quoted.
+pub fn conditional_response(
+    headers: &HeaderMap,
+    etag: &str,
+    bytes: Vec<u8>,
+) -> AppResult<Response> {
+    let etag_value = HeaderValue::from_str(etag).map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let response = if if_none_match(headers, etag) {
+        Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::ETAG, etag_value)
+            .body(Body::empty())
+    } else {
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::ETAG, etag_value)
+            .body(Body::from(bytes))
+    };
+    response.map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Whether If-None-Match lists `etag` or `*`. Weak tags match too, as RFC 9110 asks
+/// for GET.
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(if_none_match: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(if_none_match).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        assert!(if_none_match(&headers("\"abc\""), "\"abc\""));
+        assert!(if_none_match(&headers("\"x\", W/\"abc\""), "\"abc\""));
+        assert!(if_none_match(&headers("*"), "\"abc\""));
+        assert!(!if_none_match(&headers("\"abd\""), "\"abc\""));
+        assert!(!if_none_match(&HeaderMap::new(), "\"abc\""));
+    }
+
+    #[test]
+    fn test_body_hash_follows_content() {
+        assert_eq!(body_hash(b"[1,2]"), body_hash(b"[1,2]"));
+        assert_ne!(body_hash(b"[1,2]"), body_hash(b"[1,3]"));
+        assert_eq!(body_hash(b"[]").len(), BODY_HASH_LENGTH);
+    }
+}
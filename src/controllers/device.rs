@@ -0,0 +1,49 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use std::sync::Arc;
+
+use crate::domain::device::{DeviceResponse, RegisterDeviceRequest};
+use crate::{
+    domain::device::{DeviceService, DeviceServiceApi},
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+pub struct DeviceController {
+    device_service: Arc<DeviceService>,
+}
+
+impl DeviceController {
+    pub fn new(device_service: Arc<DeviceService>) -> Self {
+        Self { device_service }
+    }
+
+    /// POST /api/me/devices - Register a push notification token
+    pub async fn register_device(
+        State(controller): State<Arc<DeviceController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<RegisterDeviceRequest>,
+    ) -> AppResult<(StatusCode, Json<DeviceResponse>)> {
+        let device = controller
+            .device_service
+            .register_device(auth_user.user_id, request)
+            .await?;
+        Ok((StatusCode::CREATED, Json(device)))
+    }
+
+    /// DELETE /api/me/devices/{token} - Unregister a push notification token
+    pub async fn unregister_device(
+        State(controller): State<Arc<DeviceController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(token): Path<String>,
+    ) -> AppResult<StatusCode> {
+        controller
+            .device_service
+            .unregister_device(auth_user.user_id, &token)
+            .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
@@ -0,0 +1,51 @@
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+use serde_json::json;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt,
+};
+
+use crate::infrastructure::{auth::AuthUser, events::AccountEventStream};
+
+/// Comment sent on idle streams so proxies don't close them
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+pub struct EventsController {
+    events: Arc<AccountEventStream>,
+}
+
+impl EventsController {
+    pub fn new(events: Arc<AccountEventStream>) -> Self {
+        Self { events }
+    }
+
+    /// GET /api/events - Stream the user's account events as Server-Sent Events
+    pub async fn stream(
+        State(controller): State<Arc<EventsController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+        let user_id = auth_user.user_id;
+        let events =
+            BroadcastStream::new(controller.events.subscribe()).filter_map(move |received| {
+                match received {
+                    Ok(event) if event.user_id == user_id => Some(Ok(Event::default()
+                        .event(event.kind.as_str())
+                        .data(event.data.to_string()))),
+                    Ok(_) => None,
+                    // The client fell behind and missed events; tell it to refetch its state
+                    Err(BroadcastStreamRecvError::Lagged(missed)) => Some(Ok(Event::default()
+                        .event("lagged")
+                        .data(json!({ "missed": missed }).to_string()))),
+                }
+            });
+
+        Sse::new(events).keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL))
+    }
+}
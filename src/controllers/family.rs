@@ -0,0 +1,99 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::family::{
+    CreateFamilyInviteRequest, FamilyInviteResponse, FamilyResponse, JoinFamilyRequest,
+};
+use crate::{
+    domain::family::{FamilyService, FamilyServiceApi},
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+pub struct FamilyController {
+    family_service: Arc<FamilyService>,
+}
+
+impl FamilyController {
+    pub fn new(family_service: Arc<FamilyService>) -> Self {
+        Self { family_service }
+    }
+
+    /// GET /api/family - Get the user's family plan, members and pending invites
+    pub async fn get_family(
+        State(controller): State<Arc<FamilyController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<FamilyResponse>> {
+        let response = controller
+            .family_service
+            .get_family(auth_user.user_id)
+            .await?;
+        Ok(Json(response))
+    }
+
+    /// POST /api/family/invites - Invite someone to share the user's Pro plan
+    pub async fn invite(
+        State(controller): State<Arc<FamilyController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<CreateFamilyInviteRequest>,
+    ) -> AppResult<(StatusCode, Json<FamilyInviteResponse>)> {
+        let response = controller
+            .family_service
+            .invite(auth_user.user_id, request)
+            .await?;
+        Ok((StatusCode::CREATED, Json(response)))
+    }
+
+    /// DELETE /api/family/invites/{code} - Withdraw a pending invite
+    pub async fn revoke_invite(
+        State(controller): State<Arc<FamilyController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(code): Path<String>,
+    ) -> AppResult<StatusCode> {
+        controller
+            .family_service
+            .revoke_invite(auth_user.user_id, &code)
+            .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// POST /api/family/join - Accept a family invite
+    pub async fn join(
+        State(controller): State<Arc<FamilyController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<JoinFamilyRequest>,
+    ) -> AppResult<Json<FamilyResponse>> {
+        let response = controller
+            .family_service
+            .join(auth_user.user_id, &request.code)
+            .await?;
+        Ok(Json(response))
+    }
+
+    /// DELETE /api/family/members/{memberId} - Remove a member from the user's family
+    pub async fn remove_member(
+        State(controller): State<Arc<FamilyController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(member_id): Path<Uuid>,
+    ) -> AppResult<StatusCode> {
+        controller
+            .family_service
+            .remove_member(auth_user.user_id, member_id)
+            .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// POST /api/family/leave - Leave the family the user belongs to
+    pub async fn leave(
+        State(controller): State<Arc<FamilyController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<StatusCode> {
+        controller.family_service.leave(auth_user.user_id).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
@@ -1,12 +1,16 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
     Extension, Json,
 };
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::domain::feed::{CreateFeedRequest, FeedResponse};
+use crate::controllers::conditional::conditional_json;
+use crate::domain::feed::{
+    CreateFeedRequest, FeedResponse, FeedValidationResult, ListFeedsQuery, ValidateFeedsRequest,
+};
 use crate::{
     domain::feed::{FeedService, FeedServiceApi},
     error::AppResult,
@@ -22,16 +26,41 @@ impl FeedController {
         Self { feed_service }
     }
 
-    /// GET /api/feeds - List user's feeds
+    /// GET /api/feeds - List user's feeds, a page at a time when `limit` or `cursor` is
+    /// given; 304 when If-None-Match has the current ETag
     pub async fn list_feeds(
         State(controller): State<Arc<FeedController>>,
         Extension(auth_user): Extension<AuthUser>,
-    ) -> AppResult<Json<Vec<FeedResponse>>> {
-        let feeds = controller
+        Query(query): Query<ListFeedsQuery>,
+        headers: HeaderMap,
+    ) -> AppResult<Response> {
+        if query.is_paginated() {
+            let page = controller
+                .feed_service
+                .get_user_feeds_page(auth_user.user_id, query.limit, query.cursor.as_deref())
+                .await?;
+            return conditional_json(&headers, &page);
+        }
+
+        let feeds: Vec<FeedResponse> = controller
             .feed_service
             .get_user_feeds(auth_user.user_id)
             .await?;
-        Ok(Json(feeds))
+        conditional_json(&headers, &feeds)
+    }
+
+    /// GET /v1/feeds - List user's feeds in the page envelope, paginated by default
+    pub async fn list_feeds_v1(
+        State(controller): State<Arc<FeedController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Query(query): Query<ListFeedsQuery>,
+        headers: HeaderMap,
+    ) -> AppResult<Response> {
+        let page = controller
+            .feed_service
+            .get_user_feeds_page(auth_user.user_id, query.limit, query.cursor.as_deref())
+            .await?;
+        conditional_json(&headers, &page)
     }
 
     /// POST /api/feeds - Create new feed
@@ -47,6 +76,19 @@ impl FeedController {
         Ok(StatusCode::CREATED)
     }
 
+    /// POST /api/feeds/validate - Check feed URLs without subscribing
+    pub async fn validate_feeds(
+        State(controller): State<Arc<FeedController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<ValidateFeedsRequest>,
+    ) -> AppResult<Json<Vec<FeedValidationResult>>> {
+        let results = controller
+            .feed_service
+            .validate_feeds(auth_user.user_id, request.urls)
+            .await?;
+        Ok(Json(results))
+    }
+
     /// DELETE /api/feeds/{feedId} - Delete feed
     pub async fn delete_feed(
         State(controller): State<Arc<FeedController>>,
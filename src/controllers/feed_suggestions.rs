@@ -1,10 +1,20 @@
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
     Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use uuid::Uuid;
 
+use crate::controllers::conditional::conditional_json;
+use crate::domain::feed_suggestions::{
+    ApproveSubmissionRequest, ListSubmissionsQuery, RejectSubmissionRequest,
+    SubmitSuggestionRequest, SuggestionModerationService, SuggestionModerationServiceApi,
+    SuggestionSubmission, SuggestionSubmissionsResponse,
+};
+use crate::domain::shared::PageResponse;
 use crate::{
     domain::feed_suggestions::{Category, FeedSuggestionsService},
     error::AppResult,
@@ -44,28 +54,113 @@ pub struct SuggestionsResponse {
 
 pub struct FeedSuggestionsController {
     service: Arc<FeedSuggestionsService>,
+    moderation_service: Arc<SuggestionModerationService>,
 }
 
 impl FeedSuggestionsController {
-    pub fn new(service: Arc<FeedSuggestionsService>) -> Self {
-        Self { service }
+    pub fn new(
+        service: Arc<FeedSuggestionsService>,
+        moderation_service: Arc<SuggestionModerationService>,
+    ) -> Self {
+        Self {
+            service,
+            moderation_service,
+        }
     }
 
     /// GET /api/feed-suggestions - Get categories with their feed suggestions
     /// If category_ids is provided, returns only those categories.
     /// If no category_ids provided, returns all categories.
+    /// Returns 304 when If-None-Match has the current ETag.
     pub async fn get_suggestions(
         State(controller): State<Arc<FeedSuggestionsController>>,
         Extension(_auth_user): Extension<AuthUser>,
         Query(query): Query<GetSuggestionsQuery>,
-    ) -> AppResult<Json<SuggestionsResponse>> {
+        headers: HeaderMap,
+    ) -> AppResult<Response> {
+        conditional_json(
+            &headers,
+            &SuggestionsResponse {
+                categories: controller.categories(query),
+            },
+        )
+    }
+
+    /// GET /v1/feed-suggestions - The same categories in the page envelope
+    pub async fn get_suggestions_v1(
+        State(controller): State<Arc<FeedSuggestionsController>>,
+        Extension(_auth_user): Extension<AuthUser>,
+        Query(query): Query<GetSuggestionsQuery>,
+        headers: HeaderMap,
+    ) -> AppResult<Response> {
+        conditional_json(
+            &headers,
+            &PageResponse::complete(controller.categories(query)),
+        )
+    }
+
+    /// POST /api/feed-suggestions/submissions - Propose a feed for the suggestions
+    pub async fn submit(
+        State(controller): State<Arc<FeedSuggestionsController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<SubmitSuggestionRequest>,
+    ) -> AppResult<(StatusCode, Json<SuggestionSubmission>)> {
+        let submission = controller
+            .moderation_service
+            .submit(auth_user.user_id, request)
+            .await?;
+        Ok((StatusCode::CREATED, Json(submission)))
+    }
+
+    /// GET /admin/suggestion-submissions - The review queue, or past decisions by status
+    pub async fn list_submissions(
+        State(controller): State<Arc<FeedSuggestionsController>>,
+        Query(query): Query<ListSubmissionsQuery>,
+    ) -> AppResult<Json<SuggestionSubmissionsResponse>> {
+        let response = controller
+            .moderation_service
+            .list_submissions(query)
+            .await?;
+        Ok(Json(response))
+    }
+
+    /// POST /admin/suggestion-submissions/{submissionId}/approve - Add it to a category
+    pub async fn approve_submission(
+        State(controller): State<Arc<FeedSuggestionsController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(submission_id): Path<Uuid>,
+        Json(request): Json<ApproveSubmissionRequest>,
+    ) -> AppResult<Json<SuggestionSubmission>> {
+        let submission = controller
+            .moderation_service
+            .approve(&auth_user.email, submission_id, request)
+            .await?;
+        Ok(Json(submission))
+    }
+
+    /// POST /admin/suggestion-submissions/{submissionId}/reject - Turn it down
+    pub async fn reject_submission(
+        State(controller): State<Arc<FeedSuggestionsController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(submission_id): Path<Uuid>,
+        Json(request): Json<RejectSubmissionRequest>,
+    ) -> AppResult<Json<SuggestionSubmission>> {
+        let submission = controller
+            .moderation_service
+            .reject(&auth_user.email, submission_id, request)
+            .await?;
+        Ok(Json(submission))
+    }
+
+    /// Categories with nested suggestions, limited to the requested ones
+    fn categories(&self, query: GetSuggestionsQuery) -> Vec<CategoryWithSuggestionsResponse> {
         // Parse category IDs from query params (support both parameter names)
         let category_ids_filter: Option<Vec<String>> = query
             .category_ids
             .or(query.categories)
             .map(|s| s.split(',').map(|id| id.trim().to_string()).collect());
 
-        let all_categories = controller.service.get_categories();
+        let all_categories = self.service.get_categories();
 
         // Filter categories if specific IDs were requested
         let categories_to_return: Vec<Category> = if let Some(ref filter_ids) = category_ids_filter
@@ -83,7 +178,7 @@ impl FeedSuggestionsController {
 
         for category in categories_to_return {
             // Get suggestions for this specific category
-            let suggestions = controller.service.get_suggestions(vec![category.id.clone()]);
+            let suggestions = self.service.get_suggestions(vec![category.id.clone()]);
 
             let suggestion_responses: Vec<FeedSuggestionResponse> = suggestions
                 .into_iter()
@@ -103,8 +198,6 @@ impl FeedSuggestionsController {
             });
         }
 
-        Ok(Json(SuggestionsResponse {
-            categories: response_categories,
-        }))
+        response_categories
     }
 }
@@ -0,0 +1,109 @@
+//! GraphQL view of the REST resources the app's home screen needs, so it can fetch them
+//! in one request and only the fields it shows.
+
+mod schema;
+
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{EmptyMutation, EmptySubscription, ErrorExtensions, Schema};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{extract::State, Extension};
+use std::sync::Arc;
+
+use crate::domain::feed::FeedService;
+use crate::domain::mute::MuteService;
+use crate::domain::user::UserService;
+use crate::error::AppError;
+use crate::infrastructure::auth::AuthUser;
+use schema::{FeedMuteRulesLoader, ProfileLoader, QueryRoot};
+
+/// Deepest selection accepted; the schema itself is three levels deep
+const MAX_QUERY_DEPTH: usize = 8;
+const MAX_QUERY_COMPLEXITY: usize = 200;
+
+pub type FeedTapeSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub struct GraphQLController {
+    schema: FeedTapeSchema,
+    user_service: Arc<UserService>,
+    mute_service: Arc<MuteService>,
+}
+
+impl GraphQLController {
+    pub fn new(
+        user_service: Arc<UserService>,
+        feed_service: Arc<FeedService>,
+        mute_service: Arc<MuteService>,
+    ) -> Self {
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .data(feed_service)
+            .limit_depth(MAX_QUERY_DEPTH)
+            .limit_complexity(MAX_QUERY_COMPLEXITY)
+            .finish();
+        Self {
+            schema,
+            user_service,
+            mute_service,
+        }
+    }
+
+    /// POST /graphql - Run a query for the authenticated user
+    pub async fn execute(
+        State(controller): State<Arc<GraphQLController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        request: GraphQLRequest,
+    ) -> GraphQLResponse {
+        // Loaders live for one request, so batching never mixes users
+        let profiles = DataLoader::new(
+            ProfileLoader {
+                user_service: controller.user_service.clone(),
+            },
+            tokio::spawn,
+        );
+        let feed_mute_rules = DataLoader::new(
+            FeedMuteRulesLoader {
+                mute_service: controller.mute_service.clone(),
+                user_id: auth_user.user_id,
+            },
+            tokio::spawn,
+        );
+        let request = request
+            .into_inner()
+            .data(auth_user)
+            .data(profiles)
+            .data(feed_mute_rules);
+
+        controller.schema.execute(request).await.into()
+    }
+}
+
+/// A GraphQL error with the message and `code` the REST envelope would have used
+fn graphql_error(error: impl Into<AppError>) -> async_graphql::Error {
+    let error = error.into();
+    tracing::error!(error = %error, "GraphQL resolver failed");
+    let detail = error.to_detail();
+    async_graphql::Error::new(detail.message).extend_with(|_, extensions| {
+        extensions.set("code", detail.code);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_exposes_the_home_screen_fields() {
+        let sdl = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .finish()
+            .sdl();
+
+        for field in [
+            "me: Me!",
+            "feeds: [Feed!]!",
+            "usage: Usage!",
+            "muteRules: [MuteRule!]!",
+            "charactersLimit: Int!",
+        ] {
+            assert!(sdl.contains(field), "missing {}", field);
+        }
+    }
+}
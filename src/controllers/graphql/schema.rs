@@ -0,0 +1,248 @@
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::{Context, Object, SimpleObject};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::graphql_error;
+use crate::domain::feed::{FeedResponse, FeedService, FeedServiceApi};
+use crate::domain::mute::{MuteRuleResponse, MuteService, MuteServiceApi};
+use crate::domain::user::{MeResponse, UserService, UserServiceApi};
+use crate::infrastructure::auth::AuthUser;
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The signed-in user's profile and subscription
+    async fn me(&self, ctx: &Context<'_>) -> async_graphql::Result<Me> {
+        Ok(Me(load_profile(ctx).await?))
+    }
+
+    /// The user's feeds, newest first
+    async fn feeds(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Feed>> {
+        let auth_user = ctx.data_unchecked::<AuthUser>();
+        let feeds = ctx
+            .data_unchecked::<Arc<FeedService>>()
+            .get_user_feeds(auth_user.user_id)
+            .await
+            .map_err(graphql_error)?;
+        Ok(feeds.into_iter().map(Feed).collect())
+    }
+
+    /// Synthesis quota for the current period
+    async fn usage(&self, ctx: &Context<'_>) -> async_graphql::Result<Usage> {
+        Ok(Usage(load_profile(ctx).await?))
+    }
+}
+
+/// `me` and `usage` both read the profile; the loader runs the lookup once per request
+async fn load_profile(ctx: &Context<'_>) -> async_graphql::Result<Arc<MeResponse>> {
+    let auth_user = ctx.data_unchecked::<AuthUser>();
+    ctx.data_unchecked::<DataLoader<ProfileLoader>>()
+        .load_one(auth_user.user_id)
+        .await?
+        .ok_or_else(|| "User not found".into())
+}
+
+pub struct Me(Arc<MeResponse>);
+
+#[Object]
+impl Me {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn display_name(&self) -> Option<&str> {
+        self.0.display_name.as_deref()
+    }
+
+    async fn avatar_url(&self) -> Option<&str> {
+        self.0.avatar_url.as_deref()
+    }
+
+    async fn language(&self) -> &str {
+        &self.0.settings.language
+    }
+
+    async fn voice(&self) -> &str {
+        &self.0.settings.voice
+    }
+
+    /// `free` or `pro`
+    async fn tier(&self) -> &str {
+        &self.0.subscription.tier
+    }
+
+    async fn subscription_status(&self) -> &str {
+        &self.0.subscription.status
+    }
+
+    async fn trial_ends_at(&self) -> Option<DateTime<Utc>> {
+        self.0
+            .subscription
+            .trial
+            .as_ref()
+            .map(|trial| trial.ends_at)
+    }
+
+    /// Set while a deletion request is pending
+    async fn deletion_scheduled_at(&self) -> Option<DateTime<Utc>> {
+        self.0.deletion_scheduled_at
+    }
+}
+
+pub struct Usage(Arc<MeResponse>);
+
+#[Object]
+impl Usage {
+    /// `daily` or `monthly`
+    async fn period(&self) -> String {
+        self.0.subscription.usage.period.to_string()
+    }
+
+    async fn characters_used(&self) -> i32 {
+        self.0.subscription.usage.characters_used_today
+    }
+
+    async fn characters_limit(&self) -> i32 {
+        self.0.subscription.usage.characters_limit
+    }
+
+    async fn minutes_used(&self) -> f32 {
+        self.0.subscription.usage.minutes_used_today
+    }
+
+    async fn minutes_limit(&self) -> i32 {
+        self.0.subscription.usage.minutes_limit
+    }
+
+    /// Unused Pro quota carried over from earlier days, on top of `charactersLimit`
+    async fn rollover_characters(&self) -> Option<i32> {
+        self.0.subscription.usage.rollover_characters
+    }
+
+    async fn resets_at(&self) -> DateTime<Utc> {
+        self.0.subscription.usage.resets_at
+    }
+}
+
+pub struct Feed(FeedResponse);
+
+#[Object]
+impl Feed {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn url(&self) -> &str {
+        &self.0.url
+    }
+
+    async fn title(&self) -> Option<&str> {
+        self.0.title.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn avg_articles_per_day(&self) -> Option<f64> {
+        self.0.avg_articles_per_day
+    }
+
+    async fn last_published_at(&self) -> Option<DateTime<Utc>> {
+        self.0.last_published_at
+    }
+
+    /// Suggestion the feed was added from
+    async fn suggestion_id(&self) -> Option<&str> {
+        self.0.suggestion_id.as_deref()
+    }
+
+    async fn category_id(&self) -> Option<&str> {
+        self.0.category_id.as_deref()
+    }
+
+    /// Mute rules that apply only to this feed. Loaded for every feed in the response
+    /// at once.
+    async fn mute_rules(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<MuteRule>> {
+        Ok(ctx
+            .data_unchecked::<DataLoader<FeedMuteRulesLoader>>()
+            .load_one(self.0.id)
+            .await?
+            .unwrap_or_default())
+    }
+}
+
+#[derive(Clone, SimpleObject)]
+pub struct MuteRule {
+    id: Uuid,
+    pattern: String,
+    /// `keyword` or `regex`
+    kind: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<MuteRuleResponse> for MuteRule {
+    fn from(rule: MuteRuleResponse) -> Self {
+        Self {
+            id: rule.id,
+            pattern: rule.pattern,
+            kind: rule.kind.to_string(),
+            created_at: rule.created_at,
+        }
+    }
+}
+
+/// Profiles by user id
+pub struct ProfileLoader {
+    pub user_service: Arc<UserService>,
+}
+
+impl Loader<Uuid> for ProfileLoader {
+    type Value = Arc<MeResponse>;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let mut profiles = HashMap::with_capacity(keys.len());
+        for user_id in keys {
+            let profile = self
+                .user_service
+                .get_user_profile(*user_id)
+                .await
+                .map_err(graphql_error)?;
+            profiles.insert(*user_id, Arc::new(profile));
+        }
+        Ok(profiles)
+    }
+}
+
+/// Per-feed mute rules of one user, read with a single query for all requested feeds
+pub struct FeedMuteRulesLoader {
+    pub mute_service: Arc<MuteService>,
+    pub user_id: Uuid,
+}
+
+impl Loader<Uuid> for FeedMuteRulesLoader {
+    type Value = Vec<MuteRule>;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
+        let rules = self
+            .mute_service
+            .list_rules(self.user_id)
+            .await
+            .map_err(graphql_error)?;
+
+        let mut by_feed: HashMap<Uuid, Vec<MuteRule>> =
+            keys.iter().map(|feed_id| (*feed_id, Vec::new())).collect();
+        for rule in rules {
+            if let Some(feed_rules) = rule.feed_id.and_then(|feed_id| by_feed.get_mut(&feed_id)) {
+                feed_rules.push(rule.into());
+            }
+        }
+        Ok(by_feed)
+    }
+}
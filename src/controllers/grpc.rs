@@ -0,0 +1,247 @@
+//! Internal gRPC API over the same services as the REST handlers. Callers are other
+//! systems on the private network, so users are named by id rather than by token.
+
+pub mod proto {
+    tonic::include_proto!("feedtape.internal.v1");
+}
+
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio_stream::Stream;
+use tonic::{metadata::MetadataValue, Code, Request, Response, Status};
+use uuid::Uuid;
+
+use crate::domain::auth::{AuthService, AuthServiceApi};
+use crate::domain::plan::PlanLimits;
+use crate::domain::tts::{TtsService, TtsServiceApi};
+use crate::domain::user::{UserService, UserServiceApi};
+use crate::error::AppError;
+use proto::internal_service_server::InternalService;
+use proto::{
+    synthesize_response::Payload, GetUsageRequest, IntrospectTokenRequest, IntrospectTokenResponse,
+    SynthesisMetadata, SynthesizeRequest, SynthesizeResponse, Usage,
+};
+
+/// Size of the audio messages a synthesis is streamed in
+const AUDIO_CHUNK_BYTES: usize = 64 * 1024;
+/// Metadata key carrying the `code` the REST error envelope would have used
+const ERROR_CODE_METADATA: &str = "x-error-code";
+
+pub struct GrpcController {
+    tts_service: Arc<TtsService>,
+    user_service: Arc<UserService>,
+    auth_service: Arc<AuthService>,
+    plan_limits: Arc<PlanLimits>,
+    /// Separate from the HTTP server's permits, so each side can be saturated on its own
+    synthesis_permits: Arc<Semaphore>,
+}
+
+impl GrpcController {
+    pub fn new(
+        tts_service: Arc<TtsService>,
+        user_service: Arc<UserService>,
+        auth_service: Arc<AuthService>,
+        plan_limits: Arc<PlanLimits>,
+        max_concurrent_syntheses: usize,
+    ) -> Self {
+        Self {
+            tts_service,
+            user_service,
+            auth_service,
+            plan_limits,
+            synthesis_permits: Arc::new(Semaphore::new(max_concurrent_syntheses)),
+        }
+    }
+}
+
+type SynthesizeStream = Pin<Box<dyn Stream<Item = Result<SynthesizeResponse, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl InternalService for GrpcController {
+    type SynthesizeStream = SynthesizeStream;
+
+    async fn synthesize(
+        &self,
+        request: Request<SynthesizeRequest>,
+    ) -> Result<Response<SynthesizeStream>, Status> {
+        let request = request.into_inner();
+        let user_id = parse_user_id(&request.user_id)?;
+
+        let char_count = request.text.len() as i32;
+        if char_count == 0 {
+            return Err(Status::invalid_argument("Text cannot be empty"));
+        }
+        if char_count > self.plan_limits.max_request_characters {
+            return Err(Status::invalid_argument(format!(
+                "Text must be {} characters or less",
+                self.plan_limits.max_request_characters
+            )));
+        }
+
+        let Ok(_permit) = self.synthesis_permits.try_acquire() else {
+            return Err(Status::unavailable(
+                "Too many requests in progress, retry shortly",
+            ));
+        };
+        let result = self
+            .tts_service
+            .synthesize(user_id, request.text, request.link)
+            .await
+            .map_err(status_from)?;
+        let profile = self
+            .user_service
+            .get_user_profile(user_id)
+            .await
+            .map_err(status_from)?;
+
+        let metadata = SynthesisMetadata {
+            content_type: "audio/mpeg".to_string(),
+            language_detected: result.language_detected.to_string(),
+            character_count: result.char_count,
+            duration_seconds: result.duration_minutes * 60.0,
+            usage_remaining: profile.subscription.usage.characters_remaining(),
+        };
+        let messages = synthesis_messages(metadata, &result.audio_data);
+
+        Ok(Response::new(Box::pin(tokio_stream::iter(
+            messages.into_iter().map(Ok),
+        ))))
+    }
+
+    async fn get_usage(
+        &self,
+        request: Request<GetUsageRequest>,
+    ) -> Result<Response<Usage>, Status> {
+        let user_id = parse_user_id(&request.get_ref().user_id)?;
+        let profile = self
+            .user_service
+            .get_user_profile(user_id)
+            .await
+            .map_err(status_from)?;
+        let usage = profile.subscription.usage;
+
+        Ok(Response::new(Usage {
+            period: usage.period.to_string(),
+            characters_used: usage.characters_used_today,
+            characters_limit: usage.characters_limit,
+            minutes_used: usage.minutes_used_today,
+            minutes_limit: usage.minutes_limit,
+            rollover_characters: usage.rollover_characters,
+            resets_at: Some(timestamp(usage.resets_at)),
+        }))
+    }
+
+    async fn introspect_token(
+        &self,
+        request: Request<IntrospectTokenRequest>,
+    ) -> Result<Response<IntrospectTokenResponse>, Status> {
+        let introspection = self
+            .auth_service
+            .introspect_access_token(&request.get_ref().token)
+            .await
+            .map_err(status_from)?;
+
+        let response = match introspection {
+            Some(introspection) => IntrospectTokenResponse {
+                active: true,
+                user_id: introspection.user_id.to_string(),
+                email: introspection.email,
+                tier: introspection.tier.to_string(),
+                expires_at: Some(timestamp(introspection.expires_at)),
+            },
+            None => IntrospectTokenResponse::default(),
+        };
+        Ok(Response::new(response))
+    }
+}
+
+fn parse_user_id(user_id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(user_id).map_err(|_| Status::invalid_argument("user_id must be a UUID"))
+}
+
+fn timestamp(at: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: at.timestamp(),
+        nanos: at.timestamp_subsec_nanos() as i32,
+    }
+}
+
+/// The metadata message followed by the audio, split into chunks
+fn synthesis_messages(metadata: SynthesisMetadata, audio: &[u8]) -> Vec<SynthesizeResponse> {
+    std::iter::once(Payload::Metadata(metadata))
+        .chain(
+            audio
+                .chunks(AUDIO_CHUNK_BYTES)
+                .map(|chunk| Payload::Audio(chunk.to_vec())),
+        )
+        .map(|payload| SynthesizeResponse {
+            payload: Some(payload),
+        })
+        .collect()
+}
+
+/// The gRPC status closest to the HTTP status the REST API would answer with
+fn status_from(error: impl Into<AppError>) -> Status {
+    let error = error.into();
+    let code = match error.status_code() {
+        StatusCode::BAD_REQUEST | StatusCode::PAYLOAD_TOO_LARGE => Code::InvalidArgument,
+        StatusCode::UNAUTHORIZED => Code::Unauthenticated,
+        StatusCode::FORBIDDEN => Code::PermissionDenied,
+        StatusCode::NOT_FOUND => Code::NotFound,
+        StatusCode::CONFLICT => Code::AlreadyExists,
+        StatusCode::PRECONDITION_FAILED => Code::FailedPrecondition,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::PAYMENT_REQUIRED => Code::ResourceExhausted,
+        StatusCode::SERVICE_UNAVAILABLE => Code::Unavailable,
+        _ => Code::Internal,
+    };
+    if code == Code::Internal {
+        tracing::error!(error = %error, "gRPC call failed");
+    }
+
+    let mut status = Status::new(code, error.to_string());
+    status.metadata_mut().insert(
+        ERROR_CODE_METADATA,
+        MetadataValue::from_static(error.code()),
+    );
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_follows_the_metadata_in_chunks() {
+        let audio = vec![7u8; AUDIO_CHUNK_BYTES * 2 + 10];
+        let messages = synthesis_messages(SynthesisMetadata::default(), &audio);
+
+        assert_eq!(messages.len(), 4);
+        assert!(matches!(messages[0].payload, Some(Payload::Metadata(_))));
+        let streamed: Vec<u8> = messages[1..]
+            .iter()
+            .flat_map(|message| match &message.payload {
+                Some(Payload::Audio(chunk)) => chunk.clone(),
+                _ => panic!("expected audio"),
+            })
+            .collect();
+        assert_eq!(streamed, audio);
+    }
+
+    #[test]
+    fn test_status_keeps_the_rest_error_code() {
+        let status = status_from(AppError::PaymentRequired("Quota exceeded".to_string()));
+
+        assert_eq!(status.code(), Code::ResourceExhausted);
+        assert_eq!(
+            status.metadata().get(ERROR_CODE_METADATA).unwrap(),
+            "payment_required"
+        );
+        assert_eq!(
+            status_from(AppError::NotFound("User not found".to_string())).code(),
+            Code::NotFound
+        );
+    }
+}
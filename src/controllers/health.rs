@@ -1,29 +1,326 @@
-use crate::infrastructure::db::{check_connection, DbPool};
+use crate::infrastructure::cache::CacheStore;
+use crate::infrastructure::db::{check_connection, embedded_migrations, migration_status, DbPool};
+use aws_sdk_polly::error::SdkError;
+use aws_sdk_polly::types::LanguageCode;
+use aws_sdk_polly::Client as PollyClient;
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
-use serde_json::json;
+use serde::Serialize;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Longest a single dependency check may take before it counts as down
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Database pings slower than this mark the service as degraded
+const SLOW_DATABASE_MS: u64 = 250;
+const CACHE_PROBE_KEY: &str = "health:probe";
 
 pub async fn health() -> impl IntoResponse {
     (StatusCode::OK, "OK")
 }
 
-pub async fn health_ready(State(pool): State<Arc<DbPool>>) -> impl IntoResponse {
-    match check_connection(&pool).await {
-        Ok(_) => (
-            StatusCode::OK,
-            Json(json!({
-                "status": "ready",
-                "database": "connected",
-                "tts": "available"
-            })),
-        ),
-        Err(_) => (
-            StatusCode::SERVICE_UNAVAILABLE,
-            Json(json!({
-                "status": "not_ready",
-                "database": "disconnected",
-                "tts": "unknown"
-            })),
-        ),
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessStatus {
+    Ready,
+    /// Serving requests, but a dependency is slow or unavailable
+    Degraded,
+    NotReady,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Slow,
+    Down,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyCheck {
+    pub status: CheckStatus,
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TtsProviderCheck {
+    pub provider: &'static str,
+    pub status: CheckStatus,
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PoolStats {
+    pub size: u32,
+    pub in_use: u32,
+    pub idle: u32,
+    pub max: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MigrationCheck {
+    pub status: CheckStatus,
+    /// Latest migration applied to the database
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<i64>,
+    /// Latest migration shipped with this build
+    pub expected_version: Option<i64>,
+    /// Migrations shipped with this build that the database hasn't applied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending: Option<usize>,
+    /// Applied migrations this build doesn't know about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unknown: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessChecks {
+    pub database: DependencyCheck,
+    pub pool: PoolStats,
+    pub migrations: MigrationCheck,
+    pub cache: DependencyCheck,
+    pub tts: Vec<TtsProviderCheck>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub status: ReadinessStatus,
+    /// Summary kept for clients that only read the top-level fields
+    pub database: &'static str,
+    pub tts: &'static str,
+    pub checks: ReadinessChecks,
+}
+
+impl ReadinessChecks {
+    /// Not ready when queries can't succeed; degraded when anything else is off
+    fn overall_status(&self) -> ReadinessStatus {
+        if self.database.status == CheckStatus::Down || self.migrations.status != CheckStatus::Ok {
+            return ReadinessStatus::NotReady;
+        }
+
+        let pool_exhausted = self.pool.max > 0 && self.pool.in_use >= self.pool.max;
+        if self.database.status != CheckStatus::Ok
+            || pool_exhausted
+            || self.cache.status != CheckStatus::Ok
+            || self.tts.iter().any(|check| check.status != CheckStatus::Ok)
+        {
+            return ReadinessStatus::Degraded;
+        }
+
+        ReadinessStatus::Ready
+    }
+}
+
+pub struct HealthController {
+    pool: Arc<DbPool>,
+    cache: Arc<dyn CacheStore>,
+    polly_client: Arc<PollyClient>,
+}
+
+impl HealthController {
+    pub fn new(
+        pool: Arc<DbPool>,
+        cache: Arc<dyn CacheStore>,
+        polly_client: Arc<PollyClient>,
+    ) -> Self {
+        Self {
+            pool,
+            cache,
+            polly_client,
+        }
+    }
+
+    /// GET /health/ready - Dependency checks with latencies and pool statistics
+    pub async fn health_ready(
+        State(controller): State<Arc<HealthController>>,
+    ) -> impl IntoResponse {
+        let (database, migrations, cache, polly) = tokio::join!(
+            controller.check_database(),
+            controller.check_migrations(),
+            controller.check_cache(),
+            controller.check_polly(),
+        );
+
+        let checks = ReadinessChecks {
+            database,
+            pool: controller.pool_stats(),
+            migrations,
+            cache,
+            tts: vec![polly],
+        };
+        let status = checks.overall_status();
+        let status_code = match status {
+            ReadinessStatus::NotReady => StatusCode::SERVICE_UNAVAILABLE,
+            ReadinessStatus::Ready | ReadinessStatus::Degraded => StatusCode::OK,
+        };
+        if status != ReadinessStatus::Ready {
+            tracing::warn!(checks = ?checks, "Readiness check is {:?}", status);
+        }
+
+        let tts_available = checks
+            .tts
+            .iter()
+            .any(|check| check.status == CheckStatus::Ok);
+        let response = ReadinessResponse {
+            status,
+            database: match checks.database.status {
+                CheckStatus::Ok => "connected",
+                CheckStatus::Slow => "slow",
+                CheckStatus::Down => "disconnected",
+            },
+            tts: if tts_available {
+                "available"
+            } else {
+                "unavailable"
+            },
+            checks,
+        };
+
+        (status_code, Json(response))
+    }
+}
+
+impl HealthController {
+    async fn check_database(&self) -> DependencyCheck {
+        let (result, latency_ms) = timed(check_connection(&self.pool)).await;
+        let status = match result {
+            Some(Ok(_)) if latency_ms > SLOW_DATABASE_MS => CheckStatus::Slow,
+            Some(Ok(_)) => CheckStatus::Ok,
+            _ => CheckStatus::Down,
+        };
+        DependencyCheck { status, latency_ms }
+    }
+
+    fn pool_stats(&self) -> PoolStats {
+        let size = self.pool.size();
+        let idle = self.pool.num_idle() as u32;
+        PoolStats {
+            size,
+            in_use: size.saturating_sub(idle),
+            idle,
+            max: self.pool.options().get_max_connections(),
+        }
+    }
+
+    async fn check_migrations(&self) -> MigrationCheck {
+        let (result, _) = timed(migration_status(&self.pool)).await;
+
+        match result {
+            Some(Ok(migrations)) => MigrationCheck {
+                status: if migrations.is_current() {
+                    CheckStatus::Ok
+                } else {
+                    CheckStatus::Down
+                },
+                version: migrations.applied_version,
+                expected_version: migrations.expected_version,
+                pending: Some(migrations.pending.len()),
+                unknown: Some(migrations.unknown.len()),
+            },
+            _ => MigrationCheck {
+                status: CheckStatus::Down,
+                version: None,
+                expected_version: embedded_migrations().into_iter().max(),
+                pending: None,
+                unknown: None,
+            },
+        }
+    }
+
+    async fn check_cache(&self) -> DependencyCheck {
+        let (result, latency_ms) = timed(self.cache.get(CACHE_PROBE_KEY)).await;
+        let status = match result {
+            Some(Ok(_)) => CheckStatus::Ok,
+            _ => CheckStatus::Down,
+        };
+        DependencyCheck { status, latency_ms }
+    }
+
+    async fn check_polly(&self) -> TtsProviderCheck {
+        let request = self
+            .polly_client
+            .describe_voices()
+            .language_code(LanguageCode::EnUs)
+            .send();
+        let (result, latency_ms) = timed(request).await;
+        // An error returned by Polly itself (e.g. access denied) still means it is reachable
+        let status = match result {
+            Some(Ok(_)) | Some(Err(SdkError::ServiceError(_))) => CheckStatus::Ok,
+            _ => CheckStatus::Down,
+        };
+        TtsProviderCheck {
+            provider: "polly",
+            status,
+            latency_ms,
+        }
+    }
+}
+
+/// Run a check under `PROBE_TIMEOUT`, returning `None` when it timed out
+async fn timed<T>(check: impl Future<Output = T>) -> (Option<T>, u64) {
+    let started = Instant::now();
+    let result = tokio::time::timeout(PROBE_TIMEOUT, check).await.ok();
+    (result, started.elapsed().as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checks(database: CheckStatus, pending: usize, tts: CheckStatus) -> ReadinessChecks {
+        ReadinessChecks {
+            database: DependencyCheck {
+                status: database,
+                latency_ms: 1,
+            },
+            pool: PoolStats {
+                size: 2,
+                in_use: 1,
+                idle: 1,
+                max: 10,
+            },
+            migrations: MigrationCheck {
+                status: if pending == 0 {
+                    CheckStatus::Ok
+                } else {
+                    CheckStatus::Down
+                },
+                version: Some(1),
+                expected_version: Some(1 + pending as i64),
+                pending: Some(pending),
+                unknown: Some(0),
+            },
+            cache: DependencyCheck {
+                status: CheckStatus::Ok,
+                latency_ms: 0,
+            },
+            tts: vec![TtsProviderCheck {
+                provider: "polly",
+                status: tts,
+                latency_ms: 20,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_overall_status() {
+        let ready = checks(CheckStatus::Ok, 0, CheckStatus::Ok);
+        assert_eq!(ready.overall_status(), ReadinessStatus::Ready);
+
+        let slow = checks(CheckStatus::Slow, 0, CheckStatus::Ok);
+        assert_eq!(slow.overall_status(), ReadinessStatus::Degraded);
+
+        let tts_down = checks(CheckStatus::Ok, 0, CheckStatus::Down);
+        assert_eq!(tts_down.overall_status(), ReadinessStatus::Degraded);
+
+        let mut exhausted = checks(CheckStatus::Ok, 0, CheckStatus::Ok);
+        exhausted.pool.in_use = 10;
+        assert_eq!(exhausted.overall_status(), ReadinessStatus::Degraded);
+
+        let db_down = checks(CheckStatus::Down, 0, CheckStatus::Ok);
+        assert_eq!(db_down.overall_status(), ReadinessStatus::NotReady);
+
+        let pending = checks(CheckStatus::Ok, 1, CheckStatus::Ok);
+        assert_eq!(pending.overall_status(), ReadinessStatus::NotReady);
     }
 }
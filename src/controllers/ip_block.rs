@@ -0,0 +1,58 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::ip_block::{CreateIpBlockRequest, IpBlock, IpBlocksResponse};
+use crate::{
+    domain::ip_block::{IpBlockService, IpBlockServiceApi},
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+pub struct IpBlockController {
+    ip_block_service: Arc<IpBlockService>,
+}
+
+impl IpBlockController {
+    pub fn new(ip_block_service: Arc<IpBlockService>) -> Self {
+        Self { ip_block_service }
+    }
+
+    /// GET /admin/ip-blocks - List blocked addresses
+    pub async fn list_blocks(
+        State(controller): State<Arc<IpBlockController>>,
+    ) -> AppResult<Json<IpBlocksResponse>> {
+        let response = controller.ip_block_service.list_blocks().await?;
+        Ok(Json(response))
+    }
+
+    /// POST /admin/ip-blocks - Block an address or range
+    pub async fn create_block(
+        State(controller): State<Arc<IpBlockController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<CreateIpBlockRequest>,
+    ) -> AppResult<(StatusCode, Json<IpBlock>)> {
+        let block = controller
+            .ip_block_service
+            .block(&auth_user.email, request)
+            .await?;
+        Ok((StatusCode::CREATED, Json(block)))
+    }
+
+    /// DELETE /admin/ip-blocks/{blockId} - Lift a block
+    pub async fn delete_block(
+        State(controller): State<Arc<IpBlockController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(block_id): Path<Uuid>,
+    ) -> AppResult<StatusCode> {
+        controller
+            .ip_block_service
+            .unblock(&auth_user.email, block_id)
+            .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
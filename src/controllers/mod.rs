@@ -1,7 +1,23 @@
+pub mod admin;
+pub mod admin_dashboard;
+pub mod analytics;
+pub mod api_key;
 pub mod auth;
+pub mod conditional;
+pub mod device;
+pub mod events;
+pub mod family;
 pub mod feed;
 pub mod feed_suggestions;
+pub mod graphql;
+pub mod grpc;
 pub mod health;
+pub mod ip_block;
+pub mod mute;
 pub mod oauth;
+pub mod referral;
+pub mod subscription;
 pub mod tts;
 pub mod user;
+pub mod webhook;
+pub mod weekly_summary;
@@ -0,0 +1,75 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::mute::{CreateMuteRuleRequest, MuteRuleResponse};
+use crate::domain::shared::PageResponse;
+use crate::{
+    domain::mute::{MuteService, MuteServiceApi},
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+pub struct MuteController {
+    mute_service: Arc<MuteService>,
+}
+
+impl MuteController {
+    pub fn new(mute_service: Arc<MuteService>) -> Self {
+        Self { mute_service }
+    }
+
+    /// GET /api/mute-rules - List user's mute rules
+    pub async fn list_rules(
+        State(controller): State<Arc<MuteController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<Vec<MuteRuleResponse>>> {
+        let rules = controller
+            .mute_service
+            .list_rules(auth_user.user_id)
+            .await?;
+        Ok(Json(rules))
+    }
+
+    /// GET /v1/mute-rules - List user's mute rules in the page envelope
+    pub async fn list_rules_v1(
+        State(controller): State<Arc<MuteController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<PageResponse<MuteRuleResponse>>> {
+        let rules = controller
+            .mute_service
+            .list_rules(auth_user.user_id)
+            .await?;
+        Ok(Json(PageResponse::complete(rules)))
+    }
+
+    /// POST /api/mute-rules - Create a mute rule (global or per feed)
+    pub async fn create_rule(
+        State(controller): State<Arc<MuteController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<CreateMuteRuleRequest>,
+    ) -> AppResult<(StatusCode, Json<MuteRuleResponse>)> {
+        let rule = controller
+            .mute_service
+            .create_rule(auth_user.user_id, request)
+            .await?;
+        Ok((StatusCode::CREATED, Json(rule)))
+    }
+
+    /// DELETE /api/mute-rules/{ruleId} - Delete a mute rule
+    pub async fn delete_rule(
+        State(controller): State<Arc<MuteController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(rule_id): Path<Uuid>,
+    ) -> AppResult<StatusCode> {
+        controller
+            .mute_service
+            .delete_rule(auth_user.user_id, rule_id)
+            .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
@@ -1,21 +1,34 @@
 use axum::{
     extract::{Query, State},
+    http::{header, HeaderMap},
     response::{IntoResponse, Redirect, Response},
-    Json,
+    Extension, Json,
 };
+use chrono::{Duration, Utc};
 use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::controllers::auth::session_client;
+use crate::infrastructure::http::ClientIp;
 use crate::{
     domain::auth::{AuthService, AuthServiceApi},
-    error::AppResult,
-    infrastructure::{oauth::GitHubOAuthClient, repositories::UserRepository},
+    error::{AppError, AppResult},
+    infrastructure::{
+        auth::{admin_session, oauth_state},
+        oauth::OAuthProvider,
+        repositories::{OAuthStateRepository, UserRepository, UserRepositoryApi},
+    },
 };
 
+/// How long a sign-in may take between leaving for the provider and coming back
+const OAUTH_STATE_TTL_MINUTES: i64 = 10;
+
 #[derive(Debug, Deserialize)]
 pub struct InitiateOAuthParams {
     pub mobile: Option<bool>,
+    /// Sign in to the admin dashboard, which keeps the token in a cookie
+    pub admin: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -25,21 +38,27 @@ pub struct OAuthCallbackParams {
 }
 
 pub struct OAuthController {
-    github_client: Arc<GitHubOAuthClient>,
+    github_client: Arc<dyn OAuthProvider>,
     user_repo: Arc<UserRepository>,
+    oauth_state_repo: Arc<OAuthStateRepository>,
     auth_service: Arc<AuthService>,
+    secure_cookies: bool,
 }
 
 impl OAuthController {
     pub fn new(
-        github_client: Arc<GitHubOAuthClient>,
+        github_client: Arc<dyn OAuthProvider>,
         user_repo: Arc<UserRepository>,
+        oauth_state_repo: Arc<OAuthStateRepository>,
         auth_service: Arc<AuthService>,
+        secure_cookies: bool,
     ) -> Self {
         Self {
             github_client,
             user_repo,
+            oauth_state_repo,
             auth_service,
+            secure_cookies,
         }
     }
 
@@ -47,26 +66,47 @@ impl OAuthController {
     ///
     /// Query params:
     /// - mobile: Optional boolean. If true, callback will redirect to mobile deep link
+    /// - admin: Optional boolean. If true, callback will start an admin dashboard session
     pub async fn initiate_github(
         State(controller): State<Arc<OAuthController>>,
         Query(params): Query<InitiateOAuthParams>,
-    ) -> impl IntoResponse {
+    ) -> AppResult<Response> {
         // Generate random UUID for CSRF protection
         let uuid = Uuid::new_v4().to_string();
 
-        // Encode mobile indicator in state: "mobile:UUID" or "web:UUID"
+        // Encode the client in state: "mobile:UUID", "admin:UUID" or "web:UUID"
         let state = if params.mobile.unwrap_or(false) {
             format!("mobile:{}", uuid)
+        } else if params.admin.unwrap_or(false) {
+            format!("admin:{}", uuid)
         } else {
             format!("web:{}", uuid)
         };
 
-        // TODO: Store state in session/cache for validation (currently simplified)
-        // In production, you'd store this with expiry in Redis or DB
+        // Stored so the callback can check it came from a sign-in started here
+        controller
+            .oauth_state_repo
+            .create(
+                &state,
+                Utc::now() + Duration::minutes(OAUTH_STATE_TTL_MINUTES),
+            )
+            .await?;
 
         let auth_url = controller.github_client.get_authorization_url(&state);
 
-        Redirect::temporary(&auth_url)
+        // The callback must come back to this browser, not one a crafted link was opened in
+        Ok((
+            [(
+                header::SET_COOKIE,
+                oauth_state::state_cookie(
+                    &state,
+                    OAUTH_STATE_TTL_MINUTES * 60,
+                    controller.secure_cookies,
+                ),
+            )],
+            Redirect::temporary(&auth_url),
+        )
+            .into_response())
     }
 
     /// GET /auth/callback/github - Handle GitHub OAuth callback
@@ -74,15 +114,33 @@ impl OAuthController {
     /// Returns either:
     /// - JSON with tokens (for web clients)
     /// - Redirect to deep link (for mobile clients)
+    /// - Redirect to the admin dashboard with a session cookie (for admin sign-ins)
     pub async fn github_callback(
         State(controller): State<Arc<OAuthController>>,
+        headers: HeaderMap,
+        client_ip: Option<Extension<ClientIp>>,
         Query(params): Query<OAuthCallbackParams>,
     ) -> AppResult<Response> {
         // Parse state to detect if this is a mobile request
         let is_mobile = params.state.starts_with("mobile:");
+        let is_admin = params.state.starts_with("admin:");
 
-        // TODO: Validate state parameter against stored value
-        // For now, we skip this check for simplicity
+        if !oauth_state::state_matches_cookie(&headers, &params.state) {
+            return Err(AppError::BadRequest(
+                "OAuth state does not match this browser".to_string(),
+            ));
+        }
+
+        // Each state is good for one callback, so a forged or replayed one is refused
+        if !controller
+            .oauth_state_repo
+            .consume(&params.state, Utc::now())
+            .await?
+        {
+            return Err(AppError::BadRequest(
+                "Invalid or expired OAuth state".to_string(),
+            ));
+        }
 
         // Exchange code for access token
         let token_response = controller.github_client.exchange_code(&params.code).await?;
@@ -95,9 +153,7 @@ impl OAuthController {
 
         // Validate we have an email
         let email = github_user.email.ok_or_else(|| {
-            crate::error::AppError::BadRequest(
-                "GitHub account has no verified email address".to_string(),
-            )
+            AppError::BadRequest("GitHub account has no verified email address".to_string())
         })?;
 
         let provider_id = github_user.id.to_string();
@@ -108,12 +164,27 @@ impl OAuthController {
             .find_by_oauth("github", &provider_id)
             .await?
         {
-            Some(existing_user) => existing_user,
+            Some(existing_user) => {
+                controller
+                    .user_repo
+                    .refresh_oauth_profile(
+                        existing_user.id,
+                        github_user.name.as_deref(),
+                        github_user.avatar_url.as_deref(),
+                    )
+                    .await?
+            }
             None => {
                 // Create new user
                 controller
                     .user_repo
-                    .create(&email, "github", &provider_id)
+                    .create(
+                        &email,
+                        "github",
+                        &provider_id,
+                        github_user.name.as_deref(),
+                        github_user.avatar_url.as_deref(),
+                    )
                     .await?
             }
         };
@@ -121,7 +192,7 @@ impl OAuthController {
         // Generate JWT and refresh tokens
         let tokens = controller
             .auth_service
-            .create_tokens_for_user(user.id, &user.email)
+            .create_tokens_for_user(user.id, &user.email, &session_client(&headers, client_ip))
             .await?;
 
         // Return appropriate response based on client type
@@ -134,6 +205,20 @@ impl OAuthController {
                 tokens.expires_in
             );
             Ok(Redirect::temporary(&deep_link).into_response())
+        } else if is_admin {
+            // Non-admins get the session too; the dashboard answers them with 403
+            Ok((
+                [(
+                    header::SET_COOKIE,
+                    admin_session::session_cookie(
+                        &tokens.token,
+                        tokens.expires_in,
+                        controller.secure_cookies,
+                    ),
+                )],
+                Redirect::to("/admin"),
+            )
+                .into_response())
         } else {
             Ok(Json(tokens).into_response())
         }
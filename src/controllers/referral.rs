@@ -0,0 +1,44 @@
+use axum::{extract::State, Extension, Json};
+use std::sync::Arc;
+
+use crate::domain::referral::{RedeemReferralRequest, RedeemReferralResponse, ReferralResponse};
+use crate::{
+    domain::referral::{ReferralService, ReferralServiceApi},
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+pub struct ReferralController {
+    referral_service: Arc<ReferralService>,
+}
+
+impl ReferralController {
+    pub fn new(referral_service: Arc<ReferralService>) -> Self {
+        Self { referral_service }
+    }
+
+    /// GET /api/me/referral - Get the user's referral code and bonus status
+    pub async fn get_referral(
+        State(controller): State<Arc<ReferralController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<ReferralResponse>> {
+        let response = controller
+            .referral_service
+            .get_referral(auth_user.user_id)
+            .await?;
+        Ok(Json(response))
+    }
+
+    /// POST /auth/redeem-referral - Redeem another user's referral code
+    pub async fn redeem(
+        State(controller): State<Arc<ReferralController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<RedeemReferralRequest>,
+    ) -> AppResult<Json<RedeemReferralResponse>> {
+        let response = controller
+            .referral_service
+            .redeem(auth_user.user_id, &request.code)
+            .await?;
+        Ok(Json(response))
+    }
+}
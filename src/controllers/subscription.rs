@@ -0,0 +1,136 @@
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Extension, Json,
+};
+use std::sync::Arc;
+
+use crate::domain::shared::PageResponse;
+use crate::domain::subscription::{
+    AppStoreNotificationRequest, CheckoutSessionResponse, RedeemPromoCodeRequest,
+    SubscriptionEventResponse, SubscriptionHistoryResponse, SubscriptionResponse,
+    ValidatePurchaseRequest,
+};
+use crate::{
+    domain::subscription::{SubscriptionService, SubscriptionServiceApi},
+    error::{AppError, AppResult},
+    infrastructure::auth::AuthUser,
+};
+
+pub struct SubscriptionController {
+    subscription_service: Arc<SubscriptionService>,
+}
+
+impl SubscriptionController {
+    pub fn new(subscription_service: Arc<SubscriptionService>) -> Self {
+        Self {
+            subscription_service,
+        }
+    }
+
+    /// POST /api/subscription/validate-purchase - Validate a Google Play subscription purchase
+    pub async fn validate_purchase(
+        State(controller): State<Arc<SubscriptionController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<ValidatePurchaseRequest>,
+    ) -> AppResult<Json<SubscriptionResponse>> {
+        let subscription = controller
+            .subscription_service
+            .validate_google_purchase(auth_user.user_id, request)
+            .await?;
+        Ok(Json(subscription))
+    }
+
+    /// POST /api/subscription/start-trial - Start the free trial
+    pub async fn start_trial(
+        State(controller): State<Arc<SubscriptionController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<SubscriptionResponse>> {
+        let subscription = controller
+            .subscription_service
+            .start_trial(auth_user.user_id)
+            .await?;
+        Ok(Json(subscription))
+    }
+
+    /// POST /api/subscription/redeem - Redeem a promo code for Pro
+    pub async fn redeem_promo_code(
+        State(controller): State<Arc<SubscriptionController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<RedeemPromoCodeRequest>,
+    ) -> AppResult<Json<SubscriptionResponse>> {
+        let subscription = controller
+            .subscription_service
+            .redeem_promo_code(auth_user.user_id, request)
+            .await?;
+        Ok(Json(subscription))
+    }
+
+    /// GET /api/subscription/history - Billing history and current renewal date
+    pub async fn get_history(
+        State(controller): State<Arc<SubscriptionController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<SubscriptionHistoryResponse>> {
+        let history = controller
+            .subscription_service
+            .get_history(auth_user.user_id)
+            .await?;
+        Ok(Json(history))
+    }
+
+    /// GET /v1/subscription/history - Subscription events in the page envelope, newest
+    /// first; the current status is on GET /api/me
+    pub async fn get_history_v1(
+        State(controller): State<Arc<SubscriptionController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<PageResponse<SubscriptionEventResponse>>> {
+        let history = controller
+            .subscription_service
+            .get_history(auth_user.user_id)
+            .await?;
+        Ok(Json(PageResponse::complete(history.events)))
+    }
+
+    /// POST /api/subscription/checkout-session - Start a Stripe Checkout session (web)
+    pub async fn create_checkout_session(
+        State(controller): State<Arc<SubscriptionController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<CheckoutSessionResponse>> {
+        let session = controller
+            .subscription_service
+            .create_checkout_session(auth_user.user_id)
+            .await?;
+        Ok(Json(session))
+    }
+
+    /// POST /webhooks/stripe - Receive Stripe billing events
+    pub async fn stripe_webhook(
+        State(controller): State<Arc<SubscriptionController>>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> AppResult<StatusCode> {
+        let signature = headers
+            .get("Stripe-Signature")
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| AppError::BadRequest("Missing Stripe-Signature header".to_string()))?;
+
+        controller
+            .subscription_service
+            .handle_stripe_webhook(&body, signature)
+            .await?;
+        Ok(StatusCode::OK)
+    }
+
+    /// POST /webhooks/apple - Receive App Store Server Notifications V2
+    pub async fn apple_webhook(
+        State(controller): State<Arc<SubscriptionController>>,
+        Json(request): Json<AppStoreNotificationRequest>,
+    ) -> AppResult<StatusCode> {
+        controller
+            .subscription_service
+            .handle_app_store_notification(request)
+            .await?;
+        Ok(StatusCode::OK)
+    }
+}
@@ -1,22 +1,36 @@
 use axum::{
     body::Body,
-    extract::State,
+    extract::{Query, State},
     http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
+use chrono::{Duration, NaiveDate};
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::sync::Arc;
 
 use crate::{
     domain::{
+        plan::PlanLimits,
         shared::usage_dto::{DailyUsage, UsageLimits, UsageResponse, UsageStats},
-        tts::{TtsService, TtsServiceApi},
-        user::{UserService, UserServiceApi},
+        tts::{TtsService, TtsServiceApi, TtsServiceError},
+        user::{UsageDto, UserService, UserServiceApi},
     },
     error::{AppError, AppResult},
-    infrastructure::{auth::AuthUser, repositories::UsageRepository},
+    infrastructure::{
+        auth::AuthUser,
+        http::RateLimit,
+        repositories::{UsageRecord, UsageRepository, UsageRepositoryApi},
+    },
 };
-use chrono::{Duration, Utc};
+
+/// Days exported when the request doesn't say where to start
+const DEFAULT_EXPORT_DAYS: i64 = 30;
+/// Longest range one export covers
+const MAX_EXPORT_DAYS: i64 = 366;
+/// Seconds clients are asked to wait when the TTS provider is throttling or down
+const PROVIDER_RETRY_AFTER_SECS: &str = "5";
 
 /// Request for POST /api/tts/synthesize
 #[derive(Debug, Serialize, Deserialize)]
@@ -25,10 +39,25 @@ pub struct TtsRequest {
     pub link: String,
 }
 
+/// Query for GET /api/tts/usage/export
+#[derive(Debug, Deserialize)]
+pub struct UsageExportQuery {
+    /// Only `csv` for now
+    #[serde(default)]
+    pub format: Option<String>,
+    /// First day, inclusive; 30 days up to `to` when absent
+    #[serde(default)]
+    pub from: Option<NaiveDate>,
+    /// Last day, inclusive; today in the user's timezone when absent
+    #[serde(default)]
+    pub to: Option<NaiveDate>,
+}
+
 pub struct TtsController {
     tts_service: Arc<TtsService>,
     user_service: Arc<UserService>,
     usage_repo: Arc<UsageRepository>,
+    plan_limits: Arc<PlanLimits>,
 }
 
 impl TtsController {
@@ -36,11 +65,13 @@ impl TtsController {
         tts_service: Arc<TtsService>,
         user_service: Arc<UserService>,
         usage_repo: Arc<UsageRepository>,
+        plan_limits: Arc<PlanLimits>,
     ) -> Self {
         Self {
             tts_service,
             user_service,
             usage_repo,
+            plan_limits,
         }
     }
 
@@ -49,7 +80,7 @@ impl TtsController {
         State(controller): State<Arc<TtsController>>,
         Extension(auth_user): Extension<AuthUser>,
         Json(request): Json<TtsRequest>,
-    ) -> AppResult<(StatusCode, HeaderMap, Body)> {
+    ) -> AppResult<Response> {
         // Validate input
         let char_count = request.text.len() as i32;
 
@@ -57,33 +88,53 @@ impl TtsController {
             return Err(AppError::BadRequest("Text cannot be empty".to_string()));
         }
 
-        if char_count > 10000 {
-            return Err(AppError::PayloadTooLarge(
-                "Text must be 10,000 characters or less".to_string(),
-            ));
+        let max_request_characters = controller.plan_limits.max_request_characters;
+        if char_count > max_request_characters {
+            return Err(AppError::PayloadTooLarge(format!(
+                "Text must be {} characters or less",
+                max_request_characters
+            )));
         }
 
-        // Synthesize speech using service
-        let result = controller
+        // Synthesize speech using service. A refusal over the quota still tells the client
+        // when it resets, and a provider failure worth retrying says when to.
+        let result = match controller
             .tts_service
             .synthesize(auth_user.user_id, request.text, request.link)
             .await
-            .map_err(|e| AppError::from(e))?;
+        {
+            Ok(result) => result,
+            Err(e @ TtsServiceError::PaymentRequired(_)) => {
+                let me_response = controller
+                    .user_service
+                    .get_user_profile(auth_user.user_id)
+                    .await?;
+                let rate_limit = character_rate_limit(&me_response.subscription.usage);
+                return Ok((rate_limit, AppError::from(e)).into_response());
+            }
+            Err(TtsServiceError::Provider(e)) if e.is_retryable() => {
+                return Ok((
+                    [(header::RETRY_AFTER, PROVIDER_RETRY_AFTER_SECS)],
+                    AppError::from(TtsServiceError::Provider(e)),
+                )
+                    .into_response());
+            }
+            Err(e) => return Err(e.into()),
+        };
 
         // Calculate duration in seconds (approximate)
         let duration_seconds = (result.duration_minutes * 60.0) as u64;
 
-        // Get remaining usage
-        let usage = controller
-            .usage_repo
-            .get_today_usage(auth_user.user_id)
+        // Get remaining usage against the user's tier
+        let me_response = controller
+            .user_service
+            .get_user_profile(auth_user.user_id)
             .await?;
-        let characters_used = usage.map(|u| u.characters_used).unwrap_or(0);
-        let character_limit = 20000; // This should come from user's tier, simplified for now
+        let usage = &me_response.subscription.usage;
+        let remaining = usage.characters_remaining();
 
         // Build headers
         let mut headers = HeaderMap::new();
-        headers.insert(header::CONTENT_TYPE, "audio/mpeg".parse().unwrap());
         headers.insert(
             "X-Duration-Seconds",
             duration_seconds.to_string().parse().unwrap(),
@@ -96,42 +147,58 @@ impl TtsController {
             "X-Language-Detected",
             result.language_detected.to_string().parse().unwrap(),
         );
-        headers.insert(
-            "X-Usage-Remaining",
-            (character_limit - characters_used)
-                .to_string()
+        headers.insert("X-Usage-Remaining", remaining.to_string().parse().unwrap());
+        if let Some(warning) = usage.warnings.first() {
+            headers.insert("X-Usage-Warning", warning.kind.as_str().parse().unwrap());
+        }
+
+        // Audio on the CDN is downloaded from there rather than through this instance
+        if let Some(audio_url) = result.audio_url {
+            let location = audio_url
                 .parse()
-                .unwrap(),
-        );
+                .map_err(|_| AppError::Internal("Invalid signed audio URL".to_string()))?;
+            headers.insert(header::LOCATION, location);
+            return Ok(
+                (StatusCode::SEE_OTHER, character_rate_limit(usage), headers).into_response(),
+            );
+        }
 
-        Ok((StatusCode::OK, headers, Body::from(result.audio_data)))
+        headers.insert(header::CONTENT_TYPE, "audio/mpeg".parse().unwrap());
+        Ok((
+            StatusCode::OK,
+            character_rate_limit(usage),
+            headers,
+            Body::from(result.audio_data),
+        )
+            .into_response())
     }
 
     /// GET /api/tts/usage - Get usage statistics
     pub async fn get_usage(
         State(controller): State<Arc<TtsController>>,
         Extension(auth_user): Extension<AuthUser>,
-    ) -> AppResult<Json<UsageResponse>> {
+    ) -> AppResult<(RateLimit, Json<UsageResponse>)> {
         // Get user profile to determine limits
         let me_response = controller
             .user_service
             .get_user_profile(auth_user.user_id)
             .await?;
 
-        // Get today's usage
-        let today_usage = controller
+        // Get usage for the current quota period, in the user's timezone
+        let period = controller
+            .user_service
+            .get_usage_period(auth_user.user_id)
+            .await?;
+        let usage = controller
             .usage_repo
-            .get_today_usage(auth_user.user_id)
+            .get_usage_between(auth_user.user_id, period.start, period.end)
             .await?;
 
-        let (characters_used, articles_count) = if let Some(usage) = &today_usage {
-            (usage.characters_used, usage.articles_synthesized)
-        } else {
-            (0, 0)
-        };
+        let characters_used = usage.characters_used;
+        let articles_count = usage.articles_synthesized;
 
-        // Calculate minutes from characters (1000 chars = 1 minute)
-        let minutes_used = characters_used as f32 / 1000.0;
+        // Listening time of the audio produced, not the character estimate
+        let minutes_used = (usage.audio_seconds / 60.0) as f32;
 
         // Get limits from user profile
         let character_limit = me_response.subscription.usage.characters_limit;
@@ -147,33 +214,125 @@ impl TtsController {
             .map(|r| DailyUsage {
                 date: r.date,
                 characters: r.characters_used,
-                minutes: r.characters_used as f32 / 1000.0, // Calculate minutes from characters
+                minutes: (r.audio_seconds / 60.0) as f32,
             })
             .collect();
 
-        // Calculate reset time (midnight tonight)
-        let now = Utc::now();
-        let tomorrow = now + Duration::days(1);
-        let resets_at = tomorrow
-            .date_naive()
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc();
-
-        Ok(Json(UsageResponse {
-            period: "daily".to_string(),
-            usage: UsageStats {
-                characters: characters_used,
-                minutes: minutes_used,
-                requests: articles_count,
-            },
-            limits: UsageLimits {
-                characters: character_limit,
-                minutes: minute_limit,
-                requests: 999999, // No request limit
-            },
-            resets_at,
-            history: Some(history),
-        }))
+        let rate_limit = character_rate_limit(&me_response.subscription.usage);
+
+        Ok((
+            rate_limit,
+            Json(UsageResponse {
+                period: period.kind.to_string(),
+                usage: UsageStats {
+                    characters: characters_used,
+                    minutes: minutes_used,
+                    requests: articles_count,
+                },
+                limits: UsageLimits {
+                    characters: character_limit,
+                    minutes: minute_limit,
+                    requests: 999999, // No request limit
+                },
+                resets_at: period.resets_at,
+                rollover_characters: me_response.subscription.usage.rollover_characters,
+                warnings: me_response.subscription.usage.warnings,
+                history: Some(history),
+            }),
+        ))
     }
+
+    /// GET /api/tts/usage/export - Download daily usage as CSV
+    pub async fn export_usage(
+        State(controller): State<Arc<TtsController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Query(query): Query<UsageExportQuery>,
+    ) -> AppResult<(StatusCode, HeaderMap, Body)> {
+        if query
+            .format
+            .as_deref()
+            .is_some_and(|format| format != "csv")
+        {
+            return Err(AppError::BadRequest("format must be csv".to_string()));
+        }
+
+        // Days are recorded in the user's timezone, so "today" is theirs too
+        let period = controller
+            .user_service
+            .get_usage_period(auth_user.user_id)
+            .await?;
+        let to = query.to.unwrap_or(period.today);
+        let from = query
+            .from
+            .unwrap_or(to - Duration::days(DEFAULT_EXPORT_DAYS - 1));
+        if from > to {
+            return Err(AppError::BadRequest(
+                "from must not be after to".to_string(),
+            ));
+        }
+        if (to - from).num_days() >= MAX_EXPORT_DAYS {
+            return Err(AppError::BadRequest(format!(
+                "An export can cover at most {} days",
+                MAX_EXPORT_DAYS
+            )));
+        }
+
+        let records = controller
+            .usage_repo
+            .get_usage_days(auth_user.user_id, from, to)
+            .await?;
+        let rows = usage_csv_rows(from, to, &records);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            "text/csv; charset=utf-8".parse().unwrap(),
+        );
+        headers.insert(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"feedtape-usage-{}-to-{}.csv\"",
+                from, to
+            )
+            .parse()
+            .unwrap(),
+        );
+        let body = Body::from_stream(tokio_stream::iter(
+            rows.into_iter().map(Ok::<_, Infallible>),
+        ));
+
+        Ok((StatusCode::OK, headers, body))
+    }
+}
+
+/// The period's character quota, rollover included, as rate-limit headers
+fn character_rate_limit(usage: &UsageDto) -> RateLimit {
+    let limit = usage.characters_limit + usage.rollover_characters.unwrap_or(0);
+    RateLimit::new(
+        i64::from(limit),
+        i64::from(usage.characters_remaining()),
+        usage.resets_at,
+    )
+}
+
+/// Header and one line per day from `from` to `to`, days without usage included as zeros
+fn usage_csv_rows(from: NaiveDate, to: NaiveDate, records: &[UsageRecord]) -> Vec<String> {
+    let mut records = records.iter().peekable();
+    let days = from.iter_days().take_while(|day| *day <= to).map(|day| {
+        let record = records.next_if(|record| record.date == day);
+        let characters = record.map_or(0, |record| record.characters_used);
+        let articles = record.map_or(0, |record| record.articles_synthesized);
+        let audio_seconds = record.map_or(0.0, |record| record.audio_seconds);
+        format!(
+            "{},{},{},{:.2}\n",
+            day,
+            characters,
+            articles,
+            audio_seconds / 60.0
+        )
+    });
+
+    std::iter::once("date,characters,articles,minutes\n".to_string())
+        .chain(days)
+        .collect()
 }
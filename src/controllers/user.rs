@@ -1,48 +1,216 @@
-use axum::{extract::State, http::StatusCode, Extension, Json};
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 
-use crate::domain::user::{MeResponse, UpdateMeRequest};
+use crate::controllers::conditional::{body_hash, conditional_response, to_json};
+use crate::domain::backup::{
+    AccountBackup, BackupService, BackupServiceApi, RestoreQuery, RestoreReport,
+};
+use crate::domain::privacy::{PrivacyService, PrivacyServiceApi};
+use crate::domain::user::{
+    AccountDeletionResponse, AccountEventsQuery, AccountEventsResponse, UpdateMeRequest,
+    UsageHistoryPurgeResponse,
+};
 use crate::{
     domain::user::{UserService, UserServiceApi},
-    error::AppResult,
+    error::{AppError, AppResult},
     infrastructure::auth::AuthUser,
 };
 
 pub struct UserController {
     user_service: Arc<UserService>,
+    backup_service: Arc<BackupService>,
+    privacy_service: Arc<PrivacyService>,
 }
 
 impl UserController {
-    pub fn new(user_service: Arc<UserService>) -> Self {
-        Self { user_service }
+    pub fn new(
+        user_service: Arc<UserService>,
+        backup_service: Arc<BackupService>,
+        privacy_service: Arc<PrivacyService>,
+    ) -> Self {
+        Self {
+            user_service,
+            backup_service,
+            privacy_service,
+        }
     }
 
-    /// GET /api/me - Get current user profile
+    /// GET /api/me - Get current user profile, with its version in the ETag header. Returns
+    /// 304 when If-None-Match has the current ETag.
     pub async fn get_me(
         State(controller): State<Arc<UserController>>,
         Extension(auth_user): Extension<AuthUser>,
-    ) -> AppResult<Json<MeResponse>> {
+        headers: HeaderMap,
+    ) -> AppResult<Response> {
         let response = controller
             .user_service
             .get_user_profile(auth_user.user_id)
             .await?;
-        Ok(Json(response))
+        let bytes = to_json(&response)?;
+        let etag = etag_for(response.updated_at, &bytes);
+        conditional_response(&headers, &etag, bytes)
     }
 
-    /// PATCH /api/me - Update user settings
+    /// PATCH /api/me - Update display name and/or settings and return the updated profile.
+    /// With If-Match, the update is rejected with 412 when the profile changed since that ETag.
     pub async fn update_me(
         State(controller): State<Arc<UserController>>,
         Extension(auth_user): Extension<AuthUser>,
+        headers: HeaderMap,
         Json(request): Json<UpdateMeRequest>,
-    ) -> AppResult<StatusCode> {
-        let settings = request.settings.ok_or_else(|| {
-            crate::error::AppError::BadRequest("Settings are required".to_string())
-        })?;
+    ) -> AppResult<impl IntoResponse> {
+        let expected_version = parse_if_match(&headers)?;
+
+        let response = controller
+            .user_service
+            .update_profile(auth_user.user_id, request, expected_version)
+            .await?;
+        let bytes = to_json(&response)?;
+        let etag = HeaderValue::from_str(&etag_for(response.updated_at, &bytes))
+            .expect("a quoted version and hex digest are a valid header value");
+        Ok((
+            [
+                (
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/json"),
+                ),
+                (header::ETAG, etag),
+            ],
+            bytes,
+        ))
+    }
+
+    /// GET /api/me/events - Account events since the given time
+    pub async fn get_events(
+        State(controller): State<Arc<UserController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Query(query): Query<AccountEventsQuery>,
+    ) -> AppResult<Json<AccountEventsResponse>> {
+        let response = controller
+            .user_service
+            .get_account_events(auth_user.user_id, query)
+            .await?;
+        Ok(Json(response))
+    }
 
+    /// POST /api/me/delete - Schedule the account for deletion
+    pub async fn delete_me(
+        State(controller): State<Arc<UserController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<(StatusCode, Json<AccountDeletionResponse>)> {
+        let response = controller
+            .user_service
+            .schedule_account_deletion(auth_user.user_id)
+            .await?;
+        Ok((StatusCode::ACCEPTED, Json(response)))
+    }
+
+    /// POST /api/me/delete/cancel - Cancel a pending account deletion
+    pub async fn cancel_delete_me(
+        State(controller): State<Arc<UserController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<StatusCode> {
         controller
             .user_service
-            .update_user_settings(auth_user.user_id, settings)
+            .cancel_account_deletion(auth_user.user_id)
             .await?;
         Ok(StatusCode::NO_CONTENT)
     }
+
+    /// DELETE /api/me/usage-history - Delete usage from before the current quota period
+    pub async fn purge_usage_history(
+        State(controller): State<Arc<UserController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<UsageHistoryPurgeResponse>> {
+        let response = controller
+            .user_service
+            .purge_usage_history(auth_user.user_id)
+            .await?;
+        Ok(Json(response))
+    }
+
+    /// GET /api/me/backup - Export feeds, mute rules and settings for POST /api/me/restore
+    pub async fn export_backup(
+        State(controller): State<Arc<UserController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<AccountBackup>> {
+        let backup = controller
+            .backup_service
+            .export_backup(auth_user.user_id)
+            .await?;
+        Ok(Json(backup))
+    }
+
+    /// POST /api/me/restore - Recreate what a backup holds and the account lacks. With
+    /// `dry_run=true` only reports what would change.
+    pub async fn restore_backup(
+        State(controller): State<Arc<UserController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Query(query): Query<RestoreQuery>,
+        Json(backup): Json<AccountBackup>,
+    ) -> AppResult<Json<RestoreReport>> {
+        let report = controller
+            .backup_service
+            .restore_backup(auth_user.user_id, backup, query.dry_run)
+            .await?;
+        Ok(Json(report))
+    }
+
+    /// GET /api/me/export - Download everything stored about the account as JSON
+    pub async fn export_data(
+        State(controller): State<Arc<UserController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Response> {
+        let export = controller
+            .privacy_service
+            .export_user_data(auth_user.user_id)
+            .await?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_static("attachment; filename=\"feedtape-data.json\""),
+        );
+        Ok((headers, Json(export)).into_response())
+    }
+}
+
+/// Strong ETag made of the profile version, which If-Match checks, and a digest of the
+/// whole body, which changes with usage too and lets If-None-Match detect any change
+fn etag_for(updated_at: DateTime<Utc>, body: &[u8]) -> String {
+    format!("\"{}-{}\"", updated_at.timestamp_micros(), body_hash(body))
+}
+
+/// Version the client expects from If-Match; None when absent or `*`
+fn parse_if_match(headers: &HeaderMap) -> AppResult<Option<DateTime<Utc>>> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| AppError::BadRequest("Invalid If-Match header".to_string()))?
+        .trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    let version = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .and_then(|v| v.split('-').next())
+        .and_then(|v| v.parse::<i64>().ok())
+        .and_then(DateTime::from_timestamp_micros);
+    match version {
+        Some(version) => Ok(Some(version)),
+        // Unknown tags can never match the current version
+        None => Err(AppError::PreconditionFailed(
+            "If-Match does not match the current profile version".to_string(),
+        )),
+    }
 }
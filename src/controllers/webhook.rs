@@ -0,0 +1,97 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::shared::PageResponse;
+use crate::domain::webhook::{
+    CreateWebhookRequest, ListWebhookDeliveriesQuery, WebhookDeliveriesResponse, WebhookResponse,
+    WebhooksResponse,
+};
+use crate::{
+    domain::webhook::{WebhookService, WebhookServiceApi},
+    error::AppResult,
+    infrastructure::auth::AuthUser,
+};
+
+pub struct WebhookController {
+    webhook_service: Arc<WebhookService>,
+}
+
+impl WebhookController {
+    pub fn new(webhook_service: Arc<WebhookService>) -> Self {
+        Self { webhook_service }
+    }
+
+    /// GET /api/me/webhooks - List the user's webhooks
+    pub async fn list_webhooks(
+        State(controller): State<Arc<WebhookController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<WebhooksResponse>> {
+        let webhooks = controller
+            .webhook_service
+            .list_webhooks(auth_user.user_id)
+            .await?;
+        Ok(Json(webhooks))
+    }
+
+    /// GET /v1/me/webhooks - List the user's webhooks in the page envelope
+    pub async fn list_webhooks_v1(
+        State(controller): State<Arc<WebhookController>>,
+        Extension(auth_user): Extension<AuthUser>,
+    ) -> AppResult<Json<PageResponse<WebhookResponse>>> {
+        let webhooks = controller
+            .webhook_service
+            .list_webhooks(auth_user.user_id)
+            .await?;
+        Ok(Json(PageResponse::complete(webhooks.webhooks)))
+    }
+
+    /// POST /api/me/webhooks - Register a webhook endpoint
+    pub async fn create_webhook(
+        State(controller): State<Arc<WebhookController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Json(request): Json<CreateWebhookRequest>,
+    ) -> AppResult<(StatusCode, Json<WebhookResponse>)> {
+        let webhook = controller
+            .webhook_service
+            .create_webhook(auth_user.user_id, request)
+            .await?;
+        Ok((StatusCode::CREATED, Json(webhook)))
+    }
+
+    /// DELETE /api/me/webhooks/{webhookId} - Delete a webhook and its delivery log
+    pub async fn delete_webhook(
+        State(controller): State<Arc<WebhookController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(webhook_id): Path<Uuid>,
+    ) -> AppResult<StatusCode> {
+        controller
+            .webhook_service
+            .delete_webhook(auth_user.user_id, webhook_id)
+            .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// GET /api/me/webhooks/{webhookId}/deliveries - Delivery log of a webhook
+    pub async fn list_deliveries(
+        State(controller): State<Arc<WebhookController>>,
+        Extension(auth_user): Extension<AuthUser>,
+        Path(webhook_id): Path<Uuid>,
+        Query(query): Query<ListWebhookDeliveriesQuery>,
+    ) -> AppResult<Json<WebhookDeliveriesResponse>> {
+        let deliveries = controller
+            .webhook_service
+            .list_deliveries(
+                auth_user.user_id,
+                webhook_id,
+                query.limit,
+                query.cursor.as_deref(),
+            )
+            .await?;
+        Ok(Json(deliveries))
+    }
+}
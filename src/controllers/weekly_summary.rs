@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Query, State},
+    response::Html,
+};
+use std::sync::Arc;
+
+use crate::domain::weekly_summary::UnsubscribeQuery;
+use crate::infrastructure::sanitizer::escape_html;
+use crate::{
+    domain::weekly_summary::{WeeklySummaryService, WeeklySummaryServiceApi},
+    error::AppResult,
+};
+
+/// Pages behind the unsubscribe link in weekly summary emails. They're reached from a mail
+/// client, so they're plain HTML and the link's token stands in for signing in.
+pub struct WeeklySummaryController {
+    weekly_summary_service: Arc<WeeklySummaryService>,
+}
+
+impl WeeklySummaryController {
+    pub fn new(weekly_summary_service: Arc<WeeklySummaryService>) -> Self {
+        Self {
+            weekly_summary_service,
+        }
+    }
+
+    /// GET /email/unsubscribe - Ask to confirm, since mail scanners follow links
+    pub async fn confirm_unsubscribe(Query(query): Query<UnsubscribeQuery>) -> Html<String> {
+        Html(page(&format!(
+            "<p>Stop receiving the weekly listening summary?</p>\
+             <form method=\"post\" action=\"/email/unsubscribe?token={}\">\
+             <button type=\"submit\">Unsubscribe</button></form>",
+            escape_html(&query.token)
+        )))
+    }
+
+    /// POST /email/unsubscribe - Turn the weekly summary off; also the one-click target
+    pub async fn unsubscribe(
+        State(controller): State<Arc<WeeklySummaryController>>,
+        Query(query): Query<UnsubscribeQuery>,
+    ) -> AppResult<Html<String>> {
+        controller
+            .weekly_summary_service
+            .unsubscribe(&query.token)
+            .await?;
+        Ok(Html(page(
+            "<p>You won't receive the weekly listening summary anymore. \
+             You can turn it back on in the app's settings.</p>",
+        )))
+    }
+}
+
+fn page(body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>Unsubscribe - FeedTape</title></head>\
+         <body style=\"font-family: sans-serif; max-width: 32rem; margin: 2rem auto\">\
+         <h1>FeedTape</h1>{body}</body></html>"
+    )
+}
@@ -0,0 +1,236 @@
+pub mod error;
+pub mod service;
+
+pub use error::AdminServiceError;
+pub use service::{AdminService, AdminServiceApi};
+
+use crate::domain::audit::{AuditAction, AuditEntry};
+use crate::domain::plan::{LimitOverrides, QuotaPeriod};
+use crate::domain::shared::PageResponse;
+use crate::domain::subscription::PromoCode;
+use crate::domain::user::{SubscriptionStatus, SubscriptionTier, User, UserRole};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Query for GET /admin/users
+#[derive(Debug, Deserialize)]
+pub struct ListUsersQuery {
+    /// Case-insensitive substring of the email or display name
+    pub q: Option<String>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+/// Response for GET /admin/users, newest first
+pub type AdminUserListResponse = PageResponse<AdminUserResponse>;
+
+/// A user as seen by support
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminUserResponse {
+    pub id: Uuid,
+    pub email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub oauth_provider: String,
+    pub subscription_tier: SubscriptionTier,
+    pub subscription_status: SubscriptionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription_expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial_ends_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deletion_scheduled_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_overrides: Option<LimitOverrides>,
+    pub role: UserRole,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<User> for AdminUserResponse {
+    fn from(user: User) -> Self {
+        let overrides = user.limit_overrides();
+        Self {
+            limit_overrides: (!overrides.is_empty()).then_some(overrides),
+            id: user.id,
+            email: user.email,
+            display_name: user.display_name,
+            oauth_provider: user.oauth_provider,
+            subscription_tier: user.subscription_tier,
+            subscription_status: user.subscription_status,
+            subscription_expires_at: user.subscription_expires_at,
+            trial_ends_at: user.trial_ends_at,
+            deletion_scheduled_at: user.deletion_scheduled_at,
+            role: user.role,
+            created_at: user.created_at,
+        }
+    }
+}
+
+/// Response for GET /admin/users/{userId}/usage
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminUserUsageResponse {
+    pub user_id: Uuid,
+    pub period: QuotaPeriod,
+    pub characters_used: i32,
+    pub characters_limit: i32,
+    pub articles_synthesized: i32,
+    /// Listening time produced this period
+    pub audio_seconds: f64,
+    pub resets_at: DateTime<Utc>,
+    /// Most recent days first
+    pub history: Vec<AdminDailyUsage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminDailyUsage {
+    pub date: NaiveDate,
+    pub characters_used: i32,
+    pub articles_synthesized: i32,
+    pub audio_seconds: f64,
+    /// Provider and engine of the day's latest synthesis, when recorded
+    pub provider: Option<String>,
+    pub engine: Option<String>,
+}
+
+/// Request for POST /admin/users/{userId}/grant-pro
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GrantProRequest {
+    /// When the granted Pro access ends; open-ended when omitted
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Request for PUT /admin/users/{userId}/role
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetRoleRequest {
+    pub role: UserRole,
+}
+
+/// Request for POST /admin/promo-codes
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatePromoCodeRequest {
+    /// Code users type in; a random one is generated when omitted
+    #[serde(default)]
+    pub code: Option<String>,
+    /// Days of Pro granted per redemption
+    pub duration_days: i32,
+    /// Total redemptions allowed; single-use when omitted, unlimited when null
+    #[serde(default = "single_use")]
+    pub max_redemptions: Option<i32>,
+    /// Last moment the code can be redeemed; never expires when omitted
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+fn single_use() -> Option<i32> {
+    Some(1)
+}
+
+/// Query for GET /admin/promo-codes
+#[derive(Debug, Deserialize)]
+pub struct ListPromoCodesQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+/// Response for GET /admin/promo-codes, newest first
+pub type PromoCodeListResponse = PageResponse<PromoCodeResponse>;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PromoCodeResponse {
+    pub code: String,
+    pub duration_days: i32,
+    /// Absent for codes without a redemption cap
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_redemptions: Option<i32>,
+    pub redemption_count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PromoCode> for PromoCodeResponse {
+    fn from(promo_code: PromoCode) -> Self {
+        Self {
+            code: promo_code.code,
+            duration_days: promo_code.duration_days,
+            max_redemptions: promo_code.max_redemptions,
+            redemption_count: promo_code.redemption_count,
+            expires_at: promo_code.expires_at,
+            created_by: promo_code.created_by,
+            created_at: promo_code.created_at,
+        }
+    }
+}
+
+/// Query for GET /admin/audit-log
+#[derive(Debug, Deserialize)]
+pub struct ListAuditLogQuery {
+    /// Only changes to this account
+    pub user_id: Option<Uuid>,
+    pub action: Option<AuditAction>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+/// Response for GET /admin/audit-log, newest first
+pub type AuditLogResponse = PageResponse<AuditEntry>;
+
+/// Query for GET /admin/stats
+#[derive(Debug, Deserialize)]
+pub struct AdminStatsQuery {
+    /// Days covered by the activity figures, counting today
+    pub days: Option<i64>,
+}
+
+/// Response for GET /admin/stats
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminStatsResponse {
+    /// First day covered by the activity figures
+    pub since: NaiveDate,
+    pub users: AdminUserStats,
+    /// Most recent days first; days without any synthesis are omitted
+    pub daily: Vec<AdminDailyStats>,
+    /// Characters synthesized per provider, including audio served from the cache
+    pub characters_by_provider: BTreeMap<String, i64>,
+    pub cache: AdminCacheStats,
+    pub feeds: AdminFeedStats,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminUserStats {
+    pub total: i64,
+    pub pro: i64,
+    /// Users who synthesized anything since `since`
+    pub active: i64,
+    pub pending_deletion: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminDailyStats {
+    pub date: NaiveDate,
+    pub active_users: i64,
+    pub characters: i64,
+    /// Listening time of the audio produced that day
+    pub synthesis_minutes: f64,
+    pub articles: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminCacheStats {
+    pub hits: i64,
+    pub requests: i64,
+    /// Share of requests served from the cache; absent when there were none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hit_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminFeedStats {
+    pub total: i64,
+    pub users_with_feeds: i64,
+    pub from_suggestions: i64,
+}
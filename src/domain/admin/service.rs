@@ -0,0 +1,565 @@
+use super::error::AdminServiceError;
+use super::{
+    AdminCacheStats, AdminDailyStats, AdminDailyUsage, AdminFeedStats, AdminStatsQuery,
+    AdminStatsResponse, AdminUserListResponse, AdminUserResponse, AdminUserStats,
+    AdminUserUsageResponse, AuditLogResponse, CreatePromoCodeRequest, GrantProRequest,
+    ListAuditLogQuery, ListPromoCodesQuery, ListUsersQuery, PromoCodeListResponse,
+    PromoCodeResponse, SetRoleRequest,
+};
+use crate::domain::audit::{AuditAction, AuditEvent, AuditService, AuditServiceApi};
+use crate::domain::plan::{LimitOverrides, PlanLimits};
+use crate::domain::shared::code::{generate_code, normalize_code};
+use crate::domain::shared::{Cursor, PageRequest, PageResponse};
+use crate::domain::tts::CACHE_PROVIDER;
+use crate::domain::user::{SubscriptionStatus, SubscriptionTier, User};
+use crate::infrastructure::auth::AuthUserCache;
+use crate::infrastructure::repositories::{
+    FeedRepository, FeedRepositoryApi, PromoCodeRepository, UsageRepository, UsageRepositoryApi,
+    UserRepository, UserRepositoryApi,
+};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+const USAGE_HISTORY_DAYS: i64 = 30;
+const DEFAULT_STATS_DAYS: i64 = 30;
+const MAX_STATS_DAYS: i64 = 90;
+/// Longest Pro grant a single promo code redemption can give
+const MAX_PROMO_DURATION_DAYS: i32 = 366;
+
+pub struct AdminService {
+    user_repo: Arc<UserRepository>,
+    usage_repo: Arc<UsageRepository>,
+    promo_code_repo: Arc<PromoCodeRepository>,
+    plan_limits: Arc<PlanLimits>,
+    audit_service: Arc<AuditService>,
+    feed_repo: Arc<FeedRepository>,
+    user_cache: Arc<AuthUserCache>,
+}
+
+impl AdminService {
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        usage_repo: Arc<UsageRepository>,
+        promo_code_repo: Arc<PromoCodeRepository>,
+        plan_limits: Arc<PlanLimits>,
+        audit_service: Arc<AuditService>,
+        feed_repo: Arc<FeedRepository>,
+        user_cache: Arc<AuthUserCache>,
+    ) -> Self {
+        Self {
+            user_repo,
+            usage_repo,
+            promo_code_repo,
+            plan_limits,
+            audit_service,
+            feed_repo,
+            user_cache,
+        }
+    }
+}
+
+#[async_trait]
+pub trait AdminServiceApi: Send + Sync {
+    async fn list_users(
+        &self,
+        query: ListUsersQuery,
+    ) -> Result<AdminUserListResponse, AdminServiceError>;
+
+    async fn get_user(&self, user_id: Uuid) -> Result<AdminUserResponse, AdminServiceError>;
+
+    /// Usage in the user's current quota period plus recent daily history
+    async fn get_user_usage(
+        &self,
+        user_id: Uuid,
+    ) -> Result<AdminUserUsageResponse, AdminServiceError>;
+
+    /// Give a user Pro without a store purchase, e.g. to settle a billing issue
+    async fn grant_pro(
+        &self,
+        admin_email: &str,
+        user_id: Uuid,
+        request: GrantProRequest,
+    ) -> Result<AdminUserResponse, AdminServiceError>;
+
+    /// Move a user back to the Free tier
+    async fn revoke_pro(
+        &self,
+        admin_email: &str,
+        user_id: Uuid,
+    ) -> Result<AdminUserResponse, AdminServiceError>;
+
+    /// Replace the user's limit overrides; an empty set removes them
+    async fn set_limit_overrides(
+        &self,
+        admin_email: &str,
+        user_id: Uuid,
+        overrides: LimitOverrides,
+    ) -> Result<AdminUserResponse, AdminServiceError>;
+
+    /// Make a user an admin or take it back. Admins can't change their own role, so
+    /// nobody locks themselves out by accident.
+    async fn set_role(
+        &self,
+        admin_email: &str,
+        user_id: Uuid,
+        request: SetRoleRequest,
+    ) -> Result<AdminUserResponse, AdminServiceError>;
+
+    async fn list_promo_codes(
+        &self,
+        query: ListPromoCodesQuery,
+    ) -> Result<PromoCodeListResponse, AdminServiceError>;
+
+    /// Create a code that grants Pro for a number of days
+    async fn create_promo_code(
+        &self,
+        admin_email: &str,
+        request: CreatePromoCodeRequest,
+    ) -> Result<PromoCodeResponse, AdminServiceError>;
+
+    /// Recorded changes, newest first
+    async fn list_audit_log(
+        &self,
+        query: ListAuditLogQuery,
+    ) -> Result<AuditLogResponse, AdminServiceError>;
+
+    /// Service-wide totals and activity over the last days
+    async fn get_stats(
+        &self,
+        query: AdminStatsQuery,
+    ) -> Result<AdminStatsResponse, AdminServiceError>;
+}
+
+#[async_trait]
+impl AdminServiceApi for AdminService {
+    async fn list_users(
+        &self,
+        query: ListUsersQuery,
+    ) -> Result<AdminUserListResponse, AdminServiceError> {
+        let page = page(query.limit, query.cursor.as_deref())?;
+        let search = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+
+        let users = self
+            .user_repo
+            .search(search, page.after.as_ref(), page.fetch_limit())
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+
+        Ok(PageResponse::from_rows(
+            users,
+            &page,
+            |user| Cursor {
+                at: user.created_at,
+                key: user.id,
+            },
+            AdminUserResponse::from,
+        ))
+    }
+
+    async fn get_user(&self, user_id: Uuid) -> Result<AdminUserResponse, AdminServiceError> {
+        let user = self.find_user(user_id).await?;
+        Ok(AdminUserResponse::from(user))
+    }
+
+    async fn get_user_usage(
+        &self,
+        user_id: Uuid,
+    ) -> Result<AdminUserUsageResponse, AdminServiceError> {
+        let user = self.find_user(user_id).await?;
+        let period = user.usage_period(self.plan_limits.quota_period);
+
+        let totals = self
+            .usage_repo
+            .get_usage_between(user_id, period.start, period.end)
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+        let history = self
+            .usage_repo
+            .get_usage_history(user_id, USAGE_HISTORY_DAYS)
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+
+        Ok(AdminUserUsageResponse {
+            user_id,
+            period: period.kind,
+            characters_used: totals.characters_used,
+            characters_limit: self.plan_limits.for_user(&user).characters,
+            articles_synthesized: totals.articles_synthesized,
+            audio_seconds: totals.audio_seconds,
+            resets_at: period.resets_at,
+            history: history
+                .into_iter()
+                .map(|r| AdminDailyUsage {
+                    date: r.date,
+                    characters_used: r.characters_used,
+                    articles_synthesized: r.articles_synthesized,
+                    audio_seconds: r.audio_seconds,
+                    provider: r.provider,
+                    engine: r.engine,
+                })
+                .collect(),
+        })
+    }
+
+    async fn grant_pro(
+        &self,
+        admin_email: &str,
+        user_id: Uuid,
+        request: GrantProRequest,
+    ) -> Result<AdminUserResponse, AdminServiceError> {
+        if let Some(expires_at) = request.expires_at {
+            if expires_at <= Utc::now() {
+                return Err(AdminServiceError::Invalid(
+                    "expires_at must be in the future".to_string(),
+                ));
+            }
+        }
+        self.find_user(user_id).await?;
+
+        let user = self
+            .user_repo
+            .update_subscription(
+                user_id,
+                SubscriptionTier::Pro,
+                SubscriptionStatus::Active,
+                request.expires_at,
+            )
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+        self.user_cache.invalidate(user_id).await;
+
+        tracing::info!(
+            admin = %admin_email,
+            user_id = %user_id,
+            expires_at = ?request.expires_at,
+            "Pro granted manually"
+        );
+        self.audit_service
+            .record(
+                AuditEvent::by_admin(admin_email, AuditAction::ProGranted)
+                    .for_user(user_id)
+                    .details(json!({ "expires_at": request.expires_at })),
+            )
+            .await;
+
+        Ok(AdminUserResponse::from(user))
+    }
+
+    async fn revoke_pro(
+        &self,
+        admin_email: &str,
+        user_id: Uuid,
+    ) -> Result<AdminUserResponse, AdminServiceError> {
+        self.find_user(user_id).await?;
+
+        let user = self
+            .user_repo
+            .update_subscription(
+                user_id,
+                SubscriptionTier::Free,
+                SubscriptionStatus::Active,
+                None,
+            )
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+        self.user_cache.invalidate(user_id).await;
+
+        tracing::info!(admin = %admin_email, user_id = %user_id, "Pro revoked manually");
+        self.audit_service
+            .record(AuditEvent::by_admin(admin_email, AuditAction::ProRevoked).for_user(user_id))
+            .await;
+
+        Ok(AdminUserResponse::from(user))
+    }
+
+    async fn set_limit_overrides(
+        &self,
+        admin_email: &str,
+        user_id: Uuid,
+        overrides: LimitOverrides,
+    ) -> Result<AdminUserResponse, AdminServiceError> {
+        for (name, value) in [
+            ("characters", overrides.characters),
+            ("max_feeds", overrides.max_feeds),
+        ] {
+            if matches!(value, Some(v) if v <= 0) {
+                return Err(AdminServiceError::Invalid(format!(
+                    "{} must be positive",
+                    name
+                )));
+            }
+        }
+        self.find_user(user_id).await?;
+
+        let stored = if overrides.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_value(&overrides).map_err(anyhow::Error::from)?)
+        };
+        let user = self
+            .user_repo
+            .set_limit_overrides(user_id, stored)
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+
+        tracing::info!(
+            admin = %admin_email,
+            user_id = %user_id,
+            overrides = ?overrides,
+            "Limit overrides updated"
+        );
+        self.audit_service
+            .record(
+                AuditEvent::by_admin(admin_email, AuditAction::LimitOverridesChanged)
+                    .for_user(user_id)
+                    .details(json!({ "overrides": overrides })),
+            )
+            .await;
+
+        Ok(AdminUserResponse::from(user))
+    }
+
+    async fn set_role(
+        &self,
+        admin_email: &str,
+        user_id: Uuid,
+        request: SetRoleRequest,
+    ) -> Result<AdminUserResponse, AdminServiceError> {
+        let user = self.find_user(user_id).await?;
+        if user.email.eq_ignore_ascii_case(admin_email) {
+            return Err(AdminServiceError::Invalid(
+                "Admins can't change their own role".to_string(),
+            ));
+        }
+        if user.role == request.role {
+            return Ok(AdminUserResponse::from(user));
+        }
+
+        let user = self
+            .user_repo
+            .set_role(user_id, request.role)
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+        self.user_cache.invalidate(user_id).await;
+
+        tracing::info!(
+            admin = %admin_email,
+            user_id = %user_id,
+            role = ?request.role,
+            "Role changed"
+        );
+        self.audit_service
+            .record(
+                AuditEvent::by_admin(admin_email, AuditAction::RoleChanged)
+                    .for_user(user_id)
+                    .details(json!({ "role": request.role })),
+            )
+            .await;
+
+        Ok(AdminUserResponse::from(user))
+    }
+
+    async fn list_promo_codes(
+        &self,
+        query: ListPromoCodesQuery,
+    ) -> Result<PromoCodeListResponse, AdminServiceError> {
+        let page = page(query.limit, query.cursor.as_deref())?;
+
+        let promo_codes = self
+            .promo_code_repo
+            .list(page.after.as_ref(), page.fetch_limit())
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+
+        Ok(PageResponse::from_rows(
+            promo_codes,
+            &page,
+            |promo_code| Cursor {
+                at: promo_code.created_at,
+                key: promo_code.code.clone(),
+            },
+            PromoCodeResponse::from,
+        ))
+    }
+
+    async fn create_promo_code(
+        &self,
+        admin_email: &str,
+        request: CreatePromoCodeRequest,
+    ) -> Result<PromoCodeResponse, AdminServiceError> {
+        let code = match request.code.as_deref() {
+            Some(code) => normalize_code(code)
+                .ok_or_else(|| AdminServiceError::Invalid("code cannot be empty".to_string()))?,
+            None => generate_code(),
+        };
+        if !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(AdminServiceError::Invalid(
+                "code can only contain letters and digits".to_string(),
+            ));
+        }
+        if !(1..=MAX_PROMO_DURATION_DAYS).contains(&request.duration_days) {
+            return Err(AdminServiceError::Invalid(format!(
+                "duration_days must be between 1 and {}",
+                MAX_PROMO_DURATION_DAYS
+            )));
+        }
+        if matches!(request.max_redemptions, Some(max) if max <= 0) {
+            return Err(AdminServiceError::Invalid(
+                "max_redemptions must be positive".to_string(),
+            ));
+        }
+        if matches!(request.expires_at, Some(at) if at <= Utc::now()) {
+            return Err(AdminServiceError::Invalid(
+                "expires_at must be in the future".to_string(),
+            ));
+        }
+
+        let promo_code = self
+            .promo_code_repo
+            .create(
+                &code,
+                request.duration_days,
+                request.max_redemptions,
+                request.expires_at,
+                admin_email,
+            )
+            .await?;
+
+        tracing::info!(
+            admin = %admin_email,
+            code = %promo_code.code,
+            duration_days = promo_code.duration_days,
+            max_redemptions = ?promo_code.max_redemptions,
+            "Promo code created"
+        );
+        self.audit_service
+            .record(
+                AuditEvent::by_admin(admin_email, AuditAction::PromoCodeCreated)
+                    .target(&promo_code.code)
+                    .details(json!({
+                        "duration_days": promo_code.duration_days,
+                        "max_redemptions": promo_code.max_redemptions,
+                        "expires_at": promo_code.expires_at,
+                    })),
+            )
+            .await;
+
+        Ok(PromoCodeResponse::from(promo_code))
+    }
+
+    async fn list_audit_log(
+        &self,
+        query: ListAuditLogQuery,
+    ) -> Result<AuditLogResponse, AdminServiceError> {
+        let page = page(query.limit, query.cursor.as_deref())?;
+
+        let entries = self
+            .audit_service
+            .list(
+                query.user_id,
+                query.action,
+                page.after.as_ref(),
+                page.fetch_limit(),
+            )
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+
+        Ok(PageResponse::from_rows(
+            entries,
+            &page,
+            |entry| Cursor {
+                at: entry.occurred_at,
+                key: entry.id,
+            },
+            std::convert::identity,
+        ))
+    }
+
+    async fn get_stats(
+        &self,
+        query: AdminStatsQuery,
+    ) -> Result<AdminStatsResponse, AdminServiceError> {
+        let days = query.days.unwrap_or(DEFAULT_STATS_DAYS);
+        if !(1..=MAX_STATS_DAYS).contains(&days) {
+            return Err(AdminServiceError::Invalid(format!(
+                "days must be between 1 and {}",
+                MAX_STATS_DAYS
+            )));
+        }
+        let since = Utc::now().date_naive() - Duration::days(days - 1);
+
+        let (user_counts, active_users, daily, provider_totals, feed_counts) = tokio::try_join!(
+            self.user_repo.count_users(),
+            self.usage_repo.count_active_users(since),
+            self.usage_repo.get_daily_totals(since),
+            self.usage_repo.get_provider_totals(since),
+            self.feed_repo.count_feeds(),
+        )
+        .map_err(|e| AdminServiceError::Dependency(e.to_string()))?;
+
+        let requests: i64 = provider_totals.iter().map(|totals| totals.requests).sum();
+        let hits = provider_totals
+            .iter()
+            .find(|totals| totals.provider == CACHE_PROVIDER)
+            .map_or(0, |totals| totals.requests);
+
+        Ok(AdminStatsResponse {
+            since,
+            users: AdminUserStats {
+                total: user_counts.total,
+                pro: user_counts.pro,
+                active: active_users,
+                pending_deletion: user_counts.pending_deletion,
+            },
+            daily: daily
+                .into_iter()
+                .map(|day| AdminDailyStats {
+                    date: day.date,
+                    active_users: day.active_users,
+                    characters: day.characters_used,
+                    synthesis_minutes: day.audio_seconds / 60.0,
+                    articles: day.articles_synthesized,
+                })
+                .collect(),
+            characters_by_provider: provider_totals
+                .into_iter()
+                .map(|totals| (totals.provider, totals.characters))
+                .collect(),
+            cache: AdminCacheStats {
+                hits,
+                requests,
+                hit_rate: (requests > 0).then(|| hits as f64 / requests as f64),
+            },
+            feeds: AdminFeedStats {
+                total: feed_counts.total,
+                users_with_feeds: feed_counts.users_with_feeds,
+                from_suggestions: feed_counts.from_suggestions,
+            },
+        })
+    }
+}
+
+impl AdminService {
+    async fn find_user(&self, user_id: Uuid) -> Result<User, AdminServiceError> {
+        self.user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| AdminServiceError::Dependency(e.to_string()))?
+            .ok_or(AdminServiceError::NotFound)
+    }
+}
+
+/// Validated page of an admin listing
+fn page<K: FromStr>(
+    limit: Option<i64>,
+    cursor: Option<&str>,
+) -> Result<PageRequest<K>, AdminServiceError> {
+    PageRequest::parse(limit, cursor, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE)
+        .map_err(|e| AdminServiceError::Invalid(e.to_string()))
+}
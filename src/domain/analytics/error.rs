@@ -0,0 +1,21 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AnalyticsServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AnalyticsServiceError> for AppError {
+    fn from(err: AnalyticsServiceError) -> Self {
+        match err {
+            AnalyticsServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            AnalyticsServiceError::Dependency(msg) => AppError::Internal(msg),
+            AnalyticsServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::AnalyticsServiceError;
+pub use model::{AnalyticsEventName, NewAnalyticsEvent};
+pub use service::{AnalyticsService, AnalyticsServiceApi};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Request for POST /api/analytics/events
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngestEventsRequest {
+    pub events: Vec<AnalyticsEventInput>,
+}
+
+/// One event as sent by the app
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnalyticsEventInput {
+    /// e.g. `screen_view`
+    pub name: String,
+    pub occurred_at: DateTime<Utc>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub properties: Map<String, Value>,
+}
+
+/// Outcome of a batch. Sampled-out events count as accepted, so the app doesn't
+/// retry them.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngestEventsResponse {
+    pub accepted: usize,
+    pub rejected: Vec<RejectedEvent>,
+}
+
+/// An event left out of the batch, by its position in the request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RejectedEvent {
+    pub index: usize,
+    pub error: String,
+}
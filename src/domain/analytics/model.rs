@@ -0,0 +1,187 @@
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+/// Events the apps may send. Each has a fixed set of properties, so a client bug can't
+/// fill the table with shapes nobody queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyticsEventName {
+    ScreenView,
+    PlaybackCompleted,
+}
+
+impl AnalyticsEventName {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "screen_view" => Some(Self::ScreenView),
+            "playback_completed" => Some(Self::PlaybackCompleted),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ScreenView => "screen_view",
+            Self::PlaybackCompleted => "playback_completed",
+        }
+    }
+
+    fn properties(&self) -> &'static [PropertySpec] {
+        match self {
+            Self::ScreenView => SCREEN_VIEW_PROPERTIES,
+            Self::PlaybackCompleted => PLAYBACK_COMPLETED_PROPERTIES,
+        }
+    }
+
+    /// Check the properties against the event's schema: required ones present, each of
+    /// the right type, and nothing else
+    pub fn validate_properties(&self, properties: &Map<String, Value>) -> Result<(), String> {
+        let specs = self.properties();
+
+        if let Some(unknown) = properties
+            .keys()
+            .find(|key| !specs.iter().any(|spec| spec.name == key.as_str()))
+        {
+            return Err(format!("unknown property '{}'", unknown));
+        }
+        for spec in specs {
+            match properties.get(spec.name) {
+                Some(value) => spec
+                    .kind
+                    .check(value)
+                    .map_err(|expected| format!("property '{}' must be {}", spec.name, expected))?,
+                None if spec.required => {
+                    return Err(format!("property '{}' is required", spec.name));
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+const SCREEN_VIEW_PROPERTIES: &[PropertySpec] = &[
+    PropertySpec::required("screen", PropertyKind::Text { max_len: 64 }),
+    PropertySpec::optional("previous_screen", PropertyKind::Text { max_len: 64 }),
+];
+
+const PLAYBACK_COMPLETED_PROPERTIES: &[PropertySpec] = &[
+    PropertySpec::required("link", PropertyKind::Text { max_len: 2048 }),
+    PropertySpec::required(
+        "duration_seconds",
+        PropertyKind::Number {
+            min: 0.0,
+            max: 24.0 * 60.0 * 60.0,
+        },
+    ),
+    PropertySpec::optional("feed_id", PropertyKind::Uuid),
+];
+
+struct PropertySpec {
+    name: &'static str,
+    kind: PropertyKind,
+    required: bool,
+}
+
+impl PropertySpec {
+    const fn required(name: &'static str, kind: PropertyKind) -> Self {
+        Self {
+            name,
+            kind,
+            required: true,
+        }
+    }
+
+    const fn optional(name: &'static str, kind: PropertyKind) -> Self {
+        Self {
+            name,
+            kind,
+            required: false,
+        }
+    }
+}
+
+enum PropertyKind {
+    Text { max_len: usize },
+    Number { min: f64, max: f64 },
+    Uuid,
+}
+
+impl PropertyKind {
+    /// Describes what was expected when the value doesn't fit
+    fn check(&self, value: &Value) -> Result<(), String> {
+        match self {
+            PropertyKind::Text { max_len } => match value.as_str() {
+                Some(text) if !text.is_empty() && text.chars().count() <= *max_len => Ok(()),
+                _ => Err(format!("a string of 1 to {} characters", max_len)),
+            },
+            PropertyKind::Number { min, max } => match value.as_f64() {
+                Some(number) if (*min..=*max).contains(&number) => Ok(()),
+                _ => Err(format!("a number between {} and {}", min, max)),
+            },
+            PropertyKind::Uuid => match value.as_str().map(Uuid::parse_str) {
+                Some(Ok(_)) => Ok(()),
+                _ => Err("a UUID".to_string()),
+            },
+        }
+    }
+}
+
+/// A validated event ready to be stored
+#[derive(Debug, Clone)]
+pub struct NewAnalyticsEvent {
+    pub user_id: Uuid,
+    pub name: AnalyticsEventName,
+    pub session_id: Option<String>,
+    pub properties: Map<String, Value>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn properties(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_valid_properties_pass() {
+        let screen_view = properties(json!({ "screen": "library" }));
+        let playback = properties(json!({
+            "link": "https://example.com/post",
+            "duration_seconds": 312.5,
+            "feed_id": Uuid::new_v4().to_string(),
+        }));
+
+        assert!(AnalyticsEventName::ScreenView
+            .validate_properties(&screen_view)
+            .is_ok());
+        assert!(AnalyticsEventName::PlaybackCompleted
+            .validate_properties(&playback)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_schema_violations_are_rejected() {
+        let event = AnalyticsEventName::PlaybackCompleted;
+
+        assert_eq!(
+            event.validate_properties(&properties(json!({ "duration_seconds": 10 }))),
+            Err("property 'link' is required".to_string())
+        );
+        assert_eq!(
+            event.validate_properties(&properties(
+                json!({ "link": "https://example.com", "duration_seconds": "10" })
+            )),
+            Err("property 'duration_seconds' must be a number between 0 and 86400".to_string())
+        );
+        assert_eq!(
+            AnalyticsEventName::ScreenView
+                .validate_properties(&properties(json!({ "screen": "home", "email": "a@b.c" }))),
+            Err("unknown property 'email'".to_string())
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use super::error::AnalyticsServiceError;
+use super::model::{AnalyticsEventName, NewAnalyticsEvent};
+use super::{AnalyticsEventInput, IngestEventsRequest, IngestEventsResponse, RejectedEvent};
+use crate::infrastructure::repositories::AnalyticsEventRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const MAX_BATCH_EVENTS: usize = 100;
+const MAX_SESSION_ID_LENGTH: usize = 64;
+/// Apps queue events while offline; anything older than this is dropped
+const MAX_EVENT_AGE_DAYS: i64 = 7;
+/// How far ahead of the server clock an event may claim to be
+const MAX_CLOCK_SKEW_SECS: i64 = 5 * 60;
+
+pub struct AnalyticsService {
+    analytics_repo: Arc<AnalyticsEventRepository>,
+    /// Share of users whose events are stored, from 0 to 1
+    sample_rate: f64,
+}
+
+impl AnalyticsService {
+    pub fn new(analytics_repo: Arc<AnalyticsEventRepository>, sample_rate: f64) -> Self {
+        Self {
+            analytics_repo,
+            sample_rate,
+        }
+    }
+
+    fn validate_event(
+        &self,
+        user_id: Uuid,
+        event: AnalyticsEventInput,
+        now: DateTime<Utc>,
+    ) -> Result<NewAnalyticsEvent, String> {
+        let name = AnalyticsEventName::parse(&event.name)
+            .ok_or_else(|| format!("unknown event '{}'", event.name))?;
+        if event.occurred_at > now + Duration::seconds(MAX_CLOCK_SKEW_SECS) {
+            return Err("occurred_at is in the future".to_string());
+        }
+        if event.occurred_at < now - Duration::days(MAX_EVENT_AGE_DAYS) {
+            return Err(format!(
+                "occurred_at is more than {} days old",
+                MAX_EVENT_AGE_DAYS
+            ));
+        }
+        if event
+            .session_id
+            .as_ref()
+            .is_some_and(|session_id| session_id.len() > MAX_SESSION_ID_LENGTH)
+        {
+            return Err(format!(
+                "session_id cannot exceed {} characters",
+                MAX_SESSION_ID_LENGTH
+            ));
+        }
+        name.validate_properties(&event.properties)?;
+
+        Ok(NewAnalyticsEvent {
+            user_id,
+            name,
+            session_id: event.session_id,
+            properties: event.properties,
+            occurred_at: event.occurred_at,
+        })
+    }
+}
+
+#[async_trait]
+pub trait AnalyticsServiceApi: Send + Sync {
+    /// Validate a batch of app events and store the valid ones. Invalid events are
+    /// reported back without failing the rest of the batch.
+    async fn ingest_events(
+        &self,
+        user_id: Uuid,
+        request: IngestEventsRequest,
+    ) -> Result<IngestEventsResponse, AnalyticsServiceError>;
+}
+
+#[async_trait]
+impl AnalyticsServiceApi for AnalyticsService {
+    async fn ingest_events(
+        &self,
+        user_id: Uuid,
+        request: IngestEventsRequest,
+    ) -> Result<IngestEventsResponse, AnalyticsServiceError> {
+        if request.events.is_empty() {
+            return Err(AnalyticsServiceError::Invalid(
+                "At least one event is required".to_string(),
+            ));
+        }
+        if request.events.len() > MAX_BATCH_EVENTS {
+            return Err(AnalyticsServiceError::Invalid(format!(
+                "A batch cannot exceed {} events",
+                MAX_BATCH_EVENTS
+            )));
+        }
+
+        let now = Utc::now();
+        let mut events = Vec::with_capacity(request.events.len());
+        let mut rejected = Vec::new();
+        for (index, event) in request.events.into_iter().enumerate() {
+            match self.validate_event(user_id, event, now) {
+                Ok(event) => events.push(event),
+                Err(error) => rejected.push(RejectedEvent { index, error }),
+            }
+        }
+
+        let accepted = events.len();
+        if accepted > 0 && in_sample(user_id, self.sample_rate) {
+            self.analytics_repo
+                .insert_batch(&events, now)
+                .await
+                .map_err(|e| AnalyticsServiceError::Dependency(e.to_string()))?;
+        }
+
+        tracing::debug!(
+            user_id = %user_id,
+            accepted,
+            rejected = rejected.len(),
+            "Analytics events ingested"
+        );
+
+        Ok(IngestEventsResponse { accepted, rejected })
+    }
+}
+
+/// Whether the user's events are kept. Sampling by user rather than by event keeps each
+/// sampled user's sessions complete, and a given user is always in or always out.
+fn in_sample(user_id: Uuid, sample_rate: f64) -> bool {
+    if sample_rate >= 1.0 {
+        return true;
+    }
+    let digest = Sha256::digest(user_id.as_bytes());
+    let bucket = u64::from_be_bytes(digest[..8].try_into().expect("digest has 32 bytes"));
+
+    (bucket as f64 / u64::MAX as f64) < sample_rate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_is_stable_per_user() {
+        let users: Vec<Uuid> = (0..1000).map(|_| Uuid::new_v4()).collect();
+
+        assert!(users.iter().all(|user| in_sample(*user, 1.0)));
+        assert!(!users.iter().any(|user| in_sample(*user, 0.0)));
+
+        let sampled: Vec<bool> = users.iter().map(|user| in_sample(*user, 0.25)).collect();
+        let kept = sampled.iter().filter(|kept| **kept).count();
+        assert!((150..350).contains(&kept), "kept {} of 1000", kept);
+        assert_eq!(
+            sampled,
+            users
+                .iter()
+                .map(|user| in_sample(*user, 0.25))
+                .collect::<Vec<_>>()
+        );
+    }
+}
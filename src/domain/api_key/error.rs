@@ -0,0 +1,34 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeyServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("api key not found")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for ApiKeyServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => ApiKeyServiceError::Invalid(msg),
+            AppError::NotFound(_) => ApiKeyServiceError::NotFound,
+            _ => ApiKeyServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<ApiKeyServiceError> for AppError {
+    fn from(err: ApiKeyServiceError) -> Self {
+        match err {
+            ApiKeyServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            ApiKeyServiceError::NotFound => AppError::NotFound("API key not found".to_string()),
+            ApiKeyServiceError::Dependency(msg) => AppError::Internal(msg),
+            ApiKeyServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::ApiKeyServiceError;
+pub use model::{generate_api_key, hash_api_key, ApiKey, ApiKeyScope, API_KEY_PREFIX};
+pub use service::{ApiKeyService, ApiKeyServiceApi};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Request for POST /api/me/api-keys
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+/// An API key. The key itself is only returned when it is created.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyResponse {
+    pub id: Uuid,
+    pub name: String,
+    /// Start of the key, enough to recognise it
+    pub prefix: String,
+    pub scopes: Vec<ApiKeyScope>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(api_key: ApiKey) -> Self {
+        Self {
+            id: api_key.id,
+            name: api_key.name,
+            prefix: api_key.prefix,
+            scopes: api_key.scopes.0,
+            key: None,
+            created_at: api_key.created_at,
+        }
+    }
+}
+
+/// Response for GET /api/me/api-keys
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeysResponse {
+    pub api_keys: Vec<ApiKeyResponse>,
+}
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{types::Json, FromRow};
+use uuid::Uuid;
+
+/// Every key starts with this, so leaked keys are easy to spot
+pub const API_KEY_PREFIX: &str = "ftk_";
+/// Characters of the key kept in the clear for display
+const DISPLAYED_KEY_LENGTH: usize = 12;
+
+/// A group of routes an API key may call. Routes outside every scope, like account and
+/// key management, only accept access tokens.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Synthesis and usage, under /api/tts
+    Tts,
+    /// Reading and managing feeds, under /api/feeds and /v1/feeds
+    Feeds,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::Tts => "tts",
+            ApiKeyScope::Feeds => "feeds",
+        }
+    }
+
+    /// Scope a key needs to call `path`; None when keys can't call it at all
+    pub fn required_for(path: &str) -> Option<Self> {
+        let under = |prefix: &str| {
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        };
+        if under("/api/tts") {
+            Some(ApiKeyScope::Tts)
+        } else if under("/api/feeds") || under("/v1/feeds") {
+            Some(ApiKeyScope::Feeds)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Json<Vec<ApiKeyScope>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    pub fn allows(&self, scope: ApiKeyScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// A new random key and the part of it shown when listing keys
+pub fn generate_api_key() -> (String, String) {
+    let key = format!(
+        "{}{}{}",
+        API_KEY_PREFIX,
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    );
+    let prefix = key[..DISPLAYED_KEY_LENGTH].to_string();
+    (key, prefix)
+}
+
+/// What is stored and looked up instead of the key
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_for_matches_whole_path_segments() {
+        assert_eq!(
+            ApiKeyScope::required_for("/api/tts/synthesize"),
+            Some(ApiKeyScope::Tts)
+        );
+        assert_eq!(
+            ApiKeyScope::required_for("/api/feeds"),
+            Some(ApiKeyScope::Feeds)
+        );
+        assert_eq!(
+            ApiKeyScope::required_for("/v1/feeds/validate"),
+            Some(ApiKeyScope::Feeds)
+        );
+        assert_eq!(ApiKeyScope::required_for("/api/feeds-export"), None);
+        assert_eq!(ApiKeyScope::required_for("/api/me/api-keys"), None);
+        assert_eq!(ApiKeyScope::required_for("/admin/users"), None);
+    }
+
+    #[test]
+    fn test_generated_keys_are_unique_and_hashed() {
+        let (key, prefix) = generate_api_key();
+        let (other, _) = generate_api_key();
+
+        assert!(key.starts_with(API_KEY_PREFIX));
+        assert!(key.starts_with(&prefix));
+        assert_ne!(key, other);
+        assert_eq!(hash_api_key(&key), hash_api_key(&key));
+        assert_ne!(hash_api_key(&key), hash_api_key(&other));
+        assert!(!hash_api_key(&key).contains(&key));
+    }
+}
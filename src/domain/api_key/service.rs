@@ -0,0 +1,125 @@
+use super::error::ApiKeyServiceError;
+use super::{generate_api_key, hash_api_key, ApiKeyResponse, ApiKeysResponse, CreateApiKeyRequest};
+use crate::infrastructure::repositories::ApiKeyRepository;
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const MAX_API_KEYS_PER_USER: i64 = 10;
+const MAX_NAME_LENGTH: usize = 100;
+
+pub struct ApiKeyService {
+    api_key_repo: Arc<ApiKeyRepository>,
+}
+
+impl ApiKeyService {
+    pub fn new(api_key_repo: Arc<ApiKeyRepository>) -> Self {
+        Self { api_key_repo }
+    }
+}
+
+#[async_trait]
+pub trait ApiKeyServiceApi: Send + Sync {
+    /// Create a key limited to the requested scopes. The response carries the key, which
+    /// is not shown again.
+    async fn create_api_key(
+        &self,
+        user_id: Uuid,
+        request: CreateApiKeyRequest,
+    ) -> Result<ApiKeyResponse, ApiKeyServiceError>;
+
+    async fn list_api_keys(&self, user_id: Uuid) -> Result<ApiKeysResponse, ApiKeyServiceError>;
+
+    async fn delete_api_key(
+        &self,
+        user_id: Uuid,
+        api_key_id: Uuid,
+    ) -> Result<(), ApiKeyServiceError>;
+}
+
+#[async_trait]
+impl ApiKeyServiceApi for ApiKeyService {
+    async fn create_api_key(
+        &self,
+        user_id: Uuid,
+        request: CreateApiKeyRequest,
+    ) -> Result<ApiKeyResponse, ApiKeyServiceError> {
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(ApiKeyServiceError::Invalid(
+                "API key name is required".to_string(),
+            ));
+        }
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return Err(ApiKeyServiceError::Invalid(format!(
+                "API key name must be at most {} characters",
+                MAX_NAME_LENGTH
+            )));
+        }
+
+        let mut scopes = request.scopes;
+        scopes.sort_by_key(|scope| scope.as_str());
+        scopes.dedup();
+        if scopes.is_empty() {
+            return Err(ApiKeyServiceError::Invalid(
+                "At least one scope is required".to_string(),
+            ));
+        }
+
+        let count = self
+            .api_key_repo
+            .count_by_user(user_id)
+            .await
+            .map_err(|e| ApiKeyServiceError::Dependency(e.to_string()))?;
+        if count >= MAX_API_KEYS_PER_USER {
+            return Err(ApiKeyServiceError::Invalid(format!(
+                "A maximum of {} API keys is allowed",
+                MAX_API_KEYS_PER_USER
+            )));
+        }
+
+        let (key, prefix) = generate_api_key();
+        let api_key = self
+            .api_key_repo
+            .create(user_id, name, &prefix, &hash_api_key(&key), &scopes)
+            .await
+            .map_err(|e| ApiKeyServiceError::Dependency(e.to_string()))?;
+
+        tracing::info!(user_id = %user_id, api_key_id = %api_key.id, "API key created");
+
+        let mut response = ApiKeyResponse::from(api_key);
+        response.key = Some(key);
+        Ok(response)
+    }
+
+    async fn list_api_keys(&self, user_id: Uuid) -> Result<ApiKeysResponse, ApiKeyServiceError> {
+        let api_keys = self
+            .api_key_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| ApiKeyServiceError::Dependency(e.to_string()))?;
+
+        Ok(ApiKeysResponse {
+            api_keys: api_keys.into_iter().map(ApiKeyResponse::from).collect(),
+        })
+    }
+
+    async fn delete_api_key(
+        &self,
+        user_id: Uuid,
+        api_key_id: Uuid,
+    ) -> Result<(), ApiKeyServiceError> {
+        let deleted = self
+            .api_key_repo
+            .delete_for_user(api_key_id, user_id)
+            .await
+            .map_err(|e| ApiKeyServiceError::Dependency(e.to_string()))?;
+        if !deleted {
+            return Err(ApiKeyServiceError::NotFound);
+        }
+
+        tracing::info!(user_id = %user_id, api_key_id = %api_key_id, "API key deleted");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod model;
+pub mod service;
+
+pub use model::{AuditAction, AuditActorType, AuditEntry, AuditEvent};
+pub use service::{AuditService, AuditServiceApi};
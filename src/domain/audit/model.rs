@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// Who made an audited change
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditActorType {
+    User,
+    Admin,
+    /// A background job or store notification
+    System,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    FeedCreated,
+    FeedDeleted,
+    AccountCreated,
+    ProfileUpdated,
+    AccountDeletionScheduled,
+    AccountDeletionCancelled,
+    /// Account soft-deleted once its deletion date passed
+    AccountDeleted,
+    /// Soft-deleted account removed for good after the retention period
+    AccountPurged,
+    UsageHistoryPurged,
+    TrialStarted,
+    /// Any entry added to the billing history
+    SubscriptionChanged,
+    ProGranted,
+    ProRevoked,
+    LimitOverridesChanged,
+    RoleChanged,
+    PromoCodeCreated,
+    IpBlocked,
+    IpUnblocked,
+    SuggestionApproved,
+    SuggestionRejected,
+    /// A replaced refresh token came back, so the sessions issued from it were revoked
+    RefreshTokenReused,
+}
+
+/// A change to record, built by the service that made it
+#[derive(Debug, Clone)]
+pub struct AuditEvent {
+    pub actor_type: AuditActorType,
+    /// User id, admin email or the name of the background process
+    pub actor: String,
+    pub action: AuditAction,
+    /// Account the change applies to
+    pub user_id: Option<Uuid>,
+    /// Changed record, e.g. a feed id or promo code
+    pub target: Option<String>,
+    pub details: Value,
+    /// When the change happened, if earlier than when it is recorded
+    pub occurred_at: Option<DateTime<Utc>>,
+    /// Request the change came from, when it is recorded outside of it; otherwise the
+    /// current request's are used
+    pub client_ip: Option<IpAddr>,
+    pub request_id: Option<String>,
+}
+
+impl AuditEvent {
+    /// A user changing their own account
+    pub fn by_user(user_id: Uuid, action: AuditAction) -> Self {
+        Self::new(AuditActorType::User, user_id.to_string(), action).for_user(user_id)
+    }
+
+    pub fn by_admin(admin_email: &str, action: AuditAction) -> Self {
+        Self::new(AuditActorType::Admin, admin_email.to_string(), action)
+    }
+
+    pub fn by_system(source: &str, action: AuditAction) -> Self {
+        Self::new(AuditActorType::System, source.to_string(), action)
+    }
+
+    pub fn for_user(mut self, user_id: Uuid) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    pub fn target(mut self, target: impl ToString) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    pub fn details(mut self, details: Value) -> Self {
+        self.details = details;
+        self
+    }
+
+    pub fn occurred_at(mut self, occurred_at: DateTime<Utc>) -> Self {
+        self.occurred_at = Some(occurred_at);
+        self
+    }
+
+    pub fn requested_from(mut self, client_ip: Option<IpAddr>, request_id: Option<String>) -> Self {
+        self.client_ip = client_ip;
+        self.request_id = request_id;
+        self
+    }
+
+    fn new(actor_type: AuditActorType, actor: String, action: AuditAction) -> Self {
+        Self {
+            actor_type,
+            actor,
+            action,
+            user_id: None,
+            target: None,
+            details: Value::Object(Default::default()),
+            occurred_at: None,
+            client_ip: None,
+            request_id: None,
+        }
+    }
+}
+
+/// A recorded entry of the audit log
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub actor_type: AuditActorType,
+    pub actor: String,
+    pub action: AuditAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub details: Value,
+    /// Address the change was requested from; absent for background jobs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_ip: Option<String>,
+    /// `X-Request-Id` of that request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
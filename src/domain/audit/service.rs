@@ -0,0 +1,68 @@
+use super::model::{AuditAction, AuditEntry, AuditEvent};
+use crate::domain::shared::Cursor;
+use crate::error::AppResult;
+use crate::infrastructure::auth::current_request_id;
+use crate::infrastructure::http::current_client_ip;
+use crate::infrastructure::repositories::AuditLogRepositoryApi;
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct AuditService {
+    audit_log_repo: Arc<dyn AuditLogRepositoryApi>,
+}
+
+impl AuditService {
+    pub fn new(audit_log_repo: Arc<dyn AuditLogRepositoryApi>) -> Self {
+        Self { audit_log_repo }
+    }
+}
+
+#[async_trait]
+pub trait AuditServiceApi: Send + Sync {
+    /// Append an event to the audit log. Services call this once their change is
+    /// committed, so a failure is logged instead of failing the request.
+    async fn record(&self, event: AuditEvent);
+
+    /// Entries newest first, optionally limited to one account or action
+    async fn list(
+        &self,
+        user_id: Option<Uuid>,
+        action: Option<AuditAction>,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<AuditEntry>>;
+}
+
+#[async_trait]
+impl AuditServiceApi for AuditService {
+    async fn record(&self, event: AuditEvent) {
+        let client_ip = event.client_ip.or_else(current_client_ip);
+        let request_id = event.request_id.clone().or_else(current_request_id);
+        if let Err(e) = self
+            .audit_log_repo
+            .append(&event, client_ip, request_id.as_deref())
+            .await
+        {
+            tracing::error!(
+                action = ?event.action,
+                actor = %event.actor,
+                user_id = ?event.user_id,
+                error = %e,
+                "Failed to record audit event"
+            );
+        }
+    }
+
+    async fn list(
+        &self,
+        user_id: Option<Uuid>,
+        action: Option<AuditAction>,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<AuditEntry>> {
+        self.audit_log_repo
+            .list(user_id, action, after, limit)
+            .await
+    }
+}
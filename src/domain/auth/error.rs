@@ -10,6 +10,8 @@ pub enum AuthServiceError {
     Expired,
     #[error("unauthorized: {0}")]
     Unauthorized(String),
+    #[error("session not found")]
+    SessionNotFound,
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -21,7 +23,9 @@ impl From<AppError> for AuthServiceError {
                 AuthServiceError::Invalid("Invalid refresh token".to_string())
             }
             AppError::RefreshTokenExpired => AuthServiceError::Expired,
-            AppError::Unauthorized(msg) => AuthServiceError::Unauthorized(msg),
+            AppError::Unauthorized(msg) | AppError::InvalidCredentials(msg) => {
+                AuthServiceError::Unauthorized(msg)
+            }
             _ => AuthServiceError::Dependency(err.to_string()),
         }
     }
@@ -33,6 +37,9 @@ impl From<AuthServiceError> for AppError {
             AuthServiceError::Invalid(_) => AppError::InvalidRefreshToken,
             AuthServiceError::Expired => AppError::RefreshTokenExpired,
             AuthServiceError::Unauthorized(msg) => AppError::Unauthorized(msg),
+            AuthServiceError::SessionNotFound => {
+                AppError::NotFound("Session not found".to_string())
+            }
             AuthServiceError::Dependency(msg) => AppError::Internal(msg),
             AuthServiceError::Other(e) => AppError::Internal(e.to_string()),
         }
@@ -2,10 +2,18 @@ pub mod error;
 pub mod jwt;
 pub mod service;
 
+use crate::domain::user::SubscriptionTier;
+use crate::infrastructure::repositories::RefreshSession;
+use chrono::{DateTime, Utc};
 pub use error::AuthServiceError;
-pub use jwt::{generate_refresh_token, Claims, JwtManager};
+pub use jwt::{generate_refresh_token, Claims, Jwk, JwkSet, JwtManager, RsaKey};
 use serde::{Deserialize, Serialize};
 pub use service::{AuthService, AuthServiceApi};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// Longest user agent kept for a session; clients can send anything
+const MAX_USER_AGENT_CHARS: usize = 256;
 
 /// Token response for OAuth callbacks
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,3 +28,61 @@ pub struct TokenResponse {
 pub struct RefreshTokenRequest {
     pub refresh_token: String,
 }
+
+/// Who an access token belongs to, for services that receive tokens issued here
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenIntrospection {
+    pub user_id: Uuid,
+    pub email: String,
+    pub tier: SubscriptionTier,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The client a session was signed in or refreshed from
+#[derive(Debug, Clone, Default)]
+pub struct SessionClient {
+    pub user_agent: Option<String>,
+    pub ip: Option<IpAddr>,
+}
+
+impl SessionClient {
+    pub fn new(user_agent: Option<&str>, ip: Option<IpAddr>) -> Self {
+        let user_agent = user_agent
+            .map(str::trim)
+            .filter(|user_agent| !user_agent.is_empty())
+            .map(|user_agent| user_agent.chars().take(MAX_USER_AGENT_CHARS).collect());
+        Self { user_agent, ip }
+    }
+}
+
+/// A signed-in session, as listed in GET /api/me/sessions
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionResponse {
+    pub id: Uuid,
+    /// When the session was signed in
+    pub created_at: DateTime<Utc>,
+    /// When the session last refreshed its access token, if it has yet
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<RefreshSession> for SessionResponse {
+    fn from(session: RefreshSession) -> Self {
+        Self {
+            id: session.id,
+            created_at: session.created_at,
+            last_used_at: session.last_used_at,
+            user_agent: session.user_agent,
+            ip: session.client_ip,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+/// Response for GET /api/me/sessions
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionsResponse {
+    pub sessions: Vec<SessionResponse>,
+}
@@ -1,73 +1,116 @@
 use super::error::AuthServiceError;
-use super::{generate_refresh_token, JwtManager, TokenResponse};
+use super::{
+    generate_refresh_token, JwkSet, JwtManager, SessionClient, SessionResponse, SessionsResponse,
+    TokenIntrospection, TokenResponse,
+};
+use crate::domain::events::{DomainEvent, EventBus};
 use crate::domain::user::User;
-use crate::infrastructure::repositories::{RefreshTokenRepository, UserRepository};
+use crate::infrastructure::auth::AccessTokenDenylist;
+use crate::infrastructure::repositories::{RefreshTokenRepositoryApi, UserRepositoryApi};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct AuthService {
-    user_repo: Arc<UserRepository>,
-    refresh_token_repo: Arc<RefreshTokenRepository>,
-    jwt_secret: String,
-    jwt_expiration_hours: i64,
+    user_repo: Arc<dyn UserRepositoryApi>,
+    refresh_token_repo: Arc<dyn RefreshTokenRepositoryApi>,
+    jwt_manager: Arc<JwtManager>,
     refresh_token_expiration_days: i64,
+    token_denylist: Arc<AccessTokenDenylist>,
+    domain_events: Arc<dyn EventBus>,
 }
 
 impl AuthService {
     pub fn new(
-        user_repo: Arc<UserRepository>,
-        refresh_token_repo: Arc<RefreshTokenRepository>,
-        jwt_secret: String,
-        jwt_expiration_hours: i64,
+        user_repo: Arc<dyn UserRepositoryApi>,
+        refresh_token_repo: Arc<dyn RefreshTokenRepositoryApi>,
+        jwt_manager: Arc<JwtManager>,
         refresh_token_expiration_days: i64,
+        token_denylist: Arc<AccessTokenDenylist>,
+        domain_events: Arc<dyn EventBus>,
     ) -> Self {
         Self {
             user_repo,
             refresh_token_repo,
-            jwt_secret,
-            jwt_expiration_hours,
+            jwt_manager,
             refresh_token_expiration_days,
+            token_denylist,
+            domain_events,
         }
     }
 }
 
 #[async_trait]
 pub trait AuthServiceApi: Send + Sync {
-    async fn refresh_token(&self, refresh_token: &str) -> Result<TokenResponse, AuthServiceError>;
+    async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        client: &SessionClient,
+    ) -> Result<TokenResponse, AuthServiceError>;
 
     async fn logout(&self, refresh_token: &str) -> Result<(), AuthServiceError>;
 
+    /// Revoke every refresh token of the user and deny the access tokens issued so far
     async fn logout_all(&self, user_id: Uuid) -> Result<(), AuthServiceError>;
 
+    /// The user's signed-in sessions, newest first
+    async fn list_sessions(&self, user_id: Uuid) -> Result<SessionsResponse, AuthServiceError>;
+
+    /// Sign one of the user's sessions out. Its access token lasts until it expires, as
+    /// with logging out.
+    async fn revoke_session(&self, user_id: Uuid, session_id: Uuid)
+        -> Result<(), AuthServiceError>;
+
     async fn create_tokens_for_user(
         &self,
         user_id: Uuid,
         email: &str,
+        client: &SessionClient,
     ) -> Result<TokenResponse, AuthServiceError>;
+
+    /// The user behind an access token, or None when the token is invalid, expired or
+    /// belongs to an account that no longer signs in
+    async fn introspect_access_token(
+        &self,
+        access_token: &str,
+    ) -> Result<Option<TokenIntrospection>, AuthServiceError>;
+
+    /// Public keys access tokens can be verified with
+    fn jwks(&self) -> JwkSet;
 }
 
 #[async_trait]
 impl AuthServiceApi for AuthService {
-    async fn refresh_token(&self, refresh_token: &str) -> Result<TokenResponse, AuthServiceError> {
+    async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        client: &SessionClient,
+    ) -> Result<TokenResponse, AuthServiceError> {
         let (user_id, _expires_at) = self.find_valid_refresh_token(refresh_token).await?;
         let user = self.find_user(user_id).await?;
         let access_token = self.generate_access_token(user.id, &user.email)?;
         let new_refresh_token = generate_refresh_token();
 
-        self.refresh_token_repo
-            .revoke(refresh_token)
+        let rotated = self
+            .refresh_token_repo
+            .rotate(
+                refresh_token,
+                &new_refresh_token,
+                self.refresh_token_expiration_days,
+                client,
+            )
             .await
             .map_err(|e| AuthServiceError::Dependency(e.to_string()))?;
-
-        self.store_refresh_token(user.id, &new_refresh_token)
-            .await?;
+        if !rotated {
+            // Another request refreshed with the same token since it was looked up
+            return Err(self.reject_reused_token(user.id, refresh_token).await);
+        }
 
         Ok(TokenResponse {
             token: access_token,
             refresh_token: new_refresh_token,
-            expires_in: self.jwt_expiration_hours * 3600,
+            expires_in: self.jwt_manager.expiration_hours() * 3600,
         })
     }
 
@@ -82,25 +125,100 @@ impl AuthServiceApi for AuthService {
         self.refresh_token_repo
             .revoke_all_for_user(user_id)
             .await
-            .map_err(|e| AuthServiceError::Dependency(e.to_string()))
+            .map_err(|e| AuthServiceError::Dependency(e.to_string()))?;
+        self.token_denylist
+            .revoke_issued_until(user_id, Utc::now())
+            .await;
+        Ok(())
+    }
+
+    async fn list_sessions(&self, user_id: Uuid) -> Result<SessionsResponse, AuthServiceError> {
+        let sessions = self
+            .refresh_token_repo
+            .find_active_by_user(user_id)
+            .await
+            .map_err(|e| AuthServiceError::Dependency(e.to_string()))?;
+
+        Ok(SessionsResponse {
+            sessions: sessions.into_iter().map(SessionResponse::from).collect(),
+        })
+    }
+
+    async fn revoke_session(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<(), AuthServiceError> {
+        let revoked = self
+            .refresh_token_repo
+            .revoke_by_id(user_id, session_id)
+            .await
+            .map_err(|e| AuthServiceError::Dependency(e.to_string()))?;
+        if !revoked {
+            return Err(AuthServiceError::SessionNotFound);
+        }
+
+        tracing::info!(user_id = %user_id, session_id = %session_id, "Session revoked");
+
+        Ok(())
     }
 
     async fn create_tokens_for_user(
         &self,
         user_id: Uuid,
         email: &str,
+        client: &SessionClient,
     ) -> Result<TokenResponse, AuthServiceError> {
+        // Rejects accounts past their deletion date before any session is issued
+        self.find_user(user_id).await?;
+
         let access_token = self.generate_access_token(user_id, email)?;
         let refresh_token = generate_refresh_token();
 
-        self.store_refresh_token(user_id, &refresh_token).await?;
+        self.store_refresh_token(user_id, &refresh_token, client)
+            .await?;
 
         Ok(TokenResponse {
             token: access_token,
             refresh_token,
-            expires_in: self.jwt_expiration_hours * 3600,
+            expires_in: self.jwt_manager.expiration_hours() * 3600,
         })
     }
+
+    async fn introspect_access_token(
+        &self,
+        access_token: &str,
+    ) -> Result<Option<TokenIntrospection>, AuthServiceError> {
+        let Ok(claims) = self.jwt_manager.validate_token(access_token) else {
+            return Ok(None);
+        };
+        let (Ok(user_id), Some(expires_at)) = (
+            Uuid::parse_str(&claims.sub),
+            DateTime::from_timestamp(claims.exp, 0),
+        ) else {
+            return Ok(None);
+        };
+        if self.token_denylist.is_revoked(user_id, claims.iat).await {
+            return Ok(None);
+        }
+
+        let user = match self.find_user(user_id).await {
+            Ok(user) => user,
+            Err(AuthServiceError::Unauthorized(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Some(TokenIntrospection {
+            user_id,
+            email: user.email.clone(),
+            tier: user.effective_tier(),
+            expires_at,
+        }))
+    }
+
+    fn jwks(&self) -> JwkSet {
+        self.jwt_manager.jwks()
+    }
 }
 
 impl AuthService {
@@ -108,15 +226,18 @@ impl AuthService {
         &self,
         refresh_token: &str,
     ) -> Result<(Uuid, DateTime<Utc>), AuthServiceError> {
-        if let Some((revoked, is_expired)) = self
+        if let Some((user_id, revoked, is_expired)) = self
             .refresh_token_repo
             .check_token_status(refresh_token)
             .await
             .map_err(|e| AuthServiceError::Dependency(e.to_string()))?
         {
-            if revoked || is_expired {
+            if is_expired {
                 return Err(AuthServiceError::Expired);
             }
+            if revoked {
+                return Err(self.reject_reused_token(user_id, refresh_token).await);
+            }
         } else {
             return Err(AuthServiceError::Invalid("Token not found".to_string()));
         }
@@ -128,12 +249,53 @@ impl AuthService {
             .ok_or_else(|| AuthServiceError::Invalid("Token not valid".to_string()))
     }
 
+    /// A replaced token presented again was copied at some point, and the copy can't be told
+    /// apart from the original. Revoke whatever was issued in its place, so both holders
+    /// have to sign in again. Tokens revoked by logging out have nothing live below them
+    /// and are just rejected.
+    async fn reject_reused_token(&self, user_id: Uuid, refresh_token: &str) -> AuthServiceError {
+        let revoked_tokens = match self
+            .refresh_token_repo
+            .revoke_descendants(refresh_token)
+            .await
+        {
+            Ok(count) => count,
+            Err(e) => return AuthServiceError::Dependency(e.to_string()),
+        };
+        if revoked_tokens == 0 {
+            return AuthServiceError::Expired;
+        }
+
+        tracing::warn!(
+            user_id = %user_id,
+            revoked_tokens,
+            "Revoked refresh token reused, revoking the tokens issued from it"
+        );
+        self.domain_events
+            .publish(DomainEvent::RefreshTokenReused {
+                user_id,
+                revoked_tokens,
+            })
+            .await;
+
+        AuthServiceError::Expired
+    }
+
     async fn find_user(&self, user_id: Uuid) -> Result<User, AuthServiceError> {
-        self.user_repo
+        let user = self
+            .user_repo
             .find_by_id(user_id)
             .await
             .map_err(|e| AuthServiceError::Dependency(e.to_string()))?
-            .ok_or_else(|| AuthServiceError::Unauthorized("User not found".to_string()))
+            .ok_or_else(|| AuthServiceError::Unauthorized("User not found".to_string()))?;
+
+        if user.is_deleted() {
+            return Err(AuthServiceError::Unauthorized(
+                "Account has been deleted".to_string(),
+            ));
+        }
+
+        Ok(user)
     }
 
     fn generate_access_token(
@@ -141,20 +303,214 @@ impl AuthService {
         user_id: Uuid,
         email: &str,
     ) -> Result<String, AuthServiceError> {
-        let jwt_manager = JwtManager::new(self.jwt_secret.clone(), self.jwt_expiration_hours);
-        jwt_manager
+        self.jwt_manager
             .generate_token(user_id, email)
-            .map_err(|e| AuthServiceError::from(e))
+            .map_err(AuthServiceError::from)
     }
 
     async fn store_refresh_token(
         &self,
         user_id: Uuid,
         token: &str,
+        client: &SessionClient,
     ) -> Result<(), AuthServiceError> {
         self.refresh_token_repo
-            .create(user_id, token, self.refresh_token_expiration_days)
+            .create(user_id, token, self.refresh_token_expiration_days, client)
             .await
             .map_err(|e| AuthServiceError::Dependency(e.to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::InProcessEventBus;
+    use crate::infrastructure::cache::MemoryCacheStore;
+    use crate::infrastructure::repositories::{
+        InMemoryRefreshTokenRepository, InMemoryUserRepository,
+    };
+    use std::time::Duration;
+
+    const UNKNOWN_CLIENT: SessionClient = SessionClient {
+        user_agent: None,
+        ip: None,
+    };
+
+    async fn service_with_user() -> (AuthService, Uuid) {
+        let user_repo = Arc::new(InMemoryUserRepository::new());
+        let user = user_repo
+            .create("reader@example.com", "google", "g-1", None, None)
+            .await
+            .unwrap();
+        let service = AuthService::new(
+            user_repo,
+            Arc::new(InMemoryRefreshTokenRepository::new()),
+            Arc::new(JwtManager::new("test-secret".to_string(), 1)),
+            30,
+            Arc::new(AccessTokenDenylist::new(
+                Arc::new(MemoryCacheStore::new()),
+                Duration::from_secs(3600),
+            )),
+            Arc::new(InProcessEventBus::new(vec![])),
+        );
+
+        (service, user.id)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_the_refresh_token() {
+        let (service, user_id) = service_with_user().await;
+        let issued = service
+            .create_tokens_for_user(user_id, "reader@example.com", &UNKNOWN_CLIENT)
+            .await
+            .unwrap();
+
+        let refreshed = service
+            .refresh_token(&issued.refresh_token, &UNKNOWN_CLIENT)
+            .await
+            .unwrap();
+        let reused = service
+            .refresh_token(&issued.refresh_token, &UNKNOWN_CLIENT)
+            .await;
+
+        assert_ne!(refreshed.refresh_token, issued.refresh_token);
+        assert!(matches!(reused, Err(AuthServiceError::Expired)));
+    }
+
+    #[tokio::test]
+    async fn test_reusing_a_replaced_token_revokes_its_successors() {
+        let (service, user_id) = service_with_user().await;
+        let issued = service
+            .create_tokens_for_user(user_id, "reader@example.com", &UNKNOWN_CLIENT)
+            .await
+            .unwrap();
+        let refreshed = service
+            .refresh_token(&issued.refresh_token, &UNKNOWN_CLIENT)
+            .await
+            .unwrap();
+        let latest = service
+            .refresh_token(&refreshed.refresh_token, &UNKNOWN_CLIENT)
+            .await
+            .unwrap();
+        let other_session = service
+            .create_tokens_for_user(user_id, "reader@example.com", &UNKNOWN_CLIENT)
+            .await
+            .unwrap();
+
+        let reused = service
+            .refresh_token(&issued.refresh_token, &UNKNOWN_CLIENT)
+            .await;
+
+        assert!(matches!(reused, Err(AuthServiceError::Expired)));
+        assert!(matches!(
+            service
+                .refresh_token(&latest.refresh_token, &UNKNOWN_CLIENT)
+                .await,
+            Err(AuthServiceError::Expired)
+        ));
+        assert!(service
+            .refresh_token(&other_session.refresh_token, &UNKNOWN_CLIENT)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_logout_all_revokes_every_session() {
+        let (service, user_id) = service_with_user().await;
+        let first = service
+            .create_tokens_for_user(user_id, "reader@example.com", &UNKNOWN_CLIENT)
+            .await
+            .unwrap();
+        let second = service
+            .create_tokens_for_user(user_id, "reader@example.com", &UNKNOWN_CLIENT)
+            .await
+            .unwrap();
+
+        service.logout_all(user_id).await.unwrap();
+
+        for tokens in [first, second] {
+            let result = service
+                .refresh_token(&tokens.refresh_token, &UNKNOWN_CLIENT)
+                .await;
+            assert!(matches!(result, Err(AuthServiceError::Expired)));
+            let introspection = service.introspect_access_token(&tokens.token).await;
+            assert_eq!(introspection.unwrap(), None);
+        }
+        assert!(matches!(
+            service.refresh_token("never-issued", &UNKNOWN_CLIENT).await,
+            Err(AuthServiceError::Invalid(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sessions_carry_on_across_refreshes() {
+        let (service, user_id) = service_with_user().await;
+        let phone = SessionClient::new(Some("FeedTape/2.1 iOS"), "198.51.100.7".parse().ok());
+        let issued = service
+            .create_tokens_for_user(user_id, "reader@example.com", &phone)
+            .await
+            .unwrap();
+        let signed_in = service.list_sessions(user_id).await.unwrap().sessions;
+        let moved = SessionClient::new(None, "203.0.113.9".parse().ok());
+        let refreshed = service
+            .refresh_token(&issued.refresh_token, &moved)
+            .await
+            .unwrap();
+
+        let sessions = service.list_sessions(user_id).await.unwrap().sessions;
+
+        assert_eq!(sessions.len(), 1);
+        assert_ne!(sessions[0].id, signed_in[0].id);
+        assert_eq!(sessions[0].created_at, signed_in[0].created_at);
+        assert!(signed_in[0].last_used_at.is_none());
+        assert!(sessions[0].last_used_at.is_some());
+        assert_eq!(sessions[0].user_agent.as_deref(), Some("FeedTape/2.1 iOS"));
+        assert_eq!(sessions[0].ip.as_deref(), Some("203.0.113.9"));
+
+        service
+            .revoke_session(user_id, sessions[0].id)
+            .await
+            .unwrap();
+        assert!(matches!(
+            service
+                .refresh_token(&refreshed.refresh_token, &UNKNOWN_CLIENT)
+                .await,
+            Err(AuthServiceError::Expired)
+        ));
+        assert!(matches!(
+            service.revoke_session(user_id, sessions[0].id).await,
+            Err(AuthServiceError::SessionNotFound)
+        ));
+        assert!(service
+            .list_sessions(user_id)
+            .await
+            .unwrap()
+            .sessions
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_introspect_access_token() {
+        let (service, user_id) = service_with_user().await;
+        let issued = service
+            .create_tokens_for_user(user_id, "reader@example.com", &UNKNOWN_CLIENT)
+            .await
+            .unwrap();
+
+        let introspection = service
+            .introspect_access_token(&issued.token)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(introspection.user_id, user_id);
+        assert_eq!(introspection.email, "reader@example.com");
+        assert!(introspection.expires_at > Utc::now());
+
+        let other_secret = JwtManager::new("other-secret".to_string(), 1)
+            .generate_token(user_id, "reader@example.com")
+            .unwrap();
+        for token in ["not-a-jwt", other_secret.as_str()] {
+            assert_eq!(service.introspect_access_token(token).await.unwrap(), None);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackupServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("user not found")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for BackupServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => BackupServiceError::Invalid(msg),
+            AppError::NotFound(_) => BackupServiceError::NotFound,
+            _ => BackupServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<BackupServiceError> for AppError {
+    fn from(err: BackupServiceError) -> Self {
+        match err {
+            BackupServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            BackupServiceError::NotFound => AppError::NotFound("User not found".to_string()),
+            BackupServiceError::Dependency(msg) => AppError::Internal(msg),
+            BackupServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::BackupServiceError;
+pub use model::{AccountBackup, BackupFeed, BackupMuteRule, BACKUP_VERSION};
+pub use service::{BackupService, BackupServiceApi};
+
+use serde::{Deserialize, Serialize};
+
+/// Query for POST /api/me/restore
+#[derive(Debug, Default, Deserialize)]
+pub struct RestoreQuery {
+    /// Report what the restore would do without changing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Response for POST /api/me/restore
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RestoreReport {
+    pub dry_run: bool,
+    /// Whether the display name or settings were (or, in a dry run, would be) changed
+    pub settings_changed: bool,
+    pub feeds: RestoreCounts,
+    pub mute_rules: RestoreCounts,
+    /// Items that could not be restored; the rest of the backup is still applied
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<RestoreFailure>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RestoreCounts {
+    pub created: usize,
+    /// Already on the account, so left alone
+    pub existing: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestoreFailure {
+    pub kind: RestoreItemKind,
+    /// Feed URL or mute rule pattern; absent for settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreItemKind {
+    Settings,
+    Feed,
+    MuteRule,
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::mute::MuteRuleKind;
+use crate::domain::user::UserSettingsDto;
+
+/// Format version written into exports; restores refuse any other
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Everything needed to recreate an account's setup on another instance. Ids are left
+/// out, since they don't carry over: feeds are matched by URL and mute rules point at
+/// their feed through its URL.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountBackup {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub settings: UserSettingsDto,
+    #[serde(default)]
+    pub feeds: Vec<BackupFeed>,
+    #[serde(default)]
+    pub mute_rules: Vec<BackupMuteRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupFeed {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Dropped on restore when the instance doesn't list this suggestion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupMuteRule {
+    pub pattern: String,
+    pub kind: MuteRuleKind,
+    /// URL of the feed the rule is limited to; applies to every feed when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feed_url: Option<String>,
+}
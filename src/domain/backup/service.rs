@@ -0,0 +1,509 @@
+use super::error::BackupServiceError;
+use super::{
+    AccountBackup, BackupFeed, BackupMuteRule, RestoreCounts, RestoreFailure, RestoreItemKind,
+    RestoreReport, BACKUP_VERSION,
+};
+use crate::domain::feed::{CreateFeedRequest, FeedService, FeedServiceApi};
+use crate::domain::feed_suggestions::FeedSuggestionsRepository;
+use crate::domain::mute::{CreateMuteRuleRequest, MuteService, MuteServiceApi};
+use crate::domain::user::{
+    MeResponse, UpdateMeRequest, UpdateNotificationsDto, UpdatePlaybackDto, UpdateSettingsDto,
+    UserService, UserServiceApi, UserSettingsDto,
+};
+use crate::error::AppError;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const MAX_BACKUP_FEEDS: usize = 1000;
+const MAX_BACKUP_MUTE_RULES: usize = 500;
+
+/// A mute rule as the backup identifies it: pattern, kind and feed URL
+type MuteRuleKey = (String, String, Option<String>);
+
+pub struct BackupService {
+    user_service: Arc<UserService>,
+    feed_service: Arc<FeedService>,
+    mute_service: Arc<MuteService>,
+    feed_suggestions_repo: Arc<dyn FeedSuggestionsRepository>,
+}
+
+impl BackupService {
+    pub fn new(
+        user_service: Arc<UserService>,
+        feed_service: Arc<FeedService>,
+        mute_service: Arc<MuteService>,
+        feed_suggestions_repo: Arc<dyn FeedSuggestionsRepository>,
+    ) -> Self {
+        Self {
+            user_service,
+            feed_service,
+            mute_service,
+            feed_suggestions_repo,
+        }
+    }
+}
+
+#[async_trait]
+pub trait BackupServiceApi: Send + Sync {
+    async fn export_backup(&self, user_id: Uuid) -> Result<AccountBackup, BackupServiceError>;
+
+    /// Recreate what the backup holds and the account lacks. Restoring the same backup
+    /// twice changes nothing the second time.
+    async fn restore_backup(
+        &self,
+        user_id: Uuid,
+        backup: AccountBackup,
+        dry_run: bool,
+    ) -> Result<RestoreReport, BackupServiceError>;
+}
+
+#[async_trait]
+impl BackupServiceApi for BackupService {
+    async fn export_backup(&self, user_id: Uuid) -> Result<AccountBackup, BackupServiceError> {
+        let profile = self
+            .user_service
+            .get_user_profile(user_id)
+            .await
+            .map_err(service_error)?;
+        let feeds = self
+            .feed_service
+            .get_user_feeds(user_id)
+            .await
+            .map_err(service_error)?;
+        let rules = self
+            .mute_service
+            .list_rules(user_id)
+            .await
+            .map_err(service_error)?;
+
+        let feed_urls: HashMap<Uuid, String> = feeds
+            .iter()
+            .map(|feed| (feed.id, feed.url.clone()))
+            .collect();
+        let mute_rules = rules
+            .into_iter()
+            .filter_map(|rule| {
+                let feed_url = match rule.feed_id {
+                    Some(feed_id) => Some(feed_urls.get(&feed_id)?.clone()),
+                    None => None,
+                };
+                Some(BackupMuteRule {
+                    pattern: rule.pattern,
+                    kind: rule.kind,
+                    feed_url,
+                })
+            })
+            .collect();
+
+        Ok(AccountBackup {
+            version: BACKUP_VERSION,
+            exported_at: Utc::now(),
+            display_name: profile.display_name,
+            settings: profile.settings,
+            feeds: feeds
+                .into_iter()
+                .map(|feed| BackupFeed {
+                    url: feed.url,
+                    title: feed.title,
+                    suggestion_id: feed.suggestion_id,
+                })
+                .collect(),
+            mute_rules,
+        })
+    }
+
+    async fn restore_backup(
+        &self,
+        user_id: Uuid,
+        backup: AccountBackup,
+        dry_run: bool,
+    ) -> Result<RestoreReport, BackupServiceError> {
+        Self::validate_backup(&backup)?;
+
+        let mut report = RestoreReport {
+            dry_run,
+            ..Default::default()
+        };
+
+        self.restore_profile(user_id, &backup, dry_run, &mut report)
+            .await?;
+        let restored_urls = self
+            .restore_feeds(user_id, backup.feeds, dry_run, &mut report)
+            .await?;
+        self.restore_mute_rules(
+            user_id,
+            backup.mute_rules,
+            &restored_urls,
+            dry_run,
+            &mut report,
+        )
+        .await?;
+
+        Ok(report)
+    }
+}
+
+impl BackupService {
+    fn validate_backup(backup: &AccountBackup) -> Result<(), BackupServiceError> {
+        if backup.version != BACKUP_VERSION {
+            return Err(BackupServiceError::Invalid(format!(
+                "Unsupported backup version: {}",
+                backup.version
+            )));
+        }
+        if backup.feeds.len() > MAX_BACKUP_FEEDS {
+            return Err(BackupServiceError::Invalid(format!(
+                "A backup can hold at most {} feeds",
+                MAX_BACKUP_FEEDS
+            )));
+        }
+        if backup.mute_rules.len() > MAX_BACKUP_MUTE_RULES {
+            return Err(BackupServiceError::Invalid(format!(
+                "A backup can hold at most {} mute rules",
+                MAX_BACKUP_MUTE_RULES
+            )));
+        }
+        Ok(())
+    }
+
+    async fn restore_profile(
+        &self,
+        user_id: Uuid,
+        backup: &AccountBackup,
+        dry_run: bool,
+        report: &mut RestoreReport,
+    ) -> Result<(), BackupServiceError> {
+        let current = self
+            .user_service
+            .get_user_profile(user_id)
+            .await
+            .map_err(service_error)?;
+        if Self::profile_matches(backup, &current) {
+            return Ok(());
+        }
+
+        report.settings_changed = true;
+        if dry_run {
+            return Ok(());
+        }
+
+        // The main voice can speak another language than the one the account reads in,
+        // which an update carrying both refuses, so it goes first and on its own. The
+        // per-language voices set after it then end up exactly as in the backup.
+        let mut result = Ok(());
+        if current.settings.voice != backup.settings.voice {
+            let request = UpdateMeRequest {
+                display_name: None,
+                settings: Some(UpdateSettingsDto {
+                    voice: Some(backup.settings.voice.clone()),
+                    ..Default::default()
+                }),
+            };
+            result = self
+                .user_service
+                .update_profile(user_id, request, None)
+                .await
+                .map(|_| ());
+        }
+        if result.is_ok() {
+            let request = UpdateMeRequest {
+                display_name: backup.display_name.clone(),
+                settings: Some(Self::settings_update(&backup.settings, &current.settings)),
+            };
+            result = self
+                .user_service
+                .update_profile(user_id, request, None)
+                .await
+                .map(|_| ());
+        }
+
+        if let Err(e) = result {
+            report.failures.push(RestoreFailure {
+                kind: RestoreItemKind::Settings,
+                item: None,
+                reason: AppError::from(e).to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Create the backup's feeds the account doesn't follow yet. Returns the URLs of all
+    /// the account's feeds once restored, or in a dry run once they would be.
+    async fn restore_feeds(
+        &self,
+        user_id: Uuid,
+        feeds: Vec<BackupFeed>,
+        dry_run: bool,
+        report: &mut RestoreReport,
+    ) -> Result<HashSet<String>, BackupServiceError> {
+        let mut urls: HashSet<String> = self
+            .feed_service
+            .get_user_feeds(user_id)
+            .await
+            .map_err(service_error)?
+            .into_iter()
+            .map(|feed| feed.url)
+            .collect();
+
+        for feed in feeds {
+            if urls.contains(&feed.url) {
+                report.feeds.existing += 1;
+                continue;
+            }
+            if dry_run {
+                report.feeds.created += 1;
+                urls.insert(feed.url);
+                continue;
+            }
+
+            // Suggestions differ between instances; the feed itself still carries over
+            let suggestion_id = feed
+                .suggestion_id
+                .filter(|id| self.feed_suggestions_repo.find_suggestion(id).is_some());
+            let request = CreateFeedRequest {
+                id: Uuid::new_v4(),
+                url: feed.url.clone(),
+                title: feed.title.unwrap_or_default(),
+                suggestion_id,
+            };
+            match self.feed_service.create_feed(user_id, request).await {
+                Ok(()) => {
+                    report.feeds.created += 1;
+                    urls.insert(feed.url);
+                }
+                Err(e) => Self::record_failure(
+                    &mut report.feeds,
+                    &mut report.failures,
+                    RestoreItemKind::Feed,
+                    feed.url,
+                    AppError::from(e),
+                ),
+            }
+        }
+
+        Ok(urls)
+    }
+
+    async fn restore_mute_rules(
+        &self,
+        user_id: Uuid,
+        rules: Vec<BackupMuteRule>,
+        restored_urls: &HashSet<String>,
+        dry_run: bool,
+        report: &mut RestoreReport,
+    ) -> Result<(), BackupServiceError> {
+        // Feeds created above have ids only once they exist, so look them up afresh
+        let feed_ids: HashMap<String, Uuid> = self
+            .feed_service
+            .get_user_feeds(user_id)
+            .await
+            .map_err(service_error)?
+            .into_iter()
+            .map(|feed| (feed.url, feed.id))
+            .collect();
+        let feed_urls: HashMap<Uuid, &String> =
+            feed_ids.iter().map(|(url, id)| (*id, url)).collect();
+
+        let mut existing: HashSet<MuteRuleKey> = self
+            .mute_service
+            .list_rules(user_id)
+            .await
+            .map_err(service_error)?
+            .into_iter()
+            .map(|rule| {
+                let feed_url = rule
+                    .feed_id
+                    .and_then(|feed_id| feed_urls.get(&feed_id).map(|url| url.to_string()));
+                (rule.pattern, rule.kind.to_string(), feed_url)
+            })
+            .collect();
+
+        for rule in rules {
+            let pattern = rule.pattern.trim().to_string();
+            let key = (
+                pattern.clone(),
+                rule.kind.to_string(),
+                rule.feed_url.clone(),
+            );
+            if existing.contains(&key) {
+                report.mute_rules.existing += 1;
+                continue;
+            }
+
+            let feed_id = match &rule.feed_url {
+                None => None,
+                Some(url) if !restored_urls.contains(url) => {
+                    Self::record_failure(
+                        &mut report.mute_rules,
+                        &mut report.failures,
+                        RestoreItemKind::MuteRule,
+                        pattern,
+                        AppError::NotFound(format!("Feed {} was not restored", url)),
+                    );
+                    continue;
+                }
+                Some(url) => feed_ids.get(url).copied(),
+            };
+            if dry_run {
+                report.mute_rules.created += 1;
+                existing.insert(key);
+                continue;
+            }
+
+            let request = CreateMuteRuleRequest {
+                pattern: pattern.clone(),
+                kind: rule.kind,
+                feed_id,
+            };
+            match self.mute_service.create_rule(user_id, request).await {
+                Ok(_) => {
+                    report.mute_rules.created += 1;
+                    existing.insert(key);
+                }
+                Err(e) => Self::record_failure(
+                    &mut report.mute_rules,
+                    &mut report.failures,
+                    RestoreItemKind::MuteRule,
+                    pattern,
+                    AppError::from(e),
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn record_failure(
+        counts: &mut RestoreCounts,
+        failures: &mut Vec<RestoreFailure>,
+        kind: RestoreItemKind,
+        item: String,
+        error: AppError,
+    ) {
+        counts.failed += 1;
+        failures.push(RestoreFailure {
+            kind,
+            item: Some(item),
+            reason: error.to_string(),
+        });
+    }
+
+    /// Whether restoring the backup's display name and settings would change nothing
+    fn profile_matches(backup: &AccountBackup, current: &MeResponse) -> bool {
+        if backup
+            .display_name
+            .as_ref()
+            .is_some_and(|name| current.display_name.as_ref() != Some(name))
+        {
+            return false;
+        }
+
+        // Settings missing from the backup are kept as they are; voices are replaced
+        let backup_settings = serde_json::to_value(&backup.settings).unwrap_or_default();
+        let current_settings = serde_json::to_value(&current.settings).unwrap_or_default();
+        let Some(fields) = backup_settings.as_object() else {
+            return false;
+        };
+        fields
+            .iter()
+            .all(|(field, value)| current_settings.get(field) == Some(value))
+            && backup.settings.voices == current.settings.voices
+    }
+
+    /// Every setting the backup holds except the main voice; languages the account has a
+    /// voice for and the backup doesn't are cleared
+    fn settings_update(backup: &UserSettingsDto, current: &UserSettingsDto) -> UpdateSettingsDto {
+        let mut voices: BTreeMap<String, Option<String>> = current
+            .voices
+            .keys()
+            .map(|language| (language.clone(), None))
+            .collect();
+        voices.extend(
+            backup
+                .voices
+                .iter()
+                .map(|(language, voice)| (language.clone(), Some(voice.clone()))),
+        );
+
+        UpdateSettingsDto {
+            voice: None,
+            language: Some(backup.language.clone()),
+            max_article_age_days: backup.max_article_age_days,
+            max_items_per_feed: backup.max_items_per_feed,
+            timezone: backup.timezone.clone(),
+            voices: Some(voices),
+            notifications: Some(UpdateNotificationsDto {
+                digest_emails: Some(backup.notifications.digest_emails),
+                quota_warnings: Some(backup.notifications.quota_warnings),
+                product_updates: Some(backup.notifications.product_updates),
+                weekly_summary: Some(backup.notifications.weekly_summary),
+            }),
+            playback: Some(UpdatePlaybackDto {
+                speed: Some(backup.playback.speed),
+                skip_silence: Some(backup.playback.skip_silence),
+                auto_play_next: Some(backup.playback.auto_play_next),
+            }),
+        }
+    }
+}
+
+/// Keep a not-found or invalid outcome from another service recognisable as such
+fn service_error(err: impl Into<AppError>) -> BackupServiceError {
+    BackupServiceError::from(err.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::user::{NotificationPreferences, PlaybackPreferences};
+
+    fn settings(voices: &[(&str, &str)]) -> UserSettingsDto {
+        UserSettingsDto {
+            voice: "voice-en-1".to_string(),
+            language: "en".to_string(),
+            max_article_age_days: Some(7),
+            max_items_per_feed: None,
+            timezone: None,
+            voices: voices
+                .iter()
+                .map(|(language, voice)| (language.to_string(), voice.to_string()))
+                .collect(),
+            notifications: NotificationPreferences::default(),
+            playback: PlaybackPreferences::default(),
+        }
+    }
+
+    #[test]
+    fn test_settings_update_clears_voices_missing_from_the_backup() {
+        let backup = settings(&[("en", "voice-en-1")]);
+        let current = settings(&[("en", "voice-en-2"), ("fr", "voice-fr-1")]);
+
+        let update = BackupService::settings_update(&backup, &current);
+
+        let voices = update.voices.unwrap();
+        assert_eq!(voices["en"], Some("voice-en-1".to_string()));
+        assert_eq!(voices["fr"], None);
+        assert!(update.voice.is_none());
+        assert_eq!(update.max_article_age_days, Some(7));
+    }
+
+    #[test]
+    fn test_validate_backup_rejects_other_versions() {
+        let backup = AccountBackup {
+            version: BACKUP_VERSION + 1,
+            exported_at: Utc::now(),
+            display_name: None,
+            settings: settings(&[]),
+            feeds: Vec::new(),
+            mute_rules: Vec::new(),
+        };
+
+        assert!(matches!(
+            BackupService::validate_backup(&backup),
+            Err(BackupServiceError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,34 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeviceServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("device not found")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for DeviceServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => DeviceServiceError::Invalid(msg),
+            AppError::NotFound(_) => DeviceServiceError::NotFound,
+            _ => DeviceServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<DeviceServiceError> for AppError {
+    fn from(err: DeviceServiceError) -> Self {
+        match err {
+            DeviceServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            DeviceServiceError::NotFound => AppError::NotFound("Device not found".to_string()),
+            DeviceServiceError::Dependency(msg) => AppError::Internal(msg),
+            DeviceServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::DeviceServiceError;
+pub use model::{Device, DevicePlatform};
+pub use service::{DeviceService, DeviceServiceApi};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Request for POST /api/me/devices
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterDeviceRequest {
+    pub platform: DevicePlatform,
+    pub token: String,
+    #[serde(default)]
+    pub app_version: Option<String>,
+}
+
+/// A device registered for push notifications
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeviceResponse {
+    pub id: Uuid,
+    pub platform: DevicePlatform,
+    pub token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_version: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Device> for DeviceResponse {
+    fn from(device: Device) -> Self {
+        Self {
+            id: device.id,
+            platform: device.platform,
+            token: device.token,
+            app_version: device.app_version,
+            created_at: device.created_at,
+            updated_at: device.updated_at,
+        }
+    }
+}
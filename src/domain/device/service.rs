@@ -0,0 +1,95 @@
+use super::error::DeviceServiceError;
+use super::{DeviceResponse, RegisterDeviceRequest};
+use crate::infrastructure::repositories::DeviceRepository;
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// APNs tokens are 64 hex characters and FCM tokens are around 160
+const MAX_TOKEN_LENGTH: usize = 4096;
+const MAX_APP_VERSION_LENGTH: usize = 32;
+
+pub struct DeviceService {
+    device_repo: Arc<DeviceRepository>,
+}
+
+impl DeviceService {
+    pub fn new(device_repo: Arc<DeviceRepository>) -> Self {
+        Self { device_repo }
+    }
+}
+
+#[async_trait]
+pub trait DeviceServiceApi: Send + Sync {
+    /// Register a push token for the user. A token already registered, even by
+    /// another account, moves to this user.
+    async fn register_device(
+        &self,
+        user_id: Uuid,
+        request: RegisterDeviceRequest,
+    ) -> Result<DeviceResponse, DeviceServiceError>;
+
+    /// Stop sending push notifications to a token
+    async fn unregister_device(&self, user_id: Uuid, token: &str)
+        -> Result<(), DeviceServiceError>;
+}
+
+#[async_trait]
+impl DeviceServiceApi for DeviceService {
+    async fn register_device(
+        &self,
+        user_id: Uuid,
+        request: RegisterDeviceRequest,
+    ) -> Result<DeviceResponse, DeviceServiceError> {
+        let token = request.token.trim();
+        if token.is_empty() {
+            return Err(DeviceServiceError::Invalid(
+                "Device token is required".to_string(),
+            ));
+        }
+        if token.len() > MAX_TOKEN_LENGTH {
+            return Err(DeviceServiceError::Invalid(format!(
+                "Device token cannot exceed {} characters",
+                MAX_TOKEN_LENGTH
+            )));
+        }
+        let app_version = request
+            .app_version
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        if app_version.is_some_and(|v| v.len() > MAX_APP_VERSION_LENGTH) {
+            return Err(DeviceServiceError::Invalid(format!(
+                "App version cannot exceed {} characters",
+                MAX_APP_VERSION_LENGTH
+            )));
+        }
+
+        let device = self
+            .device_repo
+            .upsert(user_id, request.platform, token, app_version)
+            .await
+            .map_err(|e| DeviceServiceError::Dependency(e.to_string()))?;
+
+        tracing::info!(user_id = %user_id, platform = %device.platform, "Device registered");
+
+        Ok(DeviceResponse::from(device))
+    }
+
+    async fn unregister_device(
+        &self,
+        user_id: Uuid,
+        token: &str,
+    ) -> Result<(), DeviceServiceError> {
+        let deleted = self
+            .device_repo
+            .delete_for_user(user_id, token)
+            .await
+            .map_err(|e| DeviceServiceError::Dependency(e.to_string()))?;
+        if !deleted {
+            return Err(DeviceServiceError::NotFound);
+        }
+
+        Ok(())
+    }
+}
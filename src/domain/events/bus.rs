@@ -0,0 +1,127 @@
+use super::model::{DomainEvent, EventOrigin};
+use crate::error::AppResult;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// A subsystem reacting to domain events, e.g. by queueing webhooks or writing the audit
+/// log. The same subscribers are handed events published to the bus and events
+/// dispatched from the outbox.
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// Shown in logs and in the outbox's stored error
+    fn name(&self) -> &'static str;
+
+    /// Events the subscriber has no use for are ignored. `occurred_at` is when the event
+    /// was published, which for outbox events can be well before they are dispatched, and
+    /// `origin` the request it was published from.
+    async fn handle(
+        &self,
+        event: &DomainEvent,
+        occurred_at: DateTime<Utc>,
+        origin: &EventOrigin,
+    ) -> AppResult<()>;
+}
+
+/// Where services publish domain events instead of calling the services that react to
+/// them. Delivery guarantees are the implementation's.
+#[async_trait]
+pub trait EventBus: Send + Sync {
+    async fn publish(&self, event: DomainEvent);
+}
+
+/// Hands each event to every subscriber before `publish` returns, in the publisher's
+/// task. Delivery is at most once: a failing subscriber is logged and the event is not
+/// retried.
+pub struct InProcessEventBus {
+    subscribers: Vec<Arc<dyn EventSubscriber>>,
+}
+
+impl InProcessEventBus {
+    pub fn new(subscribers: Vec<Arc<dyn EventSubscriber>>) -> Self {
+        Self { subscribers }
+    }
+}
+
+#[async_trait]
+impl EventBus for InProcessEventBus {
+    async fn publish(&self, event: DomainEvent) {
+        let occurred_at = Utc::now();
+        let origin = EventOrigin::current();
+        for subscriber in &self.subscribers {
+            if let Err(e) = subscriber.handle(&event, occurred_at, &origin).await {
+                tracing::warn!(
+                    event_type = event.event_type(),
+                    user_id = %event.user_id(),
+                    subscriber = subscriber.name(),
+                    error = %e,
+                    "Event subscriber failed"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    struct Recording {
+        seen: Mutex<Vec<&'static str>>,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for Recording {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn handle(
+            &self,
+            event: &DomainEvent,
+            _occurred_at: DateTime<Utc>,
+            _origin: &EventOrigin,
+        ) -> AppResult<()> {
+            self.seen.lock().unwrap().push(event.event_type());
+            Ok(())
+        }
+    }
+
+    struct Failing;
+
+    #[async_trait]
+    impl EventSubscriber for Failing {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn handle(
+            &self,
+            _event: &DomainEvent,
+            _occurred_at: DateTime<Utc>,
+            _origin: &EventOrigin,
+        ) -> AppResult<()> {
+            Err(AppError::Internal("unavailable".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_every_subscriber_sees_the_event_despite_failures() {
+        let recording = Arc::new(Recording {
+            seen: Mutex::new(Vec::new()),
+        });
+        let bus = InProcessEventBus::new(vec![Arc::new(Failing), recording.clone()]);
+
+        bus.publish(DomainEvent::FeedDeleted {
+            user_id: Uuid::new_v4(),
+            feed_id: Uuid::new_v4(),
+            url: "https://example.com/rss".to_string(),
+        })
+        .await;
+
+        assert_eq!(*recording.seen.lock().unwrap(), vec!["feed_deleted"]);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod bus;
+pub mod model;
+pub mod subscribers;
+
+pub use bus::{EventBus, EventSubscriber, InProcessEventBus};
+pub use model::{DomainEvent, EventOrigin};
+pub use subscribers::{AuditSubscriber, WebhookSubscriber};
@@ -0,0 +1,124 @@
+use crate::infrastructure::auth::current_request_id;
+use crate::infrastructure::http::current_client_ip;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// Something that happened in the domain, for the subsystems that react to it. Published
+/// to the `EventBus`, or recorded in the outbox when it must not be lost.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomainEvent {
+    UserCreated {
+        user_id: Uuid,
+        oauth_provider: String,
+    },
+    FeedAdded {
+        user_id: Uuid,
+        feed_id: Uuid,
+        url: String,
+    },
+    FeedDeleted {
+        user_id: Uuid,
+        feed_id: Uuid,
+        url: String,
+    },
+    /// A feed published entries since it was last checked
+    NewArticles {
+        user_id: Uuid,
+        feed_id: Uuid,
+        url: String,
+        title: Option<String>,
+        new_articles: usize,
+    },
+    /// A synthesis was refused for going over the quota
+    QuotaExceeded {
+        user_id: Uuid,
+        characters_used: i32,
+        character_limit: i32,
+        characters_requested: i32,
+        /// When the quota period began, so subscribers can react once per period
+        period_started_at: DateTime<Utc>,
+        resets_at: DateTime<Utc>,
+    },
+    /// An article was turned into audio, from Polly or the cache
+    SynthesisCompleted {
+        user_id: Uuid,
+        link: String,
+        language: String,
+        characters: i32,
+        duration_minutes: f32,
+    },
+    /// A refresh token was presented again after being replaced or revoked
+    RefreshTokenReused {
+        user_id: Uuid,
+        /// Tokens issued from it that were still valid and are now revoked
+        revoked_tokens: u64,
+    },
+}
+
+impl DomainEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::UserCreated { .. } => "user_created",
+            DomainEvent::FeedAdded { .. } => "feed_added",
+            DomainEvent::FeedDeleted { .. } => "feed_deleted",
+            DomainEvent::NewArticles { .. } => "new_articles",
+            DomainEvent::QuotaExceeded { .. } => "quota_exceeded",
+            DomainEvent::SynthesisCompleted { .. } => "synthesis_completed",
+            DomainEvent::RefreshTokenReused { .. } => "refresh_token_reused",
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        match self {
+            DomainEvent::UserCreated { user_id, .. }
+            | DomainEvent::FeedAdded { user_id, .. }
+            | DomainEvent::FeedDeleted { user_id, .. }
+            | DomainEvent::NewArticles { user_id, .. }
+            | DomainEvent::QuotaExceeded { user_id, .. }
+            | DomainEvent::SynthesisCompleted { user_id, .. }
+            | DomainEvent::RefreshTokenReused { user_id, .. } => *user_id,
+        }
+    }
+}
+
+/// The request an event was published from. Taken when the event is published, since
+/// outbox events are handled later, outside of the request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EventOrigin {
+    pub client_ip: Option<IpAddr>,
+    pub request_id: Option<String>,
+}
+
+impl EventOrigin {
+    /// Origin of the request being handled by the current task; empty in background jobs
+    pub fn current() -> Self {
+        Self {
+            client_ip: current_client_ip(),
+            request_id: current_request_id(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_payload_carries_its_type() {
+        let event = DomainEvent::FeedAdded {
+            user_id: Uuid::new_v4(),
+            feed_id: Uuid::new_v4(),
+            url: "https://example.com/rss".to_string(),
+        };
+
+        let payload = serde_json::to_value(&event).unwrap();
+        assert_eq!(payload["type"], event.event_type());
+        assert_eq!(
+            serde_json::from_value::<DomainEvent>(payload).unwrap(),
+            event
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use super::bus::EventSubscriber;
+use super::model::{DomainEvent, EventOrigin};
+use crate::domain::audit::{AuditAction, AuditEvent, AuditService, AuditServiceApi};
+use crate::domain::webhook::{WebhookEventType, WebhookService, WebhookServiceApi};
+use crate::error::AppResult;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Queues deliveries for the events users can subscribe webhooks to
+pub struct WebhookSubscriber {
+    webhook_service: Arc<WebhookService>,
+}
+
+impl WebhookSubscriber {
+    pub fn new(webhook_service: Arc<WebhookService>) -> Self {
+        Self { webhook_service }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for WebhookSubscriber {
+    fn name(&self) -> &'static str {
+        "webhooks"
+    }
+
+    async fn handle(
+        &self,
+        event: &DomainEvent,
+        _occurred_at: DateTime<Utc>,
+        _origin: &EventOrigin,
+    ) -> AppResult<()> {
+        match event {
+            DomainEvent::SynthesisCompleted {
+                user_id,
+                link,
+                language,
+                characters,
+                duration_minutes,
+            } => {
+                self.webhook_service
+                    .dispatch(
+                        *user_id,
+                        WebhookEventType::SynthesisCompleted,
+                        json!({
+                            "link": link,
+                            "language": language,
+                            "characters": characters,
+                            "duration_minutes": duration_minutes,
+                        }),
+                    )
+                    .await;
+            }
+            DomainEvent::NewArticles {
+                user_id,
+                feed_id,
+                url,
+                title,
+                new_articles,
+            } => {
+                self.webhook_service
+                    .dispatch(
+                        *user_id,
+                        WebhookEventType::NewArticles,
+                        json!({
+                            "feed_id": feed_id,
+                            "url": url,
+                            "title": title,
+                            "new_articles": new_articles,
+                        }),
+                    )
+                    .await;
+            }
+            DomainEvent::QuotaExceeded {
+                user_id,
+                characters_used,
+                character_limit,
+                characters_requested,
+                period_started_at,
+                resets_at,
+            } => {
+                // Only the first refusal of a period is worth telling the user about
+                self.webhook_service
+                    .dispatch_once_since(
+                        *user_id,
+                        WebhookEventType::QuotaExceeded,
+                        json!({
+                            "characters_used": characters_used,
+                            "character_limit": character_limit,
+                            "characters_requested": characters_requested,
+                            "resets_at": resets_at,
+                        }),
+                        *period_started_at,
+                    )
+                    .await;
+            }
+            DomainEvent::UserCreated { .. }
+            | DomainEvent::FeedAdded { .. }
+            | DomainEvent::FeedDeleted { .. }
+            | DomainEvent::RefreshTokenReused { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+/// Records account and feed changes, and signs of stolen sessions, in the audit log
+pub struct AuditSubscriber {
+    audit_service: Arc<AuditService>,
+}
+
+impl AuditSubscriber {
+    pub fn new(audit_service: Arc<AuditService>) -> Self {
+        Self { audit_service }
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for AuditSubscriber {
+    fn name(&self) -> &'static str {
+        "audit_log"
+    }
+
+    async fn handle(
+        &self,
+        event: &DomainEvent,
+        occurred_at: DateTime<Utc>,
+        origin: &EventOrigin,
+    ) -> AppResult<()> {
+        let audit_event = match event {
+            DomainEvent::UserCreated {
+                user_id,
+                oauth_provider,
+            } => AuditEvent::by_user(*user_id, AuditAction::AccountCreated)
+                .details(json!({ "oauth_provider": oauth_provider })),
+            DomainEvent::FeedAdded {
+                user_id,
+                feed_id,
+                url,
+            } => AuditEvent::by_user(*user_id, AuditAction::FeedCreated)
+                .target(*feed_id)
+                .details(json!({ "url": url })),
+            DomainEvent::FeedDeleted {
+                user_id,
+                feed_id,
+                url,
+            } => AuditEvent::by_user(*user_id, AuditAction::FeedDeleted)
+                .target(*feed_id)
+                .details(json!({ "url": url })),
+            DomainEvent::RefreshTokenReused {
+                user_id,
+                revoked_tokens,
+            } => AuditEvent::by_system("auth", AuditAction::RefreshTokenReused)
+                .for_user(*user_id)
+                .details(json!({ "revoked_tokens": revoked_tokens })),
+            DomainEvent::NewArticles { .. }
+            | DomainEvent::QuotaExceeded { .. }
+            | DomainEvent::SynthesisCompleted { .. } => return Ok(()),
+        };
+        self.audit_service
+            .record(
+                audit_event
+                    .occurred_at(occurred_at)
+                    .requested_from(origin.client_ip, origin.request_id.clone()),
+            )
+            .await;
+        Ok(())
+    }
+}
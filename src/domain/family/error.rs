@@ -0,0 +1,65 @@
+use super::model::MAX_FAMILY_MEMBERS;
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum FamilyServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("user not found")]
+    UserNotFound,
+    #[error("pro required")]
+    ProRequired,
+    #[error("family is full")]
+    FamilyFull,
+    #[error("invite not found")]
+    InviteNotFound,
+    #[error("invite addressed to another email")]
+    WrongRecipient,
+    #[error("already in a family")]
+    AlreadyMember,
+    #[error("member not found")]
+    MemberNotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for FamilyServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => FamilyServiceError::Invalid(msg),
+            _ => FamilyServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<FamilyServiceError> for AppError {
+    fn from(err: FamilyServiceError) -> Self {
+        match err {
+            FamilyServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            FamilyServiceError::UserNotFound => AppError::NotFound("User not found".to_string()),
+            FamilyServiceError::ProRequired => AppError::PaymentRequired(
+                "A Pro subscription is required to share it with family".to_string(),
+            ),
+            FamilyServiceError::FamilyFull => AppError::Conflict(format!(
+                "A family plan can have at most {} members, including pending invites",
+                MAX_FAMILY_MEMBERS
+            )),
+            FamilyServiceError::InviteNotFound => {
+                AppError::NotFound("Family invite not found".to_string())
+            }
+            FamilyServiceError::WrongRecipient => {
+                AppError::Forbidden("This invite was sent to a different email".to_string())
+            }
+            FamilyServiceError::AlreadyMember => {
+                AppError::Conflict("You are already part of a family plan".to_string())
+            }
+            FamilyServiceError::MemberNotFound => {
+                AppError::NotFound("Family member not found".to_string())
+            }
+            FamilyServiceError::Dependency(msg) => AppError::Internal(msg),
+            FamilyServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::FamilyServiceError;
+pub use model::{FamilyInvite, FAMILY_INVITE_DAYS, MAX_FAMILY_MEMBERS};
+pub use service::{FamilyService, FamilyServiceApi};
+
+use crate::domain::user::User;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Response for GET /api/family
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FamilyResponse {
+    /// Set for members: the user whose Pro plan they share
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<FamilyMemberResponse>,
+    /// Members of the user's own family plan
+    pub members: Vec<FamilyMemberResponse>,
+    /// Invites the user sent that haven't been accepted yet
+    pub invites: Vec<FamilyInviteResponse>,
+    pub max_members: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FamilyMemberResponse {
+    pub id: Uuid,
+    pub email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+}
+
+impl From<User> for FamilyMemberResponse {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            display_name: user.display_name,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FamilyInviteResponse {
+    /// Code the invitee enters to join
+    pub code: String,
+    pub email: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<FamilyInvite> for FamilyInviteResponse {
+    fn from(invite: FamilyInvite) -> Self {
+        Self {
+            code: invite.code,
+            email: invite.email,
+            expires_at: invite.expires_at,
+        }
+    }
+}
+
+/// Request for POST /api/family/invites
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateFamilyInviteRequest {
+    pub email: String,
+}
+
+/// Request for POST /api/family/join
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JoinFamilyRequest {
+    pub code: String,
+}
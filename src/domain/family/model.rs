@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Members a family owner can have, counting pending invites
+pub const MAX_FAMILY_MEMBERS: usize = 5;
+
+/// How long an invite can be accepted for
+pub const FAMILY_INVITE_DAYS: i64 = 7;
+
+/// Invite to join a family plan, addressed to an email
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FamilyInvite {
+    pub code: String,
+    pub owner_id: Uuid,
+    /// Lowercased; only the account with this email can accept
+    pub email: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FamilyInvite {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
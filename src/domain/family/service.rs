@@ -0,0 +1,284 @@
+use super::error::FamilyServiceError;
+use super::model::{FamilyInvite, FAMILY_INVITE_DAYS, MAX_FAMILY_MEMBERS};
+use super::{
+    CreateFamilyInviteRequest, FamilyInviteResponse, FamilyMemberResponse, FamilyResponse,
+};
+use crate::domain::shared::code::{generate_code, normalize_code};
+use crate::domain::user::{SubscriptionTier, User};
+use crate::error::AppError;
+use crate::infrastructure::auth::AuthUserCache;
+use crate::infrastructure::repositories::{FamilyRepository, UserRepository, UserRepositoryApi};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Attempts at finding an unused invite code before giving up
+const CODE_GENERATION_ATTEMPTS: usize = 3;
+
+pub struct FamilyService {
+    user_repo: Arc<UserRepository>,
+    family_repo: Arc<FamilyRepository>,
+    user_cache: Arc<AuthUserCache>,
+}
+
+impl FamilyService {
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        family_repo: Arc<FamilyRepository>,
+        user_cache: Arc<AuthUserCache>,
+    ) -> Self {
+        Self {
+            user_repo,
+            family_repo,
+            user_cache,
+        }
+    }
+}
+
+#[async_trait]
+pub trait FamilyServiceApi: Send + Sync {
+    /// The family the user owns or belongs to, with pending invites for owners
+    async fn get_family(&self, user_id: Uuid) -> Result<FamilyResponse, FamilyServiceError>;
+
+    /// Invite someone by email to share the user's Pro plan
+    async fn invite(
+        &self,
+        user_id: Uuid,
+        request: CreateFamilyInviteRequest,
+    ) -> Result<FamilyInviteResponse, FamilyServiceError>;
+
+    /// Withdraw an invite that hasn't been accepted yet
+    async fn revoke_invite(&self, user_id: Uuid, code: &str) -> Result<(), FamilyServiceError>;
+
+    /// Accept an invite sent to the user's email
+    async fn join(&self, user_id: Uuid, code: &str) -> Result<FamilyResponse, FamilyServiceError>;
+
+    /// Remove a member from the user's family
+    async fn remove_member(&self, user_id: Uuid, member_id: Uuid)
+        -> Result<(), FamilyServiceError>;
+
+    /// Leave the family the user belongs to
+    async fn leave(&self, user_id: Uuid) -> Result<(), FamilyServiceError>;
+}
+
+#[async_trait]
+impl FamilyServiceApi for FamilyService {
+    async fn get_family(&self, user_id: Uuid) -> Result<FamilyResponse, FamilyServiceError> {
+        let user = self.find_user(user_id).await?;
+
+        if let Some(owner_id) = user.family_owner_id {
+            let owner = self.find_user(owner_id).await?;
+            return Ok(FamilyResponse {
+                owner: Some(FamilyMemberResponse::from(owner)),
+                members: Vec::new(),
+                invites: Vec::new(),
+                max_members: MAX_FAMILY_MEMBERS,
+            });
+        }
+
+        let members = self.list_members(user_id).await?;
+        let invites = self.list_pending_invites(user_id).await?;
+
+        Ok(FamilyResponse {
+            owner: None,
+            members: members
+                .into_iter()
+                .map(FamilyMemberResponse::from)
+                .collect(),
+            invites: invites
+                .into_iter()
+                .map(FamilyInviteResponse::from)
+                .collect(),
+            max_members: MAX_FAMILY_MEMBERS,
+        })
+    }
+
+    async fn invite(
+        &self,
+        user_id: Uuid,
+        request: CreateFamilyInviteRequest,
+    ) -> Result<FamilyInviteResponse, FamilyServiceError> {
+        let email = request.email.trim().to_lowercase();
+        if !email.contains('@') {
+            return Err(FamilyServiceError::Invalid(
+                "A valid email is required".to_string(),
+            ));
+        }
+
+        let owner = self.find_user(user_id).await?;
+        if owner.family_owner_id.is_some() {
+            return Err(FamilyServiceError::Invalid(
+                "Family members cannot invite others".to_string(),
+            ));
+        }
+        if owner.effective_tier() != SubscriptionTier::Pro {
+            return Err(FamilyServiceError::ProRequired);
+        }
+        if owner.email.eq_ignore_ascii_case(&email) {
+            return Err(FamilyServiceError::Invalid(
+                "You cannot invite yourself".to_string(),
+            ));
+        }
+
+        let members = self.list_members(user_id).await?;
+        if members.iter().any(|m| m.email.eq_ignore_ascii_case(&email)) {
+            return Err(FamilyServiceError::Invalid(
+                "This user is already in your family".to_string(),
+            ));
+        }
+        // Re-inviting an email replaces its invite, so it doesn't take another seat
+        let invites = self.list_pending_invites(user_id).await?;
+        let reinvite = invites.iter().any(|invite| invite.email == email);
+        if !reinvite && members.len() + invites.len() >= MAX_FAMILY_MEMBERS {
+            return Err(FamilyServiceError::FamilyFull);
+        }
+
+        let expires_at = Utc::now() + Duration::days(FAMILY_INVITE_DAYS);
+        for _ in 0..CODE_GENERATION_ATTEMPTS {
+            match self
+                .family_repo
+                .upsert_invite(&generate_code(), user_id, &email, expires_at)
+                .await
+            {
+                Ok(invite) => {
+                    tracing::info!(owner_id = %user_id, "Family invite sent");
+                    return Ok(FamilyInviteResponse::from(invite));
+                }
+                Err(AppError::Conflict(_)) => continue,
+                Err(e) => return Err(FamilyServiceError::Dependency(e.to_string())),
+            }
+        }
+        Err(FamilyServiceError::Dependency(
+            "Could not generate a unique invite code".to_string(),
+        ))
+    }
+
+    async fn revoke_invite(&self, user_id: Uuid, code: &str) -> Result<(), FamilyServiceError> {
+        let code = normalize_code(code).ok_or(FamilyServiceError::InviteNotFound)?;
+
+        let deleted = self
+            .family_repo
+            .delete_invite(user_id, &code)
+            .await
+            .map_err(|e| FamilyServiceError::Dependency(e.to_string()))?;
+        if !deleted {
+            return Err(FamilyServiceError::InviteNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn join(&self, user_id: Uuid, code: &str) -> Result<FamilyResponse, FamilyServiceError> {
+        let code = normalize_code(code)
+            .ok_or_else(|| FamilyServiceError::Invalid("Invite code is required".to_string()))?;
+
+        let invite = self
+            .family_repo
+            .find_invite(&code)
+            .await
+            .map_err(|e| FamilyServiceError::Dependency(e.to_string()))?
+            .ok_or(FamilyServiceError::InviteNotFound)?;
+        if invite.is_expired() {
+            return Err(FamilyServiceError::Invalid(
+                "Family invite has expired".to_string(),
+            ));
+        }
+
+        let user = self.find_user(user_id).await?;
+        if !user.email.eq_ignore_ascii_case(&invite.email) {
+            return Err(FamilyServiceError::WrongRecipient);
+        }
+        if invite.owner_id == user_id {
+            return Err(FamilyServiceError::Invalid(
+                "You cannot join your own family".to_string(),
+            ));
+        }
+        if user.family_owner_id.is_some() {
+            return Err(FamilyServiceError::AlreadyMember);
+        }
+        // Families don't nest: members inherit from their owner only
+        if !self.list_members(user_id).await?.is_empty() {
+            return Err(FamilyServiceError::Invalid(
+                "Family owners cannot join another family".to_string(),
+            ));
+        }
+
+        self.family_repo
+            .accept_invite(&code, user_id)
+            .await
+            .map_err(|e| FamilyServiceError::Dependency(e.to_string()))?
+            .ok_or_else(|| {
+                FamilyServiceError::Invalid("Family invite is no longer available".to_string())
+            })?;
+        self.user_cache.invalidate(user_id).await;
+
+        tracing::info!(user_id = %user_id, owner_id = %invite.owner_id, "Joined family");
+
+        self.get_family(user_id).await
+    }
+
+    async fn remove_member(
+        &self,
+        user_id: Uuid,
+        member_id: Uuid,
+    ) -> Result<(), FamilyServiceError> {
+        let removed = self
+            .family_repo
+            .remove_member(user_id, member_id)
+            .await
+            .map_err(|e| FamilyServiceError::Dependency(e.to_string()))?;
+        if !removed {
+            return Err(FamilyServiceError::MemberNotFound);
+        }
+        self.user_cache.invalidate(member_id).await;
+
+        tracing::info!(owner_id = %user_id, member_id = %member_id, "Family member removed");
+
+        Ok(())
+    }
+
+    async fn leave(&self, user_id: Uuid) -> Result<(), FamilyServiceError> {
+        let user = self.find_user(user_id).await?;
+        let owner_id = user.family_owner_id.ok_or_else(|| {
+            FamilyServiceError::Invalid("You are not part of a family".to_string())
+        })?;
+
+        self.family_repo
+            .remove_member(owner_id, user_id)
+            .await
+            .map_err(|e| FamilyServiceError::Dependency(e.to_string()))?;
+        self.user_cache.invalidate(user_id).await;
+
+        tracing::info!(user_id = %user_id, owner_id = %owner_id, "Left family");
+
+        Ok(())
+    }
+}
+
+impl FamilyService {
+    async fn find_user(&self, user_id: Uuid) -> Result<User, FamilyServiceError> {
+        self.user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| FamilyServiceError::Dependency(e.to_string()))?
+            .ok_or(FamilyServiceError::UserNotFound)
+    }
+
+    async fn list_members(&self, owner_id: Uuid) -> Result<Vec<User>, FamilyServiceError> {
+        self.family_repo
+            .list_members(owner_id)
+            .await
+            .map_err(|e| FamilyServiceError::Dependency(e.to_string()))
+    }
+
+    async fn list_pending_invites(
+        &self,
+        owner_id: Uuid,
+    ) -> Result<Vec<FamilyInvite>, FamilyServiceError> {
+        self.family_repo
+            .list_pending_invites(owner_id)
+            .await
+            .map_err(|e| FamilyServiceError::Dependency(e.to_string()))
+    }
+}
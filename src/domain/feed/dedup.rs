@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Minimum Jaccard similarity between title shingles to treat two stories as the same
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.6;
+const SHINGLE_SIZE: usize = 2;
+const MAX_SOURCE_SUFFIX_WORDS: usize = 3;
+
+/// Article data needed for duplicate detection
+#[derive(Debug, Clone)]
+pub struct StoryTitle<'a> {
+    pub id: Uuid,
+    pub feed_id: Uuid,
+    pub title: &'a str,
+}
+
+/// Detects the same story published by several feeds (e.g. wire stories) by comparing
+/// word shingles of normalized titles.
+pub struct DuplicateDetector {
+    threshold: f32,
+}
+
+impl DuplicateDetector {
+    pub fn new(threshold: f32) -> Self {
+        Self { threshold }
+    }
+
+    /// Returns, for each story in order, the id of the earlier story it duplicates
+    /// (`duplicates_of`), or `None` when it is the first occurrence. Stories should be passed
+    /// oldest first so the original publication wins.
+    pub fn detect(&self, stories: &[StoryTitle<'_>]) -> Vec<Option<Uuid>> {
+        let mut canonical: Vec<(Uuid, Uuid, HashSet<String>)> = Vec::new();
+        let mut duplicates_of = Vec::with_capacity(stories.len());
+
+        for story in stories {
+            let shingles = shingles(story.title);
+            let original = canonical
+                .iter()
+                .find(|(_, feed_id, existing)| {
+                    *feed_id != story.feed_id && jaccard(existing, &shingles) >= self.threshold
+                })
+                .map(|(id, _, _)| *id);
+
+            if original.is_none() && !shingles.is_empty() {
+                canonical.push((story.id, story.feed_id, shingles));
+            }
+            duplicates_of.push(original);
+        }
+
+        duplicates_of
+    }
+}
+
+impl Default for DuplicateDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_SIMILARITY_THRESHOLD)
+    }
+}
+
+/// Lowercase, strip punctuation and drop trailing source attributions like " - Reuters"
+fn normalize_title(title: &str) -> Vec<String> {
+    let title = title
+        .rsplit_once(" - ")
+        .or_else(|| title.rsplit_once(" | "))
+        .filter(|(_, source)| source.split_whitespace().count() <= MAX_SOURCE_SUFFIX_WORDS)
+        .map(|(head, _source)| head)
+        .unwrap_or(title);
+
+    title
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn shingles(title: &str) -> HashSet<String> {
+    let words = normalize_title(title);
+    if words.len() < SHINGLE_SIZE {
+        return words.into_iter().collect();
+    }
+    words
+        .windows(SHINGLE_SIZE)
+        .map(|window| window.join(" "))
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.union(b).count();
+    intersection as f32 / union as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(feed_id: Uuid, title: &str) -> StoryTitle<'_> {
+        StoryTitle {
+            id: Uuid::new_v4(),
+            feed_id,
+            title,
+        }
+    }
+
+    #[test]
+    fn test_detects_wire_story_across_feeds() {
+        let stories = vec![
+            story(
+                Uuid::new_v4(),
+                "Central bank raises interest rates to fight inflation - Reuters",
+            ),
+            story(
+                Uuid::new_v4(),
+                "Central bank raises interest rates to fight inflation",
+            ),
+            story(
+                Uuid::new_v4(),
+                "New rust compiler release improves build times",
+            ),
+        ];
+
+        let result = DuplicateDetector::default().detect(&stories);
+
+        assert_eq!(result, vec![None, Some(stories[0].id), None]);
+    }
+
+    #[test]
+    fn test_ignores_similar_titles_within_the_same_feed() {
+        let feed_id = Uuid::new_v4();
+        let stories = vec![
+            story(feed_id, "Live updates: election night results"),
+            story(feed_id, "Live updates: election night results"),
+        ];
+
+        let result = DuplicateDetector::default().detect(&stories);
+
+        assert_eq!(result, vec![None, None]);
+    }
+
+    #[test]
+    fn test_different_stories_are_not_collapsed() {
+        let stories = vec![
+            story(
+                Uuid::new_v4(),
+                "Apple announces new iPhone at September event",
+            ),
+            story(
+                Uuid::new_v4(),
+                "Google announces new Pixel at October event",
+            ),
+        ];
+
+        let result = DuplicateDetector::default().detect(&stories);
+
+        assert_eq!(result, vec![None, None]);
+    }
+}
@@ -1,10 +1,14 @@
+pub mod dedup;
 pub mod error;
 pub mod model;
 pub mod service;
+pub mod stats;
 
+pub use dedup::{DuplicateDetector, StoryTitle};
 pub use error::FeedServiceError;
 pub use model::Feed;
 pub use service::{FeedService, FeedServiceApi};
+pub use stats::FeedStats;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -18,6 +22,28 @@ pub struct FeedResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_articles_per_day: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_published_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<String>,
+}
+
+/// Query for GET /api/feeds. Without either field the full list is returned as a bare
+/// array, which app versions from before pagination expect.
+#[derive(Debug, Deserialize)]
+pub struct ListFeedsQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+impl ListFeedsQuery {
+    pub fn is_paginated(&self) -> bool {
+        self.limit.is_some() || self.cursor.is_some()
+    }
 }
 
 /// Request to create a new feed
@@ -26,6 +52,31 @@ pub struct CreateFeedRequest {
     pub id: Uuid,
     pub url: String,
     pub title: String,
+    /// Set when the feed is added from GET /api/feed-suggestions
+    #[serde(default)]
+    pub suggestion_id: Option<String>,
+}
+
+/// Request to validate feed URLs without subscribing to them
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidateFeedsRequest {
+    pub urls: Vec<String>,
+}
+
+/// Validation outcome for a single URL
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeedValidationResult {
+    pub url: String,
+    /// The URL could be fetched successfully
+    pub resolves: bool,
+    /// The fetched document is a valid RSS/Atom feed
+    pub parses: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The user is already subscribed to this URL
+    pub conflict: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 impl From<Feed> for FeedResponse {
@@ -35,6 +86,10 @@ impl From<Feed> for FeedResponse {
             url: feed.url,
             title: feed.title,
             created_at: feed.created_at,
+            avg_articles_per_day: feed.avg_articles_per_day,
+            last_published_at: feed.last_published_at,
+            suggestion_id: feed.suggestion_id,
+            category_id: feed.category_id,
         }
     }
 }
@@ -10,4 +10,11 @@ pub struct Feed {
     pub url: String,
     pub title: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub avg_articles_per_day: Option<f64>,
+    pub last_published_at: Option<DateTime<Utc>>,
+    pub stats_updated_at: Option<DateTime<Utc>>,
+    /// Suggestion the feed was added from, if any
+    pub suggestion_id: Option<String>,
+    /// Category of that suggestion
+    pub category_id: Option<String>,
 }
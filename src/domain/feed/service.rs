@@ -1,24 +1,73 @@
+use super::dedup::{DuplicateDetector, StoryTitle};
 use super::error::FeedServiceError;
-use crate::domain::feed::{CreateFeedRequest, Feed, FeedResponse};
-use crate::domain::user::{SubscriptionTier, User};
-use crate::infrastructure::repositories::{FeedRepository, UserRepository};
+use crate::domain::events::{DomainEvent, EventBus};
+use crate::domain::feed::{CreateFeedRequest, Feed, FeedResponse, FeedStats, FeedValidationResult};
+use crate::domain::feed_suggestions::{FeedSuggestion, FeedSuggestionsRepository};
+use crate::domain::mute::{MuteRuleSet, MuteServiceApi};
+use crate::domain::plan::PlanLimits;
+use crate::domain::shared::{Cursor, PageRequest, PageResponse};
+use crate::domain::user::{User, TRIAL_EXPIRED_MESSAGE};
+use crate::infrastructure::cache::CacheStore;
+use crate::infrastructure::events::{AccountEventKind, AccountEventStream};
+use crate::infrastructure::feed_fetcher::{FeedFetchError, FeedFetcher, FetchedEntry};
+use crate::infrastructure::repositories::{FeedRepositoryApi, UserRepositoryApi};
+use crate::infrastructure::sanitizer::sanitize_text;
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
 use std::sync::Arc;
+use tokio::task::JoinSet;
+use tracing::Instrument;
 use uuid::Uuid;
 
-const MAX_FEEDS_FREE: i64 = 3;
-const MAX_FEEDS_PRO: i64 = 999;
+const MAX_URLS_PER_VALIDATION: usize = 50;
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+const STATS_MAX_AGE_HOURS: i64 = 24;
+const STATS_REFRESH_BATCH_SIZE: i64 = 100;
+/// How long stories a user was told about are remembered, to recognize them in other feeds
+const RECENT_STORIES_TTL_HOURS: i64 = 48;
+/// Most stories remembered per user
+const MAX_RECENT_STORIES: usize = 500;
 
 pub struct FeedService {
-    feed_repo: Arc<FeedRepository>,
-    user_repo: Arc<UserRepository>,
+    feed_repo: Arc<dyn FeedRepositoryApi>,
+    user_repo: Arc<dyn UserRepositoryApi>,
+    feed_fetcher: Arc<FeedFetcher>,
+    feed_suggestions_repo: Arc<dyn FeedSuggestionsRepository>,
+    plan_limits: Arc<PlanLimits>,
+    domain_events: Arc<dyn EventBus>,
+    account_events: Arc<AccountEventStream>,
+    mute_service: Arc<dyn MuteServiceApi>,
+    /// Holds the stories each user was recently told about
+    cache: Arc<dyn CacheStore>,
 }
 
 impl FeedService {
-    pub fn new(feed_repo: Arc<FeedRepository>, user_repo: Arc<UserRepository>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        feed_repo: Arc<dyn FeedRepositoryApi>,
+        user_repo: Arc<dyn UserRepositoryApi>,
+        feed_fetcher: Arc<FeedFetcher>,
+        feed_suggestions_repo: Arc<dyn FeedSuggestionsRepository>,
+        plan_limits: Arc<PlanLimits>,
+        domain_events: Arc<dyn EventBus>,
+        account_events: Arc<AccountEventStream>,
+        mute_service: Arc<dyn MuteServiceApi>,
+        cache: Arc<dyn CacheStore>,
+    ) -> Self {
         Self {
             feed_repo,
             user_repo,
+            feed_fetcher,
+            feed_suggestions_repo,
+            plan_limits,
+            domain_events,
+            account_events,
+            mute_service,
+            cache,
         }
     }
 }
@@ -27,6 +76,13 @@ impl FeedService {
 pub trait FeedServiceApi: Send + Sync {
     async fn get_user_feeds(&self, user_id: Uuid) -> Result<Vec<FeedResponse>, FeedServiceError>;
 
+    async fn get_user_feeds_page(
+        &self,
+        user_id: Uuid,
+        limit: Option<i64>,
+        cursor: Option<&str>,
+    ) -> Result<PageResponse<FeedResponse>, FeedServiceError>;
+
     async fn create_feed(
         &self,
         user_id: Uuid,
@@ -34,6 +90,16 @@ pub trait FeedServiceApi: Send + Sync {
     ) -> Result<(), FeedServiceError>;
 
     async fn delete_feed(&self, user_id: Uuid, feed_id: Uuid) -> Result<(), FeedServiceError>;
+
+    async fn validate_feeds(
+        &self,
+        user_id: Uuid,
+        urls: Vec<String>,
+    ) -> Result<Vec<FeedValidationResult>, FeedServiceError>;
+
+    /// Recompute activity stats for feeds whose stats are missing or outdated.
+    /// Returns the number of feeds processed.
+    async fn refresh_stale_stats(&self) -> Result<usize, FeedServiceError>;
 }
 
 #[async_trait]
@@ -47,6 +113,37 @@ impl FeedServiceApi for FeedService {
         Ok(feeds.into_iter().map(FeedResponse::from).collect())
     }
 
+    async fn get_user_feeds_page(
+        &self,
+        user_id: Uuid,
+        limit: Option<i64>,
+        cursor: Option<&str>,
+    ) -> Result<PageResponse<FeedResponse>, FeedServiceError> {
+        let page = PageRequest::parse(limit, cursor, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE)
+            .map_err(|e| FeedServiceError::Invalid(e.to_string()))?;
+        let feeds = self
+            .feed_repo
+            .find_page_by_user(user_id, page.after.as_ref(), page.fetch_limit())
+            .await
+            .map_err(|e| FeedServiceError::Dependency(e.to_string()))?;
+        let total = self
+            .feed_repo
+            .count_by_user(user_id)
+            .await
+            .map_err(|e| FeedServiceError::Dependency(e.to_string()))?;
+
+        Ok(PageResponse::from_rows(
+            feeds,
+            &page,
+            |feed| Cursor {
+                at: feed.created_at,
+                key: feed.id,
+            },
+            FeedResponse::from,
+        )
+        .with_total(total))
+    }
+
     async fn create_feed(
         &self,
         user_id: Uuid,
@@ -55,6 +152,7 @@ impl FeedServiceApi for FeedService {
         let user = self.find_user(user_id).await?;
 
         self.validate_url(&request.url)?;
+        let suggestion = self.resolve_suggestion(request.suggestion_id.as_deref())?;
 
         if self
             .feed_repo
@@ -65,30 +163,341 @@ impl FeedServiceApi for FeedService {
             return Err(FeedServiceError::Conflict);
         }
 
-        self.check_feed_limit(user_id, user.subscription_tier)
-            .await?;
+        self.check_feed_limit(&user).await?;
 
         self.feed_repo
-            .create(request.id, user_id, &request.url, &request.title)
+            .create(
+                request.id,
+                user_id,
+                &request.url,
+                // Served back through REST and GraphQL, and usually taken from the feed itself
+                &sanitize_text(&request.title),
+                suggestion.as_ref(),
+            )
             .await
             .map_err(|e| FeedServiceError::Dependency(e.to_string()))?;
 
+        // Stats need a network round trip; compute them without delaying the response
+        let feed_repo = self.feed_repo.clone();
+        let feed_fetcher = self.feed_fetcher.clone();
+        let account_events = self.account_events.clone();
+        tokio::spawn(
+            async move {
+                let entries =
+                    Self::refresh_feed_stats(&*feed_repo, &feed_fetcher, request.id, &request.url)
+                        .await;
+                account_events.publish(
+                    user_id,
+                    AccountEventKind::JobProgress,
+                    json!({
+                        "job": "feed_stats",
+                        "feed_id": request.id,
+                        "status": if entries.is_some() { "completed" } else { "failed" },
+                    }),
+                );
+            }
+            .in_current_span(),
+        );
+
         Ok(())
     }
 
     async fn delete_feed(&self, user_id: Uuid, feed_id: Uuid) -> Result<(), FeedServiceError> {
-        self.verify_feed_ownership(feed_id, user_id).await?;
+        let feed = self.verify_feed_ownership(feed_id, user_id).await?;
 
         self.feed_repo
             .delete(feed_id)
             .await
             .map_err(|e| FeedServiceError::Dependency(e.to_string()))?;
 
+        self.domain_events
+            .publish(DomainEvent::FeedDeleted {
+                user_id,
+                feed_id,
+                url: feed.url,
+            })
+            .await;
+
         Ok(())
     }
+
+    async fn validate_feeds(
+        &self,
+        user_id: Uuid,
+        urls: Vec<String>,
+    ) -> Result<Vec<FeedValidationResult>, FeedServiceError> {
+        if urls.is_empty() {
+            return Err(FeedServiceError::Invalid(
+                "At least one URL is required".to_string(),
+            ));
+        }
+
+        if urls.len() > MAX_URLS_PER_VALIDATION {
+            return Err(FeedServiceError::Invalid(format!(
+                "A maximum of {} URLs can be validated at once",
+                MAX_URLS_PER_VALIDATION
+            )));
+        }
+
+        let subscribed_urls: HashSet<String> = self
+            .feed_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| FeedServiceError::Dependency(e.to_string()))?
+            .into_iter()
+            .map(|feed| feed.url)
+            .collect();
+
+        let total = urls.len();
+        let mut tasks = JoinSet::new();
+
+        for (index, url) in urls.into_iter().enumerate() {
+            let conflict = subscribed_urls.contains(&url);
+            let valid_format = self.validate_url(&url).is_ok();
+            let fetcher = self.feed_fetcher.clone();
+
+            tasks.spawn(async move {
+                let result = if valid_format {
+                    Self::probe_feed(&fetcher, url, conflict).await
+                } else {
+                    FeedValidationResult {
+                        url,
+                        resolves: false,
+                        parses: false,
+                        title: None,
+                        conflict,
+                        error: Some("Invalid URL format".to_string()),
+                    }
+                };
+                (index, result)
+            });
+        }
+
+        // Tasks finish in any order; slot results back into request order
+        let mut results: Vec<Option<FeedValidationResult>> = (0..total).map(|_| None).collect();
+        while let Some(joined) = tasks.join_next().await {
+            let (index, result) =
+                joined.map_err(|e| FeedServiceError::Dependency(e.to_string()))?;
+            results[index] = Some(result);
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    async fn refresh_stale_stats(&self) -> Result<usize, FeedServiceError> {
+        let stale_before = Utc::now() - Duration::hours(STATS_MAX_AGE_HOURS);
+        let feeds = self
+            .feed_repo
+            .find_with_stale_stats(stale_before, STATS_REFRESH_BATCH_SIZE)
+            .await
+            .map_err(|e| FeedServiceError::Dependency(e.to_string()))?;
+
+        for feed in &feeds {
+            let entries =
+                Self::refresh_feed_stats(&*self.feed_repo, &self.feed_fetcher, feed.id, &feed.url)
+                    .await;
+            if let Some(entries) = entries {
+                self.notify_new_articles(feed, &entries).await;
+            }
+        }
+
+        Ok(feeds.len())
+    }
 }
 
 impl FeedService {
+    /// Fetch the feed and store fresh stats. Returns the feed's entries, or None when the
+    /// feed couldn't be fetched.
+    async fn refresh_feed_stats(
+        feed_repo: &dyn FeedRepositoryApi,
+        feed_fetcher: &FeedFetcher,
+        feed_id: Uuid,
+        url: &str,
+    ) -> Option<Vec<FetchedEntry>> {
+        let now = Utc::now();
+
+        let (result, entries) = match feed_fetcher.fetch(url).await {
+            Ok(fetched) => {
+                let stats = FeedStats::from_published_dates(&fetched.published_dates(), now);
+                (
+                    feed_repo.update_stats(feed_id, &stats, now).await,
+                    Some(fetched.entries),
+                )
+            }
+            Err(e) => {
+                // Keep the previous stats, but don't retry this feed on every run
+                tracing::debug!(feed_id = %feed_id, error = %e, "Failed to fetch feed for stats");
+                (feed_repo.mark_stats_checked(feed_id, now).await, None)
+            }
+        };
+
+        if let Err(e) = result {
+            tracing::warn!(feed_id = %feed_id, error = %e, "Failed to store feed stats");
+        }
+
+        entries
+    }
+
+    /// Tell the owner about entries published since the feed was last checked, leaving out
+    /// what they muted and stories another of their feeds already carried, and keeping to
+    /// their `max_article_age_days` and `max_items_per_feed` settings. A feed checked for
+    /// the first time has nothing to compare against, so it's skipped.
+    async fn notify_new_articles(&self, feed: &Feed, entries: &[FetchedEntry]) {
+        let Some(last_published_at) = feed.last_published_at else {
+            return;
+        };
+        let filter = match self.article_filter(feed.user_id).await {
+            Ok(filter) => filter,
+            Err(e) => {
+                tracing::warn!(feed_id = %feed.id, error = %e, "Failed to load article filters");
+                return;
+            }
+        };
+        let new_entries = filter.new_entries(feed.id, entries, last_published_at, Utc::now());
+        if new_entries.is_empty() {
+            return;
+        }
+        let new_articles = filter.capped(self.count_unseen_stories(feed, &new_entries).await);
+        if new_articles == 0 {
+            return;
+        }
+
+        self.account_events.publish(
+            feed.user_id,
+            AccountEventKind::NewArticles,
+            json!({
+                "feed_id": feed.id,
+                "url": feed.url,
+                "title": feed.title,
+                "new_articles": new_articles,
+            }),
+        );
+        self.domain_events
+            .publish(DomainEvent::NewArticles {
+                user_id: feed.user_id,
+                feed_id: feed.id,
+                url: feed.url.clone(),
+                title: feed.title.clone(),
+                new_articles,
+            })
+            .await;
+    }
+
+    /// Count the entries that aren't a story another of the owner's feeds carried
+    /// recently, and remember them for the feeds checked after this one. Entries without a
+    /// title can't be compared, so they always count.
+    async fn count_unseen_stories(&self, feed: &Feed, entries: &[&FetchedEntry]) -> usize {
+        let key = recent_stories_key(feed.user_id);
+        let mut recent: Vec<RecentStory> = match self.cache.get(&key).await {
+            Ok(entry) => entry
+                .and_then(|entry| serde_json::from_slice(&entry).ok())
+                .unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!(user_id = %feed.user_id, error = %e, "Failed to read recent stories");
+                Vec::new()
+            }
+        };
+        let oldest = Utc::now() - Duration::hours(RECENT_STORIES_TTL_HOURS);
+        recent.retain(|story| story.published_at >= oldest);
+
+        let untitled = entries.iter().filter(|entry| entry.title.is_none()).count();
+        let fresh: Vec<RecentStory> = entries
+            .iter()
+            .filter_map(|entry| {
+                Some(RecentStory {
+                    id: Uuid::new_v4(),
+                    feed_id: feed.id,
+                    title: entry.title.clone()?,
+                    published_at: entry.published_at,
+                })
+            })
+            .collect();
+        let stories: Vec<StoryTitle<'_>> = recent
+            .iter()
+            .chain(&fresh)
+            .map(|story| StoryTitle {
+                id: story.id,
+                feed_id: story.feed_id,
+                title: &story.title,
+            })
+            .collect();
+        let duplicates_of = DuplicateDetector::default().detect(&stories);
+
+        let mut unseen = Vec::new();
+        for (story, duplicate_of) in fresh.into_iter().zip(&duplicates_of[recent.len()..]) {
+            match duplicate_of {
+                Some(duplicate_of) => tracing::debug!(
+                    feed_id = %feed.id,
+                    duplicates_of = %duplicate_of,
+                    title = %story.title,
+                    "Leaving out a story another feed carried"
+                ),
+                None => unseen.push(story),
+            }
+        }
+        let unseen_count = unseen.len();
+
+        recent.extend(unseen);
+        let overflow = recent.len().saturating_sub(MAX_RECENT_STORIES);
+        recent.drain(..overflow);
+        let ttl = std::time::Duration::from_secs(RECENT_STORIES_TTL_HOURS as u64 * 3600);
+        if let Ok(entry) = serde_json::to_vec(&recent) {
+            if let Err(e) = self.cache.set(&key, entry, ttl).await {
+                tracing::warn!(user_id = %feed.user_id, error = %e, "Failed to store recent stories");
+            }
+        }
+
+        untitled + unseen_count
+    }
+
+    async fn probe_feed(
+        fetcher: &FeedFetcher,
+        url: String,
+        conflict: bool,
+    ) -> FeedValidationResult {
+        match fetcher.fetch(&url).await {
+            Ok(feed) => FeedValidationResult {
+                url,
+                resolves: true,
+                parses: true,
+                title: feed.title,
+                conflict,
+                error: None,
+            },
+            Err(FeedFetchError::Unreachable(message)) => FeedValidationResult {
+                url,
+                resolves: false,
+                parses: false,
+                title: None,
+                conflict,
+                error: Some(message),
+            },
+            Err(FeedFetchError::Parse(message)) => FeedValidationResult {
+                url,
+                resolves: true,
+                parses: false,
+                title: None,
+                conflict,
+                error: Some(message),
+            },
+        }
+    }
+
+    async fn article_filter(&self, user_id: Uuid) -> Result<ArticleFilter, FeedServiceError> {
+        let owner = self.find_user(user_id).await?;
+        let mute_rules = self
+            .mute_service
+            .get_rule_set(user_id)
+            .await
+            .map_err(|e| FeedServiceError::Dependency(e.to_string()))?;
+
+        Ok(ArticleFilter {
+            mute_rules,
+            max_age_days: owner.max_article_age_days(),
+            max_items: owner.max_items_per_feed(),
+        })
+    }
+
     async fn find_user(&self, user_id: Uuid) -> Result<User, FeedServiceError> {
         self.user_repo
             .find_by_id(user_id)
@@ -104,23 +513,36 @@ impl FeedService {
         Ok(())
     }
 
-    async fn check_feed_limit(
+    fn resolve_suggestion(
         &self,
-        user_id: Uuid,
-        tier: SubscriptionTier,
-    ) -> Result<(), FeedServiceError> {
+        suggestion_id: Option<&str>,
+    ) -> Result<Option<FeedSuggestion>, FeedServiceError> {
+        let Some(suggestion_id) = suggestion_id else {
+            return Ok(None);
+        };
+
+        self.feed_suggestions_repo
+            .find_suggestion(suggestion_id)
+            .map(Some)
+            .ok_or_else(|| FeedServiceError::Invalid("Unknown suggestion_id".to_string()))
+    }
+
+    async fn check_feed_limit(&self, user: &User) -> Result<(), FeedServiceError> {
+        if user.is_trial_expired() {
+            return Err(FeedServiceError::PaymentRequired(
+                TRIAL_EXPIRED_MESSAGE.to_string(),
+            ));
+        }
+
         let feed_count = self
             .feed_repo
-            .count_by_user(user_id)
+            .count_by_user(user.id)
             .await
             .map_err(|e| FeedServiceError::Dependency(e.to_string()))?;
 
-        let max_feeds = match tier {
-            SubscriptionTier::Free => MAX_FEEDS_FREE,
-            SubscriptionTier::Pro => MAX_FEEDS_PRO,
-        };
+        let max_feeds = self.plan_limits.for_user(user).max_feeds;
 
-        if feed_count >= max_feeds {
+        if feed_count >= i64::from(max_feeds) {
             return Err(FeedServiceError::PaymentRequired(format!(
                 "Free tier allows maximum {} feeds. Upgrade to Pro for unlimited feeds.",
                 max_feeds
@@ -149,3 +571,300 @@ impl FeedService {
         Ok(feed)
     }
 }
+
+/// What a user wants left out of the articles they're told about
+struct ArticleFilter {
+    mute_rules: MuteRuleSet,
+    max_age_days: Option<u32>,
+    max_items: Option<u32>,
+}
+
+impl ArticleFilter {
+    /// Entries of `feed_id` published after `last_published_at` that aren't muted or older
+    /// than `max_age_days`, oldest first
+    fn new_entries<'a>(
+        &self,
+        feed_id: Uuid,
+        entries: &'a [FetchedEntry],
+        last_published_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Vec<&'a FetchedEntry> {
+        let oldest = self
+            .max_age_days
+            .map(|days| now - Duration::days(i64::from(days)));
+        let mut new_entries: Vec<&FetchedEntry> = entries
+            .iter()
+            .filter(|entry| entry.published_at > last_published_at)
+            .filter(|entry| oldest.is_none_or(|oldest| entry.published_at >= oldest))
+            .filter(|entry| !self.mute_rules.is_muted(feed_id, &entry.text))
+            .collect();
+        new_entries.sort_by_key(|entry| entry.published_at);
+
+        new_entries
+    }
+
+    /// A count of new articles held to `max_items`
+    fn capped(&self, new_articles: usize) -> usize {
+        self.max_items
+            .map_or(new_articles, |max| new_articles.min(max as usize))
+    }
+}
+
+/// A story a user was told about
+#[derive(Serialize, Deserialize)]
+struct RecentStory {
+    id: Uuid,
+    feed_id: Uuid,
+    title: String,
+    published_at: DateTime<Utc>,
+}
+
+fn recent_stories_key(user_id: Uuid) -> String {
+    format!("recent_stories:{}", user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::events::InProcessEventBus;
+    use crate::domain::mute::{
+        CreateMuteRuleRequest, MuteRule, MuteRuleKind, MuteRuleResponse, MuteServiceError,
+    };
+    use crate::infrastructure::cache::MemoryCacheStore;
+    use crate::infrastructure::repositories::{
+        HardcodedFeedSuggestionsRepository, InMemoryFeedRepository, InMemoryUserRepository,
+    };
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Nothing muted
+    struct NoMuteRules;
+
+    #[async_trait]
+    impl MuteServiceApi for NoMuteRules {
+        async fn list_rules(
+            &self,
+            _user_id: Uuid,
+        ) -> Result<Vec<MuteRuleResponse>, MuteServiceError> {
+            Ok(Vec::new())
+        }
+
+        async fn create_rule(
+            &self,
+            _user_id: Uuid,
+            _request: CreateMuteRuleRequest,
+        ) -> Result<MuteRuleResponse, MuteServiceError> {
+            unimplemented!("mute rules aren't created in these tests")
+        }
+
+        async fn delete_rule(
+            &self,
+            _user_id: Uuid,
+            _rule_id: Uuid,
+        ) -> Result<(), MuteServiceError> {
+            Ok(())
+        }
+
+        async fn get_rule_set(&self, _user_id: Uuid) -> Result<MuteRuleSet, MuteServiceError> {
+            Ok(MuteRuleSet::new(&[]))
+        }
+    }
+
+    fn rss(titles: &[&str], published_at: DateTime<Utc>) -> String {
+        let items: String = titles
+            .iter()
+            .map(|title| {
+                format!(
+                    "<item><title>{}</title><pubDate>{}</pubDate></item>",
+                    title,
+                    published_at.to_rfc2822()
+                )
+            })
+            .collect();
+        format!(
+            r#"<rss version="2.0"><channel><title>News</title>{}</channel></rss>"#,
+            items
+        )
+    }
+
+    fn entries(now: DateTime<Utc>, days_ago: &[(i64, &str)]) -> Vec<FetchedEntry> {
+        days_ago
+            .iter()
+            .map(|(days, title)| FetchedEntry {
+                published_at: now - Duration::days(*days),
+                title: title.lines().next().map(str::to_string),
+                text: title.to_string(),
+            })
+            .collect()
+    }
+
+    impl ArticleFilter {
+        fn count_new(
+            &self,
+            feed_id: Uuid,
+            entries: &[FetchedEntry],
+            last_published_at: DateTime<Utc>,
+            now: DateTime<Utc>,
+        ) -> usize {
+            self.capped(
+                self.new_entries(feed_id, entries, last_published_at, now)
+                    .len(),
+            )
+        }
+    }
+
+    fn filter(
+        rules: &[MuteRule],
+        max_age_days: Option<u32>,
+        max_items: Option<u32>,
+    ) -> ArticleFilter {
+        ArticleFilter {
+            mute_rules: MuteRuleSet::new(rules),
+            max_age_days,
+            max_items,
+        }
+    }
+
+    fn global_rule(pattern: &str) -> MuteRule {
+        MuteRule {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            feed_id: None,
+            pattern: pattern.to_string(),
+            kind: MuteRuleKind::Keyword,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_new_articles_are_those_after_the_last_check() {
+        let now = Utc::now();
+        let entries = entries(now, &[(0, "a"), (1, "b"), (5, "c"), (10, "d")]);
+
+        let count = filter(&[], None, None).count_new(
+            Uuid::new_v4(),
+            &entries,
+            now - Duration::days(3),
+            now,
+        );
+
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_new_articles_respect_the_owner_settings() {
+        let now = Utc::now();
+        let feed_id = Uuid::new_v4();
+        let entries = entries(
+            now,
+            &[(0, "a"), (1, "b"), (2, "c"), (5, "d"), (8, "e"), (20, "f")],
+        );
+        let last_published_at = now - Duration::days(30);
+
+        // Too old for the owner
+        let count = filter(&[], Some(7), None).count_new(feed_id, &entries, last_published_at, now);
+        assert_eq!(count, 4);
+        // A heavy feed counts no more than the cap
+        let count = filter(&[], None, Some(3)).count_new(feed_id, &entries, last_published_at, now);
+        assert_eq!(count, 3);
+        let count =
+            filter(&[], Some(1), Some(3)).count_new(feed_id, &entries, last_published_at, now);
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_muted_articles_are_not_counted() {
+        let now = Utc::now();
+        let entries = entries(
+            now,
+            &[
+                (0, "Final score: Madrid 2-1 Barcelona"),
+                (1, "Sponsored: the best laptops\nBuy now"),
+                (2, "A regular post"),
+            ],
+        );
+        let filter = filter(
+            &[global_rule("final score"), global_rule("sponsored")],
+            None,
+            None,
+        );
+
+        let count = filter.count_new(Uuid::new_v4(), &entries, now - Duration::days(3), now);
+
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_a_story_carried_by_two_feeds_is_notified_once() {
+        let now = Utc::now();
+        let server = MockServer::start().await;
+        for (feed_path, title) in [
+            (
+                "/wire",
+                "Central bank raises interest rates to fight inflation - Reuters",
+            ),
+            (
+                "/paper",
+                "Central bank raises interest rates to fight inflation",
+            ),
+        ] {
+            Mock::given(path(feed_path))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(rss(&[title], now - Duration::hours(1))),
+                )
+                .mount(&server)
+                .await;
+        }
+
+        let user_repo = Arc::new(InMemoryUserRepository::new());
+        let user = user_repo
+            .create("reader@example.com", "google", "g-1", None, None)
+            .await
+            .unwrap();
+        let feed_repo = Arc::new(InMemoryFeedRepository::new());
+        let last_checked = now - Duration::days(2);
+        for feed_path in ["/wire", "/paper"] {
+            let feed_id = Uuid::new_v4();
+            feed_repo
+                .create(
+                    feed_id,
+                    user.id,
+                    &format!("{}{}", server.uri(), feed_path),
+                    "News",
+                    None,
+                )
+                .await
+                .unwrap();
+            let stats = FeedStats::from_published_dates(&[last_checked], last_checked);
+            feed_repo
+                .update_stats(feed_id, &stats, last_checked)
+                .await
+                .unwrap();
+        }
+        let account_events = Arc::new(AccountEventStream::new());
+        let mut notifications = account_events.subscribe();
+        let service = FeedService::new(
+            feed_repo,
+            user_repo,
+            Arc::new(FeedFetcher::for_loopback()),
+            Arc::new(HardcodedFeedSuggestionsRepository::new()),
+            Arc::new(PlanLimits::default()),
+            Arc::new(InProcessEventBus::new(vec![])),
+            account_events,
+            Arc::new(NoMuteRules),
+            Arc::new(MemoryCacheStore::new()),
+        );
+
+        let refreshed = service.refresh_stale_stats().await.unwrap();
+
+        assert_eq!(refreshed, 2);
+        let mut new_articles = Vec::new();
+        while let Ok(event) = notifications.try_recv() {
+            if event.kind == AccountEventKind::NewArticles {
+                new_articles.push(event.data["new_articles"].clone());
+            }
+        }
+        assert_eq!(new_articles, vec![json!(1)]);
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Activity statistics for a feed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FeedStats {
+    pub avg_articles_per_day: Option<f64>,
+    pub last_published_at: Option<DateTime<Utc>>,
+}
+
+impl FeedStats {
+    /// Compute stats from the publication dates of the entries a feed currently exposes.
+    ///
+    /// Feeds only list their most recent entries, so the rate is measured from the oldest
+    /// entry up to `now` (at least one day). Measuring up to `now` rather than the newest
+    /// entry makes feeds that stopped publishing trend towards zero.
+    pub fn from_published_dates(dates: &[DateTime<Utc>], now: DateTime<Utc>) -> Self {
+        let (Some(oldest), Some(newest)) = (dates.iter().min(), dates.iter().max()) else {
+            return Self::default();
+        };
+
+        let window_days = ((now - *oldest).num_seconds() as f64 / SECONDS_PER_DAY).max(1.0);
+        let avg_articles_per_day = dates.len() as f64 / window_days;
+
+        Self {
+            avg_articles_per_day: Some((avg_articles_per_day * 100.0).round() / 100.0),
+            last_published_at: Some((*newest).min(now)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_empty_feed_has_no_stats() {
+        let stats = FeedStats::from_published_dates(&[], Utc::now());
+        assert_eq!(stats, FeedStats::default());
+    }
+
+    #[test]
+    fn test_average_over_covered_window() {
+        let now = Utc::now();
+        let dates: Vec<_> = (0..10).map(|i| now - Duration::hours(i * 12)).collect();
+
+        let stats = FeedStats::from_published_dates(&dates, now);
+
+        // 10 entries over 4.5 days
+        assert_eq!(stats.avg_articles_per_day, Some(2.22));
+        assert_eq!(stats.last_published_at, Some(now));
+    }
+
+    #[test]
+    fn test_dead_feed_trends_to_zero() {
+        let now = Utc::now();
+        let dates: Vec<_> = (0..5)
+            .map(|i| now - Duration::days(365) - Duration::days(i))
+            .collect();
+
+        let stats = FeedStats::from_published_dates(&dates, now);
+
+        assert!(stats.avg_articles_per_day.unwrap() < 0.02);
+        assert_eq!(stats.last_published_at, Some(now - Duration::days(365)));
+    }
+}
@@ -0,0 +1,40 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SuggestionModerationError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("submission not found")]
+    NotFound,
+    #[error("conflict: {0}")]
+    Conflict(String),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for SuggestionModerationError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => SuggestionModerationError::Invalid(msg),
+            AppError::NotFound(_) => SuggestionModerationError::NotFound,
+            AppError::Conflict(msg) => SuggestionModerationError::Conflict(msg),
+            _ => SuggestionModerationError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<SuggestionModerationError> for AppError {
+    fn from(err: SuggestionModerationError) -> Self {
+        match err {
+            SuggestionModerationError::Invalid(msg) => AppError::BadRequest(msg),
+            SuggestionModerationError::NotFound => {
+                AppError::NotFound("Submission not found".to_string())
+            }
+            SuggestionModerationError::Conflict(msg) => AppError::Conflict(msg),
+            SuggestionModerationError::Dependency(msg) => AppError::Internal(msg),
+            SuggestionModerationError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
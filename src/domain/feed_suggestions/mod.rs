@@ -22,8 +22,20 @@ pub struct FeedSuggestion {
 pub trait FeedSuggestionsRepository: Send + Sync {
     fn get_all_categories(&self) -> Vec<Category>;
     fn get_suggestions_by_categories(&self, category_ids: &[String]) -> Vec<FeedSuggestion>;
+    fn find_suggestion(&self, suggestion_id: &str) -> Option<FeedSuggestion>;
+    /// Replace the suggestions promoted from user submissions, served after the curated ones
+    fn set_promoted(&self, suggestions: Vec<FeedSuggestion>);
 }
 
-// Re-export service
+pub mod error;
+pub mod moderation;
 pub mod service;
+pub mod submission;
+
+pub use error::SuggestionModerationError;
+pub use moderation::{SuggestionModerationService, SuggestionModerationServiceApi};
 pub use service::FeedSuggestionsService;
+pub use submission::{
+    ApproveSubmissionRequest, ListSubmissionsQuery, RejectSubmissionRequest, SubmissionStatus,
+    SubmitSuggestionRequest, SuggestionSubmission, SuggestionSubmissionsResponse,
+};
@@ -0,0 +1,455 @@
+use super::error::SuggestionModerationError;
+use super::submission::{
+    ApproveSubmissionRequest, ListSubmissionsQuery, RejectSubmissionRequest, SubmissionStatus,
+    SubmitSuggestionRequest, SuggestionSubmission, SuggestionSubmissionsResponse,
+};
+use super::FeedSuggestionsRepository;
+use crate::domain::audit::{AuditAction, AuditEvent, AuditService, AuditServiceApi};
+use crate::infrastructure::email::{EmailSender, EmailTemplate};
+use crate::infrastructure::repositories::{
+    SuggestionSubmissionRepository, UserRepository, UserRepositoryApi,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Url;
+use serde_json::json;
+use std::sync::Arc;
+use tracing::Instrument;
+use uuid::Uuid;
+
+const MAX_URL_LENGTH: usize = 2048;
+const MAX_TITLE_LENGTH: usize = 200;
+const MAX_DESCRIPTION_LENGTH: usize = 1000;
+const MAX_REASON_LENGTH: usize = 500;
+/// Submissions a user can have waiting for review at once
+const MAX_PENDING_PER_USER: i64 = 5;
+
+pub struct SuggestionModerationService {
+    submission_repo: Arc<SuggestionSubmissionRepository>,
+    suggestions_repo: Arc<dyn FeedSuggestionsRepository>,
+    user_repo: Arc<UserRepository>,
+    email_sender: Arc<dyn EmailSender>,
+    audit_service: Arc<AuditService>,
+}
+
+impl SuggestionModerationService {
+    pub fn new(
+        submission_repo: Arc<SuggestionSubmissionRepository>,
+        suggestions_repo: Arc<dyn FeedSuggestionsRepository>,
+        user_repo: Arc<UserRepository>,
+        email_sender: Arc<dyn EmailSender>,
+        audit_service: Arc<AuditService>,
+    ) -> Self {
+        Self {
+            submission_repo,
+            suggestions_repo,
+            user_repo,
+            email_sender,
+            audit_service,
+        }
+    }
+}
+
+#[async_trait]
+pub trait SuggestionModerationServiceApi: Send + Sync {
+    /// Queue a feed for an admin to review
+    async fn submit(
+        &self,
+        user_id: Uuid,
+        request: SubmitSuggestionRequest,
+    ) -> Result<SuggestionSubmission, SuggestionModerationError>;
+
+    async fn list_submissions(
+        &self,
+        query: ListSubmissionsQuery,
+    ) -> Result<SuggestionSubmissionsResponse, SuggestionModerationError>;
+
+    /// List a pending submission under a category and let the submitter know
+    async fn approve(
+        &self,
+        admin_email: &str,
+        id: Uuid,
+        request: ApproveSubmissionRequest,
+    ) -> Result<SuggestionSubmission, SuggestionModerationError>;
+
+    /// Turn a pending submission down, telling the submitter why
+    async fn reject(
+        &self,
+        admin_email: &str,
+        id: Uuid,
+        request: RejectSubmissionRequest,
+    ) -> Result<SuggestionSubmission, SuggestionModerationError>;
+
+    /// Re-read the approved submissions into the suggestions store. Approvals on this
+    /// instance apply at once; other instances' show up on the next reload.
+    async fn reload(&self) -> Result<usize, SuggestionModerationError>;
+}
+
+#[async_trait]
+impl SuggestionModerationServiceApi for SuggestionModerationService {
+    async fn submit(
+        &self,
+        user_id: Uuid,
+        request: SubmitSuggestionRequest,
+    ) -> Result<SuggestionSubmission, SuggestionModerationError> {
+        let url = validate_url(&request.url)?;
+        let title = validate_title(&request.title)?;
+        let description = validate_description(request.description.as_deref())?;
+
+        if self.is_suggested(&url) {
+            return Err(SuggestionModerationError::Conflict(
+                "This feed is already suggested".to_string(),
+            ));
+        }
+        if self
+            .submission_repo
+            .find_open_by_url(&url)
+            .await
+            .map_err(|e| SuggestionModerationError::Dependency(e.to_string()))?
+            .is_some()
+        {
+            return Err(SuggestionModerationError::Conflict(
+                "This feed has already been submitted".to_string(),
+            ));
+        }
+        let pending = self
+            .submission_repo
+            .count_pending_by_user(user_id)
+            .await
+            .map_err(|e| SuggestionModerationError::Dependency(e.to_string()))?;
+        if pending >= MAX_PENDING_PER_USER {
+            return Err(SuggestionModerationError::Invalid(format!(
+                "You can have at most {} submissions waiting for review",
+                MAX_PENDING_PER_USER
+            )));
+        }
+
+        let submission = SuggestionSubmission {
+            id: Uuid::new_v4(),
+            user_id: Some(user_id),
+            url,
+            title,
+            description,
+            status: SubmissionStatus::Pending,
+            category_id: None,
+            rejection_reason: None,
+            reviewed_by: None,
+            reviewed_at: None,
+            created_at: Utc::now(),
+        };
+        self.submission_repo
+            .create(&submission)
+            .await
+            .map_err(|e| SuggestionModerationError::Dependency(e.to_string()))?;
+
+        tracing::info!(
+            submission_id = %submission.id,
+            user_id = %user_id,
+            url = %submission.url,
+            "Feed suggestion submitted"
+        );
+
+        Ok(submission)
+    }
+
+    async fn list_submissions(
+        &self,
+        query: ListSubmissionsQuery,
+    ) -> Result<SuggestionSubmissionsResponse, SuggestionModerationError> {
+        let submissions = self
+            .submission_repo
+            .find_by_status(query.status)
+            .await
+            .map_err(|e| SuggestionModerationError::Dependency(e.to_string()))?;
+
+        Ok(SuggestionSubmissionsResponse { submissions })
+    }
+
+    async fn approve(
+        &self,
+        admin_email: &str,
+        id: Uuid,
+        request: ApproveSubmissionRequest,
+    ) -> Result<SuggestionSubmission, SuggestionModerationError> {
+        let category_id = request.category_id.trim();
+        if !self
+            .suggestions_repo
+            .get_all_categories()
+            .iter()
+            .any(|category| category.id == category_id)
+        {
+            return Err(SuggestionModerationError::Invalid(
+                "Unknown category_id".to_string(),
+            ));
+        }
+
+        let mut submission = self.find_pending(id).await?;
+        if let Some(title) = request.title.as_deref() {
+            submission.title = validate_title(title)?;
+        }
+        if request.description.is_some() {
+            submission.description = validate_description(request.description.as_deref())?;
+        }
+        submission.status = SubmissionStatus::Approved;
+        submission.category_id = Some(category_id.to_string());
+        submission.reviewed_by = Some(admin_email.to_string());
+        submission.reviewed_at = Some(Utc::now());
+
+        let submission = self.save_review(submission).await?;
+        self.reload().await?;
+
+        tracing::info!(
+            submission_id = %submission.id,
+            category_id,
+            admin = %admin_email,
+            "Feed suggestion approved"
+        );
+        self.record_review(
+            admin_email,
+            AuditAction::SuggestionApproved,
+            &submission,
+            json!({ "url": submission.url, "category_id": category_id }),
+        )
+        .await;
+        self.notify_submitter(
+            &submission,
+            EmailTemplate::SuggestionApproved {
+                title: submission.title.clone(),
+            },
+        )
+        .await;
+
+        Ok(submission)
+    }
+
+    async fn reject(
+        &self,
+        admin_email: &str,
+        id: Uuid,
+        request: RejectSubmissionRequest,
+    ) -> Result<SuggestionSubmission, SuggestionModerationError> {
+        let reason = request.reason.trim();
+        if reason.is_empty() {
+            return Err(SuggestionModerationError::Invalid(
+                "A reason is required".to_string(),
+            ));
+        }
+        if reason.chars().count() > MAX_REASON_LENGTH {
+            return Err(SuggestionModerationError::Invalid(format!(
+                "Reason cannot exceed {} characters",
+                MAX_REASON_LENGTH
+            )));
+        }
+
+        let mut submission = self.find_pending(id).await?;
+        submission.status = SubmissionStatus::Rejected;
+        submission.rejection_reason = Some(reason.to_string());
+        submission.reviewed_by = Some(admin_email.to_string());
+        submission.reviewed_at = Some(Utc::now());
+
+        let submission = self.save_review(submission).await?;
+
+        tracing::info!(
+            submission_id = %submission.id,
+            admin = %admin_email,
+            "Feed suggestion rejected"
+        );
+        self.record_review(
+            admin_email,
+            AuditAction::SuggestionRejected,
+            &submission,
+            json!({ "url": submission.url, "reason": reason }),
+        )
+        .await;
+        self.notify_submitter(
+            &submission,
+            EmailTemplate::SuggestionRejected {
+                title: submission.title.clone(),
+                reason: reason.to_string(),
+            },
+        )
+        .await;
+
+        Ok(submission)
+    }
+
+    async fn reload(&self) -> Result<usize, SuggestionModerationError> {
+        let promoted: Vec<_> = self
+            .submission_repo
+            .find_by_status(SubmissionStatus::Approved)
+            .await
+            .map_err(|e| SuggestionModerationError::Dependency(e.to_string()))?
+            .iter()
+            .filter_map(SuggestionSubmission::to_suggestion)
+            .collect();
+        let count = promoted.len();
+        self.suggestions_repo.set_promoted(promoted);
+
+        Ok(count)
+    }
+}
+
+impl SuggestionModerationService {
+    /// Whether a suggestion, curated or promoted, already points at this URL
+    fn is_suggested(&self, url: &str) -> bool {
+        let category_ids: Vec<String> = self
+            .suggestions_repo
+            .get_all_categories()
+            .into_iter()
+            .map(|category| category.id)
+            .collect();
+        self.suggestions_repo
+            .get_suggestions_by_categories(&category_ids)
+            .iter()
+            .any(|suggestion| suggestion.url == url)
+    }
+
+    async fn find_pending(
+        &self,
+        id: Uuid,
+    ) -> Result<SuggestionSubmission, SuggestionModerationError> {
+        let submission = self
+            .submission_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| SuggestionModerationError::Dependency(e.to_string()))?
+            .ok_or(SuggestionModerationError::NotFound)?;
+        if submission.status != SubmissionStatus::Pending {
+            return Err(already_reviewed());
+        }
+
+        Ok(submission)
+    }
+
+    async fn save_review(
+        &self,
+        submission: SuggestionSubmission,
+    ) -> Result<SuggestionSubmission, SuggestionModerationError> {
+        self.submission_repo
+            .save_review(&submission)
+            .await
+            .map_err(|e| SuggestionModerationError::Dependency(e.to_string()))?
+            .ok_or_else(already_reviewed)
+    }
+
+    async fn record_review(
+        &self,
+        admin_email: &str,
+        action: AuditAction,
+        submission: &SuggestionSubmission,
+        details: serde_json::Value,
+    ) {
+        let mut event = AuditEvent::by_admin(admin_email, action)
+            .target(submission.id)
+            .details(details);
+        if let Some(user_id) = submission.user_id {
+            event = event.for_user(user_id);
+        }
+        self.audit_service.record(event).await;
+    }
+
+    /// Email the submitter the outcome in the background. A submitter whose account is
+    /// gone isn't told.
+    async fn notify_submitter(&self, submission: &SuggestionSubmission, template: EmailTemplate) {
+        let Some(user_id) = submission.user_id else {
+            return;
+        };
+        let user = match self.user_repo.find_by_id(user_id).await {
+            Ok(Some(user)) => user,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, error = %e, "Failed to look up submitter");
+                return;
+            }
+        };
+
+        let message = template.render(&user.email);
+        let email_sender = self.email_sender.clone();
+        tokio::spawn(
+            async move {
+                if let Err(e) = email_sender.send(&message).await {
+                    tracing::warn!(
+                        user_id = %user_id,
+                        error = %e,
+                        "Failed to send suggestion review email"
+                    );
+                }
+            }
+            .in_current_span(),
+        );
+    }
+}
+
+fn already_reviewed() -> SuggestionModerationError {
+    SuggestionModerationError::Conflict("Submission was already reviewed".to_string())
+}
+
+/// The trimmed URL, if it is an http(s) URL
+fn validate_url(url: &str) -> Result<String, SuggestionModerationError> {
+    let url = url.trim();
+    let invalid = || SuggestionModerationError::Invalid("URL must be an http(s) URL".to_string());
+
+    if url.len() > MAX_URL_LENGTH {
+        return Err(SuggestionModerationError::Invalid(format!(
+            "URL cannot exceed {} characters",
+            MAX_URL_LENGTH
+        )));
+    }
+    let parsed = Url::parse(url).map_err(|_| invalid())?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(invalid());
+    }
+
+    Ok(url.to_string())
+}
+
+fn validate_title(title: &str) -> Result<String, SuggestionModerationError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(SuggestionModerationError::Invalid(
+            "A title is required".to_string(),
+        ));
+    }
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(SuggestionModerationError::Invalid(format!(
+            "Title cannot exceed {} characters",
+            MAX_TITLE_LENGTH
+        )));
+    }
+
+    Ok(title.to_string())
+}
+
+fn validate_description(description: Option<&str>) -> Result<String, SuggestionModerationError> {
+    let description = description.unwrap_or_default().trim();
+    if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        return Err(SuggestionModerationError::Invalid(format!(
+            "Description cannot exceed {} characters",
+            MAX_DESCRIPTION_LENGTH
+        )));
+    }
+
+    Ok(description.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_url_accepts_only_web_urls() {
+        assert_eq!(
+            validate_url(" https://example.com/feed.xml ").unwrap(),
+            "https://example.com/feed.xml"
+        );
+        assert!(validate_url("ftp://example.com/feed.xml").is_err());
+        assert!(validate_url("example.com/feed.xml").is_err());
+    }
+
+    #[test]
+    fn test_validate_title_requires_text() {
+        assert_eq!(validate_title("  Rust Blog ").unwrap(), "Rust Blog");
+        assert!(validate_title("   ").is_err());
+        assert!(validate_title(&"a".repeat(MAX_TITLE_LENGTH + 1)).is_err());
+    }
+}
@@ -1,13 +1,26 @@
 use super::{Category, FeedSuggestion, FeedSuggestionsRepository};
-use std::sync::Arc;
+use crate::infrastructure::feed_fetcher::FeedFetcher;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use tokio::task::JoinSet;
 
 pub struct FeedSuggestionsService {
     repository: Arc<dyn FeedSuggestionsRepository>,
+    feed_fetcher: Arc<FeedFetcher>,
+    /// IDs of suggestions whose URL failed the last link check
+    broken_suggestions: RwLock<HashSet<String>>,
 }
 
 impl FeedSuggestionsService {
-    pub fn new(repository: Arc<dyn FeedSuggestionsRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn FeedSuggestionsRepository>,
+        feed_fetcher: Arc<FeedFetcher>,
+    ) -> Self {
+        Self {
+            repository,
+            feed_fetcher,
+            broken_suggestions: RwLock::new(HashSet::new()),
+        }
     }
 
     /// Returns all available categories for display in UI
@@ -15,7 +28,7 @@ impl FeedSuggestionsService {
         self.repository.get_all_categories()
     }
 
-    /// Returns feed suggestions filtered by categories
+    /// Returns feed suggestions filtered by categories, excluding suggestions flagged as broken
     /// Returns empty Vec if category_ids is empty
     pub fn get_suggestions(&self, category_ids: Vec<String>) -> Vec<FeedSuggestion> {
         if category_ids.is_empty() {
@@ -28,6 +41,151 @@ impl FeedSuggestionsService {
             "Fetching suggestions for categories"
         );
 
-        self.repository.get_suggestions_by_categories(&category_ids)
+        let broken = self.broken_suggestions.read().unwrap();
+        self.repository
+            .get_suggestions_by_categories(&category_ids)
+            .into_iter()
+            .filter(|suggestion| !broken.contains(&suggestion.id))
+            .collect()
+    }
+
+    /// Every suggestion, including broken ones, paired with whether the last link check
+    /// flagged it
+    pub fn get_suggestions_with_link_status(&self) -> Vec<(FeedSuggestion, bool)> {
+        let category_ids: Vec<String> = self
+            .repository
+            .get_all_categories()
+            .into_iter()
+            .map(|category| category.id)
+            .collect();
+        let broken = self.broken_suggestions.read().unwrap();
+        self.repository
+            .get_suggestions_by_categories(&category_ids)
+            .into_iter()
+            .map(|suggestion| {
+                let is_broken = broken.contains(&suggestion.id);
+                (suggestion, is_broken)
+            })
+            .collect()
+    }
+
+    /// Fetch every suggestion URL and flag the ones that don't resolve to a valid feed.
+    /// Suggestions that pass are unflagged. Returns the number of broken suggestions.
+    pub async fn check_suggestion_links(&self) -> usize {
+        let category_ids: Vec<String> = self
+            .repository
+            .get_all_categories()
+            .into_iter()
+            .map(|category| category.id)
+            .collect();
+        let suggestions = self.repository.get_suggestions_by_categories(&category_ids);
+
+        let mut tasks = JoinSet::new();
+        for suggestion in suggestions {
+            let fetcher = self.feed_fetcher.clone();
+            tasks.spawn(async move {
+                let result = fetcher.fetch(&suggestion.url).await;
+                if let Err(ref e) = result {
+                    tracing::warn!(
+                        suggestion_id = %suggestion.id,
+                        url = %suggestion.url,
+                        error = %e,
+                        "Feed suggestion failed link check"
+                    );
+                }
+                (suggestion.id, result.is_ok())
+            });
+        }
+
+        let mut results = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => results.push(result),
+                Err(e) => tracing::error!(error = %e, "Feed suggestion link check task failed"),
+            }
+        }
+
+        self.record_link_check(results)
+    }
+}
+
+impl FeedSuggestionsService {
+    fn record_link_check(&self, results: Vec<(String, bool)>) -> usize {
+        let mut broken = self.broken_suggestions.write().unwrap();
+        for (suggestion_id, healthy) in results {
+            if healthy {
+                broken.remove(&suggestion_id);
+            } else {
+                broken.insert(suggestion_id);
+            }
+        }
+        broken.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StubRepository;
+
+    impl FeedSuggestionsRepository for StubRepository {
+        fn get_all_categories(&self) -> Vec<Category> {
+            Vec::new()
+        }
+
+        fn get_suggestions_by_categories(&self, _category_ids: &[String]) -> Vec<FeedSuggestion> {
+            ["ok", "broken"]
+                .iter()
+                .map(|id| FeedSuggestion {
+                    id: id.to_string(),
+                    title: id.to_string(),
+                    description: String::new(),
+                    url: format!("https://{}.example.com/rss", id),
+                    category_id: "news".to_string(),
+                })
+                .collect()
+        }
+
+        fn find_suggestion(&self, _suggestion_id: &str) -> Option<FeedSuggestion> {
+            None
+        }
+
+        fn set_promoted(&self, _suggestions: Vec<FeedSuggestion>) {}
+    }
+
+    fn service() -> FeedSuggestionsService {
+        FeedSuggestionsService::new(Arc::new(StubRepository), Arc::new(FeedFetcher::new()))
+    }
+
+    fn suggestion_ids(service: &FeedSuggestionsService) -> Vec<String> {
+        service
+            .get_suggestions(vec!["news".to_string()])
+            .into_iter()
+            .map(|s| s.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_broken_suggestions_are_excluded() {
+        let service = service();
+
+        let broken_count = service.record_link_check(vec![
+            ("ok".to_string(), true),
+            ("broken".to_string(), false),
+        ]);
+
+        assert_eq!(broken_count, 1);
+        assert_eq!(suggestion_ids(&service), vec!["ok"]);
+    }
+
+    #[test]
+    fn test_recovered_suggestions_are_restored() {
+        let service = service();
+
+        service.record_link_check(vec![("broken".to_string(), false)]);
+        service.record_link_check(vec![("broken".to_string(), true)]);
+
+        assert_eq!(suggestion_ids(&service), vec!["ok", "broken"]);
     }
 }
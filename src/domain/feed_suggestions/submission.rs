@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::FeedSuggestion;
+
+/// Prefix of the ids suggestions promoted from a submission are served under
+const PROMOTED_ID_PREFIX: &str = "community-";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Default)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionStatus {
+    /// Waiting for an admin
+    #[default]
+    Pending,
+    /// Listed among the suggestions
+    Approved,
+    Rejected,
+}
+
+/// A feed a user proposed for the suggestions list
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SuggestionSubmission {
+    pub id: Uuid,
+    /// Absent once the submitter's account is gone
+    pub user_id: Option<Uuid>,
+    pub url: String,
+    pub title: String,
+    pub description: String,
+    pub status: SubmissionStatus,
+    /// Category the suggestion was approved into
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection_reason: Option<String>,
+    /// Admin who reviewed it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewed_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SuggestionSubmission {
+    /// The suggestion an approved submission is served as
+    pub fn to_suggestion(&self) -> Option<FeedSuggestion> {
+        if self.status != SubmissionStatus::Approved {
+            return None;
+        }
+
+        Some(FeedSuggestion {
+            id: format!("{}{}", PROMOTED_ID_PREFIX, self.id),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            url: self.url.clone(),
+            category_id: self.category_id.clone()?,
+        })
+    }
+}
+
+/// Request for POST /api/feed-suggestions/submissions
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitSuggestionRequest {
+    pub url: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Query for GET /admin/suggestion-submissions
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ListSubmissionsQuery {
+    /// Pending when absent
+    #[serde(default)]
+    pub status: SubmissionStatus,
+}
+
+/// Request for POST /admin/suggestion-submissions/{submissionId}/approve
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApproveSubmissionRequest {
+    pub category_id: String,
+    /// Replace the submitter's wording before it is listed
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Request for POST /admin/suggestion-submissions/{submissionId}/reject
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RejectSubmissionRequest {
+    /// Passed on to the submitter
+    pub reason: String,
+}
+
+/// Response for GET /admin/suggestion-submissions
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuggestionSubmissionsResponse {
+    pub submissions: Vec<SuggestionSubmission>,
+}
@@ -0,0 +1,34 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IpBlockServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("ip block not found")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for IpBlockServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => IpBlockServiceError::Invalid(msg),
+            AppError::NotFound(_) => IpBlockServiceError::NotFound,
+            _ => IpBlockServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<IpBlockServiceError> for AppError {
+    fn from(err: IpBlockServiceError) -> Self {
+        match err {
+            IpBlockServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            IpBlockServiceError::NotFound => AppError::NotFound("IP block not found".to_string()),
+            IpBlockServiceError::Dependency(msg) => AppError::Internal(msg),
+            IpBlockServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
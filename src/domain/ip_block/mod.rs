@@ -0,0 +1,27 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::IpBlockServiceError;
+pub use model::{IpBlock, IpBlockSource};
+pub use service::{IpBlockService, IpBlockServiceApi};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Request for POST /admin/ip-blocks
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateIpBlockRequest {
+    /// Address or CIDR range
+    pub network: String,
+    pub reason: String,
+    /// Block until removed when absent
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Response for GET /admin/ip-blocks
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IpBlocksResponse {
+    pub blocks: Vec<IpBlock>,
+}
@@ -0,0 +1,298 @@
+use super::error::IpBlockServiceError;
+use super::model::{IpBlock, IpBlockSource};
+use super::{CreateIpBlockRequest, IpBlocksResponse};
+use crate::domain::audit::{AuditAction, AuditEvent, AuditService, AuditServiceApi};
+use crate::infrastructure::cache::CacheStore;
+use crate::infrastructure::config::AuthFailureBlockConfig;
+use crate::infrastructure::repositories::IpBlockRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use ipnet::IpNet;
+use serde_json::json;
+use std::net::IpAddr;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+const MAX_REASON_LENGTH: usize = 500;
+/// Actor recorded in the audit log for automatic blocks
+const AUTH_FAILURES_ACTOR: &str = "auth_failures";
+
+/// A block in the form the request path checks against
+struct ActiveBlock {
+    network: IpNet,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+pub struct IpBlockService {
+    ip_block_repo: Arc<IpBlockRepository>,
+    cache_store: Arc<dyn CacheStore>,
+    audit_service: Arc<AuditService>,
+    auth_failure_block: Option<AuthFailureBlockConfig>,
+    /// Copy of the table, so checking a request never touches the database. Changes made
+    /// by this instance apply at once; other instances' show up on the next reload.
+    active: RwLock<Vec<ActiveBlock>>,
+}
+
+impl IpBlockService {
+    pub fn new(
+        ip_block_repo: Arc<IpBlockRepository>,
+        cache_store: Arc<dyn CacheStore>,
+        audit_service: Arc<AuditService>,
+        auth_failure_block: Option<AuthFailureBlockConfig>,
+    ) -> Self {
+        Self {
+            ip_block_repo,
+            cache_store,
+            audit_service,
+            auth_failure_block,
+            active: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+pub trait IpBlockServiceApi: Send + Sync {
+    /// Whether requests from this address must be refused
+    fn is_blocked(&self, ip: IpAddr) -> bool;
+
+    /// Count a failed authentication from an address, blocking it once it reaches the
+    /// configured threshold. Failures are logged rather than returned.
+    async fn record_auth_failure(&self, ip: IpAddr);
+
+    /// Re-read the blocklist from the database
+    async fn reload(&self) -> Result<usize, IpBlockServiceError>;
+
+    async fn list_blocks(&self) -> Result<IpBlocksResponse, IpBlockServiceError>;
+
+    /// Block an address or range, replacing any existing block for it
+    async fn block(
+        &self,
+        admin_email: &str,
+        request: CreateIpBlockRequest,
+    ) -> Result<IpBlock, IpBlockServiceError>;
+
+    async fn unblock(&self, admin_email: &str, id: Uuid) -> Result<(), IpBlockServiceError>;
+}
+
+#[async_trait]
+impl IpBlockServiceApi for IpBlockService {
+    fn is_blocked(&self, ip: IpAddr) -> bool {
+        let now = Utc::now();
+        let active = self.active.read().unwrap_or_else(|e| e.into_inner());
+        active
+            .iter()
+            .any(|block| block.network.contains(&ip) && block.expires_at.is_none_or(|at| at > now))
+    }
+
+    async fn record_auth_failure(&self, ip: IpAddr) {
+        let Some(policy) = &self.auth_failure_block else {
+            return;
+        };
+
+        let key = format!("auth_failures:{}", ip);
+        let window = std::time::Duration::from_secs(policy.window_secs);
+        let failures = match self.cache_store.increment(&key, window).await {
+            Ok(failures) => failures,
+            Err(e) => {
+                tracing::warn!(client_ip = %ip, error = %e, "Failed to count auth failure");
+                return;
+            }
+        };
+        // Only the request that crosses the threshold blocks, so instances sharing the
+        // counter don't all write the same block
+        if failures != policy.threshold {
+            return;
+        }
+
+        let block = IpBlock {
+            id: Uuid::new_v4(),
+            network: IpNet::from(ip).to_string(),
+            reason: format!(
+                "{} failed authentications within {} seconds",
+                failures, policy.window_secs
+            ),
+            source: IpBlockSource::AuthFailures,
+            created_by: None,
+            created_at: Utc::now(),
+            expires_at: Some(Utc::now() + Duration::seconds(policy.block_secs)),
+        };
+        match self.save(block).await {
+            Ok(block) => {
+                tracing::warn!(
+                    client_ip = %ip,
+                    failures,
+                    "Blocked client after repeated auth failures"
+                );
+                self.audit_service
+                    .record(
+                        AuditEvent::by_system(AUTH_FAILURES_ACTOR, AuditAction::IpBlocked)
+                            .target(&block.network)
+                            .details(json!({
+                                "reason": block.reason,
+                                "expires_at": block.expires_at,
+                            })),
+                    )
+                    .await;
+            }
+            Err(e) => tracing::error!(client_ip = %ip, error = %e, "Failed to block client"),
+        }
+    }
+
+    async fn reload(&self) -> Result<usize, IpBlockServiceError> {
+        let blocks = self
+            .ip_block_repo
+            .find_active(Utc::now())
+            .await
+            .map_err(|e| IpBlockServiceError::Dependency(e.to_string()))?;
+
+        let active: Vec<ActiveBlock> = blocks
+            .iter()
+            .filter_map(|block| match block.network.parse() {
+                Ok(network) => Some(ActiveBlock {
+                    network,
+                    expires_at: block.expires_at,
+                }),
+                Err(_) => {
+                    tracing::warn!(network = %block.network, "Skipping unparseable IP block");
+                    None
+                }
+            })
+            .collect();
+        let count = active.len();
+        *self.active.write().unwrap_or_else(|e| e.into_inner()) = active;
+
+        Ok(count)
+    }
+
+    async fn list_blocks(&self) -> Result<IpBlocksResponse, IpBlockServiceError> {
+        let blocks = self
+            .ip_block_repo
+            .find_active(Utc::now())
+            .await
+            .map_err(|e| IpBlockServiceError::Dependency(e.to_string()))?;
+
+        Ok(IpBlocksResponse { blocks })
+    }
+
+    async fn block(
+        &self,
+        admin_email: &str,
+        request: CreateIpBlockRequest,
+    ) -> Result<IpBlock, IpBlockServiceError> {
+        let network = parse_network(&request.network).ok_or_else(|| {
+            IpBlockServiceError::Invalid(format!(
+                "'{}' is not an IP address or CIDR range",
+                request.network.trim()
+            ))
+        })?;
+        let reason = request.reason.trim();
+        if reason.is_empty() {
+            return Err(IpBlockServiceError::Invalid(
+                "A reason is required".to_string(),
+            ));
+        }
+        if reason.len() > MAX_REASON_LENGTH {
+            return Err(IpBlockServiceError::Invalid(format!(
+                "Reason cannot exceed {} characters",
+                MAX_REASON_LENGTH
+            )));
+        }
+        if request.expires_at.is_some_and(|at| at <= Utc::now()) {
+            return Err(IpBlockServiceError::Invalid(
+                "expires_at must be in the future".to_string(),
+            ));
+        }
+
+        let block = self
+            .save(IpBlock {
+                id: Uuid::new_v4(),
+                network: network.to_string(),
+                reason: reason.to_string(),
+                source: IpBlockSource::Admin,
+                created_by: Some(admin_email.to_string()),
+                created_at: Utc::now(),
+                expires_at: request.expires_at,
+            })
+            .await?;
+
+        tracing::info!(network = %block.network, admin = %admin_email, "IP blocked");
+        self.audit_service
+            .record(
+                AuditEvent::by_admin(admin_email, AuditAction::IpBlocked)
+                    .target(&block.network)
+                    .details(json!({
+                        "reason": block.reason,
+                        "expires_at": block.expires_at,
+                    })),
+            )
+            .await;
+
+        Ok(block)
+    }
+
+    async fn unblock(&self, admin_email: &str, id: Uuid) -> Result<(), IpBlockServiceError> {
+        let block = self
+            .ip_block_repo
+            .delete(id)
+            .await
+            .map_err(|e| IpBlockServiceError::Dependency(e.to_string()))?
+            .ok_or(IpBlockServiceError::NotFound)?;
+
+        self.reload().await?;
+
+        tracing::info!(network = %block.network, admin = %admin_email, "IP unblocked");
+        self.audit_service
+            .record(
+                AuditEvent::by_admin(admin_email, AuditAction::IpUnblocked).target(&block.network),
+            )
+            .await;
+
+        Ok(())
+    }
+}
+
+impl IpBlockService {
+    /// Store a block and start enforcing it on this instance right away
+    async fn save(&self, block: IpBlock) -> Result<IpBlock, IpBlockServiceError> {
+        let block = self
+            .ip_block_repo
+            .upsert(&block)
+            .await
+            .map_err(|e| IpBlockServiceError::Dependency(e.to_string()))?;
+        self.reload().await?;
+
+        Ok(block)
+    }
+}
+
+/// An address or range, with the host bits of a range cleared
+fn parse_network(value: &str) -> Option<IpNet> {
+    let value = value.trim();
+    value
+        .parse::<IpNet>()
+        .map(|network| network.trunc())
+        .or_else(|_| value.parse::<IpAddr>().map(IpNet::from))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_network_accepts_addresses_and_ranges() {
+        assert_eq!(
+            parse_network(" 203.0.113.7 ").map(|n| n.to_string()),
+            Some("203.0.113.7/32".to_string())
+        );
+        assert_eq!(
+            parse_network("10.1.2.3/8").map(|n| n.to_string()),
+            Some("10.0.0.0/8".to_string())
+        );
+        assert_eq!(
+            parse_network("2001:db8::1").map(|n| n.to_string()),
+            Some("2001:db8::1/128".to_string())
+        );
+        assert!(parse_network("example.com").is_none());
+    }
+}
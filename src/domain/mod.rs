@@ -1,6 +1,23 @@
+pub mod admin;
+pub mod analytics;
+pub mod api_key;
+pub mod audit;
 pub mod auth;
+pub mod backup;
+pub mod device;
+pub mod events;
+pub mod family;
 pub mod feed;
 pub mod feed_suggestions;
+pub mod ip_block;
+pub mod mute;
+pub mod outbox;
+pub mod plan;
+pub mod privacy;
+pub mod referral;
 pub mod shared;
+pub mod subscription;
 pub mod tts;
 pub mod user;
+pub mod webhook;
+pub mod weekly_summary;
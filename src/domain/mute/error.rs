@@ -0,0 +1,37 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MuteServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("mute rule not found")]
+    NotFound,
+    #[error("feed not found")]
+    FeedNotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for MuteServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => MuteServiceError::Invalid(msg),
+            AppError::NotFound(_) => MuteServiceError::NotFound,
+            _ => MuteServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<MuteServiceError> for AppError {
+    fn from(err: MuteServiceError) -> Self {
+        match err {
+            MuteServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            MuteServiceError::NotFound => AppError::NotFound("Mute rule not found".to_string()),
+            MuteServiceError::FeedNotFound => AppError::NotFound("Feed not found".to_string()),
+            MuteServiceError::Dependency(msg) => AppError::Internal(msg),
+            MuteServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use super::{MuteRule, MuteRuleKind};
+use regex::{Regex, RegexBuilder};
+use uuid::Uuid;
+
+/// Upper bound for compiled regex size, keeps user-provided patterns cheap to evaluate
+pub const REGEX_SIZE_LIMIT: usize = 64 * 1024;
+
+/// Compiled set of a user's mute rules, used to leave muted entries out of new-article
+/// notifications
+pub struct MuteRuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+struct CompiledRule {
+    feed_id: Option<Uuid>,
+    matcher: Matcher,
+}
+
+enum Matcher {
+    Keyword(String),
+    Regex(Regex),
+}
+
+impl MuteRuleSet {
+    pub fn new(rules: &[MuteRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|rule| {
+                let matcher = match rule.kind {
+                    MuteRuleKind::Keyword => Matcher::Keyword(rule.pattern.to_lowercase()),
+                    MuteRuleKind::Regex => match compile_pattern(&rule.pattern) {
+                        Ok(regex) => Matcher::Regex(regex),
+                        Err(e) => {
+                            tracing::warn!(rule_id = %rule.id, error = %e, "Skipping invalid mute rule");
+                            return None;
+                        }
+                    },
+                };
+                Some(CompiledRule {
+                    feed_id: rule.feed_id,
+                    matcher,
+                })
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Check whether an article from `feed_id` with the given text (title, summary) is muted.
+    /// Global rules apply to every feed, per-feed rules only to their own feed.
+    pub fn is_muted(&self, feed_id: Uuid, text: &str) -> bool {
+        let lowercase_text = text.to_lowercase();
+
+        self.rules
+            .iter()
+            .filter(|rule| rule.feed_id.is_none_or(|id| id == feed_id))
+            .any(|rule| match &rule.matcher {
+                Matcher::Keyword(keyword) => lowercase_text.contains(keyword.as_str()),
+                Matcher::Regex(regex) => regex.is_match(text),
+            })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// Compile a user-provided regex pattern (case-insensitive, size-limited)
+pub fn compile_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn rule(feed_id: Option<Uuid>, pattern: &str, kind: MuteRuleKind) -> MuteRule {
+        MuteRule {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            feed_id,
+            pattern: pattern.to_string(),
+            kind,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_keyword_rules_are_case_insensitive() {
+        let rules = MuteRuleSet::new(&[rule(None, "Sponsored", MuteRuleKind::Keyword)]);
+        assert!(rules.is_muted(Uuid::new_v4(), "SPONSORED: Buy this laptop"));
+        assert!(!rules.is_muted(Uuid::new_v4(), "A regular post"));
+    }
+
+    #[test]
+    fn test_feed_rules_only_apply_to_their_feed() {
+        let sports_feed = Uuid::new_v4();
+        let rules = MuteRuleSet::new(&[rule(
+            Some(sports_feed),
+            r"final score|\d+-\d+",
+            MuteRuleKind::Regex,
+        )]);
+        assert!(rules.is_muted(sports_feed, "Final score: Madrid 2-1 Barcelona"));
+        assert!(!rules.is_muted(Uuid::new_v4(), "Final score: Madrid 2-1 Barcelona"));
+    }
+
+    #[test]
+    fn test_invalid_regex_rules_are_skipped() {
+        let rules = MuteRuleSet::new(&[rule(None, "(unclosed", MuteRuleKind::Regex)]);
+        assert!(rules.is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+pub mod error;
+pub mod matcher;
+pub mod model;
+pub mod service;
+
+pub use error::MuteServiceError;
+pub use matcher::MuteRuleSet;
+pub use model::{MuteRule, MuteRuleKind};
+pub use service::{MuteService, MuteServiceApi};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Response for mute rule endpoints
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MuteRuleResponse {
+    pub id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feed_id: Option<Uuid>,
+    pub pattern: String,
+    pub kind: MuteRuleKind,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to create a mute rule. Rules without `feed_id` apply to every feed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateMuteRuleRequest {
+    pub pattern: String,
+    #[serde(default = "default_kind")]
+    pub kind: MuteRuleKind,
+    #[serde(default)]
+    pub feed_id: Option<Uuid>,
+}
+
+fn default_kind() -> MuteRuleKind {
+    MuteRuleKind::Keyword
+}
+
+impl From<MuteRule> for MuteRuleResponse {
+    fn from(rule: MuteRule) -> Self {
+        Self {
+            id: rule.id,
+            feed_id: rule.feed_id,
+            pattern: rule.pattern,
+            kind: rule.kind,
+            created_at: rule.created_at,
+        }
+    }
+}
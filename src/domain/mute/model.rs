@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MuteRule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub feed_id: Option<Uuid>,
+    pub pattern: String,
+    pub kind: MuteRuleKind,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "lowercase")]
+pub enum MuteRuleKind {
+    #[serde(rename = "keyword")]
+    Keyword,
+    #[serde(rename = "regex")]
+    Regex,
+}
+
+impl std::fmt::Display for MuteRuleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MuteRuleKind::Keyword => write!(f, "keyword"),
+            MuteRuleKind::Regex => write!(f, "regex"),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use super::error::MuteServiceError;
+use super::matcher::compile_pattern;
+use super::{CreateMuteRuleRequest, MuteRuleKind, MuteRuleResponse, MuteRuleSet};
+use crate::infrastructure::repositories::{FeedRepository, FeedRepositoryApi, MuteRuleRepository};
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const MAX_RULES_PER_USER: i64 = 100;
+const MAX_PATTERN_LENGTH: usize = 200;
+
+pub struct MuteService {
+    mute_rule_repo: Arc<MuteRuleRepository>,
+    feed_repo: Arc<FeedRepository>,
+}
+
+impl MuteService {
+    pub fn new(mute_rule_repo: Arc<MuteRuleRepository>, feed_repo: Arc<FeedRepository>) -> Self {
+        Self {
+            mute_rule_repo,
+            feed_repo,
+        }
+    }
+}
+
+#[async_trait]
+pub trait MuteServiceApi: Send + Sync {
+    async fn list_rules(&self, user_id: Uuid) -> Result<Vec<MuteRuleResponse>, MuteServiceError>;
+
+    async fn create_rule(
+        &self,
+        user_id: Uuid,
+        request: CreateMuteRuleRequest,
+    ) -> Result<MuteRuleResponse, MuteServiceError>;
+
+    async fn delete_rule(&self, user_id: Uuid, rule_id: Uuid) -> Result<(), MuteServiceError>;
+
+    /// Load the compiled rule set used to filter a user's articles
+    async fn get_rule_set(&self, user_id: Uuid) -> Result<MuteRuleSet, MuteServiceError>;
+}
+
+#[async_trait]
+impl MuteServiceApi for MuteService {
+    async fn list_rules(&self, user_id: Uuid) -> Result<Vec<MuteRuleResponse>, MuteServiceError> {
+        let rules = self
+            .mute_rule_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| MuteServiceError::Dependency(e.to_string()))?;
+        Ok(rules.into_iter().map(MuteRuleResponse::from).collect())
+    }
+
+    async fn create_rule(
+        &self,
+        user_id: Uuid,
+        request: CreateMuteRuleRequest,
+    ) -> Result<MuteRuleResponse, MuteServiceError> {
+        let pattern = request.pattern.trim();
+        self.validate_pattern(pattern, request.kind)?;
+
+        if let Some(feed_id) = request.feed_id {
+            self.verify_feed_ownership(feed_id, user_id).await?;
+        }
+
+        let rule_count = self
+            .mute_rule_repo
+            .count_by_user(user_id)
+            .await
+            .map_err(|e| MuteServiceError::Dependency(e.to_string()))?;
+        if rule_count >= MAX_RULES_PER_USER {
+            return Err(MuteServiceError::Invalid(format!(
+                "A maximum of {} mute rules is allowed",
+                MAX_RULES_PER_USER
+            )));
+        }
+
+        let rule = self
+            .mute_rule_repo
+            .create(user_id, request.feed_id, pattern, request.kind)
+            .await
+            .map_err(|e| MuteServiceError::Dependency(e.to_string()))?;
+
+        Ok(MuteRuleResponse::from(rule))
+    }
+
+    async fn delete_rule(&self, user_id: Uuid, rule_id: Uuid) -> Result<(), MuteServiceError> {
+        let deleted = self
+            .mute_rule_repo
+            .delete_for_user(rule_id, user_id)
+            .await
+            .map_err(|e| MuteServiceError::Dependency(e.to_string()))?;
+
+        if !deleted {
+            return Err(MuteServiceError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn get_rule_set(&self, user_id: Uuid) -> Result<MuteRuleSet, MuteServiceError> {
+        let rules = self
+            .mute_rule_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| MuteServiceError::Dependency(e.to_string()))?;
+        Ok(MuteRuleSet::new(&rules))
+    }
+}
+
+impl MuteService {
+    fn validate_pattern(&self, pattern: &str, kind: MuteRuleKind) -> Result<(), MuteServiceError> {
+        if pattern.is_empty() {
+            return Err(MuteServiceError::Invalid(
+                "Pattern cannot be empty".to_string(),
+            ));
+        }
+
+        if pattern.chars().count() > MAX_PATTERN_LENGTH {
+            return Err(MuteServiceError::Invalid(format!(
+                "Pattern must be {} characters or less",
+                MAX_PATTERN_LENGTH
+            )));
+        }
+
+        if kind == MuteRuleKind::Regex {
+            compile_pattern(pattern)
+                .map_err(|e| MuteServiceError::Invalid(format!("Invalid regex: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    async fn verify_feed_ownership(
+        &self,
+        feed_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), MuteServiceError> {
+        let feed = self
+            .feed_repo
+            .find_by_id(feed_id)
+            .await
+            .map_err(|e| MuteServiceError::Dependency(e.to_string()))?
+            .ok_or(MuteServiceError::FeedNotFound)?;
+
+        if feed.user_id != user_id {
+            return Err(MuteServiceError::FeedNotFound);
+        }
+
+        Ok(())
+    }
+}
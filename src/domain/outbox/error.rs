@@ -0,0 +1,15 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OutboxServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+}
+
+impl From<OutboxServiceError> for AppError {
+    fn from(err: OutboxServiceError) -> Self {
+        match err {
+            OutboxServiceError::Dependency(msg) => AppError::Internal(msg),
+        }
+    }
+}
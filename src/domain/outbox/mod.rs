@@ -0,0 +1,7 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::OutboxServiceError;
+pub use model::{OutboxEvent, OutboxPayload};
+pub use service::{OutboxService, OutboxServiceApi};
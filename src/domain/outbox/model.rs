@@ -0,0 +1,91 @@
+use crate::domain::events::{DomainEvent, EventOrigin};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use uuid::Uuid;
+
+/// Attempts made before an event is given up on
+pub const MAX_DISPATCH_ATTEMPTS: i32 = 10;
+const FIRST_RETRY_DELAY_SECS: i64 = 10;
+
+/// An event waiting in, or claimed from, the outbox
+#[derive(Debug, Clone, FromRow)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub payload: Json<OutboxPayload>,
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What the outbox stores of an event: the event's own fields, and the request it came
+/// from under `origin`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutboxPayload {
+    #[serde(flatten)]
+    pub event: DomainEvent,
+    /// Missing from events stored before it was recorded
+    #[serde(default)]
+    pub origin: EventOrigin,
+}
+
+/// How long to wait after the given failed attempt (1-based) before trying again: 10s,
+/// doubling each time. None once the attempts are used up.
+pub fn retry_delay(attempt: i32) -> Option<Duration> {
+    if attempt >= MAX_DISPATCH_ATTEMPTS {
+        return None;
+    }
+    Some(Duration::seconds(
+        FIRST_RETRY_DELAY_SECS << (attempt.max(1) - 1),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_payload_keeps_the_origin_next_to_the_event() {
+        let payload = OutboxPayload {
+            event: DomainEvent::FeedAdded {
+                user_id: Uuid::new_v4(),
+                feed_id: Uuid::new_v4(),
+                url: "https://example.com/rss".to_string(),
+            },
+            origin: EventOrigin {
+                client_ip: Some("203.0.113.7".parse().unwrap()),
+                request_id: Some("req-1".to_string()),
+            },
+        };
+
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value["type"], "feed_added");
+        assert_eq!(value["origin"]["client_ip"], "203.0.113.7");
+        assert_eq!(
+            serde_json::from_value::<OutboxPayload>(value).unwrap(),
+            payload
+        );
+    }
+
+    #[test]
+    fn test_payload_without_origin_still_reads() {
+        let value = json!({
+            "type": "feed_deleted",
+            "user_id": Uuid::new_v4(),
+            "feed_id": Uuid::new_v4(),
+            "url": "https://example.com/rss",
+        });
+
+        let payload = serde_json::from_value::<OutboxPayload>(value).unwrap();
+
+        assert_eq!(payload.origin, EventOrigin::default());
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_until_attempts_run_out() {
+        assert_eq!(retry_delay(1), Some(Duration::seconds(10)));
+        assert_eq!(retry_delay(3), Some(Duration::seconds(40)));
+        assert_eq!(retry_delay(MAX_DISPATCH_ATTEMPTS), None);
+    }
+}
@@ -0,0 +1,117 @@
+use super::error::OutboxServiceError;
+use super::model::{retry_delay, OutboxEvent, OutboxPayload};
+use crate::domain::events::EventSubscriber;
+use crate::infrastructure::repositories::OutboxRepositoryApi;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+/// Events handed out per dispatcher run
+const DISPATCH_BATCH_SIZE: i64 = 100;
+/// How long a claimed event is hidden from other dispatchers; longer than a run can take
+const DISPATCH_LEASE_SECS: i64 = 60;
+
+/// Hands recorded events to the subscribers. Events are delivered at least once: when
+/// any subscriber fails, every subscriber sees the event again on the retry.
+pub struct OutboxService {
+    outbox_repo: Arc<dyn OutboxRepositoryApi>,
+    subscribers: Vec<Arc<dyn EventSubscriber>>,
+}
+
+impl OutboxService {
+    pub fn new(
+        outbox_repo: Arc<dyn OutboxRepositoryApi>,
+        subscribers: Vec<Arc<dyn EventSubscriber>>,
+    ) -> Self {
+        Self {
+            outbox_repo,
+            subscribers,
+        }
+    }
+}
+
+#[async_trait]
+pub trait OutboxServiceApi: Send + Sync {
+    /// Hand events that are due to every subscriber, oldest first. Called by the background
+    /// dispatcher; returns how many events were claimed.
+    async fn dispatch_due(&self) -> Result<usize, OutboxServiceError>;
+}
+
+#[async_trait]
+impl OutboxServiceApi for OutboxService {
+    async fn dispatch_due(&self) -> Result<usize, OutboxServiceError> {
+        let now = Utc::now();
+        let mut events = self
+            .outbox_repo
+            .claim_due(
+                now,
+                now + Duration::seconds(DISPATCH_LEASE_SECS),
+                DISPATCH_BATCH_SIZE,
+            )
+            .await
+            .map_err(|e| OutboxServiceError::Dependency(e.to_string()))?;
+        events.sort_by_key(|event| event.created_at);
+
+        for event in &events {
+            self.dispatch(event).await;
+        }
+
+        Ok(events.len())
+    }
+}
+
+impl OutboxService {
+    async fn dispatch(&self, event: &OutboxEvent) {
+        let OutboxPayload {
+            event: domain_event,
+            origin,
+        } = &event.payload.0;
+
+        let mut failures = Vec::new();
+        for subscriber in &self.subscribers {
+            if let Err(e) = subscriber
+                .handle(domain_event, event.created_at, origin)
+                .await
+            {
+                tracing::warn!(
+                    event_id = %event.id,
+                    event_type = domain_event.event_type(),
+                    subscriber = subscriber.name(),
+                    error = %e,
+                    "Outbox subscriber failed"
+                );
+                failures.push(format!("{}: {}", subscriber.name(), e));
+            }
+        }
+
+        let now = Utc::now();
+        let result = if failures.is_empty() {
+            self.outbox_repo.mark_completed(event.id, now).await
+        } else {
+            let attempts = event.attempts + 1;
+            let error = failures.join("; ");
+            match retry_delay(attempts) {
+                Some(delay) => {
+                    self.outbox_repo
+                        .record_failure(event.id, attempts, now + delay, &error, None)
+                        .await
+                }
+                None => {
+                    tracing::error!(
+                        event_id = %event.id,
+                        event_type = domain_event.event_type(),
+                        attempts,
+                        "Outbox event given up"
+                    );
+                    self.outbox_repo
+                        .record_failure(event.id, attempts, now, &error, Some(now))
+                        .await
+                }
+            }
+        };
+
+        if let Err(e) = result {
+            tracing::warn!(event_id = %event.id, error = %e, "Failed to store outbox dispatch result");
+        }
+    }
+}
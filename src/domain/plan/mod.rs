@@ -0,0 +1,180 @@
+pub mod rollover;
+pub mod usage_period;
+pub mod usage_warning;
+
+pub use rollover::{remaining_rollover, rollover_balance, RolloverDay};
+pub use usage_period::{local_midnight, UsagePeriod};
+pub use usage_warning::{usage_warnings, UsageWarning, UsageWarningKind};
+
+use crate::domain::user::{SubscriptionTier, User};
+use serde::{Deserialize, Serialize};
+
+/// Polly bills per character; the app presents quotas in minutes of audio
+pub const CHARACTERS_PER_MINUTE: i32 = 1000;
+
+/// Window over which synthesis usage is counted before the quota resets
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaPeriod {
+    Daily,
+    Monthly,
+}
+
+impl std::fmt::Display for QuotaPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuotaPeriod::Daily => write!(f, "daily"),
+            QuotaPeriod::Monthly => write!(f, "monthly"),
+        }
+    }
+}
+
+impl std::str::FromStr for QuotaPeriod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daily" => Ok(QuotaPeriod::Daily),
+            "monthly" => Ok(QuotaPeriod::Monthly),
+            other => Err(format!("unknown quota period '{}'", other)),
+        }
+    }
+}
+
+/// Quotas for a single subscription tier
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TierLimits {
+    /// Characters that can be synthesized per quota period
+    pub characters: i32,
+    pub max_feeds: i32,
+}
+
+impl TierLimits {
+    pub fn minutes(&self) -> i32 {
+        self.characters / CHARACTERS_PER_MINUTE
+    }
+}
+
+/// Per-user exceptions to the tier limits, set by support. Unset fields fall back to the tier.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LimitOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub characters: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_feeds: Option<i32>,
+}
+
+impl LimitOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.characters.is_none() && self.max_feeds.is_none()
+    }
+}
+
+/// Limits for every plan, loaded once at startup and shared by the services that enforce them
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct PlanLimits {
+    pub free: TierLimits,
+    pub pro: TierLimits,
+    pub quota_period: QuotaPeriod,
+    /// Longest text accepted by a single synthesis request, regardless of tier
+    pub max_request_characters: i32,
+    /// Extra characters per quota period while a referral bonus is active
+    pub referral_bonus_characters: i32,
+    /// Pro users on a daily quota can carry unused characters into later days, up to this
+    /// many times their daily limit. 0 disables rollover.
+    pub rollover_cap_multiplier: i32,
+}
+
+impl PlanLimits {
+    pub fn for_tier(&self, tier: &SubscriptionTier) -> &TierLimits {
+        match tier {
+            SubscriptionTier::Free => &self.free,
+            SubscriptionTier::Pro => &self.pro,
+        }
+    }
+
+    /// Tier limits with any per-user overrides applied on top
+    pub fn resolve(&self, tier: &SubscriptionTier, overrides: &LimitOverrides) -> TierLimits {
+        let defaults = self.for_tier(tier);
+        TierLimits {
+            characters: overrides.characters.unwrap_or(defaults.characters),
+            max_feeds: overrides.max_feeds.unwrap_or(defaults.max_feeds),
+        }
+    }
+
+    /// Limits that apply to the user right now, including any referral bonus
+    pub fn for_user(&self, user: &User) -> TierLimits {
+        let mut limits = self.resolve(&user.effective_tier(), &user.limit_overrides());
+        if user.has_referral_bonus() {
+            limits.characters += self.referral_bonus_characters;
+        }
+        limits
+    }
+
+    /// Largest rollover balance the user can hold, or None when rollover doesn't apply
+    pub fn rollover_cap(&self, user: &User) -> Option<i32> {
+        let eligible = self.rollover_cap_multiplier > 0
+            && self.quota_period == QuotaPeriod::Daily
+            && user.effective_tier() == SubscriptionTier::Pro;
+
+        eligible.then(|| {
+            self.for_user(user)
+                .characters
+                .saturating_mul(self.rollover_cap_multiplier)
+        })
+    }
+}
+
+impl Default for PlanLimits {
+    fn default() -> Self {
+        Self {
+            free: TierLimits {
+                characters: 20_000,
+                max_feeds: 3,
+            },
+            pro: TierLimits {
+                characters: 200_000,
+                max_feeds: 999,
+            },
+            quota_period: QuotaPeriod::Daily,
+            max_request_characters: 10_000,
+            referral_bonus_characters: 5_000,
+            rollover_cap_multiplier: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_for_tier() {
+        let limits = PlanLimits::default();
+
+        assert_eq!(limits.for_tier(&SubscriptionTier::Free).max_feeds, 3);
+        assert_eq!(limits.for_tier(&SubscriptionTier::Pro).max_feeds, 999);
+    }
+
+    #[test]
+    fn test_overrides_take_precedence_over_tier() {
+        let limits = PlanLimits::default();
+        let overrides = LimitOverrides {
+            characters: Some(50_000),
+            max_feeds: None,
+        };
+
+        let resolved = limits.resolve(&SubscriptionTier::Free, &overrides);
+
+        assert_eq!(resolved.characters, 50_000);
+        assert_eq!(resolved.max_feeds, 3);
+    }
+
+    #[test]
+    fn test_minutes_derived_from_characters() {
+        let limits = PlanLimits::default();
+
+        assert_eq!(limits.free.minutes(), 20);
+        assert_eq!(limits.pro.minutes(), 200);
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::NaiveDate;
+use sqlx::FromRow;
+
+/// A day of usage as seen by rollover
+#[derive(Debug, Clone, FromRow)]
+pub struct RolloverDay {
+    pub date: NaiveDate,
+    pub characters_used: i32,
+    /// Balance carried into the day; None when the user wasn't eligible that day
+    pub rollover_characters: Option<i32>,
+}
+
+/// Rollover balance carried into `today`, given the user's most recent usage days (newest
+/// first). A balance already fixed for today is kept. Otherwise it is what was left of the
+/// last eligible day's limit and balance, plus the full limit for each idle day since,
+/// capped at `cap`.
+pub fn rollover_balance(
+    today: NaiveDate,
+    recent: &[RolloverDay],
+    daily_limit: i32,
+    cap: i32,
+) -> i32 {
+    if let Some(balance) = recent
+        .iter()
+        .find(|day| day.date == today)
+        .and_then(|day| day.rollover_characters)
+    {
+        return balance;
+    }
+
+    let Some(previous) = recent.iter().find(|day| day.date < today) else {
+        return 0;
+    };
+    let Some(previous_balance) = previous.rollover_characters else {
+        return 0;
+    };
+
+    let unused = (daily_limit + previous_balance - previous.characters_used).max(0);
+    let idle_days = ((today - previous.date).num_days() - 1).max(0) as i32;
+
+    unused
+        .saturating_add(idle_days.saturating_mul(daily_limit))
+        .min(cap)
+}
+
+/// Part of the balance still unused after today's usage. The daily limit is spent first.
+pub fn remaining_rollover(balance: i32, characters_used: i32, daily_limit: i32) -> i32 {
+    (balance - (characters_used - daily_limit).max(0)).max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(date: &str, characters_used: i32, rollover_characters: Option<i32>) -> RolloverDay {
+        RolloverDay {
+            date: date.parse().unwrap(),
+            characters_used,
+            rollover_characters,
+        }
+    }
+
+    #[test]
+    fn test_unused_limit_rolls_over() {
+        let today = "2025-03-10".parse().unwrap();
+        let recent = [day("2025-03-09", 600, Some(0))];
+
+        assert_eq!(rollover_balance(today, &recent, 1000, 2000), 400);
+    }
+
+    #[test]
+    fn test_idle_days_roll_over_up_to_cap() {
+        let today = "2025-03-10".parse().unwrap();
+
+        let recent = [day("2025-03-08", 1000, Some(500))];
+        assert_eq!(rollover_balance(today, &recent, 1000, 2000), 1500);
+
+        let recent = [day("2025-03-01", 0, Some(0))];
+        assert_eq!(rollover_balance(today, &recent, 1000, 2000), 2000);
+    }
+
+    #[test]
+    fn test_no_rollover_from_ineligible_days() {
+        let today = "2025-03-10".parse().unwrap();
+
+        assert_eq!(rollover_balance(today, &[], 1000, 2000), 0);
+        let recent = [day("2025-03-09", 0, None)];
+        assert_eq!(rollover_balance(today, &recent, 1000, 2000), 0);
+    }
+
+    #[test]
+    fn test_balance_fixed_for_today_is_kept() {
+        let today = "2025-03-10".parse().unwrap();
+        let recent = [
+            day("2025-03-10", 300, Some(700)),
+            day("2025-03-09", 0, Some(0)),
+        ];
+
+        assert_eq!(rollover_balance(today, &recent, 1000, 2000), 700);
+    }
+
+    #[test]
+    fn test_remaining_rollover_spends_daily_limit_first() {
+        assert_eq!(remaining_rollover(500, 800, 1000), 500);
+        assert_eq!(remaining_rollover(500, 1200, 1000), 300);
+        assert_eq!(remaining_rollover(500, 2000, 1000), 0);
+    }
+}
@@ -0,0 +1,122 @@
+use super::QuotaPeriod;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// The window quotas are counted against, in the user's own timezone
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsagePeriod {
+    pub kind: QuotaPeriod,
+    /// Local day usage is recorded under
+    pub today: NaiveDate,
+    /// First day of the window, inclusive
+    pub start: NaiveDate,
+    /// First day after the window
+    pub end: NaiveDate,
+    /// Local midnight at `start`, when the window began
+    pub started_at: DateTime<Utc>,
+    /// Local midnight at `end`, when the quota resets
+    pub resets_at: DateTime<Utc>,
+}
+
+impl UsagePeriod {
+    pub fn current(kind: QuotaPeriod, tz: Tz, now: DateTime<Utc>) -> Self {
+        let today = now.with_timezone(&tz).date_naive();
+
+        let (start, end) = match kind {
+            QuotaPeriod::Daily => (today, today + Duration::days(1)),
+            QuotaPeriod::Monthly => {
+                let start = today.with_day(1).unwrap();
+                let end = if start.month() == 12 {
+                    NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+                }
+                .unwrap();
+                (start, end)
+            }
+        };
+
+        Self {
+            kind,
+            today,
+            start,
+            end,
+            started_at: local_midnight(tz, start),
+            resets_at: local_midnight(tz, end),
+        }
+    }
+}
+
+/// When `date` begins in `tz`
+pub fn local_midnight(tz: Tz, date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+
+    // A few zones skip midnight on DST changes; the day then starts at the first valid hour
+    (0..24)
+        .find_map(|hour| {
+            tz.from_local_datetime(&(midnight + Duration::hours(hour)))
+                .earliest()
+        })
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_utc_day() {
+        let period = UsagePeriod::current(QuotaPeriod::Daily, Tz::UTC, utc("2025-03-10T15:00:00Z"));
+
+        assert_eq!(period.start, date(2025, 3, 10));
+        assert_eq!(period.end, date(2025, 3, 11));
+        assert_eq!(period.started_at, utc("2025-03-10T00:00:00Z"));
+        assert_eq!(period.resets_at, utc("2025-03-11T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_day_ahead_of_utc() {
+        // 15:00 UTC is already the next day in Tokyo
+        let period = UsagePeriod::current(
+            QuotaPeriod::Daily,
+            chrono_tz::Asia::Tokyo,
+            utc("2025-03-10T15:00:00Z"),
+        );
+
+        assert_eq!(period.today, date(2025, 3, 11));
+        assert_eq!(period.resets_at, utc("2025-03-11T15:00:00Z"));
+    }
+
+    #[test]
+    fn test_midnight_skipped_by_dst() {
+        // Santiago springs forward from 00:00 to 01:00 on 2024-09-08
+        let period = UsagePeriod::current(
+            QuotaPeriod::Daily,
+            chrono_tz::America::Santiago,
+            utc("2024-09-07T12:00:00Z"),
+        );
+
+        assert_eq!(period.today, date(2024, 9, 7));
+        assert_eq!(period.resets_at, utc("2024-09-08T04:00:00Z"));
+    }
+
+    #[test]
+    fn test_month_rolls_over_year() {
+        let period =
+            UsagePeriod::current(QuotaPeriod::Monthly, Tz::UTC, utc("2025-12-20T08:00:00Z"));
+
+        assert_eq!(period.today, date(2025, 12, 20));
+        assert_eq!(period.start, date(2025, 12, 1));
+        assert_eq!(period.end, date(2026, 1, 1));
+        assert_eq!(period.resets_at, utc("2026-01-01T00:00:00Z"));
+    }
+}
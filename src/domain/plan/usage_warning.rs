@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// Share of the quota, in percent, past which users are told they are running low
+pub const NEARLY_EXHAUSTED_PERCENT: i64 = 80;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageWarningKind {
+    QuotaNearlyExhausted,
+    QuotaExhausted,
+}
+
+impl UsageWarningKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UsageWarningKind::QuotaNearlyExhausted => "quota_nearly_exhausted",
+            UsageWarningKind::QuotaExhausted => "quota_exhausted",
+        }
+    }
+}
+
+/// Something the client should tell the user about their quota
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UsageWarning {
+    pub kind: UsageWarningKind,
+    /// Percent of the quota at which this warning starts
+    pub threshold_percent: i64,
+    pub message: String,
+}
+
+/// Warnings for the given usage of a period's character quota. Only the most severe threshold
+/// crossed is reported, so a user who is out of quota isn't also told they are running low.
+pub fn usage_warnings(characters_used: i32, characters_limit: i32) -> Vec<UsageWarning> {
+    let used = i64::from(characters_used);
+    let limit = i64::from(characters_limit);
+
+    if used >= limit {
+        vec![UsageWarning {
+            kind: UsageWarningKind::QuotaExhausted,
+            threshold_percent: 100,
+            message: "You've used all your minutes for this period".to_string(),
+        }]
+    } else if used * 100 >= limit * NEARLY_EXHAUSTED_PERCENT {
+        vec![UsageWarning {
+            kind: UsageWarningKind::QuotaNearlyExhausted,
+            threshold_percent: NEARLY_EXHAUSTED_PERCENT,
+            message: format!(
+                "You've used {}% of your minutes for this period",
+                used * 100 / limit
+            ),
+        }]
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_warning_below_threshold() {
+        assert!(usage_warnings(15_999, 20_000).is_empty());
+    }
+
+    #[test]
+    fn test_nearly_exhausted_from_eighty_percent() {
+        let warnings = usage_warnings(16_000, 20_000);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, UsageWarningKind::QuotaNearlyExhausted);
+        assert_eq!(
+            warnings[0].message,
+            "You've used 80% of your minutes for this period"
+        );
+    }
+
+    #[test]
+    fn test_only_exhausted_reported_at_limit() {
+        let warnings = usage_warnings(20_500, 20_000);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, UsageWarningKind::QuotaExhausted);
+    }
+}
@@ -0,0 +1,30 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PrivacyServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("user not found")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for PrivacyServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::NotFound(_) => PrivacyServiceError::NotFound,
+            _ => PrivacyServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<PrivacyServiceError> for AppError {
+    fn from(err: PrivacyServiceError) -> Self {
+        match err {
+            PrivacyServiceError::NotFound => AppError::NotFound("User not found".to_string()),
+            PrivacyServiceError::Dependency(msg) => AppError::Internal(msg),
+            PrivacyServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::PrivacyServiceError;
+pub use model::{
+    ExportAccount, ExportDevice, ExportUsageDay, UserDataExport, USER_DATA_EXPORT_VERSION,
+};
+pub use service::{PrivacyService, PrivacyServiceApi};
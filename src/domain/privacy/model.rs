@@ -0,0 +1,66 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::device::DevicePlatform;
+use crate::domain::feed::Feed;
+use crate::domain::mute::MuteRule;
+use crate::domain::user::MeResponse;
+use crate::infrastructure::repositories::RefreshSession;
+
+/// Format version written into exports, bumped when a section changes shape
+pub const USER_DATA_EXPORT_VERSION: u32 = 1;
+
+/// Everything stored about a user, as handed over on a data access request. Refresh and
+/// push tokens are credentials, so only their metadata is included.
+#[derive(Debug, Serialize)]
+pub struct UserDataExport {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub account: ExportAccount,
+    /// Display name, settings and subscription, as GET /api/me returns them
+    pub profile: MeResponse,
+    pub feeds: Vec<Feed>,
+    pub mute_rules: Vec<MuteRule>,
+    /// Every day of usage still kept, newest first
+    pub usage: Vec<ExportUsageDay>,
+    pub sessions: Vec<RefreshSession>,
+    pub devices: Vec<ExportDevice>,
+}
+
+/// Sign-in identity and account dates
+#[derive(Debug, Serialize)]
+pub struct ExportAccount {
+    pub id: Uuid,
+    pub email: String,
+    pub oauth_provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referral_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription_expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportUsageDay {
+    pub date: NaiveDate,
+    pub characters_used: i32,
+    pub articles_synthesized: i32,
+    pub audio_seconds: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+}
+
+/// A device registered for push notifications, without its push token
+#[derive(Debug, Serialize)]
+pub struct ExportDevice {
+    pub id: Uuid,
+    pub platform: DevicePlatform,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_version: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
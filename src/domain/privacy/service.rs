@@ -0,0 +1,139 @@
+use super::error::PrivacyServiceError;
+use super::{
+    ExportAccount, ExportDevice, ExportUsageDay, UserDataExport, USER_DATA_EXPORT_VERSION,
+};
+use crate::domain::user::{UserService, UserServiceApi};
+use crate::error::AppError;
+use crate::infrastructure::repositories::{
+    DeviceRepository, FeedRepositoryApi, MuteRuleRepository, RefreshTokenRepositoryApi,
+    UsageRepositoryApi, UserRepositoryApi,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Usage days are purged by maintenance, so this only bounds the query
+const MAX_EXPORT_USAGE_DAYS: i64 = 100_000;
+
+/// Gathers what is stored about a user for data access requests. Takes the user id rather
+/// than the signed-in user, so support tooling can run the same export.
+pub struct PrivacyService {
+    user_repo: Arc<dyn UserRepositoryApi>,
+    user_service: Arc<UserService>,
+    feed_repo: Arc<dyn FeedRepositoryApi>,
+    mute_rule_repo: Arc<MuteRuleRepository>,
+    usage_repo: Arc<dyn UsageRepositoryApi>,
+    refresh_token_repo: Arc<dyn RefreshTokenRepositoryApi>,
+    device_repo: Arc<DeviceRepository>,
+}
+
+impl PrivacyService {
+    pub fn new(
+        user_repo: Arc<dyn UserRepositoryApi>,
+        user_service: Arc<UserService>,
+        feed_repo: Arc<dyn FeedRepositoryApi>,
+        mute_rule_repo: Arc<MuteRuleRepository>,
+        usage_repo: Arc<dyn UsageRepositoryApi>,
+        refresh_token_repo: Arc<dyn RefreshTokenRepositoryApi>,
+        device_repo: Arc<DeviceRepository>,
+    ) -> Self {
+        Self {
+            user_repo,
+            user_service,
+            feed_repo,
+            mute_rule_repo,
+            usage_repo,
+            refresh_token_repo,
+            device_repo,
+        }
+    }
+}
+
+#[async_trait]
+pub trait PrivacyServiceApi: Send + Sync {
+    /// Everything stored about the user, in one document
+    async fn export_user_data(&self, user_id: Uuid) -> Result<UserDataExport, PrivacyServiceError>;
+}
+
+#[async_trait]
+impl PrivacyServiceApi for PrivacyService {
+    async fn export_user_data(&self, user_id: Uuid) -> Result<UserDataExport, PrivacyServiceError> {
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| PrivacyServiceError::Dependency(e.to_string()))?
+            .ok_or(PrivacyServiceError::NotFound)?;
+        let profile = self
+            .user_service
+            .get_user_profile(user_id)
+            .await
+            .map_err(|e| PrivacyServiceError::from(AppError::from(e)))?;
+
+        let feeds = self
+            .feed_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| PrivacyServiceError::Dependency(e.to_string()))?;
+        let mute_rules = self
+            .mute_rule_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| PrivacyServiceError::Dependency(e.to_string()))?;
+        let usage = self
+            .usage_repo
+            .get_usage_history(user_id, MAX_EXPORT_USAGE_DAYS)
+            .await
+            .map_err(|e| PrivacyServiceError::Dependency(e.to_string()))?;
+        let sessions = self
+            .refresh_token_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| PrivacyServiceError::Dependency(e.to_string()))?;
+        let devices = self
+            .device_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| PrivacyServiceError::Dependency(e.to_string()))?;
+
+        Ok(UserDataExport {
+            version: USER_DATA_EXPORT_VERSION,
+            exported_at: Utc::now(),
+            account: ExportAccount {
+                id: user.id,
+                email: user.email,
+                oauth_provider: user.oauth_provider,
+                referral_code: user.referral_code,
+                subscription_expires_at: user.subscription_expires_at,
+                created_at: user.created_at,
+                updated_at: user.updated_at,
+            },
+            profile,
+            feeds,
+            mute_rules,
+            usage: usage
+                .into_iter()
+                .map(|record| ExportUsageDay {
+                    date: record.date,
+                    characters_used: record.characters_used,
+                    articles_synthesized: record.articles_synthesized,
+                    audio_seconds: record.audio_seconds,
+                    provider: record.provider,
+                    engine: record.engine,
+                })
+                .collect(),
+            sessions,
+            devices: devices
+                .into_iter()
+                .map(|device| ExportDevice {
+                    id: device.id,
+                    platform: device.platform,
+                    app_version: device.app_version,
+                    created_at: device.created_at,
+                    updated_at: device.updated_at,
+                })
+                .collect(),
+        })
+    }
+}
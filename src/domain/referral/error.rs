@@ -0,0 +1,48 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReferralServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("user not found")]
+    UserNotFound,
+    #[error("referral code not found")]
+    CodeNotFound,
+    #[error("referral already redeemed")]
+    AlreadyRedeemed,
+    #[error("referral limit reached")]
+    LimitReached,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for ReferralServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => ReferralServiceError::Invalid(msg),
+            _ => ReferralServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<ReferralServiceError> for AppError {
+    fn from(err: ReferralServiceError) -> Self {
+        match err {
+            ReferralServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            ReferralServiceError::UserNotFound => AppError::NotFound("User not found".to_string()),
+            ReferralServiceError::CodeNotFound => {
+                AppError::NotFound("Referral code not found".to_string())
+            }
+            ReferralServiceError::AlreadyRedeemed => AppError::Conflict(
+                "A referral code has already been redeemed for this account".to_string(),
+            ),
+            ReferralServiceError::LimitReached => AppError::RateLimitExceeded(
+                "This referral code has been used too many times recently".to_string(),
+            ),
+            ReferralServiceError::Dependency(msg) => AppError::Internal(msg),
+            ReferralServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::ReferralServiceError;
+pub use model::{Referral, REFERRAL_BONUS_DAYS};
+pub use service::{ReferralService, ReferralServiceApi};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Response for GET /api/me/referral
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReferralResponse {
+    pub code: String,
+    /// Users who redeemed this code
+    pub referrals_count: i64,
+    /// Extra characters per quota period while the bonus is active
+    pub bonus_characters: i32,
+    /// Set while the user's referral bonus is active
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bonus_until: Option<DateTime<Utc>>,
+}
+
+/// Request for POST /auth/redeem-referral
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RedeemReferralRequest {
+    pub code: String,
+}
+
+/// Response for POST /auth/redeem-referral
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RedeemReferralResponse {
+    pub bonus_characters: i32,
+    pub bonus_until: DateTime<Utc>,
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// How long the bonus quota lasts after each referral, for both users
+pub const REFERRAL_BONUS_DAYS: i32 = 30;
+
+/// Only new accounts can redeem a code, so existing users can't trade codes for bonuses
+pub const REFERRAL_REDEEM_WINDOW_DAYS: i64 = 14;
+
+/// Referrals credited to a single code within REFERRAL_LIMIT_WINDOW_DAYS
+pub const MAX_REFERRALS_PER_WINDOW: i64 = 10;
+pub const REFERRAL_LIMIT_WINDOW_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Referral {
+    pub id: Uuid,
+    pub referrer_id: Uuid,
+    pub referee_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
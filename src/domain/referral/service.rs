@@ -0,0 +1,181 @@
+use super::error::ReferralServiceError;
+use super::model::{
+    MAX_REFERRALS_PER_WINDOW, REFERRAL_BONUS_DAYS, REFERRAL_LIMIT_WINDOW_DAYS,
+    REFERRAL_REDEEM_WINDOW_DAYS,
+};
+use super::{RedeemReferralResponse, ReferralResponse};
+use crate::domain::plan::PlanLimits;
+use crate::domain::shared::code::{generate_code, normalize_code};
+use crate::domain::user::User;
+use crate::error::AppError;
+use crate::infrastructure::repositories::{ReferralRepository, UserRepository, UserRepositoryApi};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Attempts at finding an unused code before giving up
+const CODE_GENERATION_ATTEMPTS: usize = 3;
+
+pub struct ReferralService {
+    user_repo: Arc<UserRepository>,
+    referral_repo: Arc<ReferralRepository>,
+    plan_limits: Arc<PlanLimits>,
+}
+
+impl ReferralService {
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        referral_repo: Arc<ReferralRepository>,
+        plan_limits: Arc<PlanLimits>,
+    ) -> Self {
+        Self {
+            user_repo,
+            referral_repo,
+            plan_limits,
+        }
+    }
+}
+
+#[async_trait]
+pub trait ReferralServiceApi: Send + Sync {
+    /// The user's referral code, generated on first request, and how it has been used
+    async fn get_referral(&self, user_id: Uuid) -> Result<ReferralResponse, ReferralServiceError>;
+
+    /// Redeem another user's code, granting the bonus to both users
+    async fn redeem(
+        &self,
+        user_id: Uuid,
+        code: &str,
+    ) -> Result<RedeemReferralResponse, ReferralServiceError>;
+}
+
+#[async_trait]
+impl ReferralServiceApi for ReferralService {
+    async fn get_referral(&self, user_id: Uuid) -> Result<ReferralResponse, ReferralServiceError> {
+        let user = self.find_user(user_id).await?;
+        let code = match &user.referral_code {
+            Some(code) => code.clone(),
+            None => self.assign_code(user_id).await?,
+        };
+
+        let referrals_count = self
+            .referral_repo
+            .count_by_referrer(user_id, None)
+            .await
+            .map_err(|e| ReferralServiceError::Dependency(e.to_string()))?;
+
+        Ok(ReferralResponse {
+            code,
+            referrals_count,
+            bonus_characters: self.plan_limits.referral_bonus_characters,
+            bonus_until: user
+                .referral_bonus_until
+                .filter(|_| user.has_referral_bonus()),
+        })
+    }
+
+    async fn redeem(
+        &self,
+        user_id: Uuid,
+        code: &str,
+    ) -> Result<RedeemReferralResponse, ReferralServiceError> {
+        let code = normalize_code(code).ok_or_else(|| {
+            ReferralServiceError::Invalid("Referral code is required".to_string())
+        })?;
+
+        let referee = self.find_user(user_id).await?;
+        if Utc::now() - referee.created_at > Duration::days(REFERRAL_REDEEM_WINDOW_DAYS) {
+            return Err(ReferralServiceError::Invalid(format!(
+                "Referral codes can only be redeemed within {} days of signing up",
+                REFERRAL_REDEEM_WINDOW_DAYS
+            )));
+        }
+        if self.referred_by(user_id).await?.is_some() {
+            return Err(ReferralServiceError::AlreadyRedeemed);
+        }
+
+        let referrer = self
+            .user_repo
+            .find_by_referral_code(&code)
+            .await
+            .map_err(|e| ReferralServiceError::Dependency(e.to_string()))?
+            .ok_or(ReferralServiceError::CodeNotFound)?;
+        if referrer.id == user_id {
+            return Err(ReferralServiceError::Invalid(
+                "You cannot redeem your own referral code".to_string(),
+            ));
+        }
+        // Two accounts referring each other would double the bonus for one sign-up
+        if self.referred_by(referrer.id).await? == Some(user_id) {
+            return Err(ReferralServiceError::Invalid(
+                "You cannot redeem the code of a user you referred".to_string(),
+            ));
+        }
+
+        let recent = self
+            .referral_repo
+            .count_by_referrer(
+                referrer.id,
+                Some(Utc::now() - Duration::days(REFERRAL_LIMIT_WINDOW_DAYS)),
+            )
+            .await
+            .map_err(|e| ReferralServiceError::Dependency(e.to_string()))?;
+        if recent >= MAX_REFERRALS_PER_WINDOW {
+            tracing::warn!(referrer_id = %referrer.id, "Referral limit reached");
+            return Err(ReferralServiceError::LimitReached);
+        }
+
+        let bonus_until = self
+            .referral_repo
+            .redeem(referrer.id, user_id, REFERRAL_BONUS_DAYS)
+            .await
+            .map_err(|e| ReferralServiceError::Dependency(e.to_string()))?
+            .ok_or(ReferralServiceError::AlreadyRedeemed)?;
+
+        tracing::info!(referrer_id = %referrer.id, referee_id = %user_id, "Referral redeemed");
+
+        Ok(RedeemReferralResponse {
+            bonus_characters: self.plan_limits.referral_bonus_characters,
+            bonus_until,
+        })
+    }
+}
+
+impl ReferralService {
+    async fn find_user(&self, user_id: Uuid) -> Result<User, ReferralServiceError> {
+        self.user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| ReferralServiceError::Dependency(e.to_string()))?
+            .ok_or(ReferralServiceError::UserNotFound)
+    }
+
+    /// Who referred the user, if anyone
+    async fn referred_by(&self, user_id: Uuid) -> Result<Option<Uuid>, ReferralServiceError> {
+        let referral = self
+            .referral_repo
+            .find_by_referee(user_id)
+            .await
+            .map_err(|e| ReferralServiceError::Dependency(e.to_string()))?;
+        Ok(referral.map(|r| r.referrer_id))
+    }
+
+    /// Store a fresh code for the user, retrying on the rare collision with an existing one
+    async fn assign_code(&self, user_id: Uuid) -> Result<String, ReferralServiceError> {
+        for _ in 0..CODE_GENERATION_ATTEMPTS {
+            match self
+                .user_repo
+                .set_referral_code(user_id, &generate_code())
+                .await
+            {
+                Ok(code) => return Ok(code),
+                Err(AppError::Conflict(_)) => continue,
+                Err(e) => return Err(ReferralServiceError::Dependency(e.to_string())),
+            }
+        }
+        Err(ReferralServiceError::Dependency(
+            "Could not generate a unique referral code".to_string(),
+        ))
+    }
+}
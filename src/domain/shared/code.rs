@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+/// Length of generated referral and promo codes
+pub const CODE_LENGTH: usize = 8;
+
+/// Uppercase letters and digits without the easily confused 0/O and 1/I. 32 symbols, so each
+/// random byte maps onto it without bias.
+const ALPHABET: &[u8; 32] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Random code that is easy to read aloud and type on a phone
+pub fn generate_code() -> String {
+    Uuid::new_v4()
+        .as_bytes()
+        .iter()
+        .take(CODE_LENGTH)
+        .map(|b| ALPHABET[(*b as usize) % ALPHABET.len()] as char)
+        .collect()
+}
+
+/// Canonical form of a code typed by a user: surrounding whitespace and dashes dropped,
+/// uppercased. None if nothing is left.
+pub fn normalize_code(input: &str) -> Option<String> {
+    let code: String = input
+        .trim()
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    (!code.is_empty()).then_some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_code_uses_alphabet() {
+        let code = generate_code();
+
+        assert_eq!(code.len(), CODE_LENGTH);
+        assert!(code.bytes().all(|b| ALPHABET.contains(&b)));
+    }
+
+    #[test]
+    fn test_normalize_code() {
+        assert_eq!(normalize_code(" abcd-ef23 "), Some("ABCDEF23".to_string()));
+        assert_eq!(normalize_code("  "), None);
+    }
+}
@@ -7,7 +7,7 @@ pub struct ErrorResponse {
     pub request_id: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorDetail {
     pub code: String,
     pub message: String,
@@ -1,5 +1,8 @@
+pub mod code;
 pub mod error_dto;
+pub mod pagination;
 pub mod usage_dto;
 
 pub use error_dto::{ErrorDetail, ErrorResponse};
+pub use pagination::{Cursor, PageError, PageRequest, PageResponse};
 pub use usage_dto::UsageResponse;
@@ -0,0 +1,196 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Position in a listing sorted newest first: the timestamp and unique key of the last
+/// item already returned. Ties on the timestamp are broken by the key, so no item is
+/// skipped or repeated between pages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor<K> {
+    pub at: DateTime<Utc>,
+    pub key: K,
+}
+
+impl<K: Display> Cursor<K> {
+    /// Opaque form handed to clients; they only ever send it back
+    pub fn encode(&self) -> String {
+        let raw = format!(
+            "{}|{}",
+            self.at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            self.key
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+}
+
+impl<K: FromStr> Cursor<K> {
+    pub fn decode(value: &str) -> Option<Self> {
+        let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(value).ok()?).ok()?;
+        let (at, key) = raw.split_once('|')?;
+
+        Some(Self {
+            at: DateTime::parse_from_rfc3339(at).ok()?.with_timezone(&Utc),
+            key: key.parse().ok()?,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PageError {
+    #[error("limit must be between 1 and {0}")]
+    Limit(i64),
+    #[error("cursor is invalid")]
+    Cursor,
+}
+
+/// Validated `limit` and `cursor` query parameters
+#[derive(Debug)]
+pub struct PageRequest<K> {
+    pub limit: i64,
+    /// None for the first page
+    pub after: Option<Cursor<K>>,
+}
+
+impl<K: FromStr> PageRequest<K> {
+    pub fn parse(
+        limit: Option<i64>,
+        cursor: Option<&str>,
+        default_limit: i64,
+        max_limit: i64,
+    ) -> Result<Self, PageError> {
+        let limit = limit.unwrap_or(default_limit);
+        if !(1..=max_limit).contains(&limit) {
+            return Err(PageError::Limit(max_limit));
+        }
+        let after = cursor
+            .map(|cursor| Cursor::decode(cursor).ok_or(PageError::Cursor))
+            .transpose()?;
+
+        Ok(Self { limit, after })
+    }
+}
+
+impl<K> PageRequest<K> {
+    /// Rows to ask the repository for. The one past the page only tells whether
+    /// another page follows.
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit + 1
+    }
+}
+
+/// Envelope shared by every listing: paginated ones, and under `/v1` the lists that
+/// come whole
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    /// Pass as `cursor` to get the next page; absent on the last one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Items across all pages, for listings that can count them cheaply
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+}
+
+impl<T> PageResponse<T> {
+    /// A list that is never paginated, as a single last page
+    pub fn complete(items: Vec<T>) -> Self {
+        Self {
+            total: Some(items.len() as i64),
+            items,
+            next_cursor: None,
+        }
+    }
+
+    pub fn with_total(mut self, total: i64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Build a page from rows fetched with `PageRequest::fetch_limit`, in listing order
+    pub fn from_rows<R, K: Display>(
+        mut rows: Vec<R>,
+        request: &PageRequest<K>,
+        cursor: impl Fn(&R) -> Cursor<K>,
+        item: impl FnMut(R) -> T,
+    ) -> Self {
+        let has_more = rows.len() as i64 > request.limit;
+        rows.truncate(request.limit as usize);
+        let next_cursor = has_more
+            .then(|| rows.last().map(|row| cursor(row).encode()))
+            .flatten();
+
+        Self {
+            items: rows.into_iter().map(item).collect(),
+            next_cursor,
+            total: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_cursor_round_trips() {
+        let cursor = Cursor {
+            at: Utc.with_ymd_and_hms(2025, 10, 16, 8, 30, 0).unwrap(),
+            key: Uuid::new_v4(),
+        };
+
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(Cursor::<Uuid>::decode("not-a-cursor"), None);
+    }
+
+    #[test]
+    fn test_next_cursor_only_when_more_rows_exist() {
+        let request = PageRequest::<String>::parse(Some(2), None, 50, 200).unwrap();
+        let at = Utc::now();
+        let cursor = |key: &&str| Cursor {
+            at,
+            key: key.to_string(),
+        };
+
+        let page = PageResponse::from_rows(vec!["c", "b", "a"], &request, cursor, String::from);
+        assert_eq!(page.items, vec!["c", "b"]);
+        let next = Cursor::<String>::decode(page.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!(next.key, "b");
+
+        let page = PageResponse::from_rows(vec!["b", "a"], &request, cursor, String::from);
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_complete_list_is_a_single_page() {
+        let page = PageResponse::complete(vec!["a", "b"]);
+
+        assert_eq!(page.total, Some(2));
+        assert!(page.next_cursor.is_none());
+        assert_eq!(
+            serde_json::to_value(&page).unwrap(),
+            serde_json::json!({ "items": ["a", "b"], "total": 2 })
+        );
+    }
+
+    #[test]
+    fn test_limit_and_cursor_are_validated() {
+        assert!(matches!(
+            PageRequest::<Uuid>::parse(Some(0), None, 50, 200),
+            Err(PageError::Limit(200))
+        ));
+        assert!(matches!(
+            PageRequest::<Uuid>::parse(None, Some("garbage"), 50, 200),
+            Err(PageError::Cursor)
+        ));
+        assert_eq!(
+            PageRequest::<Uuid>::parse(None, None, 50, 200)
+                .unwrap()
+                .fetch_limit(),
+            51
+        );
+    }
+}
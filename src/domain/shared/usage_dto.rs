@@ -1,3 +1,4 @@
+use crate::domain::plan::UsageWarning;
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +9,12 @@ pub struct UsageResponse {
     pub usage: UsageStats,
     pub limits: UsageLimits,
     pub resets_at: DateTime<Utc>,
+    /// Unused Pro quota carried over from earlier days and still available, on top of
+    /// `limits`. Absent when rollover doesn't apply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollover_characters: Option<i32>,
+    /// Quota thresholds crossed in the current period, most severe only
+    pub warnings: Vec<UsageWarning>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub history: Option<Vec<DailyUsage>>,
 }
@@ -15,6 +22,7 @@ pub struct UsageResponse {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UsageStats {
     pub characters: i32,
+    /// Listening time of the audio produced
     pub minutes: f32,
     pub requests: i32,
 }
@@ -22,6 +30,7 @@ pub struct UsageStats {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UsageLimits {
     pub characters: i32,
+    /// The character limit at 1000 characters a minute
     pub minutes: i32,
     pub requests: i32,
 }
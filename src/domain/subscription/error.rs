@@ -0,0 +1,61 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SubscriptionServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("purchase belongs to another account")]
+    Conflict,
+    #[error("subscription is not active")]
+    Inactive,
+    #[error("free trial already used")]
+    TrialUsed,
+    #[error("promo code not found")]
+    PromoCodeNotFound,
+    #[error("promo code already redeemed")]
+    PromoCodeRedeemed,
+    #[error("store not configured: {0}")]
+    NotConfigured(String),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for SubscriptionServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => SubscriptionServiceError::Invalid(msg),
+            AppError::Conflict(_) => SubscriptionServiceError::Conflict,
+            _ => SubscriptionServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<SubscriptionServiceError> for AppError {
+    fn from(err: SubscriptionServiceError) -> Self {
+        match err {
+            SubscriptionServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            SubscriptionServiceError::Conflict => {
+                AppError::Conflict("Purchase is already linked to another account".to_string())
+            }
+            SubscriptionServiceError::Inactive => {
+                AppError::PaymentRequired("Subscription is not active".to_string())
+            }
+            SubscriptionServiceError::TrialUsed => {
+                AppError::Conflict("Free trial already used".to_string())
+            }
+            SubscriptionServiceError::PromoCodeNotFound => {
+                AppError::NotFound("Promo code not found".to_string())
+            }
+            SubscriptionServiceError::PromoCodeRedeemed => {
+                AppError::Conflict("You have already redeemed this promo code".to_string())
+            }
+            SubscriptionServiceError::NotConfigured(store) => {
+                AppError::ExternalService(format!("{} purchases are not configured", store))
+            }
+            SubscriptionServiceError::Dependency(msg) => AppError::Internal(msg),
+            SubscriptionServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::SubscriptionServiceError;
+pub use model::{
+    PromoCode, SubscriptionEvent, SubscriptionEventType, SubscriptionPlatform, SubscriptionPurchase,
+};
+pub use service::{SubscriptionService, SubscriptionServiceApi};
+
+use crate::domain::user::{SubscriptionStatus, SubscriptionTier, User};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Request to validate a Google Play subscription purchase
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidatePurchaseRequest {
+    pub purchase_token: String,
+}
+
+/// Request for POST /api/subscription/redeem
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RedeemPromoCodeRequest {
+    pub code: String,
+}
+
+/// Body of an App Store Server Notification V2 delivery
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppStoreNotificationRequest {
+    #[serde(rename = "signedPayload")]
+    pub signed_payload: String,
+}
+
+/// Response for POST /api/subscription/checkout-session
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckoutSessionResponse {
+    pub session_id: String,
+    /// Stripe-hosted page the web client redirects to
+    pub url: String,
+}
+
+/// Subscription state of the user after a purchase has been validated
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscriptionResponse {
+    pub subscription_tier: SubscriptionTier,
+    pub subscription_status: SubscriptionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription_expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial_started_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial_ends_at: Option<DateTime<Utc>>,
+}
+
+impl From<User> for SubscriptionResponse {
+    fn from(user: User) -> Self {
+        Self {
+            subscription_tier: user.subscription_tier,
+            subscription_status: user.subscription_status,
+            subscription_expires_at: user.subscription_expires_at,
+            trial_started_at: user.trial_started_at,
+            trial_ends_at: user.trial_ends_at,
+        }
+    }
+}
+
+/// Response for GET /api/subscription/history
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscriptionHistoryResponse {
+    pub subscription_tier: SubscriptionTier,
+    pub subscription_status: SubscriptionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription_expires_at: Option<DateTime<Utc>>,
+    /// When the store will next charge the user; absent when Pro won't auto-renew
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renews_at: Option<DateTime<Utc>>,
+    /// Newest first
+    pub events: Vec<SubscriptionEventResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubscriptionEventResponse {
+    pub event_type: SubscriptionEventType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<SubscriptionPlatform>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SubscriptionEvent> for SubscriptionEventResponse {
+    fn from(event: SubscriptionEvent) -> Self {
+        Self {
+            event_type: event.event_type,
+            platform: event.platform,
+            product_id: event.product_id,
+            expires_at: event.expires_at,
+            created_at: event.created_at,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Store a purchase was made through
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "lowercase")]
+pub enum SubscriptionPlatform {
+    #[serde(rename = "apple")]
+    Apple,
+    #[serde(rename = "google")]
+    Google,
+    #[serde(rename = "stripe")]
+    Stripe,
+}
+
+impl std::fmt::Display for SubscriptionPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubscriptionPlatform::Apple => write!(f, "apple"),
+            SubscriptionPlatform::Google => write!(f, "google"),
+            SubscriptionPlatform::Stripe => write!(f, "stripe"),
+        }
+    }
+}
+
+/// A store purchase that has been verified and linked to a user
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SubscriptionPurchase {
+    pub purchase_token: String,
+    pub user_id: Uuid,
+    pub platform: SubscriptionPlatform,
+    pub product_id: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Kind of change recorded in a user's billing history
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionEventType {
+    Purchase,
+    Renewal,
+    /// Auto-renew was turned off; Pro lasts until the paid period ends
+    Cancellation,
+    /// A renewal payment failed and the store is retrying
+    PaymentFailed,
+    Refund,
+    Expiration,
+    PromoCode,
+}
+
+/// An entry in a user's billing history
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SubscriptionEvent {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub event_type: SubscriptionEventType,
+    /// None for events that didn't come from a store
+    pub platform: Option<SubscriptionPlatform>,
+    pub product_id: Option<String>,
+    /// When Pro ends after this event
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Code that grants Pro for `duration_days` when redeemed
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PromoCode {
+    pub code: String,
+    pub duration_days: i32,
+    /// None for codes without a redemption cap
+    pub max_redemptions: Option<i32>,
+    pub redemption_count: i32,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Email of the admin who created the code
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PromoCode {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| at <= Utc::now())
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.max_redemptions
+            .is_some_and(|max| self.redemption_count >= max)
+    }
+}
@@ -0,0 +1,863 @@
+use super::error::SubscriptionServiceError;
+use crate::domain::audit::{AuditAction, AuditEvent, AuditService, AuditServiceApi};
+use crate::domain::shared::code::normalize_code;
+use crate::domain::subscription::{
+    AppStoreNotificationRequest, CheckoutSessionResponse, RedeemPromoCodeRequest,
+    SubscriptionEventResponse, SubscriptionEventType, SubscriptionHistoryResponse,
+    SubscriptionPlatform, SubscriptionPurchase, SubscriptionResponse, ValidatePurchaseRequest,
+};
+use crate::domain::user::{SubscriptionStatus, SubscriptionTier, TRIAL_DURATION_DAYS};
+use crate::error::AppError;
+use crate::infrastructure::auth::AuthUserCache;
+use crate::infrastructure::billing::google_play::{
+    GooglePlayClient, SUBSCRIPTION_STATE_ACTIVE, SUBSCRIPTION_STATE_CANCELED,
+    SUBSCRIPTION_STATE_IN_GRACE_PERIOD,
+};
+use crate::infrastructure::billing::{
+    AppStoreNotificationVerifier, StripeClient, StripeSubscription,
+};
+use crate::infrastructure::repositories::{
+    PromoCodeRepository, SubscriptionEventRepository, SubscriptionEventRepositoryApi,
+    SubscriptionPurchaseRepository, UserRepository, UserRepositoryApi,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const MAX_PURCHASE_TOKEN_LENGTH: usize = 4096;
+/// Billing history entries returned by GET /api/subscription/history
+const HISTORY_LIMIT: i64 = 100;
+
+/// A purchase confirmed by a store, independent of which store it came from
+struct VerifiedPurchase {
+    platform: SubscriptionPlatform,
+    purchase_token: String,
+    product_id: String,
+    tier: SubscriptionTier,
+    status: SubscriptionStatus,
+    expires_at: Option<DateTime<Utc>>,
+    /// The store refunded or revoked the purchase
+    refunded: bool,
+}
+
+pub struct SubscriptionService {
+    user_repo: Arc<UserRepository>,
+    purchase_repo: Arc<SubscriptionPurchaseRepository>,
+    promo_code_repo: Arc<PromoCodeRepository>,
+    event_repo: Arc<SubscriptionEventRepository>,
+    google_play_client: Option<Arc<GooglePlayClient>>,
+    stripe_client: Option<Arc<StripeClient>>,
+    app_store_verifier: Option<Arc<AppStoreNotificationVerifier>>,
+    audit_service: Arc<AuditService>,
+    user_cache: Arc<AuthUserCache>,
+}
+
+impl SubscriptionService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        purchase_repo: Arc<SubscriptionPurchaseRepository>,
+        promo_code_repo: Arc<PromoCodeRepository>,
+        event_repo: Arc<SubscriptionEventRepository>,
+        google_play_client: Option<Arc<GooglePlayClient>>,
+        stripe_client: Option<Arc<StripeClient>>,
+        app_store_verifier: Option<Arc<AppStoreNotificationVerifier>>,
+        audit_service: Arc<AuditService>,
+        user_cache: Arc<AuthUserCache>,
+    ) -> Self {
+        Self {
+            user_repo,
+            purchase_repo,
+            promo_code_repo,
+            event_repo,
+            google_play_client,
+            stripe_client,
+            app_store_verifier,
+            audit_service,
+            user_cache,
+        }
+    }
+}
+
+#[async_trait]
+pub trait SubscriptionServiceApi: Send + Sync {
+    /// Verify an Android subscription purchase and upgrade the user accordingly
+    async fn validate_google_purchase(
+        &self,
+        user_id: Uuid,
+        request: ValidatePurchaseRequest,
+    ) -> Result<SubscriptionResponse, SubscriptionServiceError>;
+
+    /// Start the free trial for a Free user who never had one
+    async fn start_trial(
+        &self,
+        user_id: Uuid,
+    ) -> Result<SubscriptionResponse, SubscriptionServiceError>;
+
+    /// Redeem a promo code, granting or extending Pro by the code's duration
+    async fn redeem_promo_code(
+        &self,
+        user_id: Uuid,
+        request: RedeemPromoCodeRequest,
+    ) -> Result<SubscriptionResponse, SubscriptionServiceError>;
+
+    /// Current subscription state with the user's billing history
+    async fn get_history(
+        &self,
+        user_id: Uuid,
+    ) -> Result<SubscriptionHistoryResponse, SubscriptionServiceError>;
+
+    /// Start a Stripe Checkout session for the web client
+    async fn create_checkout_session(
+        &self,
+        user_id: Uuid,
+    ) -> Result<CheckoutSessionResponse, SubscriptionServiceError>;
+
+    /// Verify and process a Stripe webhook delivery
+    async fn handle_stripe_webhook(
+        &self,
+        payload: &[u8],
+        signature: &str,
+    ) -> Result<(), SubscriptionServiceError>;
+
+    /// Verify and process an App Store Server Notification V2
+    async fn handle_app_store_notification(
+        &self,
+        request: AppStoreNotificationRequest,
+    ) -> Result<(), SubscriptionServiceError>;
+}
+
+#[async_trait]
+impl SubscriptionServiceApi for SubscriptionService {
+    async fn validate_google_purchase(
+        &self,
+        user_id: Uuid,
+        request: ValidatePurchaseRequest,
+    ) -> Result<SubscriptionResponse, SubscriptionServiceError> {
+        self.validate_purchase_token(&request.purchase_token)?;
+
+        let client = self
+            .google_play_client
+            .as_ref()
+            .ok_or_else(|| SubscriptionServiceError::NotConfigured("Google Play".to_string()))?;
+
+        let subscription = client.get_subscription(&request.purchase_token).await?;
+
+        let expires_at = subscription.expires_at();
+        let product_id = subscription
+            .product_id()
+            .ok_or_else(|| {
+                SubscriptionServiceError::Invalid("Purchase has no subscription items".to_string())
+            })?
+            .to_string();
+
+        let status = match subscription.subscription_state.as_str() {
+            SUBSCRIPTION_STATE_ACTIVE => SubscriptionStatus::Active,
+            SUBSCRIPTION_STATE_IN_GRACE_PERIOD => SubscriptionStatus::GracePeriod,
+            // Cancelled subscriptions stay entitled until the paid period ends
+            SUBSCRIPTION_STATE_CANCELED if expires_at.is_some_and(|at| at > Utc::now()) => {
+                SubscriptionStatus::Cancelled
+            }
+            _ => return Err(SubscriptionServiceError::Inactive),
+        };
+
+        self.ensure_purchase_owner(&request.purchase_token, user_id)
+            .await?;
+
+        if subscription.needs_acknowledgement() {
+            // A failed acknowledgement is retried the next time the client validates
+            if let Err(e) = client
+                .acknowledge_subscription(&product_id, &request.purchase_token)
+                .await
+            {
+                tracing::warn!(user_id = %user_id, error = %e, "Failed to acknowledge Google Play purchase");
+            }
+        }
+
+        self.apply_purchase(
+            user_id,
+            VerifiedPurchase {
+                platform: SubscriptionPlatform::Google,
+                purchase_token: request.purchase_token,
+                product_id,
+                tier: SubscriptionTier::Pro,
+                status,
+                expires_at,
+                refunded: false,
+            },
+        )
+        .await
+    }
+
+    async fn start_trial(
+        &self,
+        user_id: Uuid,
+    ) -> Result<SubscriptionResponse, SubscriptionServiceError> {
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?
+            .ok_or_else(|| SubscriptionServiceError::Invalid("User not found".to_string()))?;
+
+        if user.subscription_tier == SubscriptionTier::Pro {
+            return Err(SubscriptionServiceError::Invalid(
+                "Pro subscribers cannot start a free trial".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let user = self
+            .user_repo
+            .start_trial(user_id, now, now + Duration::days(TRIAL_DURATION_DAYS))
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?
+            .ok_or(SubscriptionServiceError::TrialUsed)?;
+
+        tracing::info!(user_id = %user_id, "Free trial started");
+        self.audit_service
+            .record(
+                AuditEvent::by_user(user_id, AuditAction::TrialStarted)
+                    .details(json!({ "trial_ends_at": user.trial_ends_at })),
+            )
+            .await;
+
+        Ok(SubscriptionResponse::from(user))
+    }
+
+    async fn redeem_promo_code(
+        &self,
+        user_id: Uuid,
+        request: RedeemPromoCodeRequest,
+    ) -> Result<SubscriptionResponse, SubscriptionServiceError> {
+        let code = normalize_code(&request.code).ok_or_else(|| {
+            SubscriptionServiceError::Invalid("Promo code is required".to_string())
+        })?;
+
+        let promo_code = self
+            .promo_code_repo
+            .find_by_code(&code)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?
+            .ok_or(SubscriptionServiceError::PromoCodeNotFound)?;
+        if promo_code.is_expired() {
+            return Err(SubscriptionServiceError::Invalid(
+                "Promo code has expired".to_string(),
+            ));
+        }
+        if promo_code.is_exhausted() {
+            return Err(SubscriptionServiceError::Invalid(
+                "Promo code has been fully redeemed".to_string(),
+            ));
+        }
+        let already_redeemed = self
+            .promo_code_repo
+            .has_redeemed(&code, user_id)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?;
+        if already_redeemed {
+            return Err(SubscriptionServiceError::PromoCodeRedeemed);
+        }
+
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?
+            .ok_or_else(|| SubscriptionServiceError::Invalid("User not found".to_string()))?;
+
+        // Days from the code are added on top of Pro time the user already has
+        let now = Utc::now();
+        let pro_from = match (user.effective_tier(), user.subscription_expires_at) {
+            (SubscriptionTier::Pro, None) => {
+                return Err(SubscriptionServiceError::Invalid(
+                    "Your Pro subscription has no end date to extend".to_string(),
+                ))
+            }
+            (SubscriptionTier::Pro, Some(expires_at)) => expires_at.max(now),
+            (SubscriptionTier::Free, _) => now,
+        };
+
+        let user = self
+            .promo_code_repo
+            .redeem(&code, user_id, pro_from)
+            .await
+            .map_err(|e| match e {
+                AppError::Conflict(_) => SubscriptionServiceError::PromoCodeRedeemed,
+                e => SubscriptionServiceError::Dependency(e.to_string()),
+            })?
+            // Lost a race for the last redemption, or the code expired in the meantime
+            .ok_or_else(|| {
+                SubscriptionServiceError::Invalid("Promo code is no longer available".to_string())
+            })?;
+        self.user_cache.invalidate(user_id).await;
+
+        tracing::info!(
+            user_id = %user_id,
+            code = %code,
+            expires_at = ?user.subscription_expires_at,
+            "Promo code redeemed"
+        );
+
+        self.record_event(
+            user_id,
+            SubscriptionEventType::PromoCode,
+            None,
+            None,
+            user.subscription_expires_at,
+        )
+        .await;
+
+        Ok(SubscriptionResponse::from(user))
+    }
+
+    async fn get_history(
+        &self,
+        user_id: Uuid,
+    ) -> Result<SubscriptionHistoryResponse, SubscriptionServiceError> {
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?
+            .ok_or_else(|| SubscriptionServiceError::Invalid("User not found".to_string()))?;
+
+        let events = self
+            .event_repo
+            .list_for_user(user_id, HISTORY_LIMIT)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?;
+
+        // Only store subscriptions renew; Pro from a promo code or an admin grant just ends
+        let renews_from_store = events.first().is_some_and(|event| {
+            event.platform.is_some()
+                && matches!(
+                    event.event_type,
+                    SubscriptionEventType::Purchase | SubscriptionEventType::Renewal
+                )
+        });
+        let renews_at = user.subscription_expires_at.filter(|_| {
+            renews_from_store && user.subscription_status == SubscriptionStatus::Active
+        });
+
+        Ok(SubscriptionHistoryResponse {
+            subscription_tier: user.subscription_tier,
+            subscription_status: user.subscription_status,
+            subscription_expires_at: user.subscription_expires_at,
+            renews_at,
+            events: events
+                .into_iter()
+                .map(SubscriptionEventResponse::from)
+                .collect(),
+        })
+    }
+
+    async fn create_checkout_session(
+        &self,
+        user_id: Uuid,
+    ) -> Result<CheckoutSessionResponse, SubscriptionServiceError> {
+        let client = self.stripe()?;
+
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?
+            .ok_or_else(|| SubscriptionServiceError::Invalid("User not found".to_string()))?;
+
+        let session = client.create_checkout_session(user.id, &user.email).await?;
+        let url = session.url.ok_or_else(|| {
+            SubscriptionServiceError::Dependency("Stripe returned no checkout URL".to_string())
+        })?;
+
+        Ok(CheckoutSessionResponse {
+            session_id: session.id,
+            url,
+        })
+    }
+
+    async fn handle_stripe_webhook(
+        &self,
+        payload: &[u8],
+        signature: &str,
+    ) -> Result<(), SubscriptionServiceError> {
+        let client = self.stripe()?;
+        let event = client.construct_event(payload, signature)?;
+
+        let subscription_id = match event.event_type.as_str() {
+            "checkout.session.completed" | "invoice.payment_failed" => {
+                event.data.object.get("subscription")
+            }
+            "customer.subscription.updated" | "customer.subscription.deleted" => {
+                event.data.object.get("id")
+            }
+            _ => {
+                tracing::debug!(event_id = %event.id, event_type = %event.event_type, "Ignoring Stripe event");
+                return Ok(());
+            }
+        }
+        .and_then(|id| id.as_str());
+
+        let Some(subscription_id) = subscription_id else {
+            tracing::debug!(event_id = %event.id, "Stripe event is not tied to a subscription");
+            return Ok(());
+        };
+
+        // Events can arrive out of order, so always act on the subscription's current state
+        let subscription = client.get_subscription(subscription_id).await?;
+
+        let Some(user_id) = self
+            .find_subscriber(subscription.user_id(), &subscription.id)
+            .await?
+        else {
+            tracing::warn!(event_id = %event.id, subscription_id = %subscription.id, "Stripe subscription has no matching user");
+            return Ok(());
+        };
+
+        let (tier, status) = stripe_entitlement(&subscription);
+
+        self.apply_purchase(
+            user_id,
+            VerifiedPurchase {
+                platform: SubscriptionPlatform::Stripe,
+                purchase_token: subscription.id.clone(),
+                product_id: subscription.price_id().unwrap_or_default().to_string(),
+                tier,
+                status,
+                expires_at: subscription.current_period_end(),
+                refunded: false,
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn handle_app_store_notification(
+        &self,
+        request: AppStoreNotificationRequest,
+    ) -> Result<(), SubscriptionServiceError> {
+        let verifier = self
+            .app_store_verifier
+            .as_deref()
+            .ok_or_else(|| SubscriptionServiceError::NotConfigured("App Store".to_string()))?;
+
+        let notification = verifier.verify_notification(&request.signed_payload)?;
+
+        let Some((tier, status)) = app_store_entitlement(
+            &notification.notification_type,
+            notification.subtype.as_deref(),
+        ) else {
+            tracing::debug!(
+                notification_uuid = %notification.notification_uuid,
+                notification_type = %notification.notification_type,
+                "Ignoring App Store notification"
+            );
+            return Ok(());
+        };
+
+        let Some(transaction) = notification.transaction else {
+            tracing::warn!(notification_uuid = %notification.notification_uuid, "App Store notification has no transaction");
+            return Ok(());
+        };
+
+        let Some(user_id) = self
+            .find_subscriber(
+                transaction.app_account_token,
+                &transaction.original_transaction_id,
+            )
+            .await?
+        else {
+            tracing::warn!(
+                notification_uuid = %notification.notification_uuid,
+                original_transaction_id = %transaction.original_transaction_id,
+                "App Store transaction has no matching user"
+            );
+            return Ok(());
+        };
+
+        let refunded = matches!(notification.notification_type.as_str(), "REFUND" | "REVOKE");
+
+        self.apply_purchase(
+            user_id,
+            VerifiedPurchase {
+                platform: SubscriptionPlatform::Apple,
+                expires_at: transaction.expires_at(),
+                purchase_token: transaction.original_transaction_id,
+                product_id: transaction.product_id,
+                tier,
+                status,
+                refunded,
+            },
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl SubscriptionService {
+    fn stripe(&self) -> Result<&StripeClient, SubscriptionServiceError> {
+        self.stripe_client
+            .as_deref()
+            .ok_or_else(|| SubscriptionServiceError::NotConfigured("Stripe".to_string()))
+    }
+
+    /// Resolve the user behind a store subscription, from the user id the client attached
+    /// to the purchase or from an earlier event that linked it
+    async fn find_subscriber(
+        &self,
+        attached_user_id: Option<Uuid>,
+        purchase_token: &str,
+    ) -> Result<Option<Uuid>, SubscriptionServiceError> {
+        let user_id = match attached_user_id {
+            Some(user_id) => Some(user_id),
+            None => self
+                .purchase_repo
+                .find_by_token(purchase_token)
+                .await
+                .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?
+                .map(|purchase| purchase.user_id),
+        };
+
+        let Some(user_id) = user_id else {
+            return Ok(None);
+        };
+
+        let exists = self
+            .user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?
+            .is_some();
+
+        Ok(exists.then_some(user_id))
+    }
+
+    fn validate_purchase_token(&self, token: &str) -> Result<(), SubscriptionServiceError> {
+        let valid = !token.is_empty()
+            && token.len() <= MAX_PURCHASE_TOKEN_LENGTH
+            && token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+
+        if !valid {
+            return Err(SubscriptionServiceError::Invalid(
+                "Invalid purchase token".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn ensure_purchase_owner(
+        &self,
+        purchase_token: &str,
+        user_id: Uuid,
+    ) -> Result<(), SubscriptionServiceError> {
+        let existing = self
+            .purchase_repo
+            .find_by_token(purchase_token)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?;
+
+        match existing {
+            Some(purchase) if purchase.user_id != user_id => {
+                Err(SubscriptionServiceError::Conflict)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Record a verified purchase and apply its entitlement. Shared by every store integration.
+    async fn apply_purchase(
+        &self,
+        user_id: Uuid,
+        purchase: VerifiedPurchase,
+    ) -> Result<SubscriptionResponse, SubscriptionServiceError> {
+        let previous = self
+            .purchase_repo
+            .find_by_token(&purchase.purchase_token)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?;
+        let event_type = purchase_event(previous.as_ref(), &purchase);
+
+        let linked = self
+            .purchase_repo
+            .upsert_for_user(
+                &purchase.purchase_token,
+                user_id,
+                purchase.platform,
+                &purchase.product_id,
+                purchase.expires_at,
+            )
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?;
+
+        if !linked {
+            return Err(SubscriptionServiceError::Conflict);
+        }
+
+        let user = self
+            .user_repo
+            .update_subscription(user_id, purchase.tier, purchase.status, purchase.expires_at)
+            .await
+            .map_err(|e| SubscriptionServiceError::Dependency(e.to_string()))?;
+        self.user_cache.invalidate(user_id).await;
+
+        tracing::info!(
+            user_id = %user_id,
+            platform = %purchase.platform,
+            product_id = %purchase.product_id,
+            "Subscription purchase validated"
+        );
+
+        if let Some(event_type) = event_type {
+            self.record_event(
+                user_id,
+                event_type,
+                Some(purchase.platform),
+                Some(&purchase.product_id),
+                purchase.expires_at,
+            )
+            .await;
+        }
+
+        Ok(SubscriptionResponse::from(user))
+    }
+
+    /// Add an entry to the billing history and the audit log. The entitlement has already
+    /// been applied by then, so a failure here is logged rather than failing the request.
+    async fn record_event(
+        &self,
+        user_id: Uuid,
+        event_type: SubscriptionEventType,
+        platform: Option<SubscriptionPlatform>,
+        product_id: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) {
+        match self
+            .event_repo
+            .record(user_id, event_type, platform, product_id, expires_at)
+            .await
+        {
+            Ok(true) => {}
+            // A redelivered notification or revalidation: nothing new to audit
+            Ok(false) => return,
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, event_type = ?event_type, error = %e, "Failed to record subscription event");
+            }
+        }
+
+        self.audit_service
+            .record(
+                AuditEvent::by_system("billing", AuditAction::SubscriptionChanged)
+                    .for_user(user_id)
+                    .details(json!({
+                        "event_type": event_type,
+                        "platform": platform,
+                        "product_id": product_id,
+                        "expires_at": expires_at,
+                    })),
+            )
+            .await;
+    }
+}
+
+/// Classify a verified purchase for the billing history by comparing it with what we had
+/// stored for the same purchase token. Returns None when nothing billing-related changed.
+fn purchase_event(
+    previous: Option<&SubscriptionPurchase>,
+    purchase: &VerifiedPurchase,
+) -> Option<SubscriptionEventType> {
+    if purchase.refunded {
+        return Some(SubscriptionEventType::Refund);
+    }
+    if purchase.tier == SubscriptionTier::Free {
+        return Some(SubscriptionEventType::Expiration);
+    }
+    match purchase.status {
+        SubscriptionStatus::Cancelled => return Some(SubscriptionEventType::Cancellation),
+        SubscriptionStatus::GracePeriod | SubscriptionStatus::OnHold => {
+            return Some(SubscriptionEventType::PaymentFailed)
+        }
+        _ => {}
+    }
+    match previous {
+        None => Some(SubscriptionEventType::Purchase),
+        Some(previous) if purchase.expires_at > previous.expires_at => {
+            Some(SubscriptionEventType::Renewal)
+        }
+        Some(_) => None,
+    }
+}
+
+/// Map a Stripe subscription status to our tier and status
+fn stripe_entitlement(subscription: &StripeSubscription) -> (SubscriptionTier, SubscriptionStatus) {
+    match subscription.status.as_str() {
+        "active" | "trialing" if subscription.cancel_at_period_end => {
+            (SubscriptionTier::Pro, SubscriptionStatus::Cancelled)
+        }
+        "active" | "trialing" => (SubscriptionTier::Pro, SubscriptionStatus::Active),
+        // Stripe is retrying the renewal payment
+        "past_due" => (SubscriptionTier::Pro, SubscriptionStatus::GracePeriod),
+        // Retries exhausted but the subscription is kept open for a later payment
+        "unpaid" | "paused" => (SubscriptionTier::Pro, SubscriptionStatus::OnHold),
+        _ => (SubscriptionTier::Free, SubscriptionStatus::Expired),
+    }
+}
+
+/// Map an App Store notification to our tier and status. Returns None for notifications
+/// that don't change entitlement.
+fn app_store_entitlement(
+    notification_type: &str,
+    subtype: Option<&str>,
+) -> Option<(SubscriptionTier, SubscriptionStatus)> {
+    match (notification_type, subtype) {
+        ("SUBSCRIBED" | "DID_RENEW" | "OFFER_REDEEMED", _)
+        | ("DID_CHANGE_RENEWAL_STATUS", Some("AUTO_RENEW_ENABLED")) => {
+            Some((SubscriptionTier::Pro, SubscriptionStatus::Active))
+        }
+        ("DID_CHANGE_RENEWAL_STATUS", Some("AUTO_RENEW_DISABLED")) => {
+            Some((SubscriptionTier::Pro, SubscriptionStatus::Cancelled))
+        }
+        ("DID_FAIL_TO_RENEW", Some("GRACE_PERIOD")) => {
+            Some((SubscriptionTier::Pro, SubscriptionStatus::GracePeriod))
+        }
+        // Apple keeps retrying billing after the grace period, without entitlement
+        ("DID_FAIL_TO_RENEW" | "GRACE_PERIOD_EXPIRED", _) => {
+            Some((SubscriptionTier::Pro, SubscriptionStatus::OnHold))
+        }
+        ("EXPIRED" | "REFUND" | "REVOKE", _) => {
+            Some((SubscriptionTier::Free, SubscriptionStatus::Expired))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stripe_subscription(status: &str, cancel_at_period_end: bool) -> StripeSubscription {
+        serde_json::from_value(serde_json::json!({
+            "id": "sub_123",
+            "status": status,
+            "cancel_at_period_end": cancel_at_period_end,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_stripe_payment_retry_states() {
+        assert_eq!(
+            stripe_entitlement(&stripe_subscription("past_due", false)),
+            (SubscriptionTier::Pro, SubscriptionStatus::GracePeriod)
+        );
+        assert_eq!(
+            stripe_entitlement(&stripe_subscription("unpaid", false)),
+            (SubscriptionTier::Pro, SubscriptionStatus::OnHold)
+        );
+        assert_eq!(
+            stripe_entitlement(&stripe_subscription("active", true)),
+            (SubscriptionTier::Pro, SubscriptionStatus::Cancelled)
+        );
+        assert_eq!(
+            stripe_entitlement(&stripe_subscription("canceled", false)),
+            (SubscriptionTier::Free, SubscriptionStatus::Expired)
+        );
+    }
+
+    #[test]
+    fn test_app_store_billing_failure_states() {
+        assert_eq!(
+            app_store_entitlement("DID_FAIL_TO_RENEW", Some("GRACE_PERIOD")),
+            Some((SubscriptionTier::Pro, SubscriptionStatus::GracePeriod))
+        );
+        assert_eq!(
+            app_store_entitlement("GRACE_PERIOD_EXPIRED", None),
+            Some((SubscriptionTier::Pro, SubscriptionStatus::OnHold))
+        );
+        assert_eq!(
+            app_store_entitlement("REFUND", None),
+            Some((SubscriptionTier::Free, SubscriptionStatus::Expired))
+        );
+        assert_eq!(app_store_entitlement("TEST", None), None);
+    }
+
+    fn verified_purchase(
+        status: SubscriptionStatus,
+        expires_at: DateTime<Utc>,
+        refunded: bool,
+    ) -> VerifiedPurchase {
+        VerifiedPurchase {
+            platform: SubscriptionPlatform::Google,
+            purchase_token: "token".to_string(),
+            product_id: "pro_monthly".to_string(),
+            tier: SubscriptionTier::Pro,
+            status,
+            expires_at: Some(expires_at),
+            refunded,
+        }
+    }
+
+    #[test]
+    fn test_purchase_event_classification() {
+        let now = Utc::now();
+        let stored = SubscriptionPurchase {
+            purchase_token: "token".to_string(),
+            user_id: Uuid::new_v4(),
+            platform: SubscriptionPlatform::Google,
+            product_id: "pro_monthly".to_string(),
+            expires_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        };
+        let next_period = now + Duration::days(30);
+
+        assert_eq!(
+            purchase_event(
+                None,
+                &verified_purchase(SubscriptionStatus::Active, next_period, false)
+            ),
+            Some(SubscriptionEventType::Purchase)
+        );
+        assert_eq!(
+            purchase_event(
+                Some(&stored),
+                &verified_purchase(SubscriptionStatus::Active, next_period, false)
+            ),
+            Some(SubscriptionEventType::Renewal)
+        );
+        // Revalidating an unchanged purchase adds nothing to the history
+        assert_eq!(
+            purchase_event(
+                Some(&stored),
+                &verified_purchase(SubscriptionStatus::Active, now, false)
+            ),
+            None
+        );
+        assert_eq!(
+            purchase_event(
+                Some(&stored),
+                &verified_purchase(SubscriptionStatus::Cancelled, now, false)
+            ),
+            Some(SubscriptionEventType::Cancellation)
+        );
+        assert_eq!(
+            purchase_event(
+                Some(&stored),
+                &verified_purchase(SubscriptionStatus::GracePeriod, now, false)
+            ),
+            Some(SubscriptionEventType::PaymentFailed)
+        );
+        assert_eq!(
+            purchase_event(
+                Some(&stored),
+                &verified_purchase(SubscriptionStatus::Expired, now, true)
+            ),
+            Some(SubscriptionEventType::Refund)
+        );
+    }
+}
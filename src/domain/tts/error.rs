@@ -1,5 +1,11 @@
 use crate::error::AppError;
 
+/// Message for provider failures worth retrying
+pub const TTS_PROVIDER_UNAVAILABLE_MESSAGE: &str =
+    "Speech synthesis is temporarily unavailable, retry shortly";
+/// Message for text the provider refused to voice
+pub const TTS_PROVIDER_REJECTED_MESSAGE: &str = "The text could not be synthesized";
+
 #[derive(Debug, thiserror::Error)]
 pub enum TtsServiceError {
     #[error("dependency error: {0}")]
@@ -8,10 +14,41 @@ pub enum TtsServiceError {
     Invalid(String),
     #[error("payment required: {0}")]
     PaymentRequired(String),
+    #[error("TTS provider error: {0}")]
+    Provider(#[from] TtsProviderError),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+/// Why the TTS provider returned no audio
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TtsProviderError {
+    /// The provider asked us to slow down
+    #[error("throttled: {0}")]
+    Throttled(String),
+    /// The provider refused the request itself, e.g. text too long or malformed SSML
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    /// The provider failed on its side, timed out or couldn't be reached
+    #[error("provider unavailable: {0}")]
+    ProviderUnavailable(String),
+    /// The provider rejected our credentials
+    #[error("authentication failed: {0}")]
+    Auth(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl TtsProviderError {
+    /// Whether the same request can succeed later; clients are told to retry these
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            TtsProviderError::Throttled(_) | TtsProviderError::ProviderUnavailable(_)
+        )
+    }
+}
+
 impl From<AppError> for TtsServiceError {
     fn from(err: AppError) -> Self {
         match err {
@@ -28,7 +65,43 @@ impl From<TtsServiceError> for AppError {
             TtsServiceError::PaymentRequired(msg) => AppError::PaymentRequired(msg),
             TtsServiceError::Invalid(msg) => AppError::BadRequest(msg),
             TtsServiceError::Dependency(msg) => AppError::ExternalService(msg),
+            TtsServiceError::Provider(e) if e.is_retryable() => {
+                AppError::Overloaded(TTS_PROVIDER_UNAVAILABLE_MESSAGE.to_string())
+            }
+            TtsServiceError::Provider(TtsProviderError::InvalidInput(_)) => {
+                AppError::BadRequest(TTS_PROVIDER_REJECTED_MESSAGE.to_string())
+            }
+            TtsServiceError::Provider(e) => AppError::ExternalService(e.to_string()),
             TtsServiceError::Other(e) => AppError::Internal(e.to_string()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    #[test]
+    fn test_provider_errors_map_to_statuses() {
+        let status =
+            |error: TtsProviderError| AppError::from(TtsServiceError::from(error)).status_code();
+
+        assert_eq!(
+            status(TtsProviderError::Throttled("slow down".to_string())),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(TtsProviderError::ProviderUnavailable("timeout".to_string())),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(TtsProviderError::InvalidInput("bad SSML".to_string())),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(TtsProviderError::Auth("expired token".to_string())),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
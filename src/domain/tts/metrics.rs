@@ -0,0 +1,242 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+/// Upper bounds of the latency buckets, in milliseconds; slower calls land in the last,
+/// unbounded bucket
+const LATENCY_BUCKETS_MS: [u64; 8] = [100, 250, 500, 1000, 2500, 5000, 10_000, 30_000];
+
+/// What kind of failure a provider call ended in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TtsErrorCategory {
+    /// The provider asked us to slow down
+    Throttled,
+    /// The provider refused the request itself, e.g. text it can't voice
+    Rejected,
+    /// The provider failed on its side
+    ProviderError,
+    /// No answer in time
+    Timeout,
+    /// The request or the audio download didn't get through
+    Network,
+}
+
+impl fmt::Display for TtsErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let category = match self {
+            Self::Throttled => "throttled",
+            Self::Rejected => "rejected",
+            Self::ProviderError => "provider_error",
+            Self::Timeout => "timeout",
+            Self::Network => "network",
+        };
+        f.write_str(category)
+    }
+}
+
+/// Provider, engine and language a latency series covers
+type SeriesKey = (String, String, String);
+
+#[derive(Default)]
+struct LatencyHistogram {
+    /// One count per bucket in `LATENCY_BUCKETS_MS`, plus the unbounded one
+    buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    count: u64,
+    sum_ms: u64,
+}
+
+impl LatencyHistogram {
+    fn observe(&mut self, elapsed_ms: u64) {
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| elapsed_ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum_ms += elapsed_ms;
+    }
+
+    fn series(&self, (provider, engine, language): &SeriesKey) -> TtsLatencySeries {
+        TtsLatencySeries {
+            provider: provider.clone(),
+            engine: engine.clone(),
+            language: language.clone(),
+            count: self.count,
+            sum_ms: self.sum_ms,
+            buckets: self
+                .buckets
+                .iter()
+                .enumerate()
+                .map(|(index, count)| LatencyBucket {
+                    le_ms: LATENCY_BUCKETS_MS.get(index).copied(),
+                    count: *count,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Recorded {
+    latency: BTreeMap<SeriesKey, LatencyHistogram>,
+    errors: BTreeMap<(String, TtsErrorCategory), u64>,
+}
+
+/// Latency and failures of calls to TTS providers, kept in memory per instance since it
+/// started. Each call of a multi-batch synthesis counts on its own.
+pub struct TtsMetrics {
+    started_at: DateTime<Utc>,
+    recorded: Mutex<Recorded>,
+}
+
+impl TtsMetrics {
+    pub fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            recorded: Mutex::new(Recorded::default()),
+        }
+    }
+
+    /// A call that returned audio
+    pub fn record_success(&self, provider: &str, engine: &str, language: &str, elapsed: Duration) {
+        let key = (
+            provider.to_string(),
+            engine.to_string(),
+            language.to_string(),
+        );
+        self.lock()
+            .latency
+            .entry(key)
+            .or_default()
+            .observe(elapsed.as_millis() as u64);
+    }
+
+    pub fn record_error(&self, provider: &str, category: TtsErrorCategory) {
+        *self
+            .lock()
+            .errors
+            .entry((provider.to_string(), category))
+            .or_default() += 1;
+    }
+
+    pub fn snapshot(&self) -> TtsMetricsSnapshot {
+        let recorded = self.lock();
+
+        TtsMetricsSnapshot {
+            since: self.started_at,
+            latency: recorded
+                .latency
+                .iter()
+                .map(|(key, histogram)| histogram.series(key))
+                .collect(),
+            errors: recorded
+                .errors
+                .iter()
+                .map(|((provider, category), count)| TtsErrorCount {
+                    provider: provider.clone(),
+                    category: *category,
+                    count: *count,
+                })
+                .collect(),
+        }
+    }
+
+    /// Every update leaves the counters consistent, so a panic elsewhere doesn't poison them
+    fn lock(&self) -> MutexGuard<'_, Recorded> {
+        self.recorded.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for TtsMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Response for GET /admin/tts/metrics
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TtsMetricsSnapshot {
+    /// When this instance started counting
+    pub since: DateTime<Utc>,
+    pub latency: Vec<TtsLatencySeries>,
+    pub errors: Vec<TtsErrorCount>,
+}
+
+/// Latency of successful calls for one provider, engine and language
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TtsLatencySeries {
+    pub provider: String,
+    pub engine: String,
+    pub language: String,
+    pub count: u64,
+    pub sum_ms: u64,
+    /// Calls per bucket, not cumulative
+    pub buckets: Vec<LatencyBucket>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LatencyBucket {
+    /// Slowest call counted in the bucket, in milliseconds; absent for the last bucket
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TtsErrorCount {
+    pub provider: String,
+    pub category: TtsErrorCategory,
+    pub count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_is_bucketed_per_series() {
+        let metrics = TtsMetrics::new();
+        for elapsed_ms in [80, 100, 700, 45_000] {
+            metrics.record_success("polly", "neural", "en", Duration::from_millis(elapsed_ms));
+        }
+        metrics.record_success("polly", "neural", "es", Duration::from_millis(300));
+
+        let snapshot = metrics.snapshot();
+
+        assert_eq!(snapshot.latency.len(), 2);
+        let english = &snapshot.latency[0];
+        assert_eq!(english.language, "en");
+        assert_eq!(english.count, 4);
+        assert_eq!(english.sum_ms, 45_880);
+        let counts: Vec<u64> = english.buckets.iter().map(|bucket| bucket.count).collect();
+        assert_eq!(counts, vec![2, 0, 0, 1, 0, 0, 0, 0, 1]);
+        assert_eq!(english.buckets.last().unwrap().le_ms, None);
+    }
+
+    #[test]
+    fn test_errors_are_counted_by_category() {
+        let metrics = TtsMetrics::new();
+        metrics.record_error("polly", TtsErrorCategory::Throttled);
+        metrics.record_error("polly", TtsErrorCategory::Throttled);
+        metrics.record_error("polly", TtsErrorCategory::Timeout);
+
+        assert_eq!(
+            metrics.snapshot().errors,
+            vec![
+                TtsErrorCount {
+                    provider: "polly".to_string(),
+                    category: TtsErrorCategory::Throttled,
+                    count: 2,
+                },
+                TtsErrorCount {
+                    provider: "polly".to_string(),
+                    category: TtsErrorCategory::Timeout,
+                    count: 1,
+                },
+            ]
+        );
+    }
+}
@@ -1,7 +1,17 @@
+pub mod audio;
 pub mod error;
 pub mod language;
+pub mod metrics;
+pub mod polly;
 pub mod service;
 
-pub use error::TtsServiceError;
+pub use error::{TtsProviderError, TtsServiceError};
 pub use language::{detect_language, get_voice_for_language, LanguageCode};
+pub use metrics::{TtsErrorCategory, TtsMetrics, TtsMetricsSnapshot};
+pub use polly::PollyApi;
 pub use service::{TtsService, TtsServiceApi, TtsSynthesisResult};
+
+/// Provider recorded in synthesis statistics for audio made by Polly
+pub const POLLY_PROVIDER: &str = "polly";
+/// Provider recorded for audio served from the cache
+pub const CACHE_PROVIDER: &str = "cache";
@@ -0,0 +1,53 @@
+use crate::infrastructure::auth::request_id::{current_request_id, X_REQUEST_ID};
+use async_trait::async_trait;
+use aws_sdk_polly::{
+    error::SdkError,
+    operation::synthesize_speech::{SynthesizeSpeechError, SynthesizeSpeechOutput},
+    types::{Engine, OutputFormat, VoiceId},
+    Client as PollyClient,
+};
+
+/// The Polly calls `TtsService` makes. Errors are the SDK's own, so the service maps
+/// them the same way whatever answers.
+#[async_trait]
+pub trait PollyApi: Send + Sync {
+    /// Synthesize `text` to MP3 with the voice and engine
+    async fn synthesize_speech(
+        &self,
+        text: &str,
+        voice_id: VoiceId,
+        engine: Engine,
+    ) -> Result<SynthesizeSpeechOutput, SdkError<SynthesizeSpeechError>>;
+}
+
+#[async_trait]
+impl PollyApi for PollyClient {
+    async fn synthesize_speech(
+        &self,
+        text: &str,
+        voice_id: VoiceId,
+        engine: Engine,
+    ) -> Result<SynthesizeSpeechOutput, SdkError<SynthesizeSpeechError>> {
+        let request = PollyClient::synthesize_speech(self)
+            .text(text)
+            .voice_id(voice_id)
+            .output_format(OutputFormat::Mp3)
+            .engine(engine);
+
+        // Lets a synthesis be traced from our logs into AWS's
+        match current_request_id() {
+            Some(request_id) => {
+                request
+                    .customize()
+                    .mutate_request(move |http_request| {
+                        http_request
+                            .headers_mut()
+                            .insert(X_REQUEST_ID, request_id.clone());
+                    })
+                    .send()
+                    .await
+            }
+            None => request.send().await,
+        }
+    }
+}
@@ -1,21 +1,67 @@
-use super::error::TtsServiceError;
-use super::language::LanguageCode;
-use crate::domain::user::{SubscriptionTier, User};
-use crate::infrastructure::repositories::{UsageRepository, UserRepository};
+use super::audio::mp3_duration;
+use super::error::{TtsProviderError, TtsServiceError};
+use super::language::{get_voice_for_language, is_voice_neural_compatible, LanguageCode};
+use super::metrics::{TtsErrorCategory, TtsMetrics};
+use super::polly::PollyApi;
+use super::{CACHE_PROVIDER, POLLY_PROVIDER};
+use crate::domain::events::{DomainEvent, EventBus};
+use crate::domain::plan::{
+    rollover_balance, usage_warnings, PlanLimits, QuotaPeriod, UsagePeriod, CHARACTERS_PER_MINUTE,
+};
+use crate::domain::user::{User, TRIAL_EXPIRED_MESSAGE};
+use crate::infrastructure::audio_cdn::AudioCdn;
+use crate::infrastructure::cache::CacheStore;
+use crate::infrastructure::config::RetentionConfig;
+use crate::infrastructure::email::{EmailSender, EmailTemplate};
+use crate::infrastructure::events::{AccountEventKind, AccountEventStream};
+use crate::infrastructure::repositories::{
+    OutboxRepositoryApi, SynthesisUsage, UsageRepositoryApi, UserRepositoryApi,
+};
+use crate::infrastructure::sanitizer::sanitize_html;
 use async_trait::async_trait;
 use aws_sdk_polly::{
-    types::{Engine, OutputFormat, VoiceId},
-    Client as PollyClient,
+    error::{ProvideErrorMetadata, SdkError},
+    types::{Engine, VoiceId},
 };
+use chrono::{NaiveDate, Utc};
 use html2text::from_read;
 use lingua::{LanguageDetector, LanguageDetectorBuilder};
-use moka::future::Cache;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::future::Future;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
 use uuid::Uuid;
 
-const CHARACTERS_PER_MINUTE: f32 = 1000.0;
 const MAX_BATCH_SIZE: usize = 3000;
+/// Always the neural engine; select_voice only returns neural-capable voices
+const POLLY_ENGINE: Engine = Engine::Neural;
+/// Codes AWS answers with when it doesn't accept our credentials
+const POLLY_AUTH_ERROR_CODES: [&str; 5] = [
+    "UnrecognizedClientException",
+    "InvalidSignatureException",
+    "AccessDeniedException",
+    "ExpiredTokenException",
+    "MissingAuthenticationTokenException",
+];
+/// Longest synthesized audio stays cached; the cached audio retention can only shorten it
+const CACHE_TTL: Duration = Duration::from_secs(30 * 60);
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+/// Extra attempts at a batch the provider throttled or failed, before the client is told
+/// to retry
+const PROVIDER_RETRIES: u32 = 2;
+/// Wait before the first retry; doubled for each one after
+const PROVIDER_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Where the user stands against their quota, before the request being checked
+struct QuotaCheck {
+    characters_used: i32,
+    character_limit: i32,
+    /// Rollover balance carried into today, for users eligible for rollover
+    rollover: Option<i32>,
+}
 
 #[derive(Debug, Clone)]
 pub struct TtsSynthesisResult {
@@ -23,55 +69,136 @@ pub struct TtsSynthesisResult {
     pub language_detected: LanguageCode,
     pub char_count: i32,
     pub duration_minutes: f32,
+    /// Signed CDN URL of the audio, when it's delivered through a CDN
+    pub audio_url: Option<String>,
+}
+
+/// Text ready for synthesis, cached by a hash of the text it was made from
+#[derive(Serialize, Deserialize)]
+struct CleanedText {
+    text: String,
+    language: LanguageCode,
+}
+
+/// Metadata stored in front of the audio in cache entries
+#[derive(Serialize, Deserialize)]
+struct CachedSynthesis {
+    language_detected: LanguageCode,
+    char_count: i32,
+    duration_minutes: f32,
+    /// The audio was also uploaded to the CDN
+    #[serde(default)]
+    on_cdn: bool,
+}
+
+impl TtsSynthesisResult {
+    /// Cache entries hold the metadata as a JSON line followed by the raw audio
+    fn to_cache_entry(&self) -> Vec<u8> {
+        let metadata = CachedSynthesis {
+            language_detected: self.language_detected,
+            char_count: self.char_count,
+            duration_minutes: self.duration_minutes,
+            on_cdn: self.audio_url.is_some(),
+        };
+        let mut entry = serde_json::to_vec(&metadata).unwrap_or_default();
+        entry.push(b'\n');
+        entry.extend_from_slice(&self.audio_data);
+        entry
+    }
+
+    /// The cached result, without a URL as those expire, and whether the audio is on the
+    /// CDN to sign a new one for
+    fn from_cache_entry(entry: &[u8]) -> Option<(Self, bool)> {
+        let newline = entry.iter().position(|&b| b == b'\n')?;
+        let metadata: CachedSynthesis = serde_json::from_slice(&entry[..newline]).ok()?;
+
+        let result = Self {
+            audio_data: entry[newline + 1..].to_vec(),
+            language_detected: metadata.language_detected,
+            char_count: metadata.char_count,
+            duration_minutes: metadata.duration_minutes,
+            audio_url: None,
+        };
+        Some((result, metadata.on_cdn))
+    }
 }
 
 pub struct TtsService {
-    user_repo: Arc<UserRepository>,
-    usage_repo: Arc<UsageRepository>,
-    polly_client: Arc<PollyClient>,
+    user_repo: Arc<dyn UserRepositoryApi>,
+    usage_repo: Arc<dyn UsageRepositoryApi>,
+    polly_client: Arc<dyn PollyApi>,
+    plan_limits: Arc<PlanLimits>,
     language_detector: LanguageDetector,
-    cache: Option<Cache<String, TtsSynthesisResult>>,
+    cache: Option<Arc<dyn CacheStore>>,
+    domain_events: Arc<dyn EventBus>,
+    account_events: Arc<AccountEventStream>,
+    outbox_repo: Arc<dyn OutboxRepositoryApi>,
+    email_sender: Arc<dyn EmailSender>,
+    metrics: Arc<TtsMetrics>,
+    /// Where audio is uploaded for clients to download, instead of getting it inline
+    audio_cdn: Option<Arc<dyn AudioCdn>>,
+    /// Zero when audio isn't to be cached at all
+    audio_cache_ttl: Duration,
+    /// Cleaned text doesn't depend on the voice, so it can outlive the audio and spare
+    /// re-syntheses of an article the HTML processing and language detection. Zero when
+    /// article text isn't to be kept.
+    cleaned_text_cache_ttl: Duration,
 }
 
 impl TtsService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        user_repo: Arc<UserRepository>,
-        usage_repo: Arc<UsageRepository>,
-        polly_client: Arc<PollyClient>,
-        cache_enabled: bool,
+        user_repo: Arc<dyn UserRepositoryApi>,
+        usage_repo: Arc<dyn UsageRepositoryApi>,
+        polly_client: Arc<dyn PollyApi>,
+        plan_limits: Arc<PlanLimits>,
+        cache: Option<Arc<dyn CacheStore>>,
+        domain_events: Arc<dyn EventBus>,
+        account_events: Arc<AccountEventStream>,
+        outbox_repo: Arc<dyn OutboxRepositoryApi>,
+        email_sender: Arc<dyn EmailSender>,
+        metrics: Arc<TtsMetrics>,
+        audio_cdn: Option<Arc<dyn AudioCdn>>,
+        retention: &RetentionConfig,
     ) -> Self {
         // Create language detector with the languages we support in Cargo.toml
         let language_detector = LanguageDetectorBuilder::from_all_languages().build();
 
-        // Initialize cache if enabled
-        let cache = if cache_enabled {
-            Some(
-                Cache::builder()
-                    .max_capacity(100)
-                    .time_to_idle(Duration::from_secs(30 * 60)) // 30 minutes, refreshes on access
-                    .build(),
-            )
-        } else {
-            None
-        };
-
         Self {
             user_repo,
             usage_repo,
             polly_client,
+            plan_limits,
             language_detector,
             cache,
+            domain_events,
+            account_events,
+            outbox_repo,
+            email_sender,
+            metrics,
+            audio_cdn,
+            audio_cache_ttl: CACHE_TTL.min(days(retention.cached_audio_days)),
+            cleaned_text_cache_ttl: days(retention.article_days),
         }
     }
 }
 
+/// The same audio is uploaded under the same name, which doesn't give away the link
+fn audio_object_name(cache_key: &str) -> String {
+    format!("{}.mp3", hex::encode(Sha256::digest(cache_key.as_bytes())))
+}
+
+fn days(days: i64) -> Duration {
+    Duration::from_secs(u64::try_from(days).unwrap_or(0) * SECONDS_PER_DAY)
+}
+
 #[async_trait]
 pub trait TtsServiceApi: Send + Sync {
     /// Synthesize text to speech for a given user
     ///
     /// This operation:
     /// - Validates user exists and has quota
-    /// - Calls AWS Polly for synthesis (English, neural voice)
+    /// - Calls AWS Polly for synthesis (neural voice, per the user's language preferences)
     /// - Tracks usage
     ///
     /// Returns audio data along with metadata (language, char count, duration)
@@ -99,22 +226,12 @@ impl TtsServiceApi for TtsService {
             "TTS synthesis request"
         );
 
-        // Check cache first (if enabled)
-        if let Some(cache) = &self.cache {
-            if let Some(cached_result) = cache.get(&link).await {
-                tracing::info!(
-                    link = %link,
-                    cached_audio_size = cached_result.audio_data.len(),
-                    cached_char_count = cached_result.char_count,
-                    cached_language = %cached_result.language_detected,
-                    "TTS cache hit - returning cached audio"
-                );
-                return Ok(cached_result);
-            }
-        }
-
-        // 1. Clean the text (remove HTML, URLs, normalize whitespace)
-        let cleaned_text = self.clean_text(&text);
+        // 1-2. Clean the text (remove HTML, URLs, normalize whitespace) and detect its
+        // language, or reuse both from an earlier request for the same text
+        let CleanedText {
+            text: cleaned_text,
+            language: detected_language,
+        } = self.cleaned_text(&text).await;
         let char_count = cleaned_text.len() as i32;
 
         tracing::info!(
@@ -123,56 +240,211 @@ impl TtsServiceApi for TtsService {
             "Text cleaned"
         );
 
-        // 2. Detect language from cleaned text
-        let detected_language = self.detect_language(&cleaned_text);
-
         tracing::info!(
             link = %link,
             language_detected = %detected_language,
             "Language detected for TTS synthesis"
         );
 
-        // 3. Find user
+        // 3. Find user and pick their voice for the detected language
         let user = self.find_user(user_id).await?;
+        let voice = Self::select_voice(&user, detected_language);
+
+        // Check cache (if enabled). Audio differs per voice, so the voice is part of the key.
+        let cache_key = format!("tts:{}:{}", voice, link);
+        if let Some((mut cached_result, on_cdn)) = self.cached_result(&cache_key).await {
+            if on_cdn {
+                cached_result.audio_url = self.signed_audio_url(&cache_key);
+            }
+            tracing::info!(
+                link = %link,
+                voice = voice,
+                cached_audio_size = cached_result.audio_data.len(),
+                cached_char_count = cached_result.char_count,
+                cached_language = %cached_result.language_detected,
+                "TTS cache hit - returning cached audio"
+            );
+            self.record_synthesis(CACHE_PROVIDER, cached_result.char_count)
+                .await;
+            self.notify_synthesis_completed(user_id, &link, &cached_result)
+                .await;
+            return Ok(cached_result);
+        }
 
-        // 4. Guard usage limits
-        self.guard_usage(&user, char_count).await?;
+        // 4. Guard usage limits for the user's current quota period
+        let period = user.usage_period(self.plan_limits.quota_period);
+        let quota = self.guard_usage(&user, &period, char_count).await?;
 
         // 5. Split text into batches
         let batches = self.split_into_batches(&cleaned_text);
         tracing::info!(batch_count = batches.len(), "Text split into batches");
 
-        // 6. Call Polly for each batch and merge results using the detected language
-        let audio_data = self.synthesize_batches(&batches, detected_language).await?;
-
-        // 7. Track usage
-        self.track_usage(user_id, char_count).await?;
-
-        // 8. Calculate duration and create result
-        let duration_minutes = char_count as f32 / CHARACTERS_PER_MINUTE;
+        // 6. Call Polly for each batch and merge results using the selected voice
+        let audio_data = self
+            .synthesize_batches(user_id, &link, &batches, voice, detected_language)
+            .await?;
+
+        // 7. Track usage, with the listening time the audio actually has
+        let audio_seconds = audio_seconds(&audio_data, char_count);
+        let usage = SynthesisUsage {
+            characters: char_count,
+            audio_seconds,
+            provider: POLLY_PROVIDER.to_string(),
+            engine: POLLY_ENGINE.as_str().to_string(),
+        };
+        self.track_usage(user_id, period.today, usage, quota.rollover)
+            .await?;
+        self.record_synthesis(POLLY_PROVIDER, char_count).await;
+        self.account_events.publish(
+            user_id,
+            AccountEventKind::UsageUpdated,
+            json!({
+                "characters_used": quota.characters_used + char_count,
+                "character_limit": quota.character_limit,
+                "resets_at": period.resets_at,
+            }),
+        );
+        self.send_quota_warning(&user, &quota, &period, char_count);
 
+        // 8. Create result, with a URL to download the audio from when there's a CDN
+        let audio_url = self.upload_audio(&cache_key, &audio_data).await;
         let result = TtsSynthesisResult {
             audio_data,
             language_detected: detected_language,
             char_count,
-            duration_minutes,
+            duration_minutes: (audio_seconds / 60.0) as f32,
+            audio_url,
         };
 
-        // 9. Cache the result if caching is enabled
-        if let Some(cache) = &self.cache {
-            cache.insert(link.clone(), result.clone()).await;
-            tracing::info!(
-                link = %link,
-                audio_size = result.audio_data.len(),
-                "TTS result cached"
-            );
+        // 9. Cache the result if caching is enabled and audio may be kept
+        if let Some(cache) = self
+            .cache
+            .as_ref()
+            .filter(|_| !self.audio_cache_ttl.is_zero())
+        {
+            match cache
+                .set(&cache_key, result.to_cache_entry(), self.audio_cache_ttl)
+                .await
+            {
+                Ok(()) => tracing::info!(
+                    link = %link,
+                    audio_size = result.audio_data.len(),
+                    "TTS result cached"
+                ),
+                Err(e) => tracing::warn!(link = %link, "Failed to cache TTS result: {}", e),
+            }
         }
 
+        self.notify_synthesis_completed(user_id, &link, &result)
+            .await;
+
         Ok(result)
     }
 }
 
 impl TtsService {
+    /// The cleaned text and its language, from the cache when this text was seen
+    /// recently. Cache failures count as a miss.
+    async fn cleaned_text(&self, text: &str) -> CleanedText {
+        let Some(cache) = self
+            .cache
+            .as_ref()
+            .filter(|_| !self.cleaned_text_cache_ttl.is_zero())
+        else {
+            return self.clean_and_detect(text);
+        };
+
+        let cache_key = format!("tts_text:{}", hex::encode(Sha256::digest(text.as_bytes())));
+        match cache.get(&cache_key).await {
+            Ok(Some(entry)) => {
+                if let Ok(cleaned) = serde_json::from_slice::<CleanedText>(&entry) {
+                    tracing::debug!("Cleaned text cache hit");
+                    return cleaned;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Cleaned text cache lookup failed: {}", e),
+        }
+
+        let cleaned = self.clean_and_detect(text);
+        if let Ok(entry) = serde_json::to_vec(&cleaned) {
+            if let Err(e) = cache
+                .set(&cache_key, entry, self.cleaned_text_cache_ttl)
+                .await
+            {
+                tracing::warn!("Failed to cache cleaned text: {}", e);
+            }
+        }
+        cleaned
+    }
+
+    fn clean_and_detect(&self, text: &str) -> CleanedText {
+        let text = self.clean_text(text);
+        let language = self.detect_language(&text);
+        CleanedText { text, language }
+    }
+
+    /// Cached audio for `cache_key`, and whether it's on the CDN. Cache failures count as
+    /// a miss.
+    async fn cached_result(&self, cache_key: &str) -> Option<(TtsSynthesisResult, bool)> {
+        let cache = self.cache.as_ref()?;
+        match cache.get(cache_key).await {
+            Ok(entry) => entry.and_then(|entry| TtsSynthesisResult::from_cache_entry(&entry)),
+            Err(e) => {
+                tracing::warn!("TTS cache lookup failed: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Upload fresh audio to the CDN, if there is one, and sign a URL for it. The audio is
+    /// returned inline when the upload fails.
+    async fn upload_audio(&self, cache_key: &str, audio_data: &[u8]) -> Option<String> {
+        let audio_cdn = self.audio_cdn.as_ref()?;
+        if let Err(e) = audio_cdn
+            .upload(&audio_object_name(cache_key), audio_data.to_vec())
+            .await
+        {
+            tracing::warn!("Failed to upload audio to the CDN: {}", e);
+            return None;
+        }
+        self.signed_audio_url(cache_key)
+    }
+
+    fn signed_audio_url(&self, cache_key: &str) -> Option<String> {
+        match self
+            .audio_cdn
+            .as_ref()?
+            .signed_url(&audio_object_name(cache_key))
+        {
+            Ok(url) => Some(url),
+            Err(e) => {
+                tracing::warn!("Failed to sign audio URL: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Webhooks go out from the outbox dispatcher. The audio is already produced, so a
+    /// failed write is only logged.
+    async fn notify_synthesis_completed(
+        &self,
+        user_id: Uuid,
+        link: &str,
+        result: &TtsSynthesisResult,
+    ) {
+        let event = DomainEvent::SynthesisCompleted {
+            user_id,
+            link: link.to_string(),
+            language: result.language_detected.as_str().to_string(),
+            characters: result.char_count,
+            duration_minutes: result.duration_minutes,
+        };
+        if let Err(e) = self.outbox_repo.append(&event).await {
+            tracing::warn!(user_id = %user_id, error = %e, "Failed to record synthesis event");
+        }
+    }
+
     async fn find_user(&self, user_id: Uuid) -> Result<User, TtsServiceError> {
         self.user_repo
             .find_by_id(user_id)
@@ -181,48 +453,86 @@ impl TtsService {
             .ok_or_else(|| TtsServiceError::Invalid("User not found".to_string()))
     }
 
-    async fn guard_usage(&self, user: &User, char_count: i32) -> Result<(), TtsServiceError> {
+    /// Check the request fits in the user's quota
+    async fn guard_usage(
+        &self,
+        user: &User,
+        period: &UsagePeriod,
+        char_count: i32,
+    ) -> Result<QuotaCheck, TtsServiceError> {
         let usage = self
             .usage_repo
-            .get_today_usage(user.id)
+            .get_usage_between(user.id, period.start, period.end)
             .await
             .map_err(|e| TtsServiceError::Dependency(e.to_string()))?;
-        let characters_used_today = usage.map(|u| u.characters_used).unwrap_or(0);
-
-        // Determine character limit based on tier
-        let character_limit = match user.subscription_tier {
-            SubscriptionTier::Free => {
-                // Check if trial expired
-                if user.is_trial_expired() {
-                    return Err(TtsServiceError::PaymentRequired(
-                        "Free trial expired. Please upgrade to Pro to continue.".to_string(),
-                    ));
-                }
-                20000 // 20 minutes/day = 20,000 characters
+        let characters_used = usage.characters_used;
+
+        if user.is_trial_expired() {
+            return Err(TtsServiceError::PaymentRequired(
+                TRIAL_EXPIRED_MESSAGE.to_string(),
+            ));
+        }
+
+        let daily_limit = self.plan_limits.for_user(user).characters;
+        let rollover = match self.plan_limits.rollover_cap(user) {
+            Some(cap) => {
+                let days = self
+                    .usage_repo
+                    .get_rollover_days(user.id, period.today)
+                    .await
+                    .map_err(|e| TtsServiceError::Dependency(e.to_string()))?;
+                Some(rollover_balance(period.today, &days, daily_limit, cap))
             }
-            SubscriptionTier::Pro => 200000, // 200 minutes/day = 200,000 characters
+            None => None,
         };
+        let character_limit = daily_limit + rollover.unwrap_or(0);
 
         // Check if adding this request would exceed the limit
-        if characters_used_today + char_count > character_limit {
+        if characters_used + char_count > character_limit {
+            let period_name = match period.kind {
+                QuotaPeriod::Daily => "Daily",
+                QuotaPeriod::Monthly => "Monthly",
+            };
+            self.domain_events
+                .publish(DomainEvent::QuotaExceeded {
+                    user_id: user.id,
+                    characters_used,
+                    character_limit,
+                    characters_requested: char_count,
+                    period_started_at: period.started_at,
+                    resets_at: period.resets_at,
+                })
+                .await;
             return Err(TtsServiceError::PaymentRequired(format!(
-                "Daily character limit exceeded. Used: {}, Limit: {}, Request: {}",
-                characters_used_today, character_limit, char_count
+                "{} character limit exceeded. Used: {}, Limit: {}, Request: {}",
+                period_name, characters_used, character_limit, char_count
             )));
         }
 
-        Ok(())
+        Ok(QuotaCheck {
+            characters_used,
+            character_limit,
+            rollover,
+        })
+    }
+
+    /// The user's voice for the language when Polly can render it with the neural engine,
+    /// otherwise the default voice for that language
+    fn select_voice(user: &User, language_code: LanguageCode) -> &'static str {
+        user.voice_for_language(language_code.as_str())
+            .map(|voice| voice.name)
+            .filter(|name| is_voice_neural_compatible(name))
+            .unwrap_or_else(|| get_voice_for_language(language_code))
     }
 
     async fn call_polly(
         &self,
         text: &str,
+        voice_name: &str,
         language_code: LanguageCode,
     ) -> Result<Vec<u8>, TtsServiceError> {
-        // Select voice based on detected language (always use neural)
-        let voice_name = super::language::get_voice_for_language(language_code);
         let voice_id = VoiceId::from(voice_name);
-        let engine = Engine::Neural;
+        let engine = POLLY_ENGINE;
 
         // Log the full request details for debugging
         tracing::info!(
@@ -238,16 +548,12 @@ impl TtsService {
 
         // Clone voice_id for error logging since it will be moved
         let voice_id_for_error = voice_id.clone();
+        let started = Instant::now();
 
         // Call Polly
         let result = self
             .polly_client
-            .synthesize_speech()
-            .text(text)
-            .voice_id(voice_id)
-            .output_format(OutputFormat::Mp3)
-            .engine(engine.clone())
-            .send()
+            .synthesize_speech(text, voice_id, engine.clone())
             .await
             .map_err(|e| {
                 tracing::error!(
@@ -259,7 +565,9 @@ impl TtsService {
                     text_length = text.len(),
                     "AWS Polly synthesize_speech failed"
                 );
-                TtsServiceError::Dependency(format!("AWS Polly error: {:?}", e))
+                let category = polly_error_category(&e);
+                self.metrics.record_error(POLLY_PROVIDER, category);
+                TtsServiceError::Provider(polly_provider_error(&e, category))
             })?;
 
         tracing::debug!("AWS Polly synthesize_speech successful, reading audio stream");
@@ -267,10 +575,18 @@ impl TtsService {
         // Get audio stream
         let audio_stream = result.audio_stream.collect().await.map_err(|e| {
             tracing::error!(error = %e, "Failed to collect audio stream from Polly response");
-            TtsServiceError::Dependency(format!("Failed to read audio stream: {}", e))
+            self.metrics
+                .record_error(POLLY_PROVIDER, TtsErrorCategory::Network);
+            TtsProviderError::ProviderUnavailable(format!("Failed to read audio stream: {}", e))
         })?;
 
         let audio_bytes = audio_stream.into_bytes().to_vec();
+        self.metrics.record_success(
+            POLLY_PROVIDER,
+            engine.as_str(),
+            language_code.as_str(),
+            started.elapsed(),
+        );
         tracing::debug!(
             audio_size = audio_bytes.len(),
             "Audio stream collected successfully"
@@ -282,7 +598,10 @@ impl TtsService {
     /// Synthesize multiple text batches and merge the audio results in order
     async fn synthesize_batches(
         &self,
+        user_id: Uuid,
+        link: &str,
         batches: &[String],
+        voice_name: &str,
         language_code: LanguageCode,
     ) -> Result<Vec<u8>, TtsServiceError> {
         let mut merged_audio = Vec::new();
@@ -294,7 +613,8 @@ impl TtsService {
                 "Synthesizing batch"
             );
 
-            let audio_data = self.call_polly(batch, language_code).await?;
+            let audio_data =
+                with_provider_retries(|| self.call_polly(batch, voice_name, language_code)).await?;
             merged_audio.extend(audio_data);
 
             tracing::info!(
@@ -302,18 +622,79 @@ impl TtsService {
                 total_audio_size = merged_audio.len(),
                 "Batch synthesized and merged"
             );
+            self.account_events.publish(
+                user_id,
+                AccountEventKind::JobProgress,
+                json!({
+                    "job": "synthesis",
+                    "link": link,
+                    "completed_batches": index + 1,
+                    "total_batches": batches.len(),
+                }),
+            );
         }
 
         Ok(merged_audio)
     }
 
-    async fn track_usage(&self, user_id: Uuid, char_count: i32) -> Result<(), TtsServiceError> {
+    async fn track_usage(
+        &self,
+        user_id: Uuid,
+        usage_date: NaiveDate,
+        usage: SynthesisUsage,
+        rollover: Option<i32>,
+    ) -> Result<(), TtsServiceError> {
         self.usage_repo
-            .increment_usage(user_id, char_count)
+            .increment_usage(user_id, usage_date, usage, rollover)
             .await
             .map_err(|e| TtsServiceError::Dependency(e.to_string()))
     }
 
+    /// Email the user once per period, on the synthesis that takes them past the warning
+    /// threshold. Sent in the background so a slow mail server doesn't delay the audio.
+    fn send_quota_warning(
+        &self,
+        user: &User,
+        quota: &QuotaCheck,
+        period: &UsagePeriod,
+        char_count: i32,
+    ) {
+        let characters_used = quota.characters_used + char_count;
+        let crossed = usage_warnings(quota.characters_used, quota.character_limit).is_empty()
+            && !usage_warnings(characters_used, quota.character_limit).is_empty();
+        if !crossed || !user.notification_preferences().quota_warnings {
+            return;
+        }
+
+        let message = EmailTemplate::QuotaWarning {
+            characters_used,
+            character_limit: quota.character_limit,
+            resets_at: period.resets_at,
+        }
+        .render(&user.email);
+        let email_sender = self.email_sender.clone();
+        let user_id = user.id;
+        tokio::spawn(
+            async move {
+                if let Err(e) = email_sender.send(&message).await {
+                    tracing::warn!(user_id = %user_id, error = %e, "Failed to send quota warning");
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    /// Count the synthesis in the admin statistics. Failures don't affect the request.
+    async fn record_synthesis(&self, provider: &str, char_count: i32) {
+        if let Err(e) = self
+            .usage_repo
+            .record_synthesis(Utc::now().date_naive(), provider, char_count)
+            .await
+        {
+            tracing::warn!(provider, error = %e, "Failed to record synthesis statistics");
+        }
+    }
+
     /// Detect language from text
     fn detect_language(&self, text: &str) -> LanguageCode {
         match self.language_detector.detect_language_of(text) {
@@ -330,8 +711,11 @@ impl TtsService {
 
     /// Clean text by removing HTML tags and normalizing whitespace
     fn clean_text(&self, text: &str) -> String {
+        // Sanitize untrusted feed HTML before extraction (scripts, iframes, trackers)
+        let sanitized = sanitize_html(text);
+
         // Convert HTML to plain text
-        let plain_text = from_read(text.as_bytes(), usize::MAX);
+        let plain_text = from_read(sanitized.as_bytes(), usize::MAX);
 
         // Remove URLs (both http and https)
         let url_pattern = regex::Regex::new(r"https?://[^\s]+").unwrap();
@@ -401,14 +785,392 @@ impl TtsService {
     }
 }
 
+/// Listening time of the audio, or the reading-speed estimate when its frames can't be read
+fn audio_seconds(audio: &[u8], char_count: i32) -> f64 {
+    let measured = mp3_duration(audio).as_secs_f64();
+    if measured > 0.0 {
+        measured
+    } else {
+        f64::from(char_count) * 60.0 / f64::from(CHARACTERS_PER_MINUTE)
+    }
+}
+
+/// Polly has no modeled throttling error, so it's recognized by its code
+fn polly_error_category<E: ProvideErrorMetadata>(error: &SdkError<E>) -> TtsErrorCategory {
+    match error {
+        SdkError::TimeoutError(_) => TtsErrorCategory::Timeout,
+        SdkError::DispatchFailure(failure) if failure.is_timeout() => TtsErrorCategory::Timeout,
+        SdkError::DispatchFailure(_) | SdkError::ResponseError(_) => TtsErrorCategory::Network,
+        SdkError::ServiceError(context) => {
+            let status = context.raw().status().as_u16();
+            let throttled = context
+                .err()
+                .code()
+                .is_some_and(|code| code.contains("Throttl"));
+            if throttled || status == 429 {
+                TtsErrorCategory::Throttled
+            } else if (400..500).contains(&status) {
+                TtsErrorCategory::Rejected
+            } else {
+                TtsErrorCategory::ProviderError
+            }
+        }
+        _ => TtsErrorCategory::ProviderError,
+    }
+}
+
+/// Run a provider call, retrying throttling and provider outages with backoff. Returns
+/// the last error once the retries run out.
+async fn with_provider_retries<T, F, Fut>(mut call: F) -> Result<T, TtsServiceError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, TtsServiceError>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        let error = match call().await {
+            Err(TtsServiceError::Provider(e)) if e.is_retryable() => e,
+            result => return result,
+        };
+
+        let Some(delay) = provider_retry_delay(attempt) else {
+            return Err(error.into());
+        };
+        tracing::warn!(
+            attempt,
+            retry_in_ms = delay.as_millis() as u64,
+            error = %error,
+            "TTS provider unavailable, retrying"
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Wait before the attempt following `attempt`, or None when no retries are left
+fn provider_retry_delay(attempt: u32) -> Option<Duration> {
+    if attempt > PROVIDER_RETRIES {
+        return None;
+    }
+    Some(PROVIDER_BACKOFF * 2u32.pow(attempt - 1))
+}
+
+/// What a Polly failure means for the request, from the category it was counted under.
+/// Rejections are told apart by code: our credentials failing isn't the text's fault.
+fn polly_provider_error<E: ProvideErrorMetadata>(
+    error: &SdkError<E>,
+    category: TtsErrorCategory,
+) -> TtsProviderError {
+    let (code, message) = match error {
+        SdkError::ServiceError(context) => (
+            context.err().code().unwrap_or_default(),
+            context.err().message().unwrap_or_default(),
+        ),
+        _ => ("", ""),
+    };
+    let detail = format!("AWS Polly error: {} {}", error, message)
+        .trim_end()
+        .to_string();
+
+    match category {
+        TtsErrorCategory::Throttled => TtsProviderError::Throttled(detail),
+        TtsErrorCategory::Timeout | TtsErrorCategory::Network => {
+            TtsProviderError::ProviderUnavailable(detail)
+        }
+        TtsErrorCategory::ProviderError if matches!(error, SdkError::ServiceError(_)) => {
+            TtsProviderError::ProviderUnavailable(detail)
+        }
+        TtsErrorCategory::ProviderError => TtsProviderError::Other(detail),
+        TtsErrorCategory::Rejected if POLLY_AUTH_ERROR_CODES.contains(&code) => {
+            TtsProviderError::Auth(detail)
+        }
+        TtsErrorCategory::Rejected => TtsProviderError::InvalidInput(detail),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{AppError, AppResult};
+    use crate::infrastructure::repositories::OutboxRepositoryApi;
+    use crate::infrastructure::test_util::InMemoryServices;
+    use aws_sdk_polly::operation::synthesize_speech::SynthesizeSpeechError;
+    use aws_smithy_runtime_api::http::{Response as HttpResponse, StatusCode};
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::error::ErrorMetadata;
     use lingua::Language;
+    use std::sync::Mutex;
+
+    /// Keeps the names of uploaded audio, or refuses every upload when `unavailable`
+    #[derive(Default)]
+    struct FakeAudioCdn {
+        uploads: Mutex<Vec<String>>,
+        unavailable: bool,
+    }
+
+    #[async_trait]
+    impl AudioCdn for FakeAudioCdn {
+        async fn upload(&self, name: &str, _audio: Vec<u8>) -> AppResult<()> {
+            if self.unavailable {
+                return Err(AppError::ExternalService("S3 is down".to_string()));
+            }
+            self.uploads.lock().unwrap().push(name.to_string());
+            Ok(())
+        }
+
+        fn signed_url(&self, name: &str) -> AppResult<String> {
+            Ok(format!(
+                "https://cdn.example.com/audio/{}?Signature=sig",
+                name
+            ))
+        }
+
+        async fn purge_before(&self, _before: chrono::DateTime<Utc>) -> AppResult<u64> {
+            Ok(0)
+        }
+    }
+
+    fn polly_service_error(status: u16, code: &str) -> SdkError<SynthesizeSpeechError> {
+        SdkError::service_error(
+            SynthesizeSpeechError::generic(ErrorMetadata::builder().code(code).build()),
+            HttpResponse::new(StatusCode::try_from(status).unwrap(), SdkBody::empty()),
+        )
+    }
+
+    #[test]
+    fn test_polly_errors_are_categorized() {
+        assert_eq!(
+            polly_error_category(&polly_service_error(400, "ThrottlingException")),
+            TtsErrorCategory::Throttled
+        );
+        assert_eq!(
+            polly_error_category(&polly_service_error(400, "TextLengthExceededException")),
+            TtsErrorCategory::Rejected
+        );
+        assert_eq!(
+            polly_error_category(&polly_service_error(500, "ServiceFailureException")),
+            TtsErrorCategory::ProviderError
+        );
+        assert_eq!(
+            polly_error_category::<SynthesizeSpeechError>(&SdkError::timeout_error("slow")),
+            TtsErrorCategory::Timeout
+        );
+    }
+
+    #[test]
+    fn test_polly_errors_tell_credentials_from_input() {
+        let provider_error = |error: SdkError<SynthesizeSpeechError>| {
+            let category = polly_error_category(&error);
+            polly_provider_error(&error, category)
+        };
+
+        assert!(matches!(
+            provider_error(polly_service_error(400, "ThrottlingException")),
+            TtsProviderError::Throttled(_)
+        ));
+        assert!(matches!(
+            provider_error(polly_service_error(400, "InvalidSsmlException")),
+            TtsProviderError::InvalidInput(_)
+        ));
+        assert!(matches!(
+            provider_error(polly_service_error(403, "ExpiredTokenException")),
+            TtsProviderError::Auth(_)
+        ));
+        assert!(matches!(
+            provider_error(polly_service_error(500, "ServiceFailureException")),
+            TtsProviderError::ProviderUnavailable(_)
+        ));
+        assert!(provider_error(SdkError::timeout_error("slow")).is_retryable());
+    }
+
+    #[test]
+    fn test_provider_retry_delay_doubles_until_retries_run_out() {
+        assert_eq!(provider_retry_delay(1), Some(PROVIDER_BACKOFF));
+        assert_eq!(provider_retry_delay(2), Some(PROVIDER_BACKOFF * 2));
+        assert_eq!(provider_retry_delay(3), None);
+    }
+
+    #[tokio::test]
+    async fn test_provider_retries_only_retryable_errors() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let result = with_provider_retries(|| async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(TtsProviderError::Throttled("slow down".to_string()).into()),
+                1 => Err(TtsProviderError::ProviderUnavailable("503".to_string()).into()),
+                _ => Ok(vec![1u8]),
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), vec![1u8]);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = with_provider_retries(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TtsProviderError::InvalidInput("bad SSML".to_string()).into())
+        })
+        .await;
+        assert!(matches!(
+            result,
+            Err(TtsServiceError::Provider(TtsProviderError::InvalidInput(_)))
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_provider_retries_give_up_after_the_last_retry() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = with_provider_retries(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TtsProviderError::Throttled("slow down".to_string()).into())
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(TtsServiceError::Provider(TtsProviderError::Throttled(_)))
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), PROVIDER_RETRIES + 1);
+    }
+
+    #[tokio::test]
+    async fn test_synthesis_counts_usage_and_records_the_event() {
+        let services = InMemoryServices::new(PlanLimits::default());
+        let user = services
+            .user_repo
+            .create("reader@example.com", "google", "g-1", None, None)
+            .await
+            .unwrap();
+        let text = "The quick brown fox jumps over the lazy dog. ".repeat(20);
+
+        let result = services
+            .tts_service
+            .synthesize(user.id, text, "https://example.com/a".to_string())
+            .await
+            .unwrap();
+
+        assert!(!result.audio_data.is_empty());
+        assert_eq!(result.language_detected, LanguageCode::English);
+        let period = user.usage_period(QuotaPeriod::Daily);
+        let usage = services
+            .usage_repo
+            .get_usage_between(user.id, period.start, period.end)
+            .await
+            .unwrap();
+        assert_eq!(usage.characters_used, result.char_count);
+        let now = Utc::now();
+        let events = services.outbox_repo.claim_due(now, now, 10).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0].payload.event,
+            DomainEvent::SynthesisCompleted { user_id, characters, .. }
+                if *user_id == user.id && *characters == result.char_count
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_synthesis_past_the_quota_is_refused_and_not_counted() {
+        let mut plan_limits = PlanLimits::default();
+        plan_limits.free.characters = 100;
+        let services = InMemoryServices::new(plan_limits);
+        let user = services
+            .user_repo
+            .create("reader@example.com", "google", "g-1", None, None)
+            .await
+            .unwrap();
+
+        let result = services
+            .tts_service
+            .synthesize(
+                user.id,
+                "The quick brown fox jumps over the lazy dog. ".repeat(20),
+                "https://example.com/a".to_string(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(TtsServiceError::PaymentRequired(_))));
+        let period = user.usage_period(QuotaPeriod::Daily);
+        let usage = services
+            .usage_repo
+            .get_usage_between(user.id, period.start, period.end)
+            .await
+            .unwrap();
+        assert_eq!(usage.characters_used, 0);
+    }
+
+    #[tokio::test]
+    async fn test_synthesized_audio_is_uploaded_and_handed_out_as_a_signed_url() {
+        let audio_cdn = Arc::new(FakeAudioCdn::default());
+        let services = InMemoryServices::with_audio_cdn(PlanLimits::default(), audio_cdn.clone());
+        let user = services
+            .user_repo
+            .create("reader@example.com", "google", "g-1", None, None)
+            .await
+            .unwrap();
+
+        let result = services
+            .tts_service
+            .synthesize(
+                user.id,
+                "The quick brown fox jumps over the lazy dog. ".repeat(20),
+                "https://example.com/a".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let uploads = audio_cdn.uploads.lock().unwrap().clone();
+        assert_eq!(uploads.len(), 1);
+        assert!(!uploads[0].contains("example.com"));
+        assert_eq!(
+            result.audio_url,
+            Some(format!(
+                "https://cdn.example.com/audio/{}?Signature=sig",
+                uploads[0]
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_audio_the_cdn_refused_is_returned_inline() {
+        let audio_cdn = Arc::new(FakeAudioCdn {
+            unavailable: true,
+            ..Default::default()
+        });
+        let services = InMemoryServices::with_audio_cdn(PlanLimits::default(), audio_cdn);
+        let user = services
+            .user_repo
+            .create("reader@example.com", "google", "g-1", None, None)
+            .await
+            .unwrap();
+
+        let result = services
+            .tts_service
+            .synthesize(
+                user.id,
+                "The quick brown fox jumps over the lazy dog. ".repeat(20),
+                "https://example.com/a".to_string(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.audio_url, None);
+        assert!(!result.audio_data.is_empty());
+    }
+
+    #[test]
+    fn test_unreadable_audio_falls_back_to_reading_speed() {
+        assert_eq!(audio_seconds(b"not audio", 1500), 90.0);
+    }
 
     // Test helper functions that mirror the service methods
     fn clean_text_test(text: &str) -> String {
-        let plain_text = from_read(text.as_bytes(), usize::MAX);
+        let sanitized = sanitize_html(text);
+        let plain_text = from_read(sanitized.as_bytes(), usize::MAX);
         let url_pattern = regex::Regex::new(r"https?://[^\s]+").unwrap();
         let without_urls = url_pattern.replace_all(&plain_text, "");
         let whitespace_pattern = regex::Regex::new(r"\s+").unwrap();
@@ -513,6 +1275,13 @@ mod tests {
         assert!(result.contains("Paragraph"));
     }
 
+    #[test]
+    fn test_clean_text_drops_script_content() {
+        let input = "<p>Article body</p><script>var tracker = 'x';</script>";
+        let result = clean_text_test(input);
+        assert_eq!(result, "Article body");
+    }
+
     #[test]
     fn test_split_into_batches_small_text() {
         let text = "This is a short text.";
@@ -672,4 +1441,25 @@ mod tests {
         let language = detector.detect_language_of(text);
         assert_eq!(language, Some(Language::Portuguese));
     }
+
+    #[test]
+    fn test_cache_entry_round_trip() {
+        let result = TtsSynthesisResult {
+            audio_data: vec![0, b'\n', 255, 10],
+            language_detected: LanguageCode::Spanish,
+            char_count: 1500,
+            duration_minutes: 1.0,
+            audio_url: Some("https://cdn.example.com/audio/a.mp3?Expires=1".to_string()),
+        };
+
+        let (restored, on_cdn) =
+            TtsSynthesisResult::from_cache_entry(&result.to_cache_entry()).unwrap();
+
+        assert_eq!(restored.audio_data, result.audio_data);
+        assert_eq!(restored.language_detected, LanguageCode::Spanish);
+        assert_eq!(restored.char_count, 1500);
+        assert_eq!(restored.audio_url, None);
+        assert!(on_cdn);
+        assert!(TtsSynthesisResult::from_cache_entry(b"not cached audio").is_none());
+    }
 }
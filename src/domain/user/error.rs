@@ -8,6 +8,8 @@ pub enum UserServiceError {
     Invalid(String),
     #[error("user not found")]
     NotFound,
+    #[error("profile was modified by another request")]
+    VersionMismatch,
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -27,6 +29,9 @@ impl From<UserServiceError> for AppError {
         match err {
             UserServiceError::Invalid(msg) => AppError::BadRequest(msg),
             UserServiceError::NotFound => AppError::NotFound("User not found".to_string()),
+            UserServiceError::VersionMismatch => AppError::PreconditionFailed(
+                "Profile was modified by another request. Fetch it again and retry.".to_string(),
+            ),
             UserServiceError::Dependency(msg) => AppError::Internal(msg),
             UserServiceError::Other(e) => AppError::Internal(e.to_string()),
         }
@@ -0,0 +1,176 @@
+use crate::domain::plan::UsagePeriod;
+use crate::domain::subscription::{SubscriptionEvent, SubscriptionEventType};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How long before a trial ends clients are told it is ending soon
+pub const TRIAL_ENDING_NOTICE_HOURS: i64 = 48;
+
+/// Account-level change a client may want to react to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountEventKind {
+    /// Pro was purchased or granted by a promo code
+    TierUpgraded,
+    /// Pro expired or was refunded
+    TierDowngraded,
+    /// Renewal, cancellation or a failed payment; the tier didn't change
+    SubscriptionUpdated,
+    /// A new quota period began
+    QuotaReset,
+    TrialEndingSoon,
+    TrialEnded,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccountEvent {
+    pub kind: AccountEventKind,
+    pub occurred_at: DateTime<Utc>,
+    /// Billing history entry behind subscription events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription_event: Option<SubscriptionEventType>,
+}
+
+/// Events that happened in `(since, now]`, oldest first. Billing events come from the
+/// subscription history, while quota and trial events are derived from their windows.
+pub fn account_events(
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+    subscription_events: &[SubscriptionEvent],
+    period: &UsagePeriod,
+    trial_ends_at: Option<DateTime<Utc>>,
+) -> Vec<AccountEvent> {
+    let in_window = |at: DateTime<Utc>| since < at && at <= now;
+
+    let mut events: Vec<AccountEvent> = subscription_events
+        .iter()
+        .filter(|event| in_window(event.created_at))
+        .map(|event| AccountEvent {
+            kind: match event.event_type {
+                SubscriptionEventType::Purchase | SubscriptionEventType::PromoCode => {
+                    AccountEventKind::TierUpgraded
+                }
+                SubscriptionEventType::Expiration | SubscriptionEventType::Refund => {
+                    AccountEventKind::TierDowngraded
+                }
+                SubscriptionEventType::Renewal
+                | SubscriptionEventType::Cancellation
+                | SubscriptionEventType::PaymentFailed => AccountEventKind::SubscriptionUpdated,
+            },
+            occurred_at: event.created_at,
+            subscription_event: Some(event.event_type),
+        })
+        .collect();
+
+    let mut derived = vec![(AccountEventKind::QuotaReset, period.started_at)];
+    if let Some(ends_at) = trial_ends_at {
+        derived.push((
+            AccountEventKind::TrialEndingSoon,
+            ends_at - Duration::hours(TRIAL_ENDING_NOTICE_HOURS),
+        ));
+        derived.push((AccountEventKind::TrialEnded, ends_at));
+    }
+    for (kind, occurred_at) in derived {
+        if in_window(occurred_at) {
+            events.push(AccountEvent {
+                kind,
+                occurred_at,
+                subscription_event: None,
+            });
+        }
+    }
+
+    events.sort_by_key(|event| event.occurred_at);
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::plan::QuotaPeriod;
+    use chrono_tz::Tz;
+    use uuid::Uuid;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn subscription_event(event_type: SubscriptionEventType, at: &str) -> SubscriptionEvent {
+        SubscriptionEvent {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            event_type,
+            platform: None,
+            product_id: None,
+            expires_at: None,
+            created_at: utc(at),
+        }
+    }
+
+    fn kinds(events: &[AccountEvent]) -> Vec<AccountEventKind> {
+        events.iter().map(|event| event.kind).collect()
+    }
+
+    #[test]
+    fn test_quota_reset_only_once_per_period() {
+        let now = utc("2025-03-10T15:00:00Z");
+        let period = UsagePeriod::current(QuotaPeriod::Daily, Tz::UTC, now);
+
+        let events = account_events(utc("2025-03-09T23:00:00Z"), now, &[], &period, None);
+        assert_eq!(kinds(&events), vec![AccountEventKind::QuotaReset]);
+        assert_eq!(events[0].occurred_at, utc("2025-03-10T00:00:00Z"));
+
+        let events = account_events(utc("2025-03-10T00:00:00Z"), now, &[], &period, None);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_subscription_events_map_to_tier_changes() {
+        let now = utc("2025-03-10T15:00:00Z");
+        let period = UsagePeriod::current(QuotaPeriod::Daily, Tz::UTC, now);
+        let history = [
+            subscription_event(SubscriptionEventType::Refund, "2025-03-10T14:00:00Z"),
+            subscription_event(SubscriptionEventType::Cancellation, "2025-03-10T12:00:00Z"),
+            subscription_event(SubscriptionEventType::Purchase, "2025-03-10T10:00:00Z"),
+            subscription_event(SubscriptionEventType::PromoCode, "2025-03-01T10:00:00Z"),
+        ];
+
+        let events = account_events(utc("2025-03-10T09:00:00Z"), now, &history, &period, None);
+
+        assert_eq!(
+            kinds(&events),
+            vec![
+                AccountEventKind::TierUpgraded,
+                AccountEventKind::SubscriptionUpdated,
+                AccountEventKind::TierDowngraded,
+            ]
+        );
+        assert_eq!(
+            events[0].subscription_event,
+            Some(SubscriptionEventType::Purchase)
+        );
+    }
+
+    #[test]
+    fn test_trial_events() {
+        let ends_at = utc("2025-03-12T10:00:00Z");
+        let since = utc("2025-03-10T09:00:00Z");
+
+        let now = utc("2025-03-10T11:00:00Z");
+        let period = UsagePeriod::current(QuotaPeriod::Daily, Tz::UTC, now);
+        let events = account_events(since, now, &[], &period, Some(ends_at));
+        assert_eq!(kinds(&events), vec![AccountEventKind::TrialEndingSoon]);
+        assert_eq!(events[0].occurred_at, utc("2025-03-10T10:00:00Z"));
+
+        let now = utc("2025-03-12T11:00:00Z");
+        let period = UsagePeriod::current(QuotaPeriod::Monthly, Tz::UTC, now);
+        let events = account_events(since, now, &[], &period, Some(ends_at));
+        assert_eq!(
+            kinds(&events),
+            vec![
+                AccountEventKind::TrialEndingSoon,
+                AccountEventKind::TrialEnded
+            ]
+        );
+    }
+}
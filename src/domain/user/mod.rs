@@ -1,29 +1,90 @@
 pub mod dto;
 pub mod error;
+pub mod events;
 pub mod model;
 pub mod service;
 pub mod voice_mapping;
 
 pub use error::UserServiceError;
-pub use model::{SubscriptionStatus, SubscriptionTier, User, UserSettings};
+pub use events::{AccountEvent, AccountEventKind};
+pub use model::{
+    NotificationPreferences, PlaybackPreferences, SubscriptionStatus, SubscriptionTier, User,
+    UserRole, UserSettings, ACCOUNT_DELETION_GRACE_DAYS, DELETED_ACCOUNT_RETENTION_DAYS,
+    TRIAL_DURATION_DAYS, TRIAL_EXPIRED_MESSAGE,
+};
 pub use service::{UserService, UserServiceApi};
 
-use chrono::{DateTime, Utc};
+use crate::domain::plan::{QuotaPeriod, UsageWarning};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 /// Response for GET /api/me
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MeResponse {
     pub id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
     pub settings: UserSettingsDto,
     pub subscription: SubscriptionDto,
+    /// Set while a deletion request is pending
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deletion_scheduled_at: Option<DateTime<Utc>>,
+    /// Profile version, sent as the ETag header rather than in the body
+    #[serde(skip)]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Response for POST /api/me/delete
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountDeletionResponse {
+    pub deletion_scheduled_at: DateTime<Utc>,
+}
+
+/// Query for GET /api/me/events
+#[derive(Debug, Deserialize)]
+pub struct AccountEventsQuery {
+    /// Only events after this moment, usually the previous response's `until`. Defaults to,
+    /// and is capped at, 30 days ago.
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Response for GET /api/me/events
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountEventsResponse {
+    /// Oldest first
+    pub events: Vec<AccountEvent>,
+    /// Server time the events were collected up to; pass it as `since` on the next request
+    pub until: DateTime<Utc>,
+}
+
+/// Response for DELETE /api/me/usage-history
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageHistoryPurgeResponse {
+    /// Days of usage that were deleted
+    pub deleted_days: i32,
+    /// First day still kept, the start of the current quota period
+    pub kept_from: NaiveDate,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UserSettingsDto {
     pub voice: String,
     pub language: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_article_age_days: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items_per_feed: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Voice ID per language code
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub voices: BTreeMap<String, String>,
+    pub notifications: NotificationPreferences,
+    pub playback: PlaybackPreferences,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,15 +93,44 @@ pub struct SubscriptionDto {
     pub status: String,
     pub usage: UsageDto,
     pub limits: LimitsDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial: Option<TrialDto>,
+    /// Owner of the family plan the user's limits come from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_owner_id: Option<Uuid>,
+}
+
+/// Free trial window, present once the trial has started
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrialDto {
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub expired: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UsageDto {
+    /// Window the usage and limits below cover; the `_today` fields hold the whole period
+    pub period: QuotaPeriod,
     pub minutes_used_today: f32,
     pub minutes_limit: i32,
     pub characters_used_today: i32,
     pub characters_limit: i32,
     pub resets_at: DateTime<Utc>,
+    /// Unused Pro quota carried over from earlier days and still available today, on top of
+    /// `characters_limit`. Absent when rollover doesn't apply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollover_characters: Option<i32>,
+    /// Quota thresholds crossed in the current period, most severe only
+    pub warnings: Vec<UsageWarning>,
+}
+
+impl UsageDto {
+    /// Characters still available in the period, rollover included
+    pub fn characters_remaining(&self) -> i32 {
+        (self.characters_limit - self.characters_used_today).max(0)
+            + self.rollover_characters.unwrap_or(0)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,13 +141,52 @@ pub struct LimitsDto {
 /// Request for PATCH /api/me
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateMeRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
     pub settings: Option<UpdateSettingsDto>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct UpdateSettingsDto {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub voice: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_article_age_days: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items_per_feed: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Voice name or ID per language code; null removes the entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voices: Option<BTreeMap<String, Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<UpdateNotificationsDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playback: Option<UpdatePlaybackDto>,
+}
+
+/// Partial update of notification preferences; omitted fields keep their current value
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateNotificationsDto {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest_emails: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota_warnings: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_updates: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weekly_summary: Option<bool>,
+}
+
+/// Partial update of playback preferences; omitted fields keep their current value
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdatePlaybackDto {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_silence: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_play_next: Option<bool>,
 }
@@ -1,19 +1,55 @@
+use super::voice_mapping::{find_voice, Voice};
+use crate::domain::plan::{LimitOverrides, QuotaPeriod, UsagePeriod};
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use sqlx::FromRow;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+/// Length of the free trial granted at signup or via start-trial
+pub const TRIAL_DURATION_DAYS: i64 = 7;
+
+/// Days between a deletion request and the purge, during which the user can cancel
+pub const ACCOUNT_DELETION_GRACE_DAYS: i64 = 14;
+
+/// Days a soft-deleted account is kept, hidden, before it is removed for good
+pub const DELETED_ACCOUNT_RETENTION_DAYS: i64 = 30;
+
+/// Shown wherever a Free user is blocked because the trial ended
+pub const TRIAL_EXPIRED_MESSAGE: &str = "Free trial expired. Please upgrade to Pro to continue.";
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
     pub id: Uuid,
     pub email: String,
     pub oauth_provider: String,
     pub oauth_provider_id: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
     pub settings: JsonValue,
     pub subscription_tier: SubscriptionTier,
     pub subscription_status: SubscriptionStatus,
     pub subscription_expires_at: Option<DateTime<Utc>>,
+    pub trial_started_at: Option<DateTime<Utc>>,
+    pub trial_ends_at: Option<DateTime<Utc>>,
+    pub deletion_scheduled_at: Option<DateTime<Utc>>,
+    /// Set once the deletion date passes; repository lookups no longer see the account
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub limit_overrides: Option<JsonValue>,
+    /// Code others redeem to be referred by this user; generated on first request
+    pub referral_code: Option<String>,
+    /// Referral bonus quota applies until this moment
+    pub referral_bonus_until: Option<DateTime<Utc>>,
+    /// Owner of the family plan the user belongs to
+    pub family_owner_id: Option<Uuid>,
+    pub role: UserRole,
+    /// Effective tier of the family owner, which members inherit. Only loaded by
+    /// `UserRepository::find_by_id`.
+    #[sqlx(skip)]
+    #[serde(skip)]
+    pub family_tier: Option<SubscriptionTier>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -47,6 +83,14 @@ pub enum SubscriptionStatus {
     Expired,
     #[serde(rename = "cancelled")]
     Cancelled,
+    /// Renewal payment failed; the store is retrying and the user keeps Pro meanwhile
+    #[serde(rename = "grace_period")]
+    #[sqlx(rename = "grace_period")]
+    GracePeriod,
+    /// Grace period ran out while the store keeps retrying; Pro limits are suspended
+    #[serde(rename = "on_hold")]
+    #[sqlx(rename = "on_hold")]
+    OnHold,
 }
 
 impl std::fmt::Display for SubscriptionStatus {
@@ -55,15 +99,45 @@ impl std::fmt::Display for SubscriptionStatus {
             SubscriptionStatus::Active => write!(f, "active"),
             SubscriptionStatus::Expired => write!(f, "expired"),
             SubscriptionStatus::Cancelled => write!(f, "cancelled"),
+            SubscriptionStatus::GracePeriod => write!(f, "grace_period"),
+            SubscriptionStatus::OnHold => write!(f, "on_hold"),
         }
     }
 }
 
+/// What an account may do beyond using the app
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    #[default]
+    User,
+    /// Support staff, who reach the /admin API and dashboard
+    Admin,
+}
+
 /// User settings structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserSettings {
     pub voice: String,
     pub language: String,
+    /// Articles older than this are left out of new-article notifications
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_article_age_days: Option<u32>,
+    /// Cap on articles counted for a single feed, so heavy feeds don't drown smaller ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items_per_feed: Option<u32>,
+    /// IANA timezone (e.g. "Asia/Tokyo") used for the daily quota boundary; UTC when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Voice name per language code, used when an article is detected in that language
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub voices: BTreeMap<String, String>,
+    #[serde(default)]
+    pub notifications: NotificationPreferences,
+    #[serde(default)]
+    pub playback: PlaybackPreferences,
 }
 
 impl Default for UserSettings {
@@ -71,20 +145,170 @@ impl Default for UserSettings {
         Self {
             voice: "Lucia".to_string(),
             language: "en".to_string(),
+            max_article_age_days: None,
+            max_items_per_feed: None,
+            timezone: None,
+            voices: BTreeMap::new(),
+            notifications: NotificationPreferences::default(),
+            playback: PlaybackPreferences::default(),
+        }
+    }
+}
+
+/// Emails the user has opted into. Everything is off until the user enables it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NotificationPreferences {
+    /// Periodic digest of new articles from followed feeds
+    pub digest_emails: bool,
+    /// Heads-up when the synthesis quota is close to running out
+    pub quota_warnings: bool,
+    pub product_updates: bool,
+    /// Monday email with the past week's listening
+    pub weekly_summary: bool,
+}
+
+/// Player defaults, stored server-side so they follow the user across devices
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PlaybackPreferences {
+    /// Playback rate multiplier, 1.0 being normal speed
+    pub speed: f64,
+    pub skip_silence: bool,
+    /// Start the next article in the queue when one finishes
+    pub auto_play_next: bool,
+}
+
+impl Default for PlaybackPreferences {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            skip_silence: false,
+            auto_play_next: true,
         }
     }
 }
 
 impl User {
-    /// Check if user is on free trial (trial = first 7 days from account creation)
+    /// Tier whose limits apply right now. Pro users that are on hold or expired get Free
+    /// limits but keep their Pro record, so access returns as soon as a payment succeeds.
+    /// Family members get Pro while their owner has it.
+    pub fn effective_tier(&self) -> SubscriptionTier {
+        match (&self.subscription_tier, &self.subscription_status) {
+            (
+                SubscriptionTier::Pro,
+                SubscriptionStatus::Active
+                | SubscriptionStatus::Cancelled
+                | SubscriptionStatus::GracePeriod,
+            ) => SubscriptionTier::Pro,
+            _ => self.family_tier.clone().unwrap_or(SubscriptionTier::Free),
+        }
+    }
+
+    /// Timezone from the user's settings, falling back to UTC when unset or unknown
+    pub fn timezone(&self) -> Tz {
+        self.settings
+            .get("timezone")
+            .and_then(|v| v.as_str())
+            .and_then(|tz| tz.parse().ok())
+            .unwrap_or(Tz::UTC)
+    }
+
+    /// Language code from the user's settings, e.g. `es`
+    pub fn language(&self) -> Option<&str> {
+        self.settings.get("language").and_then(|v| v.as_str())
+    }
+
+    /// Age past which articles are left out, from the user's settings
+    pub fn max_article_age_days(&self) -> Option<u32> {
+        self.settings
+            .get("max_article_age_days")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+    }
+
+    /// Cap on articles taken from a single feed, from the user's settings
+    pub fn max_items_per_feed(&self) -> Option<u32> {
+        self.settings
+            .get("max_items_per_feed")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+    }
+
+    /// Email preferences from the user's settings; missing or malformed entries count as opted out
+    pub fn notification_preferences(&self) -> NotificationPreferences {
+        self.settings
+            .get("notifications")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Playback preferences from the user's settings; defaults when missing or malformed
+    pub fn playback_preferences(&self) -> PlaybackPreferences {
+        self.settings
+            .get("playback")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Limit overrides granted by support; none when unset or malformed
+    pub fn limit_overrides(&self) -> LimitOverrides {
+        self.limit_overrides
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn has_referral_bonus(&self) -> bool {
+        self.referral_bonus_until
+            .is_some_and(|until| until > Utc::now())
+    }
+
+    /// Preferred voice for a language: the per-language map first, then the legacy single
+    /// voice if it speaks that language
+    pub fn voice_for_language(&self, language: &str) -> Option<&'static Voice> {
+        let from_map = self
+            .settings
+            .get("voices")
+            .and_then(|voices| voices.get(language))
+            .and_then(|v| v.as_str())
+            .and_then(find_voice);
+
+        from_map.or_else(|| {
+            self.settings
+                .get("voice")
+                .and_then(|v| v.as_str())
+                .and_then(find_voice)
+                .filter(|voice| voice.language == language)
+        })
+    }
+
+    /// Current quota period in the user's timezone
+    pub fn usage_period(&self, kind: QuotaPeriod) -> UsagePeriod {
+        UsagePeriod::current(kind, self.timezone(), Utc::now())
+    }
+
+    /// Deletion grace window is over; the account can no longer sign in and awaits purge
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+            || self
+                .deletion_scheduled_at
+                .is_some_and(|scheduled_at| scheduled_at <= Utc::now())
+    }
+
+    /// Check if user is on a running free trial
     pub fn is_trial(&self) -> bool {
-        let days_since_signup = Utc::now().signed_duration_since(self.created_at).num_days();
-        self.subscription_tier == SubscriptionTier::Free && days_since_signup < 7
+        self.subscription_tier == SubscriptionTier::Free
+            && self
+                .trial_ends_at
+                .is_some_and(|ends_at| ends_at > Utc::now())
     }
 
-    /// Check if trial has expired
+    /// Check if trial has expired. Free users whose trial never started are not expired.
     pub fn is_trial_expired(&self) -> bool {
-        let days_since_signup = Utc::now().signed_duration_since(self.created_at).num_days();
-        self.subscription_tier == SubscriptionTier::Free && days_since_signup >= 7
+        self.subscription_tier == SubscriptionTier::Free
+            && self
+                .trial_ends_at
+                .is_some_and(|ends_at| ends_at <= Utc::now())
     }
 }
@@ -1,34 +1,65 @@
 use super::error::UserServiceError;
-use super::voice_mapping::get_voice_id;
+use super::events::account_events;
+use super::voice_mapping::{find_voice, get_voice_id, voices_for_language, Voice, VOICES};
 use super::{
-    LimitsDto, MeResponse, SubscriptionDto, UpdateSettingsDto, UsageDto, User, UserSettingsDto,
+    AccountDeletionResponse, AccountEventsQuery, AccountEventsResponse, LimitsDto, MeResponse,
+    NotificationPreferences, PlaybackPreferences, SubscriptionDto, SubscriptionTier, TrialDto,
+    UpdateMeRequest, UpdateNotificationsDto, UpdatePlaybackDto, UpdateSettingsDto, UsageDto,
+    UsageHistoryPurgeResponse, User, UserSettingsDto, ACCOUNT_DELETION_GRACE_DAYS,
+    DELETED_ACCOUNT_RETENTION_DAYS,
+};
+use crate::domain::audit::{AuditAction, AuditEvent, AuditService, AuditServiceApi};
+use crate::domain::plan::{
+    remaining_rollover, rollover_balance, usage_warnings, PlanLimits, UsagePeriod,
+    CHARACTERS_PER_MINUTE,
+};
+use crate::infrastructure::auth::AuthUserCache;
+use crate::infrastructure::repositories::{
+    SubscriptionEventRepositoryApi, UsageRepositoryApi, UsageTotals, UserRepositoryApi,
 };
-use crate::infrastructure::repositories::{UsageRecord, UsageRepository, UserRepository};
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-const CHARACTERS_PER_MINUTE: f32 = 1000.0;
-const FREE_TIER_CHARACTERS: i32 = 20000;
-const FREE_TIER_MINUTES: i32 = 20;
-const FREE_TIER_MAX_FEEDS: i32 = 3;
-const PRO_TIER_CHARACTERS: i32 = 200000;
-const PRO_TIER_MINUTES: i32 = 200;
-const PRO_TIER_MAX_FEEDS: i32 = 999;
 const SUPPORTED_LANGUAGES: &[&str] = &["es", "en", "fr", "de", "pt", "it"];
+const MAX_ARTICLE_AGE_DAYS: u32 = 365;
+const MAX_ITEMS_PER_FEED: u32 = 200;
+const MAX_DISPLAY_NAME_CHARS: usize = 100;
+const MIN_PLAYBACK_SPEED: f64 = 0.5;
+const MAX_PLAYBACK_SPEED: f64 = 3.0;
+/// Furthest back account events can be requested
+const MAX_EVENTS_LOOKBACK_DAYS: i64 = 30;
+const MAX_SUBSCRIPTION_EVENTS: i64 = 100;
 
 pub struct UserService {
-    user_repo: Arc<UserRepository>,
-    usage_repo: Arc<UsageRepository>,
+    user_repo: Arc<dyn UserRepositoryApi>,
+    usage_repo: Arc<dyn UsageRepositoryApi>,
+    subscription_event_repo: Arc<dyn SubscriptionEventRepositoryApi>,
+    plan_limits: Arc<PlanLimits>,
+    audit_service: Arc<AuditService>,
+    user_cache: Arc<AuthUserCache>,
 }
 
 impl UserService {
-    pub fn new(user_repo: Arc<UserRepository>, usage_repo: Arc<UsageRepository>) -> Self {
+    pub fn new(
+        user_repo: Arc<dyn UserRepositoryApi>,
+        usage_repo: Arc<dyn UsageRepositoryApi>,
+        subscription_event_repo: Arc<dyn SubscriptionEventRepositoryApi>,
+        plan_limits: Arc<PlanLimits>,
+        audit_service: Arc<AuditService>,
+        user_cache: Arc<AuthUserCache>,
+    ) -> Self {
         Self {
             user_repo,
             usage_repo,
+            subscription_event_repo,
+            plan_limits,
+            audit_service,
+            user_cache,
         }
     }
 }
@@ -37,48 +68,275 @@ impl UserService {
 pub trait UserServiceApi: Send + Sync {
     async fn get_user_profile(&self, user_id: Uuid) -> Result<MeResponse, UserServiceError>;
 
-    async fn update_user_settings(
+    /// Apply a profile update in a single write. With `expected_version` the write only
+    /// happens if the profile is unchanged since that version. Returns the updated profile.
+    async fn update_profile(
         &self,
         user_id: Uuid,
-        updates: UpdateSettingsDto,
-    ) -> Result<(), UserServiceError>;
+        request: UpdateMeRequest,
+        expected_version: Option<DateTime<Utc>>,
+    ) -> Result<MeResponse, UserServiceError>;
+
+    /// Current quota period in the user's timezone
+    async fn get_usage_period(&self, user_id: Uuid) -> Result<UsagePeriod, UserServiceError>;
+
+    /// Subscription, quota and trial changes since the client last checked
+    async fn get_account_events(
+        &self,
+        user_id: Uuid,
+        query: AccountEventsQuery,
+    ) -> Result<AccountEventsResponse, UserServiceError>;
+
+    /// Schedule the account for deletion after the grace window. Repeated requests keep
+    /// the original date.
+    async fn schedule_account_deletion(
+        &self,
+        user_id: Uuid,
+    ) -> Result<AccountDeletionResponse, UserServiceError>;
+
+    /// Withdraw a pending deletion request
+    async fn cancel_account_deletion(&self, user_id: Uuid) -> Result<(), UserServiceError>;
+
+    /// Delete usage from before the current quota period, which quota enforcement no
+    /// longer needs
+    async fn purge_usage_history(
+        &self,
+        user_id: Uuid,
+    ) -> Result<UsageHistoryPurgeResponse, UserServiceError>;
+
+    /// Soft-delete accounts whose grace window has ended; returns how many were deleted
+    async fn delete_due_accounts(&self) -> Result<usize, UserServiceError>;
+
+    /// Permanently delete accounts soft-deleted more than the retention period ago;
+    /// returns how many were purged
+    async fn purge_deleted_accounts(&self) -> Result<usize, UserServiceError>;
 }
 
 #[async_trait]
 impl UserServiceApi for UserService {
     async fn get_user_profile(&self, user_id: Uuid) -> Result<MeResponse, UserServiceError> {
         let user = self.find_user(user_id).await?;
-        let usage = self.get_today_usage(user_id).await?;
+        let period = user.usage_period(self.plan_limits.quota_period);
+        let usage = self.get_usage(user_id, &period).await?;
+        let rollover = self.get_rollover(&user, &period).await?;
 
-        let response = Self::build_me_response(&user, usage.as_ref())?;
+        let response = self.build_me_response(&user, &usage, rollover, &period)?;
 
         Ok(response)
     }
 
-    async fn update_user_settings(
+    async fn update_profile(
         &self,
         user_id: Uuid,
-        updates: UpdateSettingsDto,
-    ) -> Result<(), UserServiceError> {
+        request: UpdateMeRequest,
+        expected_version: Option<DateTime<Utc>>,
+    ) -> Result<MeResponse, UserServiceError> {
+        if request.display_name.is_none() && request.settings.is_none() {
+            return Err(UserServiceError::Invalid(
+                "Display name or settings are required".to_string(),
+            ));
+        }
+
         let user = self.find_user(user_id).await?;
+        if expected_version.is_some_and(|version| version != user.updated_at) {
+            return Err(UserServiceError::VersionMismatch);
+        }
 
-        let mut settings: serde_json::Value = user.settings.clone();
+        let display_name = request.display_name.as_deref().map(str::trim);
+        if let Some(display_name) = display_name {
+            Self::validate_display_name(display_name)?;
+        }
+        let settings = request
+            .settings
+            .map(|updates| self.apply_settings_updates(&user, updates))
+            .transpose()?;
+        let settings_changed = settings.is_some();
+        let changes = json!({ "display_name": display_name, "settings": settings });
 
-        if let Some(voice) = updates.voice {
-            settings["voice"] = json!(voice);
+        // The version is checked again in the write, so a concurrent update in between
+        // still fails instead of being overwritten
+        let mut updated = self
+            .user_repo
+            .update_profile(user_id, display_name, settings, expected_version)
+            .await
+            .map_err(|e| UserServiceError::Dependency(e.to_string()))?
+            .ok_or(match expected_version {
+                Some(_) => UserServiceError::VersionMismatch,
+                None => UserServiceError::NotFound,
+            })?;
+        // The write doesn't touch family membership, so the loaded owner's tier still applies
+        updated.family_tier = user.family_tier;
+        // Requests are authenticated from the cache, which carries the language errors use
+        if settings_changed {
+            self.user_cache.invalidate(user_id).await;
         }
-        if let Some(language) = &updates.language {
-            self.validate_language(language)?;
-            settings["language"] = json!(language);
+
+        self.audit_service
+            .record(AuditEvent::by_user(user_id, AuditAction::ProfileUpdated).details(changes))
+            .await;
+
+        let period = updated.usage_period(self.plan_limits.quota_period);
+        let usage = self.get_usage(user_id, &period).await?;
+        let rollover = self.get_rollover(&updated, &period).await?;
+
+        self.build_me_response(&updated, &usage, rollover, &period)
+    }
+
+    async fn get_usage_period(&self, user_id: Uuid) -> Result<UsagePeriod, UserServiceError> {
+        let user = self.find_user(user_id).await?;
+        Ok(user.usage_period(self.plan_limits.quota_period))
+    }
+
+    async fn get_account_events(
+        &self,
+        user_id: Uuid,
+        query: AccountEventsQuery,
+    ) -> Result<AccountEventsResponse, UserServiceError> {
+        let now = Utc::now();
+        let oldest = now - Duration::days(MAX_EVENTS_LOOKBACK_DAYS);
+        let since = query.since.map_or(oldest, |since| since.max(oldest));
+
+        let user = self.find_user(user_id).await?;
+        let period = user.usage_period(self.plan_limits.quota_period);
+        let subscription_events = self
+            .subscription_event_repo
+            .list_since(user_id, since, MAX_SUBSCRIPTION_EVENTS)
+            .await
+            .map_err(|e| UserServiceError::Dependency(e.to_string()))?;
+        // Trial notices stop once the user pays for Pro
+        let trial_ends_at = user
+            .trial_ends_at
+            .filter(|_| user.subscription_tier == SubscriptionTier::Free);
+
+        Ok(AccountEventsResponse {
+            events: account_events(since, now, &subscription_events, &period, trial_ends_at),
+            until: now,
+        })
+    }
+
+    async fn schedule_account_deletion(
+        &self,
+        user_id: Uuid,
+    ) -> Result<AccountDeletionResponse, UserServiceError> {
+        let user = self.find_user(user_id).await?;
+
+        let deletion_scheduled_at = match user.deletion_scheduled_at {
+            Some(scheduled_at) => scheduled_at,
+            None => {
+                let scheduled_at = Utc::now() + Duration::days(ACCOUNT_DELETION_GRACE_DAYS);
+                self.user_repo
+                    .set_deletion_schedule(user_id, Some(scheduled_at))
+                    .await
+                    .map_err(|e| UserServiceError::Dependency(e.to_string()))?;
+                self.user_cache.invalidate(user_id).await;
+                tracing::info!(user_id = %user_id, %scheduled_at, "Account deletion scheduled");
+                self.audit_service
+                    .record(
+                        AuditEvent::by_user(user_id, AuditAction::AccountDeletionScheduled)
+                            .details(json!({ "scheduled_at": scheduled_at })),
+                    )
+                    .await;
+                scheduled_at
+            }
+        };
+
+        Ok(AccountDeletionResponse {
+            deletion_scheduled_at,
+        })
+    }
+
+    async fn cancel_account_deletion(&self, user_id: Uuid) -> Result<(), UserServiceError> {
+        let user = self.find_user(user_id).await?;
+
+        if user.deletion_scheduled_at.is_none() {
+            return Err(UserServiceError::Invalid(
+                "Account is not scheduled for deletion".to_string(),
+            ));
         }
 
         self.user_repo
-            .update_settings(user_id, settings)
+            .set_deletion_schedule(user_id, None)
             .await
             .map_err(|e| UserServiceError::Dependency(e.to_string()))?;
+        self.user_cache.invalidate(user_id).await;
+        tracing::info!(user_id = %user_id, "Account deletion cancelled");
+        self.audit_service
+            .record(AuditEvent::by_user(
+                user_id,
+                AuditAction::AccountDeletionCancelled,
+            ))
+            .await;
 
         Ok(())
     }
+
+    async fn purge_usage_history(
+        &self,
+        user_id: Uuid,
+    ) -> Result<UsageHistoryPurgeResponse, UserServiceError> {
+        let user = self.find_user(user_id).await?;
+        let period = user.usage_period(self.plan_limits.quota_period);
+
+        let deleted_days = self
+            .usage_repo
+            .purge_history(user_id, period.start)
+            .await
+            .map_err(|e| UserServiceError::Dependency(e.to_string()))?;
+
+        tracing::info!(user_id = %user_id, deleted_days, "Usage history purged");
+        self.audit_service
+            .record(
+                AuditEvent::by_user(user_id, AuditAction::UsageHistoryPurged)
+                    .details(json!({ "deleted_days": deleted_days })),
+            )
+            .await;
+
+        Ok(UsageHistoryPurgeResponse {
+            deleted_days,
+            kept_from: period.start,
+        })
+    }
+
+    async fn delete_due_accounts(&self) -> Result<usize, UserServiceError> {
+        let deleted = self
+            .user_repo
+            .soft_delete_scheduled(Utc::now())
+            .await
+            .map_err(|e| UserServiceError::Dependency(e.to_string()))?;
+
+        for user_id in &deleted {
+            tracing::info!(user_id = %user_id, "Account deleted");
+            self.audit_service
+                .record(
+                    AuditEvent::by_system("account_purge", AuditAction::AccountDeleted)
+                        .for_user(*user_id),
+                )
+                .await;
+        }
+
+        Ok(deleted.len())
+    }
+
+    async fn purge_deleted_accounts(&self) -> Result<usize, UserServiceError> {
+        let before = Utc::now() - Duration::days(DELETED_ACCOUNT_RETENTION_DAYS);
+        let purged = self
+            .user_repo
+            .purge_deleted(before)
+            .await
+            .map_err(|e| UserServiceError::Dependency(e.to_string()))?;
+
+        for user_id in &purged {
+            tracing::info!(user_id = %user_id, "Account purged");
+            self.audit_service
+                .record(
+                    AuditEvent::by_system("account_purge", AuditAction::AccountPurged)
+                        .for_user(*user_id),
+                )
+                .await;
+        }
+
+        Ok(purged.len())
+    }
 }
 
 impl UserService {
@@ -90,16 +348,84 @@ impl UserService {
             .ok_or(UserServiceError::NotFound)
     }
 
-    async fn get_today_usage(
+    async fn get_usage(
         &self,
         user_id: Uuid,
-    ) -> Result<Option<UsageRecord>, UserServiceError> {
+        period: &UsagePeriod,
+    ) -> Result<UsageTotals, UserServiceError> {
         self.usage_repo
-            .get_today_usage(user_id)
+            .get_usage_between(user_id, period.start, period.end)
             .await
             .map_err(|e| UserServiceError::Dependency(e.to_string()))
     }
 
+    /// Rollover balance carried into today, for users eligible for rollover
+    async fn get_rollover(
+        &self,
+        user: &User,
+        period: &UsagePeriod,
+    ) -> Result<Option<i32>, UserServiceError> {
+        let Some(cap) = self.plan_limits.rollover_cap(user) else {
+            return Ok(None);
+        };
+        let days = self
+            .usage_repo
+            .get_rollover_days(user.id, period.today)
+            .await
+            .map_err(|e| UserServiceError::Dependency(e.to_string()))?;
+
+        let daily_limit = self.plan_limits.for_user(user).characters;
+        let balance = rollover_balance(period.today, &days, daily_limit, cap);
+        Ok(Some(balance))
+    }
+
+    fn apply_settings_updates(
+        &self,
+        user: &User,
+        updates: UpdateSettingsDto,
+    ) -> Result<serde_json::Value, UserServiceError> {
+        let mut settings: serde_json::Value = user.settings.clone();
+
+        if let Some(language) = &updates.language {
+            self.validate_language(language)?;
+            settings["language"] = json!(language);
+        }
+        if let Some(voice) = updates.voice {
+            let voice = Self::validate_voice(&voice, updates.language.as_deref())?;
+            settings["voice"] = json!(voice.name);
+            // Keep the per-language map in step for clients that only know the single voice
+            Self::voices_map(&mut settings).insert(voice.language.to_string(), json!(voice.name));
+        }
+        if let Some(voices) = updates.voices {
+            Self::apply_voice_updates(&mut settings, voices)?;
+        }
+        if let Some(days) = updates.max_article_age_days {
+            Self::validate_range("max_article_age_days", days, MAX_ARTICLE_AGE_DAYS)?;
+            settings["max_article_age_days"] = json!(days);
+        }
+        if let Some(items) = updates.max_items_per_feed {
+            Self::validate_range("max_items_per_feed", items, MAX_ITEMS_PER_FEED)?;
+            settings["max_items_per_feed"] = json!(items);
+        }
+        if let Some(timezone) = &updates.timezone {
+            if timezone.parse::<Tz>().is_err() {
+                return Err(UserServiceError::Invalid(format!(
+                    "Invalid timezone: {}",
+                    timezone
+                )));
+            }
+            settings["timezone"] = json!(timezone);
+        }
+        if let Some(notifications) = updates.notifications {
+            Self::apply_notification_updates(&mut settings, notifications)?;
+        }
+        if let Some(playback) = updates.playback {
+            Self::apply_playback_updates(&mut settings, playback)?;
+        }
+
+        Ok(settings)
+    }
+
     fn validate_language(&self, language: &str) -> Result<(), UserServiceError> {
         if !SUPPORTED_LANGUAGES.contains(&language) {
             return Err(UserServiceError::Invalid(format!(
@@ -110,30 +436,166 @@ impl UserService {
         Ok(())
     }
 
-    fn calculate_limits(tier: crate::domain::user::SubscriptionTier) -> (i32, i32, i32) {
-        match tier {
-            crate::domain::user::SubscriptionTier::Free => {
-                (FREE_TIER_CHARACTERS, FREE_TIER_MINUTES, FREE_TIER_MAX_FEEDS)
+    /// The voice must exist, and must speak the language when both are changed together
+    /// Entries set the voice for their language; a null value removes it
+    fn apply_voice_updates(
+        settings: &mut serde_json::Value,
+        updates: BTreeMap<String, Option<String>>,
+    ) -> Result<(), UserServiceError> {
+        for (language, voice) in updates {
+            if !SUPPORTED_LANGUAGES.contains(&language.as_str()) {
+                return Err(UserServiceError::Invalid(format!(
+                    "Invalid language: {}",
+                    language
+                )));
+            }
+            match voice {
+                Some(voice) => {
+                    let voice = Self::validate_voice(&voice, Some(&language))?;
+                    Self::voices_map(settings).insert(language, json!(voice.name));
+                }
+                None => {
+                    Self::voices_map(settings).remove(&language);
+                }
             }
-            crate::domain::user::SubscriptionTier::Pro => {
-                (PRO_TIER_CHARACTERS, PRO_TIER_MINUTES, PRO_TIER_MAX_FEEDS)
+        }
+        Ok(())
+    }
+
+    fn voices_map(
+        settings: &mut serde_json::Value,
+    ) -> &mut serde_json::Map<String, serde_json::Value> {
+        if !settings["voices"].is_object() {
+            settings["voices"] = json!({});
+        }
+        settings["voices"]
+            .as_object_mut()
+            .expect("voices was just set to an object")
+    }
+
+    fn validate_voice(
+        voice: &str,
+        language: Option<&str>,
+    ) -> Result<&'static Voice, UserServiceError> {
+        let Some(found) = find_voice(voice) else {
+            let allowed: Vec<&str> = VOICES.iter().map(|v| v.name).collect();
+            return Err(UserServiceError::Invalid(format!(
+                "Invalid voice: {}. Allowed values: {}",
+                voice,
+                allowed.join(", ")
+            )));
+        };
+
+        if let Some(language) = language {
+            if found.language != language {
+                return Err(UserServiceError::Invalid(format!(
+                    "Voice {} does not speak {}. Allowed values: {}",
+                    voice,
+                    language,
+                    voices_for_language(language).join(", ")
+                )));
             }
         }
+        Ok(found)
     }
 
-    fn calculate_reset_time() -> DateTime<Utc> {
-        let now = Utc::now();
-        let tomorrow = now + Duration::days(1);
-        tomorrow
-            .date_naive()
-            .and_hms_opt(0, 0, 0)
-            .unwrap()
-            .and_utc()
+    fn validate_range(name: &str, value: u32, max: u32) -> Result<(), UserServiceError> {
+        if value == 0 || value > max {
+            return Err(UserServiceError::Invalid(format!(
+                "{} must be between 1 and {}",
+                name, max
+            )));
+        }
+        Ok(())
+    }
+
+    fn apply_notification_updates(
+        settings: &mut serde_json::Value,
+        updates: UpdateNotificationsDto,
+    ) -> Result<(), UserServiceError> {
+        let changes = [
+            ("digest_emails", updates.digest_emails),
+            ("quota_warnings", updates.quota_warnings),
+            ("product_updates", updates.product_updates),
+            ("weekly_summary", updates.weekly_summary),
+        ];
+        if changes.iter().all(|(_, value)| value.is_none()) {
+            return Err(UserServiceError::Invalid(
+                "notifications must include at least one preference".to_string(),
+            ));
+        }
+
+        if !settings["notifications"].is_object() {
+            settings["notifications"] = json!(NotificationPreferences::default());
+        }
+        for (name, value) in changes {
+            if let Some(enabled) = value {
+                settings["notifications"][name] = json!(enabled);
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_playback_updates(
+        settings: &mut serde_json::Value,
+        updates: UpdatePlaybackDto,
+    ) -> Result<(), UserServiceError> {
+        if updates.speed.is_none()
+            && updates.skip_silence.is_none()
+            && updates.auto_play_next.is_none()
+        {
+            return Err(UserServiceError::Invalid(
+                "playback must include at least one preference".to_string(),
+            ));
+        }
+
+        if !settings["playback"].is_object() {
+            settings["playback"] = json!(PlaybackPreferences::default());
+        }
+        if let Some(speed) = updates.speed {
+            if !(MIN_PLAYBACK_SPEED..=MAX_PLAYBACK_SPEED).contains(&speed) {
+                return Err(UserServiceError::Invalid(format!(
+                    "playback speed must be between {} and {}",
+                    MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED
+                )));
+            }
+            settings["playback"]["speed"] = json!(speed);
+        }
+        if let Some(skip_silence) = updates.skip_silence {
+            settings["playback"]["skip_silence"] = json!(skip_silence);
+        }
+        if let Some(auto_play_next) = updates.auto_play_next {
+            settings["playback"]["auto_play_next"] = json!(auto_play_next);
+        }
+        Ok(())
+    }
+
+    fn validate_display_name(display_name: &str) -> Result<(), UserServiceError> {
+        if display_name.is_empty() {
+            return Err(UserServiceError::Invalid(
+                "Display name cannot be empty".to_string(),
+            ));
+        }
+        if display_name.chars().count() > MAX_DISPLAY_NAME_CHARS {
+            return Err(UserServiceError::Invalid(format!(
+                "Display name must be at most {} characters",
+                MAX_DISPLAY_NAME_CHARS
+            )));
+        }
+        if display_name.chars().any(char::is_control) {
+            return Err(UserServiceError::Invalid(
+                "Display name cannot contain control characters".to_string(),
+            ));
+        }
+        Ok(())
     }
 
     fn build_me_response(
+        &self,
         user: &User,
-        usage: Option<&UsageRecord>,
+        usage: &UsageTotals,
+        rollover: Option<i32>,
+        period: &UsagePeriod,
     ) -> Result<MeResponse, UserServiceError> {
         let settings_json = &user.settings;
         let voice_name = settings_json
@@ -141,38 +603,178 @@ impl UserService {
             .and_then(|v| v.as_str())
             .unwrap_or("Lucia");
         let voice_id = get_voice_id(voice_name);
+        let voices = settings_json
+            .get("voices")
+            .and_then(|v| v.as_object())
+            .map(|map| {
+                map.iter()
+                    .filter_map(|(language, name)| {
+                        let voice = find_voice(name.as_str()?)?;
+                        Some((language.clone(), voice.id.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
         let language = settings_json
             .get("language")
             .and_then(|v| v.as_str())
             .unwrap_or("en")
             .to_string();
+        let max_article_age_days = user.max_article_age_days();
+        let max_items_per_feed = user.max_items_per_feed();
+
+        let limits = self.plan_limits.for_user(user);
+        let characters_limit = limits.characters;
+        let minutes_limit = limits.minutes();
+        let max_feeds = limits.max_feeds;
 
-        let (characters_limit, minutes_limit, max_feeds) =
-            Self::calculate_limits(user.subscription_tier.clone());
+        let characters_used_today = usage.characters_used;
+        let minutes_used_today = characters_used_today as f32 / CHARACTERS_PER_MINUTE as f32;
+        // Warnings count the rollover balance as part of today's allowance
+        let allowance = characters_limit + rollover.unwrap_or(0);
+        let rollover_characters = rollover
+            .map(|balance| remaining_rollover(balance, characters_used_today, characters_limit));
 
-        let characters_used_today = usage.map(|u| u.characters_used).unwrap_or(0);
-        let minutes_used_today = characters_used_today as f32 / CHARACTERS_PER_MINUTE;
+        let resets_at = period.resets_at;
+        let timezone = settings_json
+            .get("timezone")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
 
-        let resets_at = Self::calculate_reset_time();
+        let trial = match (user.trial_started_at, user.trial_ends_at) {
+            (Some(started_at), Some(ends_at)) => Some(TrialDto {
+                started_at,
+                ends_at,
+                expired: ends_at <= Utc::now(),
+            }),
+            _ => None,
+        };
 
         Ok(MeResponse {
             id: user.id,
+            display_name: user.display_name.clone(),
+            avatar_url: user.avatar_url.clone(),
+            updated_at: user.updated_at,
+            deletion_scheduled_at: user.deletion_scheduled_at,
             settings: UserSettingsDto {
                 voice: voice_id,
                 language,
+                max_article_age_days,
+                max_items_per_feed,
+                timezone,
+                voices,
+                notifications: user.notification_preferences(),
+                playback: user.playback_preferences(),
             },
             subscription: SubscriptionDto {
                 tier: user.subscription_tier.to_string(),
                 status: user.subscription_status.to_string(),
                 usage: UsageDto {
+                    period: period.kind,
                     minutes_used_today,
                     minutes_limit,
                     characters_used_today,
                     characters_limit,
                     resets_at,
+                    rollover_characters,
+                    warnings: usage_warnings(characters_used_today, allowance),
                 },
                 limits: LimitsDto { max_feeds },
+                trial,
+                family_owner_id: user.family_owner_id,
             },
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::auth::CachedAuthUser;
+    use crate::infrastructure::repositories::AuditLogRepositoryApi;
+    use crate::infrastructure::test_util::InMemoryServices;
+
+    async fn services_with_user() -> (InMemoryServices, User) {
+        let services = InMemoryServices::new(PlanLimits::default());
+        let user = services
+            .user_repo
+            .create("reader@example.com", "google", "g-1", None, None)
+            .await
+            .unwrap();
+
+        (services, user)
+    }
+
+    #[tokio::test]
+    async fn test_settings_update_drops_the_cached_auth_user() {
+        let (services, user) = services_with_user().await;
+        services
+            .auth_user_cache
+            .put(user.id, &CachedAuthUser::from(&user))
+            .await;
+
+        let updated = services
+            .user_service
+            .update_profile(
+                user.id,
+                UpdateMeRequest {
+                    display_name: None,
+                    settings: Some(UpdateSettingsDto {
+                        language: Some("es".to_string()),
+                        ..Default::default()
+                    }),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.settings.language, "es");
+        assert!(services.auth_user_cache.get(user.id).await.is_none());
+        let entries = services
+            .audit_log_repo
+            .list(Some(user.id), Some(AuditAction::ProfileUpdated), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_deletion_requests_keep_the_first_date() {
+        let (services, user) = services_with_user().await;
+
+        let first = services
+            .user_service
+            .schedule_account_deletion(user.id)
+            .await
+            .unwrap();
+        let second = services
+            .user_service
+            .schedule_account_deletion(user.id)
+            .await
+            .unwrap();
+
+        assert_eq!(first.deletion_scheduled_at, second.deletion_scheduled_at);
+        let stored = services
+            .user_repo
+            .find_by_id(user.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stored.deletion_scheduled_at,
+            Some(first.deletion_scheduled_at)
+        );
+        let entries = services
+            .audit_log_repo
+            .list(
+                Some(user.id),
+                Some(AuditAction::AccountDeletionScheduled),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+}
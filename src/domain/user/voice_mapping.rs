@@ -1,20 +1,60 @@
+/// A voice users can pick in settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Voice {
+    /// Name stored in settings
+    pub name: &'static str,
+    /// ID returned to clients
+    pub id: &'static str,
+    /// ISO 639-1 code of the language the voice speaks
+    pub language: &'static str,
+}
+
+const fn voice(name: &'static str, id: &'static str, language: &'static str) -> Voice {
+    Voice { name, id, language }
+}
+
+pub const VOICES: &[Voice] = &[
+    voice("Lucia", "voice_lucia_es", "es"),
+    voice("Sergio", "voice_sergio_es", "es"),
+    voice("Conchita", "voice_conchita_es", "es"),
+    voice("Lupe", "voice_lupe_es", "es"),
+    voice("Matthew", "voice_matthew_en", "en"),
+    voice("Joanna", "voice_joanna_en", "en"),
+    voice("Amy", "voice_amy_en", "en"),
+    voice("Celine", "voice_celine_fr", "fr"),
+    voice("Mathieu", "voice_mathieu_fr", "fr"),
+    voice("Lea", "voice_lea_fr", "fr"),
+    voice("Hans", "voice_hans_de", "de"),
+    voice("Marlene", "voice_marlene_de", "de"),
+    voice("Vicki", "voice_vicki_de", "de"),
+    voice("Ricardo", "voice_ricardo_pt", "pt"),
+    voice("Ines", "voice_ines_pt", "pt"),
+    voice("Carla", "voice_carla_it", "it"),
+    voice("Giorgio", "voice_giorgio_it", "it"),
+    voice("Bianca", "voice_bianca_it", "it"),
+];
+
+const DEFAULT_VOICE: &Voice = &VOICES[0];
+
+/// Look up a voice by name or by the ID returned to clients
+pub fn find_voice(name_or_id: &str) -> Option<&'static Voice> {
+    VOICES
+        .iter()
+        .find(|v| v.name == name_or_id || v.id == name_or_id)
+}
+
+/// Names of the voices that speak the given language
+pub fn voices_for_language(language: &str) -> Vec<&'static str> {
+    VOICES
+        .iter()
+        .filter(|v| v.language == language)
+        .map(|v| v.name)
+        .collect()
+}
+
 pub fn get_voice_id(voice_name: &str) -> String {
-    match voice_name {
-        "Lucia" => "voice_lucia_es",
-        "Sergio" => "voice_sergio_es",
-        "Conchita" => "voice_conchita_es",
-        "Matthew" => "voice_matthew_en",
-        "Joanna" => "voice_joanna_en",
-        "Amy" => "voice_amy_en",
-        "Celine" => "voice_celine_fr",
-        "Mathieu" => "voice_mathieu_fr",
-        "Hans" => "voice_hans_de",
-        "Marlene" => "voice_marlene_de",
-        "Ricardo" => "voice_ricardo_pt",
-        "Ines" => "voice_ines_pt",
-        "Carla" => "voice_carla_it",
-        "Giorgio" => "voice_giorgio_it",
-        _ => "voice_lucia_es",
-    }
-    .to_string()
+    find_voice(voice_name)
+        .unwrap_or(DEFAULT_VOICE)
+        .id
+        .to_string()
 }
@@ -0,0 +1,34 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error("webhook not found")]
+    NotFound,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for WebhookServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => WebhookServiceError::Invalid(msg),
+            AppError::NotFound(_) => WebhookServiceError::NotFound,
+            _ => WebhookServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<WebhookServiceError> for AppError {
+    fn from(err: WebhookServiceError) -> Self {
+        match err {
+            WebhookServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            WebhookServiceError::NotFound => AppError::NotFound("Webhook not found".to_string()),
+            WebhookServiceError::Dependency(msg) => AppError::Internal(msg),
+            WebhookServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+pub mod error;
+pub mod model;
+pub mod service;
+
+pub use error::WebhookServiceError;
+pub use model::{Webhook, WebhookDelivery, WebhookDeliveryStatus, WebhookEventType};
+pub use service::{WebhookService, WebhookServiceApi};
+
+use crate::domain::shared::PageResponse;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Request for POST /api/me/webhooks
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub events: Vec<WebhookEventType>,
+}
+
+/// A webhook endpoint. The signing secret is only returned when the webhook is created.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub events: Vec<WebhookEventType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            events: webhook.events.0,
+            secret: None,
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+/// Response for GET /api/me/webhooks
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhooksResponse {
+    pub webhooks: Vec<WebhookResponse>,
+}
+
+/// Query for GET /api/me/webhooks/{id}/deliveries
+#[derive(Debug, Deserialize)]
+pub struct ListWebhookDeliveriesQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+/// An entry of an endpoint's delivery log
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookDeliveryResponse {
+    pub id: Uuid,
+    pub event_type: WebhookEventType,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    /// HTTP status of the latest attempt, if the endpoint answered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_status_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// When the next attempt is due; only while pending
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            event_type: delivery.event_type,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            last_status_code: delivery.last_status_code,
+            last_error: delivery.last_error,
+            next_attempt_at: (delivery.status == WebhookDeliveryStatus::Pending)
+                .then_some(delivery.next_attempt_at),
+            created_at: delivery.created_at,
+            completed_at: delivery.completed_at,
+        }
+    }
+}
+
+/// Response for GET /api/me/webhooks/{id}/deliveries, newest first
+pub type WebhookDeliveriesResponse = PageResponse<WebhookDeliveryResponse>;
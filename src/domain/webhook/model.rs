@@ -0,0 +1,105 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, FromRow};
+use uuid::Uuid;
+
+/// Attempts made before a delivery is given up on
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 10;
+const FIRST_RETRY_DELAY_SECS: i64 = 30;
+
+/// Something that happened on a user's account that endpoints can subscribe to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventType {
+    /// An article was turned into audio
+    SynthesisCompleted,
+    /// A feed published articles since it was last checked
+    NewArticles,
+    /// A synthesis was refused because it would go over the quota
+    QuotaExceeded,
+}
+
+impl WebhookEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::SynthesisCompleted => "synthesis_completed",
+            WebhookEventType::NewArticles => "new_articles",
+            WebhookEventType::QuotaExceeded => "quota_exceeded",
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub events: Json<Vec<WebhookEventType>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn subscribes_to(&self, event_type: WebhookEventType) -> bool {
+        self.events.contains(&event_type)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first attempt or a retry
+    Pending,
+    Succeeded,
+    /// Every attempt failed
+    Failed,
+}
+
+/// One event sent to one endpoint, with the outcome of its latest attempt
+#[derive(Debug, Clone, FromRow)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_type: WebhookEventType,
+    pub payload: Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// How long to wait after the given failed attempt (1-based) before trying again: 30s,
+/// doubling each time. None once the attempts are used up.
+pub fn retry_delay(attempt: i32) -> Option<Duration> {
+    if attempt >= MAX_DELIVERY_ATTEMPTS {
+        return None;
+    }
+    Some(Duration::seconds(
+        FIRST_RETRY_DELAY_SECS << (attempt.max(1) - 1),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_until_attempts_run_out() {
+        assert_eq!(retry_delay(1), Some(Duration::seconds(30)));
+        assert_eq!(retry_delay(2), Some(Duration::seconds(60)));
+        assert_eq!(retry_delay(5), Some(Duration::minutes(8)));
+        assert_eq!(
+            retry_delay(MAX_DELIVERY_ATTEMPTS - 1),
+            Some(Duration::seconds(30 * 256))
+        );
+        assert_eq!(retry_delay(MAX_DELIVERY_ATTEMPTS), None);
+    }
+}
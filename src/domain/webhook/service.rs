@@ -0,0 +1,365 @@
+use super::error::WebhookServiceError;
+use super::model::{retry_delay, WebhookDeliveryStatus, WebhookEventType};
+use super::{
+    CreateWebhookRequest, WebhookDeliveriesResponse, WebhookDeliveryResponse, WebhookResponse,
+    WebhooksResponse,
+};
+use crate::domain::shared::{Cursor, PageRequest, PageResponse};
+use crate::domain::webhook::WebhookDelivery;
+use crate::infrastructure::repositories::WebhookRepository;
+use crate::infrastructure::webhooks::WebhookSender;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Url;
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+const MAX_WEBHOOKS_PER_USER: i64 = 5;
+const MAX_URL_LENGTH: usize = 2048;
+const DEFAULT_DELIVERY_PAGE_SIZE: i64 = 50;
+const MAX_DELIVERY_PAGE_SIZE: i64 = 200;
+/// Deliveries sent per worker run
+const DELIVERY_BATCH_SIZE: i64 = 50;
+/// How long a claimed delivery is hidden from other workers; longer than a request can take
+const DELIVERY_LEASE_SECS: i64 = 60;
+
+pub struct WebhookService {
+    webhook_repo: Arc<WebhookRepository>,
+    sender: Arc<WebhookSender>,
+}
+
+impl WebhookService {
+    pub fn new(webhook_repo: Arc<WebhookRepository>, sender: Arc<WebhookSender>) -> Self {
+        Self {
+            webhook_repo,
+            sender,
+        }
+    }
+}
+
+#[async_trait]
+pub trait WebhookServiceApi: Send + Sync {
+    /// Register an endpoint. The response carries the signing secret, which is not shown
+    /// again.
+    async fn create_webhook(
+        &self,
+        user_id: Uuid,
+        request: CreateWebhookRequest,
+    ) -> Result<WebhookResponse, WebhookServiceError>;
+
+    async fn list_webhooks(&self, user_id: Uuid) -> Result<WebhooksResponse, WebhookServiceError>;
+
+    async fn delete_webhook(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+    ) -> Result<(), WebhookServiceError>;
+
+    /// Delivery log of one of the user's webhooks, newest first
+    async fn list_deliveries(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        limit: Option<i64>,
+        cursor: Option<&str>,
+    ) -> Result<WebhookDeliveriesResponse, WebhookServiceError>;
+
+    /// Queue an event for every webhook of the user subscribed to it. Called after the
+    /// change that caused it, so failures are logged instead of returned.
+    async fn dispatch(&self, user_id: Uuid, event_type: WebhookEventType, data: Value);
+
+    /// Like `dispatch`, but skips webhooks that were already sent this event since
+    /// `since`, e.g. once per quota period
+    async fn dispatch_once_since(
+        &self,
+        user_id: Uuid,
+        event_type: WebhookEventType,
+        data: Value,
+        since: DateTime<Utc>,
+    );
+
+    /// Send the deliveries that are due, scheduling a retry for the ones that fail.
+    /// Returns how many were attempted.
+    async fn deliver_due(&self) -> Result<usize, WebhookServiceError>;
+}
+
+#[async_trait]
+impl WebhookServiceApi for WebhookService {
+    async fn create_webhook(
+        &self,
+        user_id: Uuid,
+        request: CreateWebhookRequest,
+    ) -> Result<WebhookResponse, WebhookServiceError> {
+        let url = request.url.trim();
+        Self::validate_url(url)?;
+
+        let mut events = request.events;
+        events.sort_by_key(|event| event.as_str());
+        events.dedup();
+        if events.is_empty() {
+            return Err(WebhookServiceError::Invalid(
+                "At least one event is required".to_string(),
+            ));
+        }
+
+        let count = self
+            .webhook_repo
+            .count_by_user(user_id)
+            .await
+            .map_err(|e| WebhookServiceError::Dependency(e.to_string()))?;
+        if count >= MAX_WEBHOOKS_PER_USER {
+            return Err(WebhookServiceError::Invalid(format!(
+                "A maximum of {} webhooks is allowed",
+                MAX_WEBHOOKS_PER_USER
+            )));
+        }
+
+        let secret = format!(
+            "whsec_{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        );
+        let webhook = self
+            .webhook_repo
+            .create(user_id, url, &secret, &events)
+            .await
+            .map_err(|e| WebhookServiceError::Dependency(e.to_string()))?;
+
+        tracing::info!(user_id = %user_id, webhook_id = %webhook.id, "Webhook created");
+
+        let mut response = WebhookResponse::from(webhook);
+        response.secret = Some(secret);
+        Ok(response)
+    }
+
+    async fn list_webhooks(&self, user_id: Uuid) -> Result<WebhooksResponse, WebhookServiceError> {
+        let webhooks = self
+            .webhook_repo
+            .find_by_user(user_id)
+            .await
+            .map_err(|e| WebhookServiceError::Dependency(e.to_string()))?;
+
+        Ok(WebhooksResponse {
+            webhooks: webhooks.into_iter().map(WebhookResponse::from).collect(),
+        })
+    }
+
+    async fn delete_webhook(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+    ) -> Result<(), WebhookServiceError> {
+        let deleted = self
+            .webhook_repo
+            .delete_for_user(webhook_id, user_id)
+            .await
+            .map_err(|e| WebhookServiceError::Dependency(e.to_string()))?;
+        if !deleted {
+            return Err(WebhookServiceError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn list_deliveries(
+        &self,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        limit: Option<i64>,
+        cursor: Option<&str>,
+    ) -> Result<WebhookDeliveriesResponse, WebhookServiceError> {
+        let webhook = self
+            .webhook_repo
+            .find_by_id(webhook_id)
+            .await
+            .map_err(|e| WebhookServiceError::Dependency(e.to_string()))?;
+        if webhook.is_none_or(|webhook| webhook.user_id != user_id) {
+            return Err(WebhookServiceError::NotFound);
+        }
+
+        let page = PageRequest::parse(
+            limit,
+            cursor,
+            DEFAULT_DELIVERY_PAGE_SIZE,
+            MAX_DELIVERY_PAGE_SIZE,
+        )
+        .map_err(|e| WebhookServiceError::Invalid(e.to_string()))?;
+        let deliveries = self
+            .webhook_repo
+            .find_deliveries(webhook_id, page.after.as_ref(), page.fetch_limit())
+            .await
+            .map_err(|e| WebhookServiceError::Dependency(e.to_string()))?;
+
+        Ok(PageResponse::from_rows(
+            deliveries,
+            &page,
+            |delivery| Cursor {
+                at: delivery.created_at,
+                key: delivery.id,
+            },
+            WebhookDeliveryResponse::from,
+        ))
+    }
+
+    async fn dispatch(&self, user_id: Uuid, event_type: WebhookEventType, data: Value) {
+        self.enqueue(user_id, event_type, &data, None).await;
+    }
+
+    async fn dispatch_once_since(
+        &self,
+        user_id: Uuid,
+        event_type: WebhookEventType,
+        data: Value,
+        since: DateTime<Utc>,
+    ) {
+        self.enqueue(user_id, event_type, &data, Some(since)).await;
+    }
+
+    async fn deliver_due(&self) -> Result<usize, WebhookServiceError> {
+        let now = Utc::now();
+        let deliveries = self
+            .webhook_repo
+            .claim_due(
+                now,
+                now + Duration::seconds(DELIVERY_LEASE_SECS),
+                DELIVERY_BATCH_SIZE,
+            )
+            .await
+            .map_err(|e| WebhookServiceError::Dependency(e.to_string()))?;
+        let count = deliveries.len();
+
+        let mut attempts = JoinSet::new();
+        for delivery in deliveries {
+            let webhook_repo = self.webhook_repo.clone();
+            let sender = self.sender.clone();
+            attempts.spawn(async move { Self::attempt(&webhook_repo, &sender, delivery).await });
+        }
+        while attempts.join_next().await.is_some() {}
+
+        Ok(count)
+    }
+}
+
+impl WebhookService {
+    fn validate_url(url: &str) -> Result<(), WebhookServiceError> {
+        let invalid = || WebhookServiceError::Invalid("URL must be an http(s) URL".to_string());
+
+        if url.len() > MAX_URL_LENGTH {
+            return Err(WebhookServiceError::Invalid(format!(
+                "URL cannot exceed {} characters",
+                MAX_URL_LENGTH
+            )));
+        }
+        let parsed = Url::parse(url).map_err(|_| invalid())?;
+        if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+            return Err(invalid());
+        }
+
+        Ok(())
+    }
+
+    async fn enqueue(
+        &self,
+        user_id: Uuid,
+        event_type: WebhookEventType,
+        data: &Value,
+        once_since: Option<DateTime<Utc>>,
+    ) {
+        let webhooks = match self.webhook_repo.find_by_user(user_id).await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                tracing::error!(user_id = %user_id, error = %e, "Failed to load webhooks");
+                return;
+            }
+        };
+
+        for webhook in webhooks
+            .iter()
+            .filter(|webhook| webhook.subscribes_to(event_type))
+        {
+            if let Some(since) = once_since {
+                match self
+                    .webhook_repo
+                    .has_delivery_since(webhook.id, event_type, since)
+                    .await
+                {
+                    Ok(true) => continue,
+                    Ok(false) => {}
+                    Err(e) => {
+                        tracing::error!(webhook_id = %webhook.id, error = %e, "Failed to check webhook deliveries");
+                        continue;
+                    }
+                }
+            }
+
+            if let Err(e) = self
+                .webhook_repo
+                .enqueue_delivery(webhook.id, event_type, data)
+                .await
+            {
+                tracing::error!(
+                    webhook_id = %webhook.id,
+                    event_type = event_type.as_str(),
+                    error = %e,
+                    "Failed to queue webhook delivery"
+                );
+            }
+        }
+    }
+
+    /// Send one claimed delivery and record how it went
+    async fn attempt(
+        webhook_repo: &WebhookRepository,
+        sender: &WebhookSender,
+        delivery: WebhookDelivery,
+    ) {
+        let webhook = match webhook_repo.find_by_id(delivery.webhook_id).await {
+            Ok(Some(webhook)) => webhook,
+            // Deleted since it was claimed; its deliveries went with it
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(delivery_id = %delivery.id, error = %e, "Failed to load webhook");
+                return;
+            }
+        };
+
+        let outcome = sender.send(&webhook.url, &webhook.secret, &delivery).await;
+        let attempts = delivery.attempts + 1;
+        let now = Utc::now();
+
+        let (status, next_attempt_at, completed_at) = if outcome.success {
+            (WebhookDeliveryStatus::Succeeded, now, Some(now))
+        } else {
+            match retry_delay(attempts) {
+                Some(delay) => (WebhookDeliveryStatus::Pending, now + delay, None),
+                None => (WebhookDeliveryStatus::Failed, now, Some(now)),
+            }
+        };
+
+        if status == WebhookDeliveryStatus::Failed {
+            tracing::warn!(
+                webhook_id = %webhook.id,
+                delivery_id = %delivery.id,
+                attempts,
+                "Giving up on webhook delivery"
+            );
+        }
+
+        if let Err(e) = webhook_repo
+            .record_attempt(
+                delivery.id,
+                status,
+                attempts,
+                next_attempt_at,
+                outcome.status_code.map(i32::from),
+                outcome.error.as_deref(),
+                completed_at,
+            )
+            .await
+        {
+            tracing::error!(delivery_id = %delivery.id, error = %e, "Failed to record webhook delivery");
+        }
+    }
+}
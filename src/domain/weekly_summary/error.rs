@@ -0,0 +1,30 @@
+use crate::error::AppError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum WeeklySummaryServiceError {
+    #[error("dependency error: {0}")]
+    Dependency(String),
+    #[error("invalid input: {0}")]
+    Invalid(String),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<AppError> for WeeklySummaryServiceError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::BadRequest(msg) => WeeklySummaryServiceError::Invalid(msg),
+            _ => WeeklySummaryServiceError::Dependency(err.to_string()),
+        }
+    }
+}
+
+impl From<WeeklySummaryServiceError> for AppError {
+    fn from(err: WeeklySummaryServiceError) -> Self {
+        match err {
+            WeeklySummaryServiceError::Invalid(msg) => AppError::BadRequest(msg),
+            WeeklySummaryServiceError::Dependency(msg) => AppError::Internal(msg),
+            WeeklySummaryServiceError::Other(e) => AppError::Internal(e.to_string()),
+        }
+    }
+}
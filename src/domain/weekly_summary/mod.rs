@@ -0,0 +1,17 @@
+pub mod error;
+pub mod model;
+pub mod service;
+pub mod unsubscribe;
+
+pub use error::WeeklySummaryServiceError;
+pub use model::{SummaryWeek, WeeklySummary};
+pub use service::{WeeklySummaryService, WeeklySummaryServiceApi};
+pub use unsubscribe::UnsubscribeLinks;
+
+use serde::{Deserialize, Serialize};
+
+/// Query for GET and POST /email/unsubscribe
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnsubscribeQuery {
+    pub token: String,
+}
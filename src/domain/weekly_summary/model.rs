@@ -0,0 +1,75 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// A Monday-to-Sunday week in the user's timezone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SummaryWeek {
+    /// Monday the week starts on
+    pub start: NaiveDate,
+    /// Monday after it, exclusive
+    pub end: NaiveDate,
+}
+
+impl SummaryWeek {
+    /// The week that just ended, when `today` is the Monday a summary goes out.
+    /// Summaries aren't sent on other days, so opting in mid-week waits for the next one.
+    pub fn ending_on(today: NaiveDate) -> Option<Self> {
+        if today.weekday() != Weekday::Mon {
+            return None;
+        }
+        Some(Self {
+            start: today - Duration::days(7),
+            end: today,
+        })
+    }
+
+    /// The week before, which listening is compared against
+    pub fn previous(&self) -> Self {
+        Self {
+            start: self.start - Duration::days(7),
+            end: self.start,
+        }
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date < self.end
+    }
+}
+
+/// The past week's listening, as mailed to the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklySummary {
+    pub week: SummaryWeek,
+    pub minutes: i64,
+    pub previous_minutes: i64,
+    pub articles: i64,
+    /// Most played feeds by title with their minutes, longest first
+    pub top_feeds: Vec<(String, i64)>,
+    /// Share of the current quota period used so far
+    pub quota_percent: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_week_ending_on_monday() {
+        let week = SummaryWeek::ending_on(date(2026, 10, 12)).unwrap();
+
+        assert_eq!(week.start, date(2026, 10, 5));
+        assert_eq!(week.end, date(2026, 10, 12));
+        assert!(week.contains(date(2026, 10, 11)));
+        assert!(!week.contains(date(2026, 10, 12)));
+        assert_eq!(week.previous().start, date(2026, 9, 28));
+    }
+
+    #[test]
+    fn test_no_week_ends_on_other_days() {
+        assert_eq!(SummaryWeek::ending_on(date(2026, 10, 13)), None);
+        assert_eq!(SummaryWeek::ending_on(date(2026, 10, 11)), None);
+    }
+}
@@ -0,0 +1,336 @@
+use super::error::WeeklySummaryServiceError;
+use super::{SummaryWeek, UnsubscribeLinks, WeeklySummary};
+use crate::domain::analytics::AnalyticsEventName;
+use crate::domain::plan::local_midnight;
+use crate::domain::user::{
+    UpdateMeRequest, UpdateNotificationsDto, UpdateSettingsDto, User, UserService, UserServiceApi,
+    UserServiceError,
+};
+use crate::error::AppError;
+use crate::infrastructure::email::{EmailSender, EmailTemplate};
+use crate::infrastructure::repositories::{
+    AnalyticsEventRepository, FeedRepository, FeedRepositoryApi, UsageRepositoryApi,
+    WeeklySummaryRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const RECIPIENT_BATCH_SIZE: i64 = 100;
+const TOP_FEEDS: usize = 3;
+
+pub struct WeeklySummaryService {
+    summary_repo: Arc<WeeklySummaryRepository>,
+    usage_repo: Arc<dyn UsageRepositoryApi>,
+    analytics_event_repo: Arc<AnalyticsEventRepository>,
+    feed_repo: Arc<FeedRepository>,
+    user_service: Arc<UserService>,
+    email_sender: Arc<dyn EmailSender>,
+    unsubscribe_links: UnsubscribeLinks,
+}
+
+impl WeeklySummaryService {
+    pub fn new(
+        summary_repo: Arc<WeeklySummaryRepository>,
+        usage_repo: Arc<dyn UsageRepositoryApi>,
+        analytics_event_repo: Arc<AnalyticsEventRepository>,
+        feed_repo: Arc<FeedRepository>,
+        user_service: Arc<UserService>,
+        email_sender: Arc<dyn EmailSender>,
+        unsubscribe_links: UnsubscribeLinks,
+    ) -> Self {
+        Self {
+            summary_repo,
+            usage_repo,
+            analytics_event_repo,
+            feed_repo,
+            user_service,
+            email_sender,
+            unsubscribe_links,
+        }
+    }
+}
+
+#[async_trait]
+pub trait WeeklySummaryServiceApi: Send + Sync {
+    /// Email the past week's summary to opted-in users whose Monday it is. Each user's
+    /// week is sent once across instances. Returns how many were sent.
+    async fn send_weekly_summaries(&self) -> Result<usize, WeeklySummaryServiceError>;
+
+    /// Turn the summary off for the user an unsubscribe link was issued to
+    async fn unsubscribe(&self, token: &str) -> Result<(), WeeklySummaryServiceError>;
+}
+
+#[async_trait]
+impl WeeklySummaryServiceApi for WeeklySummaryService {
+    async fn send_weekly_summaries(&self) -> Result<usize, WeeklySummaryServiceError> {
+        let now = Utc::now();
+        let mut after = Uuid::nil();
+        let mut sent = 0;
+
+        loop {
+            let users = self
+                .summary_repo
+                .find_recipients(after, RECIPIENT_BATCH_SIZE)
+                .await
+                .map_err(|e| WeeklySummaryServiceError::Dependency(e.to_string()))?;
+            let Some(last) = users.last() else {
+                break;
+            };
+            after = last.id;
+
+            for user in &users {
+                match self.send_summary(user, now).await {
+                    Ok(true) => sent += 1,
+                    Ok(false) => {}
+                    Err(e) => tracing::warn!(
+                        user_id = %user.id,
+                        error = %e,
+                        "Failed to send weekly summary"
+                    ),
+                }
+            }
+            if (users.len() as i64) < RECIPIENT_BATCH_SIZE {
+                break;
+            }
+        }
+
+        Ok(sent)
+    }
+
+    async fn unsubscribe(&self, token: &str) -> Result<(), WeeklySummaryServiceError> {
+        let user_id = self.unsubscribe_links.verify(token).ok_or_else(|| {
+            WeeklySummaryServiceError::Invalid("Invalid unsubscribe link".to_string())
+        })?;
+
+        let request = UpdateMeRequest {
+            display_name: None,
+            settings: Some(UpdateSettingsDto {
+                notifications: Some(UpdateNotificationsDto {
+                    weekly_summary: Some(false),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        };
+        match self
+            .user_service
+            .update_profile(user_id, request, None)
+            .await
+        {
+            // A deleted account gets no more emails either
+            Ok(_) | Err(UserServiceError::NotFound) => Ok(()),
+            Err(e) => Err(WeeklySummaryServiceError::from(AppError::from(e))),
+        }
+    }
+}
+
+impl WeeklySummaryService {
+    /// Send the user's summary if their week just ended and no instance has taken it yet.
+    /// The week stays taken once sent, or when there was nothing to report.
+    async fn send_summary(
+        &self,
+        user: &User,
+        now: DateTime<Utc>,
+    ) -> Result<bool, WeeklySummaryServiceError> {
+        let today = now.with_timezone(&user.timezone()).date_naive();
+        let Some(week) = SummaryWeek::ending_on(today) else {
+            return Ok(false);
+        };
+        let claimed = self
+            .summary_repo
+            .claim(user.id, week.start, now)
+            .await
+            .map_err(|e| WeeklySummaryServiceError::Dependency(e.to_string()))?;
+        if !claimed {
+            return Ok(false);
+        }
+
+        let result = self.compile_and_send(user, week).await;
+        if result.is_err() {
+            if let Err(e) = self.summary_repo.release(user.id, week.start).await {
+                tracing::warn!(user_id = %user.id, error = %e, "Failed to release weekly summary");
+            }
+        }
+        result
+    }
+
+    async fn compile_and_send(
+        &self,
+        user: &User,
+        week: SummaryWeek,
+    ) -> Result<bool, WeeklySummaryServiceError> {
+        let summary = self.compile(user, week).await?;
+        if summary.minutes == 0 && summary.articles == 0 {
+            return Ok(false);
+        }
+
+        let message = EmailTemplate::WeeklySummary {
+            week_start: summary.week.start,
+            minutes: summary.minutes,
+            previous_minutes: summary.previous_minutes,
+            articles: summary.articles,
+            top_feeds: summary.top_feeds,
+            quota_percent: summary.quota_percent,
+            unsubscribe_link: self.unsubscribe_links.link(user.id),
+        }
+        .render(&user.email);
+        self.email_sender
+            .send(&message)
+            .await
+            .map_err(|e| WeeklySummaryServiceError::Dependency(e.to_string()))?;
+
+        Ok(true)
+    }
+
+    async fn compile(
+        &self,
+        user: &User,
+        week: SummaryWeek,
+    ) -> Result<WeeklySummary, WeeklySummaryServiceError> {
+        let previous = week.previous();
+        let usage = self
+            .usage_repo
+            .get_usage_between(user.id, week.start, week.end)
+            .await
+            .map_err(|e| WeeklySummaryServiceError::Dependency(e.to_string()))?;
+        let previous_usage = self
+            .usage_repo
+            .get_usage_between(user.id, previous.start, previous.end)
+            .await
+            .map_err(|e| WeeklySummaryServiceError::Dependency(e.to_string()))?;
+
+        // Usage days are the user's own; playback events carry UTC times
+        let tz = user.timezone();
+        let playbacks = self
+            .analytics_event_repo
+            .find_properties(
+                user.id,
+                AnalyticsEventName::PlaybackCompleted,
+                local_midnight(tz, week.start),
+                local_midnight(tz, week.end),
+            )
+            .await
+            .map_err(|e| WeeklySummaryServiceError::Dependency(e.to_string()))?;
+        let feed_titles: HashMap<Uuid, String> = self
+            .feed_repo
+            .find_by_user(user.id)
+            .await
+            .map_err(|e| WeeklySummaryServiceError::Dependency(e.to_string()))?
+            .into_iter()
+            .map(|feed| (feed.id, feed.title.unwrap_or(feed.url)))
+            .collect();
+
+        let quota = self
+            .user_service
+            .get_user_profile(user.id)
+            .await
+            .map_err(|e| WeeklySummaryServiceError::from(AppError::from(e)))?
+            .subscription
+            .usage;
+        let quota_percent = if quota.characters_limit > 0 {
+            i64::from(quota.characters_used_today) * 100 / i64::from(quota.characters_limit)
+        } else {
+            0
+        };
+
+        Ok(WeeklySummary {
+            week,
+            minutes: minutes(usage.audio_seconds),
+            previous_minutes: minutes(previous_usage.audio_seconds),
+            articles: i64::from(usage.articles_synthesized),
+            top_feeds: top_feeds(&playbacks, &feed_titles),
+            quota_percent,
+        })
+    }
+}
+
+fn minutes(seconds: f64) -> i64 {
+    (seconds / 60.0).round() as i64
+}
+
+/// The feeds listened to longest, by title, from `playback_completed` properties.
+/// Playbacks without a feed or of feeds since removed aren't counted.
+fn top_feeds(
+    playbacks: &[Map<String, Value>],
+    feed_titles: &HashMap<Uuid, String>,
+) -> Vec<(String, i64)> {
+    let mut seconds: HashMap<&String, f64> = HashMap::new();
+    for properties in playbacks {
+        let title = properties
+            .get("feed_id")
+            .and_then(Value::as_str)
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .and_then(|id| feed_titles.get(&id));
+        let duration = properties.get("duration_seconds").and_then(Value::as_f64);
+        if let (Some(title), Some(duration)) = (title, duration) {
+            *seconds.entry(title).or_default() += duration;
+        }
+    }
+
+    let mut feeds: Vec<(String, i64)> = seconds
+        .into_iter()
+        .map(|(title, seconds)| (title.clone(), minutes(seconds)))
+        .filter(|(_, minutes)| *minutes > 0)
+        .collect();
+    feeds.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    feeds.truncate(TOP_FEEDS);
+    feeds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn playback(feed_id: Option<Uuid>, duration_seconds: f64) -> Map<String, Value> {
+        let mut properties = json!({
+            "link": "https://example.com/article",
+            "duration_seconds": duration_seconds,
+        });
+        if let Some(feed_id) = feed_id {
+            properties["feed_id"] = json!(feed_id);
+        }
+        properties.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_top_feeds_ranks_known_feeds_by_minutes() {
+        let (news, tech, sports, music) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let titles: HashMap<Uuid, String> = [
+            (news, "News"),
+            (tech, "Tech"),
+            (sports, "Sports"),
+            (music, "Music"),
+        ]
+        .into_iter()
+        .map(|(id, title)| (id, title.to_string()))
+        .collect();
+        let playbacks = vec![
+            playback(Some(tech), 600.0),
+            playback(Some(news), 300.0),
+            playback(Some(tech), 300.0),
+            playback(Some(sports), 1200.0),
+            playback(Some(music), 60.0),
+            playback(None, 3600.0),
+            playback(Some(Uuid::new_v4()), 3600.0),
+        ];
+
+        assert_eq!(
+            top_feeds(&playbacks, &titles),
+            vec![
+                ("Sports".to_string(), 20),
+                ("Tech".to_string(), 15),
+                ("News".to_string(), 5),
+            ]
+        );
+    }
+}
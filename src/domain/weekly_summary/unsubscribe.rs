@@ -0,0 +1,82 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+/// Signed links that turn the weekly summary off without signing in. They don't expire,
+/// since people unsubscribe from months-old emails too.
+#[derive(Debug, Clone)]
+pub struct UnsubscribeLinks {
+    secret: String,
+    base_url: String,
+}
+
+impl UnsubscribeLinks {
+    pub fn new(secret: String, base_url: String) -> Self {
+        Self { secret, base_url }
+    }
+
+    pub fn link(&self, user_id: Uuid) -> String {
+        format!(
+            "{}/email/unsubscribe?token={}",
+            self.base_url,
+            self.token(user_id)
+        )
+    }
+
+    /// The user a token was issued to, if it was signed with our secret
+    pub fn verify(&self, token: &str) -> Option<Uuid> {
+        let (user_id, signature) = token.split_once('.')?;
+        let user_id = Uuid::parse_str(user_id).ok()?;
+        let signature = hex::decode(signature).ok()?;
+        // Constant-time comparison
+        self.mac(user_id).verify_slice(&signature).ok()?;
+        Some(user_id)
+    }
+
+    fn token(&self, user_id: Uuid) -> String {
+        let signature = hex::encode(self.mac(user_id).finalize().into_bytes());
+        format!("{}.{}", user_id.simple(), signature)
+    }
+
+    fn mac(&self, user_id: Uuid) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        // Scoped to this list, so the same secret can sign other kinds of links
+        mac.update(b"weekly_summary:");
+        mac.update(user_id.as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_token_verifies_to_its_user() {
+        let links = UnsubscribeLinks::new("secret".to_string(), "https://api.test".to_string());
+        let user_id = Uuid::new_v4();
+
+        let link = links.link(user_id);
+        let token = link
+            .strip_prefix("https://api.test/email/unsubscribe?token=")
+            .unwrap();
+
+        assert_eq!(links.verify(token), Some(user_id));
+    }
+
+    #[test]
+    fn test_tampered_tokens_are_rejected() {
+        let links = UnsubscribeLinks::new("secret".to_string(), "https://api.test".to_string());
+        let other = UnsubscribeLinks::new("other".to_string(), "https://api.test".to_string());
+        let token = links.token(Uuid::new_v4());
+        let (_, signature) = token.split_once('.').unwrap();
+
+        assert_eq!(
+            links.verify(&format!("{}.{}", Uuid::new_v4().simple(), signature)),
+            None
+        );
+        assert_eq!(other.verify(&token), None);
+        assert_eq!(links.verify("not-a-token"), None);
+    }
+}
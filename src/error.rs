@@ -1,9 +1,15 @@
 use axum::{
-    http::StatusCode,
+    body::Body,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use serde::{Deserialize, Serialize};
+
+use crate::domain::shared::{ErrorDetail, ErrorResponse};
+use crate::infrastructure::i18n::{translate, Locale};
+
+/// Largest body read back from a framework rejection to use as its message
+const MAX_REJECTION_BODY_BYTES: usize = 4096;
 
 /// Main application error type
 #[derive(Debug, thiserror::Error)]
@@ -14,6 +20,15 @@ pub enum AppError {
     #[error("Authentication failed: {0}")]
     Unauthorized(String),
 
+    /// Credentials that were never valid, such as a forged token or an unknown API key,
+    /// unlike an expired token. Answered like `Unauthorized`, but counted against the
+    /// client's address by the blocklist.
+    #[error("Authentication failed: {0}")]
+    InvalidCredentials(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Invalid refresh token")]
     InvalidRefreshToken,
 
@@ -29,6 +44,9 @@ pub enum AppError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
     #[error("Rate limit exceeded: {0}")]
     RateLimitExceeded(String),
 
@@ -38,6 +56,9 @@ pub enum AppError {
     #[error("Text too large: {0}")]
     PayloadTooLarge(String),
 
+    #[error("Server overloaded: {0}")]
+    Overloaded(String),
+
     #[error("External service error: {0}")]
     ExternalService(String),
 
@@ -45,35 +66,96 @@ pub enum AppError {
     Internal(String),
 }
 
-/// Error response structure - simplified to just message + status code
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ErrorResponse {
-    pub message: String,
-}
-
 impl AppError {
     /// Get the HTTP status code for this error
     pub fn status_code(&self) -> StatusCode {
         match self {
-            Self::Unauthorized(_) | Self::InvalidRefreshToken | Self::RefreshTokenExpired => {
-                StatusCode::UNAUTHORIZED
-            }
+            Self::Unauthorized(_)
+            | Self::InvalidCredentials(_)
+            | Self::InvalidRefreshToken
+            | Self::RefreshTokenExpired => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::BadRequest(_) => StatusCode::BAD_REQUEST,
             Self::NotFound(_) => StatusCode::NOT_FOUND,
             Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
             Self::RateLimitExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
             Self::PaymentRequired(_) => StatusCode::PAYMENT_REQUIRED,
             Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Overloaded(_) => StatusCode::SERVICE_UNAVAILABLE,
             Self::Database(_) | Self::ExternalService(_) | Self::Internal(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
         }
     }
 
-    /// Convert to simplified error response
-    pub fn to_response(&self) -> ErrorResponse {
-        ErrorResponse {
+    /// Stable, machine-readable code clients can branch on
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Unauthorized(_) | Self::InvalidCredentials(_) => "unauthorized",
+            Self::InvalidRefreshToken => "invalid_refresh_token",
+            Self::RefreshTokenExpired => "refresh_token_expired",
+            Self::Forbidden(_) => "forbidden",
+            Self::BadRequest(_) => "bad_request",
+            Self::NotFound(_) => "not_found",
+            Self::Conflict(_) => "conflict",
+            Self::PreconditionFailed(_) => "precondition_failed",
+            Self::RateLimitExceeded(_) => "rate_limit_exceeded",
+            Self::PaymentRequired(_) => "payment_required",
+            Self::PayloadTooLarge(_) => "payload_too_large",
+            Self::Overloaded(_) => "overloaded",
+            Self::ExternalService(_) => "external_service_error",
+            Self::Database(_) | Self::Internal(_) => "internal_error",
+        }
+    }
+
+    /// The fixed part of the message, before the reason
+    fn summary(&self) -> &'static str {
+        match self {
+            Self::Database(_) => "Database error",
+            Self::Unauthorized(_) | Self::InvalidCredentials(_) => "Authentication failed",
+            Self::Forbidden(_) => "Forbidden",
+            Self::InvalidRefreshToken => "Invalid refresh token",
+            Self::RefreshTokenExpired => "Refresh token expired",
+            Self::BadRequest(_) => "Invalid input",
+            Self::NotFound(_) => "Resource not found",
+            Self::Conflict(_) => "Conflict",
+            Self::PreconditionFailed(_) => "Precondition failed",
+            Self::RateLimitExceeded(_) => "Rate limit exceeded",
+            Self::PaymentRequired(_) => "Payment required",
+            Self::PayloadTooLarge(_) => "Text too large",
+            Self::Overloaded(_) => "Server overloaded",
+            Self::ExternalService(_) => "External service error",
+            Self::Internal(_) => "Internal server error",
+        }
+    }
+
+    fn reason(&self) -> Option<String> {
+        match self {
+            Self::Database(e) => Some(e.to_string()),
+            Self::Unauthorized(reason)
+            | Self::InvalidCredentials(reason)
+            | Self::Forbidden(reason)
+            | Self::BadRequest(reason)
+            | Self::NotFound(reason)
+            | Self::Conflict(reason)
+            | Self::PreconditionFailed(reason)
+            | Self::RateLimitExceeded(reason)
+            | Self::PaymentRequired(reason)
+            | Self::PayloadTooLarge(reason)
+            | Self::Overloaded(reason)
+            | Self::ExternalService(reason)
+            | Self::Internal(reason) => Some(reason.clone()),
+            Self::InvalidRefreshToken | Self::RefreshTokenExpired => None,
+        }
+    }
+
+    pub fn to_detail(&self) -> ErrorDetail {
+        ErrorDetail {
+            code: self.code().to_string(),
             message: self.to_string(),
+            details: None,
+            help_url: None,
         }
     }
 }
@@ -89,12 +171,181 @@ impl IntoResponse for AppError {
             "Request failed"
         );
 
-        // Create simplified error response
-        let error_response = self.to_response();
+        // The envelope is completed with the request ID by `envelope_error_response`
+        let detail = self.to_detail();
+        let mut response = (status, Json(&detail)).into_response();
+        response.extensions_mut().insert(detail);
+        response.extensions_mut().insert(MessageParts {
+            summary: self.summary(),
+            reason: self.reason(),
+        });
+        if matches!(
+            self,
+            Self::InvalidCredentials(_) | Self::InvalidRefreshToken
+        ) {
+            response.extensions_mut().insert(CredentialFailure);
+        }
+        response
+    }
+}
+
+/// Left on the response when the client presented credentials that were never valid, for
+/// `ip_blocklist_middleware` to count
+#[derive(Debug, Clone, Copy)]
+pub struct CredentialFailure;
 
-        (status, Json(error_response)).into_response()
+/// The English summary and reason an `AppError` message is made of, kept on its response
+/// so the message can be translated piece by piece
+#[derive(Debug, Clone)]
+struct MessageParts {
+    summary: &'static str,
+    reason: Option<String>,
+}
+
+impl MessageParts {
+    fn translate(&self, locale: Locale) -> String {
+        let summary = translate(locale, self.summary);
+        match &self.reason {
+            Some(reason) => format!("{}: {}", summary, translate(locale, reason)),
+            None => summary,
+        }
+    }
+}
+
+/// Rewrite an error response into the `{error: {code, message}, request_id}` envelope.
+/// Responses from `AppError` carry their detail in an extension. Plain-text ones, such as
+/// extractor rejections or unmatched routes, get a code derived from their status and
+/// their body as the message. JSON bodies from handlers, like the readiness probe, are kept.
+///
+/// Messages from `AppError` are translated to `requested`, the locale from
+/// `Accept-Language`, or else to the user's language left on the response by
+/// `auth_middleware`. Codes are never translated.
+pub async fn envelope_error_response(
+    response: Response,
+    request_id: &str,
+    requested: Option<Locale>,
+) -> Response {
+    let status = response.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return response;
+    }
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+    if is_json && response.extensions().get::<ErrorDetail>().is_none() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let locale = requested
+        .or_else(|| parts.extensions.get::<Locale>().copied())
+        .unwrap_or_default();
+    // Only `AppError` messages are translated; other bodies stay in English
+    let mut language = Locale::En;
+    let error = match parts.extensions.remove::<ErrorDetail>() {
+        Some(mut detail) => {
+            if let Some(message) = parts.extensions.remove::<MessageParts>() {
+                detail.message = message.translate(locale);
+                language = locale;
+            }
+            detail
+        }
+        None => {
+            let bytes = axum::body::to_bytes(body, MAX_REJECTION_BODY_BYTES)
+                .await
+                .unwrap_or_default();
+            let message = String::from_utf8_lossy(&bytes).trim().to_string();
+            ErrorDetail {
+                code: code_for_status(status).to_string(),
+                message: if message.is_empty() {
+                    status.canonical_reason().unwrap_or("Error").to_string()
+                } else {
+                    message
+                },
+                details: None,
+                help_url: None,
+            }
+        }
+    };
+
+    let envelope = ErrorResponse {
+        error,
+        request_id: request_id.to_string(),
+    };
+    let Ok(body) = serde_json::to_vec(&envelope) else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    parts.headers.insert(
+        header::CONTENT_LANGUAGE,
+        HeaderValue::from_static(language.as_str()),
+    );
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Code for errors that didn't come from `AppError`
+fn code_for_status(status: StatusCode) -> &'static str {
+    match status {
+        StatusCode::BAD_REQUEST => "bad_request",
+        StatusCode::UNAUTHORIZED => "unauthorized",
+        StatusCode::FORBIDDEN => "forbidden",
+        StatusCode::NOT_FOUND => "not_found",
+        StatusCode::METHOD_NOT_ALLOWED => "method_not_allowed",
+        StatusCode::CONFLICT => "conflict",
+        StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+        StatusCode::UNSUPPORTED_MEDIA_TYPE => "unsupported_media_type",
+        StatusCode::UNPROCESSABLE_ENTITY => "invalid_request_body",
+        StatusCode::TOO_MANY_REQUESTS => "rate_limit_exceeded",
+        s if s.is_server_error() => "internal_error",
+        _ => "request_failed",
     }
 }
 
 /// Custom result type for the application
 pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_parts_rebuild_the_english_message() {
+        let errors = [
+            AppError::Unauthorized("User not found".to_string()),
+            AppError::InvalidRefreshToken,
+            AppError::PaymentRequired("Subscription is not active".to_string()),
+            AppError::Internal("boom".to_string()),
+        ];
+
+        for error in errors {
+            let parts = MessageParts {
+                summary: error.summary(),
+                reason: error.reason(),
+            };
+            assert_eq!(parts.translate(Locale::En), error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_message_parts_are_translated_separately() {
+        let error = AppError::NotFound("Feed not found".to_string());
+        let parts = MessageParts {
+            summary: error.summary(),
+            reason: error.reason(),
+        };
+
+        assert_eq!(
+            parts.translate(Locale::Es),
+            "Recurso no encontrado: Feed no encontrado"
+        );
+        assert_eq!(
+            parts.translate(Locale::Fr),
+            "Ressource introuvable: Flux introuvable"
+        );
+    }
+}
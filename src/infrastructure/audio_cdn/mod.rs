@@ -0,0 +1,38 @@
+pub mod cloudfront;
+pub mod s3;
+
+pub use cloudfront::{CloudFrontInvalidator, CloudFrontSigner};
+pub use s3::S3AudioCdn;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::error::AppResult;
+use crate::infrastructure::config::Config;
+
+/// Where synthesized audio is stored for clients to download directly, so large audio
+/// doesn't go through the API instances
+#[async_trait]
+pub trait AudioCdn: Send + Sync {
+    /// Store `audio` as `name`, replacing what was there
+    async fn upload(&self, name: &str, audio: Vec<u8>) -> AppResult<()>;
+
+    /// URL `name` can be downloaded from, for a limited time
+    fn signed_url(&self, name: &str) -> AppResult<String>;
+
+    /// Delete the audio uploaded before `before` and have the CDN drop its copies.
+    /// Returns how much was deleted.
+    async fn purge_before(&self, before: DateTime<Utc>) -> AppResult<u64>;
+}
+
+/// The CDN in `AUDIO_CDN_BUCKET`, if one is configured
+pub async fn create_audio_cdn(config: &Config) -> AppResult<Option<Arc<dyn AudioCdn>>> {
+    let Some(audio_cdn) = &config.audio_cdn else {
+        return Ok(None);
+    };
+
+    Ok(Some(Arc::new(
+        S3AudioCdn::connect(&config.aws_region, audio_cdn).await?,
+    )))
+}
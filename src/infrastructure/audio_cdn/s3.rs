@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{Delete, ObjectIdentifier};
+use aws_sdk_s3::Client as S3Client;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use chrono::{DateTime, Duration, Utc};
+
+use super::{AudioCdn, CloudFrontInvalidator, CloudFrontSigner};
+use crate::error::{AppError, AppResult};
+use crate::infrastructure::config::AudioCdnConfig;
+
+/// Audio is kept under this prefix, so purges leave the rest of the bucket alone
+const AUDIO_PREFIX: &str = "audio/";
+/// Most keys one DeleteObjects call takes; invalidations are batched the same way
+const MAX_KEYS_PER_BATCH: usize = 1000;
+
+/// Audio in an S3 bucket, served by a CloudFront distribution in front of it
+pub struct S3AudioCdn {
+    client: S3Client,
+    bucket: String,
+    cache_control: String,
+    url_ttl: Duration,
+    signer: CloudFrontSigner,
+    invalidator: CloudFrontInvalidator,
+}
+
+impl S3AudioCdn {
+    /// Uses the same AWS credentials as Polly
+    pub async fn connect(region: &str, config: &AudioCdnConfig) -> AppResult<Self> {
+        let aws_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new(region.to_string()))
+            .load()
+            .await;
+        let credentials = aws_config.credentials_provider().ok_or_else(|| {
+            AppError::Internal("No AWS credentials to invalidate CloudFront with".to_string())
+        })?;
+        let signer =
+            CloudFrontSigner::new(&config.domain, &config.key_pair_id, &config.private_key)
+                .map_err(|e| AppError::Internal(format!("AUDIO_CDN_PRIVATE_KEY {}", e)))?;
+
+        Ok(Self {
+            client: S3Client::new(&aws_config),
+            bucket: config.bucket.clone(),
+            cache_control: format!("public, max-age={}", config.cache_max_age_secs),
+            url_ttl: Duration::seconds(i64::try_from(config.url_ttl_secs).unwrap_or(i64::MAX)),
+            signer,
+            invalidator: CloudFrontInvalidator::new(&config.distribution_id, credentials),
+        })
+    }
+
+    /// Keys of the audio last written before `before`
+    async fn keys_before(&self, before: DateTime<Utc>) -> AppResult<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(AUDIO_PREFIX)
+            .into_paginator()
+            .send();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                AppError::ExternalService(format!("S3 listing failed: {}", DisplayErrorContext(e)))
+            })?;
+            for object in page.contents() {
+                let last_modified = object
+                    .last_modified()
+                    .and_then(|at| DateTime::from_timestamp(at.secs(), at.subsec_nanos()));
+                if let (Some(key), Some(last_modified)) = (object.key(), last_modified) {
+                    if last_modified < before {
+                        keys.push(key.to_string());
+                    }
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn delete(&self, keys: &[String]) -> AppResult<()> {
+        let objects = keys
+            .iter()
+            .map(|key| ObjectIdentifier::builder().key(key).build())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Internal(format!("Invalid S3 delete: {}", e)))?;
+        let delete = Delete::builder()
+            .set_objects(Some(objects))
+            .quiet(true)
+            .build()
+            .map_err(|e| AppError::Internal(format!("Invalid S3 delete: {}", e)))?;
+
+        self.client
+            .delete_objects()
+            .bucket(&self.bucket)
+            .delete(delete)
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::ExternalService(format!("S3 delete failed: {}", DisplayErrorContext(e)))
+            })?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AudioCdn for S3AudioCdn {
+    async fn upload(&self, name: &str, audio: Vec<u8>) -> AppResult<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(format!("{}{}", AUDIO_PREFIX, name))
+            .content_type("audio/mpeg")
+            .cache_control(&self.cache_control)
+            .body(ByteStream::from(audio))
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::ExternalService(format!("S3 upload failed: {}", DisplayErrorContext(e)))
+            })?;
+        Ok(())
+    }
+
+    fn signed_url(&self, name: &str) -> AppResult<String> {
+        let expires_at = (Utc::now() + self.url_ttl).timestamp();
+        self.signer
+            .signed_url(&format!("{}{}", AUDIO_PREFIX, name), expires_at)
+    }
+
+    /// Audio is kept at least as long as the URLs handed out for it stay valid. Objects
+    /// are deleted before they're invalidated, so the CDN can't fetch them again in
+    /// between.
+    async fn purge_before(&self, before: DateTime<Utc>) -> AppResult<u64> {
+        let before = before.min(Utc::now() - self.url_ttl);
+        let keys = self.keys_before(before).await?;
+
+        for batch in keys.chunks(MAX_KEYS_PER_BATCH) {
+            self.delete(batch).await?;
+            let paths: Vec<String> = batch.iter().map(|key| format!("/{}", key)).collect();
+            self.invalidator.invalidate(&paths).await?;
+        }
+
+        Ok(keys.len() as u64)
+    }
+}
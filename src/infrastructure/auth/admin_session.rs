@@ -0,0 +1,121 @@
+//! Browser sessions for the admin dashboard. Pages are plain navigations, which can't
+//! carry a bearer token, so the access token from the GitHub sign-in is kept in a
+//! cookie scoped to `/admin` and handed to the usual auth middleware from there.
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Redirect, Response},
+};
+
+pub const ADMIN_SESSION_COOKIE: &str = "feedtape_admin_session";
+/// Where dashboard visitors without a valid session are sent
+pub const ADMIN_SIGN_IN_PATH: &str = "/admin/sign-in";
+
+/// `Set-Cookie` value storing the access token for `max_age_secs`. `SameSite=Lax` lets
+/// the cookie through on the redirect back from GitHub while keeping it off cross-site
+/// form posts.
+pub fn session_cookie(token: &str, max_age_secs: i64, secure: bool) -> String {
+    format!(
+        "{}={}; Path=/admin; Max-Age={}; HttpOnly;{} SameSite=Lax",
+        ADMIN_SESSION_COOKIE,
+        token,
+        max_age_secs,
+        secure_attribute(secure)
+    )
+}
+
+/// `Set-Cookie` value that ends the session
+pub fn cleared_session_cookie(secure: bool) -> String {
+    format!(
+        "{}=; Path=/admin; Max-Age=0; HttpOnly;{} SameSite=Lax",
+        ADMIN_SESSION_COOKIE,
+        secure_attribute(secure)
+    )
+}
+
+/// Left off outside staging and production by default, where the app is served over
+/// plain HTTP
+pub(crate) fn secure_attribute(secure: bool) -> &'static str {
+    if secure {
+        " Secure;"
+    } else {
+        ""
+    }
+}
+
+/// Put in front of `auth_middleware` on dashboard pages: the session cookie becomes the
+/// bearer token unless the request already has one, and failed authentication sends the
+/// browser to the sign-in page instead of answering with JSON.
+pub async fn admin_session_middleware(mut request: Request, next: Next) -> Response {
+    if !request.headers().contains_key(header::AUTHORIZATION) {
+        let bearer = session_token(request.headers())
+            .and_then(|token| HeaderValue::from_str(&format!("Bearer {}", token)).ok());
+        match bearer {
+            Some(bearer) => {
+                request.headers_mut().insert(header::AUTHORIZATION, bearer);
+            }
+            None => return Redirect::to(ADMIN_SIGN_IN_PATH).into_response(),
+        }
+    }
+
+    let response = next.run(request).await;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        return Redirect::to(ADMIN_SIGN_IN_PATH).into_response();
+    }
+    response
+}
+
+fn session_token(headers: &axum::http::HeaderMap) -> Option<&str> {
+    cookie_value(headers, ADMIN_SESSION_COOKIE)
+}
+
+/// The non-empty value of the cookie called `cookie_name`, among all `Cookie` headers
+pub(crate) fn cookie_value<'a>(
+    headers: &'a axum::http::HeaderMap,
+    cookie_name: &str,
+) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == cookie_name)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderMap;
+
+    #[test]
+    fn test_session_token_is_read_among_other_cookies() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; feedtape_admin_session=abc.def; lang=es"),
+        );
+        assert_eq!(session_token(&headers), Some("abc.def"));
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("feedtape_admin_session="),
+        );
+        assert_eq!(session_token(&headers), None);
+    }
+
+    #[test]
+    fn test_session_cookie_is_secure_only_when_asked() {
+        assert_eq!(
+            session_cookie("abc", 3600, true),
+            "feedtape_admin_session=abc; Path=/admin; Max-Age=3600; HttpOnly; Secure; \
+             SameSite=Lax"
+        );
+        assert!(!session_cookie("abc", 3600, false).contains("Secure"));
+        assert!(cleared_session_cookie(true).contains("Max-Age=0; HttpOnly; Secure;"));
+    }
+}
@@ -5,9 +5,16 @@ use axum::{
 };
 use std::sync::Arc;
 
+use super::token_denylist::AccessTokenDenylist;
+use super::user_cache::{AuthUserCache, CachedAuthUser};
+use crate::domain::api_key::{hash_api_key, ApiKeyScope};
+use crate::domain::user::UserRole;
 use crate::infrastructure::config::Config;
+use crate::infrastructure::i18n::Locale;
 use crate::{
-    domain::auth::JwtManager, error::AppError, infrastructure::repositories::UserRepository,
+    domain::auth::JwtManager,
+    error::AppError,
+    infrastructure::repositories::{ApiKeyRepository, UserRepository, UserRepositoryApi},
 };
 use uuid::Uuid;
 
@@ -16,11 +23,24 @@ use uuid::Uuid;
 pub struct AuthUser {
     pub user_id: Uuid,
     pub email: String,
+    pub role: UserRole,
+    /// Key the request authenticated with, when it didn't use an access token
+    pub api_key_id: Option<Uuid>,
 }
 
-/// Authentication middleware
+/// What `auth_middleware` needs to authenticate a request
+pub type AuthState = (
+    Arc<UserRepository>,
+    Arc<JwtManager>,
+    Arc<AuthUserCache>,
+    Arc<AccessTokenDenylist>,
+    Arc<ApiKeyRepository>,
+);
+
+/// Authentication middleware. Accepts `Bearer <access token>`, or `ApiKey <key>` on the
+/// routes within one of the key's scopes.
 pub async fn auth_middleware(
-    State((user_repo, config)): State<(Arc<UserRepository>, Arc<Config>)>,
+    State((user_repo, jwt_manager, user_cache, token_denylist, api_key_repo)): State<AuthState>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
@@ -31,33 +51,104 @@ pub async fn auth_middleware(
         .and_then(|v| v.to_str().ok())
         .ok_or_else(|| AppError::Unauthorized("Missing authorization header".to_string()))?;
 
-    // Check Bearer token format
-    if !auth_header.starts_with("Bearer ") {
+    let (user_id, api_key_id) = if let Some(token) = auth_header.strip_prefix("Bearer ") {
+        let user_id = authenticate_access_token(token, &jwt_manager, &token_denylist).await?;
+        (user_id, None)
+    } else if let Some(key) = auth_header.strip_prefix("ApiKey ") {
+        let api_key = api_key_repo
+            .find_by_hash(&hash_api_key(key))
+            .await?
+            .ok_or_else(|| AppError::InvalidCredentials("Invalid API key".to_string()))?;
+        let allowed = ApiKeyScope::required_for(request.uri().path())
+            .is_some_and(|scope| api_key.allows(scope));
+        if !allowed {
+            return Err(AppError::Forbidden(
+                "API key not allowed for this endpoint".to_string(),
+            ));
+        }
+        (api_key.user_id, Some(api_key.id))
+    } else {
         return Err(AppError::Unauthorized(
             "Invalid authorization format".to_string(),
         ));
+    };
+
+    // Verify user exists, in the cache or else the database
+    let user = match user_cache.get(user_id).await {
+        Some(user) => user,
+        None => {
+            let user = user_repo
+                .find_by_id(user_id)
+                .await?
+                .ok_or_else(|| AppError::Unauthorized("User not found".to_string()))?;
+            let user = CachedAuthUser::from(&user);
+            user_cache.put(user_id, &user).await;
+            user
+        }
+    };
+
+    // Accounts past their deletion date stay locked until the purge job removes them
+    if user.is_deleted() {
+        return Err(AppError::Unauthorized(
+            "Account has been deleted".to_string(),
+        ));
     }
 
-    let token = &auth_header[7..]; // Skip "Bearer "
+    // Every log line from here on, down to the repositories, carries the user
+    let span = tracing::Span::current();
+    span.record("user_id", tracing::field::display(user_id));
+    span.record("tier", tracing::field::display(&user.tier));
+
+    // Add user context to request
+    request.extensions_mut().insert(AuthUser {
+        user_id,
+        email: user.email,
+        role: user.role,
+        api_key_id,
+    });
+
+    // Error messages fall back to the user's language when the client didn't ask for one
+    let mut response = next.run(request).await;
+    if let Some(locale) = user.language.as_deref().and_then(Locale::from_tag) {
+        response.extensions_mut().insert(locale);
+    }
 
-    // Validate JWT token
-    let jwt_manager = JwtManager::new(config.jwt_secret.clone(), config.jwt_expiration_hours);
+    Ok(response)
+}
 
+/// The user an access token was issued to, unless it was revoked since
+async fn authenticate_access_token(
+    token: &str,
+    jwt_manager: &JwtManager,
+    token_denylist: &AccessTokenDenylist,
+) -> Result<Uuid, AppError> {
     let claims = jwt_manager.validate_token(token)?;
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::Unauthorized("Invalid user ID in token".to_string()))?;
 
-    // Verify user exists in database
-    let user = user_repo
-        .find_by_id(user_id)
-        .await?
-        .ok_or_else(|| AppError::Unauthorized("User not found".to_string()))?;
+    // Sessions revoked since the token was issued
+    if token_denylist.is_revoked(user_id, claims.iat).await {
+        return Err(AppError::Unauthorized("Token has been revoked".to_string()));
+    }
 
-    // Add user context to request
-    request.extensions_mut().insert(AuthUser {
-        user_id: user.id,
-        email: user.email,
-    });
+    Ok(user_id)
+}
+
+/// Admin gate; must run after `auth_middleware` so the authenticated user is available
+pub async fn admin_middleware(
+    State(config): State<Arc<Config>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let auth_user = request
+        .extensions()
+        .get::<AuthUser>()
+        .ok_or_else(|| AppError::Unauthorized("Missing authenticated user".to_string()))?;
+
+    // ADMIN_EMAILS admits its accounts regardless of role, so there's always a first admin
+    if auth_user.role != UserRole::Admin && !config.is_admin(&auth_user.email) {
+        return Err(AppError::Forbidden("Admin access required".to_string()));
+    }
 
     Ok(next.run(request).await)
 }
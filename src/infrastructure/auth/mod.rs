@@ -1,5 +1,12 @@
+pub mod admin_session;
 pub mod middleware;
+pub mod oauth_state;
 pub mod request_id;
+pub mod token_denylist;
+pub mod user_cache;
 
-pub use middleware::{auth_middleware, AuthUser};
-pub use request_id::{request_id_middleware, RequestId};
+pub use admin_session::admin_session_middleware;
+pub use middleware::{admin_middleware, auth_middleware, AuthUser};
+pub use request_id::{current_request_id, request_id_middleware, PropagateRequestId, RequestId};
+pub use token_denylist::AccessTokenDenylist;
+pub use user_cache::{AuthUserCache, CachedAuthUser};
@@ -0,0 +1,51 @@
+//! Binds an OAuth sign-in to the browser that started it. The state sent to GitHub is
+//! also kept in a cookie, and the callback is only honoured when both agree, so a
+//! callback URL crafted by someone else can't sign a victim in to the attacker's account.
+
+use axum::http::HeaderMap;
+
+use super::admin_session::{cookie_value, secure_attribute};
+
+pub const OAUTH_STATE_COOKIE: &str = "feedtape_oauth_state";
+
+/// `Set-Cookie` value holding `state` for `max_age_secs`, sent back only to the callback.
+/// `SameSite=Lax` still lets it through on the top-level redirect back from GitHub.
+pub fn state_cookie(state: &str, max_age_secs: i64, secure: bool) -> String {
+    format!(
+        "{}={}; Path=/auth/callback; Max-Age={}; HttpOnly;{} SameSite=Lax",
+        OAUTH_STATE_COOKIE,
+        urlencoding::encode(state),
+        max_age_secs,
+        secure_attribute(secure)
+    )
+}
+
+/// Whether the browser making the callback is the one `state` was issued to
+pub fn state_matches_cookie(headers: &HeaderMap, state: &str) -> bool {
+    cookie_value(headers, OAUTH_STATE_COOKIE)
+        .and_then(|value| urlencoding::decode(value).ok())
+        .is_some_and(|value| value == state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{header, HeaderValue};
+
+    #[test]
+    fn test_state_cookie_round_trips() {
+        let cookie = state_cookie("web:abc", 600, true);
+        assert_eq!(
+            cookie,
+            "feedtape_oauth_state=web%3Aabc; Path=/auth/callback; Max-Age=600; HttpOnly; \
+             Secure; SameSite=Lax"
+        );
+
+        let mut headers = HeaderMap::new();
+        let value = cookie.split(';').next().unwrap();
+        headers.insert(header::COOKIE, HeaderValue::from_str(value).unwrap());
+        assert!(state_matches_cookie(&headers, "web:abc"));
+        assert!(!state_matches_cookie(&headers, "web:other"));
+        assert!(!state_matches_cookie(&HeaderMap::new(), "web:abc"));
+    }
+}
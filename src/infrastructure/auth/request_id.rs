@@ -1,20 +1,52 @@
-use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+use axum::{
+    extract::Request,
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
 use uuid::Uuid;
 
+use crate::error::envelope_error_response;
+use crate::infrastructure::i18n::Locale;
+
 pub const X_REQUEST_ID: &str = "x-request-id";
 
-/// Middleware to generate and attach request ID to each request
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// Middleware to generate and attach request ID to each request and its error responses
 pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
     // Generate a unique request ID
     let request_id = Uuid::new_v4().to_string();
 
+    // Error messages follow the client's language, if it asked for one we have
+    let requested_locale = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(Locale::from_accept_language);
+
     // Add request ID to request extensions for use in handlers
     request
         .extensions_mut()
         .insert(RequestId(request_id.clone()));
 
-    // Process the request
-    let mut response = next.run(request).await;
+    // Process the request; every failure leaves with the same error envelope. Logs and
+    // outbound calls made while handling it pick the id up from the span and task-local.
+    // The user fields are filled in by `auth_middleware` once the caller is known.
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        user_id = tracing::field::Empty,
+        tier = tracing::field::Empty,
+    );
+    let response = CURRENT_REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .instrument(span)
+        .await;
+    let mut response = envelope_error_response(response, &request_id, requested_locale).await;
 
     // Add request ID to response headers
     if let Ok(header_value) = HeaderValue::from_str(&request_id) {
@@ -27,3 +59,50 @@ pub async fn request_id_middleware(mut request: Request, next: Next) -> Response
 /// Request ID wrapper type for extension
 #[derive(Debug, Clone)]
 pub struct RequestId(pub String);
+
+/// Id of the request being handled by the current task. None in background jobs and in
+/// tasks spawned from a handler.
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Forward the current request id to another service
+pub trait PropagateRequestId {
+    fn with_request_id(self) -> Self;
+}
+
+impl PropagateRequestId for reqwest::RequestBuilder {
+    fn with_request_id(self) -> Self {
+        match current_request_id() {
+            Some(request_id) => self.header(X_REQUEST_ID, request_id),
+            None => self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_id_is_forwarded_only_inside_a_request() {
+        let client = reqwest::Client::new();
+        let build = || {
+            client
+                .get("http://localhost/")
+                .with_request_id()
+                .build()
+                .unwrap()
+        };
+
+        assert!(current_request_id().is_none());
+        assert!(build().headers().get(X_REQUEST_ID).is_none());
+
+        CURRENT_REQUEST_ID
+            .scope("req-1".to_string(), async {
+                assert_eq!(current_request_id().as_deref(), Some("req-1"));
+                assert_eq!(build().headers()[X_REQUEST_ID], "req-1");
+            })
+            .await;
+    }
+}
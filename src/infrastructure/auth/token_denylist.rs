@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::infrastructure::cache::CacheStore;
+
+/// Access tokens can't be revoked one by one, so revoking a user's sessions records
+/// when it happened and `auth_middleware` rejects their tokens issued until then. An
+/// entry only has to outlive the tokens it covers. Shared between instances when the
+/// cache store is Redis.
+pub struct AccessTokenDenylist {
+    store: Arc<dyn CacheStore>,
+    /// Lifetime of an access token
+    ttl: Duration,
+}
+
+impl AccessTokenDenylist {
+    pub fn new(store: Arc<dyn CacheStore>, access_token_lifetime: Duration) -> Self {
+        Self {
+            store,
+            ttl: access_token_lifetime,
+        }
+    }
+
+    /// Reject the user's access tokens issued up to `at`. Tokens carry whole seconds, so
+    /// this includes any issued later within the same second.
+    pub async fn revoke_issued_until(&self, user_id: Uuid, at: DateTime<Utc>) {
+        let entry = at.timestamp().to_string().into_bytes();
+        if let Err(e) = self
+            .store
+            .set(&denylist_key(user_id), entry, self.ttl)
+            .await
+        {
+            tracing::error!(
+                user_id = %user_id,
+                error = %e,
+                "Failed to deny access tokens; they stay valid until they expire"
+            );
+        }
+    }
+
+    /// Whether a token with this `iat` was revoked. A store that can't be read lets the
+    /// token through, like the rest of the cache does, rather than signing everyone out.
+    pub async fn is_revoked(&self, user_id: Uuid, issued_at: i64) -> bool {
+        match self.store.get(&denylist_key(user_id)).await {
+            Ok(entry) => entry
+                .and_then(|entry| String::from_utf8(entry).ok())
+                .and_then(|cutoff| cutoff.parse::<i64>().ok())
+                .is_some_and(|cutoff| issued_at <= cutoff),
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, error = %e, "Failed to read the token denylist");
+                false
+            }
+        }
+    }
+}
+
+fn denylist_key(user_id: Uuid) -> String {
+    format!("access_tokens_revoked:{}", user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::cache::MemoryCacheStore;
+
+    #[tokio::test]
+    async fn test_only_tokens_issued_up_to_the_revocation_are_denied() {
+        let denylist =
+            AccessTokenDenylist::new(Arc::new(MemoryCacheStore::new()), Duration::from_secs(3600));
+        let user_id = Uuid::new_v4();
+        let revoked_at = Utc::now();
+
+        assert!(
+            !denylist
+                .is_revoked(user_id, revoked_at.timestamp() - 60)
+                .await
+        );
+
+        denylist.revoke_issued_until(user_id, revoked_at).await;
+
+        assert!(
+            denylist
+                .is_revoked(user_id, revoked_at.timestamp() - 60)
+                .await
+        );
+        assert!(denylist.is_revoked(user_id, revoked_at.timestamp()).await);
+        assert!(
+            !denylist
+                .is_revoked(user_id, revoked_at.timestamp() + 1)
+                .await
+        );
+        assert!(
+            !denylist
+                .is_revoked(Uuid::new_v4(), revoked_at.timestamp() - 60)
+                .await
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::user::{SubscriptionTier, User, UserRole};
+use crate::infrastructure::cache::CacheStore;
+
+/// The part of a user `auth_middleware` needs, kept so most requests skip the lookup
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedAuthUser {
+    pub email: String,
+    /// Effective tier when the entry was stored; only used to annotate logs
+    pub tier: SubscriptionTier,
+    /// Kept so an account still locks at its deletion date while cached
+    pub deletion_scheduled_at: Option<DateTime<Utc>>,
+    /// Language from the user's settings, for translating error messages
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub role: UserRole,
+}
+
+impl CachedAuthUser {
+    pub fn is_deleted(&self) -> bool {
+        self.deletion_scheduled_at
+            .is_some_and(|scheduled_at| scheduled_at <= Utc::now())
+    }
+}
+
+impl From<&User> for CachedAuthUser {
+    fn from(user: &User) -> Self {
+        Self {
+            email: user.email.clone(),
+            tier: user.effective_tier(),
+            deletion_scheduled_at: user.deletion_scheduled_at,
+            language: user.language().map(str::to_string),
+            role: user.role,
+        }
+    }
+}
+
+/// Short-lived copy of authenticated users, keyed by id. Services that change a user's
+/// tier, role or deletion date invalidate the entry; anything else, like a family owner's
+/// tier reaching the members, shows up once the entry expires.
+pub struct AuthUserCache {
+    /// None when disabled
+    store: Option<Arc<dyn CacheStore>>,
+    ttl: Duration,
+}
+
+impl AuthUserCache {
+    /// A zero `ttl` disables the cache
+    pub fn new(store: Arc<dyn CacheStore>, ttl: Duration) -> Self {
+        Self {
+            store: (!ttl.is_zero()).then_some(store),
+            ttl,
+        }
+    }
+
+    /// Every request looks the user up
+    pub fn disabled() -> Self {
+        Self {
+            store: None,
+            ttl: Duration::ZERO,
+        }
+    }
+
+    /// Cache failures only cost the lookup, so they are logged rather than returned
+    pub async fn get(&self, user_id: Uuid) -> Option<CachedAuthUser> {
+        let store = self.store.as_ref()?;
+        match store.get(&cache_key(user_id)).await {
+            Ok(entry) => entry.and_then(|entry| serde_json::from_slice(&entry).ok()),
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, error = %e, "Failed to read cached user");
+                None
+            }
+        }
+    }
+
+    pub async fn put(&self, user_id: Uuid, user: &CachedAuthUser) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let Ok(entry) = serde_json::to_vec(user) else {
+            return;
+        };
+        if let Err(e) = store.set(&cache_key(user_id), entry, self.ttl).await {
+            tracing::warn!(user_id = %user_id, error = %e, "Failed to cache user");
+        }
+    }
+
+    /// Drop the entry after changing anything it holds, so the next request reads it again
+    pub async fn invalidate(&self, user_id: Uuid) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        if let Err(e) = store.delete(&cache_key(user_id)).await {
+            tracing::warn!(user_id = %user_id, error = %e, "Failed to invalidate cached user");
+        }
+    }
+}
+
+fn cache_key(user_id: Uuid) -> String {
+    format!("auth_user:{}", user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::cache::MemoryCacheStore;
+
+    fn cached_user() -> CachedAuthUser {
+        CachedAuthUser {
+            email: "user@example.com".to_string(),
+            tier: SubscriptionTier::Pro,
+            deletion_scheduled_at: None,
+            language: Some("es".to_string()),
+            role: UserRole::User,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_put_get_and_invalidate() {
+        let cache = AuthUserCache::new(Arc::new(MemoryCacheStore::new()), Duration::from_secs(60));
+        let user_id = Uuid::new_v4();
+
+        assert_eq!(cache.get(user_id).await, None);
+        cache.put(user_id, &cached_user()).await;
+        assert_eq!(cache.get(user_id).await, Some(cached_user()));
+
+        cache.invalidate(user_id).await;
+        assert_eq!(cache.get(user_id).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_zero_ttl_disables_the_cache() {
+        let cache = AuthUserCache::new(Arc::new(MemoryCacheStore::new()), Duration::ZERO);
+        let user_id = Uuid::new_v4();
+
+        cache.put(user_id, &cached_user()).await;
+        assert_eq!(cache.get(user_id).await, None);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::error::{AppError, AppResult};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+/// SHA-256 fingerprint of the DER encoding of Apple Root CA - G3, which anchors every
+/// App Store signing chain
+const APPLE_ROOT_CA_G3_SHA256: &str =
+    "63343abfb89a6a03ebb57e9b3f5fa7be7c4f5c756f3017b3a8c488c3653e9179";
+/// Marker extension Apple puts on App Store receipt signing (leaf) certificates
+const APP_STORE_SIGNING_OID: &str = "1.2.840.113635.100.6.11.1";
+
+/// Decoded App Store Server Notification V2
+#[derive(Debug, Clone)]
+pub struct AppStoreNotification {
+    pub notification_uuid: String,
+    pub notification_type: String,
+    pub subtype: Option<String>,
+    pub transaction: Option<AppStoreTransaction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NotificationPayload {
+    notification_type: String,
+    #[serde(default)]
+    subtype: Option<String>,
+    #[serde(rename = "notificationUUID")]
+    notification_uuid: String,
+    #[serde(default)]
+    data: Option<NotificationData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NotificationData {
+    #[serde(default)]
+    bundle_id: Option<String>,
+    #[serde(default)]
+    signed_transaction_info: Option<String>,
+}
+
+/// Decoded `JWSTransactionDecodedPayload`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStoreTransaction {
+    pub original_transaction_id: String,
+    pub product_id: String,
+    /// Milliseconds since the epoch
+    #[serde(default)]
+    pub expires_date: Option<i64>,
+    /// UUID the app attached to the purchase; the app sets it to the user's id
+    #[serde(default)]
+    pub app_account_token: Option<Uuid>,
+}
+
+impl AppStoreTransaction {
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_date.and_then(DateTime::from_timestamp_millis)
+    }
+}
+
+/// Verifies the JWS-signed notifications Apple sends to our webhook
+pub struct AppStoreNotificationVerifier {
+    bundle_id: String,
+}
+
+impl AppStoreNotificationVerifier {
+    pub fn new(bundle_id: String) -> Self {
+        Self { bundle_id }
+    }
+
+    /// Verify `signedPayload` and the transaction it carries
+    pub fn verify_notification(&self, signed_payload: &str) -> AppResult<AppStoreNotification> {
+        let payload: NotificationPayload = verify_jws(signed_payload)?;
+        let data = payload.data.unwrap_or(NotificationData {
+            bundle_id: None,
+            signed_transaction_info: None,
+        });
+
+        if let Some(bundle_id) = data.bundle_id.as_deref() {
+            if bundle_id != self.bundle_id {
+                return Err(AppError::BadRequest(format!(
+                    "Notification is for another app: {}",
+                    bundle_id
+                )));
+            }
+        }
+
+        let transaction = data
+            .signed_transaction_info
+            .as_deref()
+            .map(verify_jws::<AppStoreTransaction>)
+            .transpose()?;
+
+        Ok(AppStoreNotification {
+            notification_uuid: payload.notification_uuid,
+            notification_type: payload.notification_type,
+            subtype: payload.subtype,
+            transaction,
+        })
+    }
+}
+
+/// Verify an App Store JWS: the x5c chain must end at Apple Root CA - G3 and the
+/// payload must be signed by the leaf certificate
+fn verify_jws<T: DeserializeOwned>(token: &str) -> AppResult<T> {
+    let invalid =
+        |reason: &str| AppError::BadRequest(format!("Invalid App Store signature: {}", reason));
+
+    let header = jsonwebtoken::decode_header(token).map_err(|_| invalid("malformed JWS"))?;
+    if header.alg != Algorithm::ES256 {
+        return Err(invalid("unexpected algorithm"));
+    }
+
+    let chain_der = header
+        .x5c
+        .ok_or_else(|| invalid("missing certificate chain"))?
+        .iter()
+        .map(|cert| STANDARD.decode(cert))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid("malformed certificate"))?;
+
+    if chain_der.len() != 3 {
+        return Err(invalid("unexpected certificate chain length"));
+    }
+
+    let root_fingerprint = hex::encode(Sha256::digest(&chain_der[2]));
+    if root_fingerprint != APPLE_ROOT_CA_G3_SHA256 {
+        return Err(invalid("untrusted root certificate"));
+    }
+
+    let chain = chain_der
+        .iter()
+        .map(|der| X509Certificate::from_der(der).map(|(_, cert)| cert))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid("malformed certificate"))?;
+
+    // Each certificate must be signed by the next one, and be currently valid
+    for (index, cert) in chain.iter().enumerate() {
+        let issuer = chain.get(index + 1).unwrap_or(cert);
+        cert.verify_signature(Some(issuer.public_key()))
+            .map_err(|_| invalid("broken certificate chain"))?;
+        if !cert.validity().is_valid() {
+            return Err(invalid("expired certificate"));
+        }
+    }
+
+    let leaf = &chain[0];
+    let is_app_store_leaf = leaf
+        .extensions()
+        .iter()
+        .any(|extension| extension.oid.to_id_string() == APP_STORE_SIGNING_OID);
+    if !is_app_store_leaf {
+        return Err(invalid("not an App Store signing certificate"));
+    }
+
+    // ring expects the raw EC point, which is the SPKI bit string content
+    let key = DecodingKey::from_ec_der(&leaf.public_key().subject_public_key.data);
+    let mut validation = Validation::new(Algorithm::ES256);
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+
+    jsonwebtoken::decode::<T>(token, &key, &validation)
+        .map(|data| data.claims)
+        .map_err(|_| invalid("signature mismatch"))
+}
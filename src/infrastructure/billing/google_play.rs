@@ -0,0 +1,276 @@
+use crate::error::{AppError, AppResult};
+use crate::infrastructure::auth::PropagateRequestId;
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+const ANDROID_PUBLISHER_API_URL: &str =
+    "https://androidpublisher.googleapis.com/androidpublisher/v3/applications";
+const ANDROID_PUBLISHER_SCOPE: &str = "https://www.googleapis.com/auth/androidpublisher";
+const DEFAULT_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+const JWT_BEARER_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+const ASSERTION_LIFETIME_SECS: i64 = 3600;
+/// Refresh the access token this long before Google says it expires
+const TOKEN_EXPIRY_MARGIN_SECS: i64 = 60;
+
+pub const SUBSCRIPTION_STATE_ACTIVE: &str = "SUBSCRIPTION_STATE_ACTIVE";
+pub const SUBSCRIPTION_STATE_IN_GRACE_PERIOD: &str = "SUBSCRIPTION_STATE_IN_GRACE_PERIOD";
+pub const SUBSCRIPTION_STATE_CANCELED: &str = "SUBSCRIPTION_STATE_CANCELED";
+pub const ACKNOWLEDGEMENT_STATE_PENDING: &str = "ACKNOWLEDGEMENT_STATE_PENDING";
+
+/// Fields of a Google Cloud service account key file used for server-to-server auth
+#[derive(Debug, Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    #[serde(default)]
+    token_uri: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct AssertionClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccessTokenResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+/// Subset of the `SubscriptionPurchaseV2` resource returned by the Play Developer API
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePlaySubscription {
+    pub subscription_state: String,
+    #[serde(default)]
+    pub acknowledgement_state: Option<String>,
+    #[serde(default)]
+    pub line_items: Vec<GooglePlayLineItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePlayLineItem {
+    pub product_id: String,
+    #[serde(default)]
+    pub expiry_time: Option<DateTime<Utc>>,
+}
+
+impl GooglePlaySubscription {
+    /// Latest expiry across all line items
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.line_items
+            .iter()
+            .filter_map(|item| item.expiry_time)
+            .max()
+    }
+
+    /// Product of the line item that expires last
+    pub fn product_id(&self) -> Option<&str> {
+        self.line_items
+            .iter()
+            .max_by_key(|item| item.expiry_time)
+            .map(|item| item.product_id.as_str())
+    }
+
+    pub fn needs_acknowledgement(&self) -> bool {
+        self.acknowledgement_state.as_deref() == Some(ACKNOWLEDGEMENT_STATE_PENDING)
+    }
+}
+
+struct CachedToken {
+    access_token: String,
+    expires_at: DateTime<Utc>,
+}
+
+pub struct GooglePlayClient {
+    package_name: String,
+    service_account: ServiceAccountKey,
+    encoding_key: EncodingKey,
+    http_client: reqwest::Client,
+    access_token: Mutex<Option<CachedToken>>,
+}
+
+impl GooglePlayClient {
+    /// Build a client from the app's package name and the JSON contents of a service
+    /// account key with access to the Play Console
+    pub fn new(package_name: String, service_account_key_json: &str) -> AppResult<Self> {
+        let service_account: ServiceAccountKey = serde_json::from_str(service_account_key_json)
+            .map_err(|e| {
+                AppError::Internal(format!("Invalid Google service account key: {}", e))
+            })?;
+
+        let encoding_key = EncodingKey::from_rsa_pem(service_account.private_key.as_bytes())
+            .map_err(|e| {
+                AppError::Internal(format!("Invalid Google service account private key: {}", e))
+            })?;
+
+        Ok(Self {
+            package_name,
+            service_account,
+            encoding_key,
+            http_client: reqwest::Client::new(),
+            access_token: Mutex::new(None),
+        })
+    }
+
+    /// Look up a subscription purchase by its token
+    pub async fn get_subscription(
+        &self,
+        purchase_token: &str,
+    ) -> AppResult<GooglePlaySubscription> {
+        let access_token = self.access_token().await?;
+        let url = format!(
+            "{}/{}/purchases/subscriptionsv2/tokens/{}",
+            ANDROID_PUBLISHER_API_URL, self.package_name, purchase_token
+        );
+
+        let response = self
+            .http_client
+            .get(&url)
+            .bearer_auth(access_token)
+            .with_request_id()
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::ExternalService(format!("Google Play purchase lookup failed: {}", e))
+            })?;
+
+        // The API answers 400/404/410 for tokens it doesn't know or no longer tracks
+        if response.status().is_client_error() {
+            return Err(AppError::BadRequest("Invalid purchase token".to_string()));
+        }
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AppError::ExternalService(format!(
+                "Google Play purchase lookup failed: {}",
+                error_text
+            )));
+        }
+
+        response
+            .json::<GooglePlaySubscription>()
+            .await
+            .map_err(|e| {
+                AppError::ExternalService(format!("Failed to parse Google Play purchase: {}", e))
+            })
+    }
+
+    /// Acknowledge a subscription purchase. Google refunds purchases that are not
+    /// acknowledged within three days.
+    pub async fn acknowledge_subscription(
+        &self,
+        product_id: &str,
+        purchase_token: &str,
+    ) -> AppResult<()> {
+        let access_token = self.access_token().await?;
+        let url = format!(
+            "{}/{}/purchases/subscriptions/{}/tokens/{}:acknowledge",
+            ANDROID_PUBLISHER_API_URL, self.package_name, product_id, purchase_token
+        );
+
+        let response = self
+            .http_client
+            .post(&url)
+            .bearer_auth(access_token)
+            .json(&serde_json::json!({}))
+            .with_request_id()
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::ExternalService(format!("Google Play acknowledgement failed: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AppError::ExternalService(format!(
+                "Google Play acknowledgement failed: {}",
+                error_text
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Return a cached OAuth access token, exchanging a signed assertion for a new one
+    /// when it is missing or about to expire
+    async fn access_token(&self) -> AppResult<String> {
+        let mut cached = self.access_token.lock().await;
+        let now = Utc::now();
+
+        if let Some(token) = cached.as_ref() {
+            if token.expires_at > now {
+                return Ok(token.access_token.clone());
+            }
+        }
+
+        let token_uri = self
+            .service_account
+            .token_uri
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKEN_URI);
+
+        let claims = AssertionClaims {
+            iss: &self.service_account.client_email,
+            scope: ANDROID_PUBLISHER_SCOPE,
+            aud: token_uri,
+            iat: now.timestamp(),
+            exp: now.timestamp() + ASSERTION_LIFETIME_SECS,
+        };
+        let assertion =
+            jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.encoding_key)
+                .map_err(|e| {
+                    AppError::Internal(format!("Failed to sign Google assertion: {}", e))
+                })?;
+
+        let response = self
+            .http_client
+            .post(token_uri)
+            .form(&[
+                ("grant_type", JWT_BEARER_GRANT_TYPE),
+                ("assertion", assertion.as_str()),
+            ])
+            .with_request_id()
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::ExternalService(format!("Google token exchange failed: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AppError::ExternalService(format!(
+                "Google token exchange failed: {}",
+                error_text
+            )));
+        }
+
+        let token = response.json::<AccessTokenResponse>().await.map_err(|e| {
+            AppError::ExternalService(format!("Failed to parse Google token: {}", e))
+        })?;
+
+        let access_token = token.access_token.clone();
+        *cached = Some(CachedToken {
+            access_token: token.access_token,
+            expires_at: now + Duration::seconds(token.expires_in - TOKEN_EXPIRY_MARGIN_SECS),
+        });
+
+        Ok(access_token)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod app_store;
+pub mod google_play;
+pub mod stripe;
+
+pub use app_store::{AppStoreNotification, AppStoreNotificationVerifier, AppStoreTransaction};
+pub use google_play::{GooglePlayClient, GooglePlaySubscription};
+pub use stripe::{StripeClient, StripeEvent, StripeSubscription};
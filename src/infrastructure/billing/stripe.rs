@@ -0,0 +1,251 @@
+use crate::error::{AppError, AppResult};
+use crate::infrastructure::auth::PropagateRequestId;
+use crate::infrastructure::signature;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+const STRIPE_API_URL: &str = "https://api.stripe.com/v1";
+/// Reject webhook deliveries signed longer ago than this, to limit replays
+const WEBHOOK_TOLERANCE_SECS: i64 = 300;
+
+/// Metadata key carrying our user id on Stripe objects
+pub const USER_ID_METADATA_KEY: &str = "user_id";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeCheckoutSession {
+    pub id: String,
+    pub url: Option<String>,
+}
+
+/// A Stripe webhook event. `data.object` is kept as raw JSON because its shape depends
+/// on the event type.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub data: StripeEventData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeEventData {
+    pub object: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeSubscription {
+    pub id: String,
+    pub status: String,
+    #[serde(default)]
+    pub cancel_at_period_end: bool,
+    #[serde(default)]
+    pub current_period_end: Option<i64>,
+    #[serde(default)]
+    pub metadata: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    pub items: StripeSubscriptionItems,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StripeSubscriptionItems {
+    #[serde(default)]
+    pub data: Vec<StripeSubscriptionItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeSubscriptionItem {
+    pub price: StripePrice,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripePrice {
+    pub id: String,
+}
+
+impl StripeSubscription {
+    pub fn current_period_end(&self) -> Option<DateTime<Utc>> {
+        self.current_period_end
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+    }
+
+    pub fn user_id(&self) -> Option<Uuid> {
+        self.metadata
+            .get(USER_ID_METADATA_KEY)
+            .and_then(|id| Uuid::parse_str(id).ok())
+    }
+
+    pub fn price_id(&self) -> Option<&str> {
+        self.items.data.first().map(|item| item.price.id.as_str())
+    }
+}
+
+pub struct StripeClient {
+    secret_key: String,
+    webhook_secret: String,
+    price_id: String,
+    success_url: String,
+    cancel_url: String,
+    http_client: reqwest::Client,
+}
+
+impl StripeClient {
+    pub fn new(
+        secret_key: String,
+        webhook_secret: String,
+        price_id: String,
+        success_url: String,
+        cancel_url: String,
+    ) -> Self {
+        Self {
+            secret_key,
+            webhook_secret,
+            price_id,
+            success_url,
+            cancel_url,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Create a Checkout session for the Pro subscription. The user id is attached to
+    /// both the session and the resulting subscription so webhooks can be attributed.
+    pub async fn create_checkout_session(
+        &self,
+        user_id: Uuid,
+        email: &str,
+    ) -> AppResult<StripeCheckoutSession> {
+        let user_id = user_id.to_string();
+        let params = [
+            ("mode", "subscription"),
+            ("line_items[0][price]", self.price_id.as_str()),
+            ("line_items[0][quantity]", "1"),
+            ("success_url", self.success_url.as_str()),
+            ("cancel_url", self.cancel_url.as_str()),
+            ("client_reference_id", user_id.as_str()),
+            ("customer_email", email),
+            ("subscription_data[metadata][user_id]", user_id.as_str()),
+        ];
+
+        let response = self
+            .http_client
+            .post(format!("{}/checkout/sessions", STRIPE_API_URL))
+            .basic_auth(&self.secret_key, None::<&str>)
+            .form(&params)
+            .with_request_id()
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::ExternalService(format!("Stripe checkout session failed: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AppError::ExternalService(format!(
+                "Stripe checkout session failed: {}",
+                error_text
+            )));
+        }
+
+        response.json::<StripeCheckoutSession>().await.map_err(|e| {
+            AppError::ExternalService(format!("Failed to parse Stripe checkout session: {}", e))
+        })
+    }
+
+    /// Fetch the current state of a subscription
+    pub async fn get_subscription(&self, subscription_id: &str) -> AppResult<StripeSubscription> {
+        let response = self
+            .http_client
+            .get(format!(
+                "{}/subscriptions/{}",
+                STRIPE_API_URL,
+                urlencoding::encode(subscription_id)
+            ))
+            .basic_auth(&self.secret_key, None::<&str>)
+            .with_request_id()
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::ExternalService(format!("Stripe subscription lookup failed: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AppError::ExternalService(format!(
+                "Stripe subscription lookup failed: {}",
+                error_text
+            )));
+        }
+
+        response.json::<StripeSubscription>().await.map_err(|e| {
+            AppError::ExternalService(format!("Failed to parse Stripe subscription: {}", e))
+        })
+    }
+
+    /// Verify the `Stripe-Signature` header of a webhook delivery and parse the event
+    pub fn construct_event(
+        &self,
+        payload: &[u8],
+        signature_header: &str,
+    ) -> AppResult<StripeEvent> {
+        verify_signature(
+            &self.webhook_secret,
+            payload,
+            signature_header,
+            Utc::now().timestamp(),
+        )?;
+
+        serde_json::from_slice(payload)
+            .map_err(|e| AppError::BadRequest(format!("Invalid Stripe event: {}", e)))
+    }
+}
+
+fn verify_signature(
+    webhook_secret: &str,
+    payload: &[u8],
+    signature_header: &str,
+    now: i64,
+) -> AppResult<()> {
+    signature::verify_signature(
+        webhook_secret,
+        signature_header,
+        payload,
+        WEBHOOK_TOLERANCE_SECS,
+        now,
+    )
+    .map(|_| ())
+    .map_err(|_| AppError::BadRequest("Invalid Stripe signature".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepts_valid_signature() {
+        let payload = br#"{"id":"evt_1"}"#;
+        let header = signature::sign("whsec_test", 1000, payload);
+
+        assert!(verify_signature("whsec_test", payload, &header, 1010).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_tampered_payload() {
+        let header = signature::sign("whsec_test", 1000, b"original");
+
+        assert!(verify_signature("whsec_test", b"tampered", &header, 1010).is_err());
+    }
+
+    #[test]
+    fn test_rejects_stale_timestamp() {
+        let payload = b"{}";
+        let header = signature::sign("whsec_test", 1000, payload);
+
+        assert!(verify_signature("whsec_test", payload, &header, 1000 + 301).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use async_trait::async_trait;
+use moka::{future::Cache, Expiry};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::CacheStore;
+use crate::error::AppResult;
+
+/// Upper bound on the bytes held in memory across all entries
+const MAX_CAPACITY_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(Clone)]
+struct CachedValue {
+    value: Arc<Vec<u8>>,
+    /// None keeps the expiry the entry already had
+    ttl: Option<Duration>,
+}
+
+struct CachedValueExpiry;
+
+impl Expiry<String, CachedValue> for CachedValueExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        entry: &CachedValue,
+        _now: Instant,
+    ) -> Option<Duration> {
+        entry.ttl
+    }
+
+    fn expire_after_update(
+        &self,
+        _key: &String,
+        entry: &CachedValue,
+        _now: Instant,
+        current: Option<Duration>,
+    ) -> Option<Duration> {
+        entry.ttl.or(current)
+    }
+}
+
+/// Cache kept in this process, for single-instance deployments and development
+pub struct MemoryCacheStore {
+    cache: Cache<String, CachedValue>,
+}
+
+impl MemoryCacheStore {
+    pub fn new() -> Self {
+        let cache = Cache::builder()
+            .max_capacity(MAX_CAPACITY_BYTES)
+            .weigher(|key: &String, entry: &CachedValue| {
+                (key.len() + entry.value.len())
+                    .try_into()
+                    .unwrap_or(u32::MAX)
+            })
+            .expire_after(CachedValueExpiry)
+            .build();
+
+        Self { cache }
+    }
+}
+
+impl Default for MemoryCacheStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CacheStore for MemoryCacheStore {
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        Ok(self
+            .cache
+            .get(key)
+            .await
+            .map(|entry| entry.value.as_ref().clone()))
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> AppResult<()> {
+        let entry = CachedValue {
+            value: Arc::new(value),
+            ttl: Some(ttl),
+        };
+        self.cache.insert(key.to_string(), entry).await;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        self.cache.invalidate(key).await;
+        Ok(())
+    }
+
+    async fn increment(&self, key: &str, ttl: Duration) -> AppResult<i64> {
+        let entry = self
+            .cache
+            .entry(key.to_string())
+            .and_upsert_with(|existing| {
+                let (count, ttl) = match existing {
+                    Some(existing) => (parse_counter(&existing.into_value().value) + 1, None),
+                    None => (1, Some(ttl)),
+                };
+                std::future::ready(CachedValue {
+                    value: Arc::new(count.to_string().into_bytes()),
+                    ttl,
+                })
+            })
+            .await;
+
+        Ok(parse_counter(&entry.into_value().value))
+    }
+}
+
+/// Counters are stored as decimal text, like Redis does
+fn parse_counter(value: &[u8]) -> i64 {
+    std::str::from_utf8(value)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_get_and_delete() {
+        let store = MemoryCacheStore::new();
+        let ttl = Duration::from_secs(60);
+
+        assert_eq!(store.get("key").await.unwrap(), None);
+        store.set("key", b"value".to_vec(), ttl).await.unwrap();
+        assert_eq!(store.get("key").await.unwrap(), Some(b"value".to_vec()));
+
+        store.delete("key").await.unwrap();
+        assert_eq!(store.get("key").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_increment_counts_from_one() {
+        let store = MemoryCacheStore::new();
+        let ttl = Duration::from_secs(60);
+
+        assert_eq!(store.increment("hits", ttl).await.unwrap(), 1);
+        assert_eq!(store.increment("hits", ttl).await.unwrap(), 2);
+        assert_eq!(store.get("hits").await.unwrap(), Some(b"2".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_entries_expire() {
+        let store = MemoryCacheStore::new();
+
+        store
+            .set("key", b"value".to_vec(), Duration::from_millis(10))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(store.get("key").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,36 @@
+pub mod memory_store;
+pub mod redis_store;
+
+pub use memory_store::MemoryCacheStore;
+pub use redis_store::RedisCacheStore;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::AppResult;
+use crate::infrastructure::config::{CacheBackend, Config};
+
+/// Key-value cache shared by everything that keeps short-lived state. The Redis store
+/// lets several instances see the same entries; the in-memory one is per process.
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>>;
+
+    /// Store `value` under `key`, replacing any previous entry and its expiry
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> AppResult<()>;
+
+    async fn delete(&self, key: &str) -> AppResult<()>;
+
+    /// Add one to the counter at `key` and return the new count. `ttl` only applies
+    /// when the counter is created, so it counts over a fixed window.
+    async fn increment(&self, key: &str, ttl: Duration) -> AppResult<i64>;
+}
+
+/// Build the cache store selected by `CACHE_BACKEND`
+pub async fn create_cache_store(config: &Config) -> AppResult<Arc<dyn CacheStore>> {
+    match &config.cache_backend {
+        CacheBackend::Memory => Ok(Arc::new(MemoryCacheStore::new())),
+        CacheBackend::Redis { url } => Ok(Arc::new(RedisCacheStore::connect(url).await?)),
+    }
+}
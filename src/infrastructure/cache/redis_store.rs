@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use redis::{aio::ConnectionManager, AsyncCommands};
+use std::time::Duration;
+
+use super::CacheStore;
+use crate::error::{AppError, AppResult};
+
+/// Cache shared by every instance through Redis
+pub struct RedisCacheStore {
+    connection: ConnectionManager,
+}
+
+impl RedisCacheStore {
+    pub async fn connect(url: &str) -> AppResult<Self> {
+        let client = redis::Client::open(url).map_err(redis_error)?;
+        let connection = ConnectionManager::new(client).await.map_err(redis_error)?;
+
+        tracing::info!("Connected to Redis cache");
+
+        Ok(Self { connection })
+    }
+}
+
+#[async_trait]
+impl CacheStore for RedisCacheStore {
+    async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        let mut connection = self.connection.clone();
+        connection.get(key).await.map_err(redis_error)
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl: Duration) -> AppResult<()> {
+        let mut connection = self.connection.clone();
+        connection
+            .pset_ex(key, value, ttl_millis(ttl))
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        let mut connection = self.connection.clone();
+        connection.del(key).await.map_err(redis_error)
+    }
+
+    async fn increment(&self, key: &str, ttl: Duration) -> AppResult<i64> {
+        let mut connection = self.connection.clone();
+        let count: i64 = connection.incr(key, 1).await.map_err(redis_error)?;
+        if count == 1 {
+            connection
+                .pexpire::<_, ()>(key, ttl_millis(ttl) as i64)
+                .await
+                .map_err(redis_error)?;
+        }
+        Ok(count)
+    }
+}
+
+/// Redis rejects a zero expiry, so sub-millisecond TTLs round up
+fn ttl_millis(ttl: Duration) -> u64 {
+    (ttl.as_millis() as u64).max(1)
+}
+
+fn redis_error(e: redis::RedisError) -> AppError {
+    AppError::ExternalService(format!("Redis: {}", e))
+}
@@ -0,0 +1,126 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+
+use super::ConfigError;
+
+/// Load the file named by `CONFIG_FILE` into the environment, below everything that is
+/// already set.
+///
+/// Keys are the environment variable names in any case. Tables nest with `_`, so
+/// `[github] client_id = "..."` sets `GITHUB_CLIENT_ID`, and lists are joined with
+/// commas. `.toml`, `.yaml` and `.yml` files are supported.
+pub(super) fn load_config_file() -> Result<(), ConfigError> {
+    let Ok(path) = env::var("CONFIG_FILE") else {
+        return Ok(());
+    };
+    let error = |message: String| ConfigError {
+        var_name: "CONFIG_FILE".to_string(),
+        message,
+    };
+
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| error(format!("failed to read '{}': {}", path, e)))?;
+    let extension = Path::new(&path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    let document: Value = match extension {
+        "toml" => toml::from_str(&contents).map_err(|e| e.to_string()),
+        "yaml" | "yml" => serde_yaml::from_str(&contents).map_err(|e| e.to_string()),
+        _ => Err("expected a .toml, .yaml or .yml file".to_string()),
+    }
+    .map_err(|e| error(format!("failed to parse '{}': {}", path, e)))?;
+
+    let mut values = HashMap::new();
+    flatten("", &document, &mut values);
+
+    for (name, value) in values {
+        if env::var_os(&name).is_none() {
+            env::set_var(name, value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Turn a parsed config document into environment variable names and values
+fn flatten(prefix: &str, value: &Value, out: &mut HashMap<String, String>) {
+    match value {
+        Value::Object(table) => {
+            for (key, value) in table {
+                let name = if prefix.is_empty() {
+                    key.to_uppercase()
+                } else {
+                    format!("{}_{}", prefix, key.to_uppercase())
+                };
+                flatten(&name, value, out);
+            }
+        }
+        Value::Array(items) => {
+            let joined = items
+                .iter()
+                .map(scalar_to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            out.insert(prefix.to_string(), joined);
+        }
+        Value::Null => {}
+        scalar => {
+            out.insert(prefix.to_string(), scalar_to_string(scalar));
+        }
+    }
+}
+
+fn scalar_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flatten_toml() {
+        let document: Value = toml::from_str(
+            r#"
+            database_url = "postgres://localhost/feedtape"
+            port = 8080
+            tts_cache_enabled = true
+            admin_emails = ["a@example.com", "b@example.com"]
+
+            [github]
+            client_id = "id"
+            "#,
+        )
+        .unwrap();
+
+        let mut values = HashMap::new();
+        flatten("", &document, &mut values);
+
+        assert_eq!(values["DATABASE_URL"], "postgres://localhost/feedtape");
+        assert_eq!(values["PORT"], "8080");
+        assert_eq!(values["TTS_CACHE_ENABLED"], "true");
+        assert_eq!(values["ADMIN_EMAILS"], "a@example.com,b@example.com");
+        assert_eq!(values["GITHUB_CLIENT_ID"], "id");
+    }
+
+    #[test]
+    fn test_flatten_yaml() {
+        let document: Value = serde_yaml::from_str(
+            "jwt_secret: secret\nstripe:\n  price_id: price_123\napple_bundle_id: null\n",
+        )
+        .unwrap();
+
+        let mut values = HashMap::new();
+        flatten("", &document, &mut values);
+
+        assert_eq!(values["JWT_SECRET"], "secret");
+        assert_eq!(values["STRIPE_PRICE_ID"], "price_123");
+        assert!(!values.contains_key("APPLE_BUNDLE_ID"));
+    }
+}
@@ -1,6 +1,43 @@
+mod file;
+mod secrets;
+
+use crate::domain::auth::jwt::RsaKey;
+use crate::domain::plan::{PlanLimits, TierLimits};
+use crate::infrastructure::audio_cdn::CloudFrontSigner;
+use crate::infrastructure::oauth::github::{GITHUB_API_BASE_URL, GITHUB_OAUTH_BASE_URL};
+use ipnet::IpNet;
+use reqwest::Url;
 use serde::Deserialize;
 use std::env;
 use std::fmt;
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+const DEFAULT_AWS_REGION: &str = "eu-west-1";
+const DEFAULT_MAINTENANCE_INTERVAL_SECS: u64 = 60 * 60;
+const DEFAULT_USAGE_RETENTION_DAYS: i64 = 365;
+const MIN_USAGE_RETENTION_DAYS: i64 = 62;
+const DEFAULT_ARTICLE_RETENTION_DAYS: i64 = 1;
+const DEFAULT_CACHED_AUDIO_RETENTION_DAYS: i64 = 1;
+const MIN_JWT_SECRET_BYTES: usize = 32;
+const MIN_GRPC_API_KEY_BYTES: usize = 32;
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 256;
+const DEFAULT_MAX_CONCURRENT_SYNTHESES: usize = 32;
+const DEFAULT_ACCESS_LOG_SAMPLED_PATHS: &str = "/health,/api/usage";
+const DEFAULT_ACCESS_LOG_SAMPLE_EVERY: u64 = 100;
+const DEFAULT_AUTH_FAILURE_WINDOW_SECS: u64 = 10 * 60;
+const DEFAULT_AUTH_FAILURE_BLOCK_SECS: i64 = 60 * 60;
+const DEFAULT_AUTH_USER_CACHE_SECS: u64 = 30;
+const DEFAULT_USAGE_FLUSH_INTERVAL_MS: u64 = 1000;
+const DEFAULT_EMAIL_FROM: &str = "FeedTape <no-reply@feedtape.app>";
+const DEFAULT_SMTP_PORT: u16 = 587;
+const DEFAULT_EMAIL_LINK_BASE_URL: &str = "https://api.feedtape.app";
+const DEFAULT_ANALYTICS_SAMPLE_RATE: f64 = 1.0;
+const DEFAULT_DATABASE_CONNECT_RETRIES: u32 = 10;
+const DEFAULT_DATABASE_CONNECT_BACKOFF_MS: u64 = 500;
+const DEFAULT_DATABASE_CONNECT_MAX_WAIT_SECS: u64 = 60;
+const DEFAULT_AUDIO_CDN_URL_TTL_SECS: u64 = 60 * 60;
+const DEFAULT_AUDIO_CDN_CACHE_MAX_AGE_SECS: u64 = 24 * 60 * 60;
 
 #[derive(Debug)]
 pub struct ConfigError {
@@ -16,6 +53,31 @@ impl fmt::Display for ConfigError {
 
 impl std::error::Error for ConfigError {}
 
+impl ConfigError {
+    fn invalid(var_name: &str, message: &str) -> Self {
+        Self {
+            var_name: var_name.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Every problem `Config::validate` found
+#[derive(Debug)]
+pub struct ConfigValidationError(pub Vec<ConfigError>);
+
+impl fmt::Display for ConfigValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid configuration:")?;
+        for problem in &self.0 {
+            write!(f, "\n  {} - {}", problem.var_name, problem.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigValidationError {}
+
 fn required_env(name: &str) -> Result<String, ConfigError> {
     env::var(name).map_err(|_| ConfigError {
         var_name: name.to_string(),
@@ -30,32 +92,428 @@ fn parse_env<T: std::str::FromStr>(name: &str, value: String) -> Result<T, Confi
     })
 }
 
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> Result<T, ConfigError> {
+    match env::var(name) {
+        Ok(value) => parse_env(name, value),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Usage history kept by the maintenance job. It can't go below two months, which
+/// monthly quotas and rollover still read.
+fn usage_retention_days_from_env() -> Result<i64, ConfigError> {
+    let days = env_or("USAGE_RETENTION_DAYS", DEFAULT_USAGE_RETENTION_DAYS)?;
+    if days < MIN_USAGE_RETENTION_DAYS {
+        return Err(ConfigError {
+            var_name: "USAGE_RETENTION_DAYS".to_string(),
+            message: format!("must be at least {}", MIN_USAGE_RETENTION_DAYS),
+        });
+    }
+    Ok(days)
+}
+
+fn retention_from_env() -> Result<RetentionConfig, ConfigError> {
+    Ok(RetentionConfig {
+        usage_days: usage_retention_days_from_env()?,
+        audit_log_days: env::var("AUDIT_LOG_RETENTION_DAYS")
+            .ok()
+            .map(|days| parse_env("AUDIT_LOG_RETENTION_DAYS", days))
+            .transpose()?,
+        article_days: env_or("ARTICLE_RETENTION_DAYS", DEFAULT_ARTICLE_RETENTION_DAYS)?,
+        cached_audio_days: env_or(
+            "CACHED_AUDIO_RETENTION_DAYS",
+            DEFAULT_CACHED_AUDIO_RETENTION_DAYS,
+        )?,
+    })
+}
+
+/// Certificate and key for serving HTTPS directly. Both variables or neither.
+fn tls_from_env() -> Result<Option<TlsConfig>, ConfigError> {
+    let cert_path = env::var("TLS_CERT_PATH").ok();
+    let key_path = env::var("TLS_KEY_PATH").ok();
+
+    match (cert_path, key_path) {
+        (Some(cert_path), Some(key_path)) => Ok(Some(TlsConfig {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+        })),
+        (None, None) => Ok(None),
+        (Some(_), None) => Err(ConfigError::invalid(
+            "TLS_KEY_PATH",
+            "must be set together with TLS_CERT_PATH",
+        )),
+        (None, Some(_)) => Err(ConfigError::invalid(
+            "TLS_CERT_PATH",
+            "must be set together with TLS_KEY_PATH",
+        )),
+    }
+}
+
+/// Delivery of synthesized audio through CloudFront, present only when
+/// `AUDIO_CDN_BUCKET` is set
+fn audio_cdn_from_env() -> Result<Option<AudioCdnConfig>, ConfigError> {
+    let Ok(bucket) = env::var("AUDIO_CDN_BUCKET") else {
+        return Ok(None);
+    };
+
+    Ok(Some(AudioCdnConfig {
+        bucket,
+        distribution_id: required_env("AUDIO_CDN_DISTRIBUTION_ID")?,
+        domain: required_env("AUDIO_CDN_DOMAIN")?,
+        key_pair_id: required_env("AUDIO_CDN_KEY_PAIR_ID")?,
+        private_key: required_env("AUDIO_CDN_PRIVATE_KEY")?,
+        url_ttl_secs: env_or("AUDIO_CDN_URL_TTL_SECS", DEFAULT_AUDIO_CDN_URL_TTL_SECS)?,
+        cache_max_age_secs: env_or(
+            "AUDIO_CDN_CACHE_MAX_AGE_SECS",
+            DEFAULT_AUDIO_CDN_CACHE_MAX_AGE_SECS,
+        )?,
+    }))
+}
+
+/// Proxies whose forwarding headers are believed, as IPs or CIDR ranges
+fn trusted_proxies_from_env() -> Result<Vec<IpNet>, ConfigError> {
+    let Ok(value) = env::var("TRUSTED_PROXIES") else {
+        return Ok(Vec::new());
+    };
+
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            entry
+                .parse::<IpNet>()
+                .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                .map_err(|_| ConfigError {
+                    var_name: "TRUSTED_PROXIES".to_string(),
+                    message: format!("'{}' is not an IP address or CIDR range", entry),
+                })
+        })
+        .collect()
+}
+
+/// Access log settings, present only when `ACCESS_LOG` is true
+fn access_log_from_env() -> Result<Option<AccessLogConfig>, ConfigError> {
+    let enabled = env::var("ACCESS_LOG")
+        .map(|s| s.to_lowercase() == "true")
+        .unwrap_or(false);
+    if !enabled {
+        return Ok(None);
+    }
+
+    Ok(Some(AccessLogConfig {
+        sampled_paths: env::var("ACCESS_LOG_SAMPLED_PATHS")
+            .unwrap_or_else(|_| DEFAULT_ACCESS_LOG_SAMPLED_PATHS.to_string())
+            .split(',')
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect(),
+        sample_every: env_or("ACCESS_LOG_SAMPLE_EVERY", DEFAULT_ACCESS_LOG_SAMPLE_EVERY)?,
+    }))
+}
+
+/// Automatic blocking of clients that keep presenting invalid credentials, off unless
+/// `AUTH_FAILURE_BLOCK_THRESHOLD` is set above 0
+fn auth_failure_block_from_env() -> Result<Option<AuthFailureBlockConfig>, ConfigError> {
+    let threshold = env_or("AUTH_FAILURE_BLOCK_THRESHOLD", 0)?;
+    if threshold == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(AuthFailureBlockConfig {
+        threshold,
+        window_secs: env_or("AUTH_FAILURE_WINDOW_SECS", DEFAULT_AUTH_FAILURE_WINDOW_SECS)?,
+        block_secs: env_or("AUTH_FAILURE_BLOCK_SECS", DEFAULT_AUTH_FAILURE_BLOCK_SECS)?,
+    }))
+}
+
+/// Internal gRPC API, served only when `GRPC_PORT` is set
+fn grpc_from_env() -> Result<Option<GrpcConfig>, ConfigError> {
+    let Ok(port) = env::var("GRPC_PORT") else {
+        return Ok(None);
+    };
+
+    Ok(Some(GrpcConfig {
+        port: parse_env("GRPC_PORT", port)?,
+        api_keys: env::var("GRPC_API_KEYS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect(),
+    }))
+}
+
+fn client_ip_header_from_env() -> Result<ClientIpHeader, ConfigError> {
+    let value = env::var("CLIENT_IP_HEADER").map(|value| value.to_lowercase());
+    match value.as_deref() {
+        Err(_) | Ok("x-forwarded-for") => Ok(ClientIpHeader::XForwardedFor),
+        Ok("forwarded") => Ok(ClientIpHeader::Forwarded),
+        Ok(_) => Err(ConfigError::invalid(
+            "CLIENT_IP_HEADER",
+            "must be X-Forwarded-For or Forwarded",
+        )),
+    }
+}
+
+fn jwt_signing_from_env() -> Result<JwtSigning, ConfigError> {
+    match env::var("JWT_ALGORITHM").as_deref() {
+        Err(_) | Ok("HS256") => Ok(JwtSigning::Hs256),
+        Ok("RS256") => Ok(JwtSigning::Rs256 {
+            private_key: required_env("JWT_PRIVATE_KEY")?,
+            previous_public_key: env::var("JWT_PREVIOUS_PUBLIC_KEY").ok(),
+        }),
+        Ok(_) => Err(ConfigError::invalid(
+            "JWT_ALGORITHM",
+            "must be HS256 or RS256",
+        )),
+    }
+}
+
+fn database_connect_from_env() -> Result<DatabaseConnectConfig, ConfigError> {
+    Ok(DatabaseConnectConfig {
+        retries: env_or("DATABASE_CONNECT_RETRIES", DEFAULT_DATABASE_CONNECT_RETRIES)?,
+        backoff_ms: env_or(
+            "DATABASE_CONNECT_BACKOFF_MS",
+            DEFAULT_DATABASE_CONNECT_BACKOFF_MS,
+        )?,
+        max_wait_secs: env_or(
+            "DATABASE_CONNECT_MAX_WAIT_SECS",
+            DEFAULT_DATABASE_CONNECT_MAX_WAIT_SECS,
+        )?,
+    })
+}
+
+fn schema_check_from_env() -> Result<SchemaCheck, ConfigError> {
+    match env::var("SCHEMA_CHECK")
+        .unwrap_or_else(|_| "enforce".to_string())
+        .as_str()
+    {
+        "enforce" => Ok(SchemaCheck::Enforce),
+        "warn" => Ok(SchemaCheck::Warn),
+        _ => Err(ConfigError::invalid(
+            "SCHEMA_CHECK",
+            "must be enforce or warn",
+        )),
+    }
+}
+
+fn email_from_env() -> Result<EmailConfig, ConfigError> {
+    let backend = match env::var("EMAIL_BACKEND")
+        .unwrap_or_else(|_| "log".to_string())
+        .as_str()
+    {
+        "log" => EmailBackend::Log,
+        "ses" => EmailBackend::Ses,
+        "smtp" => EmailBackend::Smtp(SmtpConfig {
+            host: required_env("SMTP_HOST")?,
+            port: env_or("SMTP_PORT", DEFAULT_SMTP_PORT)?,
+            username: env::var("SMTP_USERNAME").ok(),
+            password: env::var("SMTP_PASSWORD").ok(),
+            tls: match env::var("SMTP_TLS")
+                .unwrap_or_else(|_| "starttls".to_string())
+                .as_str()
+            {
+                "starttls" => SmtpTls::StartTls,
+                "tls" => SmtpTls::Tls,
+                "none" => SmtpTls::None,
+                _ => {
+                    return Err(ConfigError::invalid(
+                        "SMTP_TLS",
+                        "must be starttls, tls or none",
+                    ))
+                }
+            },
+        }),
+        _ => {
+            return Err(ConfigError::invalid(
+                "EMAIL_BACKEND",
+                "must be log, ses or smtp",
+            ))
+        }
+    };
+
+    Ok(EmailConfig {
+        backend,
+        from: env::var("EMAIL_FROM").unwrap_or_else(|_| DEFAULT_EMAIL_FROM.to_string()),
+        link_base_url: env::var("EMAIL_LINK_BASE_URL")
+            .unwrap_or_else(|_| DEFAULT_EMAIL_LINK_BASE_URL.to_string())
+            .trim_end_matches('/')
+            .to_string(),
+    })
+}
+
+/// Plan quotas, each overridable by env var; unset values keep the defaults
+fn plan_limits_from_env() -> Result<PlanLimits, ConfigError> {
+    let defaults = PlanLimits::default();
+
+    Ok(PlanLimits {
+        free: TierLimits {
+            characters: env_or("FREE_CHARACTERS", defaults.free.characters)?,
+            max_feeds: env_or("FREE_MAX_FEEDS", defaults.free.max_feeds)?,
+        },
+        pro: TierLimits {
+            characters: env_or("PRO_CHARACTERS", defaults.pro.characters)?,
+            max_feeds: env_or("PRO_MAX_FEEDS", defaults.pro.max_feeds)?,
+        },
+        quota_period: env_or("QUOTA_PERIOD", defaults.quota_period)?,
+        max_request_characters: env_or(
+            "TTS_MAX_REQUEST_CHARACTERS",
+            defaults.max_request_characters,
+        )?,
+        referral_bonus_characters: env_or(
+            "REFERRAL_BONUS_CHARACTERS",
+            defaults.referral_bonus_characters,
+        )?,
+        rollover_cap_multiplier: env_or(
+            "PRO_ROLLOVER_CAP_MULTIPLIER",
+            defaults.rollover_cap_multiplier,
+        )?,
+    })
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub database_url: String,
+    /// Read replica for listings; the primary serves everything when unset
+    pub database_read_url: Option<String>,
+    /// How long startup keeps trying to reach the database
+    pub database_connect: DatabaseConnectConfig,
+    /// What startup does when the database hasn't applied this build's migrations
+    pub schema_check: SchemaCheck,
     pub host: String,
     pub port: u16,
     pub jwt_secret: String,
     pub jwt_expiration_hours: i64,
+    /// How access tokens are signed; `jwt_secret` keeps signing email links either way
+    pub jwt_signing: JwtSigning,
     pub refresh_token_expiration_days: i64,
     pub aws_region: String,
     pub environment: Environment,
     pub log_format: LogFormat,
+    /// Mark cookies `Secure`, which browsers then only send over HTTPS
+    pub secure_cookies: bool,
     // GitHub OAuth
     pub github_client_id: String,
     pub github_client_secret: String,
     pub github_redirect_uri: String,
+    /// github.com and api.github.com unless pointed at a stand-in
+    pub github_oauth_base_url: String,
+    pub github_api_base_url: String,
+    // Cache store, shared by instances when backed by Redis
+    pub cache_backend: CacheBackend,
     // TTS Cache
     pub tts_cache_enabled: bool,
+    /// Synthesized audio stored in S3 and handed out as signed CloudFront URLs instead
+    /// of in the response; off when unset
+    pub audio_cdn: Option<AudioCdnConfig>,
+    /// How long authenticated users are cached between lookups; 0 looks them up on
+    /// every request
+    pub auth_user_cache_secs: u64,
+    // Google Play billing (purchase validation is disabled when unset)
+    pub google_play_package_name: Option<String>,
+    pub google_play_service_account_key: Option<String>,
+    // Stripe billing for the web client (disabled when unset)
+    pub stripe_secret_key: Option<String>,
+    pub stripe_webhook_secret: Option<String>,
+    pub stripe_price_id: Option<String>,
+    pub stripe_checkout_success_url: Option<String>,
+    pub stripe_checkout_cancel_url: Option<String>,
+    // App Store Server Notifications (disabled when unset)
+    pub apple_bundle_id: Option<String>,
+    // Plan quotas
+    pub plan_limits: PlanLimits,
+    // Accounts allowed to use the /admin endpoints, matched by email
+    pub admin_emails: Vec<String>,
+    /// Load balancers allowed to report the client IP in `client_ip_header`
+    pub trusted_proxies: Vec<IpNet>,
+    /// The header the trusted proxies write; the other one is the client's to make up
+    pub client_ip_header: ClientIpHeader,
+    /// Unix socket to listen on instead of HOST:PORT
+    pub listen_socket: Option<PathBuf>,
+    /// Serve HTTPS without a reverse proxy; plain HTTP when unset
+    pub tls: Option<TlsConfig>,
+    /// gRPC API for internal services; off when unset
+    pub grpc: Option<GrpcConfig>,
+    // Load shedding: requests beyond these are rejected with 503 right away
+    pub max_concurrent_requests: usize,
+    pub max_concurrent_syntheses: usize,
+    /// Structured request logging; off when unset
+    pub access_log: Option<AccessLogConfig>,
+    /// Blocklisting of clients with repeated authentication failures; off when unset
+    pub auth_failure_block: Option<AuthFailureBlockConfig>,
+    // Cleanup of expired tokens and data past its retention
+    pub maintenance_interval_secs: u64,
+    pub retention: RetentionConfig,
+    /// How often buffered usage is written; 0 writes each synthesis's usage right away
+    pub usage_flush_interval_ms: u64,
+    /// Serve TTS from the built-in fake Polly, for running without AWS credentials
+    pub dev_fake_externals: bool,
+    /// Also fake GitHub sign-in; only together with `dev_fake_externals`
+    pub dev_fake_github_oauth: bool,
+    pub email: EmailConfig,
+    /// Share of users whose analytics events are stored, from 0 to 1
+    pub analytics_sample_rate: f64,
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+/// Deployment profile, picked by `ENVIRONMENT`. Each one supplies the defaults for
+/// settings left unset; explicit values always win.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum Environment {
+    /// A developer's machine
     Development,
+    /// Automated test runs, against the built-in fakes unless told otherwise
+    Test,
+    /// A deployment that mirrors production, with its own accounts and credentials
+    Staging,
     Production,
 }
 
+impl Environment {
+    fn from_env() -> Self {
+        match env::var("ENVIRONMENT")
+            .unwrap_or_else(|_| "development".to_string())
+            .as_str()
+        {
+            "production" => Environment::Production,
+            "staging" => Environment::Staging,
+            "test" => Environment::Test,
+            _ => Environment::Development,
+        }
+    }
+
+    /// Staging and production serve browsers over HTTPS and run on real credentials
+    pub fn is_deployed(&self) -> bool {
+        matches!(self, Environment::Staging | Environment::Production)
+    }
+
+    fn default_log_format(&self) -> LogFormat {
+        if self.is_deployed() {
+            LogFormat::Json
+        } else {
+            LogFormat::Pretty
+        }
+    }
+}
+
+/// Access token signing, picked by `JWT_ALGORITHM`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub enum JwtSigning {
+    /// HMAC with `JWT_SECRET`, so only this server can verify tokens
+    Hs256,
+    /// An RSA keypair; other services verify tokens with the keys at
+    /// `/.well-known/jwks.json`
+    Rs256 {
+        /// PEM private key new tokens are signed with
+        private_key: String,
+        /// PEM public key of the key being rotated out, whose tokens are still accepted
+        previous_public_key: Option<String>,
+    },
+}
+
 #[derive(Debug, Clone, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum LogFormat {
@@ -63,52 +521,519 @@ pub enum LogFormat {
     Json,
 }
 
+/// Failed connection attempts at startup are retried after `backoff_ms`, doubling each
+/// time, until `retries` run out or the next attempt would start after `max_wait_secs`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct DatabaseConnectConfig {
+    pub retries: u32,
+    pub backoff_ms: u64,
+    pub max_wait_secs: u64,
+}
+
+/// Forwarding header trusted proxies report the client IP in
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClientIpHeader {
+    XForwardedFor,
+    /// RFC 7239 `Forwarded`
+    Forwarded,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaCheck {
+    /// Refuse to start
+    Enforce,
+    /// Log the mismatch and serve anyway
+    Warn,
+}
+
+/// Successful requests to the sampled paths are logged one in `sample_every`, so polling
+/// endpoints don't drown out the rest
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AccessLogConfig {
+    /// Path prefixes, e.g. `/health`
+    pub sampled_paths: Vec<String>,
+    pub sample_every: u64,
+}
+
+/// A client reaching `threshold` failed authentications within `window_secs` is blocked
+/// for `block_secs`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AuthFailureBlockConfig {
+    pub threshold: i64,
+    pub window_secs: u64,
+    pub block_secs: i64,
+}
+
+/// How long personal data is kept, so each deployment can meet its own data-minimization
+/// rules. The maintenance job deletes stored records past their window; cache entries
+/// expire within theirs.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct RetentionConfig {
+    /// Daily usage; at least two months, which monthly quotas and rollover still read
+    pub usage_days: i64,
+    /// Audit log entries; kept for good when unset
+    pub audit_log_days: Option<i64>,
+    /// Cleaned article text cached for re-syntheses; 0 doesn't keep it
+    pub article_days: i64,
+    /// Synthesized audio, which the TTS cache holds for 30 minutes at most anyway; 0
+    /// doesn't keep it. Audio on the CDN stays until its URLs expire either way.
+    pub cached_audio_days: i64,
+}
+
+/// S3 bucket synthesized audio is uploaded to, behind a CloudFront distribution that
+/// only serves it with URLs signed by `key_pair_id`. The maintenance job deletes audio
+/// past the cached audio retention and invalidates it in the distribution.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AudioCdnConfig {
+    pub bucket: String,
+    /// Distribution purged objects are invalidated in
+    pub distribution_id: String,
+    /// Host the signed URLs point at, e.g. `d111111abcdef8.cloudfront.net`
+    pub domain: String,
+    /// ID of the CloudFront public key matching `private_key`
+    pub key_pair_id: String,
+    /// PEM RSA private key URLs are signed with
+    pub private_key: String,
+    /// How long a signed URL stays valid
+    pub url_ttl_secs: u64,
+    /// `max-age` of the `Cache-Control` header objects are uploaded with
+    pub cache_max_age_secs: u64,
+}
+
+/// Plain-text gRPC on `host:port`, meant for the private network only
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct GrpcConfig {
+    pub port: u16,
+    /// Bearer keys callers authenticate with; several so keys can be rotated
+    pub api_keys: Vec<String>,
+}
+
+/// PEM files read at startup and again on SIGHUP
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct TlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+/// Transactional email, sent through `EMAIL_BACKEND`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct EmailConfig {
+    pub backend: EmailBackend,
+    /// Sender address, optionally with a display name
+    pub from: String,
+    /// Where this server is reached from outside, for links in emails back to it
+    pub link_base_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailBackend {
+    /// Logged instead of sent, for development
+    Log,
+    /// Amazon SES in `AWS_REGION`
+    Ses,
+    Smtp(SmtpConfig),
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    /// Both or neither; the relay is used without authentication otherwise
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub tls: SmtpTls,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+    /// Upgrade a plain connection, usually on port 587
+    StartTls,
+    /// TLS from the start, usually on port 465
+    Tls,
+    /// Plain text, only for local mail catchers
+    None,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    Memory,
+    Redis { url: String },
+}
+
 impl Config {
-    pub fn from_env() -> Result<Self, ConfigError> {
+    /// Read the configuration from the environment and `.env`. With
+    /// `CONFIG_SECRETS_SOURCE` set, values missing from both are looked up in AWS Secrets
+    /// Manager (`secrets-manager`, `CONFIG_SECRET_ID`) or SSM Parameter Store (`ssm`,
+    /// `CONFIG_SSM_PATH`) first. `CONFIG_FILE` fills in whatever is still missing.
+    pub async fn from_env() -> Result<Self, ConfigError> {
         dotenvy::dotenv().ok();
+        secrets::load_aws_secrets().await?;
+        file::load_config_file()?;
 
         let port_str = env::var("PORT").unwrap_or_else(|_| "8080".to_string());
         let jwt_exp_str = env::var("JWT_EXPIRATION_HOURS").unwrap_or_else(|_| "1".to_string());
         let refresh_exp_str =
             env::var("REFRESH_TOKEN_EXPIRATION_DAYS").unwrap_or_else(|_| "30".to_string());
+        let environment = Environment::from_env();
+        let dev_fake_github_oauth = env_or("DEV_FAKE_GITHUB_OAUTH", false)?;
+        // The fake GitHub never sees the client credentials, so they can be left out
+        let github_env = |name: &str| {
+            if dev_fake_github_oauth {
+                Ok(env::var(name).unwrap_or_default())
+            } else {
+                required_env(name)
+            }
+        };
 
         let config = Config {
             database_url: required_env("DATABASE_URL")?,
+            database_read_url: env::var("DATABASE_READ_URL").ok(),
+            database_connect: database_connect_from_env()?,
+            schema_check: schema_check_from_env()?,
             host: env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
             port: parse_env("PORT", port_str)?,
             jwt_secret: required_env("JWT_SECRET")?,
             jwt_expiration_hours: parse_env("JWT_EXPIRATION_HOURS", jwt_exp_str)?,
+            jwt_signing: jwt_signing_from_env()?,
             refresh_token_expiration_days: parse_env(
                 "REFRESH_TOKEN_EXPIRATION_DAYS",
                 refresh_exp_str,
             )?,
-            aws_region: env::var("AWS_REGION").unwrap_or_else(|_| "eu-west-1".to_string()),
-            environment: match env::var("ENVIRONMENT")
-                .unwrap_or_else(|_| "development".to_string())
-                .as_str()
-            {
-                "production" => Environment::Production,
-                _ => Environment::Development,
+            aws_region: env::var("AWS_REGION").unwrap_or_else(|_| DEFAULT_AWS_REGION.to_string()),
+            environment,
+            log_format: match env::var("LOG_FORMAT").ok().as_deref() {
+                Some("json") => LogFormat::Json,
+                Some(_) => LogFormat::Pretty,
+                None => environment.default_log_format(),
             },
-            log_format: match env::var("LOG_FORMAT")
-                .unwrap_or_else(|_| "pretty".to_string())
+            secure_cookies: env_or("SECURE_COOKIES", environment.is_deployed())?,
+            github_client_id: github_env("GITHUB_CLIENT_ID")?,
+            github_client_secret: github_env("GITHUB_CLIENT_SECRET")?,
+            github_redirect_uri: github_env("GITHUB_REDIRECT_URI")?,
+            github_oauth_base_url: env::var("GITHUB_OAUTH_BASE_URL")
+                .unwrap_or_else(|_| GITHUB_OAUTH_BASE_URL.to_string()),
+            github_api_base_url: env::var("GITHUB_API_BASE_URL")
+                .unwrap_or_else(|_| GITHUB_API_BASE_URL.to_string()),
+            cache_backend: match env::var("CACHE_BACKEND")
+                .unwrap_or_else(|_| "memory".to_string())
                 .as_str()
             {
-                "json" => LogFormat::Json,
-                _ => LogFormat::Pretty,
+                "redis" => CacheBackend::Redis {
+                    url: required_env("REDIS_URL")?,
+                },
+                _ => CacheBackend::Memory,
             },
-            github_client_id: required_env("GITHUB_CLIENT_ID")?,
-            github_client_secret: required_env("GITHUB_CLIENT_SECRET")?,
-            github_redirect_uri: required_env("GITHUB_REDIRECT_URI")?,
             tts_cache_enabled: env::var("TTS_CACHE_ENABLED")
                 .map(|s| s.to_lowercase() == "true")
                 .unwrap_or(false),
+            audio_cdn: audio_cdn_from_env()?,
+            auth_user_cache_secs: env_or("AUTH_USER_CACHE_SECS", DEFAULT_AUTH_USER_CACHE_SECS)?,
+            google_play_package_name: env::var("GOOGLE_PLAY_PACKAGE_NAME").ok(),
+            google_play_service_account_key: env::var("GOOGLE_PLAY_SERVICE_ACCOUNT_KEY").ok(),
+            stripe_secret_key: env::var("STRIPE_SECRET_KEY").ok(),
+            stripe_webhook_secret: env::var("STRIPE_WEBHOOK_SECRET").ok(),
+            stripe_price_id: env::var("STRIPE_PRICE_ID").ok(),
+            stripe_checkout_success_url: env::var("STRIPE_CHECKOUT_SUCCESS_URL").ok(),
+            stripe_checkout_cancel_url: env::var("STRIPE_CHECKOUT_CANCEL_URL").ok(),
+            apple_bundle_id: env::var("APPLE_BUNDLE_ID").ok(),
+            plan_limits: plan_limits_from_env()?,
+            admin_emails: env::var("ADMIN_EMAILS")
+                .map(|s| {
+                    s.split(',')
+                        .map(|email| email.trim().to_lowercase())
+                        .filter(|email| !email.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            trusted_proxies: trusted_proxies_from_env()?,
+            client_ip_header: client_ip_header_from_env()?,
+            listen_socket: env::var("LISTEN_SOCKET").ok().map(PathBuf::from),
+            tls: tls_from_env()?,
+            grpc: grpc_from_env()?,
+            max_concurrent_requests: env_or(
+                "MAX_CONCURRENT_REQUESTS",
+                DEFAULT_MAX_CONCURRENT_REQUESTS,
+            )?,
+            max_concurrent_syntheses: env_or(
+                "MAX_CONCURRENT_SYNTHESES",
+                DEFAULT_MAX_CONCURRENT_SYNTHESES,
+            )?,
+            access_log: access_log_from_env()?,
+            auth_failure_block: auth_failure_block_from_env()?,
+            maintenance_interval_secs: env_or(
+                "MAINTENANCE_INTERVAL_SECS",
+                DEFAULT_MAINTENANCE_INTERVAL_SECS,
+            )?,
+            retention: retention_from_env()?,
+            usage_flush_interval_ms: env_or(
+                "USAGE_FLUSH_INTERVAL_MS",
+                DEFAULT_USAGE_FLUSH_INTERVAL_MS,
+            )?,
+            dev_fake_externals: env_or("DEV_FAKE_EXTERNALS", environment == Environment::Test)?,
+            dev_fake_github_oauth,
+            email: email_from_env()?,
+            analytics_sample_rate: env_or("ANALYTICS_SAMPLE_RATE", DEFAULT_ANALYTICS_SAMPLE_RATE)?,
         };
 
         Ok(config)
     }
 
-    pub fn is_development(&self) -> bool {
-        self.environment == Environment::Development
+    pub fn is_admin(&self, email: &str) -> bool {
+        self.admin_emails
+            .iter()
+            .any(|admin| admin.eq_ignore_ascii_case(email))
     }
+
+    /// Reject setups that parse but can't work or aren't safe, reporting every problem
+    /// at once
+    pub fn validate(&self) -> Result<(), ConfigValidationError> {
+        let mut problems = Vec::new();
+
+        if self.jwt_secret.len() < MIN_JWT_SECRET_BYTES {
+            problems.push(ConfigError::invalid(
+                "JWT_SECRET",
+                "must be at least 32 bytes long",
+            ));
+        }
+        if self.jwt_expiration_hours <= 0 {
+            problems.push(ConfigError::invalid(
+                "JWT_EXPIRATION_HOURS",
+                "must be greater than zero",
+            ));
+        }
+        if let JwtSigning::Rs256 {
+            private_key,
+            previous_public_key,
+        } = &self.jwt_signing
+        {
+            if let Err(e) = RsaKey::from_private_pem(private_key) {
+                problems.push(ConfigError::invalid("JWT_PRIVATE_KEY", &e));
+            }
+            if let Some(Err(e)) = previous_public_key.as_deref().map(RsaKey::from_public_pem) {
+                problems.push(ConfigError::invalid("JWT_PREVIOUS_PUBLIC_KEY", &e));
+            }
+        }
+        if self.refresh_token_expiration_days <= 0 {
+            problems.push(ConfigError::invalid(
+                "REFRESH_TOKEN_EXPIRATION_DAYS",
+                "must be greater than zero",
+            ));
+        }
+        if self.database_connect.retries > 0 && self.database_connect.backoff_ms == 0 {
+            problems.push(ConfigError::invalid(
+                "DATABASE_CONNECT_BACKOFF_MS",
+                "must be greater than zero when retrying",
+            ));
+        }
+        if self.maintenance_interval_secs == 0 {
+            problems.push(ConfigError::invalid(
+                "MAINTENANCE_INTERVAL_SECS",
+                "must be greater than zero",
+            ));
+        }
+        if self.retention.audit_log_days.is_some_and(|days| days <= 0) {
+            problems.push(ConfigError::invalid(
+                "AUDIT_LOG_RETENTION_DAYS",
+                "must be greater than zero",
+            ));
+        }
+        if self.retention.article_days < 0 {
+            problems.push(ConfigError::invalid(
+                "ARTICLE_RETENTION_DAYS",
+                "must not be negative",
+            ));
+        }
+        if self.retention.cached_audio_days < 0 {
+            problems.push(ConfigError::invalid(
+                "CACHED_AUDIO_RETENTION_DAYS",
+                "must not be negative",
+            ));
+        }
+        if let Some(audio_cdn) = &self.audio_cdn {
+            if let Err(e) = CloudFrontSigner::new(
+                &audio_cdn.domain,
+                &audio_cdn.key_pair_id,
+                &audio_cdn.private_key,
+            ) {
+                problems.push(ConfigError::invalid("AUDIO_CDN_PRIVATE_KEY", &e));
+            }
+            if audio_cdn.url_ttl_secs == 0 {
+                problems.push(ConfigError::invalid(
+                    "AUDIO_CDN_URL_TTL_SECS",
+                    "must be greater than zero",
+                ));
+            }
+        }
+        if self.max_concurrent_requests == 0 {
+            problems.push(ConfigError::invalid(
+                "MAX_CONCURRENT_REQUESTS",
+                "must be greater than zero",
+            ));
+        }
+        if self.max_concurrent_syntheses == 0 {
+            problems.push(ConfigError::invalid(
+                "MAX_CONCURRENT_SYNTHESES",
+                "must be greater than zero",
+            ));
+        }
+        if self
+            .access_log
+            .as_ref()
+            .is_some_and(|access_log| access_log.sample_every == 0)
+        {
+            problems.push(ConfigError::invalid(
+                "ACCESS_LOG_SAMPLE_EVERY",
+                "must be greater than zero",
+            ));
+        }
+        if let Some(auth_failure_block) = &self.auth_failure_block {
+            if auth_failure_block.threshold < 0 {
+                problems.push(ConfigError::invalid(
+                    "AUTH_FAILURE_BLOCK_THRESHOLD",
+                    "must not be negative",
+                ));
+            }
+            if auth_failure_block.window_secs == 0 {
+                problems.push(ConfigError::invalid(
+                    "AUTH_FAILURE_WINDOW_SECS",
+                    "must be greater than zero",
+                ));
+            }
+            if auth_failure_block.block_secs <= 0 {
+                problems.push(ConfigError::invalid(
+                    "AUTH_FAILURE_BLOCK_SECS",
+                    "must be greater than zero",
+                ));
+            }
+        }
+        if self.environment == Environment::Production && self.log_format == LogFormat::Pretty {
+            problems.push(ConfigError::invalid(
+                "LOG_FORMAT",
+                "must be json in production",
+            ));
+        }
+        if self.environment == Environment::Production && self.dev_fake_externals {
+            problems.push(ConfigError::invalid(
+                "DEV_FAKE_EXTERNALS",
+                "cannot be enabled in production",
+            ));
+        }
+        if self.environment == Environment::Production && !self.secure_cookies {
+            problems.push(ConfigError::invalid(
+                "SECURE_COOKIES",
+                "must be true in production",
+            ));
+        }
+        if let EmailBackend::Smtp(smtp) = &self.email.backend {
+            if smtp.username.is_some() != smtp.password.is_some() {
+                problems.push(ConfigError::invalid(
+                    "SMTP_USERNAME",
+                    "must be set together with SMTP_PASSWORD",
+                ));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.analytics_sample_rate) {
+            problems.push(ConfigError::invalid(
+                "ANALYTICS_SAMPLE_RATE",
+                "must be between 0 and 1",
+            ));
+        }
+        if self.dev_fake_github_oauth && !self.dev_fake_externals {
+            problems.push(ConfigError::invalid(
+                "DEV_FAKE_GITHUB_OAUTH",
+                "requires DEV_FAKE_EXTERNALS=true",
+            ));
+        }
+        if self.dev_fake_github_oauth && self.github_redirect_uri.is_empty() {
+            problems.push(ConfigError::invalid(
+                "GITHUB_REDIRECT_URI",
+                "is needed for the fake GitHub sign-in to return to",
+            ));
+        }
+
+        if let Some(tls) = &self.tls {
+            for (var_name, path) in [
+                ("TLS_CERT_PATH", &tls.cert_path),
+                ("TLS_KEY_PATH", &tls.key_path),
+            ] {
+                if !path.is_file() {
+                    problems.push(ConfigError::invalid(var_name, "file does not exist"));
+                }
+            }
+        }
+
+        if self.listen_socket.is_some() {
+            if cfg!(not(unix)) {
+                problems.push(ConfigError::invalid(
+                    "LISTEN_SOCKET",
+                    "is only supported on Unix",
+                ));
+            }
+            if self.tls.is_some() {
+                problems.push(ConfigError::invalid(
+                    "LISTEN_SOCKET",
+                    "cannot be combined with TLS_CERT_PATH; terminate TLS in the proxy",
+                ));
+            }
+        }
+
+        if let Some(grpc) = &self.grpc {
+            if grpc.api_keys.is_empty() {
+                problems.push(ConfigError::invalid(
+                    "GRPC_API_KEYS",
+                    "must be set when GRPC_PORT is",
+                ));
+            }
+            if grpc
+                .api_keys
+                .iter()
+                .any(|key| key.len() < MIN_GRPC_API_KEY_BYTES)
+            {
+                problems.push(ConfigError::invalid(
+                    "GRPC_API_KEYS",
+                    "every key must be at least 32 bytes long",
+                ));
+            }
+            if grpc.port == self.port {
+                problems.push(ConfigError::invalid("GRPC_PORT", "must differ from PORT"));
+            }
+        }
+
+        let mut urls = vec![
+            ("GITHUB_REDIRECT_URI", &self.github_redirect_uri),
+            ("GITHUB_OAUTH_BASE_URL", &self.github_oauth_base_url),
+            ("GITHUB_API_BASE_URL", &self.github_api_base_url),
+            ("EMAIL_LINK_BASE_URL", &self.email.link_base_url),
+        ];
+        if let Some(url) = &self.stripe_checkout_success_url {
+            urls.push(("STRIPE_CHECKOUT_SUCCESS_URL", url));
+        }
+        if let Some(url) = &self.stripe_checkout_cancel_url {
+            urls.push(("STRIPE_CHECKOUT_CANCEL_URL", url));
+        }
+        for (var_name, url) in urls {
+            if !is_http_url(url) {
+                problems.push(ConfigError::invalid(
+                    var_name,
+                    "must be an absolute http(s) URL",
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigValidationError(problems))
+        }
+    }
+}
+
+fn is_http_url(value: &str) -> bool {
+    Url::parse(value)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some())
 }
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::env;
+
+use super::{required_env, ConfigError, DEFAULT_AWS_REGION};
+
+/// Where `CONFIG_SECRETS_SOURCE` says secrets are kept
+enum SecretsSource {
+    /// A Secrets Manager secret holding a JSON object of variable names to values
+    SecretsManager { secret_id: String },
+    /// Parameter Store parameters under a path, each named after its variable
+    ParameterStore { path: String },
+}
+
+impl SecretsSource {
+    fn from_env() -> Result<Option<Self>, ConfigError> {
+        let Ok(source) = env::var("CONFIG_SECRETS_SOURCE") else {
+            return Ok(None);
+        };
+
+        match source.as_str() {
+            "secrets-manager" => Ok(Some(Self::SecretsManager {
+                secret_id: required_env("CONFIG_SECRET_ID")?,
+            })),
+            "ssm" => Ok(Some(Self::ParameterStore {
+                path: required_env("CONFIG_SSM_PATH")?,
+            })),
+            _ => Err(ConfigError {
+                var_name: "CONFIG_SECRETS_SOURCE".to_string(),
+                message: format!("unknown source '{}', use secrets-manager or ssm", source),
+            }),
+        }
+    }
+}
+
+/// Load secrets from AWS into the environment when `CONFIG_SECRETS_SOURCE` is set. Like
+/// `.env` values, they never override variables that are already set.
+pub(super) async fn load_aws_secrets() -> Result<(), ConfigError> {
+    let Some(source) = SecretsSource::from_env()? else {
+        return Ok(());
+    };
+
+    let region = env::var("AWS_REGION").unwrap_or_else(|_| DEFAULT_AWS_REGION.to_string());
+    let aws_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_config::Region::new(region))
+        .load()
+        .await;
+
+    let secrets = match source {
+        SecretsSource::SecretsManager { secret_id } => {
+            secrets_manager_values(&aws_config, &secret_id).await?
+        }
+        SecretsSource::ParameterStore { path } => {
+            parameter_store_values(&aws_config, &path).await?
+        }
+    };
+
+    for (name, value) in secrets {
+        if env::var_os(&name).is_none() {
+            env::set_var(name, value);
+        }
+    }
+
+    Ok(())
+}
+
+async fn secrets_manager_values(
+    aws_config: &aws_config::SdkConfig,
+    secret_id: &str,
+) -> Result<HashMap<String, String>, ConfigError> {
+    let error = |message: String| ConfigError {
+        var_name: "CONFIG_SECRET_ID".to_string(),
+        message,
+    };
+
+    let client = aws_sdk_secretsmanager::Client::new(aws_config);
+    let secret = client
+        .get_secret_value()
+        .secret_id(secret_id)
+        .send()
+        .await
+        .map_err(|e| error(format!("failed to read secret '{}': {}", secret_id, e)))?;
+    let secret_string = secret
+        .secret_string()
+        .ok_or_else(|| error(format!("secret '{}' has no string value", secret_id)))?;
+
+    serde_json::from_str(secret_string).map_err(|e| {
+        error(format!(
+            "secret '{}' must be a JSON object of string values: {}",
+            secret_id, e
+        ))
+    })
+}
+
+async fn parameter_store_values(
+    aws_config: &aws_config::SdkConfig,
+    path: &str,
+) -> Result<HashMap<String, String>, ConfigError> {
+    let client = aws_sdk_ssm::Client::new(aws_config);
+    let mut pages = client
+        .get_parameters_by_path()
+        .path(path)
+        .with_decryption(true)
+        .into_paginator()
+        .send();
+
+    let mut values = HashMap::new();
+    while let Some(page) = pages.next().await {
+        let page = page.map_err(|e| ConfigError {
+            var_name: "CONFIG_SSM_PATH".to_string(),
+            message: format!("failed to read parameters under '{}': {}", path, e),
+        })?;
+        for parameter in page.parameters() {
+            if let (Some(name), Some(value)) = (parameter.name(), parameter.value()) {
+                let name = name.rsplit('/').next().unwrap_or(name);
+                values.insert(name.to_string(), value.to_string());
+            }
+        }
+    }
+
+    Ok(values)
+}
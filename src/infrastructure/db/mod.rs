@@ -1,9 +1,41 @@
-use sqlx::postgres::PgPoolOptions;
-use sqlx::{Pool, Postgres};
-use std::time::Duration;
+//! Connection pool for the database backend picked at build time: Postgres by default,
+//! SQLite with the `sqlite` feature.
 
+pub mod seed;
+
+use sqlx::migrate::Migrator;
+use sqlx::Pool;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::infrastructure::config::DatabaseConnectConfig;
+
+#[cfg(not(feature = "sqlite"))]
+use sqlx::{postgres::PgPoolOptions, Postgres};
+#[cfg(feature = "sqlite")]
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+    Sqlite,
+};
+
+#[cfg(not(feature = "sqlite"))]
 pub type DbPool = Pool<Postgres>;
+#[cfg(feature = "sqlite")]
+pub type DbPool = Pool<Sqlite>;
+
+/// A single connection, e.g. the one behind an open transaction
+#[cfg(not(feature = "sqlite"))]
+pub type DbConnection = sqlx::PgConnection;
+#[cfg(feature = "sqlite")]
+pub type DbConnection = sqlx::SqliteConnection;
+
+/// Schema migrations for the backend in use
+#[cfg(not(feature = "sqlite"))]
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+#[cfg(feature = "sqlite")]
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations_sqlite");
 
+#[cfg(not(feature = "sqlite"))]
 pub async fn create_pool(database_url: &str) -> Result<DbPool, sqlx::Error> {
     PgPoolOptions::new()
         .max_connections(10)
@@ -12,6 +44,198 @@ pub async fn create_pool(database_url: &str) -> Result<DbPool, sqlx::Error> {
         .await
 }
 
+/// Open (creating it if needed) the database file. WAL lets readers carry on while a
+/// write is in progress, and the busy timeout makes writers queue instead of failing.
+#[cfg(feature = "sqlite")]
+pub async fn create_pool(database_url: &str) -> Result<DbPool, sqlx::Error> {
+    use std::str::FromStr;
+
+    let options = SqliteConnectOptions::from_str(database_url)?
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .busy_timeout(Duration::from_secs(5))
+        .foreign_keys(true);
+
+    SqlitePoolOptions::new()
+        .max_connections(10)
+        .acquire_timeout(Duration::from_secs(3))
+        .connect_with(options)
+        .await
+}
+
 pub async fn check_connection(pool: &DbPool) -> Result<bool, sqlx::Error> {
     sqlx::query("SELECT 1").fetch_one(pool).await.map(|_| true)
 }
+
+/// How the database schema compares with the migrations embedded in this build
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationStatus {
+    /// Latest migration the database has applied
+    pub applied_version: Option<i64>,
+    /// Latest migration embedded in this build
+    pub expected_version: Option<i64>,
+    /// Embedded migrations the database hasn't applied, oldest first
+    pub pending: Vec<i64>,
+    /// Applied migrations this build doesn't know about, e.g. after rolling back to an
+    /// older release
+    pub unknown: Vec<i64>,
+}
+
+impl MigrationStatus {
+    /// Compare the versions recorded in `_sqlx_migrations` with the embedded ones
+    pub fn from_applied(applied: &[i64]) -> Self {
+        Self::compare(&embedded_migrations(), applied)
+    }
+
+    fn compare(embedded: &[i64], applied: &[i64]) -> Self {
+        let applied_set: HashSet<i64> = applied.iter().copied().collect();
+        let embedded_set: HashSet<i64> = embedded.iter().copied().collect();
+        let mut pending: Vec<i64> = embedded
+            .iter()
+            .copied()
+            .filter(|version| !applied_set.contains(version))
+            .collect();
+        pending.sort_unstable();
+        let mut unknown: Vec<i64> = applied
+            .iter()
+            .copied()
+            .filter(|version| !embedded_set.contains(version))
+            .collect();
+        unknown.sort_unstable();
+
+        Self {
+            applied_version: applied.iter().copied().max(),
+            expected_version: embedded.iter().copied().max(),
+            pending,
+            unknown,
+        }
+    }
+
+    /// Whether this build's queries can run against the schema: every embedded migration
+    /// is applied. Unknown ones are tolerated, as newer migrations only add to the schema.
+    pub fn is_current(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// Versions of the migrations embedded in this build
+pub fn embedded_migrations() -> Vec<i64> {
+    MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| migration.version)
+        .collect()
+}
+
+pub async fn migration_status(pool: &DbPool) -> Result<MigrationStatus, sqlx::Error> {
+    let applied: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(MigrationStatus::from_applied(&applied))
+}
+
+/// Create a pool and check it works, retrying with backoff while the database isn't
+/// reachable yet, as when it starts alongside the app in docker-compose or ECS.
+/// Returns the last error once the retries or the wait run out.
+pub async fn connect_with_retry(
+    database_url: &str,
+    retry: &DatabaseConnectConfig,
+) -> Result<DbPool, sqlx::Error> {
+    let started_at = Instant::now();
+    let max_wait = Duration::from_secs(retry.max_wait_secs);
+    let mut attempt = 1;
+
+    loop {
+        let error = match create_pool(database_url).await {
+            Ok(pool) => match check_connection(&pool).await {
+                Ok(_) => {
+                    if attempt > 1 {
+                        tracing::info!(attempt, "Database reachable after retrying");
+                    }
+                    return Ok(pool);
+                }
+                Err(e) => e,
+            },
+            Err(e) => e,
+        };
+
+        let delay = connect_retry_delay(retry, attempt)
+            .filter(|delay| started_at.elapsed() + *delay <= max_wait);
+        let Some(delay) = delay else {
+            tracing::error!(attempt, error = %error, "Giving up connecting to the database");
+            return Err(error);
+        };
+        tracing::warn!(
+            attempt,
+            retry_in_ms = delay.as_millis() as u64,
+            error = %error,
+            "Database not reachable, retrying"
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Wait before the attempt following `attempt`, or None when no retries are left
+fn connect_retry_delay(retry: &DatabaseConnectConfig, attempt: u32) -> Option<Duration> {
+    if attempt > retry.retries {
+        return None;
+    }
+    let factor = 1u64.checked_shl(attempt - 1).unwrap_or(u64::MAX);
+
+    Some(Duration::from_millis(
+        retry.backoff_ms.saturating_mul(factor),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connect_retry_delay_doubles_until_retries_run_out() {
+        let retry = DatabaseConnectConfig {
+            retries: 3,
+            backoff_ms: 500,
+            max_wait_secs: 60,
+        };
+
+        assert_eq!(
+            connect_retry_delay(&retry, 1),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(connect_retry_delay(&retry, 2), Some(Duration::from_secs(1)));
+        assert_eq!(connect_retry_delay(&retry, 3), Some(Duration::from_secs(2)));
+        assert_eq!(connect_retry_delay(&retry, 4), None);
+    }
+
+    #[test]
+    fn test_migration_status_compares_versions() {
+        let current = MigrationStatus::compare(&[1, 2, 3], &[1, 2, 3]);
+        assert!(current.is_current());
+        assert_eq!(current.applied_version, Some(3));
+        assert_eq!(current.expected_version, Some(3));
+
+        let behind = MigrationStatus::compare(&[1, 2, 3], &[1]);
+        assert!(!behind.is_current());
+        assert_eq!(behind.pending, vec![2, 3]);
+
+        let ahead = MigrationStatus::compare(&[1, 2], &[1, 2, 3]);
+        assert!(ahead.is_current());
+        assert_eq!(ahead.unknown, vec![3]);
+        assert_eq!(ahead.applied_version, Some(3));
+    }
+
+    #[test]
+    fn test_connect_retry_delay_without_retries() {
+        let retry = DatabaseConnectConfig {
+            retries: 0,
+            backoff_ms: 500,
+            max_wait_secs: 60,
+        };
+
+        assert_eq!(connect_retry_delay(&retry, 1), None);
+    }
+}
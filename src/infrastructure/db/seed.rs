@@ -0,0 +1,230 @@
+//! Demo data for staging environments and screenshots, loaded with
+//! `feedtape-backend seed`. Accounts that already exist are left alone, so seeding
+//! again only fills in what is missing.
+
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::auth::SessionClient;
+use crate::domain::feed_suggestions::{FeedSuggestion, FeedSuggestionsRepository};
+use crate::domain::plan::CHARACTERS_PER_MINUTE;
+use crate::domain::tts::POLLY_PROVIDER;
+use crate::domain::user::{SubscriptionStatus, SubscriptionTier};
+use crate::error::AppResult;
+use crate::infrastructure::repositories::{
+    FeedRepository, FeedRepositoryApi, HardcodedFeedSuggestionsRepository, RefreshTokenRepository,
+    RefreshTokenRepositoryApi, SynthesisUsage, UsageRepository, UsageRepositoryApi, UserRepository,
+    UserRepositoryApi,
+};
+
+/// OAuth provider of the demo accounts, so they can't collide with real sign-ins
+pub const DEMO_OAUTH_PROVIDER: &str = "demo";
+/// Days of usage history given to each demo account, ending yesterday
+const USAGE_HISTORY_DAYS: i64 = 30;
+
+struct DemoUser {
+    email: &'static str,
+    display_name: &'static str,
+    tier: SubscriptionTier,
+    feeds: usize,
+    /// Typical characters synthesized on a busy day
+    daily_characters: i32,
+}
+
+const DEMO_USERS: &[DemoUser] = &[
+    DemoUser {
+        email: "ana.free@demo.feedtape.app",
+        display_name: "Ana García",
+        tier: SubscriptionTier::Free,
+        feeds: 3,
+        daily_characters: 12_000,
+    },
+    DemoUser {
+        email: "ben.free@demo.feedtape.app",
+        display_name: "Ben Carter",
+        tier: SubscriptionTier::Free,
+        feeds: 1,
+        daily_characters: 4_000,
+    },
+    DemoUser {
+        email: "chloe.pro@demo.feedtape.app",
+        display_name: "Chloé Martin",
+        tier: SubscriptionTier::Pro,
+        feeds: 8,
+        daily_characters: 90_000,
+    },
+    DemoUser {
+        email: "david.pro@demo.feedtape.app",
+        display_name: "David Rossi",
+        tier: SubscriptionTier::Pro,
+        feeds: 5,
+        daily_characters: 45_000,
+    },
+];
+
+/// What a seeding run added
+#[derive(Debug, Default, PartialEq)]
+pub struct SeedReport {
+    pub users: usize,
+    /// Demo accounts that were already there and left untouched
+    pub existing_users: usize,
+    pub feeds: usize,
+    pub usage_days: usize,
+    pub refresh_tokens: usize,
+}
+
+pub struct Seeder {
+    user_repo: Arc<UserRepository>,
+    feed_repo: Arc<FeedRepository>,
+    usage_repo: Arc<UsageRepository>,
+    refresh_token_repo: Arc<RefreshTokenRepository>,
+    refresh_token_expiration_days: i64,
+}
+
+impl Seeder {
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        feed_repo: Arc<FeedRepository>,
+        usage_repo: Arc<UsageRepository>,
+        refresh_token_repo: Arc<RefreshTokenRepository>,
+        refresh_token_expiration_days: i64,
+    ) -> Self {
+        Self {
+            user_repo,
+            feed_repo,
+            usage_repo,
+            refresh_token_repo,
+            refresh_token_expiration_days,
+        }
+    }
+
+    /// Create the demo accounts with feeds from the suggestions list, a month of usage and
+    /// a refresh token each. The tokens are logged so the accounts can be signed in to.
+    pub async fn run(&self) -> AppResult<SeedReport> {
+        let mut report = SeedReport::default();
+        let suggestions = suggestions();
+
+        for (index, demo_user) in DEMO_USERS.iter().enumerate() {
+            if self
+                .user_repo
+                .find_by_email(demo_user.email)
+                .await?
+                .is_some()
+            {
+                tracing::info!(email = demo_user.email, "Demo user already exists");
+                report.existing_users += 1;
+                continue;
+            }
+
+            let user = self
+                .user_repo
+                .create(
+                    demo_user.email,
+                    DEMO_OAUTH_PROVIDER,
+                    demo_user.email,
+                    Some(demo_user.display_name),
+                    None,
+                )
+                .await?;
+            if demo_user.tier == SubscriptionTier::Pro {
+                self.user_repo
+                    .update_subscription(
+                        user.id,
+                        SubscriptionTier::Pro,
+                        SubscriptionStatus::Active,
+                        Some(Utc::now() + Duration::days(365)),
+                    )
+                    .await?;
+            }
+            report.users += 1;
+
+            // Spread users over the categories so their lists differ
+            for suggestion in suggestions
+                .iter()
+                .skip(index)
+                .step_by(DEMO_USERS.len() + 1)
+                .take(demo_user.feeds)
+            {
+                self.feed_repo
+                    .create(
+                        Uuid::new_v4(),
+                        user.id,
+                        &suggestion.url,
+                        &suggestion.title,
+                        Some(suggestion),
+                    )
+                    .await?;
+                report.feeds += 1;
+            }
+
+            report.usage_days += self.seed_usage(user.id, demo_user).await?;
+
+            let refresh_token = Uuid::new_v4().to_string();
+            self.refresh_token_repo
+                .create(
+                    user.id,
+                    &refresh_token,
+                    self.refresh_token_expiration_days,
+                    &SessionClient::default(),
+                )
+                .await?;
+            report.refresh_tokens += 1;
+
+            tracing::info!(
+                email = demo_user.email,
+                user_id = %user.id,
+                refresh_token = %refresh_token,
+                "Seeded demo user"
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Usage for the past days, busier on weekdays and skipping the odd day entirely.
+    /// Returns how many days got usage.
+    async fn seed_usage(&self, user_id: Uuid, demo_user: &DemoUser) -> AppResult<usize> {
+        let today = Utc::now().date_naive();
+        let mut days = 0;
+
+        for days_ago in 1..=USAGE_HISTORY_DAYS {
+            if days_ago % 6 == 0 {
+                continue;
+            }
+            let date = today - Duration::days(days_ago);
+            let articles = 1 + (days_ago % 4) as i32;
+            let characters = demo_user.daily_characters * (3 + (days_ago % 7) as i32) / 10;
+
+            for _ in 0..articles {
+                // Audio as long as the text takes to read
+                let usage = SynthesisUsage {
+                    characters: characters / articles,
+                    audio_seconds: f64::from(characters / articles) * 60.0
+                        / f64::from(CHARACTERS_PER_MINUTE),
+                    provider: POLLY_PROVIDER.to_string(),
+                    engine: "neural".to_string(),
+                };
+                self.usage_repo
+                    .increment_usage(user_id, date, usage, None)
+                    .await?;
+                self.usage_repo
+                    .record_synthesis(date, POLLY_PROVIDER, characters / articles)
+                    .await?;
+            }
+            days += 1;
+        }
+
+        Ok(days)
+    }
+}
+
+fn suggestions() -> Vec<FeedSuggestion> {
+    let repo = HardcodedFeedSuggestionsRepository::new();
+    let category_ids: Vec<String> = repo
+        .get_all_categories()
+        .into_iter()
+        .map(|category| category.id)
+        .collect();
+    repo.get_suggestions_by_categories(&category_ids)
+}
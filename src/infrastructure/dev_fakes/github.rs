@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use crate::error::AppResult;
+use crate::infrastructure::oauth::{GitHubAccessToken, GitHubUser, OAuthProvider};
+
+/// Code the fake authorization page hands back; any other code signs in as an account of
+/// that name, e.g. by changing it to `code=alice` in the callback URL `/auth/oauth/github`
+/// redirects to
+pub const DEFAULT_CODE: &str = "dev";
+
+const ACCESS_TOKEN_PREFIX: &str = "fake-github-token:";
+
+/// Sign-in that never leaves the app: authorization goes straight back to
+/// `redirect_uri`, and each code stands for a made-up account
+pub struct FakeGitHubOAuth {
+    redirect_uri: String,
+}
+
+impl FakeGitHubOAuth {
+    pub fn new(redirect_uri: String) -> Self {
+        Self { redirect_uri }
+    }
+}
+
+#[async_trait]
+impl OAuthProvider for FakeGitHubOAuth {
+    fn get_authorization_url(&self, state: &str) -> String {
+        authorization_url(&self.redirect_uri, state)
+    }
+
+    async fn exchange_code(&self, code: &str) -> AppResult<GitHubAccessToken> {
+        Ok(access_token(code))
+    }
+
+    async fn get_user_info(&self, access_token: &str) -> AppResult<GitHubUser> {
+        Ok(user(access_token))
+    }
+}
+
+/// Where GitHub would send the browser after the user approved access
+pub fn authorization_url(redirect_uri: &str, state: &str) -> String {
+    format!(
+        "{}?code={}&state={}",
+        redirect_uri,
+        DEFAULT_CODE,
+        urlencoding::encode(state)
+    )
+}
+
+pub fn access_token(code: &str) -> GitHubAccessToken {
+    GitHubAccessToken {
+        access_token: format!("{}{}", ACCESS_TOKEN_PREFIX, code),
+        token_type: "bearer".to_string(),
+        scope: "user:email".to_string(),
+    }
+}
+
+/// The account behind a token from `access_token`. The id is derived from the login, so
+/// signing in with the same code finds the same user again.
+pub fn user(access_token: &str) -> GitHubUser {
+    let login = access_token
+        .strip_prefix(ACCESS_TOKEN_PREFIX)
+        .unwrap_or(access_token)
+        .to_lowercase();
+    let digest = Sha256::digest(login.as_bytes());
+    let id = i64::from_be_bytes(digest[..8].try_into().expect("digest is 32 bytes")) & i64::MAX;
+
+    GitHubUser {
+        id,
+        email: Some(format!("{}@example.com", login)),
+        name: Some(format!("Dev {}", login)),
+        avatar_url: None,
+        login,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_code_signs_in_as_the_same_user() {
+        let alice = user(&access_token("alice").access_token);
+
+        assert_eq!(alice.id, user(&access_token("alice").access_token).id);
+        assert_ne!(alice.id, user(&access_token("bob").access_token).id);
+        assert_eq!(alice.email.as_deref(), Some("alice@example.com"));
+    }
+}
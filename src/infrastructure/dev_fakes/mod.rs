@@ -0,0 +1,8 @@
+//! Stand-ins for the external services, enabled with `DEV_FAKE_EXTERNALS`, so the app
+//! runs on a laptop without cloud credentials. Answers depend only on the request, so
+//! the same input always gets the same response.
+
+pub mod github;
+pub mod polly;
+
+pub use polly::fake_polly_client;
@@ -0,0 +1,146 @@
+use aws_sdk_polly::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_polly::Client as PollyClient;
+use aws_smithy_runtime_api::client::http::{
+    HttpClient, HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::http::StatusCode;
+use aws_smithy_types::body::SdkBody;
+use reqwest::Url;
+use serde_json::{json, Value};
+
+use crate::domain::plan::CHARACTERS_PER_MINUTE;
+
+/// Header of a 32 kbps, 44.1 kHz MPEG-1 Layer III frame; with zeroed side info and data
+/// the frame decodes to silence
+const SILENT_FRAME_HEADER: [u8; 4] = [0xFF, 0xFB, 0x10, 0x00];
+const SILENT_FRAME_BYTES: usize = 104;
+/// Each frame holds 1152 samples at 44.1 kHz
+const FRAMES_PER_MINUTE: usize = 60 * 44_100 / 1152;
+
+/// A Polly client answering from memory: speech requests get silent MP3 lasting as long
+/// as the text would take to read, and voice listings succeed. Nothing leaves the
+/// process, so the credentials are placeholders.
+pub fn fake_polly_client(region: &str) -> PollyClient {
+    let config = aws_sdk_polly::Config::builder()
+        .behavior_version(BehaviorVersion::latest())
+        .region(Region::new(region.to_string()))
+        .credentials_provider(Credentials::new("fake", "fake", None, None, "dev-fakes"))
+        .http_client(FakePolly)
+        .build();
+
+    PollyClient::from_conf(config)
+}
+
+#[derive(Debug, Clone)]
+struct FakePolly;
+
+impl HttpClient for FakePolly {
+    fn http_connector(
+        &self,
+        _settings: &HttpConnectorSettings,
+        _components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        SharedHttpConnector::new(self.clone())
+    }
+}
+
+impl HttpConnector for FakePolly {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        HttpConnectorFuture::ready(Ok(respond(&request)))
+    }
+}
+
+fn respond(request: &HttpRequest) -> HttpResponse {
+    // The SDK hands over the full endpoint URL
+    let url = Url::parse(request.uri()).ok();
+    let path = url.as_ref().map(Url::path).unwrap_or_default();
+    match (request.method(), path.trim_end_matches('/')) {
+        ("POST", "/v1/speech") => {
+            let text_chars = request
+                .body()
+                .bytes()
+                .and_then(|body| serde_json::from_slice::<Value>(body).ok())
+                .and_then(|body| body["Text"].as_str().map(|text| text.chars().count()))
+                .unwrap_or(0);
+            let mut response = response(200, silent_mp3(text_chars));
+            response.headers_mut().insert("Content-Type", "audio/mpeg");
+            response
+                .headers_mut()
+                .insert("x-amzn-RequestCharacters", text_chars.to_string());
+            response
+        }
+        ("GET", "/v1/voices") => json_response(200, json!({ "Voices": [] })),
+        _ => json_response(
+            404,
+            json!({ "message": format!("{} {} is not faked", request.method(), path) }),
+        ),
+    }
+}
+
+/// Silent audio as long as reading `text_chars` characters takes, at least one frame
+fn silent_mp3(text_chars: usize) -> Vec<u8> {
+    let frames = (text_chars * FRAMES_PER_MINUTE / CHARACTERS_PER_MINUTE as usize).max(1);
+    let mut frame = [0u8; SILENT_FRAME_BYTES];
+    frame[..SILENT_FRAME_HEADER.len()].copy_from_slice(&SILENT_FRAME_HEADER);
+    frame.repeat(frames)
+}
+
+fn json_response(status: u16, body: Value) -> HttpResponse {
+    let mut response = response(status, body.to_string().into_bytes());
+    response
+        .headers_mut()
+        .insert("Content-Type", "application/json");
+    response
+}
+
+fn response(status: u16, body: Vec<u8>) -> HttpResponse {
+    HttpResponse::new(
+        StatusCode::try_from(status).expect("valid status code"),
+        SdkBody::from(body),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_polly::types::{OutputFormat, VoiceId};
+
+    async fn synthesize(client: &PollyClient, text: &str) -> Vec<u8> {
+        client
+            .synthesize_speech()
+            .text(text)
+            .voice_id(VoiceId::Joanna)
+            .output_format(OutputFormat::Mp3)
+            .send()
+            .await
+            .unwrap()
+            .audio_stream
+            .collect()
+            .await
+            .unwrap()
+            .into_bytes()
+            .to_vec()
+    }
+
+    #[tokio::test]
+    async fn test_speech_is_deterministic_and_scales_with_the_text() {
+        let client = fake_polly_client("us-east-1");
+
+        let short = synthesize(&client, "Hello there").await;
+        assert_eq!(short, synthesize(&client, "Hello there").await);
+        assert_eq!(&short[..4], &SILENT_FRAME_HEADER);
+
+        let long = synthesize(&client, &"word ".repeat(400)).await;
+        assert!(long.len() > short.len());
+        assert_eq!(long.len() % SILENT_FRAME_BYTES, 0);
+    }
+
+    #[tokio::test]
+    async fn test_voice_listing_succeeds() {
+        let client = fake_polly_client("us-east-1");
+
+        assert!(client.describe_voices().send().await.is_ok());
+    }
+}
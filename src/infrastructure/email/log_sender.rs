@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+
+use super::{EmailMessage, EmailSender};
+use crate::error::AppResult;
+
+/// Writes emails to the log instead of sending them; the default when no provider is
+/// configured, so development setups can follow links from the log
+pub struct LogEmailSender;
+
+impl LogEmailSender {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LogEmailSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EmailSender for LogEmailSender {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        tracing::info!(
+            to = %message.to,
+            subject = %message.subject,
+            body = %message.text_body,
+            "Email not sent (EMAIL_BACKEND=log)"
+        );
+        Ok(())
+    }
+}
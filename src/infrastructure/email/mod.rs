@@ -0,0 +1,43 @@
+pub mod log_sender;
+pub mod ses;
+pub mod smtp;
+pub mod templates;
+
+pub use log_sender::LogEmailSender;
+pub use ses::SesEmailSender;
+pub use smtp::SmtpEmailSender;
+pub use templates::EmailTemplate;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::error::AppResult;
+use crate::infrastructure::config::{Config, EmailBackend};
+
+/// A rendered email to one recipient, with plain text and HTML versions of the body
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: String,
+}
+
+/// Delivers transactional email. Every feature that mails users goes through this
+/// rather than talking to a provider itself.
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()>;
+}
+
+/// Build the sender selected by `EMAIL_BACKEND`
+pub async fn create_email_sender(config: &Config) -> AppResult<Arc<dyn EmailSender>> {
+    let from = config.email.from.clone();
+    match &config.email.backend {
+        EmailBackend::Log => Ok(Arc::new(LogEmailSender::new())),
+        EmailBackend::Ses => Ok(Arc::new(
+            SesEmailSender::connect(&config.aws_region, from).await,
+        )),
+        EmailBackend::Smtp(smtp) => Ok(Arc::new(SmtpEmailSender::new(smtp, from)?)),
+    }
+}
@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use aws_sdk_sesv2::types::{Body, Content, Destination, EmailContent, Message};
+use aws_sdk_sesv2::Client as SesClient;
+
+use super::{EmailMessage, EmailSender};
+use crate::error::{AppError, AppResult};
+
+/// Sends through Amazon SES, with the same AWS credentials as Polly
+pub struct SesEmailSender {
+    client: SesClient,
+    from: String,
+}
+
+impl SesEmailSender {
+    pub async fn connect(region: &str, from: String) -> Self {
+        let aws_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new(region.to_string()))
+            .load()
+            .await;
+
+        Self {
+            client: SesClient::new(&aws_config),
+            from,
+        }
+    }
+}
+
+#[async_trait]
+impl EmailSender for SesEmailSender {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        let content = email_content(message)
+            .map_err(|e| AppError::Internal(format!("Invalid email: {}", e)))?;
+
+        self.client
+            .send_email()
+            .from_email_address(&self.from)
+            .destination(Destination::builder().to_addresses(&message.to).build())
+            .content(content)
+            .send()
+            .await
+            .map_err(|e| AppError::Internal(format!("SES send failed: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+fn email_content(message: &EmailMessage) -> Result<EmailContent, aws_sdk_sesv2::error::BuildError> {
+    let utf8 = |data: &str| Content::builder().data(data).charset("UTF-8").build();
+    let body = Body::builder()
+        .text(utf8(&message.text_body)?)
+        .html(utf8(&message.html_body)?)
+        .build();
+    let simple = Message::builder()
+        .subject(utf8(&message.subject)?)
+        .body(body)
+        .build();
+
+    Ok(EmailContent::builder().simple(simple).build())
+}
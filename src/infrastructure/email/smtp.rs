@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use super::{EmailMessage, EmailSender};
+use crate::error::{AppError, AppResult};
+use crate::infrastructure::config::{SmtpConfig, SmtpTls};
+
+/// Sends through any SMTP relay, e.g. a provider's submission port or a local catcher
+/// like Mailpit
+pub struct SmtpEmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpEmailSender {
+    pub fn new(config: &SmtpConfig, from: String) -> AppResult<Self> {
+        let from = from
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid EMAIL_FROM: {}", e)))?;
+        let builder = match config.tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host),
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host),
+            SmtpTls::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &config.host,
+            )),
+        }
+        .map_err(|e| AppError::Internal(format!("Invalid SMTP_HOST: {}", e)))?
+        .port(config.port);
+        let builder = match (&config.username, &config.password) {
+            (Some(username), Some(password)) => {
+                builder.credentials(Credentials::new(username.clone(), password.clone()))
+            }
+            _ => builder,
+        };
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl EmailSender for SmtpEmailSender {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        let to: Mailbox = message
+            .to
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid recipient: {}", e)))?;
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(&message.subject)
+            .multipart(MultiPart::alternative_plain_html(
+                message.text_body.clone(),
+                message.html_body.clone(),
+            ))
+            .map_err(|e| AppError::Internal(format!("Invalid email: {}", e)))?;
+
+        self.transport
+            .send(email)
+            .await
+            .map_err(|e| AppError::Internal(format!("SMTP send failed: {}", e)))?;
+
+        Ok(())
+    }
+}
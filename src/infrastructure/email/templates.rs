@@ -0,0 +1,320 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use std::cmp::Ordering;
+
+use super::EmailMessage;
+use crate::domain::plan::CHARACTERS_PER_MINUTE;
+use crate::infrastructure::sanitizer::escape_html;
+
+/// The emails FeedTape sends. Each renders to a subject, a few paragraphs and an optional
+/// call to action, laid out the same way in the text and HTML bodies.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmailTemplate {
+    /// Confirm that the address belongs to the user
+    Verification { link: String },
+    /// Sign in without going through an OAuth provider
+    MagicLink {
+        link: String,
+        expires_in_minutes: i64,
+    },
+    /// The user has used most of their quota for the period
+    QuotaWarning {
+        characters_used: i32,
+        character_limit: i32,
+        resets_at: DateTime<Utc>,
+    },
+    /// A digest of new articles from followed feeds is ready to listen to
+    DigestReady { article_count: usize, link: String },
+    /// A feed the user submitted was added to the suggestions
+    SuggestionApproved { title: String },
+    /// A feed the user submitted won't be suggested
+    SuggestionRejected { title: String, reason: String },
+    /// The past week's listening, for users who opted in
+    WeeklySummary {
+        week_start: NaiveDate,
+        minutes: i64,
+        previous_minutes: i64,
+        articles: i64,
+        /// Most played feeds by title with their minutes
+        top_feeds: Vec<(String, i64)>,
+        quota_percent: i64,
+        unsubscribe_link: String,
+    },
+}
+
+struct Content {
+    subject: String,
+    paragraphs: Vec<String>,
+    /// Button label and link
+    action: Option<(&'static str, String)>,
+}
+
+impl EmailTemplate {
+    pub fn render(&self, to: &str) -> EmailMessage {
+        let content = self.content();
+
+        let mut text_body = content.paragraphs.join("\n\n");
+        if let Some((label, link)) = &content.action {
+            text_body.push_str(&format!("\n\n{}: {}", label, link));
+        }
+        text_body.push_str("\n\n— FeedTape\n");
+        if let Some(link) = self.unsubscribe_link() {
+            text_body.push_str(&format!("\nTo stop these emails, unsubscribe: {}\n", link));
+        }
+
+        let mut html_body = String::from(
+            "<!DOCTYPE html><html><body style=\"font-family: sans-serif; line-height: 1.5\">",
+        );
+        for paragraph in &content.paragraphs {
+            html_body.push_str(&format!("<p>{}</p>", escape_html(paragraph)));
+        }
+        if let Some((label, link)) = &content.action {
+            html_body.push_str(&format!(
+                "<p><a href=\"{}\" style=\"display: inline-block; padding: 10px 18px; \
+                 background: #ff5a36; color: #ffffff; text-decoration: none; \
+                 border-radius: 6px\">{}</a></p>",
+                escape_html(link),
+                escape_html(label)
+            ));
+        }
+        html_body.push_str("<p>— FeedTape</p>");
+        if let Some(link) = self.unsubscribe_link() {
+            html_body.push_str(&format!(
+                "<p style=\"font-size: 12px; color: #888888\">To stop these emails, \
+                 <a href=\"{}\" style=\"color: #888888\">unsubscribe</a>.</p>",
+                escape_html(link)
+            ));
+        }
+        html_body.push_str("</body></html>");
+
+        EmailMessage {
+            to: to.to_string(),
+            subject: content.subject,
+            text_body,
+            html_body,
+        }
+    }
+
+    /// Link that stops emails like this one, for those the user opted into
+    fn unsubscribe_link(&self) -> Option<&str> {
+        match self {
+            EmailTemplate::WeeklySummary {
+                unsubscribe_link, ..
+            } => Some(unsubscribe_link),
+            _ => None,
+        }
+    }
+
+    fn content(&self) -> Content {
+        match self {
+            EmailTemplate::Verification { link } => Content {
+                subject: "Confirm your email address".to_string(),
+                paragraphs: vec![
+                    "Please confirm this address so we can reach you about your account."
+                        .to_string(),
+                    "If you didn't sign up for FeedTape, you can ignore this email.".to_string(),
+                ],
+                action: Some(("Confirm email", link.clone())),
+            },
+            EmailTemplate::MagicLink {
+                link,
+                expires_in_minutes,
+            } => Content {
+                subject: "Your FeedTape sign-in link".to_string(),
+                paragraphs: vec![
+                    format!(
+                        "Use the link below to sign in. It works once and expires in {} minutes.",
+                        expires_in_minutes
+                    ),
+                    "If you didn't ask to sign in, you can ignore this email.".to_string(),
+                ],
+                action: Some(("Sign in", link.clone())),
+            },
+            EmailTemplate::QuotaWarning {
+                characters_used,
+                character_limit,
+                resets_at,
+            } => {
+                let percent =
+                    i64::from(*characters_used) * 100 / i64::from(*character_limit).max(1);
+                let minutes_left =
+                    (character_limit - characters_used).max(0) / CHARACTERS_PER_MINUTE;
+                Content {
+                    subject: format!("You've used {}% of your listening time", percent),
+                    paragraphs: vec![
+                        format!(
+                            "You have about {} minutes of audio left for this period.",
+                            minutes_left
+                        ),
+                        format!(
+                            "Your quota resets on {} UTC.",
+                            resets_at.format("%B %-d at %H:%M")
+                        ),
+                    ],
+                    action: None,
+                }
+            }
+            EmailTemplate::DigestReady {
+                article_count,
+                link,
+            } => {
+                let (noun, verb) = if *article_count == 1 {
+                    ("article", "is")
+                } else {
+                    ("articles", "are")
+                };
+                Content {
+                    subject: "Your FeedTape digest is ready".to_string(),
+                    paragraphs: vec![format!(
+                        "{} new {} from your feeds {} ready to listen to.",
+                        article_count, noun, verb
+                    )],
+                    action: Some(("Listen now", link.clone())),
+                }
+            }
+            EmailTemplate::SuggestionApproved { title } => Content {
+                subject: "Your feed suggestion was approved".to_string(),
+                paragraphs: vec![
+                    format!("Thanks for suggesting \"{}\".", title),
+                    "It is now listed in the feed suggestions for other listeners to find."
+                        .to_string(),
+                ],
+                action: None,
+            },
+            EmailTemplate::SuggestionRejected { title, reason } => Content {
+                subject: "About your feed suggestion".to_string(),
+                paragraphs: vec![
+                    format!(
+                        "Thanks for suggesting \"{}\". We won't be adding it to the feed suggestions.",
+                        title
+                    ),
+                    format!("Reason: {}", reason),
+                    "You can still follow it yourself from the app.".to_string(),
+                ],
+                action: None,
+            },
+            EmailTemplate::WeeklySummary {
+                week_start,
+                minutes,
+                previous_minutes,
+                articles,
+                top_feeds,
+                quota_percent,
+                ..
+            } => {
+                let mut paragraphs = vec![format!(
+                    "In the week of {} you listened to {} of audio across {}.",
+                    week_start.format("%B %-d"),
+                    count(*minutes, "minute"),
+                    count(*articles, "article")
+                )];
+                if *previous_minutes > 0 {
+                    paragraphs.push(match minutes.cmp(previous_minutes) {
+                        Ordering::Greater => format!(
+                            "That's {} more than the week before.",
+                            count(minutes - previous_minutes, "minute")
+                        ),
+                        Ordering::Less => format!(
+                            "That's {} fewer than the week before.",
+                            count(previous_minutes - minutes, "minute")
+                        ),
+                        Ordering::Equal => "That's as much as the week before.".to_string(),
+                    });
+                }
+                if !top_feeds.is_empty() {
+                    let feeds: Vec<String> = top_feeds
+                        .iter()
+                        .map(|(title, minutes)| format!("{} ({} min)", title, minutes))
+                        .collect();
+                    paragraphs.push(format!("Most played: {}.", feeds.join(", ")));
+                }
+                paragraphs.push(format!(
+                    "You've used {}% of your listening time for the current period.",
+                    quota_percent
+                ));
+                Content {
+                    subject: format!("Your week on FeedTape: {}", count(*minutes, "minute")),
+                    paragraphs,
+                    action: None,
+                }
+            }
+        }
+    }
+}
+
+/// `count` followed by `noun`, made plural unless it is one
+fn count(count: i64, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_action_link_is_in_both_bodies() {
+        let message = EmailTemplate::MagicLink {
+            link: "https://feedtape.app/auth/magic?token=a&b".to_string(),
+            expires_in_minutes: 15,
+        }
+        .render("user@example.com");
+
+        assert_eq!(message.to, "user@example.com");
+        assert!(message
+            .text_body
+            .contains("Sign in: https://feedtape.app/auth/magic?token=a&b"));
+        assert!(message
+            .html_body
+            .contains("href=\"https://feedtape.app/auth/magic?token=a&amp;b\""));
+        assert!(message.text_body.contains("15 minutes"));
+    }
+
+    #[test]
+    fn test_quota_warning_reports_minutes_left() {
+        let message = EmailTemplate::QuotaWarning {
+            characters_used: 16_000,
+            character_limit: 20_000,
+            resets_at: Utc.with_ymd_and_hms(2025, 10, 17, 0, 0, 0).unwrap(),
+        }
+        .render("user@example.com");
+
+        assert_eq!(message.subject, "You've used 80% of your listening time");
+        assert!(message.text_body.contains("about 4 minutes"));
+        assert!(message.text_body.contains("October 17 at 00:00"));
+    }
+
+    #[test]
+    fn test_weekly_summary_compares_weeks_and_links_unsubscribe() {
+        let message = EmailTemplate::WeeklySummary {
+            week_start: NaiveDate::from_ymd_opt(2026, 10, 5).unwrap(),
+            minutes: 42,
+            previous_minutes: 30,
+            articles: 1,
+            top_feeds: vec![("Sports".to_string(), 30), ("Tech".to_string(), 12)],
+            quota_percent: 35,
+            unsubscribe_link: "https://api.feedtape.app/email/unsubscribe?token=a&b".to_string(),
+        }
+        .render("user@example.com");
+
+        assert_eq!(message.subject, "Your week on FeedTape: 42 minutes");
+        assert!(message.text_body.contains(
+            "In the week of October 5 you listened to 42 minutes of audio across 1 article."
+        ));
+        assert!(message
+            .text_body
+            .contains("12 minutes more than the week before"));
+        assert!(message
+            .text_body
+            .contains("Most played: Sports (30 min), Tech (12 min)."));
+        assert!(message
+            .text_body
+            .contains("unsubscribe: https://api.feedtape.app/email/unsubscribe?token=a&b"));
+        assert!(message
+            .html_body
+            .contains("href=\"https://api.feedtape.app/email/unsubscribe?token=a&amp;b\""));
+    }
+}
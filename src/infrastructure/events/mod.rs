@@ -0,0 +1,96 @@
+use serde_json::Value;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Events buffered per subscriber before a slow one starts missing them
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// What happened, sent to clients as the SSE event name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccountEventKind {
+    /// Characters were used from the quota
+    UsageUpdated,
+    /// A background task started by the user moved forward or finished
+    JobProgress,
+    /// A feed published entries since it was last checked
+    NewArticles,
+}
+
+impl AccountEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountEventKind::UsageUpdated => "usage_updated",
+            AccountEventKind::JobProgress => "job_progress",
+            AccountEventKind::NewArticles => "new_articles",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountEvent {
+    pub user_id: Uuid,
+    pub kind: AccountEventKind,
+    pub data: Value,
+}
+
+/// In-process broadcast channel services publish account events to, and that clients
+/// connected to `/api/events` read from. Each subscriber sees every event and keeps the
+/// ones for its user. Other parts of the backend react to `domain::events` instead.
+///
+/// Events only reach clients connected to this instance and are not stored; clients
+/// catch up through the regular endpoints after reconnecting.
+pub struct AccountEventStream {
+    sender: broadcast::Sender<AccountEvent>,
+}
+
+impl AccountEventStream {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, user_id: Uuid, kind: AccountEventKind, data: Value) {
+        // Fails only when nobody is listening, which is the usual case
+        let _ = self.sender.send(AccountEvent {
+            user_id,
+            kind,
+            data,
+        });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AccountEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for AccountEventStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events_published_after_subscribing() {
+        let stream = AccountEventStream::new();
+        let user_id = Uuid::new_v4();
+        stream.publish(user_id, AccountEventKind::UsageUpdated, json!({}));
+
+        let mut receiver = stream.subscribe();
+        stream.publish(
+            user_id,
+            AccountEventKind::NewArticles,
+            json!({ "new_articles": 2 }),
+        );
+
+        let event = receiver.recv().await.unwrap();
+        assert_eq!(event.user_id, user_id);
+        assert_eq!(event.kind, AccountEventKind::NewArticles);
+        assert_eq!(event.data["new_articles"], 2);
+        assert!(receiver.try_recv().is_err());
+    }
+}
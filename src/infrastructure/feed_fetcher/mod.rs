@@ -0,0 +1,343 @@
+use crate::infrastructure::sanitizer::sanitize_text;
+use chrono::{DateTime, Utc};
+use reqwest::{header::LOCATION, Url};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_REDIRECTS: usize = 5;
+const MAX_FEED_SIZE: usize = 5 * 1024 * 1024;
+
+/// Metadata extracted from a fetched RSS/Atom document
+#[derive(Debug, Clone)]
+pub struct FetchedFeed {
+    pub title: Option<String>,
+    /// Entries currently in the feed that carry a date
+    pub entries: Vec<FetchedEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FetchedEntry {
+    /// Publication (or last update) timestamp
+    pub published_at: DateTime<Utc>,
+    /// For recognizing the same story in other feeds
+    pub title: Option<String>,
+    /// Title and summary, one per line, for matching mute rules against
+    pub text: String,
+}
+
+impl FetchedFeed {
+    pub fn published_dates(&self) -> Vec<DateTime<Utc>> {
+        self.entries
+            .iter()
+            .map(|entry| entry.published_at)
+            .collect()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FeedFetchError {
+    /// The URL could not be fetched (DNS, connection, HTTP status, blocked host)
+    #[error("{0}")]
+    Unreachable(String),
+    /// The URL was fetched but the body is not a valid feed
+    #[error("{0}")]
+    Parse(String),
+}
+
+/// HTTP client that downloads and parses feeds on behalf of users
+pub struct FeedFetcher {
+    /// Which addresses requests may go to; `is_public_ip` outside of tests
+    allow_address: fn(IpAddr) -> bool,
+}
+
+impl FeedFetcher {
+    pub fn new() -> Self {
+        Self {
+            allow_address: is_public_ip,
+        }
+    }
+
+    /// Mock servers listen on loopback, so in tests it stands in for a public host
+    #[cfg(test)]
+    pub(crate) fn for_loopback() -> Self {
+        Self {
+            allow_address: |ip| ip == IpAddr::from([127, 0, 0, 1]),
+        }
+    }
+
+    /// Fetch a feed URL and parse it as RSS/Atom/JSON Feed
+    pub async fn fetch(&self, url: &str) -> Result<FetchedFeed, FeedFetchError> {
+        let mut url =
+            Url::parse(url).map_err(|_| FeedFetchError::Unreachable("Invalid URL".to_string()))?;
+
+        // Users control the URL, and whoever serves it controls where it redirects, so
+        // every hop is checked and connects to the addresses that were checked
+        let mut redirects = 0;
+        let response = loop {
+            let response = self.get(&url).await?;
+            if !response.status().is_redirection() {
+                break response;
+            }
+
+            redirects += 1;
+            if redirects > MAX_REDIRECTS {
+                return Err(FeedFetchError::Unreachable(
+                    "Too many redirects".to_string(),
+                ));
+            }
+            url = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| url.join(location).ok())
+                .ok_or_else(|| {
+                    FeedFetchError::Unreachable("Redirect without a valid location".to_string())
+                })?;
+        };
+
+        if !response.status().is_success() {
+            return Err(FeedFetchError::Unreachable(format!(
+                "HTTP status {}",
+                response.status().as_u16()
+            )));
+        }
+
+        if response
+            .content_length()
+            .is_some_and(|length| length as usize > MAX_FEED_SIZE)
+        {
+            return Err(FeedFetchError::Parse("Feed is too large".to_string()));
+        }
+
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| FeedFetchError::Unreachable(format!("Failed to read body: {}", e)))?;
+
+        if body.len() > MAX_FEED_SIZE {
+            return Err(FeedFetchError::Parse("Feed is too large".to_string()));
+        }
+
+        let feed = feed_rs::parser::parse(body.as_ref())
+            .map_err(|e| FeedFetchError::Parse(format!("Not a valid RSS/Atom feed: {}", e)))?;
+
+        Ok(FetchedFeed {
+            title: feed
+                .title
+                .map(|title| sanitize_text(&title.content))
+                .filter(|title| !title.is_empty()),
+            entries: feed
+                .entries
+                .into_iter()
+                .filter_map(|entry| {
+                    let published_at = entry.published.or(entry.updated)?;
+                    let title = entry
+                        .title
+                        .map(|title| sanitize_text(&title.content))
+                        .filter(|title| !title.is_empty());
+                    let summary = entry.summary.map(|summary| sanitize_text(&summary.content));
+                    let text = [title.clone(), summary]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    Some(FetchedEntry {
+                        published_at,
+                        title,
+                        text,
+                    })
+                })
+                .collect(),
+        })
+    }
+}
+
+impl FeedFetcher {
+    /// One request, without following redirects
+    async fn get(&self, url: &Url) -> Result<reqwest::Response, FeedFetchError> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(FeedFetchError::Unreachable("Invalid URL".to_string()));
+        }
+        let addresses = allowed_addresses(url, self.allow_address)
+            .await
+            .map_err(FeedFetchError::Unreachable)?;
+
+        // Pinned to the checked addresses so a second lookup can't swap them
+        let mut http_client = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .user_agent("FeedTape-Backend")
+            .redirect(reqwest::redirect::Policy::none());
+        if let Some(domain) = url.domain() {
+            http_client = http_client.resolve_to_addrs(domain, &addresses);
+        }
+        let http_client = http_client
+            .build()
+            .map_err(|e| FeedFetchError::Unreachable(format!("Request failed: {}", e)))?;
+
+        http_client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|e| FeedFetchError::Unreachable(format!("Request failed: {}", e)))
+    }
+}
+
+impl Default for FeedFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fail unless every address the URL's host resolves to is public. Shared by everything
+/// that makes requests to user-supplied URLs.
+pub(crate) async fn ensure_public_host(url: &Url) -> Result<(), String> {
+    allowed_addresses(url, is_public_ip).await.map(|_| ())
+}
+
+/// The addresses the URL's host resolves to, if `allow_address` accepts all of them
+async fn allowed_addresses(
+    url: &Url,
+    allow_address: fn(IpAddr) -> bool,
+) -> Result<Vec<SocketAddr>, String> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| "URL has no host".to_string())?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    let addresses: Vec<_> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("DNS lookup failed: {}", e))?
+        .collect();
+
+    if addresses.iter().any(|address| !allow_address(address.ip())) {
+        return Err("Host resolves to a non-public address".to_string());
+    }
+
+    Ok(addresses)
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let octets = v4.octets();
+            let is_shared = octets[0] == 100 && (octets[1] & 0xC0) == 64; // 100.64.0.0/10
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_broadcast()
+                || v4.is_unspecified()
+                || v4.is_documentation()
+                || is_shared)
+        }
+        IpAddr::V6(v6) => {
+            let first_segment = v6.segments()[0];
+            let is_unique_local = (first_segment & 0xfe00) == 0xfc00;
+            let is_link_local = (first_segment & 0xffc0) == 0xfe80;
+            match v6.to_ipv4_mapped() {
+                Some(v4) => is_public_ip(IpAddr::V4(v4)),
+                None => {
+                    !(v6.is_loopback() || v6.is_unspecified() || is_unique_local || is_link_local)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const RSS: &str = r#"<rss version="2.0"><channel><title>Redirected</title></channel></rss>"#;
+
+    fn fetcher() -> FeedFetcher {
+        FeedFetcher::for_loopback()
+    }
+
+    fn redirect_to(location: &str) -> ResponseTemplate {
+        ResponseTemplate::new(302).insert_header("Location", location)
+    }
+
+    #[tokio::test]
+    async fn test_follows_redirects_to_allowed_hosts() {
+        let server = MockServer::start().await;
+        Mock::given(path("/old"))
+            .respond_with(redirect_to("/rss"))
+            .mount(&server)
+            .await;
+        Mock::given(path("/rss"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(RSS))
+            .mount(&server)
+            .await;
+
+        let feed = fetcher()
+            .fetch(&format!("{}/old", server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(feed.title.as_deref(), Some("Redirected"));
+    }
+
+    #[tokio::test]
+    async fn test_refuses_redirects_to_private_addresses() {
+        let server = MockServer::start().await;
+        Mock::given(path("/rss"))
+            .respond_with(redirect_to("http://169.254.169.254/latest/meta-data/"))
+            .mount(&server)
+            .await;
+
+        let error = fetcher()
+            .fetch(&format!("{}/rss", server.uri()))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "Host resolves to a non-public address");
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_on_redirect_loops() {
+        let server = MockServer::start().await;
+        Mock::given(path("/rss"))
+            .respond_with(redirect_to("/rss"))
+            .mount(&server)
+            .await;
+
+        let error = fetcher()
+            .fetch(&format!("{}/rss", server.uri()))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "Too many redirects");
+    }
+
+    #[tokio::test]
+    async fn test_feed_text_is_sanitized() {
+        let server = MockServer::start().await;
+        let rss = r#"<rss version="2.0"><channel>
+            <title>&lt;b&gt;Daily&lt;/b&gt; news&lt;script&gt;steal()&lt;/script&gt;</title>
+            <item>
+                <title>Sponsored: &lt;img src=x onerror=steal()&gt;laptops</title>
+                <description>&lt;p&gt;Buy &lt;i&gt;now&lt;/i&gt;&lt;/p&gt;</description>
+                <pubDate>Mon, 12 Oct 2026 08:00:00 GMT</pubDate>
+            </item>
+        </channel></rss>"#;
+        Mock::given(path("/rss"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(rss))
+            .mount(&server)
+            .await;
+
+        let feed = fetcher()
+            .fetch(&format!("{}/rss", server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(feed.title.as_deref(), Some("Daily news"));
+        assert_eq!(feed.entries.len(), 1);
+        assert_eq!(feed.entries[0].title.as_deref(), Some("Sponsored: laptops"));
+        assert_eq!(feed.entries[0].text, "Sponsored: laptops\nBuy now");
+    }
+}
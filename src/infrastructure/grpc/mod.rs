@@ -0,0 +1,107 @@
+//! Server for the internal gRPC API, listening next to the HTTP server
+
+use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{service::Interceptor, transport::Server, Request, Status};
+
+use crate::controllers::grpc::{
+    proto::internal_service_server::InternalServiceServer, GrpcController,
+};
+use crate::infrastructure::config::GrpcConfig;
+
+/// Accepts calls carrying `authorization: Bearer <key>` with one of the configured keys
+#[derive(Clone)]
+pub struct ApiKeyInterceptor {
+    /// SHA-256 of each key, so a failed comparison says nothing about the key itself
+    key_digests: Arc<Vec<[u8; 32]>>,
+}
+
+impl ApiKeyInterceptor {
+    pub fn new(api_keys: &[String]) -> Self {
+        Self {
+            key_digests: Arc::new(api_keys.iter().map(|key| digest(key)).collect()),
+        }
+    }
+}
+
+impl Interceptor for ApiKeyInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let key = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("Missing API key"))?;
+
+        if !self.key_digests.contains(&digest(key)) {
+            return Err(Status::unauthenticated("Invalid API key"));
+        }
+        Ok(request)
+    }
+}
+
+fn digest(key: &str) -> [u8; 32] {
+    Sha256::digest(key.as_bytes()).into()
+}
+
+/// Bind the gRPC port and serve in the background. Binding happens before returning, so
+/// a taken port fails startup instead of leaving the API silently missing.
+pub async fn start_grpc_server(
+    host: &str,
+    grpc: &GrpcConfig,
+    controller: Arc<GrpcController>,
+) -> Result<JoinHandle<()>, Box<dyn std::error::Error>> {
+    let addr: SocketAddr = format!("{}:{}", host, grpc.port).parse()?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("gRPC server listening on {}", listener.local_addr()?);
+
+    let service = InternalServiceServer::from_arc(controller);
+    let interceptor = ApiKeyInterceptor::new(&grpc.api_keys);
+    let server = Server::builder()
+        .add_service(tonic::service::interceptor::InterceptedService::new(
+            service,
+            interceptor,
+        ))
+        .serve_with_incoming(TcpListenerStream::new(listener));
+
+    Ok(tokio::spawn(async move {
+        if let Err(e) = server.await {
+            tracing::error!("gRPC server stopped: {}", e);
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_with(authorization: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(authorization) = authorization {
+            request
+                .metadata_mut()
+                .insert("authorization", authorization.parse().unwrap());
+        }
+        request
+    }
+
+    #[test]
+    fn test_only_configured_keys_are_accepted() {
+        let mut interceptor =
+            ApiKeyInterceptor::new(&["old-key".to_string(), "new-key".to_string()]);
+
+        assert!(interceptor
+            .call(request_with(Some("Bearer old-key")))
+            .is_ok());
+        assert!(interceptor
+            .call(request_with(Some("Bearer new-key")))
+            .is_ok());
+        for authorization in [None, Some("Bearer other-key"), Some("new-key")] {
+            let status = interceptor.call(request_with(authorization)).unwrap_err();
+            assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        }
+    }
+}
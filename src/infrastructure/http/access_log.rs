@@ -0,0 +1,202 @@
+use axum::{
+    body::HttpBody,
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::infrastructure::config::AccessLogConfig;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Headers that carry credentials, dropped from the log whatever their value
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+];
+
+/// Query parameters holding OAuth codes and similar one-time secrets
+const SENSITIVE_PARAMS: &[&str] = &["code", "state", "key", "signature"];
+
+/// Header and parameter names containing any of these are redacted as well
+const SENSITIVE_FRAGMENTS: &[&str] = &["token", "secret", "password"];
+
+/// Decides which requests make it into the access log
+pub struct AccessLogger {
+    config: AccessLogConfig,
+    sampled_requests: AtomicU64,
+}
+
+impl AccessLogger {
+    pub fn new(config: AccessLogConfig) -> Self {
+        Self {
+            config,
+            sampled_requests: AtomicU64::new(0),
+        }
+    }
+
+    /// Failures are always logged. Successful requests to the sampled paths are logged
+    /// one in `sample_every`, everything else every time.
+    fn should_log(&self, path: &str, status: StatusCode) -> bool {
+        if status.is_client_error() || status.is_server_error() {
+            return true;
+        }
+        let sampled = self
+            .config
+            .sampled_paths
+            .iter()
+            .any(|prefix| path.starts_with(prefix.as_str()));
+        if !sampled {
+            return true;
+        }
+
+        self.sampled_requests.fetch_add(1, Ordering::Relaxed) % self.config.sample_every == 0
+    }
+}
+
+/// Log one line per request with its method, path, status, latency and body sizes.
+/// Credentials in headers and the query string never reach the log.
+pub async fn access_log_middleware(
+    State(logger): State<Arc<AccessLogger>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().map(redact_query);
+    let headers = redact_headers(request.headers());
+    let request_bytes = content_length(request.headers());
+
+    let response = next.run(request).await;
+
+    let status = response.status();
+    if !logger.should_log(&path, status) {
+        return response;
+    }
+    let response_bytes = response
+        .body()
+        .size_hint()
+        .exact()
+        .or_else(|| content_length(response.headers()));
+
+    tracing::info!(
+        target: "access_log",
+        method = %method,
+        path = %path,
+        query = query.as_deref().unwrap_or(""),
+        status = status.as_u16(),
+        latency_ms = started.elapsed().as_millis() as u64,
+        request_bytes,
+        response_bytes,
+        headers = %headers,
+        "Request completed"
+    );
+
+    response
+}
+
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+fn is_sensitive(name: &str, exact: &[&str]) -> bool {
+    let name = name.to_ascii_lowercase();
+    exact.contains(&name.as_str())
+        || SENSITIVE_FRAGMENTS
+            .iter()
+            .any(|fragment| name.contains(fragment))
+}
+
+/// `name: value` pairs, with credentials replaced
+fn redact_headers(headers: &HeaderMap) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive(name.as_str(), SENSITIVE_HEADERS) {
+                REDACTED
+            } else {
+                value.to_str().unwrap_or("<binary>")
+            };
+            format!("{}: {}", name, value)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The query string as sent, with the values of credential parameters replaced
+fn redact_query(query: &str) -> String {
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) => {
+                let decoded = urlencoding::decode(name)
+                    .map(|name| name.into_owned())
+                    .unwrap_or_else(|_| name.to_string());
+                if is_sensitive(&decoded, SENSITIVE_PARAMS) {
+                    format!("{}={}", name, REDACTED)
+                } else {
+                    pair.to_string()
+                }
+            }
+            None => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_credentials_are_redacted_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer abc"));
+        headers.insert("x-refresh-token", HeaderValue::from_static("def"));
+        headers.insert("user-agent", HeaderValue::from_static("FeedTape/1.0"));
+
+        let logged = redact_headers(&headers);
+
+        assert!(logged.contains("authorization: [REDACTED]"));
+        assert!(logged.contains("x-refresh-token: [REDACTED]"));
+        assert!(logged.contains("user-agent: FeedTape/1.0"));
+        assert!(!logged.contains("abc") && !logged.contains("def"));
+    }
+
+    #[test]
+    fn test_credentials_are_redacted_from_query() {
+        assert_eq!(
+            redact_query("code=abc&state=xyz&limit=10&refresh%5Ftoken=def"),
+            "code=[REDACTED]&state=[REDACTED]&limit=10&refresh%5Ftoken=[REDACTED]"
+        );
+        assert_eq!(redact_query("q=news&flag"), "q=news&flag");
+    }
+
+    #[test]
+    fn test_sampled_paths_log_one_in_n_successes() {
+        let logger = AccessLogger::new(AccessLogConfig {
+            sampled_paths: vec!["/health".to_string()],
+            sample_every: 3,
+        });
+
+        let logged = (0..6)
+            .filter(|_| logger.should_log("/health/ready", StatusCode::OK))
+            .count();
+
+        assert_eq!(logged, 2);
+        assert!(logger.should_log("/health", StatusCode::SERVICE_UNAVAILABLE));
+        assert!(logger.should_log("/api/feeds", StatusCode::OK));
+    }
+}
@@ -0,0 +1,235 @@
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use ipnet::IpNet;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use crate::infrastructure::config::ClientIpHeader;
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+const FORWARDED: &str = "forwarded";
+
+tokio::task_local! {
+    static CURRENT_CLIENT_IP: IpAddr;
+}
+
+/// Address of the client that made the request, with trusted proxies stripped off
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClientIp(pub IpAddr);
+
+/// Marks requests that arrived over the Unix socket listener, which has no peer address
+#[derive(Debug, Clone, Copy)]
+pub struct UnixSocketPeer;
+
+/// Which proxies `client_ip_middleware` believes, and the one header they write. Clients
+/// can send either forwarding header, so the one the proxies don't write is never read.
+#[derive(Debug, Clone)]
+pub struct ForwardingTrust {
+    pub trusted_proxies: Vec<IpNet>,
+    pub header: ClientIpHeader,
+}
+
+/// Client IP of the request being handled by the current task, if any
+pub fn current_client_ip() -> Option<IpAddr> {
+    CURRENT_CLIENT_IP.try_with(|ip| *ip).ok()
+}
+
+/// Resolve the client IP and attach it to the request as a `ClientIp` extension.
+///
+/// Forwarding headers are only believed when the connection comes from a trusted proxy,
+/// since anyone can send them.
+pub async fn client_ip_middleware(
+    State(trust): State<Arc<ForwardingTrust>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let client_ip = match peer {
+        Some(peer) => Some(resolve_client_ip(peer, request.headers(), &trust)),
+        // Only processes on this machine can reach the socket, so the proxy on the
+        // other end is trusted without being listed
+        None if request.extensions().get::<UnixSocketPeer>().is_some() => {
+            client_from_chain(request.headers(), &trust)
+        }
+        None => None,
+    };
+    let Some(client_ip) = client_ip else {
+        return next.run(request).await;
+    };
+
+    request.extensions_mut().insert(ClientIp(client_ip));
+
+    CURRENT_CLIENT_IP.scope(client_ip, next.run(request)).await
+}
+
+/// Walk the forwarding chain from the nearest hop back, stopping at the first address
+/// that isn't one of our proxies. Everything before it could have been made up.
+fn resolve_client_ip(peer: IpAddr, headers: &HeaderMap, trust: &ForwardingTrust) -> IpAddr {
+    if !trust.trusted_proxies.iter().any(|net| net.contains(&peer)) {
+        return peer;
+    }
+
+    client_from_chain(headers, trust).unwrap_or(peer)
+}
+
+/// Nearest untrusted hop of the forwarding chain, or its first entry if every hop is trusted
+fn client_from_chain(headers: &HeaderMap, trust: &ForwardingTrust) -> Option<IpAddr> {
+    let is_trusted = |ip: &IpAddr| trust.trusted_proxies.iter().any(|net| net.contains(ip));
+
+    let chain = forwarded_chain(headers, trust.header);
+    chain
+        .iter()
+        .rev()
+        .find(|ip| !is_trusted(ip))
+        .or_else(|| chain.first())
+        .copied()
+}
+
+/// Addresses from the given forwarding header, client first. Entries that aren't IPs
+/// (obfuscated or `unknown`) end the usable chain.
+fn forwarded_chain(headers: &HeaderMap, header: ClientIpHeader) -> Vec<IpAddr> {
+    let name = match header {
+        ClientIpHeader::XForwardedFor => X_FORWARDED_FOR,
+        ClientIpHeader::Forwarded => FORWARDED,
+    };
+    let values = headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok());
+
+    let entries: Vec<String> = match header {
+        ClientIpHeader::XForwardedFor => values
+            .flat_map(|value| value.split(','))
+            .map(|entry| entry.trim().to_string())
+            .collect(),
+        ClientIpHeader::Forwarded => values
+            .flat_map(|value| value.split(','))
+            .filter_map(|element| {
+                element.split(';').find_map(|pair| {
+                    let (name, value) = pair.trim().split_once('=')?;
+                    name.eq_ignore_ascii_case("for")
+                        .then(|| value.trim_matches('"').to_string())
+                })
+            })
+            .collect(),
+    };
+
+    let parsed: Vec<Option<IpAddr>> = entries.iter().map(|entry| parse_node(entry)).collect();
+    // Only the hops after the last unparseable entry can be attributed
+    let usable_from = parsed
+        .iter()
+        .rposition(Option::is_none)
+        .map_or(0, |index| index + 1);
+    parsed[usable_from..].iter().flatten().copied().collect()
+}
+
+/// Parse a node as sent by proxies: a bare IP, `ip:port`, or `[ipv6]:port`
+fn parse_node(node: &str) -> Option<IpAddr> {
+    if let Ok(ip) = node.parse() {
+        return Some(ip);
+    }
+    if let Ok(addr) = node.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    node.strip_prefix('[')
+        .and_then(|rest| rest.split(']').next())
+        .and_then(|ip| ip.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn trusted() -> ForwardingTrust {
+        trusted_writing(ClientIpHeader::XForwardedFor)
+    }
+
+    fn trusted_writing(header: ClientIpHeader) -> ForwardingTrust {
+        ForwardingTrust {
+            trusted_proxies: vec!["10.0.0.0/8".parse().unwrap()],
+            header,
+        }
+    }
+
+    #[test]
+    fn test_untrusted_peer_headers_are_ignored() {
+        let headers = headers(&[(X_FORWARDED_FOR, "1.2.3.4")]);
+
+        let client = resolve_client_ip(ip("203.0.113.9"), &headers, &trusted());
+
+        assert_eq!(client, ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn test_x_forwarded_for_skips_trusted_hops() {
+        // The client prepended a fake address; our proxies appended the real one
+        let headers = headers(&[(X_FORWARDED_FOR, "1.2.3.4, 198.51.100.7, 10.0.1.5")]);
+
+        let client = resolve_client_ip(ip("10.0.0.2"), &headers, &trusted());
+
+        assert_eq!(client, ip("198.51.100.7"));
+    }
+
+    #[test]
+    fn test_client_sent_forwarded_header_is_ignored() {
+        // The proxy only appends X-Forwarded-For and passes Forwarded through
+        let headers = headers(&[
+            (X_FORWARDED_FOR, "198.51.100.7"),
+            (FORWARDED, "for=1.2.3.4"),
+        ]);
+
+        let client = resolve_client_ip(ip("10.0.0.2"), &headers, &trusted());
+
+        assert_eq!(client, ip("198.51.100.7"));
+    }
+
+    #[test]
+    fn test_forwarded_header_when_configured() {
+        let trust = trusted_writing(ClientIpHeader::Forwarded);
+        let both = headers(&[
+            (X_FORWARDED_FOR, "1.2.3.4"),
+            (
+                FORWARDED,
+                r#"for="[2001:db8::1]:4711";proto=https, for=10.0.1.5"#,
+            ),
+        ]);
+
+        let client = resolve_client_ip(ip("10.0.0.2"), &both, &trust);
+        assert_eq!(client, ip("2001:db8::1"));
+
+        // No falling back to X-Forwarded-For when the configured header is missing
+        let x_forwarded_for_only = headers(&[(X_FORWARDED_FOR, "1.2.3.4")]);
+        let client = resolve_client_ip(ip("10.0.0.2"), &x_forwarded_for_only, &trust);
+        assert_eq!(client, ip("10.0.0.2"));
+    }
+
+    #[test]
+    fn test_chain_without_client_falls_back_to_peer() {
+        let client = resolve_client_ip(ip("10.0.0.2"), &HeaderMap::new(), &trusted());
+        assert_eq!(client, ip("10.0.0.2"));
+
+        let headers = headers(&[(X_FORWARDED_FOR, "unknown, 10.0.1.5")]);
+        let client = resolve_client_ip(ip("10.0.0.2"), &headers, &trusted());
+        assert_eq!(client, ip("10.0.1.5"));
+    }
+}
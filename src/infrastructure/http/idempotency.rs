@@ -0,0 +1,217 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::infrastructure::auth::AuthUser;
+use crate::infrastructure::repositories::IdempotencyRepository;
+
+pub const IDEMPOTENCY_KEY: &str = "idempotency-key";
+/// Set on responses replayed from an earlier request
+pub const IDEMPOTENT_REPLAYED: &str = "idempotent-replayed";
+
+/// How long a key's response is replayed
+pub const IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+const MAX_KEY_LENGTH: usize = 255;
+/// Largest request or response body buffered for a keyed request
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Replay the stored response when a request is retried with the same Idempotency-Key.
+///
+/// Only successful responses are stored: a failed request can be retried with its key.
+/// Must run inside `auth_middleware`, since keys are scoped to the user.
+pub async fn idempotency_middleware(
+    State(repo): State<Arc<IdempotencyRepository>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(key) = request
+        .headers()
+        .get(IDEMPOTENCY_KEY)
+        .map(|value| value.to_str().unwrap_or_default().trim().to_string())
+    else {
+        return Ok(next.run(request).await);
+    };
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Idempotency-Key must be 1 to {} visible characters",
+            MAX_KEY_LENGTH
+        )));
+    }
+    let Some(user_id) = request
+        .extensions()
+        .get::<AuthUser>()
+        .map(|auth_user| auth_user.user_id)
+    else {
+        return Ok(next.run(request).await);
+    };
+
+    let (parts, body) = request.into_parts();
+    let body = axum::body::to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|_| AppError::PayloadTooLarge("Request body is too large".to_string()))?;
+    let request_hash = request_hash(parts.method.as_str(), parts.uri.path(), &body);
+
+    let expired_before = Utc::now() - Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS);
+    if !repo
+        .claim(user_id, &key, &request_hash, expired_before)
+        .await?
+    {
+        // Claims only fail while the key's row exists, unless it expired in between
+        let record = repo.find(user_id, &key).await?.ok_or_else(|| {
+            AppError::Conflict("Idempotency-Key expired, retry the request".to_string())
+        })?;
+        if record.request_hash != request_hash {
+            return Err(AppError::BadRequest(
+                "Idempotency-Key was already used for a different request".to_string(),
+            ));
+        }
+        let Some(status_code) = record.status_code else {
+            return Err(AppError::Conflict(
+                "A request with this Idempotency-Key is still in progress".to_string(),
+            ));
+        };
+
+        tracing::debug!(user_id = %user_id, "Replaying idempotent response");
+        return replay(
+            status_code,
+            record.content_type,
+            record.response_body.unwrap_or_default(),
+        );
+    }
+
+    let claim = Claim {
+        repo,
+        user_id,
+        key,
+        settled: false,
+    };
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if !response.status().is_success() {
+        claim.release().await?;
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            claim.release().await?;
+            return Err(AppError::Internal(e.to_string()));
+        }
+    };
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    claim
+        .complete(parts.status.as_u16(), content_type, &body)
+        .await?;
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+/// A key claimed for the request in flight. Dropped unsettled, as when the client
+/// disconnects and the handler future is dropped, it frees the key in the background so
+/// retries aren't refused as "in progress" until the key expires.
+struct Claim {
+    repo: Arc<IdempotencyRepository>,
+    user_id: Uuid,
+    key: String,
+    /// Whether the key was completed or released
+    settled: bool,
+}
+
+impl Claim {
+    async fn complete(
+        mut self,
+        status_code: u16,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Result<(), AppError> {
+        self.repo
+            .complete(self.user_id, &self.key, status_code, content_type, body)
+            .await?;
+        self.settled = true;
+        Ok(())
+    }
+
+    async fn release(mut self) -> Result<(), AppError> {
+        self.repo.release(self.user_id, &self.key).await?;
+        self.settled = true;
+        Ok(())
+    }
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        if self.settled {
+            return;
+        }
+        // No runtime to release on while it shuts down; the key expires instead
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let repo = self.repo.clone();
+        let user_id = self.user_id;
+        let key = std::mem::take(&mut self.key);
+        runtime.spawn(async move {
+            if let Err(e) = repo.release(user_id, &key).await {
+                tracing::warn!(user_id = %user_id, error = %e, "Failed to release abandoned Idempotency-Key");
+            }
+        });
+    }
+}
+
+/// Identify a request by what it does, so a key can't be reused for something else
+fn request_hash(method: &str, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+fn replay(
+    status_code: i16,
+    content_type: Option<String>,
+    body: Vec<u8>,
+) -> Result<Response, AppError> {
+    let status =
+        StatusCode::from_u16(status_code as u16).map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let mut builder = Response::builder()
+        .status(status)
+        .header(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    if let Some(content_type) = content_type {
+        builder = builder.header(header::CONTENT_TYPE, content_type);
+    }
+    builder
+        .body(Body::from(body))
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_hash_covers_method_path_and_body() {
+        let hash = request_hash("POST", "/api/feeds", br#"{"url":"a"}"#);
+
+        assert_eq!(hash, request_hash("POST", "/api/feeds", br#"{"url":"a"}"#));
+        assert_ne!(hash, request_hash("POST", "/api/feeds", br#"{"url":"b"}"#));
+        assert_ne!(hash, request_hash("POST", "/api/feedsx", br#"{"url":"a"}"#));
+        assert_ne!(hash, request_hash("PUT", "/api/feeds", br#"{"url":"a"}"#));
+    }
+}
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::domain::ip_block::{IpBlockService, IpBlockServiceApi};
+use crate::error::{AppError, CredentialFailure};
+use crate::infrastructure::http::ClientIp;
+
+/// Refuse requests from blocked addresses before any handler runs, and count the
+/// credentials other clients present that were never valid, so repeat offenders end up
+/// blocked too. Other 401s, like an expired access token, aren't counted. Must run inside
+/// `client_ip_middleware`.
+pub async fn ip_blocklist_middleware(
+    State(ip_block_service): State<Arc<IpBlockService>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(ClientIp(client_ip)) = request.extensions().get::<ClientIp>().copied() else {
+        return next.run(request).await;
+    };
+
+    if ip_block_service.is_blocked(client_ip) {
+        tracing::debug!(client_ip = %client_ip, "Refused request from blocked address");
+        return AppError::Forbidden("Requests from this address are blocked".to_string())
+            .into_response();
+    }
+
+    let response = next.run(request).await;
+    if response.extensions().get::<CredentialFailure>().is_some() {
+        ip_block_service.record_auth_failure(client_ip).await;
+    }
+
+    response
+}
@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use crate::error::AppError;
+
+/// Seconds clients are asked to wait before retrying a shed request
+const RETRY_AFTER_SECS: &str = "1";
+
+/// Cap how many requests run behind this layer at once. Requests over the cap get an
+/// immediate 503 instead of queueing for database connections that the ones already
+/// running are holding.
+pub async fn concurrency_limit_middleware(
+    State(permits): State<Arc<Semaphore>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Ok(_permit) = permits.try_acquire_owned() else {
+        let mut response =
+            AppError::Overloaded("Too many requests in progress, retry shortly".to_string())
+                .into_response();
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from_static(RETRY_AFTER_SECS),
+        );
+        return response;
+    };
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_requests_over_the_limit_are_shed() {
+        let permits = Arc::new(Semaphore::new(1));
+        let limit = middleware::from_fn_with_state(permits.clone(), concurrency_limit_middleware);
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(limit);
+        let request = || Request::builder().uri("/").body(Body::empty()).unwrap();
+
+        let in_flight = permits.clone().try_acquire_owned().unwrap();
+        let response = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], RETRY_AFTER_SECS);
+
+        drop(in_flight);
+        let response = app.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
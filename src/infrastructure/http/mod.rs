@@ -1,31 +1,97 @@
-use axum::{middleware, routing::get, Router};
+pub mod access_log;
+pub mod client_ip;
+pub mod idempotency;
+pub mod ip_blocklist;
+pub mod load_shed;
+pub mod rate_limit;
+pub mod shutdown;
+pub mod tls;
+#[cfg(unix)]
+pub mod unix_socket;
+
+pub use access_log::{access_log_middleware, AccessLogger};
+pub use client_ip::{
+    client_ip_middleware, current_client_ip, ClientIp, ForwardingTrust, UnixSocketPeer,
+};
+pub use idempotency::idempotency_middleware;
+pub use ip_blocklist::ip_blocklist_middleware;
+pub use load_shed::concurrency_limit_middleware;
+pub use rate_limit::RateLimit;
+pub use shutdown::shutdown_signal;
+
+use axum::{http::StatusCode, middleware, routing::get, Router};
+use std::net::SocketAddr;
 use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tower_http::trace::TraceLayer;
 
+use crate::domain::auth::JwtManager;
+use crate::domain::ip_block::IpBlockService;
 use crate::infrastructure::config::Config;
-use crate::infrastructure::db::DbPool;
 use crate::{
     controllers::{
-        auth::AuthController, feed::FeedController, feed_suggestions::FeedSuggestionsController,
-        health, oauth::OAuthController, tts::TtsController, user::UserController,
+        admin::AdminController, admin_dashboard::AdminDashboardController,
+        analytics::AnalyticsController, api_key::ApiKeyController, auth::AuthController,
+        device::DeviceController, events::EventsController, family::FamilyController,
+        feed::FeedController, feed_suggestions::FeedSuggestionsController,
+        graphql::GraphQLController, health, ip_block::IpBlockController, mute::MuteController,
+        oauth::OAuthController, referral::ReferralController, subscription::SubscriptionController,
+        tts::TtsController, user::UserController, webhook::WebhookController,
+        weekly_summary::WeeklySummaryController,
+    },
+    infrastructure::auth::{
+        admin_middleware, admin_session_middleware, auth_middleware, request_id_middleware,
+        AccessTokenDenylist, AuthUserCache,
     },
-    infrastructure::auth::{auth_middleware, request_id_middleware},
 };
 
-use crate::infrastructure::repositories::UserRepository;
+use crate::infrastructure::repositories::{
+    ApiKeyRepository, IdempotencyRepository, UserRepository,
+};
 
 /// Start the HTTP server with all routes configured
+#[allow(clippy::too_many_arguments)]
 pub async fn start_http_server(
-    pool: Arc<DbPool>,
     config: Arc<Config>,
     user_repo: Arc<UserRepository>,
+    jwt_manager: Arc<JwtManager>,
+    auth_user_cache: Arc<AuthUserCache>,
+    token_denylist: Arc<AccessTokenDenylist>,
+    api_key_repo: Arc<ApiKeyRepository>,
+    idempotency_repo: Arc<IdempotencyRepository>,
+    ip_block_service: Arc<IpBlockService>,
     auth_controller: Arc<AuthController>,
     oauth_controller: Arc<OAuthController>,
     feed_controller: Arc<FeedController>,
     feed_suggestions_controller: Arc<FeedSuggestionsController>,
+    mute_controller: Arc<MuteController>,
+    graphql_controller: Arc<GraphQLController>,
+    subscription_controller: Arc<SubscriptionController>,
     user_controller: Arc<UserController>,
     tts_controller: Arc<TtsController>,
+    admin_controller: Arc<AdminController>,
+    admin_dashboard_controller: Arc<AdminDashboardController>,
+    referral_controller: Arc<ReferralController>,
+    family_controller: Arc<FamilyController>,
+    device_controller: Arc<DeviceController>,
+    analytics_controller: Arc<AnalyticsController>,
+    webhook_controller: Arc<WebhookController>,
+    events_controller: Arc<EventsController>,
+    ip_block_controller: Arc<IpBlockController>,
+    weekly_summary_controller: Arc<WeeklySummaryController>,
+    api_key_controller: Arc<ApiKeyController>,
+    health_controller: Arc<health::HealthController>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    // Retries of these routes with the same Idempotency-Key replay the first response
+    let idempotency = middleware::from_fn_with_state(idempotency_repo, idempotency_middleware);
+    let auth_state = (
+        user_repo,
+        jwt_manager,
+        auth_user_cache,
+        token_denylist,
+        api_key_repo,
+    );
+
     // TTS routes (need auth)
     let tts_routes = Router::new()
         .route(
@@ -34,16 +100,22 @@ pub async fn start_http_server(
         )
         .with_state(tts_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
+        ))
+        // Outermost, so shed requests don't even look up the user
+        .layer(middleware::from_fn_with_state(
+            Arc::new(Semaphore::new(config.max_concurrent_syntheses)),
+            concurrency_limit_middleware,
         ));
 
     // Usage route (needs auth)
     let usage_routes = Router::new()
         .route("/api/tts/usage", get(TtsController::get_usage))
+        .route("/api/tts/usage/export", get(TtsController::export_usage))
         .with_state(tts_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
         ));
 
@@ -54,6 +126,7 @@ pub async fn start_http_server(
             axum::routing::post(AuthController::refresh),
         )
         .route("/auth/logout", axum::routing::post(AuthController::logout))
+        .route("/.well-known/jwks.json", get(AuthController::jwks))
         .with_state(auth_controller.clone());
 
     // OAuth routes (public - no auth required)
@@ -65,15 +138,20 @@ pub async fn start_http_server(
         )
         .with_state(oauth_controller.clone());
 
-    // Logout all requires auth
+    // Logout all and session management require auth
     let auth_protected_routes = Router::new()
         .route(
             "/auth/logout/all",
             axum::routing::post(AuthController::logout_all),
         )
+        .route("/api/me/sessions", get(AuthController::list_sessions))
+        .route(
+            "/api/me/sessions/:sessionId",
+            axum::routing::delete(AuthController::revoke_session),
+        )
         .with_state(auth_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
         ));
 
@@ -83,9 +161,148 @@ pub async fn start_http_server(
             "/api/me",
             get(UserController::get_me).patch(UserController::update_me),
         )
+        .route("/api/me/events", get(UserController::get_events))
+        .route(
+            "/api/me/delete",
+            axum::routing::post(UserController::delete_me),
+        )
+        .route(
+            "/api/me/delete/cancel",
+            axum::routing::post(UserController::cancel_delete_me),
+        )
+        .route(
+            "/api/me/usage-history",
+            axum::routing::delete(UserController::purge_usage_history),
+        )
+        .route("/api/me/backup", get(UserController::export_backup))
+        .route(
+            "/api/me/restore",
+            axum::routing::post(UserController::restore_backup),
+        )
+        .route("/api/me/export", get(UserController::export_data))
         .with_state(user_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Referral routes (require authentication)
+    let referral_routes = Router::new()
+        .route(
+            "/auth/redeem-referral",
+            axum::routing::post(ReferralController::redeem),
+        )
+        .route("/api/me/referral", get(ReferralController::get_referral))
+        .with_state(referral_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Family plan routes (require authentication)
+    let family_routes = Router::new()
+        .route("/api/family", get(FamilyController::get_family))
+        .route(
+            "/api/family/invites",
+            axum::routing::post(FamilyController::invite),
+        )
+        .route(
+            "/api/family/invites/:code",
+            axum::routing::delete(FamilyController::revoke_invite),
+        )
+        .route(
+            "/api/family/join",
+            axum::routing::post(FamilyController::join),
+        )
+        .route(
+            "/api/family/members/:memberId",
+            axum::routing::delete(FamilyController::remove_member),
+        )
+        .route(
+            "/api/family/leave",
+            axum::routing::post(FamilyController::leave),
+        )
+        .with_state(family_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Push notification device routes (require authentication)
+    let device_routes = Router::new()
+        .route(
+            "/api/me/devices",
+            axum::routing::post(DeviceController::register_device),
+        )
+        .route(
+            "/api/me/devices/:token",
+            axum::routing::delete(DeviceController::unregister_device),
+        )
+        .with_state(device_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Analytics routes (require authentication)
+    let analytics_routes = Router::new()
+        .route(
+            "/api/analytics/events",
+            axum::routing::post(AnalyticsController::ingest_events),
+        )
+        .with_state(analytics_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Outbound webhook routes (require authentication)
+    let user_webhook_routes = Router::new()
+        .route(
+            "/api/me/webhooks",
+            get(WebhookController::list_webhooks).post(WebhookController::create_webhook),
+        )
+        .route(
+            "/api/me/webhooks/:webhookId",
+            axum::routing::delete(WebhookController::delete_webhook),
+        )
+        .route(
+            "/api/me/webhooks/:webhookId/deliveries",
+            get(WebhookController::list_deliveries),
+        )
+        .route("/v1/me/webhooks", get(WebhookController::list_webhooks_v1))
+        .route(
+            "/v1/me/webhooks/:webhookId/deliveries",
+            get(WebhookController::list_deliveries),
+        )
+        .with_state(webhook_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // API key management (requires an access token; keys can't manage keys)
+    let api_key_routes = Router::new()
+        .route(
+            "/api/me/api-keys",
+            get(ApiKeyController::list_api_keys).post(ApiKeyController::create_api_key),
+        )
+        .route(
+            "/api/me/api-keys/:apiKeyId",
+            axum::routing::delete(ApiKeyController::delete_api_key),
+        )
+        .with_state(api_key_controller)
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Account event stream (requires authentication)
+    let events_routes = Router::new()
+        .route("/api/events", get(EventsController::stream))
+        .with_state(events_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
             auth_middleware,
         ));
 
@@ -93,15 +310,21 @@ pub async fn start_http_server(
     let feed_routes = Router::new()
         .route(
             "/api/feeds",
-            get(FeedController::list_feeds).post(FeedController::create_feed),
+            get(FeedController::list_feeds)
+                .merge(axum::routing::post(FeedController::create_feed).layer(idempotency.clone())),
+        )
+        .route(
+            "/api/feeds/validate",
+            axum::routing::post(FeedController::validate_feeds),
         )
         .route(
             "/api/feeds/:feedId",
             axum::routing::delete(FeedController::delete_feed),
         )
+        .route("/v1/feeds", get(FeedController::list_feeds_v1))
         .with_state(feed_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
         ));
 
@@ -111,35 +334,288 @@ pub async fn start_http_server(
             "/api/feed-suggestions",
             get(FeedSuggestionsController::get_suggestions),
         )
+        .route(
+            "/v1/feed-suggestions",
+            get(FeedSuggestionsController::get_suggestions_v1),
+        )
+        .route(
+            "/api/feed-suggestions/submissions",
+            axum::routing::post(FeedSuggestionsController::submit),
+        )
         .with_state(feed_suggestions_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
         ));
 
-    // Build application routes
-    let app = Router::new()
-        .route("/health", get(health::health))
-        .route("/health/ready", get(health::health_ready))
-        .with_state(pool.clone())
+    // Mute rule routes (require authentication)
+    let mute_routes = Router::new()
+        .route(
+            "/api/mute-rules",
+            get(MuteController::list_rules).post(MuteController::create_rule),
+        )
+        .route(
+            "/api/mute-rules/:ruleId",
+            axum::routing::delete(MuteController::delete_rule),
+        )
+        .route("/v1/mute-rules", get(MuteController::list_rules_v1))
+        .with_state(mute_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // GraphQL endpoint for the home screen (requires authentication)
+    let graphql_routes = Router::new()
+        .route("/graphql", axum::routing::post(GraphQLController::execute))
+        .with_state(graphql_controller)
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Subscription routes (require authentication)
+    let subscription_routes = Router::new()
+        .route(
+            "/api/subscription/validate-purchase",
+            axum::routing::post(SubscriptionController::validate_purchase)
+                .layer(idempotency.clone()),
+        )
+        .route(
+            "/api/subscription/start-trial",
+            axum::routing::post(SubscriptionController::start_trial),
+        )
+        .route(
+            "/api/subscription/redeem",
+            axum::routing::post(SubscriptionController::redeem_promo_code),
+        )
+        .route(
+            "/api/subscription/history",
+            axum::routing::get(SubscriptionController::get_history),
+        )
+        .route(
+            "/v1/subscription/history",
+            axum::routing::get(SubscriptionController::get_history_v1),
+        )
+        .route(
+            "/api/subscription/checkout-session",
+            axum::routing::post(SubscriptionController::create_checkout_session),
+        )
+        .with_state(subscription_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Admin routes (require authentication and an admin account)
+    let admin_routes = Router::new()
+        .route("/admin/users", get(AdminController::list_users))
+        .route("/admin/users/:userId", get(AdminController::get_user))
+        .route(
+            "/admin/users/:userId/usage",
+            get(AdminController::get_user_usage),
+        )
+        .route(
+            "/admin/users/:userId/grant-pro",
+            axum::routing::post(AdminController::grant_pro),
+        )
+        .route(
+            "/admin/users/:userId/revoke-pro",
+            axum::routing::post(AdminController::revoke_pro),
+        )
+        .route(
+            "/admin/users/:userId/limit-overrides",
+            axum::routing::put(AdminController::set_limit_overrides),
+        )
+        .route(
+            "/admin/users/:userId/role",
+            axum::routing::put(AdminController::set_role),
+        )
+        .route(
+            "/admin/promo-codes",
+            get(AdminController::list_promo_codes).post(AdminController::create_promo_code),
+        )
+        .route("/admin/audit-log", get(AdminController::list_audit_log))
+        .route("/admin/stats", get(AdminController::get_stats))
+        .route("/admin/tts/metrics", get(AdminController::get_tts_metrics))
+        .with_state(admin_controller.clone())
+        .merge(
+            Router::new()
+                .route(
+                    "/admin/ip-blocks",
+                    get(IpBlockController::list_blocks).post(IpBlockController::create_block),
+                )
+                .route(
+                    "/admin/ip-blocks/:blockId",
+                    axum::routing::delete(IpBlockController::delete_block),
+                )
+                .with_state(ip_block_controller.clone()),
+        )
+        .merge(
+            Router::new()
+                .route(
+                    "/admin/suggestion-submissions",
+                    get(FeedSuggestionsController::list_submissions),
+                )
+                .route(
+                    "/admin/suggestion-submissions/:submissionId/approve",
+                    axum::routing::post(FeedSuggestionsController::approve_submission),
+                )
+                .route(
+                    "/admin/suggestion-submissions/:submissionId/reject",
+                    axum::routing::post(FeedSuggestionsController::reject_submission),
+                )
+                .with_state(feed_suggestions_controller.clone()),
+        )
+        .layer(middleware::from_fn_with_state(
+            config.clone(),
+            admin_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Admin dashboard pages (session cookie or bearer token, admin account)
+    let admin_dashboard_routes = Router::new()
+        .route("/admin", get(AdminDashboardController::overview))
+        .route(
+            "/admin/dashboard/users/:userId",
+            get(AdminDashboardController::user_usage),
+        )
+        .route(
+            "/admin/dashboard/suggestions",
+            get(AdminDashboardController::suggestions),
+        )
+        .route(
+            "/admin/dashboard/suggestions/check",
+            axum::routing::post(AdminDashboardController::check_suggestion_links),
+        )
+        .with_state(admin_dashboard_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            config.clone(),
+            admin_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ))
+        .layer(middleware::from_fn(admin_session_middleware))
+        .route("/admin/sign-in", get(AdminDashboardController::sign_in))
+        .route(
+            "/admin/sign-out",
+            axum::routing::post(AdminDashboardController::sign_out),
+        )
+        .with_state(admin_dashboard_controller);
+
+    // Store webhooks (public - authenticated by signature)
+    let webhook_routes = Router::new()
+        .route(
+            "/webhooks/stripe",
+            axum::routing::post(SubscriptionController::stripe_webhook),
+        )
+        .route(
+            "/webhooks/apple",
+            axum::routing::post(SubscriptionController::apple_webhook),
+        )
+        .with_state(subscription_controller.clone());
+
+    // Unsubscribe links in emails (public - authenticated by the signed token)
+    let email_routes = Router::new()
+        .route(
+            "/email/unsubscribe",
+            get(WeeklySummaryController::confirm_unsubscribe)
+                .post(WeeklySummaryController::unsubscribe),
+        )
+        .with_state(weekly_summary_controller);
+
+    // Health checks stay outside the global limit so probes still answer under load
+    let api_routes = Router::new()
         .merge(auth_routes)
         .merge(oauth_routes)
         .merge(auth_protected_routes)
         .merge(user_routes)
+        .merge(referral_routes)
+        .merge(family_routes)
+        .merge(device_routes)
+        .merge(analytics_routes)
+        .merge(user_webhook_routes)
+        .merge(api_key_routes)
+        .merge(events_routes)
         .merge(feed_routes)
         .merge(feed_suggestions_routes)
+        .merge(mute_routes)
+        .merge(graphql_routes)
+        .merge(subscription_routes)
+        .merge(webhook_routes)
+        .merge(email_routes)
+        .merge(admin_routes)
+        .merge(admin_dashboard_routes)
         .merge(tts_routes)
         .merge(usage_routes)
+        // Otherwise the last merged router's fallback answers, behind its auth middleware
+        .fallback(|| async { StatusCode::NOT_FOUND })
+        .layer(middleware::from_fn_with_state(
+            Arc::new(Semaphore::new(config.max_concurrent_requests)),
+            concurrency_limit_middleware,
+        ));
+
+    // Build application routes
+    let app = Router::new()
+        .route("/health", get(health::health))
+        .route("/health/ready", get(health::HealthController::health_ready))
+        .with_state(health_controller)
+        .merge(api_routes);
+
+    // Blocked clients are turned away before anything else runs, but still show up in
+    // the access log
+    let app = app.layer(middleware::from_fn_with_state(
+        ip_block_service.clone(),
+        ip_blocklist_middleware,
+    ));
+
+    // Inside the request span, so each line carries the request and user ids
+    let app = match config.access_log.clone() {
+        Some(access_log) => app.layer(middleware::from_fn_with_state(
+            Arc::new(AccessLogger::new(access_log)),
+            access_log_middleware,
+        )),
+        None => app,
+    };
+
+    let app = app
+        .layer(middleware::from_fn_with_state(
+            Arc::new(ForwardingTrust {
+                trusted_proxies: config.trusted_proxies.clone(),
+                header: config.client_ip_header,
+            }),
+            client_ip_middleware,
+        ))
         .layer(middleware::from_fn(request_id_middleware))
         .layer(TraceLayer::new_for_http());
 
     // Start server
+    #[cfg(unix)]
+    if let Some(path) = &config.listen_socket {
+        return unix_socket::serve_unix(app, path).await;
+    }
+
+    if let Some(tls) = &config.tls {
+        let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
+        return tls::serve_tls(app, addr, tls).await;
+    }
+
     let listener =
         tokio::net::TcpListener::bind(format!("{}:{}", config.host, config.port)).await?;
 
     tracing::info!("Server listening on {}", listener.local_addr()?);
 
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await?;
 
     Ok(())
 }
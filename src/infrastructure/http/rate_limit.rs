@@ -0,0 +1,63 @@
+use axum::{
+    http::HeaderValue,
+    response::{IntoResponseParts, ResponseParts},
+};
+use chrono::{DateTime, Utc};
+use std::convert::Infallible;
+
+pub const X_RATELIMIT_LIMIT: &str = "x-ratelimit-limit";
+pub const X_RATELIMIT_REMAINING: &str = "x-ratelimit-remaining";
+pub const X_RATELIMIT_RESET: &str = "x-ratelimit-reset";
+
+/// Where the client stands against a limit the request counted towards. Returned as part
+/// of a response it sets `X-RateLimit-Limit`, `X-RateLimit-Remaining` and
+/// `X-RateLimit-Reset` (Unix seconds), so every limit reads the same to clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: i64,
+    pub remaining: i64,
+    pub resets_at: DateTime<Utc>,
+}
+
+impl RateLimit {
+    /// `remaining` is clamped at zero, so a client over the limit sees none left
+    pub fn new(limit: i64, remaining: i64, resets_at: DateTime<Utc>) -> Self {
+        Self {
+            limit,
+            remaining: remaining.max(0),
+            resets_at,
+        }
+    }
+}
+
+impl IntoResponseParts for RateLimit {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut parts: ResponseParts) -> Result<ResponseParts, Infallible> {
+        let headers = parts.headers_mut();
+        headers.insert(X_RATELIMIT_LIMIT, HeaderValue::from(self.limit));
+        headers.insert(X_RATELIMIT_REMAINING, HeaderValue::from(self.remaining));
+        headers.insert(
+            X_RATELIMIT_RESET,
+            HeaderValue::from(self.resets_at.timestamp()),
+        );
+        Ok(parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+
+    #[test]
+    fn test_headers_describe_the_limit() {
+        let resets_at = DateTime::from_timestamp(1_767_225_600, 0).unwrap();
+        let response = (RateLimit::new(5000, -20, resets_at), "ok").into_response();
+
+        let headers = response.headers();
+        assert_eq!(headers[X_RATELIMIT_LIMIT], "5000");
+        assert_eq!(headers[X_RATELIMIT_REMAINING], "0");
+        assert_eq!(headers[X_RATELIMIT_RESET], "1767225600");
+    }
+}
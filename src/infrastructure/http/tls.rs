@@ -0,0 +1,65 @@
+use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+use std::net::SocketAddr;
+
+use super::shutdown_signal;
+use crate::infrastructure::config::TlsConfig;
+
+/// Serve the app over HTTPS, picking up a renewed certificate on SIGHUP
+pub async fn serve_tls(
+    app: Router,
+    addr: SocketAddr,
+    tls: &TlsConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let rustls_config = RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path).await?;
+    reload_on_sighup(rustls_config.clone(), tls.clone());
+
+    tracing::info!("Server listening on https://{}", addr);
+
+    let handle = axum_server::Handle::new();
+    let shutdown_handle = handle.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        shutdown_handle.graceful_shutdown(None);
+    });
+
+    axum_server::bind_rustls(addr, rustls_config)
+        .handle(handle)
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+        .await?;
+
+    Ok(())
+}
+
+/// Re-read the PEM files whenever the process gets SIGHUP. Open connections keep the
+/// certificate they were established with; a bad file leaves the current one in place.
+#[cfg(unix)]
+fn reload_on_sighup(rustls_config: RustlsConfig, tls: TlsConfig) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(e) => {
+            tracing::warn!(error = %e, "Cannot listen for SIGHUP, TLS certificate reload is disabled");
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            match rustls_config
+                .reload_from_pem_file(&tls.cert_path, &tls.key_path)
+                .await
+            {
+                Ok(()) => tracing::info!("TLS certificate reloaded"),
+                Err(e) => tracing::error!(
+                    error = %e,
+                    "Failed to reload TLS certificate, keeping the current one"
+                ),
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn reload_on_sighup(_rustls_config: RustlsConfig, _tls: TlsConfig) {}
@@ -0,0 +1,62 @@
+use axum::{extract::Request, Extension, Router};
+use hyper::body::Incoming;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::{conn::auto, graceful::GracefulShutdown},
+};
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+use tokio::net::UnixListener;
+use tower::Service;
+
+use super::{shutdown_signal, UnixSocketPeer};
+
+/// Serve the app on a Unix socket, for a reverse proxy running on the same machine
+pub async fn serve_unix(app: Router, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    remove_stale_socket(path)?;
+    let listener = UnixListener::bind(path)?;
+
+    tracing::info!("Server listening on unix:{}", path.display());
+
+    let app = app.layer(Extension(UnixSocketPeer));
+    let graceful = GracefulShutdown::new();
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => accepted?.0,
+            _ = &mut shutdown => break,
+        };
+        let app = app.clone();
+
+        let service =
+            hyper::service::service_fn(move |request: Request<Incoming>| app.clone().call(request));
+        let builder = auto::Builder::new(TokioExecutor::new());
+        let connection = graceful.watch(
+            builder
+                .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                .into_owned(),
+        );
+
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                tracing::debug!(error = %e, "Unix socket connection closed with an error");
+            }
+        });
+    }
+
+    // Stop accepting, then let in-flight requests finish
+    graceful.shutdown().await;
+
+    Ok(())
+}
+
+/// A socket left behind by a previous run makes `bind` fail. Anything that isn't a
+/// socket is left alone so a typo in LISTEN_SOCKET can't delete a real file.
+fn remove_stale_socket(path: &Path) -> std::io::Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,407 @@
+/// An English message and its translations. `{}` stands for a value filled in at
+/// runtime; translations keep the values in the same order.
+pub(super) struct Message {
+    pub en: &'static str,
+    pub es: &'static str,
+    pub fr: &'static str,
+}
+
+const fn message(en: &'static str, es: &'static str, fr: &'static str) -> Message {
+    Message { en, es, fr }
+}
+
+/// The user-facing messages worth translating: the summary of each `AppError` kind,
+/// then the reasons users are likely to hit. Internal errors stay in English.
+pub(super) const MESSAGES: &[Message] = &[
+    // AppError summaries
+    message(
+        "Authentication failed",
+        "Error de autenticación",
+        "Échec de l'authentification",
+    ),
+    message("Forbidden", "Prohibido", "Interdit"),
+    message(
+        "Invalid refresh token",
+        "Token de actualización no válido",
+        "Jeton d'actualisation invalide",
+    ),
+    message(
+        "Refresh token expired",
+        "Token de actualización caducado",
+        "Jeton d'actualisation expiré",
+    ),
+    message("Invalid input", "Datos no válidos", "Données invalides"),
+    message(
+        "Resource not found",
+        "Recurso no encontrado",
+        "Ressource introuvable",
+    ),
+    message("Conflict", "Conflicto", "Conflit"),
+    message(
+        "Precondition failed",
+        "Precondición fallida",
+        "Échec de la précondition",
+    ),
+    message(
+        "Rate limit exceeded",
+        "Límite de solicitudes superado",
+        "Limite de requêtes dépassée",
+    ),
+    message("Payment required", "Pago requerido", "Paiement requis"),
+    message("Text too large", "Texto demasiado largo", "Texte trop long"),
+    message(
+        "Server overloaded",
+        "Servidor sobrecargado",
+        "Serveur surchargé",
+    ),
+    message(
+        "External service error",
+        "Error de un servicio externo",
+        "Erreur d'un service externe",
+    ),
+    message(
+        "Internal server error",
+        "Error interno del servidor",
+        "Erreur interne du serveur",
+    ),
+    message(
+        "Database error",
+        "Error de base de datos",
+        "Erreur de base de données",
+    ),
+    // Authentication and accounts
+    message(
+        "Missing authorization header",
+        "Falta la cabecera de autorización",
+        "En-tête d'autorisation manquant",
+    ),
+    message(
+        "Invalid authorization format",
+        "Formato de autorización no válido",
+        "Format d'autorisation invalide",
+    ),
+    message(
+        "User not found",
+        "Usuario no encontrado",
+        "Utilisateur introuvable",
+    ),
+    message(
+        "Token has been revoked",
+        "El token ha sido revocado",
+        "Le jeton a été révoqué",
+    ),
+    message(
+        "Account has been deleted",
+        "La cuenta ha sido eliminada",
+        "Le compte a été supprimé",
+    ),
+    message(
+        "Account already exists",
+        "La cuenta ya existe",
+        "Le compte existe déjà",
+    ),
+    message(
+        "Invalid or expired OAuth state",
+        "Estado de OAuth no válido o caducado",
+        "État OAuth invalide ou expiré",
+    ),
+    message(
+        "Invalid API key",
+        "Clave de API no válida",
+        "Clé d'API invalide",
+    ),
+    message(
+        "API key not allowed for this endpoint",
+        "La clave de API no permite usar este endpoint",
+        "La clé d'API n'autorise pas ce point d'accès",
+    ),
+    message(
+        "Admin access required",
+        "Se requiere acceso de administrador",
+        "Accès administrateur requis",
+    ),
+    message(
+        "Requests from this address are blocked",
+        "Las solicitudes desde esta dirección están bloqueadas",
+        "Les requêtes depuis cette adresse sont bloquées",
+    ),
+    // Settings
+    message(
+        "Invalid timezone: {}",
+        "Zona horaria no válida: {}",
+        "Fuseau horaire invalide : {}",
+    ),
+    message(
+        "Invalid language: {}",
+        "Idioma no válido: {}",
+        "Langue invalide : {}",
+    ),
+    message(
+        "Display name must be at most {} characters",
+        "El nombre visible debe tener como máximo {} caracteres",
+        "Le nom d'affichage doit comporter au maximum {} caractères",
+    ),
+    message(
+        "Invalid unsubscribe link",
+        "Enlace para darse de baja no válido",
+        "Lien de désinscription invalide",
+    ),
+    // Feeds
+    message("Feed not found", "Feed no encontrado", "Flux introuvable"),
+    message(
+        "Feed URL already exists",
+        "La URL del feed ya existe",
+        "L'URL du flux existe déjà",
+    ),
+    message(
+        "Invalid URL format",
+        "Formato de URL no válido",
+        "Format d'URL invalide",
+    ),
+    message(
+        "URL must be an http(s) URL",
+        "La URL debe ser http(s)",
+        "L'URL doit être en http(s)",
+    ),
+    message(
+        "At least one URL is required",
+        "Se requiere al menos una URL",
+        "Au moins une URL est requise",
+    ),
+    message(
+        "A maximum of {} URLs can be validated at once",
+        "Se pueden validar como máximo {} URLs a la vez",
+        "{} URL au maximum peuvent être validées à la fois",
+    ),
+    message(
+        "Free tier allows maximum {} feeds. Upgrade to Pro for unlimited feeds.",
+        "El plan gratuito permite un máximo de {} feeds. Pásate a Pro para tener feeds ilimitados.",
+        "L'offre gratuite permet au maximum {} flux. Passez à Pro pour des flux illimités.",
+    ),
+    message(
+        "Mute rule not found",
+        "Regla de silencio no encontrada",
+        "Règle de masquage introuvable",
+    ),
+    // Feed suggestion submissions
+    message(
+        "This feed is already suggested",
+        "Este feed ya está entre las sugerencias",
+        "Ce flux fait déjà partie des suggestions",
+    ),
+    message(
+        "This feed has already been submitted",
+        "Este feed ya se ha propuesto",
+        "Ce flux a déjà été proposé",
+    ),
+    message(
+        "You can have at most {} submissions waiting for review",
+        "Puedes tener como máximo {} propuestas pendientes de revisión",
+        "Vous pouvez avoir au maximum {} propositions en attente de validation",
+    ),
+    message(
+        "A title is required",
+        "Se requiere un título",
+        "Un titre est requis",
+    ),
+    message(
+        "Title cannot exceed {} characters",
+        "El título no puede superar los {} caracteres",
+        "Le titre ne peut pas dépasser {} caractères",
+    ),
+    message(
+        "Description cannot exceed {} characters",
+        "La descripción no puede superar los {} caracteres",
+        "La description ne peut pas dépasser {} caractères",
+    ),
+    // Account backups
+    message(
+        "Unsupported backup version: {}",
+        "Versión de copia de seguridad no admitida: {}",
+        "Version de sauvegarde non prise en charge : {}",
+    ),
+    message(
+        "A backup can hold at most {} feeds",
+        "Una copia de seguridad puede contener como máximo {} feeds",
+        "Une sauvegarde peut contenir au maximum {} flux",
+    ),
+    message(
+        "A backup can hold at most {} mute rules",
+        "Una copia de seguridad puede contener como máximo {} reglas de silencio",
+        "Une sauvegarde peut contenir au maximum {} règles de masquage",
+    ),
+    // Text to speech and plans
+    message(
+        "Text cannot be empty",
+        "El texto no puede estar vacío",
+        "Le texte ne peut pas être vide",
+    ),
+    message(
+        "Daily character limit exceeded. Used: {}, Limit: {}, Request: {}",
+        "Límite diario de caracteres superado. Usados: {}, Límite: {}, Solicitud: {}",
+        "Limite quotidienne de caractères dépassée. Utilisés : {}, Limite : {}, Demande : {}",
+    ),
+    message(
+        "Monthly character limit exceeded. Used: {}, Limit: {}, Request: {}",
+        "Límite mensual de caracteres superado. Usados: {}, Límite: {}, Solicitud: {}",
+        "Limite mensuelle de caractères dépassée. Utilisés : {}, Limite : {}, Demande : {}",
+    ),
+    message(
+        "Speech synthesis is temporarily unavailable, retry shortly",
+        "La síntesis de voz no está disponible en este momento, inténtalo de nuevo en breve",
+        "La synthèse vocale est momentanément indisponible, réessayez dans un instant",
+    ),
+    message(
+        "The text could not be synthesized",
+        "No se ha podido sintetizar el texto",
+        "Le texte n'a pas pu être synthétisé",
+    ),
+    message(
+        "Free trial expired. Please upgrade to Pro to continue.",
+        "La prueba gratuita ha terminado. Pásate a Pro para continuar.",
+        "L'essai gratuit a expiré. Passez à Pro pour continuer.",
+    ),
+    message(
+        "Free trial already used",
+        "Ya has usado la prueba gratuita",
+        "Vous avez déjà utilisé l'essai gratuit",
+    ),
+    message(
+        "Subscription is not active",
+        "La suscripción no está activa",
+        "L'abonnement n'est pas actif",
+    ),
+    message(
+        "Purchase is already linked to another account",
+        "La compra ya está vinculada a otra cuenta",
+        "L'achat est déjà associé à un autre compte",
+    ),
+    // Referrals, promo codes and family plans
+    message(
+        "Referral code not found",
+        "Código de referido no encontrado",
+        "Code de parrainage introuvable",
+    ),
+    message(
+        "Referral code is required",
+        "El código de referido es obligatorio",
+        "Le code de parrainage est obligatoire",
+    ),
+    message(
+        "Referral code already taken",
+        "El código de referido ya está en uso",
+        "Ce code de parrainage est déjà pris",
+    ),
+    message(
+        "Promo code not found",
+        "Código promocional no encontrado",
+        "Code promo introuvable",
+    ),
+    message(
+        "Promo code is required",
+        "El código promocional es obligatorio",
+        "Le code promo est obligatoire",
+    ),
+    message(
+        "Promo code is no longer available",
+        "El código promocional ya no está disponible",
+        "Le code promo n'est plus disponible",
+    ),
+    message(
+        "You have already redeemed this promo code",
+        "Ya has canjeado este código promocional",
+        "Vous avez déjà utilisé ce code promo",
+    ),
+    message(
+        "Family member not found",
+        "Miembro de la familia no encontrado",
+        "Membre de la famille introuvable",
+    ),
+    message(
+        "Family invite not found",
+        "Invitación familiar no encontrada",
+        "Invitation familiale introuvable",
+    ),
+    message(
+        "Family invite is no longer available",
+        "La invitación familiar ya no está disponible",
+        "L'invitation familiale n'est plus disponible",
+    ),
+    message(
+        "Invite code is required",
+        "El código de invitación es obligatorio",
+        "Le code d'invitation est obligatoire",
+    ),
+    message(
+        "You are not part of a family",
+        "No formas parte de una familia",
+        "Vous ne faites partie d'aucune famille",
+    ),
+    message(
+        "You are already part of a family plan",
+        "Ya formas parte de un plan familiar",
+        "Vous faites déjà partie d'une offre famille",
+    ),
+    message(
+        "This invite was sent to a different email",
+        "Esta invitación se envió a otro correo electrónico",
+        "Cette invitation a été envoyée à une autre adresse e-mail",
+    ),
+    // Devices, webhooks, API keys and analytics
+    message(
+        "Device not found",
+        "Dispositivo no encontrado",
+        "Appareil introuvable",
+    ),
+    message(
+        "Device token is required",
+        "El token del dispositivo es obligatorio",
+        "Le jeton de l'appareil est obligatoire",
+    ),
+    message(
+        "Webhook not found",
+        "Webhook no encontrado",
+        "Webhook introuvable",
+    ),
+    message(
+        "At least one event is required",
+        "Se requiere al menos un evento",
+        "Au moins un événement est requis",
+    ),
+    message(
+        "API key not found",
+        "Clave de API no encontrada",
+        "Clé d'API introuvable",
+    ),
+    message(
+        "API key name is required",
+        "El nombre de la clave de API es obligatorio",
+        "Le nom de la clé d'API est obligatoire",
+    ),
+    message(
+        "At least one scope is required",
+        "Se requiere al menos un ámbito",
+        "Au moins une portée est requise",
+    ),
+    message(
+        "A batch cannot exceed {} events",
+        "Un lote no puede superar los {} eventos",
+        "Un lot ne peut pas dépasser {} événements",
+    ),
+    // Request handling
+    message(
+        "Too many requests in progress, retry shortly",
+        "Demasiadas solicitudes en curso, inténtalo de nuevo en breve",
+        "Trop de requêtes en cours, réessayez dans un instant",
+    ),
+    message(
+        "Request body is too large",
+        "El cuerpo de la solicitud es demasiado grande",
+        "Le corps de la requête est trop volumineux",
+    ),
+    message(
+        "Idempotency-Key expired, retry the request",
+        "La Idempotency-Key ha caducado, repite la solicitud",
+        "L'Idempotency-Key a expiré, réessayez la requête",
+    ),
+];
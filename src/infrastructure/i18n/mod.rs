@@ -0,0 +1,171 @@
+mod messages;
+
+/// Languages user-facing error messages are translated to. Anything else gets English.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Fr,
+}
+
+impl Locale {
+    /// Match a language tag such as `es` or `fr-CA` by its primary language
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_']).next()?.trim();
+        if language.eq_ignore_ascii_case("en") {
+            Some(Self::En)
+        } else if language.eq_ignore_ascii_case("es") {
+            Some(Self::Es)
+        } else if language.eq_ignore_ascii_case("fr") {
+            Some(Self::Fr)
+        } else {
+            None
+        }
+    }
+
+    /// The supported language the client prefers most in an `Accept-Language` header,
+    /// honouring `q` weights. None when it lists none of them.
+    pub fn from_accept_language(header: &str) -> Option<Self> {
+        let mut best: Option<(Self, f32)> = None;
+        for entry in header.split(',') {
+            let mut params = entry.split(';');
+            let Some(locale) = params.next().and_then(Self::from_tag) else {
+                continue;
+            };
+            let quality = params
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())
+                .unwrap_or(0.0);
+            if quality > 0.0 && !best.is_some_and(|(_, best_quality)| quality <= best_quality) {
+                best = Some((locale, quality));
+            }
+        }
+
+        best.map(|(locale, _)| locale)
+    }
+
+    /// Value for the `Content-Language` header
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Es => "es",
+            Self::Fr => "fr",
+        }
+    }
+}
+
+/// Translate an English message from the catalog, filling in the values of a
+/// parameterised one. Messages without a translation are returned unchanged.
+pub fn translate(locale: Locale, message: &str) -> String {
+    if locale == Locale::En {
+        return message.to_string();
+    }
+
+    for entry in messages::MESSAGES {
+        if let Some(values) = match_pattern(entry.en, message) {
+            let pattern = match locale {
+                Locale::Es => entry.es,
+                Locale::Fr => entry.fr,
+                Locale::En => entry.en,
+            };
+            return fill_pattern(pattern, &values);
+        }
+    }
+
+    message.to_string()
+}
+
+/// Values standing in for each `{}` when `message` fits `pattern`
+fn match_pattern<'a>(pattern: &str, message: &'a str) -> Option<Vec<&'a str>> {
+    let mut pieces = pattern.split("{}");
+    let first = pieces.next()?;
+    let mut rest = message.strip_prefix(first)?;
+    let pieces: Vec<&str> = pieces.collect();
+    let mut values = Vec::with_capacity(pieces.len());
+
+    for (index, piece) in pieces.iter().enumerate() {
+        let end = if index == pieces.len() - 1 {
+            // The last value runs up to the fixed text closing the message
+            rest.strip_suffix(piece)?.len()
+        } else {
+            rest.find(piece)?
+        };
+        if end == 0 {
+            return None;
+        }
+        values.push(&rest[..end]);
+        rest = &rest[end + piece.len()..];
+    }
+
+    rest.is_empty().then_some(values)
+}
+
+fn fill_pattern(pattern: &str, values: &[&str]) -> String {
+    let mut filled = String::with_capacity(pattern.len());
+    let mut values = values.iter();
+    let mut pieces = pattern.split("{}").peekable();
+    while let Some(piece) = pieces.next() {
+        filled.push_str(piece);
+        if pieces.peek().is_some() {
+            filled.push_str(values.next().copied().unwrap_or_default());
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accept_language_picks_the_preferred_supported_locale() {
+        assert_eq!(
+            Locale::from_accept_language("es-ES,es;q=0.9,en;q=0.8"),
+            Some(Locale::Es)
+        );
+        assert_eq!(
+            Locale::from_accept_language("de-DE, en;q=0.5, fr;q=0.7"),
+            Some(Locale::Fr)
+        );
+        assert_eq!(Locale::from_accept_language("fr;q=0, en"), Some(Locale::En));
+        assert_eq!(Locale::from_accept_language("de, *;q=0.1"), None);
+    }
+
+    #[test]
+    fn test_translate_fills_in_values() {
+        assert_eq!(
+            translate(Locale::Es, "Feed not found"),
+            "Feed no encontrado"
+        );
+        assert_eq!(
+            translate(Locale::Fr, "Invalid language: xx"),
+            "Langue invalide : xx"
+        );
+        assert_eq!(
+            translate(
+                Locale::Es,
+                "Daily character limit exceeded. Used: 19000, Limit: 20000, Request: 2500"
+            ),
+            "Límite diario de caracteres superado. Usados: 19000, Límite: 20000, Solicitud: 2500"
+        );
+    }
+
+    #[test]
+    fn test_untranslated_messages_stay_in_english() {
+        assert_eq!(
+            translate(Locale::Es, "Something nobody translated"),
+            "Something nobody translated"
+        );
+        assert_eq!(translate(Locale::En, "Feed not found"), "Feed not found");
+    }
+
+    #[test]
+    fn test_every_translation_keeps_the_placeholders() {
+        for entry in messages::MESSAGES {
+            let placeholders = entry.en.matches("{}").count();
+            assert_eq!(entry.es.matches("{}").count(), placeholders, "{}", entry.en);
+            assert_eq!(entry.fr.matches("{}").count(), placeholders, "{}", entry.en);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use crate::infrastructure::audio_cdn::AudioCdn;
+use crate::infrastructure::config::RetentionConfig;
+use crate::infrastructure::http::idempotency::IDEMPOTENCY_KEY_TTL_HOURS;
+use crate::infrastructure::repositories::{
+    AuditLogRepository, AuditLogRepositoryApi, IdempotencyRepository, IpBlockRepository,
+    OAuthStateRepository, OutboxRepository, OutboxRepositoryApi, RefreshTokenRepository,
+    RefreshTokenRepositoryApi, UsageRepository, UsageRepositoryApi, WebhookRepository,
+};
+
+/// How long the log of finished webhook deliveries is kept
+const WEBHOOK_DELIVERY_RETENTION_DAYS: i64 = 30;
+/// How long dispatched outbox events are kept, to look into what a handler was given
+const OUTBOX_EVENT_RETENTION_DAYS: i64 = 7;
+
+/// Rows deleted by one maintenance run
+#[derive(Debug, Default, PartialEq)]
+pub struct MaintenanceReport {
+    pub refresh_tokens: u64,
+    pub idempotency_keys: u64,
+    pub usage_days: u64,
+    pub webhook_deliveries: u64,
+    pub ip_blocks: u64,
+    pub outbox_events: u64,
+    pub audit_entries: u64,
+    pub oauth_states: u64,
+    pub audio_objects: u64,
+}
+
+/// Purges data nothing reads anymore: dead refresh tokens, expired idempotency keys, old
+/// webhook delivery logs, expired IP blocks, dispatched outbox events and the states of
+/// abandoned OAuth sign-ins, plus usage, audit entries and audio on the CDN past their
+/// configured retention
+pub struct MaintenanceTask {
+    refresh_token_repo: Arc<RefreshTokenRepository>,
+    idempotency_repo: Arc<IdempotencyRepository>,
+    usage_repo: Arc<UsageRepository>,
+    webhook_repo: Arc<WebhookRepository>,
+    ip_block_repo: Arc<IpBlockRepository>,
+    outbox_repo: Arc<OutboxRepository>,
+    audit_log_repo: Arc<AuditLogRepository>,
+    oauth_state_repo: Arc<OAuthStateRepository>,
+    audio_cdn: Option<Arc<dyn AudioCdn>>,
+    retention: RetentionConfig,
+}
+
+impl MaintenanceTask {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        refresh_token_repo: Arc<RefreshTokenRepository>,
+        idempotency_repo: Arc<IdempotencyRepository>,
+        usage_repo: Arc<UsageRepository>,
+        webhook_repo: Arc<WebhookRepository>,
+        ip_block_repo: Arc<IpBlockRepository>,
+        outbox_repo: Arc<OutboxRepository>,
+        audit_log_repo: Arc<AuditLogRepository>,
+        oauth_state_repo: Arc<OAuthStateRepository>,
+        audio_cdn: Option<Arc<dyn AudioCdn>>,
+        retention: RetentionConfig,
+    ) -> Self {
+        Self {
+            refresh_token_repo,
+            idempotency_repo,
+            usage_repo,
+            webhook_repo,
+            ip_block_repo,
+            outbox_repo,
+            audit_log_repo,
+            oauth_state_repo,
+            audio_cdn,
+            retention,
+        }
+    }
+
+    /// Run every cleanup step. A failing step is logged and skipped so the others still
+    /// run; its count stays at zero.
+    pub async fn run(&self) -> MaintenanceReport {
+        let mut report = MaintenanceReport::default();
+
+        match self.refresh_token_repo.delete_expired().await {
+            Ok(count) => report.refresh_tokens = count,
+            Err(e) => tracing::warn!("Refresh token cleanup failed: {}", e),
+        }
+
+        let expired_before = Utc::now() - Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS);
+        match self.idempotency_repo.delete_expired(expired_before).await {
+            Ok(count) => report.idempotency_keys = count,
+            Err(e) => tracing::warn!("Idempotency key cleanup failed: {}", e),
+        }
+
+        let keep_from = Utc::now().date_naive() - Duration::days(self.retention.usage_days);
+        match self.usage_repo.delete_before(keep_from).await {
+            Ok(count) => report.usage_days = count,
+            Err(e) => tracing::warn!("Usage retention cleanup failed: {}", e),
+        }
+
+        let completed_before = Utc::now() - Duration::days(WEBHOOK_DELIVERY_RETENTION_DAYS);
+        match self
+            .webhook_repo
+            .delete_completed_before(completed_before)
+            .await
+        {
+            Ok(count) => report.webhook_deliveries = count,
+            Err(e) => tracing::warn!("Webhook delivery log cleanup failed: {}", e),
+        }
+
+        match self.ip_block_repo.delete_expired(Utc::now()).await {
+            Ok(count) => report.ip_blocks = count,
+            Err(e) => tracing::warn!("Expired IP block cleanup failed: {}", e),
+        }
+
+        let dispatched_before = Utc::now() - Duration::days(OUTBOX_EVENT_RETENTION_DAYS);
+        match self
+            .outbox_repo
+            .delete_completed_before(dispatched_before)
+            .await
+        {
+            Ok(count) => report.outbox_events = count,
+            Err(e) => tracing::warn!("Outbox event cleanup failed: {}", e),
+        }
+
+        if let Some(days) = self.retention.audit_log_days {
+            let occurred_before = Utc::now() - Duration::days(days);
+            match self.audit_log_repo.delete_before(occurred_before).await {
+                Ok(count) => report.audit_entries = count,
+                Err(e) => tracing::warn!("Audit log retention cleanup failed: {}", e),
+            }
+        }
+
+        match self.oauth_state_repo.delete_expired(Utc::now()).await {
+            Ok(count) => report.oauth_states = count,
+            Err(e) => tracing::warn!("Expired OAuth state cleanup failed: {}", e),
+        }
+
+        if let Some(audio_cdn) = &self.audio_cdn {
+            let uploaded_before = Utc::now() - Duration::days(self.retention.cached_audio_days);
+            match audio_cdn.purge_before(uploaded_before).await {
+                Ok(count) => report.audio_objects = count,
+                Err(e) => tracing::warn!("CDN audio retention cleanup failed: {}", e),
+            }
+        }
+
+        tracing::info!(
+            deleted_refresh_tokens = report.refresh_tokens,
+            deleted_idempotency_keys = report.idempotency_keys,
+            deleted_usage_days = report.usage_days,
+            deleted_webhook_deliveries = report.webhook_deliveries,
+            deleted_ip_blocks = report.ip_blocks,
+            deleted_outbox_events = report.outbox_events,
+            deleted_audit_entries = report.audit_entries,
+            deleted_oauth_states = report.oauth_states,
+            deleted_audio_objects = report.audio_objects,
+            "Maintenance finished"
+        );
+
+        report
+    }
+}
@@ -0,0 +1,28 @@
+pub mod maintenance;
+
+pub use maintenance::{MaintenanceReport, MaintenanceTask};
+
+use std::future::Future;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+/// Run `task` on a background tokio task every `period`, starting immediately.
+///
+/// Runs never overlap: a slow run delays the next tick instead of stacking up.
+pub fn spawn_periodic<F, Fut>(name: &'static str, period: Duration, mut task: F) -> JoinHandle<()>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            tracing::debug!(job = name, "Running background job");
+            task().await;
+        }
+    })
+}
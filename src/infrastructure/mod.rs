@@ -1,6 +1,21 @@
+pub mod audio_cdn;
 pub mod auth;
+pub mod billing;
+pub mod cache;
 pub mod config;
 pub mod db;
+pub mod dev_fakes;
+pub mod email;
+pub mod events;
+pub mod feed_fetcher;
+pub mod grpc;
 pub mod http;
+pub mod i18n;
+pub mod jobs;
 pub mod oauth;
 pub mod repositories;
+pub mod sanitizer;
+pub mod signature;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+pub mod webhooks;
@@ -1,10 +1,17 @@
+use super::OAuthProvider;
 use crate::error::{AppError, AppResult};
+use crate::infrastructure::auth::PropagateRequestId;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-const GITHUB_AUTHORIZE_URL: &str = "https://github.com/login/oauth/authorize";
-const GITHUB_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
-const GITHUB_USER_API_URL: &str = "https://api.github.com/user";
-const GITHUB_USER_EMAIL_API_URL: &str = "https://api.github.com/user/emails";
+/// Where github.com serves the browser sign-in and the token exchange
+pub const GITHUB_OAUTH_BASE_URL: &str = "https://github.com";
+pub const GITHUB_API_BASE_URL: &str = "https://api.github.com";
+
+const AUTHORIZE_PATH: &str = "/login/oauth/authorize";
+const TOKEN_PATH: &str = "/login/oauth/access_token";
+const USER_PATH: &str = "/user";
+const USER_EMAILS_PATH: &str = "/user/emails";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GitHubAccessToken {
@@ -19,6 +26,7 @@ pub struct GitHubUser {
     pub login: String,
     pub email: Option<String>,
     pub name: Option<String>,
+    pub avatar_url: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -32,29 +40,43 @@ pub struct GitHubOAuthClient {
     client_id: String,
     client_secret: String,
     redirect_uri: String,
+    /// `GITHUB_OAUTH_BASE_URL` unless pointed at a stand-in, e.g. in tests
+    oauth_base_url: String,
+    api_base_url: String,
     http_client: reqwest::Client,
 }
 
 impl GitHubOAuthClient {
-    pub fn new(client_id: String, client_secret: String, redirect_uri: String) -> Self {
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        redirect_uri: String,
+        oauth_base_url: String,
+        api_base_url: String,
+    ) -> Self {
         Self {
             client_id,
             client_secret,
             redirect_uri,
+            oauth_base_url: oauth_base_url.trim_end_matches('/').to_string(),
+            api_base_url: api_base_url.trim_end_matches('/').to_string(),
             http_client: reqwest::Client::new(),
         }
     }
+}
 
+#[async_trait]
+impl OAuthProvider for GitHubOAuthClient {
     /// Generate the GitHub OAuth authorization URL
-    pub fn get_authorization_url(&self, state: &str) -> String {
+    fn get_authorization_url(&self, state: &str) -> String {
         format!(
-            "{}?client_id={}&redirect_uri={}&scope=user:email&state={}",
-            GITHUB_AUTHORIZE_URL, self.client_id, self.redirect_uri, state
+            "{}{}?client_id={}&redirect_uri={}&scope=user:email&state={}",
+            self.oauth_base_url, AUTHORIZE_PATH, self.client_id, self.redirect_uri, state
         )
     }
 
     /// Exchange authorization code for access token
-    pub async fn exchange_code(&self, code: &str) -> AppResult<GitHubAccessToken> {
+    async fn exchange_code(&self, code: &str) -> AppResult<GitHubAccessToken> {
         let params = [
             ("client_id", self.client_id.as_str()),
             ("client_secret", self.client_secret.as_str()),
@@ -64,9 +86,10 @@ impl GitHubOAuthClient {
 
         let response = self
             .http_client
-            .post(GITHUB_TOKEN_URL)
+            .post(format!("{}{}", self.oauth_base_url, TOKEN_PATH))
             .header("Accept", "application/json")
             .form(&params)
+            .with_request_id()
             .send()
             .await
             .map_err(|e| AppError::Internal(format!("GitHub token exchange failed: {}", e)))?;
@@ -89,12 +112,13 @@ impl GitHubOAuthClient {
     }
 
     /// Get user information from GitHub
-    pub async fn get_user_info(&self, access_token: &str) -> AppResult<GitHubUser> {
+    async fn get_user_info(&self, access_token: &str) -> AppResult<GitHubUser> {
         let mut user: GitHubUser = self
             .http_client
-            .get(GITHUB_USER_API_URL)
+            .get(format!("{}{}", self.api_base_url, USER_PATH))
             .header("Authorization", format!("Bearer {}", access_token))
             .header("User-Agent", "FeedTape-Backend")
+            .with_request_id()
             .send()
             .await
             .map_err(|e| AppError::Internal(format!("Failed to get GitHub user: {}", e)))?
@@ -106,9 +130,10 @@ impl GitHubOAuthClient {
         if user.email.is_none() {
             let emails: Vec<GitHubEmail> = self
                 .http_client
-                .get(GITHUB_USER_EMAIL_API_URL)
+                .get(format!("{}{}", self.api_base_url, USER_EMAILS_PATH))
                 .header("Authorization", format!("Bearer {}", access_token))
                 .header("User-Agent", "FeedTape-Backend")
+                .with_request_id()
                 .send()
                 .await
                 .map_err(|e| AppError::Internal(format!("Failed to get GitHub emails: {}", e)))?
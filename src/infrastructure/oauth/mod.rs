@@ -1,3 +1,18 @@
 pub mod github;
 
-pub use github::GitHubOAuthClient;
+use async_trait::async_trait;
+
+use crate::error::AppResult;
+pub use github::{GitHubAccessToken, GitHubOAuthClient, GitHubUser};
+
+/// The three steps of signing in with GitHub, so the callback can run against a fake
+#[async_trait]
+pub trait OAuthProvider: Send + Sync {
+    /// Where to send the browser to approve access; `state` comes back in the callback
+    fn get_authorization_url(&self, state: &str) -> String;
+
+    async fn exchange_code(&self, code: &str) -> AppResult<GitHubAccessToken>;
+
+    /// The account behind an access token, with its verified email when it has one
+    async fn get_user_info(&self, access_token: &str) -> AppResult<GitHubUser>;
+}
@@ -0,0 +1,82 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::analytics::{AnalyticsEventName, NewAnalyticsEvent},
+    error::AppResult,
+};
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use sqlx::types::Json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Append-only store of app analytics; nothing here updates or deletes a row
+pub struct AnalyticsEventRepository {
+    pool: Arc<DbPool>,
+}
+
+impl AnalyticsEventRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Store a batch in one transaction, so a retried request never leaves half of it
+    pub async fn insert_batch(
+        &self,
+        events: &[NewAnalyticsEvent],
+        received_at: DateTime<Utc>,
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for event in events {
+            sqlx::query(
+                r#"
+                INSERT INTO analytics_events
+                    (id, user_id, name, session_id, properties, occurred_at, received_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(event.user_id)
+            .bind(event.name.as_str())
+            .bind(event.session_id.as_deref())
+            .bind(Json(&event.properties))
+            .bind(event.occurred_at)
+            .bind(received_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Properties of a user's events of one kind that happened in `[from, to)`
+    pub async fn find_properties(
+        &self,
+        user_id: Uuid,
+        name: AnalyticsEventName,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AppResult<Vec<Map<String, Value>>> {
+        let pool = self.pool.as_ref();
+        let rows = sqlx::query_scalar::<_, Json<Map<String, Value>>>(
+            r#"
+            SELECT properties
+            FROM analytics_events
+            WHERE user_id = $1 AND name = $2 AND occurred_at >= $3 AND occurred_at < $4
+            "#,
+        )
+        .bind(user_id)
+        .bind(name.as_str())
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|Json(properties)| properties)
+            .collect())
+    }
+}
@@ -0,0 +1,108 @@
+use crate::domain::api_key::{ApiKey, ApiKeyScope};
+use crate::error::AppResult;
+use crate::infrastructure::db::DbPool;
+use chrono::Utc;
+use sqlx::types::Json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct ApiKeyRepository {
+    pool: Arc<DbPool>,
+}
+
+impl ApiKeyRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        prefix: &str,
+        key_hash: &str,
+        scopes: &[ApiKeyScope],
+    ) -> AppResult<ApiKey> {
+        let pool = self.pool.as_ref();
+        let api_key = sqlx::query_as::<_, ApiKey>(
+            r#"
+            INSERT INTO api_keys (id, user_id, name, prefix, key_hash, scopes, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, user_id, name, prefix, scopes, created_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(name)
+        .bind(prefix)
+        .bind(key_hash)
+        .bind(Json(scopes))
+        .bind(Utc::now())
+        .fetch_one(pool)
+        .await?;
+
+        Ok(api_key)
+    }
+
+    /// Keys created by a user, oldest first
+    pub async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<ApiKey>> {
+        let pool = self.pool.as_ref();
+        let api_keys = sqlx::query_as::<_, ApiKey>(
+            r#"
+            SELECT id, user_id, name, prefix, scopes, created_at
+            FROM api_keys
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(api_keys)
+    }
+
+    /// The key whose digest this is, for authenticating a request
+    pub async fn find_by_hash(&self, key_hash: &str) -> AppResult<Option<ApiKey>> {
+        let pool = self.pool.as_ref();
+        let api_key = sqlx::query_as::<_, ApiKey>(
+            r#"
+            SELECT id, user_id, name, prefix, scopes, created_at
+            FROM api_keys
+            WHERE key_hash = $1
+            "#,
+        )
+        .bind(key_hash)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(api_key)
+    }
+
+    pub async fn count_by_user(&self, user_id: Uuid) -> AppResult<i64> {
+        let pool = self.pool.as_ref();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM api_keys WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Delete a key owned by a user; requests with it fail from then on
+    pub async fn delete_for_user(&self, id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM api_keys
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::audit::{AuditAction, AuditEntry, AuditEvent},
+    domain::shared::Cursor,
+    error::AppResult,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::net::IpAddr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Rows are only ever inserted; the table rejects updates, and deletes other than the
+/// retention purge
+pub struct AuditLogRepository {
+    pool: Arc<DbPool>,
+}
+
+impl AuditLogRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+pub trait AuditLogRepositoryApi: Send + Sync {
+    async fn append(
+        &self,
+        event: &AuditEvent,
+        client_ip: Option<IpAddr>,
+        request_id: Option<&str>,
+    ) -> AppResult<()>;
+
+    /// Newest first, optionally limited to one account or action
+    async fn list(
+        &self,
+        user_id: Option<Uuid>,
+        action: Option<AuditAction>,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<AuditEntry>>;
+
+    /// Delete entries that occurred before `cutoff`; returns how many went. The table only
+    /// lets the delete through for entries older than a cutoff in `audit_log_purges`,
+    /// which is removed again before the transaction commits.
+    async fn delete_before(&self, cutoff: DateTime<Utc>) -> AppResult<u64>;
+}
+
+#[async_trait]
+impl AuditLogRepositoryApi for AuditLogRepository {
+    async fn append(
+        &self,
+        event: &AuditEvent,
+        client_ip: Option<IpAddr>,
+        request_id: Option<&str>,
+    ) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log
+                (id, occurred_at, actor_type, actor, action, user_id, target, details, client_ip,
+                 request_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(event.occurred_at.unwrap_or_else(Utc::now))
+        .bind(event.actor_type)
+        .bind(&event.actor)
+        .bind(event.action)
+        .bind(event.user_id)
+        .bind(event.target.as_deref())
+        .bind(&event.details)
+        .bind(client_ip.map(|ip| ip.to_string()))
+        .bind(request_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        user_id: Option<Uuid>,
+        action: Option<AuditAction>,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<AuditEntry>> {
+        let pool = self.pool.as_ref();
+        let entries = sqlx::query_as::<_, AuditEntry>(
+            r#"
+            SELECT id, occurred_at, actor_type, actor, action, user_id, target, details,
+                   client_ip, request_id
+            FROM audit_log
+            WHERE ($1 IS NULL OR user_id = $1)
+              AND ($2 IS NULL OR action = $2)
+              AND ($3 IS NULL OR (occurred_at, id) < ($3, $4))
+            ORDER BY occurred_at DESC, id DESC
+            LIMIT $5
+            "#,
+        )
+        .bind(user_id)
+        .bind(action)
+        .bind(after.map(|cursor| cursor.at))
+        .bind(after.map(|cursor| cursor.key))
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn delete_before(&self, cutoff: DateTime<Utc>) -> AppResult<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("INSERT INTO audit_log_purges (delete_before) VALUES ($1)")
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("DELETE FROM audit_log WHERE occurred_at < $1")
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM audit_log_purges")
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+}
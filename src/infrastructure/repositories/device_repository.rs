@@ -0,0 +1,88 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::device::{Device, DevicePlatform},
+    error::AppResult,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct DeviceRepository {
+    pool: Arc<DbPool>,
+}
+
+impl DeviceRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Get all devices registered by a user
+    pub async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<Device>> {
+        let pool = self.pool.as_ref();
+        let devices = sqlx::query_as::<_, Device>(
+            r#"
+            SELECT id, user_id, platform, token, app_version, created_at, updated_at
+            FROM devices
+            WHERE user_id = $1
+            ORDER BY updated_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(devices)
+    }
+
+    /// Register a token, taking it over if it already exists so a device signed into a
+    /// new account stops receiving the previous account's notifications
+    pub async fn upsert(
+        &self,
+        user_id: Uuid,
+        platform: DevicePlatform,
+        token: &str,
+        app_version: Option<&str>,
+    ) -> AppResult<Device> {
+        let pool = self.pool.as_ref();
+        let now = chrono::Utc::now();
+
+        let device = sqlx::query_as::<_, Device>(
+            r#"
+            INSERT INTO devices (id, user_id, platform, token, app_version, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $6)
+            ON CONFLICT (token) DO UPDATE
+            SET user_id = EXCLUDED.user_id,
+                platform = EXCLUDED.platform,
+                app_version = EXCLUDED.app_version,
+                updated_at = EXCLUDED.updated_at
+            RETURNING id, user_id, platform, token, app_version, created_at, updated_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(platform)
+        .bind(token)
+        .bind(app_version)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(device)
+    }
+
+    /// Delete a device token owned by a user
+    pub async fn delete_for_user(&self, user_id: Uuid, token: &str) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM devices
+            WHERE user_id = $1 AND token = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(token)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,219 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::{family::FamilyInvite, user::User},
+    error::{AppError, AppResult},
+};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct FamilyRepository {
+    pool: Arc<DbPool>,
+}
+
+impl FamilyRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Members of the owner's family
+    pub async fn list_members(&self, owner_id: Uuid) -> AppResult<Vec<User>> {
+        let pool = self.pool.as_ref();
+        let members = sqlx::query_as::<_, User>(
+            r#"
+            SELECT * FROM users
+            WHERE family_owner_id = $1 AND deleted_at IS NULL
+            ORDER BY email
+            "#,
+        )
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(members)
+    }
+
+    /// Invites from the owner that can still be accepted
+    pub async fn list_pending_invites(&self, owner_id: Uuid) -> AppResult<Vec<FamilyInvite>> {
+        let pool = self.pool.as_ref();
+        let invites = sqlx::query_as::<_, FamilyInvite>(
+            r#"
+            SELECT code, owner_id, email, expires_at, created_at
+            FROM family_invites
+            WHERE owner_id = $1 AND expires_at > $2
+            ORDER BY created_at
+            "#,
+        )
+        .bind(owner_id)
+        .bind(Utc::now())
+        .fetch_all(pool)
+        .await?;
+
+        Ok(invites)
+    }
+
+    pub async fn find_invite(&self, code: &str) -> AppResult<Option<FamilyInvite>> {
+        let pool = self.pool.as_ref();
+        let invite = sqlx::query_as::<_, FamilyInvite>(
+            r#"
+            SELECT code, owner_id, email, expires_at, created_at
+            FROM family_invites
+            WHERE code = $1
+            "#,
+        )
+        .bind(code)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(invite)
+    }
+
+    /// Create an invite for the email, replacing any earlier invite the owner sent to it
+    pub async fn upsert_invite(
+        &self,
+        code: &str,
+        owner_id: Uuid,
+        email: &str,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<FamilyInvite> {
+        let pool = self.pool.as_ref();
+
+        let invite = sqlx::query_as::<_, FamilyInvite>(
+            r#"
+            INSERT INTO family_invites (code, owner_id, email, expires_at, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (owner_id, email) DO UPDATE
+            SET code = EXCLUDED.code,
+                expires_at = EXCLUDED.expires_at,
+                created_at = EXCLUDED.created_at
+            RETURNING code, owner_id, email, expires_at, created_at
+            "#,
+        )
+        .bind(code)
+        .bind(owner_id)
+        .bind(email)
+        .bind(expires_at)
+        .bind(Utc::now())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(ref db_err) = e {
+                if db_err.is_unique_violation() {
+                    return AppError::Conflict("Invite code already exists".to_string());
+                }
+            }
+            AppError::Database(e)
+        })?;
+
+        Ok(invite)
+    }
+
+    /// Withdraw a pending invite. Returns false if the owner has no invite with this code.
+    pub async fn delete_invite(&self, owner_id: Uuid, code: &str) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query("DELETE FROM family_invites WHERE owner_id = $1 AND code = $2")
+            .bind(owner_id)
+            .bind(code)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Use up the invite and add the user to the owner's family, in one statement. The
+    /// invite is only consumed if the user isn't in a family yet. Returns the owner's id, or
+    /// None if the invite expired or was used in the meantime.
+    #[cfg(not(feature = "sqlite"))]
+    pub async fn accept_invite(&self, code: &str, user_id: Uuid) -> AppResult<Option<Uuid>> {
+        let pool = self.pool.as_ref();
+        let now = Utc::now();
+
+        let owner_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            WITH invite AS (
+                DELETE FROM family_invites
+                WHERE code = $1
+                  AND expires_at > $3
+                  AND EXISTS (
+                      SELECT 1 FROM users WHERE id = $2 AND family_owner_id IS NULL
+                  )
+                RETURNING owner_id
+            )
+            UPDATE users
+            SET family_owner_id = invite.owner_id, updated_at = $3
+            FROM invite
+            WHERE users.id = $2
+            RETURNING users.family_owner_id
+            "#,
+        )
+        .bind(code)
+        .bind(user_id)
+        .bind(now)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(owner_id)
+    }
+
+    /// SQLite variant of `accept_invite`, as a transaction
+    #[cfg(feature = "sqlite")]
+    pub async fn accept_invite(&self, code: &str, user_id: Uuid) -> AppResult<Option<Uuid>> {
+        let mut tx = self.pool.begin().await?;
+        let now = Utc::now();
+
+        let owner_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            DELETE FROM family_invites
+            WHERE code = $1
+              AND expires_at > $3
+              AND EXISTS (
+                  SELECT 1 FROM users WHERE id = $2 AND family_owner_id IS NULL
+              )
+            RETURNING owner_id
+            "#,
+        )
+        .bind(code)
+        .bind(user_id)
+        .bind(now)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(owner_id) = owner_id else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET family_owner_id = $1, updated_at = $2
+            WHERE id = $3
+            "#,
+        )
+        .bind(owner_id)
+        .bind(now)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(Some(owner_id))
+    }
+
+    /// Take a member out of the owner's family. Returns false if they weren't a member.
+    pub async fn remove_member(&self, owner_id: Uuid, member_id: Uuid) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET family_owner_id = NULL, updated_at = $3
+            WHERE id = $2 AND family_owner_id = $1
+            "#,
+        )
+        .bind(owner_id)
+        .bind(member_id)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,26 +1,120 @@
 use crate::infrastructure::db::DbPool;
+use crate::infrastructure::repositories::outbox_repository::append_event;
 use crate::{
-    domain::feed::Feed,
+    domain::{
+        events::DomainEvent,
+        feed::{Feed, FeedStats},
+        feed_suggestions::FeedSuggestion,
+        shared::Cursor,
+    },
     error::{AppError, AppResult},
 };
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Feed totals for admin statistics
+#[derive(Debug, FromRow)]
+pub struct FeedCounts {
+    pub total: i64,
+    pub users_with_feeds: i64,
+    /// Feeds added from a suggestion
+    pub from_suggestions: i64,
+}
+
 pub struct FeedRepository {
     pool: Arc<DbPool>,
+    /// Replica for listings; lookups that gate a write stay on the primary
+    read_pool: Arc<DbPool>,
 }
 
 impl FeedRepository {
     pub fn new(pool: Arc<DbPool>) -> Self {
-        Self { pool }
+        Self {
+            read_pool: pool.clone(),
+            pool,
+        }
+    }
+
+    pub fn with_read_pool(mut self, read_pool: Arc<DbPool>) -> Self {
+        self.read_pool = read_pool;
+        self
     }
+}
 
+#[async_trait]
+pub trait FeedRepositoryApi: Send + Sync {
     /// Get all feeds for a user
-    pub async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<Feed>> {
-        let pool = self.pool.as_ref();
+    async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<Feed>>;
+
+    /// One page of a user's feeds, newest first
+    async fn find_page_by_user(
+        &self,
+        user_id: Uuid,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<Feed>>;
+
+    /// Get a feed by ID
+    async fn find_by_id(&self, feed_id: Uuid) -> AppResult<Option<Feed>>;
+
+    /// Get feeds whose stats were never computed or were computed before `before`,
+    /// least recently refreshed first
+    async fn find_with_stale_stats(
+        &self,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<Feed>>;
+
+    /// Check if a user already has a feed with this URL
+    async fn exists_for_user(&self, user_id: Uuid, url: &str) -> AppResult<bool>;
+
+    /// Feed totals across all users
+    async fn count_feeds(&self) -> AppResult<FeedCounts>;
+
+    /// Count feeds for a user
+    async fn count_by_user(&self, user_id: Uuid) -> AppResult<i64>;
+
+    /// Create a new feed with client-provided ID, optionally linked to the suggestion it
+    /// was added from
+    async fn create(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        url: &str,
+        title: &str,
+        suggestion: Option<&FeedSuggestion>,
+    ) -> AppResult<()>;
+
+    /// Update a feed (title)
+    async fn update(&self, feed: &Feed) -> AppResult<()>;
+
+    /// Store freshly computed activity stats for a feed
+    async fn update_stats(
+        &self,
+        feed_id: Uuid,
+        stats: &FeedStats,
+        updated_at: DateTime<Utc>,
+    ) -> AppResult<()>;
+
+    /// Record a stats refresh attempt without changing the stored stats
+    async fn mark_stats_checked(&self, feed_id: Uuid, checked_at: DateTime<Utc>) -> AppResult<()>;
+
+    /// Delete a feed
+    async fn delete(&self, feed_id: Uuid) -> AppResult<bool>;
+}
+
+#[async_trait]
+impl FeedRepositoryApi for FeedRepository {
+    async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<Feed>> {
+        let pool = self.read_pool.as_ref();
         let feeds = sqlx::query_as::<_, Feed>(
             r#"
-            SELECT id, user_id, url, title, created_at
+            SELECT id, user_id, url, title, created_at,
+                   avg_articles_per_day, last_published_at, stats_updated_at,
+                   suggestion_id, category_id
             FROM feeds
             WHERE user_id = $1
             ORDER BY created_at DESC
@@ -33,12 +127,42 @@ impl FeedRepository {
         Ok(feeds)
     }
 
-    /// Get a feed by ID
-    pub async fn find_by_id(&self, feed_id: Uuid) -> AppResult<Option<Feed>> {
+    async fn find_page_by_user(
+        &self,
+        user_id: Uuid,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<Feed>> {
+        let pool = self.read_pool.as_ref();
+        let feeds = sqlx::query_as::<_, Feed>(
+            r#"
+            SELECT id, user_id, url, title, created_at,
+                   avg_articles_per_day, last_published_at, stats_updated_at,
+                   suggestion_id, category_id
+            FROM feeds
+            WHERE user_id = $1
+              AND ($2 IS NULL OR (created_at, id) < ($2, $3))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(user_id)
+        .bind(after.map(|cursor| cursor.at))
+        .bind(after.map(|cursor| cursor.key))
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(feeds)
+    }
+
+    async fn find_by_id(&self, feed_id: Uuid) -> AppResult<Option<Feed>> {
         let pool = self.pool.as_ref();
         let feed = sqlx::query_as::<_, Feed>(
             r#"
-            SELECT id, user_id, url, title, created_at
+            SELECT id, user_id, url, title, created_at,
+                   avg_articles_per_day, last_published_at, stats_updated_at,
+                   suggestion_id, category_id
             FROM feeds
             WHERE id = $1
             "#,
@@ -50,8 +174,32 @@ impl FeedRepository {
         Ok(feed)
     }
 
-    /// Check if a user already has a feed with this URL
-    pub async fn exists_for_user(&self, user_id: Uuid, url: &str) -> AppResult<bool> {
+    async fn find_with_stale_stats(
+        &self,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<Feed>> {
+        let pool = self.read_pool.as_ref();
+        let feeds = sqlx::query_as::<_, Feed>(
+            r#"
+            SELECT id, user_id, url, title, created_at,
+                   avg_articles_per_day, last_published_at, stats_updated_at,
+                   suggestion_id, category_id
+            FROM feeds
+            WHERE stats_updated_at IS NULL OR stats_updated_at < $1
+            ORDER BY stats_updated_at ASC NULLS FIRST
+            LIMIT $2
+            "#,
+        )
+        .bind(before)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(feeds)
+    }
+
+    async fn exists_for_user(&self, user_id: Uuid, url: &str) -> AppResult<bool> {
         let pool = self.pool.as_ref();
         let exists = sqlx::query_scalar::<_, bool>(
             r#"
@@ -69,8 +217,23 @@ impl FeedRepository {
         Ok(exists)
     }
 
-    /// Count feeds for a user
-    pub async fn count_by_user(&self, user_id: Uuid) -> AppResult<i64> {
+    async fn count_feeds(&self) -> AppResult<FeedCounts> {
+        let pool = self.read_pool.as_ref();
+        let counts = sqlx::query_as::<_, FeedCounts>(
+            r#"
+            SELECT COUNT(*) AS total,
+                   COUNT(DISTINCT user_id) AS users_with_feeds,
+                   COUNT(suggestion_id) AS from_suggestions
+            FROM feeds
+            "#,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    async fn count_by_user(&self, user_id: Uuid) -> AppResult<i64> {
         let pool = self.pool.as_ref();
         let count = sqlx::query_scalar::<_, i64>(
             r#"
@@ -86,15 +249,21 @@ impl FeedRepository {
         Ok(count)
     }
 
-    /// Create a new feed with client-provided ID
-    pub async fn create(&self, id: Uuid, user_id: Uuid, url: &str, title: &str) -> AppResult<()> {
-        let pool = self.pool.as_ref();
+    async fn create(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        url: &str,
+        title: &str,
+        suggestion: Option<&FeedSuggestion>,
+    ) -> AppResult<()> {
         let now = chrono::Utc::now();
 
+        let mut tx = self.pool.begin().await?;
         sqlx::query(
             r#"
-            INSERT INTO feeds (id, user_id, url, title, created_at)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO feeds (id, user_id, url, title, created_at, suggestion_id, category_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
         )
         .bind(id)
@@ -102,7 +271,9 @@ impl FeedRepository {
         .bind(url)
         .bind(title)
         .bind(now)
-        .execute(pool)
+        .bind(suggestion.map(|s| s.id.as_str()))
+        .bind(suggestion.map(|s| s.category_id.as_str()))
+        .execute(&mut *tx)
         .await
         .map_err(|e| {
             if let sqlx::Error::Database(ref db_err) = e {
@@ -113,11 +284,21 @@ impl FeedRepository {
             AppError::Database(e)
         })?;
 
+        append_event(
+            &mut tx,
+            &DomainEvent::FeedAdded {
+                user_id,
+                feed_id: id,
+                url: url.to_string(),
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
         Ok(())
     }
 
-    /// Update a feed (title)
-    pub async fn update(&self, feed: &Feed) -> AppResult<()> {
+    async fn update(&self, feed: &Feed) -> AppResult<()> {
         let pool = self.pool.as_ref();
         sqlx::query(
             r#"
@@ -134,8 +315,48 @@ impl FeedRepository {
         Ok(())
     }
 
-    /// Delete a feed
-    pub async fn delete(&self, feed_id: Uuid) -> AppResult<bool> {
+    async fn update_stats(
+        &self,
+        feed_id: Uuid,
+        stats: &FeedStats,
+        updated_at: DateTime<Utc>,
+    ) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+        sqlx::query(
+            r#"
+            UPDATE feeds
+            SET avg_articles_per_day = $1, last_published_at = $2, stats_updated_at = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(stats.avg_articles_per_day)
+        .bind(stats.last_published_at)
+        .bind(updated_at)
+        .bind(feed_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_stats_checked(&self, feed_id: Uuid, checked_at: DateTime<Utc>) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+        sqlx::query(
+            r#"
+            UPDATE feeds
+            SET stats_updated_at = $1
+            WHERE id = $2
+            "#,
+        )
+        .bind(checked_at)
+        .bind(feed_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, feed_id: Uuid) -> AppResult<bool> {
         let pool = self.pool.as_ref();
         let result = sqlx::query(
             r#"
@@ -1,6 +1,6 @@
 use crate::domain::feed_suggestions::{Category, FeedSuggestion, FeedSuggestionsRepository};
 use std::collections::{HashMap, HashSet};
-use std::sync::LazyLock;
+use std::sync::{LazyLock, RwLock};
 
 static CATEGORIES: LazyLock<Vec<Category>> = LazyLock::new(|| {
     vec![
@@ -692,7 +692,11 @@ static FEED_SUGGESTIONS: LazyLock<Vec<FeedSuggestion>> = LazyLock::new(|| {
     ]
 });
 
-pub struct HardcodedFeedSuggestionsRepository;
+/// The curated suggestions, plus the ones promoted from user submissions, which live in
+/// the database and are kept here so reading suggestions never touches it
+pub struct HardcodedFeedSuggestionsRepository {
+    promoted: RwLock<Vec<FeedSuggestion>>,
+}
 
 impl HardcodedFeedSuggestionsRepository {
     pub fn new() -> Self {
@@ -719,7 +723,9 @@ impl HardcodedFeedSuggestionsRepository {
             );
         }
 
-        Self
+        Self {
+            promoted: RwLock::new(Vec::new()),
+        }
     }
 }
 
@@ -742,10 +748,11 @@ impl FeedSuggestionsRepository for HardcodedFeedSuggestionsRepository {
         }
 
         // Filter suggestions and deduplicate by URL
+        let promoted = self.promoted.read().unwrap_or_else(|e| e.into_inner());
         let mut seen_urls: HashSet<&String> = HashSet::new();
         let mut results = Vec::new();
 
-        for suggestion in FEED_SUGGESTIONS.iter() {
+        for suggestion in FEED_SUGGESTIONS.iter().chain(promoted.iter()) {
             if category_ids.contains(&suggestion.category_id) && seen_urls.insert(&suggestion.url) {
                 results.push(suggestion.clone());
             }
@@ -753,6 +760,19 @@ impl FeedSuggestionsRepository for HardcodedFeedSuggestionsRepository {
 
         results
     }
+
+    fn find_suggestion(&self, suggestion_id: &str) -> Option<FeedSuggestion> {
+        let promoted = self.promoted.read().unwrap_or_else(|e| e.into_inner());
+        FEED_SUGGESTIONS
+            .iter()
+            .chain(promoted.iter())
+            .find(|suggestion| suggestion.id == suggestion_id)
+            .cloned()
+    }
+
+    fn set_promoted(&self, suggestions: Vec<FeedSuggestion>) {
+        *self.promoted.write().unwrap_or_else(|e| e.into_inner()) = suggestions;
+    }
 }
 
 impl Default for HardcodedFeedSuggestionsRepository {
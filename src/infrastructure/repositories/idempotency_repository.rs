@@ -0,0 +1,140 @@
+use crate::error::AppResult;
+use crate::infrastructure::db::DbPool;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A request made with an Idempotency-Key, and its response once it has one
+#[derive(Debug, FromRow)]
+pub struct IdempotencyRecord {
+    pub request_hash: String,
+    pub status_code: Option<i16>,
+    pub content_type: Option<String>,
+    pub response_body: Option<Vec<u8>>,
+}
+
+pub struct IdempotencyRepository {
+    pool: Arc<DbPool>,
+}
+
+impl IdempotencyRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Reserve a key for a new request. Keys created before `expired_before` are taken
+    /// over. Returns false when the key is already in use.
+    pub async fn claim(
+        &self,
+        user_id: Uuid,
+        key: &str,
+        request_hash: &str,
+        expired_before: DateTime<Utc>,
+    ) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (user_id, key, request_hash, created_at)
+            VALUES ($1, $2, $3, $5)
+            ON CONFLICT (user_id, key) DO UPDATE
+            SET request_hash = EXCLUDED.request_hash,
+                status_code = NULL,
+                content_type = NULL,
+                response_body = NULL,
+                created_at = EXCLUDED.created_at
+            WHERE idempotency_keys.created_at < $4
+            "#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .bind(request_hash)
+        .bind(expired_before)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn find(&self, user_id: Uuid, key: &str) -> AppResult<Option<IdempotencyRecord>> {
+        let pool = self.pool.as_ref();
+        let record = sqlx::query_as::<_, IdempotencyRecord>(
+            r#"
+            SELECT request_hash, status_code, content_type, response_body
+            FROM idempotency_keys
+            WHERE user_id = $1 AND key = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Store the response to replay for a claimed key
+    pub async fn complete(
+        &self,
+        user_id: Uuid,
+        key: &str,
+        status_code: u16,
+        content_type: Option<&str>,
+        response_body: &[u8],
+    ) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+        sqlx::query(
+            r#"
+            UPDATE idempotency_keys
+            SET status_code = $3, content_type = $4, response_body = $5
+            WHERE user_id = $1 AND key = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .bind(status_code as i16)
+        .bind(content_type)
+        .bind(response_body)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Free a claimed key so the request can be retried. Keys that already have a
+    /// response are kept.
+    pub async fn release(&self, user_id: Uuid, key: &str) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+        sqlx::query(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE user_id = $1 AND key = $2 AND status_code IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(key)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete keys created before `expired_before` (cleanup)
+    pub async fn delete_expired(&self, expired_before: DateTime<Utc>) -> AppResult<u64> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE created_at < $1
+            "#,
+        )
+        .bind(expired_before)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,1266 @@
+//! HashMap-backed stand-ins for the database repositories, so domain services can be
+//! exercised without Postgres. Each one follows the semantics of the SQL it replaces,
+//! including the conflicts it reports, but nothing survives the process.
+
+use crate::domain::audit::{AuditAction, AuditEntry, AuditEvent};
+use crate::domain::auth::SessionClient;
+use crate::domain::events::{DomainEvent, EventOrigin};
+use crate::domain::feed::{Feed, FeedStats};
+use crate::domain::feed_suggestions::FeedSuggestion;
+use crate::domain::outbox::{OutboxEvent, OutboxPayload};
+use crate::domain::plan::RolloverDay;
+use crate::domain::shared::Cursor;
+use crate::domain::subscription::{SubscriptionEvent, SubscriptionEventType, SubscriptionPlatform};
+use crate::domain::user::{
+    SubscriptionStatus, SubscriptionTier, User, UserRole, TRIAL_DURATION_DAYS,
+};
+use crate::error::{AppError, AppResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde_json::json;
+use sqlx::types::Json;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use uuid::Uuid;
+
+use super::{
+    AuditLogRepositoryApi, DailyUsageTotals, FeedCounts, FeedRepositoryApi, OutboxRepositoryApi,
+    ProviderSynthesisTotals, RefreshSession, RefreshTokenRepositoryApi,
+    SubscriptionEventRepositoryApi, SynthesisUsage, UsageRecord, UsageRepositoryApi, UsageTotals,
+    UserCounts, UserRepositoryApi,
+};
+
+/// What `fetch_one` reports when an UPDATE ... RETURNING matches no row
+fn row_not_found() -> AppError {
+    AppError::Database(sqlx::Error::RowNotFound)
+}
+
+/// Whether an item sorted by `(at, key)` descending comes after the cursor
+fn is_after<K: Ord>(after: Option<&Cursor<K>>, at: DateTime<Utc>, key: &K) -> bool {
+    after.is_none_or(|cursor| (at, key) < (cursor.at, &cursor.key))
+}
+
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Default)]
+pub struct InMemoryFeedRepository {
+    feeds: RwLock<HashMap<Uuid, Feed>>,
+}
+
+impl InMemoryFeedRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl FeedRepositoryApi for InMemoryFeedRepository {
+    async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<Feed>> {
+        let mut feeds: Vec<Feed> = read(&self.feeds)
+            .values()
+            .filter(|feed| feed.user_id == user_id)
+            .cloned()
+            .collect();
+        feeds.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        Ok(feeds)
+    }
+
+    async fn find_page_by_user(
+        &self,
+        user_id: Uuid,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<Feed>> {
+        let mut feeds: Vec<Feed> = read(&self.feeds)
+            .values()
+            .filter(|feed| feed.user_id == user_id && is_after(after, feed.created_at, &feed.id))
+            .cloned()
+            .collect();
+        feeds.sort_by(|a, b| (b.created_at, b.id).cmp(&(a.created_at, a.id)));
+        feeds.truncate(limit.max(0) as usize);
+
+        Ok(feeds)
+    }
+
+    async fn find_by_id(&self, feed_id: Uuid) -> AppResult<Option<Feed>> {
+        Ok(read(&self.feeds).get(&feed_id).cloned())
+    }
+
+    async fn find_with_stale_stats(
+        &self,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<Feed>> {
+        let mut feeds: Vec<Feed> = read(&self.feeds)
+            .values()
+            .filter(|feed| feed.stats_updated_at.is_none_or(|at| at < before))
+            .cloned()
+            .collect();
+        // None sorts first, like NULLS FIRST
+        feeds.sort_by_key(|feed| feed.stats_updated_at);
+        feeds.truncate(limit.max(0) as usize);
+
+        Ok(feeds)
+    }
+
+    async fn exists_for_user(&self, user_id: Uuid, url: &str) -> AppResult<bool> {
+        Ok(read(&self.feeds)
+            .values()
+            .any(|feed| feed.user_id == user_id && feed.url == url))
+    }
+
+    async fn count_feeds(&self) -> AppResult<FeedCounts> {
+        let feeds = read(&self.feeds);
+        let users: HashSet<Uuid> = feeds.values().map(|feed| feed.user_id).collect();
+
+        Ok(FeedCounts {
+            total: feeds.len() as i64,
+            users_with_feeds: users.len() as i64,
+            from_suggestions: feeds
+                .values()
+                .filter(|feed| feed.suggestion_id.is_some())
+                .count() as i64,
+        })
+    }
+
+    async fn count_by_user(&self, user_id: Uuid) -> AppResult<i64> {
+        Ok(read(&self.feeds)
+            .values()
+            .filter(|feed| feed.user_id == user_id)
+            .count() as i64)
+    }
+
+    async fn create(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        url: &str,
+        title: &str,
+        suggestion: Option<&FeedSuggestion>,
+    ) -> AppResult<()> {
+        let mut feeds = write(&self.feeds);
+        let duplicate = feeds.contains_key(&id)
+            || feeds
+                .values()
+                .any(|feed| feed.user_id == user_id && feed.url == url);
+        if duplicate {
+            return Err(AppError::Conflict("Feed URL already exists".to_string()));
+        }
+
+        feeds.insert(
+            id,
+            Feed {
+                id,
+                user_id,
+                url: url.to_string(),
+                title: Some(title.to_string()),
+                created_at: Utc::now(),
+                avg_articles_per_day: None,
+                last_published_at: None,
+                stats_updated_at: None,
+                suggestion_id: suggestion.map(|s| s.id.clone()),
+                category_id: suggestion.map(|s| s.category_id.clone()),
+            },
+        );
+
+        Ok(())
+    }
+
+    async fn update(&self, feed: &Feed) -> AppResult<()> {
+        if let Some(stored) = write(&self.feeds).get_mut(&feed.id) {
+            stored.title = feed.title.clone();
+        }
+
+        Ok(())
+    }
+
+    async fn update_stats(
+        &self,
+        feed_id: Uuid,
+        stats: &FeedStats,
+        updated_at: DateTime<Utc>,
+    ) -> AppResult<()> {
+        if let Some(feed) = write(&self.feeds).get_mut(&feed_id) {
+            feed.avg_articles_per_day = stats.avg_articles_per_day;
+            feed.last_published_at = stats.last_published_at;
+            feed.stats_updated_at = Some(updated_at);
+        }
+
+        Ok(())
+    }
+
+    async fn mark_stats_checked(&self, feed_id: Uuid, checked_at: DateTime<Utc>) -> AppResult<()> {
+        if let Some(feed) = write(&self.feeds).get_mut(&feed_id) {
+            feed.stats_updated_at = Some(checked_at);
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, feed_id: Uuid) -> AppResult<bool> {
+        Ok(write(&self.feeds).remove(&feed_id).is_some())
+    }
+}
+
+/// A user that hasn't been soft-deleted, for the writes that must not touch deleted ones
+fn live_mut(users: &mut HashMap<Uuid, User>, user_id: Uuid) -> Option<&mut User> {
+    users
+        .get_mut(&user_id)
+        .filter(|user| user.deleted_at.is_none())
+}
+
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: RwLock<HashMap<Uuid, User>>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a user as-is, for tests that need state no repository method produces
+    pub fn insert(&self, user: User) {
+        write(&self.users).insert(user.id, user);
+    }
+
+    fn find_where(&self, predicate: impl Fn(&User) -> bool) -> Option<User> {
+        read(&self.users)
+            .values()
+            .find(|user| user.deleted_at.is_none() && predicate(user))
+            .cloned()
+    }
+
+    /// Apply `change` to a stored user and bump `updated_at`, like the UPDATE ... RETURNING
+    /// statements it stands in for
+    fn modify(&self, user_id: Uuid, change: impl FnOnce(&mut User)) -> AppResult<User> {
+        let mut users = write(&self.users);
+        let user = live_mut(&mut users, user_id).ok_or_else(row_not_found)?;
+        change(user);
+        user.updated_at = Utc::now();
+
+        Ok(user.clone())
+    }
+}
+
+#[async_trait]
+impl UserRepositoryApi for InMemoryUserRepository {
+    async fn find_by_id(&self, user_id: Uuid) -> AppResult<Option<User>> {
+        let users = read(&self.users);
+        let live = |id: &Uuid| users.get(id).filter(|user| user.deleted_at.is_none());
+        let mut user = live(&user_id).cloned();
+
+        if let Some(user) = user.as_mut() {
+            if let Some(owner_id) = user.family_owner_id {
+                user.family_tier = live(&owner_id).map(|owner| owner.effective_tier());
+            }
+        }
+
+        Ok(user)
+    }
+
+    async fn find_by_email(&self, email: &str) -> AppResult<Option<User>> {
+        Ok(self.find_where(|user| user.email == email))
+    }
+
+    async fn find_by_oauth(&self, provider: &str, provider_id: &str) -> AppResult<Option<User>> {
+        Ok(self.find_where(|user| {
+            user.oauth_provider == provider && user.oauth_provider_id == provider_id
+        }))
+    }
+
+    async fn find_by_referral_code(&self, code: &str) -> AppResult<Option<User>> {
+        Ok(self.find_where(|user| user.referral_code.as_deref() == Some(code)))
+    }
+
+    async fn search(
+        &self,
+        query: Option<&str>,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<User>> {
+        let query = query.map(str::to_lowercase);
+        let matches = |user: &User| match &query {
+            _ if user.deleted_at.is_some() => false,
+            None => true,
+            Some(q) => {
+                user.email.to_lowercase().contains(q.as_str())
+                    || user
+                        .display_name
+                        .as_ref()
+                        .is_some_and(|name| name.to_lowercase().contains(q.as_str()))
+            }
+        };
+
+        let mut users: Vec<User> = read(&self.users)
+            .values()
+            .filter(|user| matches(user) && is_after(after, user.created_at, &user.id))
+            .cloned()
+            .collect();
+        users.sort_by(|a, b| (b.created_at, b.id).cmp(&(a.created_at, a.id)));
+        users.truncate(limit.max(0) as usize);
+
+        Ok(users)
+    }
+
+    async fn count_users(&self) -> AppResult<UserCounts> {
+        let users = read(&self.users);
+        let live: Vec<&User> = users
+            .values()
+            .filter(|user| user.deleted_at.is_none())
+            .collect();
+
+        Ok(UserCounts {
+            total: live.len() as i64,
+            pro: live
+                .iter()
+                .filter(|user| user.subscription_tier == SubscriptionTier::Pro)
+                .count() as i64,
+            pending_deletion: live
+                .iter()
+                .filter(|user| user.deletion_scheduled_at.is_some())
+                .count() as i64,
+        })
+    }
+
+    async fn create(
+        &self,
+        email: &str,
+        provider: &str,
+        provider_id: &str,
+        display_name: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> AppResult<User> {
+        let mut users = write(&self.users);
+        let taken = users.values().any(|user| {
+            user.email == email
+                || (user.oauth_provider == provider && user.oauth_provider_id == provider_id)
+        });
+        if taken {
+            return Err(AppError::Conflict("Account already exists".to_string()));
+        }
+
+        let now = Utc::now();
+        let user = User {
+            id: Uuid::new_v4(),
+            email: email.to_string(),
+            oauth_provider: provider.to_string(),
+            oauth_provider_id: provider_id.to_string(),
+            display_name: display_name.map(str::to_string),
+            avatar_url: avatar_url.map(str::to_string),
+            settings: json!({
+                "voice": "Lucia",
+                "speed": 1.0,
+                "language": "auto",
+                "quality": "standard"
+            }),
+            subscription_tier: SubscriptionTier::Free,
+            subscription_status: SubscriptionStatus::Active,
+            subscription_expires_at: None,
+            trial_started_at: Some(now),
+            trial_ends_at: Some(now + Duration::days(TRIAL_DURATION_DAYS)),
+            deletion_scheduled_at: None,
+            deleted_at: None,
+            limit_overrides: None,
+            referral_code: None,
+            referral_bonus_until: None,
+            family_owner_id: None,
+            role: UserRole::User,
+            family_tier: None,
+            created_at: now,
+            updated_at: now,
+        };
+        users.insert(user.id, user.clone());
+
+        Ok(user)
+    }
+
+    async fn update_profile(
+        &self,
+        user_id: Uuid,
+        display_name: Option<&str>,
+        settings: Option<serde_json::Value>,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<User>> {
+        let mut users = write(&self.users);
+        let Some(user) = live_mut(&mut users, user_id) else {
+            return Ok(None);
+        };
+        if expected_updated_at.is_some_and(|expected| expected != user.updated_at) {
+            return Ok(None);
+        }
+
+        if let Some(display_name) = display_name {
+            user.display_name = Some(display_name.to_string());
+        }
+        if let Some(settings) = settings {
+            user.settings = settings;
+        }
+        user.updated_at = Utc::now();
+
+        Ok(Some(user.clone()))
+    }
+
+    async fn update_subscription(
+        &self,
+        user_id: Uuid,
+        tier: SubscriptionTier,
+        status: SubscriptionStatus,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> AppResult<User> {
+        self.modify(user_id, |user| {
+            user.subscription_tier = tier;
+            user.subscription_status = status;
+            user.subscription_expires_at = expires_at;
+        })
+    }
+
+    async fn set_limit_overrides(
+        &self,
+        user_id: Uuid,
+        overrides: Option<serde_json::Value>,
+    ) -> AppResult<User> {
+        self.modify(user_id, |user| user.limit_overrides = overrides)
+    }
+
+    async fn set_role(&self, user_id: Uuid, role: UserRole) -> AppResult<User> {
+        self.modify(user_id, |user| user.role = role)
+    }
+
+    async fn set_referral_code(&self, user_id: Uuid, code: &str) -> AppResult<String> {
+        let mut users = write(&self.users);
+        let taken = users
+            .values()
+            .any(|user| user.id != user_id && user.referral_code.as_deref() == Some(code));
+
+        let user = live_mut(&mut users, user_id).ok_or_else(row_not_found)?;
+        if user.referral_code.is_none() {
+            if taken {
+                return Err(AppError::Conflict(
+                    "Referral code already taken".to_string(),
+                ));
+            }
+            user.referral_code = Some(code.to_string());
+        }
+        user.updated_at = Utc::now();
+
+        Ok(user.referral_code.clone().unwrap_or_default())
+    }
+
+    async fn refresh_oauth_profile(
+        &self,
+        user_id: Uuid,
+        display_name: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> AppResult<User> {
+        self.modify(user_id, |user| {
+            if user.display_name.is_none() {
+                user.display_name = display_name.map(str::to_string);
+            }
+            user.avatar_url = avatar_url.map(str::to_string);
+        })
+    }
+
+    async fn set_deletion_schedule(
+        &self,
+        user_id: Uuid,
+        scheduled_at: Option<DateTime<Utc>>,
+    ) -> AppResult<User> {
+        self.modify(user_id, |user| user.deletion_scheduled_at = scheduled_at)
+    }
+
+    async fn soft_delete_scheduled(&self, now: DateTime<Utc>) -> AppResult<Vec<Uuid>> {
+        let mut ids = Vec::new();
+        for user in write(&self.users).values_mut() {
+            let due = user.deletion_scheduled_at.is_some_and(|at| at <= now);
+            if due && user.deleted_at.is_none() {
+                user.deleted_at = Some(now);
+                user.updated_at = now;
+                ids.push(user.id);
+            }
+        }
+
+        Ok(ids)
+    }
+
+    async fn purge_deleted(&self, before: DateTime<Utc>) -> AppResult<Vec<Uuid>> {
+        let mut users = write(&self.users);
+        let ids: Vec<Uuid> = users
+            .values()
+            .filter(|user| user.deleted_at.is_some_and(|at| at <= before))
+            .map(|user| user.id)
+            .collect();
+        for id in &ids {
+            users.remove(id);
+        }
+
+        Ok(ids)
+    }
+
+    async fn start_trial(
+        &self,
+        user_id: Uuid,
+        started_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+    ) -> AppResult<Option<User>> {
+        let mut users = write(&self.users);
+        let Some(user) =
+            live_mut(&mut users, user_id).filter(|user| user.trial_started_at.is_none())
+        else {
+            return Ok(None);
+        };
+        user.trial_started_at = Some(started_at);
+        user.trial_ends_at = Some(ends_at);
+        user.updated_at = started_at;
+
+        Ok(Some(user.clone()))
+    }
+}
+
+struct UsageDay {
+    characters_used: i32,
+    articles_synthesized: i32,
+    audio_seconds: f64,
+    provider: String,
+    engine: String,
+    rollover_characters: Option<i32>,
+}
+
+impl UsageDay {
+    fn record(&self, user_id: Uuid, date: NaiveDate) -> UsageRecord {
+        UsageRecord {
+            user_id,
+            date,
+            characters_used: self.characters_used,
+            articles_synthesized: self.articles_synthesized,
+            audio_seconds: self.audio_seconds,
+            provider: Some(self.provider.clone()),
+            engine: Some(self.engine.clone()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SynthesisDay {
+    requests: i64,
+    characters: i64,
+}
+
+#[derive(Default)]
+pub struct InMemoryUsageRepository {
+    /// Ordered by user, then day, which is how every query walks them
+    days: RwLock<BTreeMap<(Uuid, NaiveDate), UsageDay>>,
+    synthesis: RwLock<BTreeMap<(NaiveDate, String), SynthesisDay>>,
+}
+
+impl InMemoryUsageRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UsageRepositoryApi for InMemoryUsageRepository {
+    async fn get_usage_between(
+        &self,
+        user_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> AppResult<UsageTotals> {
+        let mut totals = UsageTotals::default();
+        if start >= end {
+            return Ok(totals);
+        }
+        for (_, day) in read(&self.days).range((user_id, start)..(user_id, end)) {
+            totals.characters_used += day.characters_used;
+            totals.articles_synthesized += day.articles_synthesized;
+            totals.audio_seconds += day.audio_seconds;
+        }
+
+        Ok(totals)
+    }
+
+    async fn increment_usage(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+        usage: SynthesisUsage,
+        rollover_characters: Option<i32>,
+    ) -> AppResult<()> {
+        let mut days = write(&self.days);
+        let day = days.entry((user_id, date)).or_insert(UsageDay {
+            characters_used: 0,
+            articles_synthesized: 0,
+            audio_seconds: 0.0,
+            provider: String::new(),
+            engine: String::new(),
+            rollover_characters,
+        });
+        day.characters_used += usage.characters;
+        day.articles_synthesized += 1;
+        day.audio_seconds += usage.audio_seconds;
+        day.provider = usage.provider;
+        day.engine = usage.engine;
+        day.rollover_characters = day.rollover_characters.or(rollover_characters);
+
+        Ok(())
+    }
+
+    async fn get_rollover_days(
+        &self,
+        user_id: Uuid,
+        today: NaiveDate,
+    ) -> AppResult<Vec<RolloverDay>> {
+        Ok(read(&self.days)
+            .range((user_id, NaiveDate::MIN)..=(user_id, today))
+            .rev()
+            .take(2)
+            .map(|((_, date), day)| RolloverDay {
+                date: *date,
+                characters_used: day.characters_used,
+                rollover_characters: day.rollover_characters,
+            })
+            .collect())
+    }
+
+    async fn get_usage_history(&self, user_id: Uuid, limit: i64) -> AppResult<Vec<UsageRecord>> {
+        Ok(read(&self.days)
+            .range((user_id, NaiveDate::MIN)..=(user_id, NaiveDate::MAX))
+            .rev()
+            .take(limit.max(0) as usize)
+            .map(|((user_id, date), day)| day.record(*user_id, *date))
+            .collect())
+    }
+
+    async fn get_usage_days(
+        &self,
+        user_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<UsageRecord>> {
+        if from > to {
+            return Ok(Vec::new());
+        }
+        Ok(read(&self.days)
+            .range((user_id, from)..=(user_id, to))
+            .map(|((user_id, date), day)| day.record(*user_id, *date))
+            .collect())
+    }
+
+    async fn purge_history(&self, user_id: Uuid, keep_from: NaiveDate) -> AppResult<i32> {
+        let mut days = write(&self.days);
+        let before = days.len();
+        days.retain(|(id, date), _| *id != user_id || *date >= keep_from);
+
+        Ok((before - days.len()) as i32)
+    }
+
+    async fn record_synthesis(
+        &self,
+        date: NaiveDate,
+        provider: &str,
+        characters: i32,
+    ) -> AppResult<()> {
+        let mut synthesis = write(&self.synthesis);
+        let day = synthesis.entry((date, provider.to_string())).or_default();
+        day.requests += 1;
+        day.characters += i64::from(characters);
+
+        Ok(())
+    }
+
+    async fn get_daily_totals(&self, from: NaiveDate) -> AppResult<Vec<DailyUsageTotals>> {
+        let mut totals: BTreeMap<NaiveDate, DailyUsageTotals> = BTreeMap::new();
+        for ((_, date), day) in read(&self.days)
+            .iter()
+            .filter(|((_, date), _)| *date >= from)
+        {
+            let total = totals.entry(*date).or_insert(DailyUsageTotals {
+                date: *date,
+                active_users: 0,
+                characters_used: 0,
+                articles_synthesized: 0,
+                audio_seconds: 0.0,
+            });
+            total.active_users += 1;
+            total.characters_used += i64::from(day.characters_used);
+            total.articles_synthesized += i64::from(day.articles_synthesized);
+            total.audio_seconds += day.audio_seconds;
+        }
+
+        Ok(totals.into_values().rev().collect())
+    }
+
+    async fn count_active_users(&self, from: NaiveDate) -> AppResult<i64> {
+        let users: HashSet<Uuid> = read(&self.days)
+            .keys()
+            .filter(|(_, date)| *date >= from)
+            .map(|(user_id, _)| *user_id)
+            .collect();
+
+        Ok(users.len() as i64)
+    }
+
+    async fn get_provider_totals(
+        &self,
+        from: NaiveDate,
+    ) -> AppResult<Vec<ProviderSynthesisTotals>> {
+        let mut totals: BTreeMap<String, ProviderSynthesisTotals> = BTreeMap::new();
+        for ((date, provider), day) in read(&self.synthesis).iter() {
+            if *date < from {
+                continue;
+            }
+            let total = totals
+                .entry(provider.clone())
+                .or_insert(ProviderSynthesisTotals {
+                    provider: provider.clone(),
+                    requests: 0,
+                    characters: 0,
+                });
+            total.requests += day.requests;
+            total.characters += day.characters;
+        }
+
+        Ok(totals.into_values().collect())
+    }
+
+    async fn delete_before(&self, keep_from: NaiveDate) -> AppResult<u64> {
+        let mut days = write(&self.days);
+        let before = days.len();
+        days.retain(|(_, date), _| *date >= keep_from);
+
+        Ok((before - days.len()) as u64)
+    }
+}
+
+#[derive(Default)]
+pub struct InMemorySubscriptionEventRepository {
+    /// In insertion order, which is also `created_at` order
+    events: RwLock<Vec<SubscriptionEvent>>,
+}
+
+impl InMemorySubscriptionEventRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SubscriptionEventRepositoryApi for InMemorySubscriptionEventRepository {
+    async fn record(
+        &self,
+        user_id: Uuid,
+        event_type: SubscriptionEventType,
+        platform: Option<SubscriptionPlatform>,
+        product_id: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> AppResult<bool> {
+        let mut events = write(&self.events);
+        let repeated = events
+            .iter()
+            .rev()
+            .find(|event| event.user_id == user_id)
+            .is_some_and(|latest| {
+                latest.event_type == event_type
+                    && latest.platform == platform
+                    && latest.product_id.as_deref() == product_id
+                    && latest.expires_at == expires_at
+            });
+        if repeated {
+            return Ok(false);
+        }
+
+        events.push(SubscriptionEvent {
+            id: Uuid::new_v4(),
+            user_id,
+            event_type,
+            platform,
+            product_id: product_id.map(str::to_string),
+            expires_at,
+            created_at: Utc::now(),
+        });
+
+        Ok(true)
+    }
+
+    async fn list_for_user(&self, user_id: Uuid, limit: i64) -> AppResult<Vec<SubscriptionEvent>> {
+        Ok(read(&self.events)
+            .iter()
+            .rev()
+            .filter(|event| event.user_id == user_id)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_since(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<SubscriptionEvent>> {
+        Ok(read(&self.events)
+            .iter()
+            .filter(|event| event.user_id == user_id && event.created_at > since)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+}
+
+struct StoredRefreshToken {
+    id: Uuid,
+    user_id: Uuid,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    revoked: bool,
+    /// Token this one replaced
+    parent: Option<String>,
+    last_used_at: Option<DateTime<Utc>>,
+    user_agent: Option<String>,
+    client_ip: Option<String>,
+}
+
+impl StoredRefreshToken {
+    fn is_valid(&self, now: DateTime<Utc>) -> bool {
+        !self.revoked && self.expires_at > now
+    }
+
+    fn session(&self) -> RefreshSession {
+        RefreshSession {
+            id: self.id,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            revoked: self.revoked,
+            last_used_at: self.last_used_at,
+            user_agent: self.user_agent.clone(),
+            client_ip: self.client_ip.clone(),
+        }
+    }
+}
+
+/// Newest first, as the SQL orders them
+fn newest_first(mut sessions: Vec<RefreshSession>) -> Vec<RefreshSession> {
+    sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    sessions
+}
+
+#[derive(Default)]
+pub struct InMemoryRefreshTokenRepository {
+    tokens: RwLock<HashMap<String, StoredRefreshToken>>,
+}
+
+impl InMemoryRefreshTokenRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepositoryApi for InMemoryRefreshTokenRepository {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        token: &str,
+        expiration_days: i64,
+        client: &SessionClient,
+    ) -> AppResult<()> {
+        write(&self.tokens).insert(
+            token.to_string(),
+            StoredRefreshToken {
+                id: Uuid::new_v4(),
+                user_id,
+                created_at: Utc::now(),
+                expires_at: Utc::now() + Duration::days(expiration_days),
+                revoked: false,
+                parent: None,
+                last_used_at: None,
+                user_agent: client.user_agent.clone(),
+                client_ip: client.ip.map(|ip| ip.to_string()),
+            },
+        );
+
+        Ok(())
+    }
+
+    async fn find_valid(&self, token: &str) -> AppResult<Option<(Uuid, DateTime<Utc>)>> {
+        let now = Utc::now();
+
+        Ok(read(&self.tokens)
+            .get(token)
+            .filter(|stored| stored.is_valid(now))
+            .map(|stored| (stored.user_id, stored.expires_at)))
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<RefreshSession>> {
+        Ok(newest_first(
+            read(&self.tokens)
+                .values()
+                .filter(|stored| stored.user_id == user_id)
+                .map(StoredRefreshToken::session)
+                .collect(),
+        ))
+    }
+
+    async fn find_active_by_user(&self, user_id: Uuid) -> AppResult<Vec<RefreshSession>> {
+        let now = Utc::now();
+
+        Ok(newest_first(
+            read(&self.tokens)
+                .values()
+                .filter(|stored| stored.user_id == user_id && stored.is_valid(now))
+                .map(StoredRefreshToken::session)
+                .collect(),
+        ))
+    }
+
+    async fn check_token_status(&self, token: &str) -> AppResult<Option<(Uuid, bool, bool)>> {
+        let now = Utc::now();
+
+        Ok(read(&self.tokens)
+            .get(token)
+            .map(|stored| (stored.user_id, stored.revoked, stored.expires_at <= now)))
+    }
+
+    async fn rotate(
+        &self,
+        token: &str,
+        new_token: &str,
+        expiration_days: i64,
+        client: &SessionClient,
+    ) -> AppResult<bool> {
+        let now = Utc::now();
+        let mut tokens = write(&self.tokens);
+        let Some(stored) = tokens.get_mut(token).filter(|stored| stored.is_valid(now)) else {
+            return Ok(false);
+        };
+        stored.revoked = true;
+        let replacement = StoredRefreshToken {
+            id: Uuid::new_v4(),
+            user_id: stored.user_id,
+            created_at: stored.created_at,
+            expires_at: now + Duration::days(expiration_days),
+            revoked: false,
+            parent: Some(token.to_string()),
+            last_used_at: Some(now),
+            user_agent: client.user_agent.clone().or(stored.user_agent.clone()),
+            client_ip: client
+                .ip
+                .map(|ip| ip.to_string())
+                .or(stored.client_ip.clone()),
+        };
+
+        tokens.insert(new_token.to_string(), replacement);
+
+        Ok(true)
+    }
+
+    async fn revoke(&self, token: &str) -> AppResult<()> {
+        if let Some(stored) = write(&self.tokens).get_mut(token) {
+            stored.revoked = true;
+        }
+
+        Ok(())
+    }
+
+    async fn revoke_by_id(&self, user_id: Uuid, id: Uuid) -> AppResult<bool> {
+        let now = Utc::now();
+        let mut tokens = write(&self.tokens);
+        let Some(stored) = tokens
+            .values_mut()
+            .find(|stored| stored.id == id && stored.user_id == user_id && stored.is_valid(now))
+        else {
+            return Ok(false);
+        };
+        stored.revoked = true;
+
+        Ok(true)
+    }
+
+    async fn revoke_descendants(&self, token: &str) -> AppResult<u64> {
+        let mut tokens = write(&self.tokens);
+        let mut parents = vec![token.to_string()];
+        let mut revoked = 0;
+        while let Some(parent) = parents.pop() {
+            for (child, stored) in tokens.iter_mut() {
+                if stored.parent.as_ref() == Some(&parent) {
+                    if !stored.revoked {
+                        stored.revoked = true;
+                        revoked += 1;
+                    }
+                    parents.push(child.clone());
+                }
+            }
+        }
+
+        Ok(revoked)
+    }
+
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> AppResult<()> {
+        for stored in write(&self.tokens).values_mut() {
+            if stored.user_id == user_id {
+                stored.revoked = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self) -> AppResult<u64> {
+        let now = Utc::now();
+        let mut tokens = write(&self.tokens);
+        let before = tokens.len();
+        tokens.retain(|_, stored| stored.expires_at >= now);
+
+        Ok((before - tokens.len()) as u64)
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryAuditLogRepository {
+    entries: RwLock<Vec<AuditEntry>>,
+}
+
+impl InMemoryAuditLogRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AuditLogRepositoryApi for InMemoryAuditLogRepository {
+    async fn append(
+        &self,
+        event: &AuditEvent,
+        client_ip: Option<IpAddr>,
+        request_id: Option<&str>,
+    ) -> AppResult<()> {
+        write(&self.entries).push(AuditEntry {
+            id: Uuid::new_v4(),
+            occurred_at: event.occurred_at.unwrap_or_else(Utc::now),
+            actor_type: event.actor_type,
+            actor: event.actor.clone(),
+            action: event.action,
+            user_id: event.user_id,
+            target: event.target.clone(),
+            details: event.details.clone(),
+            client_ip: client_ip.map(|ip| ip.to_string()),
+            request_id: request_id.map(str::to_string),
+        });
+
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        user_id: Option<Uuid>,
+        action: Option<AuditAction>,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<AuditEntry>> {
+        let mut entries: Vec<AuditEntry> = read(&self.entries)
+            .iter()
+            .filter(|entry| user_id.is_none_or(|user_id| entry.user_id == Some(user_id)))
+            .filter(|entry| action.is_none_or(|action| entry.action == action))
+            .filter(|entry| is_after(after, entry.occurred_at, &entry.id))
+            .cloned()
+            .collect();
+        entries.sort_by(|a, b| (b.occurred_at, b.id).cmp(&(a.occurred_at, a.id)));
+        entries.truncate(limit.max(0) as usize);
+
+        Ok(entries)
+    }
+
+    async fn delete_before(&self, cutoff: DateTime<Utc>) -> AppResult<u64> {
+        let mut entries = write(&self.entries);
+        let before = entries.len();
+        entries.retain(|entry| entry.occurred_at >= cutoff);
+
+        Ok((before - entries.len()) as u64)
+    }
+}
+
+struct StoredOutboxEvent {
+    event: OutboxEvent,
+    next_attempt_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+pub struct InMemoryOutboxRepository {
+    events: RwLock<Vec<StoredOutboxEvent>>,
+}
+
+impl InMemoryOutboxRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl OutboxRepositoryApi for InMemoryOutboxRepository {
+    async fn append(&self, event: &DomainEvent) -> AppResult<()> {
+        let now = Utc::now();
+        write(&self.events).push(StoredOutboxEvent {
+            event: OutboxEvent {
+                id: Uuid::new_v4(),
+                payload: Json(OutboxPayload {
+                    event: event.clone(),
+                    origin: EventOrigin::current(),
+                }),
+                attempts: 0,
+                created_at: now,
+            },
+            next_attempt_at: now,
+            completed_at: None,
+        });
+
+        Ok(())
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<OutboxEvent>> {
+        let mut events = write(&self.events);
+        let mut due: Vec<&mut StoredOutboxEvent> = events
+            .iter_mut()
+            .filter(|stored| stored.completed_at.is_none() && stored.next_attempt_at <= now)
+            .collect();
+        due.sort_by_key(|stored| stored.event.created_at);
+
+        Ok(due
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|stored| {
+                stored.next_attempt_at = lease_until;
+                stored.event.clone()
+            })
+            .collect())
+    }
+
+    async fn mark_completed(&self, id: Uuid, completed_at: DateTime<Utc>) -> AppResult<()> {
+        if let Some(stored) = write(&self.events)
+            .iter_mut()
+            .find(|stored| stored.event.id == id)
+        {
+            stored.completed_at = Some(completed_at);
+        }
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: Uuid,
+        attempts: i32,
+        next_attempt_at: DateTime<Utc>,
+        _error: &str,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> AppResult<()> {
+        if let Some(stored) = write(&self.events)
+            .iter_mut()
+            .find(|stored| stored.event.id == id)
+        {
+            stored.event.attempts = attempts;
+            stored.next_attempt_at = next_attempt_at;
+            stored.completed_at = completed_at;
+        }
+
+        Ok(())
+    }
+
+    async fn delete_completed_before(&self, cutoff: DateTime<Utc>) -> AppResult<u64> {
+        let mut events = write(&self.events);
+        let before = events.len();
+        events.retain(|stored| stored.completed_at.is_none_or(|at| at >= cutoff));
+
+        Ok((before - events.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 10, d).unwrap()
+    }
+
+    fn synthesis(characters: i32) -> SynthesisUsage {
+        SynthesisUsage {
+            characters,
+            audio_seconds: f64::from(characters) / 10.0,
+            provider: "polly".to_string(),
+            engine: "neural".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_feed_urls_are_unique_per_user() {
+        let repo = InMemoryFeedRepository::new();
+        let user_id = Uuid::new_v4();
+
+        repo.create(Uuid::new_v4(), user_id, "https://a.example/rss", "A", None)
+            .await
+            .unwrap();
+        let duplicate = repo
+            .create(Uuid::new_v4(), user_id, "https://a.example/rss", "A", None)
+            .await;
+        repo.create(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "https://a.example/rss",
+            "A",
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(duplicate, Err(AppError::Conflict(_))));
+        assert_eq!(repo.count_by_user(user_id).await.unwrap(), 1);
+        assert_eq!(repo.count_feeds().await.unwrap().users_with_feeds, 2);
+    }
+
+    #[tokio::test]
+    async fn test_usage_keeps_first_rollover_and_sums_range() {
+        let repo = InMemoryUsageRepository::new();
+        let user_id = Uuid::new_v4();
+
+        repo.increment_usage(user_id, day(1), synthesis(100), Some(50))
+            .await
+            .unwrap();
+        repo.increment_usage(user_id, day(2), synthesis(200), Some(10))
+            .await
+            .unwrap();
+        repo.increment_usage(user_id, day(2), synthesis(300), Some(99))
+            .await
+            .unwrap();
+        repo.increment_usage(Uuid::new_v4(), day(2), synthesis(1000), None)
+            .await
+            .unwrap();
+
+        let totals = repo
+            .get_usage_between(user_id, day(1), day(3))
+            .await
+            .unwrap();
+        let rollover = repo.get_rollover_days(user_id, day(5)).await.unwrap();
+
+        assert_eq!(totals.characters_used, 600);
+        assert_eq!(totals.articles_synthesized, 3);
+        assert_eq!(totals.audio_seconds, 60.0);
+        assert_eq!(rollover.len(), 2);
+        assert_eq!(rollover[0].date, day(2));
+        assert_eq!(rollover[0].rollover_characters, Some(10));
+    }
+}
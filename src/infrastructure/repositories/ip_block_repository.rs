@@ -0,0 +1,95 @@
+use crate::infrastructure::db::DbPool;
+use crate::{domain::ip_block::IpBlock, error::AppResult};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct IpBlockRepository {
+    pool: Arc<DbPool>,
+}
+
+impl IpBlockRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Blocks that haven't expired by `now`, newest first
+    pub async fn find_active(&self, now: DateTime<Utc>) -> AppResult<Vec<IpBlock>> {
+        let pool = self.pool.as_ref();
+        let blocks = sqlx::query_as::<_, IpBlock>(
+            r#"
+            SELECT id, network, reason, source, created_by, created_at, expires_at
+            FROM ip_blocks
+            WHERE expires_at IS NULL OR expires_at > $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(now)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(blocks)
+    }
+
+    /// Add a block, replacing any existing one for the same network
+    pub async fn upsert(&self, block: &IpBlock) -> AppResult<IpBlock> {
+        let pool = self.pool.as_ref();
+        let block = sqlx::query_as::<_, IpBlock>(
+            r#"
+            INSERT INTO ip_blocks
+                (id, network, reason, source, created_by, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (network) DO UPDATE SET
+                reason = EXCLUDED.reason,
+                source = EXCLUDED.source,
+                created_by = EXCLUDED.created_by,
+                created_at = EXCLUDED.created_at,
+                expires_at = EXCLUDED.expires_at
+            RETURNING id, network, reason, source, created_by, created_at, expires_at
+            "#,
+        )
+        .bind(block.id)
+        .bind(&block.network)
+        .bind(&block.reason)
+        .bind(block.source)
+        .bind(block.created_by.as_deref())
+        .bind(block.created_at)
+        .bind(block.expires_at)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(block)
+    }
+
+    /// Remove a block, returning it if it existed
+    pub async fn delete(&self, id: Uuid) -> AppResult<Option<IpBlock>> {
+        let pool = self.pool.as_ref();
+        let block = sqlx::query_as::<_, IpBlock>(
+            r#"
+            DELETE FROM ip_blocks
+            WHERE id = $1
+            RETURNING id, network, reason, source, created_by, created_at, expires_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(block)
+    }
+
+    pub async fn delete_expired(&self, now: DateTime<Utc>) -> AppResult<u64> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM ip_blocks
+            WHERE expires_at <= $1
+            "#,
+        )
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,11 +1,60 @@
+pub mod analytics_event_repository;
+pub mod api_key_repository;
+pub mod audit_log_repository;
+pub mod device_repository;
+pub mod family_repository;
 pub mod feed_repository;
 pub mod feed_suggestions_repository;
+pub mod idempotency_repository;
+#[cfg(any(test, feature = "test-util"))]
+pub mod in_memory;
+pub mod ip_block_repository;
+pub mod mute_rule_repository;
+pub mod oauth_state_repository;
+pub mod outbox_repository;
+pub mod promo_code_repository;
+pub mod referral_repository;
 pub mod refresh_token_repository;
+pub mod subscription_event_repository;
+pub mod subscription_purchase_repository;
+pub mod suggestion_submission_repository;
 pub mod usage_repository;
 pub mod user_repository;
+pub mod webhook_repository;
+pub mod weekly_summary_repository;
 
-pub use feed_repository::FeedRepository;
+pub use analytics_event_repository::AnalyticsEventRepository;
+pub use api_key_repository::ApiKeyRepository;
+pub use audit_log_repository::{AuditLogRepository, AuditLogRepositoryApi};
+pub use device_repository::DeviceRepository;
+pub use family_repository::FamilyRepository;
+pub use feed_repository::{FeedCounts, FeedRepository, FeedRepositoryApi};
 pub use feed_suggestions_repository::HardcodedFeedSuggestionsRepository;
-pub use refresh_token_repository::RefreshTokenRepository;
-pub use usage_repository::{UsageRecord, UsageRepository};
-pub use user_repository::UserRepository;
+pub use idempotency_repository::{IdempotencyRecord, IdempotencyRepository};
+#[cfg(any(test, feature = "test-util"))]
+pub use in_memory::{
+    InMemoryAuditLogRepository, InMemoryFeedRepository, InMemoryOutboxRepository,
+    InMemoryRefreshTokenRepository, InMemorySubscriptionEventRepository, InMemoryUsageRepository,
+    InMemoryUserRepository,
+};
+pub use ip_block_repository::IpBlockRepository;
+pub use mute_rule_repository::MuteRuleRepository;
+pub use oauth_state_repository::OAuthStateRepository;
+pub use outbox_repository::{OutboxRepository, OutboxRepositoryApi};
+pub use promo_code_repository::PromoCodeRepository;
+pub use referral_repository::ReferralRepository;
+pub use refresh_token_repository::{
+    RefreshSession, RefreshTokenRepository, RefreshTokenRepositoryApi,
+};
+pub use subscription_event_repository::{
+    SubscriptionEventRepository, SubscriptionEventRepositoryApi,
+};
+pub use subscription_purchase_repository::SubscriptionPurchaseRepository;
+pub use suggestion_submission_repository::SuggestionSubmissionRepository;
+pub use usage_repository::{
+    DailyUsageTotals, ProviderSynthesisTotals, SynthesisUsage, UsageRecord, UsageRepository,
+    UsageRepositoryApi, UsageTotals,
+};
+pub use user_repository::{UserCounts, UserRepository, UserRepositoryApi};
+pub use webhook_repository::WebhookRepository;
+pub use weekly_summary_repository::WeeklySummaryRepository;
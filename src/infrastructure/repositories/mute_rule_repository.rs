@@ -0,0 +1,100 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::mute::{MuteRule, MuteRuleKind},
+    error::AppResult,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct MuteRuleRepository {
+    pool: Arc<DbPool>,
+}
+
+impl MuteRuleRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Get all mute rules for a user
+    pub async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<MuteRule>> {
+        let pool = self.pool.as_ref();
+        let rules = sqlx::query_as::<_, MuteRule>(
+            r#"
+            SELECT id, user_id, feed_id, pattern, kind, created_at
+            FROM mute_rules
+            WHERE user_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    /// Count mute rules for a user
+    pub async fn count_by_user(&self, user_id: Uuid) -> AppResult<i64> {
+        let pool = self.pool.as_ref();
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM mute_rules
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Create a new mute rule
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        feed_id: Option<Uuid>,
+        pattern: &str,
+        kind: MuteRuleKind,
+    ) -> AppResult<MuteRule> {
+        let pool = self.pool.as_ref();
+        let id = Uuid::new_v4();
+        let now = chrono::Utc::now();
+
+        let rule = sqlx::query_as::<_, MuteRule>(
+            r#"
+            INSERT INTO mute_rules (id, user_id, feed_id, pattern, kind, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, user_id, feed_id, pattern, kind, created_at
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(feed_id)
+        .bind(pattern)
+        .bind(kind)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(rule)
+    }
+
+    /// Delete a mute rule owned by a user
+    pub async fn delete_for_user(&self, rule_id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM mute_rules
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(rule_id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::error::AppResult;
+use crate::infrastructure::db::DbPool;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+pub struct OAuthStateRepository {
+    pool: Arc<DbPool>,
+}
+
+impl OAuthStateRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Remember a state handed to the provider, good until `expires_at`
+    pub async fn create(&self, state: &str, expires_at: DateTime<Utc>) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+        sqlx::query(
+            r#"
+            INSERT INTO oauth_states (state, expires_at)
+            VALUES ($1, $2)
+            "#,
+        )
+        .bind(state)
+        .bind(expires_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Use up a state. False when it was never issued, has expired or was already used.
+    pub async fn consume(&self, state: &str, now: DateTime<Utc>) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM oauth_states
+            WHERE state = $1 AND expires_at > $2
+            "#,
+        )
+        .bind(state)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Drop states of sign-ins that were never finished
+    pub async fn delete_expired(&self, now: DateTime<Utc>) -> AppResult<u64> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM oauth_states
+            WHERE expires_at <= $1
+            "#,
+        )
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
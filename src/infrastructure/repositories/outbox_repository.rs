@@ -0,0 +1,181 @@
+use crate::infrastructure::db::{DbConnection, DbPool};
+use crate::{
+    domain::{
+        events::{DomainEvent, EventOrigin},
+        outbox::{OutboxEvent, OutboxPayload},
+    },
+    error::AppResult,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct OutboxRepository {
+    pool: Arc<DbPool>,
+}
+
+impl OutboxRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+pub trait OutboxRepositoryApi: Send + Sync {
+    /// Record an event that doesn't go with any other write
+    async fn append(&self, event: &DomainEvent) -> AppResult<()>;
+
+    /// Take up to `limit` due events and push them to `lease_until`, so an overlapping
+    /// run doesn't hand them out again while they are being dispatched
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<OutboxEvent>>;
+
+    async fn mark_completed(&self, id: Uuid, completed_at: DateTime<Utc>) -> AppResult<()>;
+
+    /// Store a failed dispatch. `completed_at` is set when the event is given up on.
+    async fn record_failure(
+        &self,
+        id: Uuid,
+        attempts: i32,
+        next_attempt_at: DateTime<Utc>,
+        error: &str,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> AppResult<()>;
+
+    /// Delete events dispatched (or given up on) before `cutoff`; returns how many went
+    async fn delete_completed_before(&self, cutoff: DateTime<Utc>) -> AppResult<u64>;
+}
+
+#[async_trait]
+impl OutboxRepositoryApi for OutboxRepository {
+    async fn append(&self, event: &DomainEvent) -> AppResult<()> {
+        let mut conn = self.pool.acquire().await?;
+        append_event(&mut conn, event).await
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<OutboxEvent>> {
+        let pool = self.pool.as_ref();
+        let events = sqlx::query_as::<_, OutboxEvent>(
+            r#"
+            UPDATE outbox_events
+            SET next_attempt_at = $1
+            WHERE id IN (
+                SELECT id FROM outbox_events
+                WHERE completed_at IS NULL AND next_attempt_at <= $2
+                ORDER BY created_at
+                LIMIT $3
+            )
+              AND completed_at IS NULL AND next_attempt_at <= $2
+            RETURNING id, payload, attempts, created_at
+            "#,
+        )
+        .bind(lease_until)
+        .bind(now)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    async fn mark_completed(&self, id: Uuid, completed_at: DateTime<Utc>) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+
+        sqlx::query(
+            r#"
+            UPDATE outbox_events
+            SET completed_at = $2, last_error = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(completed_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: Uuid,
+        attempts: i32,
+        next_attempt_at: DateTime<Utc>,
+        error: &str,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+
+        sqlx::query(
+            r#"
+            UPDATE outbox_events
+            SET attempts = $2,
+                next_attempt_at = $3,
+                last_error = $4,
+                completed_at = $5
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(attempts)
+        .bind(next_attempt_at)
+        .bind(error)
+        .bind(completed_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_completed_before(&self, cutoff: DateTime<Utc>) -> AppResult<u64> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM outbox_events
+            WHERE completed_at IS NOT NULL AND completed_at < $1
+            "#,
+        )
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Add `event` to the outbox on `conn`, with the request it came from. Called from inside
+/// the transaction that makes the change, so the event is stored exactly when the change is.
+pub async fn append_event(conn: &mut DbConnection, event: &DomainEvent) -> AppResult<()> {
+    let now = Utc::now();
+    let payload = OutboxPayload {
+        event: event.clone(),
+        origin: EventOrigin::current(),
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO outbox_events (id, event_type, user_id, payload, next_attempt_at, created_at)
+        VALUES ($1, $2, $3, $4, $5, $5)
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(event.event_type())
+    .bind(event.user_id())
+    .bind(Json(payload))
+    .bind(now)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
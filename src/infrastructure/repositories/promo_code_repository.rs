@@ -0,0 +1,241 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::{shared::Cursor, subscription::PromoCode, user::User},
+    error::{AppError, AppResult},
+};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct PromoCodeRepository {
+    pool: Arc<DbPool>,
+}
+
+impl PromoCodeRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find a promo code by its (normalized) code
+    pub async fn find_by_code(&self, code: &str) -> AppResult<Option<PromoCode>> {
+        let pool = self.pool.as_ref();
+        let promo_code =
+            sqlx::query_as::<_, PromoCode>("SELECT * FROM promo_codes WHERE code = $1")
+                .bind(code)
+                .fetch_optional(pool)
+                .await?;
+
+        Ok(promo_code)
+    }
+
+    /// List promo codes, newest first
+    pub async fn list(
+        &self,
+        after: Option<&Cursor<String>>,
+        limit: i64,
+    ) -> AppResult<Vec<PromoCode>> {
+        let pool = self.pool.as_ref();
+        let promo_codes = sqlx::query_as::<_, PromoCode>(
+            r#"
+            SELECT * FROM promo_codes
+            WHERE $1 IS NULL OR (created_at, code) < ($1, $2)
+            ORDER BY created_at DESC, code DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(after.map(|cursor| cursor.at))
+        .bind(after.map(|cursor| cursor.key.as_str()))
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(promo_codes)
+    }
+
+    /// Create a promo code
+    pub async fn create(
+        &self,
+        code: &str,
+        duration_days: i32,
+        max_redemptions: Option<i32>,
+        expires_at: Option<DateTime<Utc>>,
+        created_by: &str,
+    ) -> AppResult<PromoCode> {
+        let pool = self.pool.as_ref();
+        let now = Utc::now();
+
+        let promo_code = sqlx::query_as::<_, PromoCode>(
+            r#"
+            INSERT INTO promo_codes
+                (code, duration_days, max_redemptions, redemption_count, expires_at, created_by, created_at)
+            VALUES ($1, $2, $3, 0, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(code)
+        .bind(duration_days)
+        .bind(max_redemptions)
+        .bind(expires_at)
+        .bind(created_by)
+        .bind(now)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(ref db_err) = e {
+                if db_err.is_unique_violation() {
+                    return AppError::Conflict("Promo code already exists".to_string());
+                }
+            }
+            AppError::Database(e)
+        })?;
+
+        Ok(promo_code)
+    }
+
+    /// Whether the user has already redeemed the code
+    pub async fn has_redeemed(&self, code: &str, user_id: Uuid) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let redeemed = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM promo_code_redemptions WHERE code = $1 AND user_id = $2
+            )
+            "#,
+        )
+        .bind(code)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(redeemed)
+    }
+
+    /// Claim one redemption of the code and upgrade the user to Pro until `pro_from` plus the
+    /// code's duration, in one statement. Returns None, changing nothing, if the code is
+    /// expired, used up or was already redeemed by this user.
+    #[cfg(not(feature = "sqlite"))]
+    pub async fn redeem(
+        &self,
+        code: &str,
+        user_id: Uuid,
+        pro_from: DateTime<Utc>,
+    ) -> AppResult<Option<User>> {
+        let pool = self.pool.as_ref();
+        let now = Utc::now();
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            WITH claimed AS (
+                UPDATE promo_codes
+                SET redemption_count = redemption_count + 1
+                WHERE code = $1
+                  AND (expires_at IS NULL OR expires_at > $3)
+                  AND (max_redemptions IS NULL OR redemption_count < max_redemptions)
+                  AND NOT EXISTS (
+                      SELECT 1 FROM promo_code_redemptions WHERE code = $1 AND user_id = $2
+                  )
+                RETURNING code, duration_days
+            ),
+            redemption AS (
+                INSERT INTO promo_code_redemptions (code, user_id, redeemed_at)
+                SELECT code, $2, $3 FROM claimed
+            )
+            UPDATE users
+            SET subscription_tier = 'pro',
+                subscription_status = 'active',
+                subscription_expires_at = $4 + make_interval(days => claimed.duration_days),
+                updated_at = $3
+            FROM claimed
+            WHERE users.id = $2
+            RETURNING users.*
+            "#,
+        )
+        .bind(code)
+        .bind(user_id)
+        .bind(now)
+        .bind(pro_from)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(ref db_err) = e {
+                // A concurrent redemption by the same user won the race
+                if db_err.is_unique_violation() {
+                    return AppError::Conflict("Promo code already redeemed".to_string());
+                }
+            }
+            AppError::Database(e)
+        })?;
+
+        Ok(user)
+    }
+
+    /// SQLite variant of `redeem`, as a transaction. SQLite runs one writer at a time, so
+    /// the redemption can't race another one between the check and the insert.
+    #[cfg(feature = "sqlite")]
+    pub async fn redeem(
+        &self,
+        code: &str,
+        user_id: Uuid,
+        pro_from: DateTime<Utc>,
+    ) -> AppResult<Option<User>> {
+        let mut tx = self.pool.begin().await?;
+        let now = Utc::now();
+
+        let duration_days = sqlx::query_scalar::<_, i32>(
+            r#"
+            UPDATE promo_codes
+            SET redemption_count = redemption_count + 1
+            WHERE code = $1
+              AND (expires_at IS NULL OR expires_at > $3)
+              AND (max_redemptions IS NULL OR redemption_count < max_redemptions)
+              AND NOT EXISTS (
+                  SELECT 1 FROM promo_code_redemptions WHERE code = $1 AND user_id = $2
+              )
+            RETURNING duration_days
+            "#,
+        )
+        .bind(code)
+        .bind(user_id)
+        .bind(now)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(duration_days) = duration_days else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO promo_code_redemptions (code, user_id, redeemed_at)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(code)
+        .bind(user_id)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET subscription_tier = 'pro',
+                subscription_status = 'active',
+                subscription_expires_at = strftime(
+                    '%Y-%m-%dT%H:%M:%f+00:00', $1, '+' || $2 || ' days'
+                ),
+                updated_at = $3
+            WHERE id = $4
+            RETURNING *
+            "#,
+        )
+        .bind(pro_from)
+        .bind(duration_days)
+        .bind(now)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(user)
+    }
+}
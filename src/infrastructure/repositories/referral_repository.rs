@@ -0,0 +1,154 @@
+use crate::infrastructure::db::DbPool;
+use crate::{domain::referral::Referral, error::AppResult};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct ReferralRepository {
+    pool: Arc<DbPool>,
+}
+
+impl ReferralRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// The referral through which a user was referred, if any
+    pub async fn find_by_referee(&self, referee_id: Uuid) -> AppResult<Option<Referral>> {
+        let pool = self.pool.as_ref();
+        let referral = sqlx::query_as::<_, Referral>(
+            r#"
+            SELECT id, referrer_id, referee_id, created_at
+            FROM referrals
+            WHERE referee_id = $1
+            "#,
+        )
+        .bind(referee_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(referral)
+    }
+
+    /// Count referrals made by a user, optionally only those since the given moment
+    pub async fn count_by_referrer(
+        &self,
+        referrer_id: Uuid,
+        since: Option<DateTime<Utc>>,
+    ) -> AppResult<i64> {
+        let pool = self.pool.as_ref();
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM referrals
+            WHERE referrer_id = $1 AND ($2 IS NULL OR created_at >= $2)
+            "#,
+        )
+        .bind(referrer_id)
+        .bind(since)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Record the referral and extend both users' bonus by `bonus_days`, in one statement.
+    /// A bonus that is still running is extended from its current end. Returns the referee's
+    /// new bonus end, or None if the referee had already been referred.
+    #[cfg(not(feature = "sqlite"))]
+    pub async fn redeem(
+        &self,
+        referrer_id: Uuid,
+        referee_id: Uuid,
+        bonus_days: i32,
+    ) -> AppResult<Option<DateTime<Utc>>> {
+        let pool = self.pool.as_ref();
+        let now = Utc::now();
+
+        let rows = sqlx::query_as::<_, (Uuid, DateTime<Utc>)>(
+            r#"
+            WITH referral AS (
+                INSERT INTO referrals (id, referrer_id, referee_id, created_at)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (referee_id) DO NOTHING
+                RETURNING referrer_id, referee_id
+            )
+            UPDATE users
+            SET referral_bonus_until = GREATEST(COALESCE(users.referral_bonus_until, $4), $4)
+                    + make_interval(days => $5),
+                updated_at = $4
+            FROM referral
+            WHERE users.id IN (referral.referrer_id, referral.referee_id)
+            RETURNING users.id, users.referral_bonus_until
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(referrer_id)
+        .bind(referee_id)
+        .bind(now)
+        .bind(bonus_days)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .find(|(id, _)| *id == referee_id)
+            .map(|(_, bonus_until)| bonus_until))
+    }
+
+    /// SQLite variant of `redeem`: without data-modifying CTEs the same steps run in a
+    /// transaction.
+    #[cfg(feature = "sqlite")]
+    pub async fn redeem(
+        &self,
+        referrer_id: Uuid,
+        referee_id: Uuid,
+        bonus_days: i32,
+    ) -> AppResult<Option<DateTime<Utc>>> {
+        let mut tx = self.pool.begin().await?;
+        let now = Utc::now();
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO referrals (id, referrer_id, referee_id, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (referee_id) DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(referrer_id)
+        .bind(referee_id)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        if inserted.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let rows = sqlx::query_as::<_, (Uuid, DateTime<Utc>)>(
+            r#"
+            UPDATE users
+            SET referral_bonus_until = strftime(
+                    '%Y-%m-%dT%H:%M:%f+00:00',
+                    MAX(COALESCE(referral_bonus_until, $1), $1),
+                    '+' || $2 || ' days'
+                ),
+                updated_at = $1
+            WHERE id IN ($3, $4)
+            RETURNING id, referral_bonus_until
+            "#,
+        )
+        .bind(now)
+        .bind(bonus_days)
+        .bind(referrer_id)
+        .bind(referee_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(rows
+            .into_iter()
+            .find(|(id, _)| *id == referee_id)
+            .map(|(_, bonus_until)| bonus_until))
+    }
+}
@@ -1,9 +1,36 @@
+use crate::domain::auth::SessionClient;
 use crate::error::AppResult;
 use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// A refresh token without its secret, as listed back to its user
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct RefreshSession {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked: bool,
+    /// When the session was last refreshed
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub user_agent: Option<String>,
+    pub client_ip: Option<String>,
+}
+
+/// The session a refresh token was replaced in, carried on to its replacement
+#[derive(FromRow)]
+struct RotatedToken {
+    id: Uuid,
+    user_id: Uuid,
+    created_at: DateTime<Utc>,
+    user_agent: Option<String>,
+    client_ip: Option<String>,
+}
+
 pub struct RefreshTokenRepository {
     pool: Arc<DbPool>,
 }
@@ -12,9 +39,74 @@ impl RefreshTokenRepository {
     pub fn new(pool: Arc<DbPool>) -> Self {
         Self { pool }
     }
+}
+
+#[async_trait]
+pub trait RefreshTokenRepositoryApi: Send + Sync {
+    /// Create a new refresh token, starting a session for the client that signed in
+    async fn create(
+        &self,
+        user_id: Uuid,
+        token: &str,
+        expiration_days: i64,
+        client: &SessionClient,
+    ) -> AppResult<()>;
+
+    /// Find a valid (non-revoked, non-expired) refresh token
+    async fn find_valid(&self, token: &str) -> AppResult<Option<(Uuid, DateTime<Utc>)>>;
+
+    /// The user's refresh tokens still stored, newest first
+    async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<RefreshSession>>;
+
+    /// The user's valid refresh tokens, one per signed-in session, newest first
+    async fn find_active_by_user(&self, user_id: Uuid) -> AppResult<Vec<RefreshSession>>;
+
+    /// Look up a refresh token whatever its status: its user, whether it was revoked and
+    /// whether it has expired
+    async fn check_token_status(&self, token: &str) -> AppResult<Option<(Uuid, bool, bool)>>;
+
+    /// Revoke a valid refresh token and store `new_token` as its replacement. False when
+    /// the token was no longer valid, so two refreshes with the same token can't both
+    /// succeed.
+    ///
+    /// The replacement carries the session on: it keeps the sign-in time, is marked used
+    /// now and records the refreshing client, keeping what was known where it's silent.
+    async fn rotate(
+        &self,
+        token: &str,
+        new_token: &str,
+        expiration_days: i64,
+        client: &SessionClient,
+    ) -> AppResult<bool>;
 
-    /// Create a new refresh token
-    pub async fn create(&self, user_id: Uuid, token: &str, expiration_days: i64) -> AppResult<()> {
+    /// Revoke a refresh token
+    async fn revoke(&self, token: &str) -> AppResult<()>;
+
+    /// Revoke one of the user's refresh tokens by id. False when the user has no such
+    /// valid token.
+    async fn revoke_by_id(&self, user_id: Uuid, id: Uuid) -> AppResult<bool>;
+
+    /// Revoke every token that replaced this one, directly or through later refreshes.
+    /// Returns how many were still unrevoked.
+    async fn revoke_descendants(&self, token: &str) -> AppResult<u64>;
+
+    /// Revoke all refresh tokens for a user
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> AppResult<()>;
+
+    /// Delete expired refresh tokens (cleanup). Revoked ones are kept until they expire,
+    /// so one presented again is still recognised.
+    async fn delete_expired(&self) -> AppResult<u64>;
+}
+
+#[async_trait]
+impl RefreshTokenRepositoryApi for RefreshTokenRepository {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        token: &str,
+        expiration_days: i64,
+        client: &SessionClient,
+    ) -> AppResult<()> {
         let pool = self.pool.as_ref();
         let id = Uuid::new_v4();
         let now = Utc::now();
@@ -22,8 +114,9 @@ impl RefreshTokenRepository {
 
         sqlx::query(
             r#"
-            INSERT INTO refresh_tokens (id, user_id, token, expires_at, created_at, revoked)
-            VALUES ($1, $2, $3, $4, $5, false)
+            INSERT INTO refresh_tokens
+                (id, user_id, token, expires_at, created_at, revoked, user_agent, client_ip)
+            VALUES ($1, $2, $3, $4, $5, false, $6, $7)
             "#,
         )
         .bind(id)
@@ -31,14 +124,15 @@ impl RefreshTokenRepository {
         .bind(token)
         .bind(expires_at)
         .bind(now)
+        .bind(client.user_agent.as_deref())
+        .bind(client.ip.map(|ip| ip.to_string()))
         .execute(pool)
         .await?;
 
         Ok(())
     }
 
-    /// Find a valid (non-revoked, non-expired) refresh token
-    pub async fn find_valid(&self, token: &str) -> AppResult<Option<(Uuid, DateTime<Utc>)>> {
+    async fn find_valid(&self, token: &str) -> AppResult<Option<(Uuid, DateTime<Utc>)>> {
         let pool = self.pool.as_ref();
         let result = sqlx::query_as::<_, (Uuid, DateTime<Utc>)>(
             r#"
@@ -46,22 +140,59 @@ impl RefreshTokenRepository {
             FROM refresh_tokens
             WHERE token = $1
               AND NOT revoked
-              AND expires_at > NOW()
+              AND expires_at > $2
             "#,
         )
         .bind(token)
+        .bind(Utc::now())
         .fetch_optional(pool)
         .await?;
 
         Ok(result)
     }
 
-    /// Check if a refresh token exists and get its status
-    pub async fn check_token_status(&self, token: &str) -> AppResult<Option<(bool, bool)>> {
+    async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<RefreshSession>> {
+        let pool = self.pool.as_ref();
+        let sessions = sqlx::query_as::<_, RefreshSession>(
+            r#"
+            SELECT id, created_at, expires_at, revoked, last_used_at, user_agent, client_ip
+            FROM refresh_tokens
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
+    async fn find_active_by_user(&self, user_id: Uuid) -> AppResult<Vec<RefreshSession>> {
         let pool = self.pool.as_ref();
-        let result = sqlx::query_as::<_, (bool, DateTime<Utc>)>(
+        let sessions = sqlx::query_as::<_, RefreshSession>(
             r#"
-            SELECT revoked, expires_at
+            SELECT id, created_at, expires_at, revoked, last_used_at, user_agent, client_ip
+            FROM refresh_tokens
+            WHERE user_id = $1
+              AND NOT revoked
+              AND expires_at > $2
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(Utc::now())
+        .fetch_all(pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
+    async fn check_token_status(&self, token: &str) -> AppResult<Option<(Uuid, bool, bool)>> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query_as::<_, (Uuid, bool, DateTime<Utc>)>(
+            r#"
+            SELECT user_id, revoked, expires_at
             FROM refresh_tokens
             WHERE token = $1
             "#,
@@ -70,16 +201,65 @@ impl RefreshTokenRepository {
         .fetch_optional(pool)
         .await?;
 
-        if let Some((revoked, expires_at)) = result {
+        if let Some((user_id, revoked, expires_at)) = result {
             let is_expired = expires_at <= Utc::now();
-            Ok(Some((revoked, is_expired)))
+            Ok(Some((user_id, revoked, is_expired)))
         } else {
             Ok(None)
         }
     }
 
-    /// Revoke a refresh token
-    pub async fn revoke(&self, token: &str) -> AppResult<()> {
+    async fn rotate(
+        &self,
+        token: &str,
+        new_token: &str,
+        expiration_days: i64,
+        client: &SessionClient,
+    ) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+        let now = Utc::now();
+
+        let parent = sqlx::query_as::<_, RotatedToken>(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked = TRUE
+            WHERE token = $1 AND NOT revoked AND expires_at > $2
+            RETURNING id, user_id, created_at, user_agent, client_ip
+            "#,
+        )
+        .bind(token)
+        .bind(now)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(parent) = parent else {
+            return Ok(false);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO refresh_tokens
+                (id, user_id, token, expires_at, created_at, revoked, parent_id,
+                 last_used_at, user_agent, client_ip)
+            VALUES ($1, $2, $3, $4, $5, false, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(parent.user_id)
+        .bind(new_token)
+        .bind(now + Duration::days(expiration_days))
+        .bind(parent.created_at)
+        .bind(parent.id)
+        .bind(now)
+        .bind(client.user_agent.clone().or(parent.user_agent))
+        .bind(client.ip.map(|ip| ip.to_string()).or(parent.client_ip))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn revoke(&self, token: &str) -> AppResult<()> {
         let pool = self.pool.as_ref();
         sqlx::query(
             r#"
@@ -95,8 +275,51 @@ impl RefreshTokenRepository {
         Ok(())
     }
 
-    /// Revoke all refresh tokens for a user
-    pub async fn revoke_all_for_user(&self, user_id: Uuid) -> AppResult<()> {
+    async fn revoke_by_id(&self, user_id: Uuid, id: Uuid) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked = TRUE
+            WHERE id = $1 AND user_id = $2 AND NOT revoked AND expires_at > $3
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_descendants(&self, token: &str) -> AppResult<u64> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            WITH RECURSIVE descendants AS (
+                SELECT child.id
+                FROM refresh_tokens parent
+                JOIN refresh_tokens child ON child.parent_id = parent.id
+                WHERE parent.token = $1
+                UNION ALL
+                SELECT child.id
+                FROM descendants
+                JOIN refresh_tokens child ON child.parent_id = descendants.id
+            )
+            UPDATE refresh_tokens
+            SET revoked = TRUE
+            WHERE id IN (SELECT id FROM descendants) AND NOT revoked
+            "#,
+        )
+        .bind(token)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn revoke_all_for_user(&self, user_id: Uuid) -> AppResult<()> {
         let pool = self.pool.as_ref();
         sqlx::query(
             r#"
@@ -112,15 +335,15 @@ impl RefreshTokenRepository {
         Ok(())
     }
 
-    /// Delete expired refresh tokens (cleanup)
-    pub async fn delete_expired(&self) -> AppResult<u64> {
+    async fn delete_expired(&self) -> AppResult<u64> {
         let pool = self.pool.as_ref();
         let result = sqlx::query(
             r#"
             DELETE FROM refresh_tokens
-            WHERE expires_at < NOW()
+            WHERE expires_at < $1
             "#,
         )
+        .bind(Utc::now())
         .execute(pool)
         .await?;
 
@@ -0,0 +1,136 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::subscription::{SubscriptionEvent, SubscriptionEventType, SubscriptionPlatform},
+    error::AppResult,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct SubscriptionEventRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SubscriptionEventRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+pub trait SubscriptionEventRepositoryApi: Send + Sync {
+    /// Append an event to the user's billing history. Stores redeliver notifications and
+    /// clients revalidate purchases, so an event identical to the user's latest one is
+    /// skipped. Returns whether the event was recorded.
+    async fn record(
+        &self,
+        user_id: Uuid,
+        event_type: SubscriptionEventType,
+        platform: Option<SubscriptionPlatform>,
+        product_id: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> AppResult<bool>;
+
+    /// The user's most recent events, newest first
+    async fn list_for_user(&self, user_id: Uuid, limit: i64) -> AppResult<Vec<SubscriptionEvent>>;
+
+    /// The user's events recorded after `since`, oldest first
+    async fn list_since(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<SubscriptionEvent>>;
+}
+
+#[async_trait]
+impl SubscriptionEventRepositoryApi for SubscriptionEventRepository {
+    async fn record(
+        &self,
+        user_id: Uuid,
+        event_type: SubscriptionEventType,
+        platform: Option<SubscriptionPlatform>,
+        product_id: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO subscription_events
+                (id, user_id, event_type, platform, product_id, expires_at, created_at)
+            SELECT $1, $2, $3, $4, $5, $6, $7
+            WHERE NOT EXISTS (
+                SELECT 1
+                FROM (
+                    SELECT event_type, platform, product_id, expires_at
+                    FROM subscription_events
+                    WHERE user_id = $2
+                    ORDER BY created_at DESC
+                    LIMIT 1
+                ) latest
+                WHERE latest.event_type = $3
+                  AND latest.platform IS NOT DISTINCT FROM $4
+                  AND latest.product_id IS NOT DISTINCT FROM $5
+                  AND latest.expires_at IS NOT DISTINCT FROM $6
+            )
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(event_type)
+        .bind(platform)
+        .bind(product_id)
+        .bind(expires_at)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_for_user(&self, user_id: Uuid, limit: i64) -> AppResult<Vec<SubscriptionEvent>> {
+        let pool = self.pool.as_ref();
+        let events = sqlx::query_as::<_, SubscriptionEvent>(
+            r#"
+            SELECT id, user_id, event_type, platform, product_id, expires_at, created_at
+            FROM subscription_events
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    async fn list_since(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<SubscriptionEvent>> {
+        let pool = self.pool.as_ref();
+        let events = sqlx::query_as::<_, SubscriptionEvent>(
+            r#"
+            SELECT id, user_id, event_type, platform, product_id, expires_at, created_at
+            FROM subscription_events
+            WHERE user_id = $1 AND created_at > $2
+            ORDER BY created_at ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(since)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::subscription::{SubscriptionPlatform, SubscriptionPurchase},
+    error::AppResult,
+};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct SubscriptionPurchaseRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SubscriptionPurchaseRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Find a purchase by its store token
+    pub async fn find_by_token(
+        &self,
+        purchase_token: &str,
+    ) -> AppResult<Option<SubscriptionPurchase>> {
+        let pool = self.pool.as_ref();
+        let purchase = sqlx::query_as::<_, SubscriptionPurchase>(
+            r#"
+            SELECT purchase_token, user_id, platform, product_id, expires_at, created_at, updated_at
+            FROM subscription_purchases
+            WHERE purchase_token = $1
+            "#,
+        )
+        .bind(purchase_token)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(purchase)
+    }
+
+    /// Insert a purchase or refresh it if it already belongs to this user.
+    /// Returns false when the token is linked to a different user.
+    pub async fn upsert_for_user(
+        &self,
+        purchase_token: &str,
+        user_id: Uuid,
+        platform: SubscriptionPlatform,
+        product_id: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO subscription_purchases
+                (purchase_token, user_id, platform, product_id, expires_at, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $6)
+            ON CONFLICT (purchase_token) DO UPDATE
+            SET product_id = EXCLUDED.product_id,
+                expires_at = EXCLUDED.expires_at,
+                updated_at = EXCLUDED.updated_at
+            WHERE subscription_purchases.user_id = EXCLUDED.user_id
+            "#,
+        )
+        .bind(purchase_token)
+        .bind(user_id)
+        .bind(platform)
+        .bind(product_id)
+        .bind(expires_at)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
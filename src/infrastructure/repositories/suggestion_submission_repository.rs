@@ -0,0 +1,149 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::feed_suggestions::{SubmissionStatus, SuggestionSubmission},
+    error::AppResult,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct SuggestionSubmissionRepository {
+    pool: Arc<DbPool>,
+}
+
+impl SuggestionSubmissionRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, submission: &SuggestionSubmission) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+        sqlx::query(
+            r#"
+            INSERT INTO suggestion_submissions
+                (id, user_id, url, title, description, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(submission.id)
+        .bind(submission.user_id)
+        .bind(&submission.url)
+        .bind(&submission.title)
+        .bind(&submission.description)
+        .bind(submission.status)
+        .bind(submission.created_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> AppResult<Option<SuggestionSubmission>> {
+        let pool = self.pool.as_ref();
+        let submission = sqlx::query_as::<_, SuggestionSubmission>(
+            r#"
+            SELECT id, user_id, url, title, description, status, category_id,
+                   rejection_reason, reviewed_by, reviewed_at, created_at
+            FROM suggestion_submissions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(submission)
+    }
+
+    /// Submissions in a status, oldest first so the queue is worked in order
+    pub async fn find_by_status(
+        &self,
+        status: SubmissionStatus,
+    ) -> AppResult<Vec<SuggestionSubmission>> {
+        let pool = self.pool.as_ref();
+        let submissions = sqlx::query_as::<_, SuggestionSubmission>(
+            r#"
+            SELECT id, user_id, url, title, description, status, category_id,
+                   rejection_reason, reviewed_by, reviewed_at, created_at
+            FROM suggestion_submissions
+            WHERE status = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(status)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(submissions)
+    }
+
+    /// A pending or approved submission of this URL, if there is one
+    pub async fn find_open_by_url(&self, url: &str) -> AppResult<Option<SuggestionSubmission>> {
+        let pool = self.pool.as_ref();
+        let submission = sqlx::query_as::<_, SuggestionSubmission>(
+            r#"
+            SELECT id, user_id, url, title, description, status, category_id,
+                   rejection_reason, reviewed_by, reviewed_at, created_at
+            FROM suggestion_submissions
+            WHERE url = $1 AND status <> 'rejected'
+            LIMIT 1
+            "#,
+        )
+        .bind(url)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(submission)
+    }
+
+    pub async fn count_pending_by_user(&self, user_id: Uuid) -> AppResult<i64> {
+        let pool = self.pool.as_ref();
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM suggestion_submissions
+            WHERE user_id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Record the review of a pending submission. Returns None when it was already
+    /// reviewed, so two admins can't both decide on it.
+    pub async fn save_review(
+        &self,
+        submission: &SuggestionSubmission,
+    ) -> AppResult<Option<SuggestionSubmission>> {
+        let pool = self.pool.as_ref();
+        let submission = sqlx::query_as::<_, SuggestionSubmission>(
+            r#"
+            UPDATE suggestion_submissions
+            SET title = $2,
+                description = $3,
+                status = $4,
+                category_id = $5,
+                rejection_reason = $6,
+                reviewed_by = $7,
+                reviewed_at = $8
+            WHERE id = $1 AND status = 'pending'
+            RETURNING id, user_id, url, title, description, status, category_id,
+                      rejection_reason, reviewed_by, reviewed_at, created_at
+            "#,
+        )
+        .bind(submission.id)
+        .bind(&submission.title)
+        .bind(&submission.description)
+        .bind(submission.status)
+        .bind(submission.category_id.as_deref())
+        .bind(submission.rejection_reason.as_deref())
+        .bind(submission.reviewed_by.as_deref())
+        .bind(submission.reviewed_at)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(submission)
+    }
+}
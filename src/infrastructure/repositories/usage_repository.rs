@@ -1,86 +1,426 @@
+use crate::domain::plan::RolloverDay;
 use crate::error::AppResult;
 use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
 use chrono::{NaiveDate, Utc};
 use sqlx::FromRow;
-use std::sync::Arc;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use uuid::Uuid;
 
+/// Buffered users and days past which an increment flushes right away
+const MAX_PENDING_USAGE_ENTRIES: usize = 1000;
+
+/// One synthesis to count in a user's day
+#[derive(Debug, Clone)]
+pub struct SynthesisUsage {
+    pub characters: i32,
+    /// Length of the audio produced
+    pub audio_seconds: f64,
+    pub provider: String,
+    pub engine: String,
+}
+
 #[derive(Debug, FromRow)]
 pub struct UsageRecord {
     pub user_id: Uuid,
     pub date: NaiveDate,
     pub characters_used: i32,
     pub articles_synthesized: i32,
+    pub audio_seconds: f64,
+    /// Provider and engine of the day's latest synthesis; absent on days recorded
+    /// before they were tracked
+    pub provider: Option<String>,
+    pub engine: Option<String>,
+}
+
+/// Usage aggregated over a quota period
+#[derive(Debug, Default, FromRow)]
+pub struct UsageTotals {
+    pub characters_used: i32,
+    pub articles_synthesized: i32,
+    pub audio_seconds: f64,
+}
+
+/// Usage of all users on one day
+#[derive(Debug, FromRow)]
+pub struct DailyUsageTotals {
+    pub date: NaiveDate,
+    pub active_users: i64,
+    pub characters_used: i64,
+    pub articles_synthesized: i64,
+    pub audio_seconds: f64,
+}
+
+/// Syntheses served by one provider over a range of days
+#[derive(Debug, FromRow)]
+pub struct ProviderSynthesisTotals {
+    pub provider: String,
+    pub requests: i64,
+    pub characters: i64,
+}
+
+/// Increments not yet written for one user and day, summed
+#[derive(Debug, Clone)]
+struct PendingUsage {
+    characters: i32,
+    articles: i32,
+    audio_seconds: f64,
+    provider: String,
+    engine: String,
+    rollover_characters: Option<i32>,
 }
 
+impl PendingUsage {
+    fn merge(&mut self, other: PendingUsage) {
+        self.characters += other.characters;
+        self.articles += other.articles;
+        self.audio_seconds += other.audio_seconds;
+        self.provider = other.provider;
+        self.engine = other.engine;
+        self.rollover_characters = self.rollover_characters.or(other.rollover_characters);
+    }
+
+    /// Take out what a flush wrote; anything merged in meanwhile stays. The rollover
+    /// figure is only ever set once per day, so it goes with the first write.
+    fn subtract(&mut self, written: &PendingUsage) {
+        self.characters -= written.characters;
+        self.articles -= written.articles;
+        self.audio_seconds -= written.audio_seconds;
+        self.rollover_characters = None;
+    }
+
+    fn is_empty(&self) -> bool {
+        self.characters == 0 && self.articles == 0
+    }
+
+    fn record(self, user_id: Uuid, date: NaiveDate) -> UsageRecord {
+        UsageRecord {
+            user_id,
+            date,
+            characters_used: self.characters,
+            articles_synthesized: self.articles,
+            audio_seconds: self.audio_seconds,
+            provider: Some(self.provider),
+            engine: Some(self.engine),
+        }
+    }
+}
+
+impl UsageRecord {
+    fn add_pending(&mut self, usage: PendingUsage) {
+        self.characters_used += usage.characters;
+        self.articles_synthesized += usage.articles;
+        self.audio_seconds += usage.audio_seconds;
+        self.provider = Some(usage.provider);
+        self.engine = Some(usage.engine);
+    }
+}
+
+type PendingUsageMap = HashMap<(Uuid, NaiveDate), PendingUsage>;
+
 pub struct UsageRepository {
     pool: Arc<DbPool>,
+    /// Replica for listings; lookups that gate a write stay on the primary
+    read_pool: Arc<DbPool>,
+    /// Set when increments are written in batches by `flush_pending`
+    pending: Option<Mutex<PendingUsageMap>>,
+    /// Held for a whole flush so the periodic and the size-triggered ones don't both
+    /// write the same entries
+    flushing: tokio::sync::Mutex<()>,
 }
 
 impl UsageRepository {
     pub fn new(pool: Arc<DbPool>) -> Self {
-        Self { pool }
+        Self {
+            read_pool: pool.clone(),
+            pool,
+            pending: None,
+            flushing: tokio::sync::Mutex::new(()),
+        }
     }
 
-    /// Get today's usage for a user
-    pub async fn get_today_usage(&self, user_id: Uuid) -> AppResult<Option<UsageRecord>> {
-        let pool = self.pool.as_ref();
-        let today = Utc::now().date_naive();
+    pub fn with_read_pool(mut self, read_pool: Arc<DbPool>) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
 
-        let usage = sqlx::query_as::<_, UsageRecord>(
-            r#"
-            SELECT user_id, date, characters_used, articles_synthesized
-            FROM usage_tracking
-            WHERE user_id = $1 AND date = $2
-            "#,
-        )
-        .bind(user_id)
-        .bind(today)
-        .fetch_optional(pool)
-        .await?;
+    /// Keep increments in memory, summed per user and day, until `flush_pending` writes
+    /// them, instead of one upsert per synthesis. Per-user reads through this repository
+    /// include what is pending; other instances and the service-wide totals see it after
+    /// the next flush, and a crash loses at most what was pending.
+    pub fn with_write_buffer(mut self) -> Self {
+        self.pending = Some(Mutex::new(HashMap::new()));
+        self
+    }
 
-        Ok(usage)
+    /// Write the buffered increments. Each entry stays buffered, and counted by per-user
+    /// reads, until its write commits; entries that fail stay for the next flush.
+    /// Returns how many users and days were written.
+    pub async fn flush_pending(&self) -> AppResult<usize> {
+        let Some(pending) = &self.pending else {
+            return Ok(0);
+        };
+        let _flushing = self.flushing.lock().await;
+        let entries: Vec<_> = lock(pending)
+            .iter()
+            .map(|(key, usage)| (*key, usage.clone()))
+            .collect();
+
+        for ((user_id, date), usage) in &entries {
+            self.write_usage(*user_id, *date, usage).await?;
+
+            let mut pending = lock(pending);
+            if let Entry::Occupied(mut entry) = pending.entry((*user_id, *date)) {
+                entry.get_mut().subtract(usage);
+                if entry.get().is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+
+        Ok(entries.len())
     }
 
-    /// Increment usage for today
-    pub async fn increment_usage(&self, user_id: Uuid, characters: i32) -> AppResult<()> {
+    /// Buffered increments for one user, by day
+    fn pending_for(&self, user_id: Uuid) -> Vec<(NaiveDate, PendingUsage)> {
+        let Some(pending) = &self.pending else {
+            return Vec::new();
+        };
+        lock(pending)
+            .iter()
+            .filter(|((pending_user_id, _), _)| *pending_user_id == user_id)
+            .map(|(&(_, date), usage)| (date, usage.clone()))
+            .collect()
+    }
+
+    async fn write_usage(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+        usage: &PendingUsage,
+    ) -> AppResult<()> {
         let pool = self.pool.as_ref();
         let now = Utc::now();
-        let today = now.date_naive();
         let id = Uuid::new_v4();
 
         sqlx::query(
             r#"
-            INSERT INTO usage_tracking (id, user_id, date, characters_used, articles_synthesized, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, 1, $5, $5)
+            INSERT INTO usage_tracking (id, user_id, date, characters_used, articles_synthesized, rollover_characters, audio_seconds, provider, engine, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $7, $6, $8, $9, $10, $5, $5)
             ON CONFLICT (user_id, date)
             DO UPDATE SET
                 characters_used = usage_tracking.characters_used + $4,
-                articles_synthesized = usage_tracking.articles_synthesized + 1,
+                articles_synthesized = usage_tracking.articles_synthesized + $7,
+                rollover_characters = COALESCE(usage_tracking.rollover_characters, $6),
+                audio_seconds = usage_tracking.audio_seconds + $8,
+                provider = $9,
+                engine = $10,
                 updated_at = $5
             "#,
         )
         .bind(id)
         .bind(user_id)
-        .bind(today)
-        .bind(characters)
+        .bind(date)
+        .bind(usage.characters)
         .bind(now)
+        .bind(usage.rollover_characters)
+        .bind(usage.articles)
+        .bind(usage.audio_seconds)
+        .bind(&usage.provider)
+        .bind(&usage.engine)
         .execute(pool)
         .await?;
 
         Ok(())
     }
+}
+
+#[async_trait]
+pub trait UsageRepositoryApi: Send + Sync {
+    /// Sum a user's usage over the days in `[start, end)`. Callers pass days in the user's
+    /// timezone.
+    async fn get_usage_between(
+        &self,
+        user_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> AppResult<UsageTotals>;
+
+    /// Count a synthesis in the given day. `rollover_characters` is the balance carried
+    /// into the day; once set for a day it is kept.
+    async fn increment_usage(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+        usage: SynthesisUsage,
+        rollover_characters: Option<i32>,
+    ) -> AppResult<()>;
+
+    /// The user's two most recent usage days up to and including `today`, newest first;
+    /// enough to work out today's rollover balance
+    async fn get_rollover_days(
+        &self,
+        user_id: Uuid,
+        today: NaiveDate,
+    ) -> AppResult<Vec<RolloverDay>>;
 
     /// Get usage history for a user
-    pub async fn get_usage_history(
+    async fn get_usage_history(&self, user_id: Uuid, limit: i64) -> AppResult<Vec<UsageRecord>>;
+
+    /// A user's recorded days in `[from, to]`, oldest first
+    async fn get_usage_days(
         &self,
         user_id: Uuid,
-        limit: i64,
-    ) -> AppResult<Vec<UsageRecord>> {
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<UsageRecord>>;
+
+    /// Delete a user's usage from days before `keep_from` and record that the purge
+    /// happened. Returns how many days were deleted.
+    async fn purge_history(&self, user_id: Uuid, keep_from: NaiveDate) -> AppResult<i32>;
+
+    /// Count a synthesis in the day's statistics for the provider that served it
+    async fn record_synthesis(
+        &self,
+        date: NaiveDate,
+        provider: &str,
+        characters: i32,
+    ) -> AppResult<()>;
+
+    /// Usage of all users per day from `from` on, newest first
+    async fn get_daily_totals(&self, from: NaiveDate) -> AppResult<Vec<DailyUsageTotals>>;
+
+    /// Users with any usage from `from` on
+    async fn count_active_users(&self, from: NaiveDate) -> AppResult<i64>;
+
+    /// Synthesis statistics per provider from `from` on
+    async fn get_provider_totals(&self, from: NaiveDate)
+        -> AppResult<Vec<ProviderSynthesisTotals>>;
+
+    /// Delete every user's usage from days before `keep_from` (retention cleanup)
+    async fn delete_before(&self, keep_from: NaiveDate) -> AppResult<u64>;
+}
+
+#[async_trait]
+impl UsageRepositoryApi for UsageRepository {
+    async fn get_usage_between(
+        &self,
+        user_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> AppResult<UsageTotals> {
+        let pool = self.pool.as_ref();
+
+        let mut totals = sqlx::query_as::<_, UsageTotals>(
+            r#"
+            SELECT CAST(COALESCE(SUM(characters_used), 0) AS INTEGER) AS characters_used,
+                   CAST(COALESCE(SUM(articles_synthesized), 0) AS INTEGER) AS articles_synthesized,
+                   CAST(COALESCE(SUM(audio_seconds), 0) AS DOUBLE PRECISION) AS audio_seconds
+            FROM usage_tracking
+            WHERE user_id = $1 AND date >= $2 AND date < $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(start)
+        .bind(end)
+        .fetch_one(pool)
+        .await?;
+
+        for (date, usage) in self.pending_for(user_id) {
+            if start <= date && date < end {
+                totals.characters_used += usage.characters;
+                totals.articles_synthesized += usage.articles;
+                totals.audio_seconds += usage.audio_seconds;
+            }
+        }
+
+        Ok(totals)
+    }
+
+    async fn increment_usage(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+        usage: SynthesisUsage,
+        rollover_characters: Option<i32>,
+    ) -> AppResult<()> {
+        let usage = PendingUsage {
+            characters: usage.characters,
+            articles: 1,
+            audio_seconds: usage.audio_seconds,
+            provider: usage.provider,
+            engine: usage.engine,
+            rollover_characters,
+        };
+        let Some(pending) = &self.pending else {
+            return self.write_usage(user_id, date, &usage).await;
+        };
+
+        let full = {
+            let mut pending = lock(pending);
+            merge_pending(&mut pending, (user_id, date), usage);
+            pending.len() >= MAX_PENDING_USAGE_ENTRIES
+        };
+        // The increment is already counted, so a failed flush only delays the write
+        if full {
+            if let Err(e) = self.flush_pending().await {
+                tracing::warn!(error = %e, "Failed to flush buffered usage");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_rollover_days(
+        &self,
+        user_id: Uuid,
+        today: NaiveDate,
+    ) -> AppResult<Vec<RolloverDay>> {
         let pool = self.pool.as_ref();
-        let records = sqlx::query_as::<_, UsageRecord>(
+        let mut days = sqlx::query_as::<_, RolloverDay>(
+            r#"
+            SELECT date, characters_used, rollover_characters
+            FROM usage_tracking
+            WHERE user_id = $1 AND date <= $2
+            ORDER BY date DESC
+            LIMIT 2
+            "#,
+        )
+        .bind(user_id)
+        .bind(today)
+        .fetch_all(pool)
+        .await?;
+
+        let pending = self.pending_for(user_id);
+        if pending.is_empty() {
+            return Ok(days);
+        }
+        for (date, usage) in pending.into_iter().filter(|(date, _)| *date <= today) {
+            match days.iter_mut().find(|day| day.date == date) {
+                Some(day) => {
+                    day.characters_used += usage.characters;
+                    day.rollover_characters = day.rollover_characters.or(usage.rollover_characters);
+                }
+                None => days.push(RolloverDay {
+                    date,
+                    characters_used: usage.characters,
+                    rollover_characters: usage.rollover_characters,
+                }),
+            }
+        }
+        days.sort_by(|a, b| b.date.cmp(&a.date));
+        days.truncate(2);
+
+        Ok(days)
+    }
+
+    async fn get_usage_history(&self, user_id: Uuid, limit: i64) -> AppResult<Vec<UsageRecord>> {
+        let pool = self.read_pool.as_ref();
+        let mut records = sqlx::query_as::<_, UsageRecord>(
             r#"
-            SELECT user_id, date, characters_used, articles_synthesized
+            SELECT user_id, date, characters_used, articles_synthesized, audio_seconds, provider, engine
             FROM usage_tracking
             WHERE user_id = $1
             ORDER BY date DESC
@@ -92,6 +432,240 @@ impl UsageRepository {
         .fetch_all(pool)
         .await?;
 
+        let pending = self.pending_for(user_id);
+        if pending.is_empty() {
+            return Ok(records);
+        }
+        for (date, usage) in pending {
+            match records.iter_mut().find(|record| record.date == date) {
+                Some(record) => record.add_pending(usage),
+                None => records.push(usage.record(user_id, date)),
+            }
+        }
+        records.sort_by(|a, b| b.date.cmp(&a.date));
+        records.truncate(limit.max(0) as usize);
+
+        Ok(records)
+    }
+
+    async fn get_usage_days(
+        &self,
+        user_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<UsageRecord>> {
+        let pool = self.read_pool.as_ref();
+        let mut records = sqlx::query_as::<_, UsageRecord>(
+            r#"
+            SELECT user_id, date, characters_used, articles_synthesized, audio_seconds, provider, engine
+            FROM usage_tracking
+            WHERE user_id = $1 AND date >= $2 AND date <= $3
+            ORDER BY date
+            "#,
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(pool)
+        .await?;
+
+        let pending = self.pending_for(user_id);
+        if pending.is_empty() {
+            return Ok(records);
+        }
+        for (date, usage) in pending
+            .into_iter()
+            .filter(|(date, _)| from <= *date && *date <= to)
+        {
+            match records.iter_mut().find(|record| record.date == date) {
+                Some(record) => record.add_pending(usage),
+                None => records.push(usage.record(user_id, date)),
+            }
+        }
+        records.sort_by_key(|record| record.date);
+
         Ok(records)
     }
+
+    #[cfg(not(feature = "sqlite"))]
+    async fn purge_history(&self, user_id: Uuid, keep_from: NaiveDate) -> AppResult<i32> {
+        // Buffered days must be written first, or they would come back after the purge
+        self.flush_pending().await?;
+
+        let pool = self.pool.as_ref();
+        let deleted_days = sqlx::query_scalar::<_, i32>(
+            r#"
+            WITH deleted AS (
+                DELETE FROM usage_tracking
+                WHERE user_id = $1 AND date < $2
+                RETURNING 1
+            )
+            INSERT INTO usage_history_purges (id, user_id, deleted_days, purged_at)
+            SELECT $3, $1, COUNT(*)::INT, $4
+            FROM deleted
+            RETURNING deleted_days
+            "#,
+        )
+        .bind(user_id)
+        .bind(keep_from)
+        .bind(Uuid::new_v4())
+        .bind(Utc::now())
+        .fetch_one(pool)
+        .await?;
+
+        Ok(deleted_days)
+    }
+
+    // SQLite has no data-modifying CTEs, so the same thing runs as a transaction
+    #[cfg(feature = "sqlite")]
+    async fn purge_history(&self, user_id: Uuid, keep_from: NaiveDate) -> AppResult<i32> {
+        self.flush_pending().await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let deleted = sqlx::query(
+            r#"
+            DELETE FROM usage_tracking
+            WHERE user_id = $1 AND date < $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(keep_from)
+        .execute(&mut *tx)
+        .await?;
+        let deleted_days = deleted.rows_affected() as i32;
+
+        sqlx::query(
+            r#"
+            INSERT INTO usage_history_purges (id, user_id, deleted_days, purged_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(deleted_days)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(deleted_days)
+    }
+
+    async fn record_synthesis(
+        &self,
+        date: NaiveDate,
+        provider: &str,
+        characters: i32,
+    ) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+
+        sqlx::query(
+            r#"
+            INSERT INTO synthesis_stats (date, provider, requests, characters)
+            VALUES ($1, $2, 1, $3)
+            ON CONFLICT (date, provider)
+            DO UPDATE SET
+                requests = synthesis_stats.requests + 1,
+                characters = synthesis_stats.characters + EXCLUDED.characters
+            "#,
+        )
+        .bind(date)
+        .bind(provider)
+        .bind(i64::from(characters))
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_daily_totals(&self, from: NaiveDate) -> AppResult<Vec<DailyUsageTotals>> {
+        let pool = self.read_pool.as_ref();
+        let totals = sqlx::query_as::<_, DailyUsageTotals>(
+            r#"
+            SELECT date,
+                   COUNT(*) AS active_users,
+                   CAST(SUM(characters_used) AS BIGINT) AS characters_used,
+                   CAST(SUM(articles_synthesized) AS BIGINT) AS articles_synthesized,
+                   CAST(SUM(audio_seconds) AS DOUBLE PRECISION) AS audio_seconds
+            FROM usage_tracking
+            WHERE date >= $1
+            GROUP BY date
+            ORDER BY date DESC
+            "#,
+        )
+        .bind(from)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(totals)
+    }
+
+    async fn count_active_users(&self, from: NaiveDate) -> AppResult<i64> {
+        let pool = self.read_pool.as_ref();
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(DISTINCT user_id)
+            FROM usage_tracking
+            WHERE date >= $1
+            "#,
+        )
+        .bind(from)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn get_provider_totals(
+        &self,
+        from: NaiveDate,
+    ) -> AppResult<Vec<ProviderSynthesisTotals>> {
+        let pool = self.read_pool.as_ref();
+        let totals = sqlx::query_as::<_, ProviderSynthesisTotals>(
+            r#"
+            SELECT provider,
+                   CAST(SUM(requests) AS BIGINT) AS requests,
+                   CAST(SUM(characters) AS BIGINT) AS characters
+            FROM synthesis_stats
+            WHERE date >= $1
+            GROUP BY provider
+            ORDER BY provider
+            "#,
+        )
+        .bind(from)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(totals)
+    }
+
+    async fn delete_before(&self, keep_from: NaiveDate) -> AppResult<u64> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM usage_tracking
+            WHERE date < $1
+            "#,
+        )
+        .bind(keep_from)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+fn merge_pending(pending: &mut PendingUsageMap, key: (Uuid, NaiveDate), usage: PendingUsage) {
+    match pending.entry(key) {
+        Entry::Occupied(mut entry) => entry.get_mut().merge(usage),
+        Entry::Vacant(entry) => {
+            entry.insert(usage);
+        }
+    }
+}
+
+/// Every update leaves the map consistent, so a panic elsewhere doesn't poison it
+fn lock(pending: &Mutex<PendingUsageMap>) -> MutexGuard<'_, PendingUsageMap> {
+    pending.lock().unwrap_or_else(PoisonError::into_inner)
 }
@@ -1,9 +1,27 @@
 use crate::infrastructure::db::DbPool;
-use crate::{domain::user::User, error::AppResult};
+use crate::infrastructure::repositories::outbox_repository::append_event;
+use crate::{
+    domain::events::DomainEvent,
+    domain::shared::Cursor,
+    domain::user::{SubscriptionStatus, SubscriptionTier, User, UserRole, TRIAL_DURATION_DAYS},
+    error::{AppError, AppResult},
+};
+use async_trait::async_trait;
 use serde_json::json;
+use sqlx::FromRow;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Account totals for admin statistics
+#[derive(Debug, FromRow)]
+pub struct UserCounts {
+    pub total: i64,
+    pub pro: i64,
+    /// Accounts waiting for the purge job
+    pub pending_deletion: i64,
+}
+
+/// Every query here skips soft-deleted accounts; only the hard purge touches them
 pub struct UserRepository {
     pool: Arc<DbPool>,
 }
@@ -12,38 +30,159 @@ impl UserRepository {
     pub fn new(pool: Arc<DbPool>) -> Self {
         Self { pool }
     }
+}
 
+#[async_trait]
+pub trait UserRepositoryApi: Send + Sync {
     /// Find user by ID
-    pub async fn find_by_id(&self, user_id: Uuid) -> AppResult<Option<User>> {
+    async fn find_by_id(&self, user_id: Uuid) -> AppResult<Option<User>>;
+
+    /// Find user by email
+    async fn find_by_email(&self, email: &str) -> AppResult<Option<User>>;
+
+    /// Find user by OAuth provider and provider ID
+    async fn find_by_oauth(&self, provider: &str, provider_id: &str) -> AppResult<Option<User>>;
+
+    /// Find the user a referral code belongs to
+    async fn find_by_referral_code(&self, code: &str) -> AppResult<Option<User>>;
+
+    /// List users, newest first, optionally filtered by a case-insensitive match on email
+    /// or display name
+    async fn search(
+        &self,
+        query: Option<&str>,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<User>>;
+
+    async fn count_users(&self) -> AppResult<UserCounts>;
+
+    /// Create a new user. The free trial starts at signup. Fails with Conflict if the email
+    /// or provider identity is taken.
+    async fn create(
+        &self,
+        email: &str,
+        provider: &str,
+        provider_id: &str,
+        display_name: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> AppResult<User>;
+
+    /// Update display name and/or settings in one write, leaving omitted ones untouched.
+    /// With `expected_updated_at` the row is only written if it still has that version;
+    /// returns None when nothing was written.
+    async fn update_profile(
+        &self,
+        user_id: Uuid,
+        display_name: Option<&str>,
+        settings: Option<serde_json::Value>,
+        expected_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> AppResult<Option<User>>;
+
+    /// Update a user's subscription tier, status and expiry
+    async fn update_subscription(
+        &self,
+        user_id: Uuid,
+        tier: SubscriptionTier,
+        status: SubscriptionStatus,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> AppResult<User>;
+
+    /// Set or clear the per-user limit overrides
+    async fn set_limit_overrides(
+        &self,
+        user_id: Uuid,
+        overrides: Option<serde_json::Value>,
+    ) -> AppResult<User>;
+
+    /// Make the user an admin or a regular user again
+    async fn set_role(&self, user_id: Uuid, role: UserRole) -> AppResult<User>;
+
+    /// Give the user a referral code unless they already have one; returns the stored code.
+    /// Fails with Conflict if another user already holds `code`.
+    async fn set_referral_code(&self, user_id: Uuid, code: &str) -> AppResult<String>;
+
+    /// Refresh profile details from the OAuth provider on sign-in. The avatar always follows
+    /// the provider; the name is only filled in if the user has none.
+    async fn refresh_oauth_profile(
+        &self,
+        user_id: Uuid,
+        display_name: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> AppResult<User>;
+
+    /// Set or clear the moment the account is purged
+    async fn set_deletion_schedule(
+        &self,
+        user_id: Uuid,
+        scheduled_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> AppResult<User>;
+
+    /// Soft-delete accounts whose deletion date is at or before `now`. Returns the ids of
+    /// the accounts deleted.
+    async fn soft_delete_scheduled(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> AppResult<Vec<Uuid>>;
+
+    /// Permanently remove accounts soft-deleted at or before `before`. Related rows go
+    /// with them via ON DELETE CASCADE. Returns the ids of the purged accounts.
+    async fn purge_deleted(&self, before: chrono::DateTime<chrono::Utc>) -> AppResult<Vec<Uuid>>;
+
+    /// Start the free trial. Returns None when the user already had one.
+    async fn start_trial(
+        &self,
+        user_id: Uuid,
+        started_at: chrono::DateTime<chrono::Utc>,
+        ends_at: chrono::DateTime<chrono::Utc>,
+    ) -> AppResult<Option<User>>;
+}
+
+#[async_trait]
+impl UserRepositoryApi for UserRepository {
+    async fn find_by_id(&self, user_id: Uuid) -> AppResult<Option<User>> {
         let pool = self.pool.as_ref();
-        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
-            .bind(user_id)
-            .fetch_optional(pool)
-            .await?;
+        let mut user =
+            sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1 AND deleted_at IS NULL")
+                .bind(user_id)
+                .fetch_optional(pool)
+                .await?;
+
+        // Family members inherit the owner's tier, so limits resolve from the loaded user
+        if let Some(user) = user.as_mut() {
+            if let Some(owner_id) = user.family_owner_id {
+                let owner = sqlx::query_as::<_, User>(
+                    "SELECT * FROM users WHERE id = $1 AND deleted_at IS NULL",
+                )
+                .bind(owner_id)
+                .fetch_optional(pool)
+                .await?;
+                user.family_tier = owner.map(|owner| owner.effective_tier());
+            }
+        }
 
         Ok(user)
     }
 
-    /// Find user by email
-    pub async fn find_by_email(&self, email: &str) -> AppResult<Option<User>> {
+    async fn find_by_email(&self, email: &str) -> AppResult<Option<User>> {
         let pool = self.pool.as_ref();
-        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE email = $1")
-            .bind(email)
-            .fetch_optional(pool)
-            .await?;
+        let user = sqlx::query_as::<_, User>(
+            "SELECT * FROM users WHERE email = $1 AND deleted_at IS NULL",
+        )
+        .bind(email)
+        .fetch_optional(pool)
+        .await?;
 
         Ok(user)
     }
 
-    /// Find user by OAuth provider and provider ID
-    pub async fn find_by_oauth(
-        &self,
-        provider: &str,
-        provider_id: &str,
-    ) -> AppResult<Option<User>> {
+    async fn find_by_oauth(&self, provider: &str, provider_id: &str) -> AppResult<Option<User>> {
         let pool = self.pool.as_ref();
         let user = sqlx::query_as::<_, User>(
-            "SELECT * FROM users WHERE oauth_provider = $1 AND oauth_provider_id = $2",
+            r#"
+            SELECT * FROM users
+            WHERE oauth_provider = $1 AND oauth_provider_id = $2 AND deleted_at IS NULL
+            "#,
         )
         .bind(provider)
         .bind(provider_id)
@@ -53,11 +192,76 @@ impl UserRepository {
         Ok(user)
     }
 
-    /// Create a new user
-    pub async fn create(&self, email: &str, provider: &str, provider_id: &str) -> AppResult<User> {
+    async fn find_by_referral_code(&self, code: &str) -> AppResult<Option<User>> {
         let pool = self.pool.as_ref();
+        let user = sqlx::query_as::<_, User>(
+            "SELECT * FROM users WHERE referral_code = $1 AND deleted_at IS NULL",
+        )
+        .bind(code)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn search(
+        &self,
+        query: Option<&str>,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<User>> {
+        let pool = self.pool.as_ref();
+        let pattern = query.map(|q| format!("%{}%", q.to_lowercase()));
+
+        let users = sqlx::query_as::<_, User>(
+            r#"
+            SELECT * FROM users
+            WHERE deleted_at IS NULL
+              AND ($1 IS NULL OR LOWER(email) LIKE $1 OR LOWER(display_name) LIKE $1)
+              AND ($2 IS NULL OR (created_at, id) < ($2, $3))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(pattern)
+        .bind(after.map(|cursor| cursor.at))
+        .bind(after.map(|cursor| cursor.key))
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    async fn count_users(&self) -> AppResult<UserCounts> {
+        let pool = self.pool.as_ref();
+        let counts = sqlx::query_as::<_, UserCounts>(
+            r#"
+            SELECT COUNT(*) AS total,
+                   COUNT(CASE WHEN subscription_tier = $1 THEN 1 END) AS pro,
+                   COUNT(deletion_scheduled_at) AS pending_deletion
+            FROM users
+            WHERE deleted_at IS NULL
+            "#,
+        )
+        .bind(SubscriptionTier::Pro)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    async fn create(
+        &self,
+        email: &str,
+        provider: &str,
+        provider_id: &str,
+        display_name: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> AppResult<User> {
         let id = Uuid::new_v4();
         let now = chrono::Utc::now();
+        let trial_ends_at = now + chrono::Duration::days(TRIAL_DURATION_DAYS);
         let default_settings = json!({
             "voice": "Lucia",
             "speed": 1.0,
@@ -65,10 +269,11 @@ impl UserRepository {
             "quality": "standard"
         });
 
+        let mut tx = self.pool.begin().await?;
         let user = sqlx::query_as::<_, User>(
             r#"
-            INSERT INTO users (id, email, oauth_provider, oauth_provider_id, settings, subscription_tier, subscription_status, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, 'free', 'active', $6, $6)
+            INSERT INTO users (id, email, oauth_provider, oauth_provider_id, display_name, avatar_url, settings, subscription_tier, subscription_status, trial_started_at, trial_ends_at, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 'free', 'active', $8, $9, $8, $8)
             RETURNING *
             "#,
         )
@@ -76,19 +281,101 @@ impl UserRepository {
         .bind(email)
         .bind(provider)
         .bind(provider_id)
+        .bind(display_name)
+        .bind(avatar_url)
         .bind(default_settings)
         .bind(now)
+        .bind(trial_ends_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            // A soft-deleted account holds on to its email and provider ID until it's purged
+            if let sqlx::Error::Database(ref db_err) = e {
+                if db_err.is_unique_violation() {
+                    return AppError::Conflict("Account already exists".to_string());
+                }
+            }
+            AppError::Database(e)
+        })?;
+
+        append_event(
+            &mut tx,
+            &DomainEvent::UserCreated {
+                user_id: user.id,
+                oauth_provider: provider.to_string(),
+            },
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(user)
+    }
+
+    async fn update_profile(
+        &self,
+        user_id: Uuid,
+        display_name: Option<&str>,
+        settings: Option<serde_json::Value>,
+        expected_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> AppResult<Option<User>> {
+        let pool = self.pool.as_ref();
+        let now = chrono::Utc::now();
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET display_name = COALESCE($1, display_name),
+                settings = COALESCE($2, settings),
+                updated_at = $3
+            WHERE id = $4 AND deleted_at IS NULL AND ($5 IS NULL OR updated_at = $5)
+            RETURNING *
+            "#,
+        )
+        .bind(display_name)
+        .bind(settings)
+        .bind(now)
+        .bind(user_id)
+        .bind(expected_updated_at)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn update_subscription(
+        &self,
+        user_id: Uuid,
+        tier: SubscriptionTier,
+        status: SubscriptionStatus,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> AppResult<User> {
+        let pool = self.pool.as_ref();
+        let now = chrono::Utc::now();
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET subscription_tier = $1, subscription_status = $2, subscription_expires_at = $3,
+                updated_at = $4
+            WHERE id = $5 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(tier)
+        .bind(status)
+        .bind(expires_at)
+        .bind(now)
+        .bind(user_id)
         .fetch_one(pool)
         .await?;
 
         Ok(user)
     }
 
-    /// Update user settings
-    pub async fn update_settings(
+    async fn set_limit_overrides(
         &self,
         user_id: Uuid,
-        settings: serde_json::Value,
+        overrides: Option<serde_json::Value>,
     ) -> AppResult<User> {
         let pool = self.pool.as_ref();
         let now = chrono::Utc::now();
@@ -96,12 +383,89 @@ impl UserRepository {
         let user = sqlx::query_as::<_, User>(
             r#"
             UPDATE users
-            SET settings = $1, updated_at = $2
-            WHERE id = $3
+            SET limit_overrides = $1, updated_at = $2
+            WHERE id = $3 AND deleted_at IS NULL
             RETURNING *
             "#,
         )
-        .bind(settings)
+        .bind(overrides)
+        .bind(now)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn set_role(&self, user_id: Uuid, role: UserRole) -> AppResult<User> {
+        let pool = self.pool.as_ref();
+        let now = chrono::Utc::now();
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET role = $1, updated_at = $2
+            WHERE id = $3 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(role)
+        .bind(now)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn set_referral_code(&self, user_id: Uuid, code: &str) -> AppResult<String> {
+        let pool = self.pool.as_ref();
+        let now = chrono::Utc::now();
+
+        let stored = sqlx::query_scalar::<_, String>(
+            r#"
+            UPDATE users
+            SET referral_code = COALESCE(referral_code, $1), updated_at = $2
+            WHERE id = $3 AND deleted_at IS NULL
+            RETURNING referral_code
+            "#,
+        )
+        .bind(code)
+        .bind(now)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| {
+            if let sqlx::Error::Database(ref db_err) = e {
+                if db_err.is_unique_violation() {
+                    return AppError::Conflict("Referral code already taken".to_string());
+                }
+            }
+            AppError::Database(e)
+        })?;
+
+        Ok(stored)
+    }
+
+    async fn refresh_oauth_profile(
+        &self,
+        user_id: Uuid,
+        display_name: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> AppResult<User> {
+        let pool = self.pool.as_ref();
+        let now = chrono::Utc::now();
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET display_name = COALESCE(display_name, $1), avatar_url = $2, updated_at = $3
+            WHERE id = $4 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(display_name)
+        .bind(avatar_url)
         .bind(now)
         .bind(user_id)
         .fetch_one(pool)
@@ -109,4 +473,92 @@ impl UserRepository {
 
         Ok(user)
     }
+
+    async fn set_deletion_schedule(
+        &self,
+        user_id: Uuid,
+        scheduled_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> AppResult<User> {
+        let pool = self.pool.as_ref();
+        let now = chrono::Utc::now();
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET deletion_scheduled_at = $1, updated_at = $2
+            WHERE id = $3 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(scheduled_at)
+        .bind(now)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn soft_delete_scheduled(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> AppResult<Vec<Uuid>> {
+        let pool = self.pool.as_ref();
+
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE users
+            SET deleted_at = $1, updated_at = $1
+            WHERE deletion_scheduled_at <= $1 AND deleted_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(now)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    async fn purge_deleted(&self, before: chrono::DateTime<chrono::Utc>) -> AppResult<Vec<Uuid>> {
+        let pool = self.pool.as_ref();
+
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            DELETE FROM users
+            WHERE deleted_at <= $1
+            RETURNING id
+            "#,
+        )
+        .bind(before)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    async fn start_trial(
+        &self,
+        user_id: Uuid,
+        started_at: chrono::DateTime<chrono::Utc>,
+        ends_at: chrono::DateTime<chrono::Utc>,
+    ) -> AppResult<Option<User>> {
+        let pool = self.pool.as_ref();
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET trial_started_at = $1, trial_ends_at = $2, updated_at = $1
+            WHERE id = $3 AND trial_started_at IS NULL AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(started_at)
+        .bind(ends_at)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(user)
+    }
 }
@@ -0,0 +1,282 @@
+use crate::infrastructure::db::DbPool;
+use crate::{
+    domain::shared::Cursor,
+    domain::webhook::{Webhook, WebhookDelivery, WebhookDeliveryStatus, WebhookEventType},
+    error::AppResult,
+};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::types::Json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct WebhookRepository {
+    pool: Arc<DbPool>,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        url: &str,
+        secret: &str,
+        events: &[WebhookEventType],
+    ) -> AppResult<Webhook> {
+        let pool = self.pool.as_ref();
+        let webhook = sqlx::query_as::<_, Webhook>(
+            r#"
+            INSERT INTO webhooks (id, user_id, url, secret, events, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, user_id, url, secret, events, created_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(url)
+        .bind(secret)
+        .bind(Json(events))
+        .bind(Utc::now())
+        .fetch_one(pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    /// Webhooks registered by a user, oldest first
+    pub async fn find_by_user(&self, user_id: Uuid) -> AppResult<Vec<Webhook>> {
+        let pool = self.pool.as_ref();
+        let webhooks = sqlx::query_as::<_, Webhook>(
+            r#"
+            SELECT id, user_id, url, secret, events, created_at
+            FROM webhooks
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Webhook>> {
+        let pool = self.pool.as_ref();
+        let webhook = sqlx::query_as::<_, Webhook>(
+            r#"
+            SELECT id, user_id, url, secret, events, created_at
+            FROM webhooks
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    pub async fn count_by_user(&self, user_id: Uuid) -> AppResult<i64> {
+        let pool = self.pool.as_ref();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhooks WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Delete a webhook owned by a user, along with its delivery log
+    pub async fn delete_for_user(&self, id: Uuid, user_id: Uuid) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM webhooks
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Queue an event for a webhook, due immediately
+    pub async fn enqueue_delivery(
+        &self,
+        webhook_id: Uuid,
+        event_type: WebhookEventType,
+        payload: &Value,
+    ) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries
+                (id, webhook_id, event_type, payload, status, attempts, next_attempt_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, 0, $6, $6)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(webhook_id)
+        .bind(event_type)
+        .bind(payload)
+        .bind(WebhookDeliveryStatus::Pending)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Whether an event of this type was queued for the webhook at or after `since`
+    pub async fn has_delivery_since(
+        &self,
+        webhook_id: Uuid,
+        event_type: WebhookEventType,
+        since: DateTime<Utc>,
+    ) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM webhook_deliveries
+            WHERE webhook_id = $1 AND event_type = $2 AND created_at >= $3
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(event_type)
+        .bind(since)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    /// Claim up to `limit` pending deliveries that are due, pushing their next attempt to
+    /// `lease_until` so another worker doesn't pick them up while they're being sent
+    pub async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<WebhookDelivery>> {
+        let pool = self.pool.as_ref();
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = $1
+            WHERE id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status = $2 AND next_attempt_at <= $3
+                ORDER BY next_attempt_at
+                LIMIT $4
+            )
+              AND status = $2 AND next_attempt_at <= $3
+            RETURNING id, webhook_id, event_type, payload, status, attempts, next_attempt_at,
+                      last_status_code, last_error, created_at, completed_at
+            "#,
+        )
+        .bind(lease_until)
+        .bind(WebhookDeliveryStatus::Pending)
+        .bind(now)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// Store the outcome of an attempt. `completed_at` is set once the delivery stops
+    /// being pending.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_attempt(
+        &self,
+        id: Uuid,
+        status: WebhookDeliveryStatus,
+        attempts: i32,
+        next_attempt_at: DateTime<Utc>,
+        status_code: Option<i32>,
+        error: Option<&str>,
+        completed_at: Option<DateTime<Utc>>,
+    ) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2,
+                attempts = $3,
+                next_attempt_at = $4,
+                last_status_code = $5,
+                last_error = $6,
+                completed_at = $7
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(attempts)
+        .bind(next_attempt_at)
+        .bind(status_code)
+        .bind(error)
+        .bind(completed_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delivery log of a webhook, newest first
+    pub async fn find_deliveries(
+        &self,
+        webhook_id: Uuid,
+        after: Option<&Cursor<Uuid>>,
+        limit: i64,
+    ) -> AppResult<Vec<WebhookDelivery>> {
+        let pool = self.pool.as_ref();
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, webhook_id, event_type, payload, status, attempts, next_attempt_at,
+                   last_status_code, last_error, created_at, completed_at
+            FROM webhook_deliveries
+            WHERE webhook_id = $1
+              AND ($2 IS NULL OR (created_at, id) < ($2, $3))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(after.map(|cursor| cursor.at))
+        .bind(after.map(|cursor| cursor.key))
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// Delete finished deliveries completed before the cutoff. Pending ones are kept
+    /// however old they are.
+    pub async fn delete_completed_before(&self, cutoff: DateTime<Utc>) -> AppResult<u64> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM webhook_deliveries
+            WHERE completed_at IS NOT NULL AND completed_at < $1
+            "#,
+        )
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,95 @@
+use crate::infrastructure::db::DbPool;
+use crate::{domain::user::User, error::AppResult};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct WeeklySummaryRepository {
+    pool: Arc<DbPool>,
+}
+
+impl WeeklySummaryRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Users opted into the weekly summary, in id order after `after`
+    #[cfg(not(feature = "sqlite"))]
+    pub async fn find_recipients(&self, after: Uuid, limit: i64) -> AppResult<Vec<User>> {
+        let pool = self.pool.as_ref();
+        let users = sqlx::query_as::<_, User>(
+            r#"
+            SELECT * FROM users
+            WHERE id > $1
+              AND deleted_at IS NULL
+              AND settings->'notifications'->'weekly_summary' = 'true'::jsonb
+            ORDER BY id
+            LIMIT $2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    /// SQLite variant of `find_recipients`, reading the preference with `json_extract`
+    #[cfg(feature = "sqlite")]
+    pub async fn find_recipients(&self, after: Uuid, limit: i64) -> AppResult<Vec<User>> {
+        let pool = self.pool.as_ref();
+        let users = sqlx::query_as::<_, User>(
+            r#"
+            SELECT * FROM users
+            WHERE id > $1
+              AND deleted_at IS NULL
+              AND json_extract(settings, '$.notifications.weekly_summary') = 1
+            ORDER BY id
+            LIMIT $2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    /// Take the user's week for this instance. False when it was already taken.
+    pub async fn claim(
+        &self,
+        user_id: Uuid,
+        week_start: NaiveDate,
+        now: DateTime<Utc>,
+    ) -> AppResult<bool> {
+        let pool = self.pool.as_ref();
+        let result = sqlx::query(
+            r#"
+            INSERT INTO weekly_summaries (user_id, week_start, claimed_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, week_start) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(week_start)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Give a week back, so the next run tries it again
+    pub async fn release(&self, user_id: Uuid, week_start: NaiveDate) -> AppResult<()> {
+        let pool = self.pool.as_ref();
+        sqlx::query("DELETE FROM weekly_summaries WHERE user_id = $1 AND week_start = $2")
+            .bind(user_id)
+            .bind(week_start)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
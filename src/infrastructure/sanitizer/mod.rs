@@ -0,0 +1,193 @@
+use ammonia::{Builder, UrlRelative};
+use html2text::from_read;
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+/// Tags kept in sanitized article HTML; everything else is unwrapped to its text
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "small",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Tags removed together with their content (never readable article text)
+const STRIPPED_CONTENT_TAGS: &[&str] = &[
+    "script", "style", "iframe", "object", "embed", "noscript", "form", "svg", "template",
+];
+
+/// Hosts known to serve tracking pixels or click-tracking redirects
+const TRACKER_HOSTS: &[&str] = &[
+    "feeds.feedburner.com",
+    "feedproxy.google.com",
+    "pixel.wp.com",
+    "stats.wp.com",
+    "www.google-analytics.com",
+    "pi.feedsportal.com",
+    "rss.buysellads.com",
+];
+
+/// Query parameters stripped from links (campaign and click tracking)
+const TRACKING_PARAM_PREFIXES: &[&str] = &["utm_", "fbclid", "gclid", "mc_cid", "mc_eid"];
+
+static SANITIZER: LazyLock<Builder<'static>> = LazyLock::new(|| {
+    let mut builder = Builder::default();
+    builder
+        .tags(ALLOWED_TAGS.iter().copied().collect())
+        .clean_content_tags(STRIPPED_CONTENT_TAGS.iter().copied().collect())
+        .url_schemes(["http", "https", "mailto"].into_iter().collect())
+        .url_relative(UrlRelative::Deny)
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .attribute_filter(|element, attribute, value| match (element, attribute) {
+            ("img", "src") if is_tracker_url(value) => None,
+            ("a", "href") => Some(strip_tracking_params(value)),
+            _ => Some(Cow::Borrowed(value)),
+        });
+    builder
+});
+
+/// Keeps no tags at all, for feed fields that are only ever shown as text
+static TEXT_SANITIZER: LazyLock<Builder<'static>> = LazyLock::new(|| {
+    let mut builder = Builder::empty();
+    builder.clean_content_tags(STRIPPED_CONTENT_TAGS.iter().copied().collect());
+    builder
+});
+
+/// Sanitize untrusted feed HTML using an allowlist of tags and attributes.
+///
+/// Scripts, iframes, embeds and styles are removed with their content, tracking
+/// pixels lose their source and campaign parameters are stripped from links.
+pub fn sanitize_html(html: &str) -> String {
+    SANITIZER.clean(html).to_string()
+}
+
+/// Plain text of an untrusted feed field such as a title: tags are dropped (scripts and
+/// the like with their content), entities decoded and whitespace collapsed.
+pub fn sanitize_text(html: &str) -> String {
+    let cleaned = TEXT_SANITIZER.clean(html).to_string();
+    from_read(cleaned.as_bytes(), usize::MAX)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escape text for HTML we write ourselves, in element content or a quoted attribute
+pub fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn is_tracker_url(url: &str) -> bool {
+    let without_scheme = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let host = without_scheme.split(['/', '?', '#']).next().unwrap_or("");
+    TRACKER_HOSTS.contains(&host)
+}
+
+fn strip_tracking_params(url: &str) -> Cow<'_, str> {
+    let Some((base, query)) = url.split_once('?') else {
+        return Cow::Borrowed(url);
+    };
+    let (query, fragment) = match query.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (query, None),
+    };
+
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|param| {
+            let name = param.split('=').next().unwrap_or("");
+            !TRACKING_PARAM_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        })
+        .collect();
+
+    let mut cleaned = base.to_string();
+    if !kept.is_empty() {
+        cleaned.push('?');
+        cleaned.push_str(&kept.join("&"));
+    }
+    if let Some(fragment) = fragment {
+        cleaned.push('#');
+        cleaned.push_str(fragment);
+    }
+    Cow::Owned(cleaned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_removes_scripts_and_iframes_with_content() {
+        let input = r#"<p>Hello</p><script>alert('x')</script><iframe src="https://ads.example.com">ad</iframe>"#;
+        let result = sanitize_html(input);
+        assert_eq!(result, "<p>Hello</p>");
+    }
+
+    #[test]
+    fn test_sanitize_drops_event_handlers() {
+        let result = sanitize_html(r#"<p onclick="steal()">Text</p>"#);
+        assert_eq!(result, "<p>Text</p>");
+    }
+
+    #[test]
+    fn test_sanitize_removes_tracking_pixel_source() {
+        let result = sanitize_html(r#"<img src="https://pixel.wp.com/g.gif?blog=1">"#);
+        assert!(!result.contains("pixel.wp.com"));
+    }
+
+    #[test]
+    fn test_sanitize_text_keeps_only_the_text() {
+        let result = sanitize_text(
+            "<b>Rust</b> &amp; Go\n  weekly<script>alert('x')</script><img src=x onerror=y>",
+        );
+        assert_eq!(result, "Rust & Go weekly");
+        assert_eq!(sanitize_text("  Plain title "), "Plain title");
+    }
+
+    #[test]
+    fn test_sanitize_strips_tracking_params_from_links() {
+        let result =
+            sanitize_html(r#"<a href="https://example.com/post?id=3&utm_source=rss#top">Post</a>"#);
+        assert!(result.contains(r#"href="https://example.com/post?id=3#top""#));
+        assert!(!result.contains("utm_source"));
+    }
+}
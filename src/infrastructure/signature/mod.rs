@@ -0,0 +1,149 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Why `verify_signature` rejected a request
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum SignatureError {
+    #[error("signature header is malformed")]
+    Malformed,
+    #[error("signature timestamp is outside the tolerance window")]
+    OutsideTolerance,
+    #[error("no signature matches the body")]
+    Mismatch,
+}
+
+/// Signature header value in the scheme Stripe uses and our webhooks copy: `t=<unix
+/// time>,v1=<hex HMAC-SHA256 of "<unix time>.<body>">`. Receivers recompute it with
+/// their secret and reject stale timestamps to stop replays.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(
+            signature_mac(secret, timestamp, body)
+                .finalize()
+                .into_bytes()
+        )
+    )
+}
+
+/// Check a `t=…,v1=…` signature header against the raw body as received, with the
+/// shared secret, and only within `tolerance_secs` of `now` (unix seconds). Any `v1`
+/// entry may match, so more schemes or secrets can be added to the header without
+/// breaking receivers. Returns the signed timestamp.
+///
+/// The timestamp window stops old requests from being replayed; receivers that must
+/// never act twice should also drop repeated delivery ids.
+pub fn verify_signature(
+    secret: &str,
+    header: &str,
+    body: &[u8],
+    tolerance_secs: i64,
+    now: i64,
+) -> Result<i64, SignatureError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for (key, value) in header
+        .split(',')
+        .filter_map(|entry| entry.trim().split_once('='))
+    {
+        match key {
+            "t" => timestamp = value.parse::<i64>().ok(),
+            "v1" => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let timestamp = timestamp.ok_or(SignatureError::Malformed)?;
+    if signatures.is_empty() {
+        return Err(SignatureError::Malformed);
+    }
+    if (now - timestamp).abs() > tolerance_secs {
+        return Err(SignatureError::OutsideTolerance);
+    }
+
+    let valid = signatures.iter().any(|signature| {
+        let Ok(expected) = hex::decode(signature) else {
+            return false;
+        };
+        signature_mac(secret, timestamp, body)
+            .verify_slice(&expected)
+            .is_ok()
+    });
+    if !valid {
+        return Err(SignatureError::Mismatch);
+    }
+    Ok(timestamp)
+}
+
+fn signature_mac(secret: &str, timestamp: i64, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_covers_timestamp_and_body() {
+        let body = br#"{"id":"1"}"#;
+
+        let signature = sign("whsec_test", 1000, body);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"whsec_test").unwrap();
+        mac.update(b"1000.");
+        mac.update(body);
+        let expected = hex::encode(mac.finalize().into_bytes());
+        assert_eq!(signature, format!("t=1000,v1={}", expected));
+        assert_ne!(signature, sign("whsec_test", 1001, body));
+        assert_ne!(signature, sign("whsec_other", 1000, body));
+    }
+
+    #[test]
+    fn test_verify_signature_accepts_what_sign_produces() {
+        let body = br#"{"id":"1"}"#;
+        let header = sign("whsec_test", 1000, body);
+
+        assert_eq!(
+            verify_signature("whsec_test", &header, body, 300, 1200),
+            Ok(1000)
+        );
+        // Extra entries, like a signature with a rotated-out secret, are ignored
+        let (_, current) = header.split_once("v1=").unwrap();
+        let rotated = format!("{},v1={}", sign("whsec_old", 1000, body), current);
+        assert_eq!(
+            verify_signature("whsec_test", &rotated, body, 300, 1000),
+            Ok(1000)
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_tampering_and_replays() {
+        let body = br#"{"id":"1"}"#;
+        let header = sign("whsec_test", 1000, body);
+
+        assert_eq!(
+            verify_signature("whsec_test", &header, br#"{"id":"2"}"#, 300, 1000),
+            Err(SignatureError::Mismatch)
+        );
+        assert_eq!(
+            verify_signature("whsec_other", &header, body, 300, 1000),
+            Err(SignatureError::Mismatch)
+        );
+        assert_eq!(
+            verify_signature("whsec_test", &header, body, 300, 1301),
+            Err(SignatureError::OutsideTolerance)
+        );
+        for malformed in ["", "t=1000", "v1=abc", "t=soon,v1=abc"] {
+            assert_eq!(
+                verify_signature("whsec_test", malformed, body, 300, 1000),
+                Err(SignatureError::Malformed)
+            );
+        }
+    }
+}
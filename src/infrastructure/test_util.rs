@@ -0,0 +1,105 @@
+//! The user and TTS services wired the way `main` wires them, but over the in-memory
+//! repositories, the fake Polly and a mailer that only logs, so they run without Postgres
+//! or AWS. The repositories stay reachable for arranging state and checking what the
+//! services wrote.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::audit::AuditService;
+use crate::domain::events::{AuditSubscriber, InProcessEventBus};
+use crate::domain::plan::PlanLimits;
+use crate::domain::tts::{TtsMetrics, TtsService};
+use crate::domain::user::UserService;
+use crate::infrastructure::audio_cdn::AudioCdn;
+use crate::infrastructure::auth::AuthUserCache;
+use crate::infrastructure::cache::MemoryCacheStore;
+use crate::infrastructure::config::RetentionConfig;
+use crate::infrastructure::dev_fakes::fake_polly_client;
+use crate::infrastructure::email::LogEmailSender;
+use crate::infrastructure::events::AccountEventStream;
+use crate::infrastructure::repositories::{
+    InMemoryAuditLogRepository, InMemoryOutboxRepository, InMemorySubscriptionEventRepository,
+    InMemoryUsageRepository, InMemoryUserRepository,
+};
+
+const AUTH_USER_CACHE_TTL: Duration = Duration::from_secs(60);
+
+pub struct InMemoryServices {
+    pub user_repo: Arc<InMemoryUserRepository>,
+    pub usage_repo: Arc<InMemoryUsageRepository>,
+    pub subscription_event_repo: Arc<InMemorySubscriptionEventRepository>,
+    pub audit_log_repo: Arc<InMemoryAuditLogRepository>,
+    pub outbox_repo: Arc<InMemoryOutboxRepository>,
+    pub auth_user_cache: Arc<AuthUserCache>,
+    pub user_service: Arc<UserService>,
+    pub tts_service: Arc<TtsService>,
+}
+
+impl InMemoryServices {
+    pub fn new(plan_limits: PlanLimits) -> Self {
+        Self::build(plan_limits, None)
+    }
+
+    /// With synthesized audio delivered through `audio_cdn`
+    pub fn with_audio_cdn(plan_limits: PlanLimits, audio_cdn: Arc<dyn AudioCdn>) -> Self {
+        Self::build(plan_limits, Some(audio_cdn))
+    }
+
+    fn build(plan_limits: PlanLimits, audio_cdn: Option<Arc<dyn AudioCdn>>) -> Self {
+        let user_repo = Arc::new(InMemoryUserRepository::new());
+        let usage_repo = Arc::new(InMemoryUsageRepository::new());
+        let subscription_event_repo = Arc::new(InMemorySubscriptionEventRepository::new());
+        let audit_log_repo = Arc::new(InMemoryAuditLogRepository::new());
+        let outbox_repo = Arc::new(InMemoryOutboxRepository::new());
+
+        let plan_limits = Arc::new(plan_limits);
+        let auth_user_cache = Arc::new(AuthUserCache::new(
+            Arc::new(MemoryCacheStore::new()),
+            AUTH_USER_CACHE_TTL,
+        ));
+        let audit_service = Arc::new(AuditService::new(audit_log_repo.clone()));
+        let domain_events = Arc::new(InProcessEventBus::new(vec![Arc::new(
+            AuditSubscriber::new(audit_service.clone()),
+        )]));
+
+        let user_service = Arc::new(UserService::new(
+            user_repo.clone(),
+            usage_repo.clone(),
+            subscription_event_repo.clone(),
+            plan_limits.clone(),
+            audit_service,
+            auth_user_cache.clone(),
+        ));
+        let tts_service = Arc::new(TtsService::new(
+            user_repo.clone(),
+            usage_repo.clone(),
+            Arc::new(fake_polly_client("us-east-1")),
+            plan_limits,
+            None,
+            domain_events,
+            Arc::new(AccountEventStream::new()),
+            outbox_repo.clone(),
+            Arc::new(LogEmailSender::new()),
+            Arc::new(TtsMetrics::new()),
+            audio_cdn,
+            &RetentionConfig {
+                usage_days: 365,
+                audit_log_days: None,
+                article_days: 0,
+                cached_audio_days: 0,
+            },
+        ));
+
+        Self {
+            user_repo,
+            usage_repo,
+            subscription_event_repo,
+            audit_log_repo,
+            outbox_repo,
+            auth_user_cache,
+            user_service,
+            tts_service,
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::webhook::{WebhookDelivery, WebhookEventType};
+use crate::infrastructure::feed_fetcher::ensure_public_host;
+use crate::infrastructure::signature;
+
+pub use crate::infrastructure::signature::{verify_signature, SignatureError};
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest response excerpt kept in the delivery log
+const MAX_ERROR_LENGTH: usize = 500;
+
+pub const SIGNATURE_HEADER: &str = "X-FeedTape-Signature";
+pub const EVENT_HEADER: &str = "X-FeedTape-Event";
+pub const DELIVERY_HEADER: &str = "X-FeedTape-Delivery";
+/// How far a signature's timestamp may be from the receiver's clock before
+/// `verify_signature` treats the request as a replay
+pub const SIGNATURE_TOLERANCE_SECS: i64 = 5 * 60;
+
+/// Body of every webhook request
+#[derive(Debug, Serialize)]
+struct Envelope<'a> {
+    /// Delivery id; the same on retries so receivers can drop duplicates
+    id: Uuid,
+    #[serde(rename = "type")]
+    event_type: WebhookEventType,
+    created_at: DateTime<Utc>,
+    data: &'a Value,
+}
+
+/// What came of one attempt
+#[derive(Debug)]
+pub struct DeliveryOutcome {
+    pub success: bool,
+    /// Status the endpoint answered with, if the request got that far
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// HTTP client that posts signed events to user-registered endpoints
+pub struct WebhookSender {
+    http_client: reqwest::Client,
+}
+
+impl WebhookSender {
+    pub fn new() -> Self {
+        // Redirects could lead to hosts the public-address check never saw
+        let http_client = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .user_agent("FeedTape-Webhooks")
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build webhook HTTP client");
+
+        Self { http_client }
+    }
+
+    /// POST the delivery to `url`. Any 2xx answer counts as delivered.
+    pub async fn send(
+        &self,
+        url: &str,
+        secret: &str,
+        delivery: &WebhookDelivery,
+    ) -> DeliveryOutcome {
+        let failure = |status_code: Option<u16>, error: String| DeliveryOutcome {
+            success: false,
+            status_code,
+            error: Some(truncate(error)),
+        };
+
+        let parsed_url = match Url::parse(url) {
+            Ok(parsed_url) => parsed_url,
+            Err(_) => return failure(None, "Invalid URL".to_string()),
+        };
+        if let Err(e) = ensure_public_host(&parsed_url).await {
+            return failure(None, e);
+        }
+
+        let body = match serde_json::to_vec(&Envelope {
+            id: delivery.id,
+            event_type: delivery.event_type,
+            created_at: delivery.created_at,
+            data: &delivery.payload,
+        }) {
+            Ok(body) => body,
+            Err(e) => return failure(None, format!("Failed to encode payload: {}", e)),
+        };
+        let signature = signature::sign(secret, Utc::now().timestamp(), &body);
+
+        let response = self
+            .http_client
+            .post(parsed_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_HEADER, delivery.event_type.as_str())
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .body(body)
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => DeliveryOutcome {
+                success: true,
+                status_code: Some(response.status().as_u16()),
+                error: None,
+            },
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let body = response.text().await.unwrap_or_default();
+                failure(Some(status), format!("HTTP status {}: {}", status, body))
+            }
+            Err(e) => failure(None, format!("Request failed: {}", e)),
+        }
+    }
+}
+
+impl Default for WebhookSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn truncate(mut message: String) -> String {
+    if message.len() > MAX_ERROR_LENGTH {
+        let mut end = MAX_ERROR_LENGTH;
+        while !message.is_char_boundary(end) {
+            end -= 1;
+        }
+        message.truncate(end);
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_keeps_char_boundaries() {
+        let message = "é".repeat(MAX_ERROR_LENGTH);
+
+        let truncated = truncate(message);
+
+        assert!(truncated.len() <= MAX_ERROR_LENGTH);
+        assert!(truncated.chars().all(|c| c == 'é'));
+    }
+}
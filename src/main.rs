@@ -1,65 +1,119 @@
-use feedtape_backend::infrastructure::config::{Config, LogFormat};
-use feedtape_backend::infrastructure::db::{check_connection, create_pool};
+use feedtape_backend::domain::feed::FeedServiceApi;
+use feedtape_backend::domain::feed_suggestions::SuggestionModerationServiceApi;
+use feedtape_backend::domain::ip_block::IpBlockServiceApi;
+use feedtape_backend::domain::outbox::OutboxServiceApi;
+use feedtape_backend::domain::user::UserServiceApi;
+use feedtape_backend::domain::webhook::WebhookServiceApi;
+use feedtape_backend::domain::weekly_summary::WeeklySummaryServiceApi;
+use feedtape_backend::infrastructure::config::{Config, LogFormat, SchemaCheck};
+use feedtape_backend::infrastructure::db::{connect_with_retry, migration_status};
+use feedtape_backend::infrastructure::grpc::start_grpc_server;
 use feedtape_backend::infrastructure::http::start_http_server;
+use feedtape_backend::infrastructure::jobs::{spawn_periodic, MaintenanceTask};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+const FEED_STATS_REFRESH_INTERVAL_SECS: u64 = 60 * 60;
+const SUGGESTION_LINK_CHECK_INTERVAL_SECS: u64 = 6 * 60 * 60;
+const ACCOUNT_PURGE_INTERVAL_SECS: u64 = 60 * 60;
+const WEBHOOK_DELIVERY_INTERVAL_SECS: u64 = 10;
+const IP_BLOCKLIST_RELOAD_INTERVAL_SECS: u64 = 30;
+const PROMOTED_SUGGESTIONS_RELOAD_INTERVAL_SECS: u64 = 5 * 60;
+const OUTBOX_DISPATCH_INTERVAL_SECS: u64 = 2;
+const WEEKLY_SUMMARY_INTERVAL_SECS: u64 = 60 * 60;
+/// How long the startup credential check waits for Polly
+const POLLY_STARTUP_CHECK_TIMEOUT_SECS: u64 = 10;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let command = Command::from_args()?;
+
     // Load configuration
-    let config = Config::from_env()?;
+    let config = Config::from_env().await?;
+    config.validate()?;
 
     // Initialize logging
     init_logging(&config);
 
+    // Create and verify the database connection pool, waiting for the database to come up
+    let pool = connect_with_retry(&config.database_url, &config.database_connect).await?;
+    tracing::info!("Database connection verified");
+
+    // SQLite installs have no separate migration step, so the schema is brought up to date here
+    #[cfg(feature = "sqlite")]
+    {
+        feedtape_backend::infrastructure::db::MIGRATOR
+            .run(&pool)
+            .await?;
+        tracing::info!("Database migrations applied");
+    }
+
+    check_schema(&pool, &config).await?;
+
+    if command == Command::Seed {
+        return seed(pool, &config).await;
+    }
+
     tracing::info!(
         "Starting FeedTape Backend on {}:{}",
         config.host,
         config.port
     );
 
-    // Create database connection pool
-    let pool = create_pool(&config.database_url).await?;
-    tracing::info!("Database connection pool created");
-
-    // Verify database connection
-    check_connection(&pool).await?;
-    tracing::info!("Database connection verified");
+    let read_pool = match &config.database_read_url {
+        Some(database_read_url) => {
+            let read_pool = connect_with_retry(database_read_url, &config.database_connect).await?;
+            tracing::info!("Read replica connection verified");
+            Some(Arc::new(read_pool))
+        }
+        None => None,
+    };
 
     // Create AWS Polly client
-    tracing::info!(
-        "Initializing AWS Polly client with region: {}",
-        config.aws_region
-    );
+    let polly_client = if config.dev_fake_externals {
+        tracing::warn!(
+            "DEV_FAKE_EXTERNALS is set: TTS is served by a fake Polly returning silent audio"
+        );
+        feedtape_backend::infrastructure::dev_fakes::fake_polly_client(&config.aws_region)
+    } else {
+        tracing::info!(
+            "Initializing AWS Polly client with region: {}",
+            config.aws_region
+        );
 
-    // Check for AWS credentials in environment (for debugging)
-    let has_access_key = std::env::var("AWS_ACCESS_KEY_ID").is_ok();
-    let has_secret_key = std::env::var("AWS_SECRET_ACCESS_KEY").is_ok();
-    tracing::info!(
-        has_access_key_id = has_access_key,
-        has_secret_access_key = has_secret_key,
-        "AWS credentials environment check"
-    );
+        // Check for AWS credentials in environment (for debugging)
+        let has_access_key = std::env::var("AWS_ACCESS_KEY_ID").is_ok();
+        let has_secret_key = std::env::var("AWS_SECRET_ACCESS_KEY").is_ok();
+        tracing::info!(
+            has_access_key_id = has_access_key,
+            has_secret_access_key = has_secret_key,
+            "AWS credentials environment check"
+        );
 
-    if !has_access_key || !has_secret_key {
-        tracing::warn!("AWS credentials not found in environment variables. Will attempt to use other credential providers (instance metadata, etc.)");
-    }
+        if !has_access_key || !has_secret_key {
+            tracing::warn!("AWS credentials not found in environment variables. Will attempt to use other credential providers (instance metadata, etc.)");
+        }
 
-    let aws_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-        .region(aws_config::Region::new(config.aws_region.clone()))
-        .load()
-        .await;
+        let aws_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new(config.aws_region.clone()))
+            .load()
+            .await;
 
-    // Log AWS config details (without exposing credentials)
-    tracing::info!(
-        region = ?aws_config.region(),
-        "AWS configuration loaded"
-    );
+        // Log AWS config details (without exposing credentials)
+        tracing::info!(
+            region = ?aws_config.region(),
+            "AWS configuration loaded"
+        );
 
-    let polly_client = aws_sdk_polly::Client::new(&aws_config);
-    tracing::info!("AWS Polly client initialized successfully");
+        let polly_client = aws_sdk_polly::Client::new(&aws_config);
+        check_polly_credentials(&polly_client, &config).await?;
+        tracing::info!("AWS Polly client initialized successfully");
+        polly_client
+    };
 
     let pool = Arc::new(pool);
+    let read_pool = read_pool.unwrap_or_else(|| pool.clone());
     let config = Arc::new(config);
     let polly_client = Arc::new(polly_client);
 
@@ -68,54 +122,341 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("Instantiating repositories...");
     let user_repo =
         Arc::new(feedtape_backend::infrastructure::repositories::UserRepository::new(pool.clone()));
-    let feed_repo =
-        Arc::new(feedtape_backend::infrastructure::repositories::FeedRepository::new(pool.clone()));
+    let feed_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::FeedRepository::new(pool.clone())
+            .with_read_pool(read_pool.clone()),
+    );
     let feed_suggestions_repo = Arc::new(
         feedtape_backend::infrastructure::repositories::HardcodedFeedSuggestionsRepository::new(),
     );
     let refresh_token_repo = Arc::new(
         feedtape_backend::infrastructure::repositories::RefreshTokenRepository::new(pool.clone()),
     );
-    let usage_repo = Arc::new(
-        feedtape_backend::infrastructure::repositories::UsageRepository::new(pool.clone()),
+    let usage_repo = {
+        let repo =
+            feedtape_backend::infrastructure::repositories::UsageRepository::new(pool.clone())
+                .with_read_pool(read_pool.clone());
+        if config.usage_flush_interval_ms > 0 {
+            Arc::new(repo.with_write_buffer())
+        } else {
+            Arc::new(repo)
+        }
+    };
+    let mute_rule_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::MuteRuleRepository::new(pool.clone()),
+    );
+    let subscription_purchase_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::SubscriptionPurchaseRepository::new(
+            pool.clone(),
+        ),
+    );
+    let referral_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::ReferralRepository::new(pool.clone()),
+    );
+    let promo_code_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::PromoCodeRepository::new(pool.clone()),
+    );
+    let family_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::FamilyRepository::new(pool.clone()),
+    );
+    let device_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::DeviceRepository::new(pool.clone()),
+    );
+    let analytics_event_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::AnalyticsEventRepository::new(pool.clone()),
+    );
+    let subscription_event_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::SubscriptionEventRepository::new(
+            pool.clone(),
+        ),
+    );
+    let idempotency_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::IdempotencyRepository::new(pool.clone()),
+    );
+    let audit_log_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::AuditLogRepository::new(pool.clone()),
+    );
+    let webhook_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::WebhookRepository::new(pool.clone()),
+    );
+    let ip_block_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::IpBlockRepository::new(pool.clone()),
+    );
+    let outbox_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::OutboxRepository::new(pool.clone()),
+    );
+    let suggestion_submission_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::SuggestionSubmissionRepository::new(
+            pool.clone(),
+        ),
+    );
+    let oauth_state_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::OAuthStateRepository::new(pool.clone()),
+    );
+    let weekly_summary_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::WeeklySummaryRepository::new(pool.clone()),
+    );
+    let api_key_repo = Arc::new(
+        feedtape_backend::infrastructure::repositories::ApiKeyRepository::new(pool.clone()),
     );
 
-    // 2. Instantiate OAuth clients
+    // 2. Instantiate OAuth and HTTP clients
     tracing::info!("Instantiating OAuth clients...");
-    let github_oauth_client = Arc::new(
-        feedtape_backend::infrastructure::oauth::GitHubOAuthClient::new(
-            config.github_client_id.clone(),
-            config.github_client_secret.clone(),
-            config.github_redirect_uri.clone(),
+    let github_oauth_client: Arc<dyn feedtape_backend::infrastructure::oauth::OAuthProvider> =
+        if config.dev_fake_github_oauth {
+            tracing::warn!("DEV_FAKE_GITHUB_OAUTH is set: GitHub sign-in is faked");
+            Arc::new(
+                feedtape_backend::infrastructure::dev_fakes::github::FakeGitHubOAuth::new(
+                    config.github_redirect_uri.clone(),
+                ),
+            )
+        } else {
+            Arc::new(
+                feedtape_backend::infrastructure::oauth::GitHubOAuthClient::new(
+                    config.github_client_id.clone(),
+                    config.github_client_secret.clone(),
+                    config.github_redirect_uri.clone(),
+                    config.github_oauth_base_url.clone(),
+                    config.github_api_base_url.clone(),
+                ),
+            )
+        };
+    let feed_fetcher = Arc::new(feedtape_backend::infrastructure::feed_fetcher::FeedFetcher::new());
+    let cache_store = feedtape_backend::infrastructure::cache::create_cache_store(&config).await?;
+    let account_events =
+        Arc::new(feedtape_backend::infrastructure::events::AccountEventStream::new());
+    let email_sender =
+        feedtape_backend::infrastructure::email::create_email_sender(&config).await?;
+    let audio_cdn = feedtape_backend::infrastructure::audio_cdn::create_audio_cdn(&config).await?;
+    let auth_user_cache = Arc::new(feedtape_backend::infrastructure::auth::AuthUserCache::new(
+        cache_store.clone(),
+        Duration::from_secs(config.auth_user_cache_secs),
+    ));
+    let token_denylist = Arc::new(
+        feedtape_backend::infrastructure::auth::AccessTokenDenylist::new(
+            cache_store.clone(),
+            Duration::from_secs(config.jwt_expiration_hours as u64 * 3600),
         ),
     );
+    let webhook_sender = Arc::new(feedtape_backend::infrastructure::webhooks::WebhookSender::new());
+    let google_play_client = match (
+        &config.google_play_package_name,
+        &config.google_play_service_account_key,
+    ) {
+        (Some(package_name), Some(service_account_key)) => Some(Arc::new(
+            feedtape_backend::infrastructure::billing::GooglePlayClient::new(
+                package_name.clone(),
+                service_account_key,
+            )?,
+        )),
+        _ => {
+            tracing::warn!("Google Play billing not configured, purchase validation disabled");
+            None
+        }
+    };
+    let stripe_client = match (
+        &config.stripe_secret_key,
+        &config.stripe_webhook_secret,
+        &config.stripe_price_id,
+        &config.stripe_checkout_success_url,
+        &config.stripe_checkout_cancel_url,
+    ) {
+        (
+            Some(secret_key),
+            Some(webhook_secret),
+            Some(price_id),
+            Some(success_url),
+            Some(cancel_url),
+        ) => Some(Arc::new(
+            feedtape_backend::infrastructure::billing::StripeClient::new(
+                secret_key.clone(),
+                webhook_secret.clone(),
+                price_id.clone(),
+                success_url.clone(),
+                cancel_url.clone(),
+            ),
+        )),
+        _ => {
+            tracing::warn!("Stripe billing not configured, web checkout disabled");
+            None
+        }
+    };
+    let app_store_verifier = config.apple_bundle_id.clone().map(|bundle_id| {
+        Arc::new(
+            feedtape_backend::infrastructure::billing::AppStoreNotificationVerifier::new(bundle_id),
+        )
+    });
 
     // 3. Instantiate services (inject repositories and clients)
     tracing::info!("Instantiating services...");
+    let plan_limits = Arc::new(config.plan_limits.clone());
+    let audit_service = Arc::new(feedtape_backend::domain::audit::AuditService::new(
+        audit_log_repo.clone(),
+    ));
+    let webhook_service = Arc::new(feedtape_backend::domain::webhook::WebhookService::new(
+        webhook_repo.clone(),
+        webhook_sender,
+    ));
+    // Subsystems reacting to domain events, whether published to the bus or dispatched
+    // from the outbox
+    let event_subscribers: Vec<Arc<dyn feedtape_backend::domain::events::EventSubscriber>> = vec![
+        Arc::new(feedtape_backend::domain::events::WebhookSubscriber::new(
+            webhook_service.clone(),
+        )),
+        Arc::new(feedtape_backend::domain::events::AuditSubscriber::new(
+            audit_service.clone(),
+        )),
+    ];
+    let domain_events: Arc<dyn feedtape_backend::domain::events::EventBus> = Arc::new(
+        feedtape_backend::domain::events::InProcessEventBus::new(event_subscribers.clone()),
+    );
+    let jwt_manager = Arc::new(feedtape_backend::domain::auth::JwtManager::from_config(
+        &config,
+    )?);
     let auth_service = Arc::new(feedtape_backend::domain::auth::AuthService::new(
         user_repo.clone(),
         refresh_token_repo.clone(),
-        config.jwt_secret.clone(),
-        config.jwt_expiration_hours,
+        jwt_manager.clone(),
         config.refresh_token_expiration_days,
+        token_denylist.clone(),
+        domain_events.clone(),
+    ));
+    let ip_block_service = Arc::new(feedtape_backend::domain::ip_block::IpBlockService::new(
+        ip_block_repo.clone(),
+        cache_store.clone(),
+        audit_service.clone(),
+        config.auth_failure_block.clone(),
+    ));
+    let blocked = ip_block_service.reload().await?;
+    tracing::info!("Loaded {} IP blocks", blocked);
+    let mute_service = Arc::new(feedtape_backend::domain::mute::MuteService::new(
+        mute_rule_repo.clone(),
+        feed_repo.clone(),
     ));
     let feed_service = Arc::new(feedtape_backend::domain::feed::FeedService::new(
         feed_repo.clone(),
         user_repo.clone(),
+        feed_fetcher.clone(),
+        feed_suggestions_repo.clone(),
+        plan_limits.clone(),
+        domain_events.clone(),
+        account_events.clone(),
+        mute_service.clone(),
+        cache_store.clone(),
     ));
     let user_service = Arc::new(feedtape_backend::domain::user::UserService::new(
         user_repo.clone(),
         usage_repo.clone(),
+        subscription_event_repo.clone(),
+        plan_limits.clone(),
+        audit_service.clone(),
+        auth_user_cache.clone(),
     ));
+    let tts_metrics = Arc::new(feedtape_backend::domain::tts::TtsMetrics::new());
     let tts_service = Arc::new(feedtape_backend::domain::tts::TtsService::new(
         user_repo.clone(),
         usage_repo.clone(),
         polly_client.clone(),
-        config.tts_cache_enabled,
+        plan_limits.clone(),
+        config.tts_cache_enabled.then(|| cache_store.clone()),
+        domain_events.clone(),
+        account_events.clone(),
+        outbox_repo.clone(),
+        email_sender.clone(),
+        tts_metrics.clone(),
+        audio_cdn.clone(),
+        &config.retention,
+    ));
+    let outbox_service = Arc::new(feedtape_backend::domain::outbox::OutboxService::new(
+        outbox_repo.clone(),
+        event_subscribers,
     ));
     let feed_suggestions_service = Arc::new(
         feedtape_backend::domain::feed_suggestions::FeedSuggestionsService::new(
-            feed_suggestions_repo,
+            feed_suggestions_repo.clone(),
+            feed_fetcher.clone(),
+        ),
+    );
+    let suggestion_moderation_service = Arc::new(
+        feedtape_backend::domain::feed_suggestions::SuggestionModerationService::new(
+            suggestion_submission_repo,
+            feed_suggestions_repo.clone(),
+            user_repo.clone(),
+            email_sender.clone(),
+            audit_service.clone(),
+        ),
+    );
+    let promoted = suggestion_moderation_service.reload().await?;
+    tracing::info!("Loaded {} promoted feed suggestions", promoted);
+    let backup_service = Arc::new(feedtape_backend::domain::backup::BackupService::new(
+        user_service.clone(),
+        feed_service.clone(),
+        mute_service.clone(),
+        feed_suggestions_repo,
+    ));
+    let privacy_service = Arc::new(feedtape_backend::domain::privacy::PrivacyService::new(
+        user_repo.clone(),
+        user_service.clone(),
+        feed_repo.clone(),
+        mute_rule_repo,
+        usage_repo.clone(),
+        refresh_token_repo.clone(),
+        device_repo.clone(),
+    ));
+    let api_key_service = Arc::new(feedtape_backend::domain::api_key::ApiKeyService::new(
+        api_key_repo.clone(),
+    ));
+    let weekly_summary_service = Arc::new(
+        feedtape_backend::domain::weekly_summary::WeeklySummaryService::new(
+            weekly_summary_repo,
+            usage_repo.clone(),
+            analytics_event_repo.clone(),
+            feed_repo.clone(),
+            user_service.clone(),
+            email_sender,
+            feedtape_backend::domain::weekly_summary::UnsubscribeLinks::new(
+                config.jwt_secret.clone(),
+                config.email.link_base_url.clone(),
+            ),
+        ),
+    );
+    let admin_service = Arc::new(feedtape_backend::domain::admin::AdminService::new(
+        user_repo.clone(),
+        usage_repo.clone(),
+        promo_code_repo.clone(),
+        plan_limits.clone(),
+        audit_service.clone(),
+        feed_repo.clone(),
+        auth_user_cache.clone(),
+    ));
+    let referral_service = Arc::new(feedtape_backend::domain::referral::ReferralService::new(
+        user_repo.clone(),
+        referral_repo,
+        plan_limits.clone(),
+    ));
+    let family_service = Arc::new(feedtape_backend::domain::family::FamilyService::new(
+        user_repo.clone(),
+        family_repo,
+        auth_user_cache.clone(),
+    ));
+    let device_service = Arc::new(feedtape_backend::domain::device::DeviceService::new(
+        device_repo,
+    ));
+    let analytics_service = Arc::new(feedtape_backend::domain::analytics::AnalyticsService::new(
+        analytics_event_repo,
+        config.analytics_sample_rate,
+    ));
+    let subscription_service = Arc::new(
+        feedtape_backend::domain::subscription::SubscriptionService::new(
+            user_repo.clone(),
+            subscription_purchase_repo,
+            promo_code_repo,
+            subscription_event_repo,
+            google_play_client,
+            stripe_client,
+            app_store_verifier,
+            audit_service,
+            auth_user_cache.clone(),
         ),
     );
 
@@ -127,39 +468,289 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let oauth_controller = Arc::new(feedtape_backend::controllers::oauth::OAuthController::new(
         github_oauth_client,
         user_repo.clone(),
-        auth_service,
+        oauth_state_repo.clone(),
+        auth_service.clone(),
+        config.secure_cookies,
     ));
     let feed_controller = Arc::new(feedtape_backend::controllers::feed::FeedController::new(
-        feed_service,
+        feed_service.clone(),
     ));
     let user_controller = Arc::new(feedtape_backend::controllers::user::UserController::new(
         user_service.clone(),
+        backup_service,
+        privacy_service,
     ));
     let tts_controller = Arc::new(feedtape_backend::controllers::tts::TtsController::new(
-        tts_service,
-        user_service,
+        tts_service.clone(),
+        user_service.clone(),
         usage_repo.clone(),
+        plan_limits.clone(),
     ));
     let feed_suggestions_controller = Arc::new(
         feedtape_backend::controllers::feed_suggestions::FeedSuggestionsController::new(
-            feed_suggestions_service,
+            feed_suggestions_service.clone(),
+            suggestion_moderation_service.clone(),
+        ),
+    );
+    let mute_controller = Arc::new(feedtape_backend::controllers::mute::MuteController::new(
+        mute_service.clone(),
+    ));
+    let graphql_controller = Arc::new(
+        feedtape_backend::controllers::graphql::GraphQLController::new(
+            user_service.clone(),
+            feed_service.clone(),
+            mute_service,
+        ),
+    );
+    let subscription_controller = Arc::new(
+        feedtape_backend::controllers::subscription::SubscriptionController::new(
+            subscription_service,
+        ),
+    );
+    let admin_controller = Arc::new(feedtape_backend::controllers::admin::AdminController::new(
+        admin_service.clone(),
+        tts_metrics,
+    ));
+    let admin_dashboard_controller = Arc::new(
+        feedtape_backend::controllers::admin_dashboard::AdminDashboardController::new(
+            admin_service,
+            feed_suggestions_service.clone(),
+            config.secure_cookies,
+        ),
+    );
+    let referral_controller = Arc::new(
+        feedtape_backend::controllers::referral::ReferralController::new(referral_service),
+    );
+    let family_controller =
+        Arc::new(feedtape_backend::controllers::family::FamilyController::new(family_service));
+    let device_controller =
+        Arc::new(feedtape_backend::controllers::device::DeviceController::new(device_service));
+    let analytics_controller = Arc::new(
+        feedtape_backend::controllers::analytics::AnalyticsController::new(analytics_service),
+    );
+    let webhook_controller = Arc::new(
+        feedtape_backend::controllers::webhook::WebhookController::new(webhook_service.clone()),
+    );
+    let events_controller =
+        Arc::new(feedtape_backend::controllers::events::EventsController::new(account_events));
+    let ip_block_controller = Arc::new(
+        feedtape_backend::controllers::ip_block::IpBlockController::new(ip_block_service.clone()),
+    );
+    let weekly_summary_controller = Arc::new(
+        feedtape_backend::controllers::weekly_summary::WeeklySummaryController::new(
+            weekly_summary_service.clone(),
         ),
     );
+    let api_key_controller =
+        Arc::new(feedtape_backend::controllers::api_key::ApiKeyController::new(api_key_service));
+    let health_controller = Arc::new(
+        feedtape_backend::controllers::health::HealthController::new(
+            pool.clone(),
+            cache_store.clone(),
+            polly_client.clone(),
+        ),
+    );
+
+    // Internal gRPC API, only when GRPC_PORT is set
+    if let Some(grpc) = &config.grpc {
+        let grpc_controller = Arc::new(feedtape_backend::controllers::grpc::GrpcController::new(
+            tts_service,
+            user_service.clone(),
+            auth_service,
+            plan_limits,
+            config.max_concurrent_syntheses,
+        ));
+        start_grpc_server(&config.host, grpc, grpc_controller).await?;
+    }
+
+    // 5. Start background jobs
+    tracing::info!("Starting background jobs...");
+    spawn_periodic(
+        "feed_stats_refresh",
+        Duration::from_secs(FEED_STATS_REFRESH_INTERVAL_SECS),
+        move || {
+            let feed_service = feed_service.clone();
+            async move {
+                match feed_service.refresh_stale_stats().await {
+                    Ok(count) => tracing::debug!("Refreshed stats for {} feeds", count),
+                    Err(e) => tracing::warn!("Feed stats refresh failed: {}", e),
+                }
+            }
+        },
+    );
+    spawn_periodic(
+        "suggestion_link_check",
+        Duration::from_secs(SUGGESTION_LINK_CHECK_INTERVAL_SECS),
+        move || {
+            let feed_suggestions_service = feed_suggestions_service.clone();
+            async move {
+                let broken = feed_suggestions_service.check_suggestion_links().await;
+                tracing::info!("Feed suggestion link check finished, {} broken", broken);
+            }
+        },
+    );
+    spawn_periodic(
+        "account_purge",
+        Duration::from_secs(ACCOUNT_PURGE_INTERVAL_SECS),
+        move || {
+            let user_service = user_service.clone();
+            async move {
+                match user_service.delete_due_accounts().await {
+                    Ok(count) => tracing::debug!("Deleted {} accounts", count),
+                    Err(e) => tracing::warn!("Account deletion failed: {}", e),
+                }
+                match user_service.purge_deleted_accounts().await {
+                    Ok(count) => tracing::debug!("Purged {} deleted accounts", count),
+                    Err(e) => tracing::warn!("Account purge failed: {}", e),
+                }
+            }
+        },
+    );
+    spawn_periodic(
+        "webhook_delivery",
+        Duration::from_secs(WEBHOOK_DELIVERY_INTERVAL_SECS),
+        move || {
+            let webhook_service = webhook_service.clone();
+            async move {
+                match webhook_service.deliver_due().await {
+                    Ok(count) => tracing::debug!("Attempted {} webhook deliveries", count),
+                    Err(e) => tracing::warn!("Webhook delivery failed: {}", e),
+                }
+            }
+        },
+    );
+    spawn_periodic(
+        "outbox_dispatch",
+        Duration::from_secs(OUTBOX_DISPATCH_INTERVAL_SECS),
+        move || {
+            let outbox_service = outbox_service.clone();
+            async move {
+                match outbox_service.dispatch_due().await {
+                    Ok(count) => tracing::debug!("Dispatched {} outbox events", count),
+                    Err(e) => tracing::warn!("Outbox dispatch failed: {}", e),
+                }
+            }
+        },
+    );
+    // Picks up blocks added or lifted by other instances
+    let blocklist_service = ip_block_service.clone();
+    spawn_periodic(
+        "ip_blocklist_reload",
+        Duration::from_secs(IP_BLOCKLIST_RELOAD_INTERVAL_SECS),
+        move || {
+            let ip_block_service = blocklist_service.clone();
+            async move {
+                if let Err(e) = ip_block_service.reload().await {
+                    tracing::warn!("IP blocklist reload failed: {}", e);
+                }
+            }
+        },
+    );
+    // Picks up submissions approved on other instances
+    spawn_periodic(
+        "promoted_suggestions_reload",
+        Duration::from_secs(PROMOTED_SUGGESTIONS_RELOAD_INTERVAL_SECS),
+        move || {
+            let suggestion_moderation_service = suggestion_moderation_service.clone();
+            async move {
+                if let Err(e) = suggestion_moderation_service.reload().await {
+                    tracing::warn!("Promoted suggestions reload failed: {}", e);
+                }
+            }
+        },
+    );
+    // Each run sends to users whose Monday has begun since the last one
+    spawn_periodic(
+        "weekly_summaries",
+        Duration::from_secs(WEEKLY_SUMMARY_INTERVAL_SECS),
+        move || {
+            let weekly_summary_service = weekly_summary_service.clone();
+            async move {
+                match weekly_summary_service.send_weekly_summaries().await {
+                    Ok(count) => tracing::debug!("Sent {} weekly summaries", count),
+                    Err(e) => tracing::warn!("Weekly summaries failed: {}", e),
+                }
+            }
+        },
+    );
+    if config.usage_flush_interval_ms > 0 {
+        let usage_repo = usage_repo.clone();
+        spawn_periodic(
+            "usage_flush",
+            Duration::from_millis(config.usage_flush_interval_ms),
+            move || {
+                let usage_repo = usage_repo.clone();
+                async move {
+                    if let Err(e) = usage_repo.flush_pending().await {
+                        tracing::warn!("Usage flush failed: {}", e);
+                    }
+                }
+            },
+        );
+    }
+    let maintenance_task = Arc::new(MaintenanceTask::new(
+        refresh_token_repo,
+        idempotency_repo.clone(),
+        usage_repo.clone(),
+        webhook_repo,
+        ip_block_repo,
+        outbox_repo,
+        audit_log_repo,
+        oauth_state_repo,
+        audio_cdn,
+        config.retention.clone(),
+    ));
+    spawn_periodic(
+        "maintenance",
+        Duration::from_secs(config.maintenance_interval_secs),
+        move || {
+            let maintenance_task = maintenance_task.clone();
+            async move {
+                maintenance_task.run().await;
+            }
+        },
+    );
 
     // Start HTTP server with all routes
     start_http_server(
-        pool,
         config,
         user_repo,
+        jwt_manager,
+        auth_user_cache,
+        token_denylist,
+        api_key_repo,
+        idempotency_repo,
+        ip_block_service,
         auth_controller,
         oauth_controller,
         feed_controller,
         feed_suggestions_controller,
+        mute_controller,
+        graphql_controller,
+        subscription_controller,
         user_controller,
         tts_controller,
+        admin_controller,
+        admin_dashboard_controller,
+        referral_controller,
+        family_controller,
+        device_controller,
+        analytics_controller,
+        webhook_controller,
+        events_controller,
+        ip_block_controller,
+        weekly_summary_controller,
+        api_key_controller,
+        health_controller,
     )
     .await?;
 
+    // The server only returns once it has drained, so nothing is added after this
+    match usage_repo.flush_pending().await {
+        Ok(count) => tracing::info!("Flushed {} buffered usage entries", count),
+        Err(e) => tracing::error!("Final usage flush failed: {}", e),
+    }
+
     Ok(())
 }
 
@@ -182,3 +773,143 @@ fn init_logging(config: &Config) {
             .init();
     }
 }
+
+/// What to do, from the first command-line argument
+#[derive(Debug, PartialEq)]
+enum Command {
+    /// Run the API server and background jobs (the default)
+    Serve,
+    /// Load demo data and exit
+    Seed,
+}
+
+impl Command {
+    fn from_args() -> Result<Self, String> {
+        match std::env::args().nth(1).as_deref() {
+            None | Some("serve") => Ok(Command::Serve),
+            Some("seed") => Ok(Command::Seed),
+            Some(other) => Err(format!(
+                "Unknown command '{}'; expected 'serve' or 'seed'",
+                other
+            )),
+        }
+    }
+}
+
+/// Compare the database's migrations with the ones embedded in this build, so new code
+/// doesn't serve traffic against an old schema
+async fn check_schema(
+    pool: &feedtape_backend::infrastructure::db::DbPool,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let problem = match migration_status(pool).await {
+        Ok(status) => {
+            tracing::info!(
+                applied_version = ?status.applied_version,
+                expected_version = ?status.expected_version,
+                "Database schema version"
+            );
+            if !status.unknown.is_empty() {
+                tracing::warn!(
+                    unknown = ?status.unknown,
+                    "Database has migrations this build doesn't know about"
+                );
+            }
+            if status.is_current() {
+                return Ok(());
+            }
+            format!("migrations not applied: {:?}", status.pending)
+        }
+        Err(e) => format!("could not read the applied migrations: {}", e),
+    };
+
+    match config.schema_check {
+        SchemaCheck::Enforce => {
+            tracing::error!(problem = %problem, "Database schema is behind this build");
+            Err(format!(
+                "Database schema is behind this build ({}); run the migrations first",
+                problem
+            )
+            .into())
+        }
+        SchemaCheck::Warn => {
+            tracing::warn!(
+                problem = %problem,
+                "Database schema is behind this build, serving anyway"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Make a real Polly call so missing or wrong credentials show up at startup rather than
+/// on the first synthesis. Fatal in staging and production; development and test carry
+/// on with a warning.
+async fn check_polly_credentials(
+    polly_client: &aws_sdk_polly::Client,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use aws_smithy_types::error::display::DisplayErrorContext;
+
+    let request = polly_client
+        .describe_voices()
+        .language_code(aws_sdk_polly::types::LanguageCode::EnUs)
+        .send();
+    let error = match tokio::time::timeout(
+        Duration::from_secs(POLLY_STARTUP_CHECK_TIMEOUT_SECS),
+        request,
+    )
+    .await
+    {
+        Ok(Ok(_)) => {
+            tracing::info!("AWS credentials verified against Polly");
+            return Ok(());
+        }
+        Ok(Err(e)) => DisplayErrorContext(e).to_string(),
+        Err(_) => format!(
+            "no answer within {} seconds",
+            POLLY_STARTUP_CHECK_TIMEOUT_SECS
+        ),
+    };
+
+    if !config.environment.is_deployed() {
+        tracing::warn!(
+            error = %error,
+            "AWS Polly credential check failed; TTS requests will fail until it passes"
+        );
+        Ok(())
+    } else {
+        tracing::error!(error = %error, "AWS Polly credential check failed");
+        Err(format!("AWS Polly credential check failed: {}", error).into())
+    }
+}
+
+async fn seed(
+    pool: feedtape_backend::infrastructure::db::DbPool,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use feedtape_backend::infrastructure::db::seed::Seeder;
+    use feedtape_backend::infrastructure::repositories::{
+        FeedRepository, RefreshTokenRepository, UsageRepository, UserRepository,
+    };
+
+    let pool = Arc::new(pool);
+    let seeder = Seeder::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeedRepository::new(pool.clone())),
+        Arc::new(UsageRepository::new(pool.clone())),
+        Arc::new(RefreshTokenRepository::new(pool)),
+        config.refresh_token_expiration_days,
+    );
+    let report = seeder.run().await?;
+    tracing::info!(
+        users = report.users,
+        existing_users = report.existing_users,
+        feeds = report.feeds,
+        usage_days = report.usage_days,
+        refresh_tokens = report.refresh_tokens,
+        "Seeding finished"
+    );
+
+    Ok(())
+}
@@ -32,10 +32,39 @@ impl TestClient {
             .await
     }
 
+    pub async fn get_with_headers(
+        &self,
+        path: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<ApiResponse> {
+        self.request_with_headers::<()>(Method::GET, path, None, None, headers)
+            .await
+    }
+
+    pub async fn get_with_auth_and_headers(
+        &self,
+        path: &str,
+        token: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<ApiResponse> {
+        self.request_with_headers::<()>(Method::GET, path, None, Some(token), headers)
+            .await
+    }
+
     pub async fn post<T: Serialize>(&self, path: &str, body: &T) -> Result<ApiResponse> {
         self.request(Method::POST, path, Some(body), None).await
     }
 
+    pub async fn post_with_headers<T: Serialize>(
+        &self,
+        path: &str,
+        body: &T,
+        headers: &[(&str, &str)],
+    ) -> Result<ApiResponse> {
+        self.request_with_headers(Method::POST, path, Some(body), None, headers)
+            .await
+    }
+
     pub async fn post_with_auth<T: Serialize>(
         &self,
         path: &str,
@@ -46,6 +75,17 @@ impl TestClient {
             .await
     }
 
+    pub async fn post_with_auth_and_headers<T: Serialize>(
+        &self,
+        path: &str,
+        body: &T,
+        token: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<ApiResponse> {
+        self.request_with_headers(Method::POST, path, Some(body), Some(token), headers)
+            .await
+    }
+
     #[allow(dead_code)]
     pub async fn patch<T: Serialize>(&self, path: &str, body: &T) -> Result<ApiResponse> {
         self.request(Method::PATCH, path, Some(body), None).await
@@ -61,6 +101,27 @@ impl TestClient {
             .await
     }
 
+    pub async fn patch_with_auth_and_headers<T: Serialize>(
+        &self,
+        path: &str,
+        body: &T,
+        token: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<ApiResponse> {
+        self.request_with_headers(Method::PATCH, path, Some(body), Some(token), headers)
+            .await
+    }
+
+    pub async fn put_with_auth<T: Serialize>(
+        &self,
+        path: &str,
+        body: &T,
+        token: &str,
+    ) -> Result<ApiResponse> {
+        self.request(Method::PUT, path, Some(body), Some(token))
+            .await
+    }
+
     pub async fn delete(&self, path: &str) -> Result<ApiResponse> {
         self.request::<()>(Method::DELETE, path, None, None).await
     }
@@ -70,12 +131,51 @@ impl TestClient {
             .await
     }
 
+    pub async fn delete_with_auth_and_headers(
+        &self,
+        path: &str,
+        token: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<ApiResponse> {
+        self.request_with_headers::<()>(Method::DELETE, path, None, Some(token), headers)
+            .await
+    }
+
+    /// GET a streaming endpoint and return as soon as the headers arrive, leaving the body
+    /// to be read frame by frame
+    pub async fn open_stream(
+        &self,
+        path: &str,
+        token: &str,
+    ) -> Result<Response<hyper::body::Incoming>> {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(format!("{}{}", self.base_url, path))
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "text/event-stream")
+            .body(Full::new(Bytes::new()))?;
+
+        Ok(self.client.request(request).await?)
+    }
+
     async fn request<T: Serialize>(
         &self,
         method: Method,
         path: &str,
         body: Option<&T>,
         auth_token: Option<&str>,
+    ) -> Result<ApiResponse> {
+        self.request_with_headers(method, path, body, auth_token, &[])
+            .await
+    }
+
+    async fn request_with_headers<T: Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&T>,
+        auth_token: Option<&str>,
+        headers: &[(&str, &str)],
     ) -> Result<ApiResponse> {
         let url = format!("{}{}", self.base_url, path);
         let mut req_builder = Request::builder().method(method).uri(&url);
@@ -83,6 +183,9 @@ impl TestClient {
         if let Some(token) = auth_token {
             req_builder = req_builder.header("Authorization", format!("Bearer {}", token));
         }
+        for (name, value) in headers {
+            req_builder = req_builder.header(*name, *value);
+        }
 
         let body_bytes = if let Some(body) = body {
             req_builder = req_builder.header("Content-Type", "application/json");
@@ -155,9 +258,10 @@ impl ApiResponse {
         let message = self
             .body
             .as_ref()
-            .and_then(|b| b.get("message"))
+            .and_then(|b| b.get("error"))
+            .and_then(|e| e.get("message"))
             .and_then(|m| m.as_str())
-            .expect("Missing message field in error response");
+            .expect("Missing error.message field in error response");
 
         assert!(
             message.contains(expected_message),
@@ -168,6 +272,23 @@ impl ApiResponse {
         self
     }
 
+    /// Assert that the error response has the expected code and a request ID
+    pub fn assert_error_code(&self, expected_code: &str) -> &Self {
+        let body = self.body.as_ref().expect("Missing error response body");
+        let code = body
+            .get("error")
+            .and_then(|e| e.get("code"))
+            .and_then(|c| c.as_str())
+            .expect("Missing error.code field in error response");
+
+        assert_eq!(code, expected_code, "Unexpected error code. Body: {}", body);
+        assert!(
+            body.get("request_id").and_then(|r| r.as_str()).is_some(),
+            "Missing request_id in error response"
+        );
+        self
+    }
+
     #[allow(dead_code)]
     pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
         Ok(serde_json::from_slice(&self.body_bytes)?)
@@ -1,3 +1,4 @@
+use aws_sdk_polly::config::Credentials;
 use aws_sdk_polly::Client as PollyClient;
 
 pub async fn create_mock_polly_client() -> PollyClient {
@@ -11,6 +12,18 @@ pub async fn create_mock_polly_client() -> PollyClient {
     PollyClient::from_conf(config)
 }
 
+/// Polly client for a stub server, so readiness probes get an answer
+pub fn create_stub_polly_client(endpoint: &str) -> PollyClient {
+    let config = aws_sdk_polly::Config::builder()
+        .behavior_version(aws_sdk_polly::config::BehaviorVersion::latest())
+        .region(aws_sdk_polly::config::Region::new("us-east-1"))
+        .credentials_provider(Credentials::new("test", "test", None, None, "e2e"))
+        .endpoint_url(endpoint)
+        .build();
+
+    PollyClient::from_conf(config)
+}
+
 #[allow(dead_code)]
 pub fn mock_audio_bytes() -> Vec<u8> {
     // Minimal valid MP3 file (silence)
@@ -121,7 +121,7 @@ impl DatabasePool {
         }
 
         // Clean the database and return to available pool
-        if let Ok(_) = self.cleanup_database(&db_name).await {
+        if self.cleanup_database(&db_name).await.is_ok() {
             let mut available = self.available.write();
             available.push_back(db_name);
         }
@@ -142,9 +142,11 @@ impl DatabasePool {
             .await?;
 
         // Truncate all tables to clean the database
-        sqlx::query("TRUNCATE TABLE feeds, users, refresh_tokens, usage_tracking CASCADE")
-            .execute(&pool)
-            .await?;
+        sqlx::query(
+            "TRUNCATE TABLE feeds, users, refresh_tokens, usage_tracking, audit_log, synthesis_stats, ip_blocks CASCADE",
+        )
+        .execute(&pool)
+        .await?;
 
         pool.close().await;
 
@@ -186,7 +188,7 @@ impl DatabasePoolRef {
             {
                 // Try to clean - if it fails, just don't reuse the database
                 if sqlx::query(
-                    "TRUNCATE TABLE feeds, users, refresh_tokens, usage_tracking CASCADE",
+                    "TRUNCATE TABLE feeds, users, refresh_tokens, usage_tracking, audit_log, synthesis_stats, ip_blocks CASCADE",
                 )
                 .execute(&pool)
                 .await
@@ -1,8 +1,8 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use feedtape_backend::domain::{
     feed::model::Feed,
-    user::model::{SubscriptionStatus, SubscriptionTier, User, UserSettings},
+    user::model::{SubscriptionStatus, SubscriptionTier, User, UserRole, UserSettings},
 };
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -22,10 +22,22 @@ impl TestFixtures {
             email: email.to_string(),
             oauth_provider: "google".to_string(),
             oauth_provider_id: format!("provider_{}", Uuid::new_v4()),
-            settings: serde_json::to_value(&UserSettings::default())?,
+            display_name: None,
+            avatar_url: None,
+            settings: serde_json::to_value(UserSettings::default())?,
             subscription_tier: SubscriptionTier::Free,
             subscription_status: SubscriptionStatus::Active,
             subscription_expires_at: None,
+            trial_started_at: None,
+            trial_ends_at: None,
+            deletion_scheduled_at: None,
+            deleted_at: None,
+            limit_overrides: None,
+            referral_code: None,
+            referral_bonus_until: None,
+            family_owner_id: None,
+            role: UserRole::User,
+            family_tier: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -40,16 +52,16 @@ impl TestFixtures {
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             "#,
         )
-        .bind(&user.id)
+        .bind(user.id)
         .bind(&user.email)
         .bind(&user.oauth_provider)
         .bind(&user.oauth_provider_id)
         .bind(&user.settings)
-        .bind(&user.subscription_tier.to_string())
-        .bind(&user.subscription_status.to_string())
-        .bind(&user.subscription_expires_at)
-        .bind(&user.created_at)
-        .bind(&user.updated_at)
+        .bind(user.subscription_tier.to_string())
+        .bind(user.subscription_status.to_string())
+        .bind(user.subscription_expires_at)
+        .bind(user.created_at)
+        .bind(user.updated_at)
         .execute(&self.pool)
         .await?;
 
@@ -62,10 +74,22 @@ impl TestFixtures {
             email: email.to_string(),
             oauth_provider: "google".to_string(),
             oauth_provider_id: format!("provider_{}", Uuid::new_v4()),
-            settings: serde_json::to_value(&UserSettings::default())?,
+            display_name: None,
+            avatar_url: None,
+            settings: serde_json::to_value(UserSettings::default())?,
             subscription_tier: SubscriptionTier::Pro,
             subscription_status: SubscriptionStatus::Active,
             subscription_expires_at: Some(Utc::now() + chrono::Duration::days(30)),
+            trial_started_at: None,
+            trial_ends_at: None,
+            deletion_scheduled_at: None,
+            deleted_at: None,
+            limit_overrides: None,
+            referral_code: None,
+            referral_bonus_until: None,
+            family_owner_id: None,
+            role: UserRole::User,
+            family_tier: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -80,16 +104,16 @@ impl TestFixtures {
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             "#,
         )
-        .bind(&user.id)
+        .bind(user.id)
         .bind(&user.email)
         .bind(&user.oauth_provider)
         .bind(&user.oauth_provider_id)
         .bind(&user.settings)
-        .bind(&user.subscription_tier.to_string())
-        .bind(&user.subscription_status.to_string())
-        .bind(&user.subscription_expires_at)
-        .bind(&user.created_at)
-        .bind(&user.updated_at)
+        .bind(user.subscription_tier.to_string())
+        .bind(user.subscription_status.to_string())
+        .bind(user.subscription_expires_at)
+        .bind(user.created_at)
+        .bind(user.updated_at)
         .execute(&self.pool)
         .await?;
 
@@ -103,6 +127,11 @@ impl TestFixtures {
             url: url.to_string(),
             title: title.map(|s| s.to_string()),
             created_at: Utc::now(),
+            avg_articles_per_day: None,
+            last_published_at: None,
+            stats_updated_at: None,
+            suggestion_id: None,
+            category_id: None,
         };
 
         sqlx::query(
@@ -111,11 +140,11 @@ impl TestFixtures {
             VALUES ($1, $2, $3, $4, $5)
             "#,
         )
-        .bind(&feed.id)
-        .bind(&feed.user_id)
+        .bind(feed.id)
+        .bind(feed.user_id)
         .bind(&feed.url)
         .bind(&feed.title)
-        .bind(&feed.created_at)
+        .bind(feed.created_at)
         .execute(&self.pool)
         .await?;
 
@@ -162,15 +191,17 @@ impl TestFixtures {
         Ok(())
     }
 
+    /// Add usage for today, with audio as long as the characters take to read
     pub async fn add_tts_usage(&self, user_id: Uuid, characters: i32, articles: i32) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO usage_tracking (id, user_id, characters_used, articles_synthesized, date, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO usage_tracking (id, user_id, characters_used, articles_synthesized, audio_seconds, date, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $8, $5, $6, $7)
             ON CONFLICT (user_id, date)
             DO UPDATE SET
                 characters_used = usage_tracking.characters_used + EXCLUDED.characters_used,
                 articles_synthesized = usage_tracking.articles_synthesized + EXCLUDED.articles_synthesized,
+                audio_seconds = usage_tracking.audio_seconds + EXCLUDED.audio_seconds,
                 updated_at = EXCLUDED.updated_at
             "#,
         )
@@ -181,6 +212,82 @@ impl TestFixtures {
         .bind(Utc::now().date_naive())
         .bind(Utc::now())
         .bind(Utc::now())
+        .bind(reading_seconds(characters))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record a past day of usage with the rollover balance that was carried into it
+    pub async fn add_tts_usage_on(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+        characters: i32,
+        rollover_characters: Option<i32>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO usage_tracking (id, user_id, characters_used, articles_synthesized, rollover_characters, audio_seconds, date, created_at, updated_at)
+            VALUES ($1, $2, $3, 1, $4, $7, $5, $6, $6)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(characters)
+        .bind(rollover_characters)
+        .bind(date)
+        .bind(Utc::now())
+        .bind(reading_seconds(characters))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Count syntheses served by a provider today in the admin statistics
+    pub async fn add_synthesis_stats(
+        &self,
+        provider: &str,
+        requests: i32,
+        characters: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO synthesis_stats (date, provider, requests, characters)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(Utc::now().date_naive())
+        .bind(provider)
+        .bind(requests)
+        .bind(characters)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn create_promo_code(
+        &self,
+        code: &str,
+        duration_days: i32,
+        max_redemptions: Option<i32>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO promo_codes
+                (code, duration_days, max_redemptions, redemption_count, expires_at, created_by, created_at)
+            VALUES ($1, $2, $3, 0, $4, 'admin@example.com', $5)
+            "#,
+        )
+        .bind(code)
+        .bind(duration_days)
+        .bind(max_redemptions)
+        .bind(expires_at)
+        .bind(Utc::now())
         .execute(&self.pool)
         .await?;
 
@@ -195,73 +302,85 @@ impl TestFixtures {
         Ok(count.0)
     }
 
-    #[allow(dead_code)]
-    pub async fn get_user_by_id(&self, user_id: Uuid) -> Result<Option<User>> {
-        let user = sqlx::query_as::<
-            _,
-            (
-                Uuid,
-                String,
-                String,
-                String,
-                serde_json::Value,
-                String,
-                String,
-                Option<DateTime<Utc>>,
-                DateTime<Utc>,
-                DateTime<Utc>,
-            ),
-        >(
-            r#"
-            SELECT id, email, oauth_provider, oauth_provider_id, settings,
-                   subscription_tier, subscription_status, subscription_expires_at,
-                   created_at, updated_at
-            FROM users
-            WHERE id = $1
-            "#,
-        )
-        .bind(user_id)
-        .fetch_optional(&self.pool)
-        .await?;
+    pub async fn get_usage_day_count(&self, user_id: Uuid) -> Result<i64> {
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM usage_tracking WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(count.0)
+    }
 
-        if let Some((
-            id,
-            email,
-            oauth_provider,
-            oauth_provider_id,
-            settings,
-            subscription_tier,
-            subscription_status,
-            subscription_expires_at,
-            created_at,
-            updated_at,
-        )) = user
-        {
-            let tier = match subscription_tier.as_str() {
-                "pro" => SubscriptionTier::Pro,
-                _ => SubscriptionTier::Free,
-            };
+    pub async fn get_usage_history_purge_count(&self, user_id: Uuid) -> Result<i64> {
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM usage_history_purges WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(count.0)
+    }
 
-            let status = match subscription_status.as_str() {
-                "expired" => SubscriptionStatus::Expired,
-                "cancelled" => SubscriptionStatus::Cancelled,
-                _ => SubscriptionStatus::Active,
-            };
+    pub async fn set_subscription_status(
+        &self,
+        user_id: Uuid,
+        status: SubscriptionStatus,
+    ) -> Result<()> {
+        sqlx::query("UPDATE users SET subscription_status = $1 WHERE id = $2")
+            .bind(status.to_string())
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
 
-            Ok(Some(User {
-                id,
-                email,
-                oauth_provider,
-                oauth_provider_id,
-                settings,
-                subscription_tier: tier,
-                subscription_status: status,
-                subscription_expires_at,
-                created_at,
-                updated_at,
-            }))
-        } else {
-            Ok(None)
-        }
+    pub async fn set_trial(
+        &self,
+        user_id: Uuid,
+        started_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE users SET trial_started_at = $1, trial_ends_at = $2 WHERE id = $3")
+            .bind(started_at)
+            .bind(ends_at)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_created_at(&self, user_id: Uuid, created_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE users SET created_at = $1 WHERE id = $2")
+            .bind(created_at)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
     }
+
+    pub async fn set_deletion_schedule(
+        &self,
+        user_id: Uuid,
+        scheduled_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE users SET deletion_scheduled_at = $1 WHERE id = $2")
+            .bind(scheduled_at)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_user_by_id(&self, user_id: Uuid) -> Result<Option<User>> {
+        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(user)
+    }
+}
+
+/// Seconds reading `characters` takes at 1000 characters a minute
+fn reading_seconds(characters: i32) -> f64 {
+    f64::from(characters) * 60.0 / 1000.0
 }
@@ -1,15 +1,22 @@
 use anyhow::Result;
 use axum::Router;
 use chrono::{DateTime, Utc};
-use feedtape_backend::infrastructure::config::{Config, Environment, LogFormat};
+use feedtape_backend::domain::plan::PlanLimits;
+use feedtape_backend::domain::tts::TtsMetrics;
+use feedtape_backend::infrastructure::config::{
+    AuthFailureBlockConfig, CacheBackend, ClientIpHeader, Config, DatabaseConnectConfig,
+    EmailBackend, EmailConfig, Environment, JwtSigning, LogFormat, RetentionConfig, SchemaCheck,
+};
 use once_cell::sync::Lazy;
 use sqlx::PgPool;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use test_context::AsyncTestContext;
 use testcontainers::{clients::Cli, Container};
 use testcontainers_modules::postgres::Postgres;
 use tokio::net::TcpListener;
 use uuid::Uuid;
+use wiremock::MockServer;
 
 pub mod api_client;
 pub mod aws_mocks;
@@ -24,7 +31,7 @@ use fixtures::TestFixtures;
 static DOCKER: Lazy<Cli> = Lazy::new(Cli::default);
 
 // Shared PostgreSQL container for all tests
-static SHARED_CONTAINER: Lazy<SharedContainer> = Lazy::new(|| SharedContainer::new());
+static SHARED_CONTAINER: Lazy<SharedContainer> = Lazy::new(SharedContainer::new);
 
 // Global database pool
 static DB_POOL: Lazy<DatabasePool> = Lazy::new(|| DatabasePool::new(SHARED_CONTAINER.port));
@@ -55,96 +62,248 @@ pub struct TestContext {
     pub pool: PgPool,
     pub config: Config,
     pub fixtures: TestFixtures,
+    /// Stands in for github.com and api.github.com
+    pub github: MockServer,
+    /// Stands in for Polly in readiness probes
+    pub polly: MockServer,
     _db: PooledDatabase,
 }
 
 impl AsyncTestContext for TestContext {
-    fn setup() -> impl std::future::Future<Output = Self> + Send {
-        async {
-            // Get a database from the shared pool
-            let pooled_db = DB_POOL
-                .get_database()
-                .await
-                .expect("Failed to get database from pool");
-
-            // Create test configuration
-            let config = Config {
-                database_url: pooled_db.database_url.clone(),
-                host: "127.0.0.1".to_string(),
-                port: 0, // Will be assigned by the OS
-                jwt_secret: "test-jwt-secret-key-for-testing-only".to_string(),
-                jwt_expiration_hours: 1,
-                refresh_token_expiration_days: 30,
-                aws_region: "us-east-1".to_string(),
-                environment: Environment::Development,
-                log_format: LogFormat::Pretty,
-                github_client_id: "test_github_client_id".to_string(),
-                github_client_secret: "test_github_client_secret".to_string(),
-                github_redirect_uri: "http://localhost:8080/auth/callback/github".to_string(),
-                tts_cache_enabled: false, // Disable cache in tests to avoid test pollution
-            };
-
-            // Create app with mocked AWS
-            let app = create_app_with_mocked_aws(config.clone(), pooled_db.pool.clone())
-                .await
-                .expect("Failed to create app");
-
-            // Start server
-            let listener = TcpListener::bind("127.0.0.1:0")
-                .await
-                .expect("Failed to bind listener");
-            let addr = listener.local_addr().expect("Failed to get local addr");
-            let base_url = format!("http://{}", addr);
-
-            tokio::spawn(async move {
-                axum::serve(listener, app).await.unwrap();
-            });
-
-            // Wait for server to be ready
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-
-            // Create test client and fixtures
-            let client = TestClient::new(&base_url);
-            let fixtures = TestFixtures::new(pooled_db.pool.clone());
-
-            Self {
-                client,
-                pool: pooled_db.pool.clone(),
-                config,
-                fixtures,
-                _db: pooled_db,
-            }
+    async fn setup() -> Self {
+        // Get a database from the shared pool
+        let pooled_db = DB_POOL
+            .get_database()
+            .await
+            .expect("Failed to get database from pool");
+
+        let github = MockServer::start().await;
+        let polly = MockServer::start().await;
+
+        // Create test configuration
+        let config = Config {
+            database_url: pooled_db.database_url.clone(),
+            database_read_url: None,
+            database_connect: DatabaseConnectConfig {
+                retries: 0,
+                backoff_ms: 500,
+                max_wait_secs: 0,
+            },
+            schema_check: SchemaCheck::Enforce,
+            host: "127.0.0.1".to_string(),
+            port: 0, // Will be assigned by the OS
+            jwt_secret: "test-jwt-secret-key-for-testing-only".to_string(),
+            jwt_expiration_hours: 1,
+            jwt_signing: JwtSigning::Hs256,
+            refresh_token_expiration_days: 30,
+            aws_region: "us-east-1".to_string(),
+            environment: Environment::Test,
+            log_format: LogFormat::Pretty,
+            secure_cookies: false,
+            github_client_id: "test_github_client_id".to_string(),
+            github_client_secret: "test_github_client_secret".to_string(),
+            github_redirect_uri: "http://localhost:8080/auth/callback/github".to_string(),
+            github_oauth_base_url: github.uri(),
+            github_api_base_url: github.uri(),
+            cache_backend: CacheBackend::Memory,
+            tts_cache_enabled: false, // Disable cache in tests to avoid test pollution
+            audio_cdn: None,
+            // Tests edit users in the database directly, which the cache wouldn't see
+            auth_user_cache_secs: 0,
+            google_play_package_name: None,
+            google_play_service_account_key: None,
+            stripe_secret_key: None,
+            stripe_webhook_secret: None,
+            stripe_price_id: None,
+            stripe_checkout_success_url: None,
+            stripe_checkout_cancel_url: None,
+            apple_bundle_id: None,
+            plan_limits: PlanLimits {
+                rollover_cap_multiplier: 2,
+                ..PlanLimits::default()
+            },
+            admin_emails: vec!["admin@example.com".to_string()],
+            // The test client connects over loopback, standing in for the load balancer
+            trusted_proxies: vec!["127.0.0.1/32".parse().unwrap()],
+            client_ip_header: ClientIpHeader::XForwardedFor,
+            listen_socket: None,
+            tls: None,
+            grpc: None,
+            max_concurrent_requests: 256,
+            max_concurrent_syntheses: 32,
+            access_log: None,
+            auth_failure_block: Some(AuthFailureBlockConfig {
+                threshold: 10,
+                window_secs: 600,
+                block_secs: 3600,
+            }),
+            maintenance_interval_secs: 3600,
+            retention: RetentionConfig {
+                usage_days: 365,
+                audit_log_days: Some(365),
+                article_days: 1,
+                cached_audio_days: 1,
+            },
+            usage_flush_interval_ms: 0,
+            dev_fake_externals: false,
+            dev_fake_github_oauth: false,
+            email: EmailConfig {
+                backend: EmailBackend::Log,
+                from: "FeedTape <no-reply@feedtape.app>".to_string(),
+                link_base_url: "http://localhost".to_string(),
+            },
+            analytics_sample_rate: 1.0,
+        };
+
+        // Create app with mocked AWS
+        let app = create_app_with_mocked_aws(config.clone(), pooled_db.pool.clone(), &polly.uri())
+            .await
+            .expect("Failed to create app");
+
+        // Start server
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind listener");
+        let addr = listener.local_addr().expect("Failed to get local addr");
+        let base_url = format!("http://{}", addr);
+
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap();
+        });
+
+        // Wait for server to be ready
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        // Create test client and fixtures
+        let client = TestClient::new(&base_url);
+        let fixtures = TestFixtures::new(pooled_db.pool.clone());
+
+        Self {
+            client,
+            pool: pooled_db.pool.clone(),
+            config,
+            fixtures,
+            github,
+            polly,
+            _db: pooled_db,
         }
     }
 
-    fn teardown(self) -> impl std::future::Future<Output = ()> + Send {
-        async {
-            // Database cleanup happens automatically via Drop on PooledDatabase
-        }
+    async fn teardown(self) {
+        // Database cleanup happens automatically via Drop on PooledDatabase
     }
 }
 
-async fn create_app_with_mocked_aws(config: Config, pool: PgPool) -> Result<Router> {
-    use axum::{middleware, routing::get};
+impl TestContext {
+    /// Nothing runs the outbox dispatcher in tests; call this to hand pending events to
+    /// the webhook and audit log subscribers. Returns how many events were dispatched.
+    #[allow(dead_code)]
+    pub async fn dispatch_outbox(&self) -> usize {
+        use feedtape_backend::{
+            domain::audit::AuditService,
+            domain::events::{AuditSubscriber, WebhookSubscriber},
+            domain::outbox::{OutboxService, OutboxServiceApi},
+            domain::webhook::WebhookService,
+            infrastructure::repositories::{
+                AuditLogRepository, OutboxRepository, WebhookRepository,
+            },
+            infrastructure::webhooks::WebhookSender,
+        };
+
+        let pool = Arc::new(self.pool.clone());
+        let webhook_service = Arc::new(WebhookService::new(
+            Arc::new(WebhookRepository::new(pool.clone())),
+            Arc::new(WebhookSender::new()),
+        ));
+        let audit_service = Arc::new(AuditService::new(Arc::new(AuditLogRepository::new(
+            pool.clone(),
+        ))));
+        let outbox_service = OutboxService::new(
+            Arc::new(OutboxRepository::new(pool)),
+            vec![
+                Arc::new(WebhookSubscriber::new(webhook_service)),
+                Arc::new(AuditSubscriber::new(audit_service)),
+            ],
+        );
+
+        outbox_service
+            .dispatch_due()
+            .await
+            .expect("Failed to dispatch outbox events")
+    }
+}
+
+async fn create_app_with_mocked_aws(
+    config: Config,
+    pool: PgPool,
+    polly_probe_endpoint: &str,
+) -> Result<Router> {
+    use axum::{http::StatusCode, middleware, routing::get};
     use feedtape_backend::{
         controllers::{
-            auth::AuthController, feed::FeedController,
-            feed_suggestions::FeedSuggestionsController, health, oauth::OAuthController,
-            tts::TtsController, user::UserController,
+            admin::AdminController, admin_dashboard::AdminDashboardController,
+            analytics::AnalyticsController, api_key::ApiKeyController, auth::AuthController,
+            device::DeviceController, events::EventsController, family::FamilyController,
+            feed::FeedController, feed_suggestions::FeedSuggestionsController,
+            graphql::GraphQLController, health, ip_block::IpBlockController, mute::MuteController,
+            oauth::OAuthController, referral::ReferralController,
+            subscription::SubscriptionController, tts::TtsController, user::UserController,
+            webhook::WebhookController, weekly_summary::WeeklySummaryController,
         },
         domain::{
-            auth::AuthService, feed::FeedService, feed_suggestions::FeedSuggestionsService,
-            tts::TtsService, user::UserService,
+            admin::AdminService,
+            analytics::AnalyticsService,
+            api_key::ApiKeyService,
+            audit::AuditService,
+            auth::{AuthService, JwtManager},
+            backup::BackupService,
+            device::DeviceService,
+            events::{AuditSubscriber, InProcessEventBus, WebhookSubscriber},
+            family::FamilyService,
+            feed::FeedService,
+            feed_suggestions::{FeedSuggestionsService, SuggestionModerationService},
+            ip_block::IpBlockService,
+            mute::MuteService,
+            privacy::PrivacyService,
+            referral::ReferralService,
+            subscription::SubscriptionService,
+            tts::TtsService,
+            user::UserService,
+            webhook::WebhookService,
+            weekly_summary::{UnsubscribeLinks, WeeklySummaryService},
         },
         infrastructure::{
-            auth::{auth_middleware, request_id_middleware},
+            auth::{
+                admin_middleware, admin_session_middleware, auth_middleware, request_id_middleware,
+                AccessTokenDenylist, AuthUserCache,
+            },
+            cache::MemoryCacheStore,
+            email::LogEmailSender,
+            events::AccountEventStream,
+            feed_fetcher::FeedFetcher,
+            http::{
+                access_log_middleware, client_ip_middleware, concurrency_limit_middleware,
+                idempotency_middleware, ip_blocklist_middleware, AccessLogger, ForwardingTrust,
+            },
             oauth::GitHubOAuthClient,
             repositories::{
-                FeedRepository, HardcodedFeedSuggestionsRepository, RefreshTokenRepository,
-                UsageRepository, UserRepository,
+                AnalyticsEventRepository, ApiKeyRepository, AuditLogRepository, DeviceRepository,
+                FamilyRepository, FeedRepository, HardcodedFeedSuggestionsRepository,
+                IdempotencyRepository, IpBlockRepository, MuteRuleRepository, OAuthStateRepository,
+                OutboxRepository, PromoCodeRepository, ReferralRepository, RefreshTokenRepository,
+                SubscriptionEventRepository, SubscriptionPurchaseRepository,
+                SuggestionSubmissionRepository, UsageRepository, UserRepository, WebhookRepository,
+                WeeklySummaryRepository,
             },
+            webhooks::WebhookSender,
         },
     };
+    use tokio::sync::Semaphore;
     use tower_http::trace::TraceLayer;
 
     // Create mocked AWS Polly client
@@ -160,48 +319,241 @@ async fn create_app_with_mocked_aws(config: Config, pool: PgPool) -> Result<Rout
     let feed_suggestions_repo = Arc::new(HardcodedFeedSuggestionsRepository::new());
     let refresh_token_repo = Arc::new(RefreshTokenRepository::new(pool.clone()));
     let usage_repo = Arc::new(UsageRepository::new(pool.clone()));
+    let mute_rule_repo = Arc::new(MuteRuleRepository::new(pool.clone()));
+    let subscription_purchase_repo = Arc::new(SubscriptionPurchaseRepository::new(pool.clone()));
+    let referral_repo = Arc::new(ReferralRepository::new(pool.clone()));
+    let promo_code_repo = Arc::new(PromoCodeRepository::new(pool.clone()));
+    let subscription_event_repo = Arc::new(SubscriptionEventRepository::new(pool.clone()));
+    let family_repo = Arc::new(FamilyRepository::new(pool.clone()));
+    let device_repo = Arc::new(DeviceRepository::new(pool.clone()));
+    let analytics_event_repo = Arc::new(AnalyticsEventRepository::new(pool.clone()));
+    let idempotency_repo = Arc::new(IdempotencyRepository::new(pool.clone()));
+    let audit_log_repo = Arc::new(AuditLogRepository::new(pool.clone()));
+    let webhook_repo = Arc::new(WebhookRepository::new(pool.clone()));
+    let ip_block_repo = Arc::new(IpBlockRepository::new(pool.clone()));
+    let suggestion_submission_repo = Arc::new(SuggestionSubmissionRepository::new(pool.clone()));
+    let weekly_summary_repo = Arc::new(WeeklySummaryRepository::new(pool.clone()));
+    let api_key_repo = Arc::new(ApiKeyRepository::new(pool.clone()));
 
     // Instantiate OAuth clients
     let github_oauth_client = Arc::new(GitHubOAuthClient::new(
         config.github_client_id.clone(),
         config.github_client_secret.clone(),
         config.github_redirect_uri.clone(),
+        config.github_oauth_base_url.clone(),
+        config.github_api_base_url.clone(),
+    ));
+    let feed_fetcher = Arc::new(FeedFetcher::new());
+    let account_events = Arc::new(AccountEventStream::new());
+    let auth_user_cache = Arc::new(AuthUserCache::new(
+        Arc::new(MemoryCacheStore::new()),
+        std::time::Duration::from_secs(config.auth_user_cache_secs),
+    ));
+    let token_denylist = Arc::new(AccessTokenDenylist::new(
+        Arc::new(MemoryCacheStore::new()),
+        std::time::Duration::from_secs(config.jwt_expiration_hours as u64 * 3600),
     ));
 
     // Instantiate services
+    let plan_limits = Arc::new(config.plan_limits.clone());
+    let audit_service = Arc::new(AuditService::new(audit_log_repo));
+    // Nothing runs the delivery worker in tests, so deliveries stay pending
+    let webhook_service = Arc::new(WebhookService::new(
+        webhook_repo,
+        Arc::new(WebhookSender::new()),
+    ));
+    let domain_events = Arc::new(InProcessEventBus::new(vec![
+        Arc::new(WebhookSubscriber::new(webhook_service.clone())),
+        Arc::new(AuditSubscriber::new(audit_service.clone())),
+    ]));
+    let jwt_manager = Arc::new(JwtManager::from_config(&config)?);
     let auth_service = Arc::new(AuthService::new(
         user_repo.clone(),
         refresh_token_repo.clone(),
-        config.jwt_secret.clone(),
-        config.jwt_expiration_hours,
+        jwt_manager.clone(),
         config.refresh_token_expiration_days,
+        token_denylist.clone(),
+        domain_events.clone(),
+    ));
+    let ip_block_service = Arc::new(IpBlockService::new(
+        ip_block_repo,
+        Arc::new(MemoryCacheStore::new()),
+        audit_service.clone(),
+        config.auth_failure_block.clone(),
     ));
-    let feed_service = Arc::new(FeedService::new(feed_repo.clone(), user_repo.clone()));
-    let user_service = Arc::new(UserService::new(user_repo.clone(), usage_repo.clone()));
+    let mute_service = Arc::new(MuteService::new(mute_rule_repo.clone(), feed_repo.clone()));
+    let feed_service = Arc::new(FeedService::new(
+        feed_repo.clone(),
+        user_repo.clone(),
+        feed_fetcher.clone(),
+        feed_suggestions_repo.clone(),
+        plan_limits.clone(),
+        domain_events.clone(),
+        account_events.clone(),
+        mute_service.clone(),
+        Arc::new(MemoryCacheStore::new()),
+    ));
+    let user_service = Arc::new(UserService::new(
+        user_repo.clone(),
+        usage_repo.clone(),
+        subscription_event_repo.clone(),
+        plan_limits.clone(),
+        audit_service.clone(),
+        auth_user_cache.clone(),
+    ));
+    let tts_metrics = Arc::new(TtsMetrics::new());
     let tts_service = Arc::new(TtsService::new(
         user_repo.clone(),
         usage_repo.clone(),
         polly_client.clone(),
-        false, // Disable cache in tests
+        plan_limits.clone(),
+        None, // Disable cache in tests
+        domain_events.clone(),
+        account_events.clone(),
+        Arc::new(OutboxRepository::new(pool.clone())),
+        Arc::new(LogEmailSender::new()),
+        tts_metrics.clone(),
+        None,
+        &config.retention,
+    ));
+    let feed_suggestions_service = Arc::new(FeedSuggestionsService::new(
+        feed_suggestions_repo.clone(),
+        feed_fetcher.clone(),
+    ));
+    let suggestion_moderation_service = Arc::new(SuggestionModerationService::new(
+        suggestion_submission_repo,
+        feed_suggestions_repo.clone(),
+        user_repo.clone(),
+        Arc::new(LogEmailSender::new()),
+        audit_service.clone(),
+    ));
+    let backup_service = Arc::new(BackupService::new(
+        user_service.clone(),
+        feed_service.clone(),
+        mute_service.clone(),
+        feed_suggestions_repo,
+    ));
+    let privacy_service = Arc::new(PrivacyService::new(
+        user_repo.clone(),
+        user_service.clone(),
+        feed_repo.clone(),
+        mute_rule_repo,
+        usage_repo.clone(),
+        refresh_token_repo.clone(),
+        device_repo.clone(),
+    ));
+    let weekly_summary_service = Arc::new(WeeklySummaryService::new(
+        weekly_summary_repo,
+        usage_repo.clone(),
+        analytics_event_repo.clone(),
+        feed_repo.clone(),
+        user_service.clone(),
+        Arc::new(LogEmailSender::new()),
+        UnsubscribeLinks::new(
+            config.jwt_secret.clone(),
+            config.email.link_base_url.clone(),
+        ),
+    ));
+    let admin_service = Arc::new(AdminService::new(
+        user_repo.clone(),
+        usage_repo.clone(),
+        promo_code_repo.clone(),
+        plan_limits.clone(),
+        audit_service.clone(),
+        feed_repo.clone(),
+        auth_user_cache.clone(),
+    ));
+    let referral_service = Arc::new(ReferralService::new(
+        user_repo.clone(),
+        referral_repo,
+        plan_limits.clone(),
+    ));
+    let family_service = Arc::new(FamilyService::new(
+        user_repo.clone(),
+        family_repo,
+        auth_user_cache.clone(),
+    ));
+    let device_service = Arc::new(DeviceService::new(device_repo));
+    let analytics_service = Arc::new(AnalyticsService::new(
+        analytics_event_repo,
+        config.analytics_sample_rate,
+    ));
+    // Store integrations are not configured in tests
+    let subscription_service = Arc::new(SubscriptionService::new(
+        user_repo.clone(),
+        subscription_purchase_repo,
+        promo_code_repo,
+        subscription_event_repo,
+        None,
+        None,
+        None,
+        audit_service,
+        auth_user_cache.clone(),
     ));
-    let feed_suggestions_service = Arc::new(FeedSuggestionsService::new(feed_suggestions_repo));
 
     // Instantiate controllers
     let auth_controller = Arc::new(AuthController::new(auth_service.clone()));
     let oauth_controller = Arc::new(OAuthController::new(
         github_oauth_client,
         user_repo.clone(),
+        Arc::new(OAuthStateRepository::new(pool.clone())),
         auth_service,
+        config.secure_cookies,
+    ));
+    let feed_controller = Arc::new(FeedController::new(feed_service.clone()));
+    let user_controller = Arc::new(UserController::new(
+        user_service.clone(),
+        backup_service,
+        privacy_service,
     ));
-    let feed_controller = Arc::new(FeedController::new(feed_service));
-    let user_controller = Arc::new(UserController::new(user_service.clone()));
     let tts_controller = Arc::new(TtsController::new(
         tts_service,
-        user_service,
+        user_service.clone(),
         usage_repo.clone(),
+        plan_limits,
+    ));
+    let admin_controller = Arc::new(AdminController::new(admin_service.clone(), tts_metrics));
+    let admin_dashboard_controller = Arc::new(AdminDashboardController::new(
+        admin_service,
+        feed_suggestions_service.clone(),
+        config.secure_cookies,
+    ));
+    let referral_controller = Arc::new(ReferralController::new(referral_service));
+    let family_controller = Arc::new(FamilyController::new(family_service));
+    let device_controller = Arc::new(DeviceController::new(device_service));
+    let analytics_controller = Arc::new(AnalyticsController::new(analytics_service));
+    let webhook_controller = Arc::new(WebhookController::new(webhook_service));
+    let events_controller = Arc::new(EventsController::new(account_events));
+    let ip_block_controller = Arc::new(IpBlockController::new(ip_block_service.clone()));
+    let weekly_summary_controller = Arc::new(WeeklySummaryController::new(weekly_summary_service));
+    let api_key_controller = Arc::new(ApiKeyController::new(Arc::new(ApiKeyService::new(
+        api_key_repo.clone(),
+    ))));
+    let health_controller = Arc::new(health::HealthController::new(
+        pool.clone(),
+        Arc::new(MemoryCacheStore::new()),
+        Arc::new(aws_mocks::create_stub_polly_client(polly_probe_endpoint)),
+    ));
+    let feed_suggestions_controller = Arc::new(FeedSuggestionsController::new(
+        feed_suggestions_service,
+        suggestion_moderation_service,
     ));
-    let feed_suggestions_controller =
-        Arc::new(FeedSuggestionsController::new(feed_suggestions_service));
+    let mute_controller = Arc::new(MuteController::new(mute_service.clone()));
+    let graphql_controller = Arc::new(GraphQLController::new(
+        user_service,
+        feed_service,
+        mute_service,
+    ));
+    let subscription_controller = Arc::new(SubscriptionController::new(subscription_service));
+
+    let idempotency = middleware::from_fn_with_state(idempotency_repo, idempotency_middleware);
+    let auth_state = (
+        user_repo,
+        jwt_manager,
+        auth_user_cache,
+        token_denylist,
+        api_key_repo,
+    );
 
     // TTS routes (need auth)
     let tts_routes = Router::new()
@@ -211,16 +563,22 @@ async fn create_app_with_mocked_aws(config: Config, pool: PgPool) -> Result<Rout
         )
         .with_state(tts_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
+        ))
+        // Outermost, so shed requests don't even look up the user
+        .layer(middleware::from_fn_with_state(
+            Arc::new(Semaphore::new(config.max_concurrent_syntheses)),
+            concurrency_limit_middleware,
         ));
 
     // Usage route (needs auth)
     let usage_routes = Router::new()
         .route("/api/tts/usage", get(TtsController::get_usage))
+        .route("/api/tts/usage/export", get(TtsController::export_usage))
         .with_state(tts_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
         ));
 
@@ -231,6 +589,7 @@ async fn create_app_with_mocked_aws(config: Config, pool: PgPool) -> Result<Rout
             axum::routing::post(AuthController::refresh),
         )
         .route("/auth/logout", axum::routing::post(AuthController::logout))
+        .route("/.well-known/jwks.json", get(AuthController::jwks))
         .with_state(auth_controller.clone());
 
     // OAuth routes (public - no auth required)
@@ -242,15 +601,20 @@ async fn create_app_with_mocked_aws(config: Config, pool: PgPool) -> Result<Rout
         )
         .with_state(oauth_controller.clone());
 
-    // Logout all requires auth
+    // Logout all and session management require auth
     let auth_protected_routes = Router::new()
         .route(
             "/auth/logout/all",
             axum::routing::post(AuthController::logout_all),
         )
+        .route("/api/me/sessions", get(AuthController::list_sessions))
+        .route(
+            "/api/me/sessions/:sessionId",
+            axum::routing::delete(AuthController::revoke_session),
+        )
         .with_state(auth_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
         ));
 
@@ -260,9 +624,148 @@ async fn create_app_with_mocked_aws(config: Config, pool: PgPool) -> Result<Rout
             "/api/me",
             get(UserController::get_me).patch(UserController::update_me),
         )
+        .route("/api/me/events", get(UserController::get_events))
+        .route(
+            "/api/me/delete",
+            axum::routing::post(UserController::delete_me),
+        )
+        .route(
+            "/api/me/delete/cancel",
+            axum::routing::post(UserController::cancel_delete_me),
+        )
+        .route(
+            "/api/me/usage-history",
+            axum::routing::delete(UserController::purge_usage_history),
+        )
+        .route("/api/me/backup", get(UserController::export_backup))
+        .route(
+            "/api/me/restore",
+            axum::routing::post(UserController::restore_backup),
+        )
+        .route("/api/me/export", get(UserController::export_data))
         .with_state(user_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Referral routes (require authentication)
+    let referral_routes = Router::new()
+        .route(
+            "/auth/redeem-referral",
+            axum::routing::post(ReferralController::redeem),
+        )
+        .route("/api/me/referral", get(ReferralController::get_referral))
+        .with_state(referral_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Family plan routes (require authentication)
+    let family_routes = Router::new()
+        .route("/api/family", get(FamilyController::get_family))
+        .route(
+            "/api/family/invites",
+            axum::routing::post(FamilyController::invite),
+        )
+        .route(
+            "/api/family/invites/:code",
+            axum::routing::delete(FamilyController::revoke_invite),
+        )
+        .route(
+            "/api/family/join",
+            axum::routing::post(FamilyController::join),
+        )
+        .route(
+            "/api/family/members/:memberId",
+            axum::routing::delete(FamilyController::remove_member),
+        )
+        .route(
+            "/api/family/leave",
+            axum::routing::post(FamilyController::leave),
+        )
+        .with_state(family_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Push notification device routes (require authentication)
+    let device_routes = Router::new()
+        .route(
+            "/api/me/devices",
+            axum::routing::post(DeviceController::register_device),
+        )
+        .route(
+            "/api/me/devices/:token",
+            axum::routing::delete(DeviceController::unregister_device),
+        )
+        .with_state(device_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Analytics routes (require authentication)
+    let analytics_routes = Router::new()
+        .route(
+            "/api/analytics/events",
+            axum::routing::post(AnalyticsController::ingest_events),
+        )
+        .with_state(analytics_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Outbound webhook routes (require authentication)
+    let user_webhook_routes = Router::new()
+        .route(
+            "/api/me/webhooks",
+            get(WebhookController::list_webhooks).post(WebhookController::create_webhook),
+        )
+        .route(
+            "/api/me/webhooks/:webhookId",
+            axum::routing::delete(WebhookController::delete_webhook),
+        )
+        .route(
+            "/api/me/webhooks/:webhookId/deliveries",
+            get(WebhookController::list_deliveries),
+        )
+        .route("/v1/me/webhooks", get(WebhookController::list_webhooks_v1))
+        .route(
+            "/v1/me/webhooks/:webhookId/deliveries",
+            get(WebhookController::list_deliveries),
+        )
+        .with_state(webhook_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // API key management (requires an access token; keys can't manage keys)
+    let api_key_routes = Router::new()
+        .route(
+            "/api/me/api-keys",
+            get(ApiKeyController::list_api_keys).post(ApiKeyController::create_api_key),
+        )
+        .route(
+            "/api/me/api-keys/:apiKeyId",
+            axum::routing::delete(ApiKeyController::delete_api_key),
+        )
+        .with_state(api_key_controller)
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Account event stream (requires authentication)
+    let events_routes = Router::new()
+        .route("/api/events", get(EventsController::stream))
+        .with_state(events_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
             auth_middleware,
         ));
 
@@ -270,15 +773,21 @@ async fn create_app_with_mocked_aws(config: Config, pool: PgPool) -> Result<Rout
     let feed_routes = Router::new()
         .route(
             "/api/feeds",
-            get(FeedController::list_feeds).post(FeedController::create_feed),
+            get(FeedController::list_feeds)
+                .merge(axum::routing::post(FeedController::create_feed).layer(idempotency.clone())),
+        )
+        .route(
+            "/api/feeds/validate",
+            axum::routing::post(FeedController::validate_feeds),
         )
         .route(
             "/api/feeds/:feedId",
             axum::routing::delete(FeedController::delete_feed),
         )
+        .route("/v1/feeds", get(FeedController::list_feeds_v1))
         .with_state(feed_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
         ));
 
@@ -288,25 +797,263 @@ async fn create_app_with_mocked_aws(config: Config, pool: PgPool) -> Result<Rout
             "/api/feed-suggestions",
             get(FeedSuggestionsController::get_suggestions),
         )
+        .route(
+            "/v1/feed-suggestions",
+            get(FeedSuggestionsController::get_suggestions_v1),
+        )
+        .route(
+            "/api/feed-suggestions/submissions",
+            axum::routing::post(FeedSuggestionsController::submit),
+        )
         .with_state(feed_suggestions_controller.clone())
         .layer(middleware::from_fn_with_state(
-            (user_repo.clone(), config.clone()),
+            auth_state.clone(),
             auth_middleware,
         ));
 
-    // Build application routes
-    let app = Router::new()
-        .route("/health", get(health::health))
-        .route("/health/ready", get(health::health_ready))
-        .with_state(pool.clone())
+    // Mute rule routes (require authentication)
+    let mute_routes = Router::new()
+        .route(
+            "/api/mute-rules",
+            get(MuteController::list_rules).post(MuteController::create_rule),
+        )
+        .route(
+            "/api/mute-rules/:ruleId",
+            axum::routing::delete(MuteController::delete_rule),
+        )
+        .route("/v1/mute-rules", get(MuteController::list_rules_v1))
+        .with_state(mute_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // GraphQL endpoint for the home screen (requires authentication)
+    let graphql_routes = Router::new()
+        .route("/graphql", axum::routing::post(GraphQLController::execute))
+        .with_state(graphql_controller)
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Subscription routes (require authentication)
+    let subscription_routes = Router::new()
+        .route(
+            "/api/subscription/validate-purchase",
+            axum::routing::post(SubscriptionController::validate_purchase)
+                .layer(idempotency.clone()),
+        )
+        .route(
+            "/api/subscription/start-trial",
+            axum::routing::post(SubscriptionController::start_trial),
+        )
+        .route(
+            "/api/subscription/redeem",
+            axum::routing::post(SubscriptionController::redeem_promo_code),
+        )
+        .route(
+            "/api/subscription/history",
+            axum::routing::get(SubscriptionController::get_history),
+        )
+        .route(
+            "/v1/subscription/history",
+            axum::routing::get(SubscriptionController::get_history_v1),
+        )
+        .route(
+            "/api/subscription/checkout-session",
+            axum::routing::post(SubscriptionController::create_checkout_session),
+        )
+        .with_state(subscription_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Admin routes (require authentication and an admin account)
+    let admin_routes = Router::new()
+        .route("/admin/users", get(AdminController::list_users))
+        .route("/admin/users/:userId", get(AdminController::get_user))
+        .route(
+            "/admin/users/:userId/usage",
+            get(AdminController::get_user_usage),
+        )
+        .route(
+            "/admin/users/:userId/grant-pro",
+            axum::routing::post(AdminController::grant_pro),
+        )
+        .route(
+            "/admin/users/:userId/revoke-pro",
+            axum::routing::post(AdminController::revoke_pro),
+        )
+        .route(
+            "/admin/users/:userId/limit-overrides",
+            axum::routing::put(AdminController::set_limit_overrides),
+        )
+        .route(
+            "/admin/users/:userId/role",
+            axum::routing::put(AdminController::set_role),
+        )
+        .route(
+            "/admin/promo-codes",
+            get(AdminController::list_promo_codes).post(AdminController::create_promo_code),
+        )
+        .route("/admin/audit-log", get(AdminController::list_audit_log))
+        .route("/admin/stats", get(AdminController::get_stats))
+        .route("/admin/tts/metrics", get(AdminController::get_tts_metrics))
+        .with_state(admin_controller.clone())
+        .merge(
+            Router::new()
+                .route(
+                    "/admin/ip-blocks",
+                    get(IpBlockController::list_blocks).post(IpBlockController::create_block),
+                )
+                .route(
+                    "/admin/ip-blocks/:blockId",
+                    axum::routing::delete(IpBlockController::delete_block),
+                )
+                .with_state(ip_block_controller.clone()),
+        )
+        .merge(
+            Router::new()
+                .route(
+                    "/admin/suggestion-submissions",
+                    get(FeedSuggestionsController::list_submissions),
+                )
+                .route(
+                    "/admin/suggestion-submissions/:submissionId/approve",
+                    axum::routing::post(FeedSuggestionsController::approve_submission),
+                )
+                .route(
+                    "/admin/suggestion-submissions/:submissionId/reject",
+                    axum::routing::post(FeedSuggestionsController::reject_submission),
+                )
+                .with_state(feed_suggestions_controller.clone()),
+        )
+        .layer(middleware::from_fn_with_state(
+            config.clone(),
+            admin_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ));
+
+    // Admin dashboard pages (session cookie or bearer token, admin account)
+    let admin_dashboard_routes = Router::new()
+        .route("/admin", get(AdminDashboardController::overview))
+        .route(
+            "/admin/dashboard/users/:userId",
+            get(AdminDashboardController::user_usage),
+        )
+        .route(
+            "/admin/dashboard/suggestions",
+            get(AdminDashboardController::suggestions),
+        )
+        .route(
+            "/admin/dashboard/suggestions/check",
+            axum::routing::post(AdminDashboardController::check_suggestion_links),
+        )
+        .with_state(admin_dashboard_controller.clone())
+        .layer(middleware::from_fn_with_state(
+            config.clone(),
+            admin_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            auth_state.clone(),
+            auth_middleware,
+        ))
+        .layer(middleware::from_fn(admin_session_middleware))
+        .route("/admin/sign-in", get(AdminDashboardController::sign_in))
+        .route(
+            "/admin/sign-out",
+            axum::routing::post(AdminDashboardController::sign_out),
+        )
+        .with_state(admin_dashboard_controller);
+
+    // Store webhooks (public - authenticated by signature)
+    let webhook_routes = Router::new()
+        .route(
+            "/webhooks/stripe",
+            axum::routing::post(SubscriptionController::stripe_webhook),
+        )
+        .route(
+            "/webhooks/apple",
+            axum::routing::post(SubscriptionController::apple_webhook),
+        )
+        .with_state(subscription_controller.clone());
+
+    // Unsubscribe links in emails (public - authenticated by the signed token)
+    let email_routes = Router::new()
+        .route(
+            "/email/unsubscribe",
+            get(WeeklySummaryController::confirm_unsubscribe)
+                .post(WeeklySummaryController::unsubscribe),
+        )
+        .with_state(weekly_summary_controller);
+
+    // Health checks stay outside the global limit so probes still answer under load
+    let api_routes = Router::new()
         .merge(auth_routes)
         .merge(oauth_routes)
         .merge(auth_protected_routes)
         .merge(user_routes)
+        .merge(referral_routes)
+        .merge(family_routes)
+        .merge(device_routes)
+        .merge(analytics_routes)
+        .merge(user_webhook_routes)
+        .merge(api_key_routes)
+        .merge(events_routes)
         .merge(feed_routes)
         .merge(feed_suggestions_routes)
+        .merge(mute_routes)
+        .merge(graphql_routes)
+        .merge(subscription_routes)
+        .merge(webhook_routes)
+        .merge(email_routes)
+        .merge(admin_routes)
+        .merge(admin_dashboard_routes)
         .merge(tts_routes)
         .merge(usage_routes)
+        // Otherwise the last merged router's fallback answers, behind its auth middleware
+        .fallback(|| async { StatusCode::NOT_FOUND })
+        .layer(middleware::from_fn_with_state(
+            Arc::new(Semaphore::new(config.max_concurrent_requests)),
+            concurrency_limit_middleware,
+        ));
+
+    // Build application routes
+    let app = Router::new()
+        .route("/health", get(health::health))
+        .route("/health/ready", get(health::HealthController::health_ready))
+        .with_state(health_controller)
+        .merge(api_routes);
+
+    // Blocked clients are turned away before anything else runs, but still show up in
+    // the access log
+    let app = app.layer(middleware::from_fn_with_state(
+        ip_block_service.clone(),
+        ip_blocklist_middleware,
+    ));
+
+    // Inside the request span, so each line carries the request and user ids
+    let app = match config.access_log.clone() {
+        Some(access_log) => app.layer(middleware::from_fn_with_state(
+            Arc::new(AccessLogger::new(access_log)),
+            access_log_middleware,
+        )),
+        None => app,
+    };
+
+    let app = app
+        .layer(middleware::from_fn_with_state(
+            Arc::new(ForwardingTrust {
+                trusted_proxies: config.trusted_proxies.clone(),
+                header: config.client_ip_header,
+            }),
+            client_ip_middleware,
+        ))
         .layer(middleware::from_fn(request_id_middleware))
         .layer(TraceLayer::new_for_http());
 
@@ -378,6 +1125,35 @@ pub fn generate_test_jwt_with_email(user_id: &Uuid, email: &str, secret: &str) -
     .unwrap()
 }
 
+// Helper to generate a correctly signed JWT that expired an hour ago
+pub fn generate_expired_test_jwt(user_id: &Uuid, secret: &str) -> String {
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Claims {
+        sub: String,
+        email: String,
+        exp: i64,
+        iat: i64,
+    }
+
+    let now = chrono::Utc::now();
+    let claims = Claims {
+        sub: user_id.to_string(),
+        email: "test@example.com".to_string(),
+        exp: (now - chrono::Duration::hours(1)).timestamp(),
+        iat: (now - chrono::Duration::hours(2)).timestamp(),
+    };
+
+    encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .unwrap()
+}
+
 // Helper to create a timestamp
 #[allow(dead_code)]
 pub fn now() -> DateTime<Utc> {
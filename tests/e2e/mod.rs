@@ -13,10 +13,30 @@
 // Tests run in parallel by default, significantly improving test performance.
 
 mod helpers;
+mod test_admin;
+mod test_analytics;
+mod test_api_keys;
 mod test_auth;
+mod test_backup;
+mod test_data_export;
+mod test_devices;
+mod test_errors;
+mod test_events;
+mod test_family;
 mod test_feed_suggestions;
 mod test_feeds;
+mod test_graphql;
 mod test_health;
+mod test_ip_blocks;
+mod test_maintenance;
+mod test_mute_rules;
 mod test_oauth;
+mod test_referral;
+mod test_seed;
+mod test_sessions;
+mod test_subscription;
+mod test_suggestion_submissions;
 mod test_tts;
 mod test_user;
+mod test_webhooks;
+mod test_weekly_summary;
@@ -0,0 +1,692 @@
+use crate::e2e::helpers;
+
+use feedtape_backend::domain::user::SubscriptionTier;
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_forbid_admin_endpoints_for_regular_users(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/users", &token)
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::FORBIDDEN)
+        .assert_error_message("Admin access required");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_search_users_by_email(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let alice = ctx.fixtures.create_user("alice@example.com").await.unwrap();
+    ctx.fixtures.create_user("bob@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/users?q=ALICE", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+
+    let body = response.body.as_ref().unwrap();
+    let users = body["items"].as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0]["id"], alice.id.to_string());
+    assert_eq!(users[0]["subscription_tier"], "free");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_page_through_users_with_a_cursor(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    ctx.fixtures.create_user("alice@example.com").await.unwrap();
+    ctx.fixtures.create_user("bob@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/users?limit=2", &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["items"].as_array().unwrap().len(), 2);
+    let cursor = body["next_cursor"].as_str().unwrap().to_string();
+
+    let response = ctx
+        .client
+        .get_with_auth(&format!("/admin/users?limit=2&cursor={}", cursor), &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    let users = body["items"].as_array().unwrap();
+    assert_eq!(users.len(), 1);
+    // Oldest account comes last
+    assert_eq!(users[0]["id"], admin.id.to_string());
+    assert!(body.get("next_cursor").is_none());
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/users?limit=0", &token)
+        .await
+        .unwrap();
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("limit must be between 1 and 200");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_show_user_usage(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    ctx.fixtures.add_tts_usage(user.id, 4000, 2).await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth(&format!("/admin/users/{}/usage", user.id), &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["characters_used"], 4000);
+    assert_eq!(body["articles_synthesized"], 2);
+    assert_eq!(body["characters_limit"], 20000);
+    assert_eq!(body["history"].as_array().unwrap().len(), 1);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_grant_and_revoke_pro(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            &format!("/admin/users/{}/grant-pro", user.id),
+            &json!({}),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let stored = ctx.fixtures.get_user_by_id(user.id).await.unwrap().unwrap();
+    assert_eq!(stored.subscription_tier, SubscriptionTier::Pro);
+    assert!(stored.subscription_expires_at.is_none());
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            &format!("/admin/users/{}/revoke-pro", user.id),
+            &json!({}),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let stored = ctx.fixtures.get_user_by_id(user.id).await.unwrap().unwrap();
+    assert_eq!(stored.subscription_tier, SubscriptionTier::Free);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_grant_with_past_expiry(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            &format!("/admin/users/{}/grant-pro", user.id),
+            &json!({ "expires_at": "2020-01-01T00:00:00Z" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("expires_at must be in the future");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_apply_limit_overrides(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let user_token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .put_with_auth(
+            &format!("/admin/users/{}/limit-overrides", user.id),
+            &json!({ "max_feeds": 25 }),
+            &admin_token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["limit_overrides"], json!({ "max_feeds": 25 }));
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me", &user_token)
+        .await
+        .unwrap();
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["subscription"]["limits"]["max_feeds"], 25);
+    // Characters were not overridden, so the Free default still applies
+    assert_eq!(body["subscription"]["usage"]["characters_limit"], 20000);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_non_positive_limit_overrides(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .put_with_auth(
+            &format!("/admin/users/{}/limit-overrides", user.id),
+            &json!({ "characters": 0 }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("characters must be positive");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_create_and_list_promo_codes(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/admin/promo-codes",
+            &json!({ "code": "summer-24", "duration_days": 30 }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["code"], "SUMMER24");
+    assert_eq!(body["max_redemptions"], 1);
+    assert_eq!(body["redemption_count"], 0);
+    assert_eq!(body["created_by"], "admin@example.com");
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/admin/promo-codes",
+            &json!({ "duration_days": 7, "max_redemptions": null }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["code"].as_str().unwrap().len(), 8);
+    assert!(body.get("max_redemptions").is_none());
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/promo-codes", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let promo_codes = response.body.as_ref().unwrap()["items"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(promo_codes.len(), 2);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_duplicate_promo_code(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_promo_code("WELCOME", 30, Some(1), None)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/admin/promo-codes",
+            &json!({ "code": "welcome", "duration_days": 14 }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::CONFLICT)
+        .assert_error_message("Promo code already exists");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_record_changes_in_the_audit_log(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let user_token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let feed_id = uuid::Uuid::new_v4();
+    ctx.client
+        .post_with_auth(
+            "/api/feeds",
+            &json!({
+                "id": feed_id.to_string(),
+                "url": "https://blog.example.com/rss",
+                "title": "Example Blog"
+            }),
+            &user_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::CREATED);
+    ctx.client
+        .delete_with_auth(&format!("/api/feeds/{}", feed_id), &user_token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::NO_CONTENT);
+    ctx.client
+        .post_with_auth(
+            &format!("/admin/users/{}/grant-pro", user.id),
+            &json!({}),
+            &admin_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+    // Feed creation reaches the audit log through the outbox
+    ctx.dispatch_outbox().await;
+
+    let response = ctx
+        .client
+        .get_with_auth(
+            &format!("/admin/audit-log?user_id={}", user.id),
+            &admin_token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let entries = response.body.as_ref().unwrap()["items"]
+        .as_array()
+        .unwrap()
+        .clone();
+    let actions: Vec<&str> = entries
+        .iter()
+        .map(|entry| entry["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, vec!["pro_granted", "feed_deleted", "feed_created"]);
+
+    assert_eq!(entries[0]["actor_type"], "admin");
+    assert_eq!(entries[0]["actor"], "admin@example.com");
+    assert_eq!(entries[1]["actor_type"], "user");
+    assert_eq!(entries[1]["actor"], user.id.to_string());
+    assert_eq!(entries[1]["target"], feed_id.to_string());
+    assert_eq!(entries[2]["details"]["url"], "https://blog.example.com/rss");
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/audit-log?action=feed_created", &admin_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["items"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_keep_the_audit_log_append_only(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    ctx.client
+        .post_with_auth(
+            &format!("/admin/users/{}/revoke-pro", user.id),
+            &json!({}),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let deleted = sqlx::query("DELETE FROM audit_log WHERE user_id = $1")
+        .bind(user.id)
+        .execute(&ctx.pool)
+        .await;
+    assert!(deleted.is_err());
+
+    let updated = sqlx::query("UPDATE audit_log SET actor = 'someone' WHERE user_id = $1")
+        .bind(user.id)
+        .execute(&ctx.pool)
+        .await;
+    assert!(updated.is_err());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_record_the_forwarded_client_ip(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let user_token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let created = ctx
+        .client
+        .post_with_auth_and_headers(
+            "/api/feeds",
+            &json!({
+                "id": uuid::Uuid::new_v4().to_string(),
+                "url": "https://blog.example.com/rss",
+                "title": "Example Blog"
+            }),
+            &user_token,
+            &[("X-Forwarded-For", "198.51.100.1, 203.0.113.7")],
+        )
+        .await
+        .unwrap();
+    created.assert_status(StatusCode::CREATED);
+    // Recorded later by the outbox dispatcher, outside of the request
+    ctx.dispatch_outbox().await;
+
+    let response = ctx
+        .client
+        .get_with_auth(
+            &format!("/admin/audit-log?user_id={}", user.id),
+            &admin_token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let entries = &response.body.as_ref().unwrap()["items"];
+    assert_eq!(entries[0]["action"], "feed_created");
+    // Only the loopback test client is trusted, so the last forwarded hop is the client
+    assert_eq!(entries[0]["client_ip"], "203.0.113.7");
+    assert_eq!(
+        entries[0]["request_id"].as_str(),
+        created.header("x-request-id").map(String::as_str)
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_report_service_wide_stats(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let alice = ctx.fixtures.create_user("alice@example.com").await.unwrap();
+    ctx.fixtures
+        .create_pro_user("bob@example.com")
+        .await
+        .unwrap();
+    ctx.fixtures.add_tts_usage(alice.id, 4000, 2).await.unwrap();
+    ctx.fixtures
+        .create_feed(alice.id, "https://blog.example.com/rss", None)
+        .await
+        .unwrap();
+    ctx.fixtures
+        .add_synthesis_stats("polly", 3, 3000)
+        .await
+        .unwrap();
+    ctx.fixtures
+        .add_synthesis_stats("cache", 1, 1000)
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/stats?days=7", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["users"]["total"], 3);
+    assert_eq!(body["users"]["pro"], 1);
+    assert_eq!(body["users"]["active"], 1);
+    assert_eq!(body["daily"][0]["active_users"], 1);
+    assert_eq!(body["daily"][0]["characters"], 4000);
+    assert_eq!(body["daily"][0]["synthesis_minutes"], 4.0);
+    assert_eq!(body["characters_by_provider"]["polly"], 3000);
+    assert_eq!(body["characters_by_provider"]["cache"], 1000);
+    assert_eq!(body["cache"]["hits"], 1);
+    assert_eq!(body["cache"]["requests"], 4);
+    assert_eq!(body["cache"]["hit_rate"], 0.25);
+    assert_eq!(body["feeds"]["total"], 1);
+    assert_eq!(body["feeds"]["users_with_feeds"], 1);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/stats?days=0", &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_report_tts_provider_errors(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    // The test Polly client points at a closed port, so the call can't get through
+    ctx.client
+        .post_with_auth(
+            "/api/tts/synthesize",
+            &json!({
+                "text": "Hello, this is a test message for text to speech.",
+                "link": "https://example.com/test-article"
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/tts/metrics", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["latency"], json!([]));
+    assert_eq!(
+        body["errors"],
+        json!([{ "provider": "polly", "category": "network", "count": 1 }])
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_render_the_admin_dashboard(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    ctx.fixtures.create_user("alice@example.com").await.unwrap();
+    ctx.fixtures.create_user("bob@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin?q=alice", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    assert!(response
+        .header("content-type")
+        .unwrap()
+        .starts_with("text/html"));
+    let page = String::from_utf8(response.body_bytes.clone()).unwrap();
+    assert!(page.contains("alice@example.com"));
+    assert!(!page.contains("bob@example.com"));
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_authenticate_the_dashboard_with_the_session_cookie(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let admin_cookie = format!(
+        "feedtape_admin_session={}",
+        generate_test_jwt(&admin.id, &ctx.config.jwt_secret)
+    );
+    let user_cookie = format!(
+        "feedtape_admin_session={}",
+        generate_test_jwt(&user.id, &ctx.config.jwt_secret)
+    );
+
+    let response = ctx
+        .client
+        .get_with_headers("/admin/dashboard/suggestions", &[("Cookie", &admin_cookie)])
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .get_with_headers("/admin", &[("Cookie", &user_cookie)])
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::FORBIDDEN);
+
+    for cookie in ["", "feedtape_admin_session=not-a-token"] {
+        let response = ctx
+            .client
+            .get_with_headers("/admin", &[("Cookie", cookie)])
+            .await
+            .unwrap();
+        response
+            .assert_status(StatusCode::SEE_OTHER)
+            .assert_header("location", "/admin/sign-in");
+    }
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_admit_users_given_the_admin_role(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let support = ctx
+        .fixtures
+        .create_user("support@example.com")
+        .await
+        .unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let support_token = generate_test_jwt(&support.id, &ctx.config.jwt_secret);
+
+    ctx.client
+        .get_with_auth("/admin/stats", &support_token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::FORBIDDEN);
+
+    let response = ctx
+        .client
+        .put_with_auth(
+            &format!("/admin/users/{}/role", support.id),
+            &json!({ "role": "admin" }),
+            &admin_token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(response.body.as_ref().unwrap()["role"], "admin");
+
+    let response = ctx
+        .client
+        .get_with_auth(&format!("/admin/users/{}", admin.id), &support_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["email"],
+        "admin@example.com"
+    );
+
+    // Admins can't demote themselves, but can demote each other
+    ctx.client
+        .put_with_auth(
+            &format!("/admin/users/{}/role", support.id),
+            &json!({ "role": "user" }),
+            &support_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::BAD_REQUEST);
+    ctx.client
+        .put_with_auth(
+            &format!("/admin/users/{}/role", support.id),
+            &json!({ "role": "user" }),
+            &admin_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+    ctx.client
+        .get_with_auth("/admin/stats", &support_token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::FORBIDDEN);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/audit-log?action=role_changed", &admin_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["items"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+}
@@ -0,0 +1,105 @@
+use crate::e2e::helpers;
+
+use chrono::{Duration, Utc};
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_store_valid_events_and_report_invalid_ones(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let now = Utc::now();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/analytics/events",
+            &json!({
+                "events": [
+                    {
+                        "name": "screen_view",
+                        "occurred_at": now,
+                        "session_id": "session-1",
+                        "properties": { "screen": "library" }
+                    },
+                    {
+                        "name": "playback_completed",
+                        "occurred_at": now,
+                        "properties": { "link": "https://example.com/post", "duration_seconds": 95 }
+                    },
+                    {
+                        "name": "playback_completed",
+                        "occurred_at": now,
+                        "properties": { "duration_seconds": 95 }
+                    },
+                    {
+                        "name": "app_crashed",
+                        "occurred_at": now
+                    },
+                    {
+                        "name": "screen_view",
+                        "occurred_at": now - Duration::days(30),
+                        "properties": { "screen": "library" }
+                    }
+                ]
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::ACCEPTED);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["accepted"], 2);
+    let rejected = body["rejected"].as_array().unwrap();
+    assert_eq!(rejected.len(), 3);
+    assert_eq!(rejected[0]["index"], 2);
+    assert_eq!(rejected[0]["error"], "property 'link' is required");
+    assert_eq!(rejected[1]["index"], 3);
+    assert_eq!(rejected[2]["index"], 4);
+
+    let stored: Vec<(String, Option<String>)> = sqlx::query_as(
+        "SELECT name, session_id FROM analytics_events WHERE user_id = $1 ORDER BY name DESC",
+    )
+    .bind(user.id)
+    .fetch_all(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        stored,
+        vec![
+            ("screen_view".to_string(), Some("session-1".to_string())),
+            ("playback_completed".to_string(), None),
+        ]
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_an_empty_batch(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/analytics/events", &json!({ "events": [] }), &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_require_authentication_to_send_events(ctx: &TestContext) {
+    let response = ctx
+        .client
+        .post("/api/analytics/events", &json!({ "events": [] }))
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
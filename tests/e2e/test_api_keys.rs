@@ -0,0 +1,123 @@
+use crate::e2e::helpers;
+
+use helpers::api_client::ApiResponse;
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+async fn get_with_key(ctx: &TestContext, path: &str, key: &str) -> ApiResponse {
+    ctx.client
+        .get_with_headers(path, &[("Authorization", &format!("ApiKey {}", key))])
+        .await
+        .unwrap()
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_authenticate_api_keys_within_their_scopes(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/me/api-keys",
+            &json!({ "name": "Nightly script", "scopes": ["tts"] }),
+            &token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::CREATED);
+    let created = response.body.as_ref().unwrap();
+    let key = created["key"].as_str().unwrap().to_string();
+    let key_id = created["id"].as_str().unwrap().to_string();
+    assert!(key.starts_with("ftk_"));
+    assert!(key.starts_with(created["prefix"].as_str().unwrap()));
+    assert_eq!(created["scopes"], json!(["tts"]));
+
+    // The key itself is only shown once
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/api-keys", &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let listed = &response.body.as_ref().unwrap()["api_keys"][0];
+    assert_eq!(listed["name"], "Nightly script");
+    assert!(listed.get("key").is_none());
+
+    get_with_key(ctx, "/api/tts/usage", &key)
+        .await
+        .assert_status(StatusCode::OK);
+    for path in ["/api/feeds", "/api/me", "/api/me/api-keys"] {
+        get_with_key(ctx, path, &key)
+            .await
+            .assert_status(StatusCode::FORBIDDEN)
+            .assert_error_message("API key not allowed for this endpoint");
+    }
+
+    ctx.client
+        .delete_with_auth(&format!("/api/me/api-keys/{}", key_id), &token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::NO_CONTENT);
+    get_with_key(ctx, "/api/tts/usage", &key)
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED)
+        .assert_error_message("Invalid API key");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_validate_new_api_keys(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let other = ctx.fixtures.create_user("other@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let other_token = generate_test_jwt(&other.id, &ctx.config.jwt_secret);
+
+    ctx.client
+        .post_with_auth(
+            "/api/me/api-keys",
+            &json!({ "name": "No scopes", "scopes": [] }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("At least one scope is required");
+    ctx.client
+        .post_with_auth(
+            "/api/me/api-keys",
+            &json!({ "name": "  ", "scopes": ["feeds"] }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("API key name is required");
+
+    // Keys belong to the user who created them
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/me/api-keys",
+            &json!({ "name": "Feeds", "scopes": ["feeds", "feeds"] }),
+            &token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::CREATED);
+    let created = response.body.as_ref().unwrap();
+    assert_eq!(created["scopes"], json!(["feeds"]));
+    let key_id = created["id"].as_str().unwrap().to_string();
+
+    ctx.client
+        .delete_with_auth(&format!("/api/me/api-keys/{}", key_id), &other_token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::NOT_FOUND);
+    get_with_key(ctx, "/api/feeds", created["key"].as_str().unwrap())
+        .await
+        .assert_status(StatusCode::OK);
+}
@@ -258,6 +258,28 @@ async fn it_should_logout_all_sessions(ctx: &TestContext) {
     }
 }
 
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_access_tokens_issued_before_logout_all(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.client
+        .get_with_auth("/api/me", &token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    ctx.client
+        .post_with_auth("/auth/logout/all", &json!({}), &token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::NO_CONTENT);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    response.assert_error_message("Token has been revoked");
+}
+
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_require_auth_for_logout_all(ctx: &TestContext) {
@@ -451,3 +473,68 @@ async fn it_should_clean_expired_tokens_on_refresh(ctx: &TestContext) {
     // Old expired tokens should be cleaned up
     // (In a real test, we'd verify this in the database)
 }
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_revoke_the_successor_when_a_refresh_token_is_reused(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+
+    let stolen = "stolen_refresh_token";
+    ctx.fixtures
+        .create_refresh_token(
+            user.id,
+            stolen,
+            Utc::now() + chrono::Duration::days(30),
+            false,
+        )
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post("/auth/refresh", &json!({ "refresh_token": stolen }))
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let rotated = response.body.as_ref().unwrap()["refresh_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    ctx.client
+        .post("/auth/refresh", &json!({ "refresh_token": stolen }))
+        .await
+        .unwrap()
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    // The token issued in its place is revoked along with it
+    ctx.client
+        .post("/auth/refresh", &json!({ "refresh_token": rotated }))
+        .await
+        .unwrap()
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/audit-log?action=refresh_token_reused", &admin_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let entry = &response.body.as_ref().unwrap()["items"][0];
+    assert_eq!(entry["actor_type"], "system");
+    assert_eq!(entry["user_id"], user.id.to_string());
+    assert_eq!(entry["details"]["revoked_tokens"], 1);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_publish_no_keys_while_tokens_are_signed_with_the_secret(ctx: &TestContext) {
+    let response = ctx.client.get("/.well-known/jwks.json").await.unwrap();
+
+    response
+        .assert_status(StatusCode::OK)
+        .assert_header("cache-control", "public, max-age=300");
+    assert_eq!(response.body.as_ref().unwrap()["keys"], json!([]));
+}
@@ -0,0 +1,145 @@
+use crate::e2e::helpers;
+
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_restore_a_backup_into_another_account_once(ctx: &TestContext) {
+    let source = ctx
+        .fixtures
+        .create_user("source@example.com")
+        .await
+        .unwrap();
+    let target = ctx
+        .fixtures
+        .create_user("target@example.com")
+        .await
+        .unwrap();
+    let source_token = generate_test_jwt(&source.id, &ctx.config.jwt_secret);
+    let target_token = generate_test_jwt(&target.id, &ctx.config.jwt_secret);
+
+    let feed = ctx
+        .fixtures
+        .create_feed(source.id, "https://sports.example.com/rss", Some("Sports"))
+        .await
+        .unwrap();
+    ctx.client
+        .post_with_auth(
+            "/api/mute-rules",
+            &json!({ "pattern": "transfer rumours", "feed_id": feed.id.to_string() }),
+            &source_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::CREATED);
+    ctx.client
+        .patch_with_auth(
+            "/api/me",
+            &json!({ "settings": { "playback": { "speed": 1.5 } } }),
+            &source_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/backup", &source_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let backup = response.body.clone().unwrap();
+    assert_eq!(backup["version"], 1);
+    assert_eq!(backup["feeds"][0]["url"], "https://sports.example.com/rss");
+    assert_eq!(
+        backup["mute_rules"][0]["feed_url"],
+        "https://sports.example.com/rss"
+    );
+
+    // A dry run reports the plan and leaves the account alone
+    let response = ctx
+        .client
+        .post_with_auth("/api/me/restore?dry_run=true", &backup, &target_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let report = response.body.as_ref().unwrap();
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["settings_changed"], true);
+    assert_eq!(report["feeds"]["created"], 1);
+    assert_eq!(report["mute_rules"]["created"], 1);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/feeds", &target_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert!(response
+        .body
+        .as_ref()
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/me/restore", &backup, &target_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let report = response.body.as_ref().unwrap();
+    assert_eq!(report["feeds"]["created"], 1);
+    assert_eq!(report["mute_rules"]["created"], 1);
+    assert!(report.get("failures").is_none());
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me", &target_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["settings"]["playback"]["speed"],
+        1.5
+    );
+
+    // Restoring again finds everything in place
+    let response = ctx
+        .client
+        .post_with_auth("/api/me/restore", &backup, &target_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let report = response.body.as_ref().unwrap();
+    assert_eq!(report["settings_changed"], false);
+    assert_eq!(report["feeds"]["created"], 0);
+    assert_eq!(report["feeds"]["existing"], 1);
+    assert_eq!(report["mute_rules"]["existing"], 1);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_backups_of_another_version(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/backup", &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let mut backup = response.body.clone().unwrap();
+    backup["version"] = json!(99);
+
+    ctx.client
+        .post_with_auth("/api/me/restore", &backup, &token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::BAD_REQUEST);
+}
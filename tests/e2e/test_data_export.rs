@@ -0,0 +1,91 @@
+use crate::e2e::helpers;
+
+use chrono::{Duration, Utc};
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_export_everything_stored_about_the_user(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let other = ctx.fixtures.create_user("other@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    ctx.fixtures
+        .create_feed(user.id, "https://blog.example.com/rss", Some("Blog"))
+        .await
+        .unwrap();
+    ctx.fixtures
+        .create_feed(other.id, "https://other.example.com/rss", None)
+        .await
+        .unwrap();
+    ctx.fixtures.add_tts_usage(user.id, 1200, 2).await.unwrap();
+    ctx.fixtures
+        .create_refresh_token(
+            user.id,
+            "export_refresh_token",
+            Utc::now() + Duration::days(30),
+            false,
+        )
+        .await
+        .unwrap();
+    ctx.client
+        .post_with_auth(
+            "/api/me/devices",
+            &json!({ "platform": "apns", "token": "apns-device-token", "app_version": "1.4.0" }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::CREATED);
+    ctx.client
+        .post_with_auth(
+            "/api/mute-rules",
+            &json!({ "pattern": "sponsored" }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::CREATED);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/export", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK).assert_header(
+        "content-disposition",
+        "attachment; filename=\"feedtape-data.json\"",
+    );
+    let export = response.body.as_ref().unwrap();
+    assert_eq!(export["version"], 1);
+    assert_eq!(export["account"]["email"], "user@example.com");
+    assert_eq!(export["profile"]["subscription"]["tier"], "free");
+
+    let feeds = export["feeds"].as_array().unwrap();
+    assert_eq!(feeds.len(), 1);
+    assert_eq!(feeds[0]["url"], "https://blog.example.com/rss");
+    assert_eq!(export["mute_rules"][0]["pattern"], "sponsored");
+    assert_eq!(export["usage"][0]["characters_used"], 1200);
+    assert_eq!(export["usage"][0]["articles_synthesized"], 2);
+    assert_eq!(export["devices"][0]["app_version"], "1.4.0");
+    assert_eq!(export["sessions"][0]["revoked"], false);
+
+    // Credentials are described, never included
+    let raw = String::from_utf8(response.body_bytes.clone()).unwrap();
+    assert!(!raw.contains("export_refresh_token"));
+    assert!(!raw.contains("apns-device-token"));
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_require_auth_to_export_data(ctx: &TestContext) {
+    ctx.client
+        .get("/api/me/export")
+        .await
+        .unwrap()
+        .assert_status(StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,133 @@
+use crate::e2e::helpers;
+
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+const APNS_TOKEN: &str = "740f4707bebcf74f9b7c25d48e3358945f6aa01da5ddb387462c7eaf61bb78ad";
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_register_a_device(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/me/devices",
+            &json!({ "platform": "apns", "token": APNS_TOKEN, "app_version": "1.4.0" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["platform"], "apns");
+    assert_eq!(body["token"], APNS_TOKEN);
+    assert_eq!(body["app_version"], "1.4.0");
+    let device_id = body["id"].as_str().unwrap().to_string();
+
+    // Registering again only refreshes the app version
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/me/devices",
+            &json!({ "platform": "apns", "token": APNS_TOKEN, "app_version": "1.5.0" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["id"], device_id);
+    assert_eq!(body["app_version"], "1.5.0");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_an_empty_device_token(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/me/devices",
+            &json!({ "platform": "fcm", "token": "  " }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Device token is required");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_unregister_a_device(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    ctx.client
+        .post_with_auth(
+            "/api/me/devices",
+            &json!({ "platform": "apns", "token": APNS_TOKEN }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::CREATED);
+
+    let path = format!("/api/me/devices/{}", APNS_TOKEN);
+    let response = ctx.client.delete_with_auth(&path, &token).await.unwrap();
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    let response = ctx.client.delete_with_auth(&path, &token).await.unwrap();
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_move_a_device_to_the_latest_account(ctx: &TestContext) {
+    let first = ctx.fixtures.create_user("first@example.com").await.unwrap();
+    let second = ctx
+        .fixtures
+        .create_user("second@example.com")
+        .await
+        .unwrap();
+    let first_token = generate_test_jwt(&first.id, &ctx.config.jwt_secret);
+    let second_token = generate_test_jwt(&second.id, &ctx.config.jwt_secret);
+
+    for token in [&first_token, &second_token] {
+        ctx.client
+            .post_with_auth(
+                "/api/me/devices",
+                &json!({ "platform": "apns", "token": APNS_TOKEN }),
+                token,
+            )
+            .await
+            .unwrap()
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let path = format!("/api/me/devices/{}", APNS_TOKEN);
+    let response = ctx
+        .client
+        .delete_with_auth(&path, &first_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = ctx
+        .client
+        .delete_with_auth(&path, &second_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NO_CONTENT);
+}
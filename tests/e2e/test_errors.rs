@@ -0,0 +1,118 @@
+use crate::e2e::helpers;
+
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+use uuid::Uuid;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_wrap_app_errors_in_the_envelope(ctx: &TestContext) {
+    let response = ctx.client.get("/api/me").await.unwrap();
+
+    response
+        .assert_status(StatusCode::UNAUTHORIZED)
+        .assert_error_code("unauthorized")
+        .assert_error_message("Missing authorization header");
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(
+        body["request_id"].as_str(),
+        response.header("x-request-id").map(String::as_str)
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_wrap_json_rejections_in_the_envelope(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/mute-rules", &json!({ "pattern": 42 }), &token)
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY)
+        .assert_error_code("invalid_request_body")
+        .assert_error_message("pattern");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_wrap_unknown_routes_in_the_envelope(ctx: &TestContext) {
+    let response = ctx.client.get("/api/does-not-exist").await.unwrap();
+
+    response
+        .assert_status(StatusCode::NOT_FOUND)
+        .assert_error_code("not_found");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_use_specific_codes_for_refresh_token_errors(ctx: &TestContext) {
+    let response = ctx
+        .client
+        .post("/auth/refresh", &json!({ "refresh_token": "unknown" }))
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::UNAUTHORIZED)
+        .assert_error_code("invalid_refresh_token");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_translate_error_messages_from_accept_language(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let path = format!("/api/feeds/{}", Uuid::new_v4());
+
+    let response = ctx
+        .client
+        .delete_with_auth_and_headers(&path, &token, &[("accept-language", "es-ES,es;q=0.9")])
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::NOT_FOUND)
+        .assert_error_code("not_found")
+        .assert_error_message("Recurso no encontrado: Feed no encontrado");
+    assert_eq!(
+        response.header("content-language").map(String::as_str),
+        Some("es")
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_translate_error_messages_to_the_users_language(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    sqlx::query(
+        r#"UPDATE users SET settings = jsonb_set(settings, '{language}', '"fr"') WHERE id = $1"#,
+    )
+    .bind(user.id)
+    .execute(&ctx.pool)
+    .await
+    .unwrap();
+    let path = format!("/api/feeds/{}", Uuid::new_v4());
+
+    let response = ctx.client.delete_with_auth(&path, &token).await.unwrap();
+    response
+        .assert_status(StatusCode::NOT_FOUND)
+        .assert_error_message("Ressource introuvable: Flux introuvable");
+
+    // An explicit Accept-Language wins over the settings
+    let response = ctx
+        .client
+        .delete_with_auth_and_headers(&path, &token, &[("accept-language", "en")])
+        .await
+        .unwrap();
+    response
+        .assert_status(StatusCode::NOT_FOUND)
+        .assert_error_message("Resource not found: Feed not found");
+}
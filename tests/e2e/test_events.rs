@@ -0,0 +1,66 @@
+use crate::e2e::helpers;
+
+use helpers::{generate_test_jwt, TestContext};
+use http_body_util::BodyExt;
+use hyper::StatusCode;
+use serde_json::json;
+use std::time::Duration;
+use test_context::test_context;
+use uuid::Uuid;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_require_authentication_for_the_event_stream(ctx: &TestContext) {
+    let response = ctx.client.get("/api/events").await.unwrap();
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_stream_feed_stats_job_progress(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let stream = ctx.client.open_stream("/api/events", &token).await.unwrap();
+    assert_eq!(stream.status(), StatusCode::OK);
+    assert_eq!(
+        stream.headers()["content-type"].to_str().unwrap(),
+        "text/event-stream"
+    );
+    let mut body = stream.into_body();
+
+    // The host doesn't resolve, so the stats job fails straight away
+    let feed_id = Uuid::new_v4();
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/feeds",
+            &json!({
+                "id": feed_id.to_string(),
+                "url": "https://feedtape-events.invalid/rss",
+                "title": "Unreachable"
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::CREATED);
+
+    let mut received = String::new();
+    tokio::time::timeout(Duration::from_secs(15), async {
+        while !received.contains("\n\n") {
+            let frame = body.frame().await.unwrap().unwrap();
+            if let Some(data) = frame.data_ref() {
+                received.push_str(std::str::from_utf8(data).unwrap());
+            }
+        }
+    })
+    .await
+    .expect("No event received");
+
+    assert!(received.contains("event: job_progress"), "{}", received);
+    assert!(received.contains(r#""job":"feed_stats""#), "{}", received);
+    assert!(received.contains(&feed_id.to_string()), "{}", received);
+    assert!(received.contains(r#""status":"failed""#), "{}", received);
+}
@@ -0,0 +1,272 @@
+use crate::e2e::helpers;
+
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_invite_a_family_member(ctx: &TestContext) {
+    let owner = ctx
+        .fixtures
+        .create_pro_user("owner@example.com")
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&owner.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/family/invites",
+            &json!({ "email": "Member@Example.com" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["email"], "member@example.com");
+    assert_eq!(body["code"].as_str().unwrap().len(), 8);
+    assert!(body["expires_at"].is_string());
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/family", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert!(body.get("owner").is_none());
+    assert_eq!(body["members"].as_array().unwrap().len(), 0);
+    assert_eq!(body["invites"].as_array().unwrap().len(), 1);
+    assert_eq!(body["max_members"], 5);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_require_pro_to_invite_family_members(ctx: &TestContext) {
+    let owner = ctx.fixtures.create_user("owner@example.com").await.unwrap();
+    let token = generate_test_jwt(&owner.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/family/invites",
+            &json!({ "email": "member@example.com" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::PAYMENT_REQUIRED)
+        .assert_error_message("Pro subscription is required");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_share_pro_limits_with_members(ctx: &TestContext) {
+    let owner = ctx
+        .fixtures
+        .create_pro_user("owner@example.com")
+        .await
+        .unwrap();
+    let member = ctx
+        .fixtures
+        .create_user("member@example.com")
+        .await
+        .unwrap();
+    let owner_token = generate_test_jwt(&owner.id, &ctx.config.jwt_secret);
+    let member_token = generate_test_jwt(&member.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/family/invites",
+            &json!({ "email": "member@example.com" }),
+            &owner_token,
+        )
+        .await
+        .unwrap();
+    let code = response.body.as_ref().unwrap()["code"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/family/join", &json!({ "code": code }), &member_token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["owner"]["id"], owner.id.to_string());
+    assert_eq!(body["owner"]["email"], "owner@example.com");
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me", &member_token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let subscription = &response.body.as_ref().unwrap()["subscription"];
+    assert_eq!(subscription["tier"], "free");
+    assert_eq!(subscription["family_owner_id"], owner.id.to_string());
+    assert_eq!(subscription["usage"]["characters_limit"], 200000);
+    assert_eq!(subscription["usage"]["characters_used_today"], 0);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/family", &owner_token)
+        .await
+        .unwrap();
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["members"].as_array().unwrap().len(), 1);
+    assert_eq!(body["members"][0]["id"], member.id.to_string());
+    assert_eq!(body["invites"].as_array().unwrap().len(), 0);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_invites_sent_to_another_email(ctx: &TestContext) {
+    let owner = ctx
+        .fixtures
+        .create_pro_user("owner@example.com")
+        .await
+        .unwrap();
+    let other = ctx.fixtures.create_user("other@example.com").await.unwrap();
+    let owner_token = generate_test_jwt(&owner.id, &ctx.config.jwt_secret);
+    let other_token = generate_test_jwt(&other.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/family/invites",
+            &json!({ "email": "member@example.com" }),
+            &owner_token,
+        )
+        .await
+        .unwrap();
+    let code = response.body.as_ref().unwrap()["code"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/family/join", &json!({ "code": code }), &other_token)
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::FORBIDDEN)
+        .assert_error_message("sent to a different email");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_limit_family_size(ctx: &TestContext) {
+    let owner = ctx
+        .fixtures
+        .create_pro_user("owner@example.com")
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&owner.id, &ctx.config.jwt_secret);
+
+    for i in 0..5 {
+        let response = ctx
+            .client
+            .post_with_auth(
+                "/api/family/invites",
+                &json!({ "email": format!("member{}@example.com", i) }),
+                &token,
+            )
+            .await
+            .unwrap();
+        response.assert_status(StatusCode::CREATED);
+    }
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/family/invites",
+            &json!({ "email": "member5@example.com" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::CONFLICT)
+        .assert_error_message("at most 5 members");
+
+    // Re-sending an existing invite doesn't take another seat
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/family/invites",
+            &json!({ "email": "member0@example.com" }),
+            &token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::CREATED);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_drop_to_free_limits_when_removed(ctx: &TestContext) {
+    let owner = ctx
+        .fixtures
+        .create_pro_user("owner@example.com")
+        .await
+        .unwrap();
+    let member = ctx
+        .fixtures
+        .create_user("member@example.com")
+        .await
+        .unwrap();
+    let owner_token = generate_test_jwt(&owner.id, &ctx.config.jwt_secret);
+    let member_token = generate_test_jwt(&member.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/family/invites",
+            &json!({ "email": "member@example.com" }),
+            &owner_token,
+        )
+        .await
+        .unwrap();
+    let code = response.body.as_ref().unwrap()["code"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    ctx.client
+        .post_with_auth("/api/family/join", &json!({ "code": code }), &member_token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .delete_with_auth(&format!("/api/family/members/{}", member.id), &owner_token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me", &member_token)
+        .await
+        .unwrap();
+    let subscription = &response.body.as_ref().unwrap()["subscription"];
+    assert!(subscription.get("family_owner_id").is_none());
+    assert_eq!(subscription["usage"]["characters_limit"], 20000);
+}
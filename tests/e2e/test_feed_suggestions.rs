@@ -60,6 +60,39 @@ async fn it_should_get_all_categories_with_suggestions(ctx: &TestContext) {
     }
 }
 
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_return_not_modified_for_unchanged_suggestions(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/feed-suggestions", &token)
+        .await
+        .unwrap();
+    let etag = response.header("etag").expect("Missing ETag").clone();
+
+    let response = ctx
+        .client
+        .get_with_auth_and_headers("/api/feed-suggestions", &token, &[("If-None-Match", &etag)])
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NOT_MODIFIED);
+
+    // A filtered list is a different representation
+    let response = ctx
+        .client
+        .get_with_auth_and_headers(
+            "/api/feed-suggestions?category_ids=technology-programming",
+            &token,
+            &[("If-None-Match", &etag)],
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+}
+
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_get_suggestions_by_single_category(ctx: &TestContext) {
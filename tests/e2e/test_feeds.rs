@@ -1,5 +1,6 @@
 use crate::e2e::helpers;
 
+use chrono::{Duration, Utc};
 use helpers::{generate_test_jwt, TestContext};
 use hyper::StatusCode;
 use serde_json::json;
@@ -37,6 +38,93 @@ async fn it_should_create_a_new_feed(ctx: &TestContext) {
     assert_eq!(feed_count, 1);
 }
 
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_replay_feed_creation_with_same_idempotency_key(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let request = json!({
+        "id": uuid::Uuid::new_v4().to_string(),
+        "url": "https://blog.example.com/rss",
+        "title": "Example Blog"
+    });
+
+    let response = ctx
+        .client
+        .post_with_auth_and_headers(
+            "/api/feeds",
+            &request,
+            &token,
+            &[("Idempotency-Key", "abc-1")],
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::CREATED);
+    assert!(response.header("idempotent-replayed").is_none());
+
+    // Retrying would otherwise fail as a duplicate feed
+    let response = ctx
+        .client
+        .post_with_auth_and_headers(
+            "/api/feeds",
+            &request,
+            &token,
+            &[("Idempotency-Key", "abc-1")],
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::CREATED);
+    assert_eq!(response.header("idempotent-replayed").unwrap(), "true");
+
+    let feed_count = ctx.fixtures.get_feed_count(user.id).await.unwrap();
+    assert_eq!(feed_count, 1);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_idempotency_key_reused_for_another_request(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth_and_headers(
+            "/api/feeds",
+            &json!({
+                "id": uuid::Uuid::new_v4().to_string(),
+                "url": "https://blog.example.com/rss",
+                "title": "Example Blog"
+            }),
+            &token,
+            &[("Idempotency-Key", "abc-1")],
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::CREATED);
+
+    let response = ctx
+        .client
+        .post_with_auth_and_headers(
+            "/api/feeds",
+            &json!({
+                "id": uuid::Uuid::new_v4().to_string(),
+                "url": "https://other.example.com/rss",
+                "title": "Other Blog"
+            }),
+            &token,
+            &[("Idempotency-Key", "abc-1")],
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("already used for a different request");
+
+    let feed_count = ctx.fixtures.get_feed_count(user.id).await.unwrap();
+    assert_eq!(feed_count, 1);
+}
+
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_list_user_feeds(ctx: &TestContext) {
@@ -64,10 +152,10 @@ async fn it_should_list_user_feeds(ctx: &TestContext) {
     for (idx, feed) in feeds.iter().enumerate() {
         let feed_id = feed["id"]
             .as_str()
-            .expect(&format!("Feed {} missing id", idx));
+            .unwrap_or_else(|| panic!("Feed {} missing id", idx));
         let url = feed["url"]
             .as_str()
-            .expect(&format!("Feed {} missing url", idx));
+            .unwrap_or_else(|| panic!("Feed {} missing url", idx));
         let created_at = &feed["created_at"];
 
         assert!(!feed_id.is_empty(), "Feed ID should not be empty");
@@ -93,6 +181,135 @@ async fn it_should_list_user_feeds(ctx: &TestContext) {
     }
 }
 
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_page_through_feeds_with_a_cursor(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_multiple_feeds(user.id, 5)
+        .await
+        .unwrap();
+
+    let mut seen = Vec::new();
+    let mut path = "/api/feeds?limit=2".to_string();
+    loop {
+        let response = ctx.client.get_with_auth(&path, &token).await.unwrap();
+        response.assert_status(StatusCode::OK);
+
+        let body = response.body.as_ref().unwrap();
+        let items = body["items"].as_array().unwrap();
+        assert!(items.len() <= 2);
+        seen.extend(
+            items
+                .iter()
+                .map(|feed| feed["id"].as_str().unwrap().to_string()),
+        );
+
+        match body["next_cursor"].as_str() {
+            Some(cursor) => path = format!("/api/feeds?limit=2&cursor={}", cursor),
+            None => break,
+        }
+    }
+
+    assert_eq!(seen.len(), 5);
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), 5, "No feed should appear on two pages");
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/feeds?cursor=not-a-cursor", &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_list_feeds_in_the_v1_envelope(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_multiple_feeds(user.id, 3)
+        .await
+        .unwrap();
+
+    // Paginated without asking, unlike /api/feeds
+    let response = ctx
+        .client
+        .get_with_auth("/v1/feeds?limit=2", &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["items"].as_array().unwrap().len(), 2);
+    assert_eq!(body["total"], 3);
+    let cursor = body["next_cursor"].as_str().unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth(&format!("/v1/feeds?limit=2&cursor={}", cursor), &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["items"].as_array().unwrap().len(), 1);
+    assert!(body.get("next_cursor").is_none());
+
+    // The unpaginated lists share the envelope
+    let response = ctx
+        .client
+        .get_with_auth("/v1/mute-rules", &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["items"], json!([]));
+    assert_eq!(body["total"], 0);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_return_not_modified_for_unchanged_feeds(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_multiple_feeds(user.id, 2)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/feeds", &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let etag = response.header("etag").expect("Missing ETag").clone();
+
+    let response = ctx
+        .client
+        .get_with_auth_and_headers("/api/feeds", &token, &[("If-None-Match", &etag)])
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NOT_MODIFIED);
+    assert!(response.body_bytes.is_empty());
+    assert_eq!(response.header("etag"), Some(&etag));
+
+    ctx.fixtures
+        .create_feed(user.id, "https://news.example.com/rss", Some("News"))
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth_and_headers("/api/feeds", &token, &[("If-None-Match", &etag)])
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(response.body.as_ref().unwrap().as_array().unwrap().len(), 3);
+}
+
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_delete_a_feed(ctx: &TestContext) {
@@ -245,7 +462,6 @@ async fn it_should_return_404_for_nonexistent_feed(ctx: &TestContext) {
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_not_allow_access_to_other_users_feeds(ctx: &TestContext) {
-
     let user1 = ctx.fixtures.create_user("user1@example.com").await.unwrap();
     let user2 = ctx.fixtures.create_user("user2@example.com").await.unwrap();
 
@@ -301,7 +517,6 @@ async fn it_should_validate_feed_url_format(ctx: &TestContext) {
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_require_authentication_for_feeds(ctx: &TestContext) {
-
     // Try to list feeds without auth
     let response = ctx.client.get("/api/feeds").await.unwrap();
     response.assert_status(StatusCode::UNAUTHORIZED);
@@ -330,3 +545,181 @@ async fn it_should_require_authentication_for_feeds(ctx: &TestContext) {
         .unwrap();
     response.assert_status(StatusCode::UNAUTHORIZED);
 }
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_flag_invalid_urls_when_validating_feeds(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/feeds/validate",
+            &json!({ "urls": ["ftp://example.com/feed", "http://127.0.0.1:9/rss"] }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+
+    let results = response.body.as_ref().unwrap().as_array().unwrap();
+    assert_eq!(results.len(), 2);
+
+    assert_eq!(results[0]["url"], "ftp://example.com/feed");
+    assert_eq!(results[0]["resolves"], false);
+    assert_eq!(results[0]["parses"], false);
+    assert_eq!(results[0]["error"], "Invalid URL format");
+
+    // Loopback hosts must never be fetched
+    assert_eq!(results[1]["resolves"], false);
+    assert_eq!(results[1]["error"], "Host resolves to a non-public address");
+
+    // Nothing is created
+    let feed_count = ctx.fixtures.get_feed_count(user.id).await.unwrap();
+    assert_eq!(feed_count, 0);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_report_conflicts_when_validating_feeds(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    ctx.fixtures
+        .create_feed(user.id, "http://127.0.0.1/rss", Some("Local"))
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/feeds/validate",
+            &json!({ "urls": ["http://127.0.0.1/rss", "http://127.0.0.1/other"] }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+
+    let results = response.body.as_ref().unwrap().as_array().unwrap();
+    assert_eq!(results[0]["conflict"], true);
+    assert_eq!(results[1]["conflict"], false);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_empty_feed_validation_request(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/feeds/validate", &json!({ "urls": [] }), &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    response.assert_error_message("At least one URL is required");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_link_feed_created_from_suggestion(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/feeds",
+            &json!({
+                "id": uuid::Uuid::new_v4().to_string(),
+                "url": "https://blog.khanacademy.org/feed/",
+                "title": "Khan Academy Blog",
+                "suggestion_id": "khan-academy-blog"
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/feeds", &token)
+        .await
+        .unwrap();
+
+    let feeds = response.body.as_ref().unwrap().as_array().unwrap();
+    assert_eq!(feeds.len(), 1);
+    assert_eq!(feeds[0]["suggestion_id"], "khan-academy-blog");
+    assert_eq!(feeds[0]["category_id"], "education-learning");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_unknown_suggestion_id(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/feeds",
+            &json!({
+                "id": uuid::Uuid::new_v4().to_string(),
+                "url": "https://blog.example.com/rss",
+                "title": "Example Blog",
+                "suggestion_id": "does-not-exist"
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    response.assert_error_message("Unknown suggestion_id");
+
+    let feed_count = ctx.fixtures.get_feed_count(user.id).await.unwrap();
+    assert_eq!(feed_count, 0);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_block_adding_feeds_when_trial_expired(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .set_trial(
+            user.id,
+            Utc::now() - Duration::days(8),
+            Utc::now() - Duration::days(1),
+        )
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/feeds",
+            &json!({
+                "id": uuid::Uuid::new_v4().to_string(),
+                "url": "https://blog.example.com/rss",
+                "title": "Example Blog"
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::PAYMENT_REQUIRED)
+        .assert_error_message("Free trial expired");
+
+    let feed_count = ctx.fixtures.get_feed_count(user.id).await.unwrap();
+    assert_eq!(feed_count, 0);
+}
@@ -0,0 +1,104 @@
+use crate::e2e::helpers;
+
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_return_the_home_screen_in_one_query(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let sports = ctx
+        .fixtures
+        .create_feed(user.id, "https://sports.example.com/rss", Some("Sports"))
+        .await
+        .unwrap();
+    ctx.fixtures
+        .create_feed(user.id, "https://news.example.com/rss", Some("News"))
+        .await
+        .unwrap();
+    ctx.client
+        .post_with_auth(
+            "/api/mute-rules",
+            &json!({ "pattern": "transfer rumours", "feed_id": sports.id.to_string() }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::CREATED);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/graphql",
+            &json!({
+                "query": "{ me { id tier } feeds { url muteRules { pattern kind } } \
+                          usage { charactersUsed charactersLimit } }"
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert!(body.get("errors").is_none(), "unexpected errors: {}", body);
+    let data = &body["data"];
+    assert_eq!(data["me"]["id"], user.id.to_string());
+    assert_eq!(data["me"]["tier"], "free");
+    assert_eq!(data["usage"]["charactersUsed"], 0);
+
+    let feeds = data["feeds"].as_array().unwrap();
+    assert_eq!(feeds.len(), 2);
+    let sports_feed = feeds
+        .iter()
+        .find(|feed| feed["url"] == "https://sports.example.com/rss")
+        .unwrap();
+    assert_eq!(
+        sports_feed["muteRules"],
+        json!([{ "pattern": "transfer rumours", "kind": "keyword" }])
+    );
+    let news_feed = feeds
+        .iter()
+        .find(|feed| feed["url"] == "https://news.example.com/rss")
+        .unwrap();
+    assert_eq!(news_feed["muteRules"], json!([]));
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_require_authentication_for_graphql(ctx: &TestContext) {
+    let response = ctx
+        .client
+        .post("/graphql", &json!({ "query": "{ me { id } }" }))
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_queries_for_unknown_fields(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/graphql",
+            &json!({ "query": "{ articles { id } }" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert!(body["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("articles"));
+}
@@ -2,12 +2,28 @@ use crate::e2e::helpers;
 
 use helpers::TestContext;
 use hyper::StatusCode;
+use serde_json::json;
+use std::time::Duration;
 use test_context::test_context;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+/// Have the Polly stand-in answer readiness probes, after `delay`
+async fn mount_polly_voices(ctx: &TestContext, delay: Duration) {
+    Mock::given(method("GET"))
+        .and(path("/v1/voices"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "Voices": [] }))
+                .set_delay(delay),
+        )
+        .mount(&ctx.polly)
+        .await;
+}
 
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_return_ok_for_health_check(ctx: &TestContext) {
-
     let response = ctx.client.get("/health").await.unwrap();
 
     response.assert_status(StatusCode::OK);
@@ -20,6 +36,7 @@ async fn it_should_return_ok_for_health_check(ctx: &TestContext) {
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_return_ready_status(ctx: &TestContext) {
+    mount_polly_voices(ctx, Duration::ZERO).await;
 
     let response = ctx.client.get("/health/ready").await.unwrap();
 
@@ -28,15 +45,44 @@ async fn it_should_return_ready_status(ctx: &TestContext) {
     let body = response.body.as_ref().unwrap();
 
     // Check readiness response structure
-    assert_eq!(body.get("status").and_then(|v| v.as_str()), Some("ready"));
-    assert!(body.get("database").is_some());
-    assert!(body.get("tts").is_some());
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["database"], "connected");
+    assert_eq!(body["tts"], "available");
+
+    let checks = &body["checks"];
+    assert_eq!(checks["database"]["status"], "ok");
+    assert!(checks["database"]["latency_ms"].is_u64());
+    assert!(checks["pool"]["max"].as_u64().unwrap() > 0);
+    assert_eq!(checks["migrations"]["status"], "ok");
+    assert_eq!(checks["migrations"]["pending"], 0);
+    assert_eq!(
+        checks["migrations"]["version"],
+        checks["migrations"]["expected_version"]
+    );
+    assert_eq!(checks["cache"]["status"], "ok");
+    assert_eq!(checks["tts"][0]["status"], "ok");
 }
 
 #[test_context(TestContext)]
 #[tokio::test]
-async fn it_should_not_require_auth_for_health_checks(ctx: &TestContext) {
+async fn it_should_report_degraded_when_a_tts_provider_is_unreachable(ctx: &TestContext) {
+    // Polly answers after the probe has given up
+    mount_polly_voices(ctx, Duration::from_secs(5)).await;
+
+    let response = ctx.client.get("/health/ready").await.unwrap();
+
+    response.assert_status(StatusCode::OK);
+
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["tts"], "unavailable");
+    assert_eq!(body["checks"]["tts"][0]["provider"], "polly");
+    assert_eq!(body["checks"]["tts"][0]["status"], "down");
+}
 
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_not_require_auth_for_health_checks(ctx: &TestContext) {
     // Both health endpoints should work without authentication
     let response = ctx.client.get("/health").await.unwrap();
     response.assert_status(StatusCode::OK);
@@ -48,7 +94,6 @@ async fn it_should_not_require_auth_for_health_checks(ctx: &TestContext) {
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_include_request_id_in_health_responses(ctx: &TestContext) {
-
     let response = ctx.client.get("/health").await.unwrap();
     response.assert_header_exists("x-request-id");
 
@@ -59,7 +104,6 @@ async fn it_should_include_request_id_in_health_responses(ctx: &TestContext) {
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_handle_database_connectivity_check(ctx: &TestContext) {
-
     // The ready endpoint should verify database connection
     let response = ctx.client.get("/health/ready").await.unwrap();
 
@@ -75,7 +119,6 @@ async fn it_should_handle_database_connectivity_check(ctx: &TestContext) {
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_be_fast_health_check(ctx: &TestContext) {
-
     let start = std::time::Instant::now();
     let response = ctx.client.get("/health").await.unwrap();
     let duration = start.elapsed();
@@ -93,7 +136,6 @@ async fn it_should_be_fast_health_check(ctx: &TestContext) {
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_handle_concurrent_health_checks(ctx: &TestContext) {
-
     // Simulate multiple concurrent health checks
     let mut futures = Vec::new();
     for _ in 0..10 {
@@ -113,7 +155,6 @@ async fn it_should_handle_concurrent_health_checks(ctx: &TestContext) {
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_return_service_details_in_ready(ctx: &TestContext) {
-
     let response = ctx.client.get("/health/ready").await.unwrap();
     response.assert_status(StatusCode::OK);
 
@@ -137,7 +178,6 @@ async fn it_should_return_service_details_in_ready(ctx: &TestContext) {
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_use_different_endpoints_for_liveness_and_readiness(ctx: &TestContext) {
-
     // /health is for liveness (is the service running?)
     let liveness_response = ctx.client.get("/health").await.unwrap();
     liveness_response.assert_status(StatusCode::OK);
@@ -0,0 +1,186 @@
+use crate::e2e::helpers;
+
+use helpers::{generate_expired_test_jwt, generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+// The loopback test client is a trusted proxy, so X-Forwarded-For picks the client IP
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_refuse_requests_from_blocked_ranges(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let user_token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/admin/ip-blocks",
+            &json!({ "network": "203.0.113.77/24", "reason": "Scraping" }),
+            &admin_token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let block = response.body.as_ref().unwrap();
+    assert_eq!(block["network"], "203.0.113.0/24");
+    assert_eq!(block["source"], "admin");
+    assert_eq!(block["created_by"], "admin@example.com");
+    let block_id = block["id"].as_str().unwrap().to_string();
+
+    ctx.client
+        .get_with_auth_and_headers(
+            "/api/feeds",
+            &user_token,
+            &[("X-Forwarded-For", "203.0.113.9")],
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::FORBIDDEN);
+    ctx.client
+        .get_with_auth_and_headers(
+            "/api/feeds",
+            &user_token,
+            &[("X-Forwarded-For", "198.51.100.1")],
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    ctx.client
+        .delete_with_auth(&format!("/admin/ip-blocks/{}", block_id), &admin_token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::NO_CONTENT);
+
+    ctx.client
+        .get_with_auth_and_headers(
+            "/api/feeds",
+            &user_token,
+            &[("X-Forwarded-For", "203.0.113.9")],
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/audit-log?action=ip_blocked", &admin_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["items"][0]["target"],
+        "203.0.113.0/24"
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_block_clients_after_repeated_auth_failures(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let threshold = ctx.config.auth_failure_block.as_ref().unwrap().threshold;
+
+    for _ in 0..threshold {
+        ctx.client
+            .get_with_auth_and_headers(
+                "/api/feeds",
+                "not-a-valid-token",
+                &[("X-Forwarded-For", "203.0.113.50")],
+            )
+            .await
+            .unwrap()
+            .assert_status(StatusCode::UNAUTHORIZED);
+    }
+
+    ctx.client
+        .get_with_auth_and_headers(
+            "/api/feeds",
+            "not-a-valid-token",
+            &[("X-Forwarded-For", "203.0.113.50")],
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::FORBIDDEN)
+        .assert_error_code("forbidden");
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/ip-blocks", &admin_token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let blocks = &response.body.as_ref().unwrap()["blocks"];
+    assert_eq!(blocks.as_array().unwrap().len(), 1);
+    assert_eq!(blocks[0]["network"], "203.0.113.50/32");
+    assert_eq!(blocks[0]["source"], "auth_failures");
+    assert!(blocks[0]["expires_at"].is_string());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_not_count_expired_tokens_as_auth_failures(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let expired_token = generate_expired_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let threshold = ctx.config.auth_failure_block.as_ref().unwrap().threshold;
+
+    for _ in 0..=threshold {
+        ctx.client
+            .get_with_auth_and_headers(
+                "/api/feeds",
+                &expired_token,
+                &[("X-Forwarded-For", "203.0.113.51")],
+            )
+            .await
+            .unwrap()
+            .assert_status(StatusCode::UNAUTHORIZED);
+    }
+
+    ctx.client
+        .get_with_auth_and_headers(
+            "/api/feeds",
+            &admin_token,
+            &[("X-Forwarded-For", "203.0.113.51")],
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/ip-blocks", &admin_token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    assert!(response.body.as_ref().unwrap()["blocks"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_blocks_for_invalid_networks(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/admin/ip-blocks",
+            &json!({ "network": "example.com", "reason": "Spam" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,120 @@
+use crate::e2e::helpers;
+
+use chrono::{Duration, Utc};
+use feedtape_backend::domain::audit::{AuditAction, AuditEvent};
+use feedtape_backend::infrastructure::jobs::{MaintenanceReport, MaintenanceTask};
+use feedtape_backend::infrastructure::repositories::{
+    AuditLogRepository, AuditLogRepositoryApi, IdempotencyRepository, IpBlockRepository,
+    OAuthStateRepository, OutboxRepository, RefreshTokenRepository, UsageRepository,
+    WebhookRepository,
+};
+use helpers::TestContext;
+use std::sync::Arc;
+use test_context::test_context;
+
+fn maintenance_task(ctx: &TestContext) -> MaintenanceTask {
+    let pool = Arc::new(ctx.pool.clone());
+    MaintenanceTask::new(
+        Arc::new(RefreshTokenRepository::new(pool.clone())),
+        Arc::new(IdempotencyRepository::new(pool.clone())),
+        Arc::new(UsageRepository::new(pool.clone())),
+        Arc::new(WebhookRepository::new(pool.clone())),
+        Arc::new(IpBlockRepository::new(pool.clone())),
+        Arc::new(OutboxRepository::new(pool.clone())),
+        Arc::new(AuditLogRepository::new(pool.clone())),
+        Arc::new(OAuthStateRepository::new(pool)),
+        None,
+        ctx.config.retention.clone(),
+    )
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_purge_dead_refresh_tokens_and_old_usage(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let now = Utc::now();
+
+    ctx.fixtures
+        .create_refresh_token(user.id, "valid", now + Duration::days(30), false)
+        .await
+        .unwrap();
+    ctx.fixtures
+        .create_refresh_token(user.id, "expired", now - Duration::days(1), false)
+        .await
+        .unwrap();
+    // Revoked tokens stay until they expire, so reusing one is still caught
+    ctx.fixtures
+        .create_refresh_token(user.id, "revoked", now + Duration::days(30), true)
+        .await
+        .unwrap();
+
+    let today = now.date_naive();
+    let retention = Duration::days(ctx.config.retention.usage_days);
+    for date in [
+        today,
+        today - retention,
+        today - retention - Duration::days(1),
+    ] {
+        ctx.fixtures
+            .add_tts_usage_on(user.id, date, 1000, None)
+            .await
+            .unwrap();
+    }
+
+    let report = maintenance_task(ctx).run().await;
+
+    assert_eq!(
+        report,
+        MaintenanceReport {
+            refresh_tokens: 1,
+            idempotency_keys: 0,
+            usage_days: 1,
+            webhook_deliveries: 0,
+            ip_blocks: 0,
+            outbox_events: 0,
+            audit_entries: 0,
+            oauth_states: 0,
+            audio_objects: 0,
+        }
+    );
+    assert_eq!(ctx.fixtures.get_usage_day_count(user.id).await.unwrap(), 2);
+
+    // Nothing is left to purge on the next run
+    let report = maintenance_task(ctx).run().await;
+    assert_eq!(report, MaintenanceReport::default());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_purge_audit_entries_past_their_retention(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let audit_log_repo = AuditLogRepository::new(Arc::new(ctx.pool.clone()));
+    let retention = Duration::days(ctx.config.retention.audit_log_days.unwrap());
+
+    for occurred_at in [
+        Utc::now(),
+        Utc::now() - retention + Duration::hours(1),
+        Utc::now() - retention - Duration::hours(1),
+    ] {
+        let event =
+            AuditEvent::by_user(user.id, AuditAction::ProfileUpdated).occurred_at(occurred_at);
+        audit_log_repo.append(&event, None, None).await.unwrap();
+    }
+
+    let report = maintenance_task(ctx).run().await;
+
+    assert_eq!(report.audit_entries, 1);
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM audit_log WHERE user_id = $1")
+        .bind(user.id)
+        .fetch_one(&ctx.pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 2);
+
+    // Outside the purge the log still rejects deletes
+    let deleted = sqlx::query("DELETE FROM audit_log WHERE user_id = $1")
+        .bind(user.id)
+        .execute(&ctx.pool)
+        .await;
+    assert!(deleted.is_err());
+}
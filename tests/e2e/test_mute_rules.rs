@@ -0,0 +1,152 @@
+use crate::e2e::helpers;
+
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_create_and_list_mute_rules(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/mute-rules",
+            &json!({ "pattern": "sponsored" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["pattern"], "sponsored");
+    assert_eq!(body["kind"], "keyword");
+    assert!(body.get("feed_id").is_none());
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/mute-rules", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let rules = response.body.as_ref().unwrap().as_array().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0]["id"], body["id"]);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_create_feed_scoped_regex_rule(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let feed = ctx
+        .fixtures
+        .create_feed(user.id, "https://sports.example.com/rss", Some("Sports"))
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/mute-rules",
+            &json!({
+                "pattern": r"final score|\d+-\d+",
+                "kind": "regex",
+                "feed_id": feed.id.to_string()
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["kind"], "regex");
+    assert_eq!(body["feed_id"], feed.id.to_string());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_invalid_regex_rule(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/mute-rules",
+            &json!({ "pattern": "(unclosed", "kind": "regex" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Invalid regex");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_not_allow_rules_on_other_users_feeds(ctx: &TestContext) {
+    let owner = ctx.fixtures.create_user("owner@example.com").await.unwrap();
+    let other = ctx.fixtures.create_user("other@example.com").await.unwrap();
+    let token = generate_test_jwt(&other.id, &ctx.config.jwt_secret);
+    let feed = ctx
+        .fixtures
+        .create_feed(owner.id, "https://blog.example.com/rss", Some("Blog"))
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/mute-rules",
+            &json!({ "pattern": "spoiler", "feed_id": feed.id.to_string() }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::NOT_FOUND)
+        .assert_error_message("Feed not found");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_delete_a_mute_rule(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/mute-rules", &json!({ "pattern": "ad" }), &token)
+        .await
+        .unwrap();
+    let rule_id = response.body.as_ref().unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = ctx
+        .client
+        .delete_with_auth(&format!("/api/mute-rules/{}", rule_id), &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    let response = ctx
+        .client
+        .delete_with_auth(&format!("/api/mute-rules/{}", rule_id), &token)
+        .await
+        .unwrap();
+    response
+        .assert_status(StatusCode::NOT_FOUND)
+        .assert_error_message("Mute rule not found");
+}
@@ -1,8 +1,94 @@
 use crate::e2e::helpers;
 
-use helpers::TestContext;
+use helpers::{api_client::ApiResponse, TestContext};
 use hyper::StatusCode;
+use serde_json::json;
 use test_context::test_context;
+use uuid::Uuid;
+use wiremock::matchers::{body_string_contains, header, method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+const GITHUB_ACCESS_TOKEN: &str = "gho_test_token";
+
+/// Have the GitHub stand-in accept `code` and answer for `user`
+async fn mount_github_sign_in(ctx: &TestContext, code: &str, user: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(path("/login/oauth/access_token"))
+        .and(body_string_contains(format!("code={}", code)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": GITHUB_ACCESS_TOKEN,
+            "token_type": "bearer",
+            "scope": "user:email"
+        })))
+        .mount(&ctx.github)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/user"))
+        .and(header(
+            "authorization",
+            format!("Bearer {}", GITHUB_ACCESS_TOKEN).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(user))
+        .mount(&ctx.github)
+        .await;
+}
+
+/// A sign-in started from a browser: the state sent to GitHub and the cookie the browser
+/// was given with it
+struct SignIn {
+    state: String,
+    cookie: String,
+}
+
+/// Start a sign-in the way a browser would
+async fn start_sign_in(ctx: &TestContext, query: &str) -> SignIn {
+    let response = ctx
+        .client
+        .get(&format!("/auth/oauth/github{}", query))
+        .await
+        .unwrap();
+    let location = reqwest::Url::parse(response.header("location").unwrap()).unwrap();
+    let state = location
+        .query_pairs()
+        .find(|(name, _)| name == "state")
+        .map(|(_, state)| state.into_owned())
+        .unwrap();
+    let cookie = response.header("set-cookie").unwrap();
+    SignIn {
+        state,
+        cookie: cookie.split(';').next().unwrap().to_string(),
+    }
+}
+
+/// Come back from GitHub with `code`, in the browser that started `sign_in`
+async fn return_from_github(ctx: &TestContext, code: &str, sign_in: &SignIn) -> ApiResponse {
+    ctx.client
+        .get_with_headers(
+            &callback_path(code, &sign_in.state),
+            &[("Cookie", &sign_in.cookie)],
+        )
+        .await
+        .unwrap()
+}
+
+/// Callback URL GitHub would send the browser back to
+fn callback_path(code: &str, state: &str) -> String {
+    format!(
+        "/auth/callback/github?code={}&state={}",
+        code,
+        urlencoding::encode(state)
+    )
+}
+
+fn github_user(email: Option<&str>) -> serde_json::Value {
+    json!({
+        "id": 4242,
+        "login": "octocat",
+        "email": email,
+        "name": "The Octocat",
+        "avatar_url": "https://avatars.example.com/octocat.png"
+    })
+}
 
 #[test_context(TestContext)]
 #[tokio::test]
@@ -24,7 +110,7 @@ async fn it_should_initiate_github_oauth(ctx: &TestContext) {
     assert!(location.is_some(), "Missing Location header");
     let location = location.unwrap();
     assert!(
-        location.starts_with("https://github.com/login/oauth/authorize"),
+        location.starts_with(&format!("{}/login/oauth/authorize", ctx.github.uri())),
         "Location should point to GitHub OAuth, got: {}",
         location
     );
@@ -66,7 +152,7 @@ async fn it_should_initiate_github_oauth_with_mobile_param(ctx: &TestContext) {
     assert!(location.is_some(), "Missing Location header");
     let location = location.unwrap();
     assert!(
-        location.starts_with("https://github.com/login/oauth/authorize"),
+        location.starts_with(&format!("{}/login/oauth/authorize", ctx.github.uri())),
         "Location should point to GitHub OAuth, got: {}",
         location
     );
@@ -117,9 +203,222 @@ async fn it_should_reject_callback_without_state(ctx: &TestContext) {
     );
 }
 
-// Note: Full OAuth flow testing would require mocking GitHub's OAuth endpoints
-// which is complex. For now, we test the basic endpoint existence and redirect behavior.
-// In production, you'd want to:
-// 1. Mock the GitHub OAuth server
-// 2. Test the full flow: initiate -> callback with valid code -> get tokens
-// 3. Test error cases: invalid code, missing email, etc.
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_sign_in_with_github_and_return_tokens(ctx: &TestContext) {
+    mount_github_sign_in(ctx, "good-code", github_user(Some("octocat@example.com"))).await;
+    let sign_in = start_sign_in(ctx, "").await;
+
+    let response = return_from_github(ctx, "good-code", &sign_in).await;
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert!(body["token"].as_str().is_some());
+    assert!(body["refresh_token"].as_str().is_some());
+
+    let stored: (String, Option<String>) = sqlx::query_as(
+        "SELECT email, display_name FROM users \
+         WHERE oauth_provider = 'github' AND oauth_provider_id = '4242'",
+    )
+    .fetch_one(&ctx.pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        stored,
+        (
+            "octocat@example.com".to_string(),
+            Some("The Octocat".to_string())
+        )
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_sign_in_a_returning_github_user_to_the_same_account(ctx: &TestContext) {
+    mount_github_sign_in(ctx, "good-code", github_user(Some("octocat@example.com"))).await;
+    for _ in 0..2 {
+        let sign_in = start_sign_in(ctx, "").await;
+        return_from_github(ctx, "good-code", &sign_in)
+            .await
+            .assert_status(StatusCode::OK);
+    }
+
+    let users: Vec<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM users WHERE oauth_provider = 'github'")
+            .fetch_all(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(users.len(), 1);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_use_the_primary_verified_email_when_github_hides_it(ctx: &TestContext) {
+    mount_github_sign_in(ctx, "good-code", github_user(None)).await;
+    Mock::given(method("GET"))
+        .and(path("/user/emails"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "email": "old@example.com", "primary": false, "verified": true },
+            { "email": "octocat@example.com", "primary": true, "verified": true }
+        ])))
+        .mount(&ctx.github)
+        .await;
+    let sign_in = start_sign_in(ctx, "").await;
+
+    return_from_github(ctx, "good-code", &sign_in)
+        .await
+        .assert_status(StatusCode::OK);
+
+    let (email,): (String,) =
+        sqlx::query_as("SELECT email FROM users WHERE oauth_provider = 'github'")
+            .fetch_one(&ctx.pool)
+            .await
+            .unwrap();
+    assert_eq!(email, "octocat@example.com");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_redirect_mobile_sign_ins_to_the_app(ctx: &TestContext) {
+    mount_github_sign_in(ctx, "good-code", github_user(Some("octocat@example.com"))).await;
+    let sign_in = start_sign_in(ctx, "?mobile=true").await;
+
+    let response = return_from_github(ctx, "good-code", &sign_in).await;
+
+    response.assert_status(StatusCode::TEMPORARY_REDIRECT);
+    let location = response.header("location").unwrap();
+    assert!(
+        location.starts_with("feedtape://auth/callback?token="),
+        "Expected a deep link, got: {}",
+        location
+    );
+    assert!(location.contains("&refresh_token="));
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_github_accounts_without_a_verified_email(ctx: &TestContext) {
+    mount_github_sign_in(ctx, "good-code", github_user(None)).await;
+    Mock::given(method("GET"))
+        .and(path("/user/emails"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "email": "octocat@example.com", "primary": true, "verified": false }
+        ])))
+        .mount(&ctx.github)
+        .await;
+    let sign_in = start_sign_in(ctx, "").await;
+
+    let response = return_from_github(ctx, "good-code", &sign_in).await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+    response.assert_error_message("GitHub account has no verified email address");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_fail_the_callback_when_github_rejects_the_code(ctx: &TestContext) {
+    Mock::given(method("POST"))
+        .and(path("/login/oauth/access_token"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("bad_verification_code"))
+        .mount(&ctx.github)
+        .await;
+    let sign_in = start_sign_in(ctx, "").await;
+
+    let response = return_from_github(ctx, "expired", &sign_in).await;
+
+    response.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    let users: Vec<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM users WHERE oauth_provider = 'github'")
+            .fetch_all(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(users.is_empty());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_callbacks_with_a_state_it_did_not_issue(ctx: &TestContext) {
+    mount_github_sign_in(ctx, "good-code", github_user(Some("octocat@example.com"))).await;
+
+    // Even when the browser carries the same made-up state
+    let forged = SignIn {
+        state: "web:forged".to_string(),
+        cookie: "feedtape_oauth_state=web%3Aforged".to_string(),
+    };
+    let response = return_from_github(ctx, "good-code", &forged).await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    response.assert_error_message("Invalid or expired OAuth state");
+
+    // A state is good for one callback only
+    let sign_in = start_sign_in(ctx, "").await;
+    return_from_github(ctx, "good-code", &sign_in)
+        .await
+        .assert_status(StatusCode::OK);
+    return_from_github(ctx, "good-code", &sign_in)
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_callbacks_from_a_browser_that_did_not_start_the_sign_in(
+    ctx: &TestContext,
+) {
+    mount_github_sign_in(ctx, "good-code", github_user(Some("octocat@example.com"))).await;
+    let attacker = start_sign_in(ctx, "").await;
+    let victim = start_sign_in(ctx, "").await;
+
+    // The victim opens the attacker's callback link: their cookie holds another state
+    let response = ctx
+        .client
+        .get_with_headers(
+            &callback_path("good-code", &attacker.state),
+            &[("Cookie", &victim.cookie)],
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::BAD_REQUEST);
+    response.assert_error_message("OAuth state does not match this browser");
+
+    // Or they have no sign-in cookie at all
+    let response = ctx
+        .client
+        .get(&callback_path("good-code", &attacker.state))
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::BAD_REQUEST);
+    response.assert_error_message("OAuth state does not match this browser");
+
+    let users: Vec<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM users WHERE oauth_provider = 'github'")
+            .fetch_all(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(users.is_empty());
+
+    // The mismatches didn't use up the state, so the attacker's own browser still works
+    return_from_github(ctx, "good-code", &attacker)
+        .await
+        .assert_status(StatusCode::OK);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_callbacks_after_the_state_expired(ctx: &TestContext) {
+    mount_github_sign_in(ctx, "good-code", github_user(Some("octocat@example.com"))).await;
+    let sign_in = start_sign_in(ctx, "").await;
+    sqlx::query("UPDATE oauth_states SET expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(&ctx.pool)
+        .await
+        .unwrap();
+
+    return_from_github(ctx, "good-code", &sign_in)
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    let users: Vec<(Uuid,)> =
+        sqlx::query_as("SELECT id FROM users WHERE oauth_provider = 'github'")
+            .fetch_all(&ctx.pool)
+            .await
+            .unwrap();
+    assert!(users.is_empty());
+}
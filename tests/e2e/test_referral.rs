@@ -0,0 +1,230 @@
+use crate::e2e::helpers;
+
+use chrono::{Duration, Utc};
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_return_a_stable_referral_code(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/referral", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    let code = body["code"].as_str().unwrap().to_string();
+    assert_eq!(code.len(), 8);
+    assert_eq!(body["referrals_count"], 0);
+    assert!(body.get("bonus_until").is_none());
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/referral", &token)
+        .await
+        .unwrap();
+    assert_eq!(response.body.as_ref().unwrap()["code"], code);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_grant_bonus_to_both_users_on_redeem(ctx: &TestContext) {
+    let referrer = ctx
+        .fixtures
+        .create_user("referrer@example.com")
+        .await
+        .unwrap();
+    let referee = ctx
+        .fixtures
+        .create_user("referee@example.com")
+        .await
+        .unwrap();
+    let referrer_token = generate_test_jwt(&referrer.id, &ctx.config.jwt_secret);
+    let referee_token = generate_test_jwt(&referee.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/referral", &referrer_token)
+        .await
+        .unwrap();
+    let code = response.body.as_ref().unwrap()["code"]
+        .as_str()
+        .unwrap()
+        .to_lowercase();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/auth/redeem-referral",
+            &json!({ "code": code }),
+            &referee_token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["bonus_characters"], 5000);
+    assert!(body["bonus_until"].is_string());
+
+    for token in [&referrer_token, &referee_token] {
+        let response = ctx.client.get_with_auth("/api/me", token).await.unwrap();
+        let body = response.body.as_ref().unwrap();
+        assert_eq!(body["subscription"]["usage"]["characters_limit"], 25000);
+    }
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/referral", &referrer_token)
+        .await
+        .unwrap();
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["referrals_count"], 1);
+    assert!(body["bonus_until"].is_string());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_redeeming_own_referral_code(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/referral", &token)
+        .await
+        .unwrap();
+    let code = response.body.as_ref().unwrap()["code"].clone();
+
+    let response = ctx
+        .client
+        .post_with_auth("/auth/redeem-referral", &json!({ "code": code }), &token)
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("You cannot redeem your own referral code");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_allow_only_one_redemption_per_user(ctx: &TestContext) {
+    let first = ctx.fixtures.create_user("first@example.com").await.unwrap();
+    let second = ctx
+        .fixtures
+        .create_user("second@example.com")
+        .await
+        .unwrap();
+    let referee = ctx
+        .fixtures
+        .create_user("referee@example.com")
+        .await
+        .unwrap();
+    let referee_token = generate_test_jwt(&referee.id, &ctx.config.jwt_secret);
+
+    let mut codes = Vec::new();
+    for user in [&first, &second] {
+        let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+        let response = ctx
+            .client
+            .get_with_auth("/api/me/referral", &token)
+            .await
+            .unwrap();
+        codes.push(response.body.as_ref().unwrap()["code"].clone());
+    }
+
+    ctx.client
+        .post_with_auth(
+            "/auth/redeem-referral",
+            &json!({ "code": codes[0] }),
+            &referee_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/auth/redeem-referral",
+            &json!({ "code": codes[1] }),
+            &referee_token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::CONFLICT)
+        .assert_error_message("already been redeemed");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_redeem_from_established_accounts(ctx: &TestContext) {
+    let referrer = ctx
+        .fixtures
+        .create_user("referrer@example.com")
+        .await
+        .unwrap();
+    let referee = ctx
+        .fixtures
+        .create_user("referee@example.com")
+        .await
+        .unwrap();
+    ctx.fixtures
+        .set_created_at(referee.id, Utc::now() - Duration::days(30))
+        .await
+        .unwrap();
+    let referrer_token = generate_test_jwt(&referrer.id, &ctx.config.jwt_secret);
+    let referee_token = generate_test_jwt(&referee.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/referral", &referrer_token)
+        .await
+        .unwrap();
+    let code = response.body.as_ref().unwrap()["code"].clone();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/auth/redeem-referral",
+            &json!({ "code": code }),
+            &referee_token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("within 14 days of signing up");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_return_404_for_unknown_referral_code(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/auth/redeem-referral",
+            &json!({ "code": "NOPE2345" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::NOT_FOUND)
+        .assert_error_message("Referral code not found");
+}
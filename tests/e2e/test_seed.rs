@@ -0,0 +1,55 @@
+use crate::e2e::helpers;
+
+use feedtape_backend::domain::user::SubscriptionTier;
+use feedtape_backend::infrastructure::db::seed::{SeedReport, Seeder};
+use feedtape_backend::infrastructure::repositories::{
+    FeedRepository, RefreshTokenRepository, UsageRepository, UserRepository, UserRepositoryApi,
+};
+use helpers::TestContext;
+use std::sync::Arc;
+use test_context::test_context;
+
+fn seeder(ctx: &TestContext) -> Seeder {
+    let pool = Arc::new(ctx.pool.clone());
+    Seeder::new(
+        Arc::new(UserRepository::new(pool.clone())),
+        Arc::new(FeedRepository::new(pool.clone())),
+        Arc::new(UsageRepository::new(pool.clone())),
+        Arc::new(RefreshTokenRepository::new(pool)),
+        ctx.config.refresh_token_expiration_days,
+    )
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_seed_demo_users_once(ctx: &TestContext) {
+    let report = seeder(ctx).run().await.unwrap();
+
+    assert_eq!(report.users, 4);
+    assert_eq!(report.existing_users, 0);
+    assert_eq!(report.feeds, 17);
+    assert_eq!(report.refresh_tokens, 4);
+
+    let user_repo = UserRepository::new(Arc::new(ctx.pool.clone()));
+    let pro = user_repo
+        .find_by_email("chloe.pro@demo.feedtape.app")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(pro.subscription_tier, SubscriptionTier::Pro);
+    assert_eq!(ctx.fixtures.get_feed_count(pro.id).await.unwrap(), 8);
+    assert_eq!(
+        ctx.fixtures.get_usage_day_count(pro.id).await.unwrap() as usize,
+        report.usage_days / 4
+    );
+
+    // Running it again leaves the existing accounts alone
+    let report = seeder(ctx).run().await.unwrap();
+    assert_eq!(
+        report,
+        SeedReport {
+            existing_users: 4,
+            ..SeedReport::default()
+        }
+    );
+}
@@ -0,0 +1,150 @@
+use crate::e2e::helpers;
+
+use chrono::{Duration, Utc};
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_list_and_sign_out_sessions(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    for refresh_token in ["phone_refresh_token", "laptop_refresh_token"] {
+        ctx.fixtures
+            .create_refresh_token(
+                user.id,
+                refresh_token,
+                Utc::now() + Duration::days(30),
+                false,
+            )
+            .await
+            .unwrap();
+    }
+
+    let response = ctx
+        .client
+        .post_with_headers(
+            "/auth/refresh",
+            &json!({ "refresh_token": "phone_refresh_token" }),
+            &[("User-Agent", "FeedTape/2.1 iOS")],
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let phone_refresh_token = response.body.as_ref().unwrap()["refresh_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/sessions", &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let sessions = response.body.as_ref().unwrap()["sessions"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(sessions.len(), 2);
+    let phone = sessions
+        .iter()
+        .find(|session| session["user_agent"] == "FeedTape/2.1 iOS")
+        .expect("refreshed session should be listed");
+    assert_eq!(phone["ip"], "127.0.0.1");
+    assert!(phone["last_used_at"].is_string());
+    let laptop = sessions
+        .iter()
+        .find(|session| session["id"] != phone["id"])
+        .unwrap();
+    assert!(laptop["last_used_at"].is_null());
+
+    let path = format!("/api/me/sessions/{}", phone["id"].as_str().unwrap());
+    ctx.client
+        .delete_with_auth(&path, &token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::NO_CONTENT);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/sessions", &token)
+        .await
+        .unwrap();
+    let sessions = response.body.as_ref().unwrap()["sessions"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["id"], laptop["id"]);
+
+    // The signed-out device can't refresh, and is gone for good
+    ctx.client
+        .post(
+            "/auth/refresh",
+            &json!({ "refresh_token": phone_refresh_token }),
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::UNAUTHORIZED);
+    ctx.client
+        .delete_with_auth(&path, &token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_not_sign_out_other_users_sessions(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let other = ctx.fixtures.create_user("other@example.com").await.unwrap();
+    ctx.fixtures
+        .create_refresh_token(
+            user.id,
+            "user_refresh_token",
+            Utc::now() + Duration::days(30),
+            false,
+        )
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let other_token = generate_test_jwt(&other.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/sessions", &token)
+        .await
+        .unwrap();
+    let session_id = response.body.as_ref().unwrap()["sessions"][0]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/sessions", &other_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert!(response.body.as_ref().unwrap()["sessions"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    ctx.client
+        .delete_with_auth(&format!("/api/me/sessions/{}", session_id), &other_token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::NOT_FOUND);
+    ctx.client
+        .post(
+            "/auth/refresh",
+            &json!({ "refresh_token": "user_refresh_token" }),
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+}
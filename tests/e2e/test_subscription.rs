@@ -0,0 +1,419 @@
+use crate::e2e::helpers;
+
+use chrono::{Duration, Utc};
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_malformed_purchase_token(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/subscription/validate-purchase",
+            &json!({ "purchase_token": "not/a valid token" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Invalid purchase token");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_fail_purchase_validation_when_google_play_is_not_configured(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/subscription/validate-purchase",
+            &json!({ "purchase_token": "opaque-token.AO-J1Oy_example" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::INTERNAL_SERVER_ERROR)
+        .assert_error_message("Google Play purchases are not configured");
+
+    // The user must not be upgraded
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["subscription"]["tier"],
+        "free"
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_require_auth_to_validate_purchase(ctx: &TestContext) {
+    let response = ctx
+        .client
+        .post(
+            "/api/subscription/validate-purchase",
+            &json!({ "purchase_token": "token" }),
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_stripe_webhook_without_signature(ctx: &TestContext) {
+    let response = ctx
+        .client
+        .post(
+            "/webhooks/stripe",
+            &json!({ "id": "evt_1", "type": "checkout.session.completed" }),
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Missing Stripe-Signature header");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_fail_checkout_session_when_stripe_is_not_configured(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/subscription/checkout-session", &json!({}), &token)
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::INTERNAL_SERVER_ERROR)
+        .assert_error_message("Stripe purchases are not configured");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_fail_apple_webhook_when_app_store_is_not_configured(ctx: &TestContext) {
+    let response = ctx
+        .client
+        .post(
+            "/webhooks/apple",
+            &json!({ "signedPayload": "eyJhbGciOiJFUzI1NiJ9.e30.c2ln" }),
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::INTERNAL_SERVER_ERROR)
+        .assert_error_message("App Store purchases are not configured");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_start_free_trial(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/subscription/start-trial", &json!({}), &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["subscription_tier"], "free");
+    assert!(body["trial_started_at"].is_string());
+    assert!(body["trial_ends_at"].is_string());
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    response.assert_status(StatusCode::OK);
+    let trial = &response.body.as_ref().unwrap()["subscription"]["trial"];
+    assert_eq!(trial["expired"], false);
+    assert!(trial["ends_at"].is_string());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_not_start_a_second_free_trial(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .set_trial(
+            user.id,
+            Utc::now() - Duration::days(10),
+            Utc::now() - Duration::days(3),
+        )
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/subscription/start-trial", &json!({}), &token)
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::CONFLICT)
+        .assert_error_message("Free trial already used");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_not_start_free_trial_for_pro_users(ctx: &TestContext) {
+    let user = ctx
+        .fixtures
+        .create_pro_user("pro@example.com")
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/subscription/start-trial", &json!({}), &token)
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Pro subscribers cannot start a free trial");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_grant_pro_when_redeeming_promo_code(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_promo_code("GIFT30", 30, Some(1), None)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/subscription/redeem",
+            &json!({ "code": "gift30" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["subscription_tier"], "pro");
+    assert_eq!(body["subscription_status"], "active");
+
+    let updated = ctx.fixtures.get_user_by_id(user.id).await.unwrap().unwrap();
+    let expires_at = updated.subscription_expires_at.unwrap();
+    assert!(expires_at > Utc::now() + Duration::days(29));
+    assert!(expires_at <= Utc::now() + Duration::days(30));
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_extend_pro_when_redeeming_promo_code(ctx: &TestContext) {
+    let user = ctx
+        .fixtures
+        .create_pro_user("pro@example.com")
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_promo_code("EXTRA", 10, None, None)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/subscription/redeem",
+            &json!({ "code": "EXTRA" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let updated = ctx.fixtures.get_user_by_id(user.id).await.unwrap().unwrap();
+    assert!(updated.subscription_expires_at.unwrap() > Utc::now() + Duration::days(39));
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_single_use_promo_code_once_redeemed(ctx: &TestContext) {
+    let first = ctx.fixtures.create_user("first@example.com").await.unwrap();
+    let second = ctx
+        .fixtures
+        .create_user("second@example.com")
+        .await
+        .unwrap();
+    ctx.fixtures
+        .create_promo_code("ONCE", 30, Some(1), None)
+        .await
+        .unwrap();
+
+    let token = generate_test_jwt(&first.id, &ctx.config.jwt_secret);
+    ctx.client
+        .post_with_auth(
+            "/api/subscription/redeem",
+            &json!({ "code": "ONCE" }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let token = generate_test_jwt(&second.id, &ctx.config.jwt_secret);
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/subscription/redeem",
+            &json!({ "code": "ONCE" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Promo code has been fully redeemed");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_not_redeem_the_same_promo_code_twice(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_promo_code("CAMPAIGN", 7, None, None)
+        .await
+        .unwrap();
+
+    ctx.client
+        .post_with_auth(
+            "/api/subscription/redeem",
+            &json!({ "code": "CAMPAIGN" }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/subscription/redeem",
+            &json!({ "code": "CAMPAIGN" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::CONFLICT)
+        .assert_error_message("You have already redeemed this promo code");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_expired_promo_code(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_promo_code("OLD", 30, None, Some(Utc::now() - Duration::days(1)))
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/subscription/redeem",
+            &json!({ "code": "OLD" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Promo code has expired");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_return_empty_billing_history(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/subscription/history", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["subscription_tier"], "free");
+    assert!(body.get("renews_at").is_none());
+    assert_eq!(body["events"], json!([]));
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_list_promo_code_redemption_in_billing_history(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_promo_code("GIFT30", 30, Some(1), None)
+        .await
+        .unwrap();
+    ctx.client
+        .post_with_auth(
+            "/api/subscription/redeem",
+            &json!({ "code": "GIFT30" }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/subscription/history", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["subscription_tier"], "pro");
+    // Promo Pro ends instead of renewing
+    assert!(body.get("renews_at").is_none());
+    let events = body["events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["event_type"], "promo_code");
+    assert_eq!(events[0]["expires_at"], body["subscription_expires_at"]);
+    assert!(events[0].get("platform").is_none());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_require_auth_to_view_billing_history(ctx: &TestContext) {
+    let response = ctx.client.get("/api/subscription/history").await.unwrap();
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,187 @@
+use crate::e2e::helpers;
+
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_list_approved_submissions_among_the_suggestions(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let user_token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/feed-suggestions/submissions",
+            &json!({
+                "url": "https://blog.example.com/feed.xml",
+                "title": "Example Blog"
+            }),
+            &user_token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let submission = response.body.as_ref().unwrap();
+    assert_eq!(submission["status"], "pending");
+    let submission_id = submission["id"].as_str().unwrap().to_string();
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/suggestion-submissions", &admin_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let queue = response.body.as_ref().unwrap()["submissions"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0]["id"], submission_id.as_str());
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            &format!("/admin/suggestion-submissions/{}/approve", submission_id),
+            &json!({
+                "category_id": "technology-programming",
+                "description": "Notes on building software"
+            }),
+            &admin_token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let approved = response.body.as_ref().unwrap();
+    assert_eq!(approved["status"], "approved");
+    assert_eq!(approved["reviewed_by"], "admin@example.com");
+
+    let response = ctx
+        .client
+        .get_with_auth(
+            "/api/feed-suggestions?category_ids=technology-programming",
+            &user_token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let suggestions = response.body.as_ref().unwrap()["categories"][0]["suggestions"]
+        .as_array()
+        .unwrap()
+        .clone();
+    let promoted = suggestions
+        .iter()
+        .find(|suggestion| suggestion["url"] == "https://blog.example.com/feed.xml")
+        .expect("approved submission should be suggested");
+    assert_eq!(promoted["title"], "Example Blog");
+    assert_eq!(promoted["description"], "Notes on building software");
+
+    // Reviewed once, and the URL can't be submitted again
+    ctx.client
+        .post_with_auth(
+            &format!("/admin/suggestion-submissions/{}/reject", submission_id),
+            &json!({ "reason": "Changed my mind" }),
+            &admin_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::CONFLICT);
+    ctx.client
+        .post_with_auth(
+            "/api/feed-suggestions/submissions",
+            &json!({
+                "url": "https://blog.example.com/feed.xml",
+                "title": "Example Blog again"
+            }),
+            &user_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::CONFLICT);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_record_rejections_with_their_reason(ctx: &TestContext) {
+    let admin = ctx.fixtures.create_user("admin@example.com").await.unwrap();
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let admin_token = generate_test_jwt(&admin.id, &ctx.config.jwt_secret);
+    let user_token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/feed-suggestions/submissions",
+            &json!({ "url": "https://spam.example.com/rss", "title": "Deals" }),
+            &user_token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::CREATED);
+    let submission_id = response.body.as_ref().unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    ctx.client
+        .post_with_auth(
+            &format!("/admin/suggestion-submissions/{}/reject", submission_id),
+            &json!({ "reason": "  " }),
+            &admin_token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            &format!("/admin/suggestion-submissions/{}/reject", submission_id),
+            &json!({ "reason": "Mostly advertising" }),
+            &admin_token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["rejection_reason"],
+        "Mostly advertising"
+    );
+
+    let response = ctx
+        .client
+        .get_with_auth(
+            "/admin/suggestion-submissions?status=rejected",
+            &admin_token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["submissions"][0]["id"],
+        submission_id.as_str()
+    );
+
+    let response = ctx
+        .client
+        .get_with_auth("/admin/audit-log?action=suggestion_rejected", &admin_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["items"][0]["target"],
+        submission_id.as_str()
+    );
+
+    // Only admins see the queue
+    ctx.client
+        .get_with_auth("/admin/suggestion-submissions", &user_token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::FORBIDDEN);
+}
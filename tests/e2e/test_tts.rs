@@ -1,8 +1,13 @@
 use crate::e2e::helpers;
 
+use chrono::{Duration, Utc};
+use feedtape_backend::infrastructure::repositories::{
+    SynthesisUsage, UsageRepository, UsageRepositoryApi,
+};
 use helpers::{generate_test_jwt, TestContext};
 use hyper::StatusCode;
 use serde_json::json;
+use std::sync::Arc;
 use test_context::test_context;
 
 #[test_context(TestContext)]
@@ -359,6 +364,53 @@ async fn it_should_get_tts_usage_statistics(ctx: &TestContext) {
     }
 }
 
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_report_the_character_quota_in_rate_limit_headers(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .add_tts_usage(user.id, 15000, 10)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/tts/usage", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    let limit = body["limits"]["characters"].as_i64().unwrap();
+    let resets_at: chrono::DateTime<Utc> = body["resets_at"].as_str().unwrap().parse().unwrap();
+    response
+        .assert_header("x-ratelimit-limit", &limit.to_string())
+        .assert_header("x-ratelimit-remaining", &(limit - 15000).to_string())
+        .assert_header("x-ratelimit-reset", &resets_at.timestamp().to_string());
+
+    // A refused synthesis says the same
+    ctx.fixtures
+        .add_tts_usage(user.id, limit as i32 - 15000 - 100, 1)
+        .await
+        .unwrap();
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/tts/synthesize",
+            &json!({ "text": "a".repeat(200), "link": "https://example.com/test" }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::PAYMENT_REQUIRED)
+        .assert_header("x-ratelimit-limit", &limit.to_string())
+        .assert_header("x-ratelimit-remaining", "100")
+        .assert_header("x-ratelimit-reset", &resets_at.timestamp().to_string());
+}
+
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_track_usage_history(ctx: &TestContext) {
@@ -467,3 +519,276 @@ async fn it_should_include_usage_remaining_header(ctx: &TestContext) {
         assert!(response.header("x-usage-remaining").is_some());
     }
 }
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_warn_when_usage_nears_limit(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    // 85% of the free quota
+    ctx.fixtures.add_tts_usage(user.id, 17000, 8).await.unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/tts/usage", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let warnings = &response.body.as_ref().unwrap()["warnings"];
+    assert_eq!(warnings.as_array().unwrap().len(), 1);
+    assert_eq!(warnings[0]["kind"], "quota_nearly_exhausted");
+    assert_eq!(warnings[0]["threshold_percent"], 80);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/tts/synthesize",
+            &json!({
+                "text": "Almost out of minutes",
+                "link": "https://example.com/test"
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    // With mocked AWS, synthesis fails; the warning rides on successful responses
+    assert!(
+        response.status == StatusCode::OK
+            || response.status == StatusCode::SERVICE_UNAVAILABLE
+            || response.status == StatusCode::INTERNAL_SERVER_ERROR
+    );
+    if response.status == StatusCode::OK {
+        response.assert_header("x-usage-warning", "quota_nearly_exhausted");
+    }
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_roll_over_unused_quota_for_pro_users(ctx: &TestContext) {
+    let user = ctx
+        .fixtures
+        .create_pro_user("pro@example.com")
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let yesterday = Utc::now().date_naive() - Duration::days(1);
+    ctx.fixtures
+        .add_tts_usage_on(user.id, yesterday, 150_000, Some(0))
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/tts/usage", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["limits"]["characters"], 200_000);
+    assert_eq!(body["rollover_characters"], 50_000);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_cap_rollover_at_twice_the_daily_limit(ctx: &TestContext) {
+    let user = ctx
+        .fixtures
+        .create_pro_user("pro@example.com")
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let last_week = Utc::now().date_naive() - Duration::days(7);
+    ctx.fixtures
+        .add_tts_usage_on(user.id, last_week, 0, Some(0))
+        .await
+        .unwrap();
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let usage = &response.body.as_ref().unwrap()["subscription"]["usage"];
+    assert_eq!(usage["rollover_characters"], 400_000);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_allow_synthesis_past_daily_limit_with_rollover(ctx: &TestContext) {
+    let user = ctx
+        .fixtures
+        .create_pro_user("pro@example.com")
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let yesterday = Utc::now().date_naive() - Duration::days(1);
+    ctx.fixtures
+        .add_tts_usage_on(user.id, yesterday, 150_000, Some(0))
+        .await
+        .unwrap();
+    ctx.fixtures
+        .add_tts_usage(user.id, 200_000, 40)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/tts/synthesize",
+            &json!({
+                "text": "Rollover keeps this request within quota",
+                "link": "https://example.com/rollover"
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    // With mocked AWS, synthesis fails with 500, but the quota check has passed
+    assert_ne!(response.status, StatusCode::PAYMENT_REQUIRED);
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/tts/usage", &token)
+        .await
+        .unwrap();
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["rollover_characters"], 50_000);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_not_roll_over_quota_for_free_users(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let yesterday = Utc::now().date_naive() - Duration::days(1);
+    ctx.fixtures
+        .add_tts_usage_on(user.id, yesterday, 0, None)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/tts/usage", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    assert!(response
+        .body
+        .as_ref()
+        .unwrap()
+        .get("rollover_characters")
+        .is_none());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_count_buffered_usage_before_it_is_flushed(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let usage_repo = UsageRepository::new(Arc::new(ctx.pool.clone())).with_write_buffer();
+    let today = Utc::now().date_naive();
+    let tomorrow = today + Duration::days(1);
+    let synthesis = |characters, audio_seconds, engine: &str| SynthesisUsage {
+        characters,
+        audio_seconds,
+        provider: "polly".to_string(),
+        engine: engine.to_string(),
+    };
+
+    usage_repo
+        .increment_usage(user.id, today, synthesis(1000, 58.5, "standard"), None)
+        .await
+        .unwrap();
+    usage_repo
+        .increment_usage(user.id, today, synthesis(500, 31.5, "neural"), Some(200))
+        .await
+        .unwrap();
+
+    assert_eq!(ctx.fixtures.get_usage_day_count(user.id).await.unwrap(), 0);
+    let totals = usage_repo
+        .get_usage_between(user.id, today, tomorrow)
+        .await
+        .unwrap();
+    assert_eq!(totals.characters_used, 1500);
+    assert_eq!(totals.articles_synthesized, 2);
+    assert_eq!(totals.audio_seconds, 90.0);
+
+    assert_eq!(usage_repo.flush_pending().await.unwrap(), 1);
+    assert_eq!(usage_repo.flush_pending().await.unwrap(), 0);
+
+    let stored = UsageRepository::new(Arc::new(ctx.pool.clone()))
+        .get_rollover_days(user.id, today)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].characters_used, 1500);
+    assert_eq!(stored[0].rollover_characters, Some(200));
+
+    let days = usage_repo
+        .get_usage_days(user.id, today, today)
+        .await
+        .unwrap();
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0].audio_seconds, 90.0);
+    assert_eq!(days[0].provider.as_deref(), Some("polly"));
+    assert_eq!(days[0].engine.as_deref(), Some("neural"));
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_export_usage_as_csv(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let day = |d| chrono::NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+    ctx.fixtures
+        .add_tts_usage_on(user.id, day(1), 1500, None)
+        .await
+        .unwrap();
+    ctx.fixtures
+        .add_tts_usage_on(user.id, day(3), 4000, None)
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth(
+            "/api/tts/usage/export?format=csv&from=2026-03-01&to=2026-03-04",
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    response.assert_header("content-type", "text/csv; charset=utf-8");
+    response.assert_header(
+        "content-disposition",
+        "attachment; filename=\"feedtape-usage-2026-03-01-to-2026-03-04.csv\"",
+    );
+    assert_eq!(
+        String::from_utf8(response.body_bytes.clone()).unwrap(),
+        "date,characters,articles,minutes\n\
+         2026-03-01,1500,1,1.50\n\
+         2026-03-02,0,0,0.00\n\
+         2026-03-03,4000,1,4.00\n\
+         2026-03-04,0,0,0.00\n"
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_invalid_usage_export_ranges(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    for path in [
+        "/api/tts/usage/export?format=xlsx",
+        "/api/tts/usage/export?from=2026-03-04&to=2026-03-01",
+        "/api/tts/usage/export?from=2024-01-01&to=2026-01-01",
+    ] {
+        let response = ctx.client.get_with_auth(path, &token).await.unwrap();
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+}
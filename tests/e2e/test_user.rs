@@ -1,8 +1,12 @@
 use crate::e2e::helpers;
 
+use chrono::{DateTime, Duration, SecondsFormat, Timelike, Utc};
+use feedtape_backend::domain::user::SubscriptionStatus;
+use feedtape_backend::infrastructure::repositories::{UserRepository, UserRepositoryApi};
 use helpers::{generate_test_jwt, TestContext};
 use hyper::StatusCode;
 use serde_json::json;
+use std::sync::Arc;
 use test_context::test_context;
 
 #[test_context(TestContext)]
@@ -24,7 +28,18 @@ async fn it_should_get_current_user_info(ctx: &TestContext) {
             "id": user.id.to_string(),
             "settings": {
                 "voice": body["settings"]["voice"],
-                "language": body["settings"]["language"]
+                "language": body["settings"]["language"],
+                "notifications": {
+                    "digest_emails": false,
+                    "quota_warnings": false,
+                    "product_updates": false,
+                    "weekly_summary": false
+                },
+                "playback": {
+                    "speed": 1.0,
+                    "skip_silence": false,
+                    "auto_play_next": true
+                }
             },
             "subscription": {
                 "tier": "free",
@@ -59,7 +74,11 @@ async fn it_should_update_user_settings(ctx: &TestContext) {
         .await
         .unwrap();
 
-    response.assert_status(StatusCode::NO_CONTENT);
+    response.assert_status(StatusCode::OK);
+    assert!(response.header("etag").is_some());
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["settings"]["voice"], "voice_sergio_es");
+    assert_eq!(body["subscription"]["tier"], "free");
 
     // Verify settings persist by fetching user profile
     let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
@@ -95,7 +114,7 @@ async fn it_should_update_partial_settings(ctx: &TestContext) {
         .await
         .unwrap();
 
-    response.assert_status(StatusCode::NO_CONTENT);
+    response.assert_status(StatusCode::OK);
 
     // Verify partial update persisted
     let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
@@ -135,6 +154,53 @@ async fn it_should_show_pro_user_subscription(ctx: &TestContext) {
     assert!(max_feeds > 3, "Pro tier should allow more than 3 feeds");
 }
 
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_keep_pro_limits_during_grace_period(ctx: &TestContext) {
+    let user = ctx
+        .fixtures
+        .create_pro_user("pro@example.com")
+        .await
+        .unwrap();
+    ctx.fixtures
+        .set_subscription_status(user.id, SubscriptionStatus::GracePeriod)
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let subscription = &response.body.as_ref().unwrap()["subscription"];
+    assert_eq!(subscription["tier"], "pro");
+    assert_eq!(subscription["status"], "grace_period");
+    assert_eq!(subscription["usage"]["characters_limit"], 200000);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_apply_free_limits_while_on_hold(ctx: &TestContext) {
+    let user = ctx
+        .fixtures
+        .create_pro_user("pro@example.com")
+        .await
+        .unwrap();
+    ctx.fixtures
+        .set_subscription_status(user.id, SubscriptionStatus::OnHold)
+        .await
+        .unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let subscription = &response.body.as_ref().unwrap()["subscription"];
+    assert_eq!(subscription["tier"], "pro");
+    assert_eq!(subscription["status"], "on_hold");
+    assert_eq!(subscription["usage"]["characters_limit"], 20000);
+    assert_eq!(subscription["limits"]["max_feeds"], 3);
+}
+
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_show_usage_statistics(ctx: &TestContext) {
@@ -155,15 +221,37 @@ async fn it_should_show_usage_statistics(ctx: &TestContext) {
     assert_eq!(
         usage,
         &json!({
+            "period": "daily",
             "characters_used_today": 5000,
             "minutes_used_today": 5.0,  // 1000 chars = 1 minute
             "characters_limit": usage["characters_limit"],
             "minutes_limit": usage["minutes_limit"],
-            "resets_at": usage["resets_at"]
+            "resets_at": usage["resets_at"],
+            "warnings": []
         })
     );
 }
 
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_warn_when_quota_is_exhausted(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    ctx.fixtures
+        .add_tts_usage(user.id, 20000, 10)
+        .await
+        .unwrap();
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let warnings = &response.body.as_ref().unwrap()["subscription"]["usage"]["warnings"];
+    assert_eq!(warnings.as_array().unwrap().len(), 1);
+    assert_eq!(warnings[0]["kind"], "quota_exhausted");
+    assert_eq!(warnings[0]["threshold_percent"], 100);
+}
+
 #[test_context(TestContext)]
 #[tokio::test]
 async fn it_should_validate_language_settings(ctx: &TestContext) {
@@ -188,7 +276,7 @@ async fn it_should_validate_language_settings(ctx: &TestContext) {
             .await
             .unwrap();
 
-        response.assert_status(StatusCode::NO_CONTENT);
+        response.assert_status(StatusCode::OK);
     }
 
     // Invalid language
@@ -211,102 +299,835 @@ async fn it_should_validate_language_settings(ctx: &TestContext) {
 
 #[test_context(TestContext)]
 #[tokio::test]
-async fn it_should_require_authentication_for_user_endpoints(ctx: &TestContext) {
+async fn it_should_reject_unknown_voice(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
 
-    // Try to get user info without auth
-    let response = ctx.client.get("/api/me").await.unwrap();
-    response.assert_status(StatusCode::UNAUTHORIZED);
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "voice": "Sergoi"
+                }
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Invalid voice: Sergoi. Allowed values: Lucia, Sergio");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_voice_for_another_language(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
 
-    // Try to update settings without auth
     let response = ctx
         .client
-        .patch(
+        .patch_with_auth(
             "/api/me",
             &json!({
                 "settings": {
-                    "voice": "Sergio"
+                    "voice": "Sergio",
+                    "language": "fr"
                 }
             }),
+            &token,
         )
         .await
         .unwrap();
-    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Voice Sergio does not speak fr. Allowed values: Celine, Mathieu");
 }
 
 #[test_context(TestContext)]
 #[tokio::test]
-async fn it_should_validate_subscription_receipt(ctx: &TestContext) {
+async fn it_should_update_voice_per_language(ctx: &TestContext) {
     let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
     let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
 
-    // Note: The subscription receipt validation endpoint doesn't exist yet
-    // This is a placeholder test that should be implemented when the endpoint is added
     let response = ctx
         .client
-        .post_with_auth(
-            "/api/subscription/validate-receipt",
+        .patch_with_auth(
+            "/api/me",
             &json!({
-                "receipt_data": "base64_encoded_receipt_here",
-                "store": "apple"
+                "settings": {
+                    "voices": {
+                        "es": "Lupe",
+                        "en": "voice_joanna_en"
+                    }
+                }
             }),
             &token,
         )
         .await
         .unwrap();
 
-    // Since the endpoint doesn't exist, we expect a 404
-    assert_eq!(response.status, StatusCode::NOT_FOUND);
+    response.assert_status(StatusCode::OK);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(
+        body["settings"]["voices"],
+        json!({
+            "en": "voice_joanna_en",
+            "es": "voice_lupe_es"
+        })
+    );
 }
 
 #[test_context(TestContext)]
 #[tokio::test]
-async fn it_should_reject_invalid_jwt(ctx: &TestContext) {
-    let invalid_token = "invalid.jwt.token";
+async fn it_should_reject_voice_map_entry_for_wrong_language(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
 
     let response = ctx
         .client
-        .get_with_auth("/api/me", invalid_token)
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "voices": { "en": "Lupe" }
+                }
+            }),
+            &token,
+        )
         .await
         .unwrap();
 
-    response.assert_status(StatusCode::UNAUTHORIZED);
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Voice Lupe does not speak en");
 }
 
 #[test_context(TestContext)]
 #[tokio::test]
-async fn it_should_reject_expired_jwt(ctx: &TestContext) {
+async fn it_should_update_article_limit_settings(ctx: &TestContext) {
     let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
 
-    // Create an expired token
-    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
-    use serde::Serialize;
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "max_article_age_days": 7,
+                    "max_items_per_feed": 10
+                }
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
 
-    #[derive(Serialize)]
-    struct Claims {
-        sub: String,
-        exp: i64,
-        iat: i64,
-    }
+    response.assert_status(StatusCode::OK);
 
-    let now = chrono::Utc::now();
-    let claims = Claims {
-        sub: user.id.to_string(),
-        exp: (now - chrono::Duration::hours(1)).timestamp(), // Expired 1 hour ago
-        iat: (now - chrono::Duration::hours(2)).timestamp(),
-    };
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let body = response.body.as_ref().unwrap();
 
-    let expired_token = encode(
-        &Header::new(Algorithm::HS256),
-        &claims,
-        &EncodingKey::from_secret(ctx.config.jwt_secret.as_bytes()),
-    )
-    .unwrap();
+    assert_eq!(body["settings"]["max_article_age_days"], 7);
+    assert_eq!(body["settings"]["max_items_per_feed"], 10);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_out_of_range_article_limits(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
 
     let response = ctx
         .client
-        .get_with_auth("/api/me", &expired_token)
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "max_items_per_feed": 0
+                }
+            }),
+            &token,
+        )
         .await
         .unwrap();
 
-    response.assert_status(StatusCode::UNAUTHORIZED);
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("max_items_per_feed must be between 1 and 200");
+
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "max_article_age_days": 1000
+                }
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("max_article_age_days must be between 1 and 365");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reset_usage_at_local_midnight(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "timezone": "Asia/Tokyo"
+                }
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let body = response.body.as_ref().unwrap();
+
+    assert_eq!(body["settings"]["timezone"], "Asia/Tokyo");
+
+    // Midnight in Tokyo (UTC+9, no DST) is 15:00 UTC
+    let resets_at: DateTime<Utc> = body["subscription"]["usage"]["resets_at"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(resets_at.hour(), 15);
+    assert_eq!(resets_at.minute(), 0);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_unknown_timezone(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "timezone": "Mars/Olympus_Mons"
+                }
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Invalid timezone: Mars/Olympus_Mons");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_update_notification_preferences(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "notifications": { "quota_warnings": true }
+                }
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(
+        body["settings"]["notifications"],
+        json!({
+            "digest_emails": false,
+            "quota_warnings": true,
+            "product_updates": false,
+            "weekly_summary": false
+        })
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_empty_notification_update(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({ "settings": { "notifications": {} } }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("notifications must include at least one preference");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_update_playback_preferences(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "playback": { "speed": 1.5, "skip_silence": true }
+                }
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(
+        body["settings"]["playback"],
+        json!({
+            "speed": 1.5,
+            "skip_silence": true,
+            "auto_play_next": true
+        })
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_out_of_range_playback_speed(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({ "settings": { "playback": { "speed": 4.0 } } }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("playback speed must be between 0.5 and 3");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_update_settings_when_if_match_is_current(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let etag = response.header("etag").expect("Missing ETag").clone();
+
+    let response = ctx
+        .client
+        .patch_with_auth_and_headers(
+            "/api/me",
+            &json!({ "settings": { "language": "es" } }),
+            &token,
+            &[("If-Match", &etag)],
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let new_etag = response.header("etag").expect("Missing ETag");
+    assert_ne!(new_etag, &etag);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_return_not_modified_for_unchanged_profile(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let etag = response.header("etag").expect("Missing ETag").clone();
+
+    let response = ctx
+        .client
+        .get_with_auth_and_headers("/api/me", &token, &[("If-None-Match", &etag)])
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NOT_MODIFIED);
+    assert!(response.body_bytes.is_empty());
+
+    ctx.client
+        .patch_with_auth(
+            "/api/me",
+            &json!({ "settings": { "language": "fr" } }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .get_with_auth_and_headers("/api/me", &token, &[("If-None-Match", &etag)])
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(
+        response.body.as_ref().unwrap()["settings"]["language"],
+        "fr"
+    );
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_update_with_stale_if_match(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let etag = response.header("etag").expect("Missing ETag").clone();
+
+    // Another device updates the profile first
+    ctx.client
+        .patch_with_auth(
+            "/api/me",
+            &json!({ "settings": { "language": "fr" } }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx
+        .client
+        .patch_with_auth_and_headers(
+            "/api/me",
+            &json!({ "settings": { "language": "es" } }),
+            &token,
+            &[("If-Match", &etag)],
+        )
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::PRECONDITION_FAILED)
+        .assert_error_message("Profile was modified by another request");
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["settings"]["language"], "fr");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_update_display_name(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .patch_with_auth(
+            "/api/me",
+            &json!({ "display_name": "  Ada Lovelace  " }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["display_name"], "Ada Lovelace");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_blank_display_name(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .patch_with_auth("/api/me", &json!({ "display_name": "   " }), &token)
+        .await
+        .unwrap();
+
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Display name cannot be empty");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_schedule_account_deletion(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/me/delete", &json!({}), &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::ACCEPTED);
+    let scheduled_at: DateTime<Utc> = response.body.as_ref().unwrap()["deletion_scheduled_at"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!((scheduled_at - Utc::now()).num_days(), 13);
+
+    // The account stays usable during the grace window and reports the pending deletion
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    response.assert_status(StatusCode::OK);
+    assert!(response.body.as_ref().unwrap()["deletion_scheduled_at"].is_string());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_cancel_scheduled_account_deletion(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    ctx.client
+        .post_with_auth("/api/me/delete", &json!({}), &token)
+        .await
+        .unwrap()
+        .assert_status(StatusCode::ACCEPTED);
+
+    let response = ctx
+        .client
+        .post_with_auth("/api/me/delete/cancel", &json!({}), &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    assert!(response.body.as_ref().unwrap()["deletion_scheduled_at"].is_null());
+
+    // Nothing left to cancel
+    let response = ctx
+        .client
+        .post_with_auth("/api/me/delete/cancel", &json!({}), &token)
+        .await
+        .unwrap();
+    response
+        .assert_status(StatusCode::BAD_REQUEST)
+        .assert_error_message("Account is not scheduled for deletion");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_purge_usage_history_except_today(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let today = Utc::now().date_naive();
+    for days_ago in [3, 10] {
+        ctx.fixtures
+            .add_tts_usage_on(user.id, today - Duration::days(days_ago), 1000, None)
+            .await
+            .unwrap();
+    }
+    ctx.fixtures.add_tts_usage(user.id, 500, 1).await.unwrap();
+
+    let response = ctx
+        .client
+        .delete_with_auth("/api/me/usage-history", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["deleted_days"], 2);
+    assert_eq!(body["kept_from"], today.to_string());
+    assert_eq!(ctx.fixtures.get_usage_day_count(user.id).await.unwrap(), 1);
+    assert_eq!(
+        ctx.fixtures
+            .get_usage_history_purge_count(user.id)
+            .await
+            .unwrap(),
+        1
+    );
+
+    // Today's usage still counts against the quota
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let usage = &response.body.as_ref().unwrap()["subscription"]["usage"];
+    assert_eq!(usage["characters_used_today"], 500);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_block_access_once_deletion_date_passes(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .set_deletion_schedule(user.id, Utc::now() - chrono::Duration::hours(1))
+        .await
+        .unwrap();
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+
+    response
+        .assert_status(StatusCode::UNAUTHORIZED)
+        .assert_error_message("Account has been deleted");
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_soft_delete_accounts_before_purging_them(ctx: &TestContext) {
+    let user_repo = UserRepository::new(Arc::new(ctx.pool.clone()));
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let other = ctx.fixtures.create_user("other@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .set_deletion_schedule(user.id, Utc::now() - Duration::hours(1))
+        .await
+        .unwrap();
+
+    let deleted = user_repo.soft_delete_scheduled(Utc::now()).await.unwrap();
+
+    assert_eq!(deleted, vec![user.id]);
+    assert!(user_repo.find_by_id(user.id).await.unwrap().is_none());
+    assert!(user_repo
+        .find_by_email("user@example.com")
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(user_repo.count_users().await.unwrap().total, 1);
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    // The row is still there, hidden, until the retention period is over
+    let stored = ctx.fixtures.get_user_by_id(user.id).await.unwrap().unwrap();
+    assert!(stored.deleted_at.is_some());
+
+    let purged = user_repo
+        .purge_deleted(Utc::now() - Duration::days(1))
+        .await
+        .unwrap();
+    assert!(purged.is_empty());
+
+    let purged = user_repo.purge_deleted(Utc::now()).await.unwrap();
+    assert_eq!(purged, vec![user.id]);
+    assert!(ctx
+        .fixtures
+        .get_user_by_id(user.id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(user_repo.find_by_id(other.id).await.unwrap().is_some());
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_require_authentication_for_user_endpoints(ctx: &TestContext) {
+    // Try to get user info without auth
+    let response = ctx.client.get("/api/me").await.unwrap();
+    response.assert_status(StatusCode::UNAUTHORIZED);
+
+    // Try to update settings without auth
+    let response = ctx
+        .client
+        .patch(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "voice": "Sergio"
+                }
+            }),
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_validate_subscription_receipt(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    // Note: The subscription receipt validation endpoint doesn't exist yet
+    // This is a placeholder test that should be implemented when the endpoint is added
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/subscription/validate-receipt",
+            &json!({
+                "receipt_data": "base64_encoded_receipt_here",
+                "store": "apple"
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    // Since the endpoint doesn't exist, we expect a 404
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_invalid_jwt(ctx: &TestContext) {
+    let invalid_token = "invalid.jwt.token";
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me", invalid_token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_expired_jwt(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+
+    // Create an expired token
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Claims {
+        sub: String,
+        exp: i64,
+        iat: i64,
+    }
+
+    let now = chrono::Utc::now();
+    let claims = Claims {
+        sub: user.id.to_string(),
+        exp: (now - chrono::Duration::hours(1)).timestamp(), // Expired 1 hour ago
+        iat: (now - chrono::Duration::hours(2)).timestamp(),
+    };
+
+    let expired_token = encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(ctx.config.jwt_secret.as_bytes()),
+    )
+    .unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me", &expired_token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_report_tier_upgrade_in_account_events(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    ctx.fixtures
+        .create_promo_code("GIFT30", 30, Some(1), None)
+        .await
+        .unwrap();
+    let since = Utc::now() - Duration::minutes(1);
+
+    ctx.client
+        .post_with_auth(
+            "/api/subscription/redeem",
+            &json!({ "code": "GIFT30" }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let path = format!(
+        "/api/me/events?since={}",
+        since.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+    let response = ctx.client.get_with_auth(&path, &token).await.unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let body = response.body.as_ref().unwrap();
+    let events = body["events"].as_array().unwrap();
+    let upgrade = events
+        .iter()
+        .find(|event| event["kind"] == "tier_upgraded")
+        .expect("Missing tier_upgraded event");
+    assert_eq!(upgrade["subscription_event"], "promo_code");
+
+    // Nothing new since the previous response
+    let until = body["until"].as_str().unwrap();
+    let path = format!("/api/me/events?since={}", until);
+    let response = ctx.client.get_with_auth(&path, &token).await.unwrap();
+    response.assert_status(StatusCode::OK);
+    assert_eq!(response.body.as_ref().unwrap()["events"], json!([]));
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_report_trial_ending_soon_in_account_events(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+    let now = Utc::now();
+    ctx.fixtures
+        .set_trial(user.id, now - Duration::days(6), now + Duration::hours(24))
+        .await
+        .unwrap();
+
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/events", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let events = response.body.as_ref().unwrap()["events"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert!(events
+        .iter()
+        .any(|event| event["kind"] == "trial_ending_soon"));
+    assert!(!events.iter().any(|event| event["kind"] == "trial_ended"));
 }
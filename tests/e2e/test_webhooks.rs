@@ -0,0 +1,243 @@
+use crate::e2e::helpers;
+
+use feedtape_backend::domain::events::DomainEvent;
+use feedtape_backend::infrastructure::repositories::{OutboxRepository, OutboxRepositoryApi};
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_create_list_and_delete_webhooks(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/me/webhooks",
+            &json!({
+                "url": "https://hooks.example.com/feedtape",
+                "events": ["synthesis_completed", "quota_exceeded"]
+            }),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::CREATED);
+    let body = response.body.as_ref().unwrap();
+    assert_eq!(body["url"], "https://hooks.example.com/feedtape");
+    assert!(body["secret"].as_str().unwrap().starts_with("whsec_"));
+    let webhook_id = body["id"].as_str().unwrap().to_string();
+
+    // The secret is only shown once
+    let response = ctx
+        .client
+        .get_with_auth("/api/me/webhooks", &token)
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let webhooks = response.body.as_ref().unwrap()["webhooks"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(webhooks.len(), 1);
+    assert_eq!(webhooks[0]["id"], webhook_id);
+    assert!(webhooks[0].get("secret").is_none());
+    assert_eq!(webhooks[0]["events"].as_array().unwrap().len(), 2);
+
+    let response = ctx
+        .client
+        .delete_with_auth(&format!("/api/me/webhooks/{}", webhook_id), &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    let response = ctx
+        .client
+        .delete_with_auth(&format!("/api/me/webhooks/{}", webhook_id), &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_invalid_webhooks(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    for body in [
+        json!({ "url": "ftp://hooks.example.com", "events": ["new_articles"] }),
+        json!({ "url": "not a url", "events": ["new_articles"] }),
+        json!({ "url": "https://hooks.example.com", "events": [] }),
+    ] {
+        let response = ctx
+            .client
+            .post_with_auth("/api/me/webhooks", &body, &token)
+            .await
+            .unwrap();
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_hide_other_users_webhooks(ctx: &TestContext) {
+    let owner = ctx.fixtures.create_user("owner@example.com").await.unwrap();
+    let other = ctx.fixtures.create_user("other@example.com").await.unwrap();
+    let owner_token = generate_test_jwt(&owner.id, &ctx.config.jwt_secret);
+    let other_token = generate_test_jwt(&other.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/me/webhooks",
+            &json!({ "url": "https://hooks.example.com", "events": ["new_articles"] }),
+            &owner_token,
+        )
+        .await
+        .unwrap();
+    let webhook_id = response.body.as_ref().unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = ctx
+        .client
+        .get_with_auth(
+            &format!("/api/me/webhooks/{}/deliveries", webhook_id),
+            &other_token,
+        )
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = ctx
+        .client
+        .delete_with_auth(&format!("/api/me/webhooks/{}", webhook_id), &other_token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_queue_one_quota_exceeded_delivery_per_period(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/me/webhooks",
+            &json!({ "url": "https://hooks.example.com", "events": ["quota_exceeded"] }),
+            &token,
+        )
+        .await
+        .unwrap();
+    let webhook_id = response.body.as_ref().unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    ctx.fixtures
+        .add_tts_usage(user.id, 10_000_000, 1)
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        let response = ctx
+            .client
+            .post_with_auth(
+                "/api/tts/synthesize",
+                &json!({ "text": "Over the limit", "link": "https://example.com/article" }),
+                &token,
+            )
+            .await
+            .unwrap();
+        response.assert_status(StatusCode::PAYMENT_REQUIRED);
+    }
+
+    let response = ctx
+        .client
+        .get_with_auth(
+            &format!("/api/me/webhooks/{}/deliveries", webhook_id),
+            &token,
+        )
+        .await
+        .unwrap();
+
+    response.assert_status(StatusCode::OK);
+    let deliveries = response.body.as_ref().unwrap()["items"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0]["event_type"], "quota_exceeded");
+    assert_eq!(deliveries[0]["status"], "pending");
+    assert_eq!(deliveries[0]["attempts"], 0);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_queue_synthesis_completed_deliveries_from_the_outbox(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    let response = ctx
+        .client
+        .post_with_auth(
+            "/api/me/webhooks",
+            &json!({ "url": "https://hooks.example.com", "events": ["synthesis_completed"] }),
+            &token,
+        )
+        .await
+        .unwrap();
+    let webhook_id = response.body.as_ref().unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let outbox_repo = OutboxRepository::new(Arc::new(ctx.pool.clone()));
+    outbox_repo
+        .append(&DomainEvent::SynthesisCompleted {
+            user_id: user.id,
+            link: "https://example.com/article".to_string(),
+            language: "en".to_string(),
+            characters: 1200,
+            duration_minutes: 1.2,
+        })
+        .await
+        .unwrap();
+
+    let deliveries_path = format!("/api/me/webhooks/{}/deliveries", webhook_id);
+    let response = ctx
+        .client
+        .get_with_auth(&deliveries_path, &token)
+        .await
+        .unwrap();
+    assert_eq!(response.body.as_ref().unwrap()["items"], json!([]));
+
+    assert_eq!(ctx.dispatch_outbox().await, 1);
+    // Dispatched events are not handed out again
+    assert_eq!(ctx.dispatch_outbox().await, 0);
+
+    let response = ctx
+        .client
+        .get_with_auth(&deliveries_path, &token)
+        .await
+        .unwrap();
+    response.assert_status(StatusCode::OK);
+    let deliveries = response.body.as_ref().unwrap()["items"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0]["event_type"], "synthesis_completed");
+    assert_eq!(deliveries[0]["status"], "pending");
+}
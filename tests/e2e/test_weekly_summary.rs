@@ -0,0 +1,84 @@
+use crate::e2e::helpers;
+
+use feedtape_backend::domain::weekly_summary::UnsubscribeLinks;
+use helpers::{generate_test_jwt, TestContext};
+use hyper::StatusCode;
+use serde_json::json;
+use test_context::test_context;
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_turn_the_weekly_summary_off_from_the_unsubscribe_link(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+    let token = generate_test_jwt(&user.id, &ctx.config.jwt_secret);
+
+    ctx.client
+        .patch_with_auth(
+            "/api/me",
+            &json!({
+                "settings": {
+                    "notifications": { "weekly_summary": true }
+                }
+            }),
+            &token,
+        )
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let links = UnsubscribeLinks::new(
+        ctx.config.jwt_secret.clone(),
+        ctx.config.email.link_base_url.clone(),
+    );
+    let link = links.link(user.id);
+    let path = link.strip_prefix(&ctx.config.email.link_base_url).unwrap();
+
+    // Opening the link only asks for confirmation
+    let response = ctx.client.get(path).await.unwrap();
+    response.assert_status(StatusCode::OK);
+    let page = String::from_utf8(response.body_bytes.clone()).unwrap();
+    assert!(page.contains("<form method=\"post\""));
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    assert_eq!(
+        response.body.as_ref().unwrap()["settings"]["notifications"]["weekly_summary"],
+        true
+    );
+
+    ctx.client
+        .post(path, &json!({}))
+        .await
+        .unwrap()
+        .assert_status(StatusCode::OK);
+
+    let response = ctx.client.get_with_auth("/api/me", &token).await.unwrap();
+    let notifications = &response.body.as_ref().unwrap()["settings"]["notifications"];
+    assert_eq!(notifications["weekly_summary"], false);
+    assert_eq!(notifications["digest_emails"], false);
+}
+
+#[test_context(TestContext)]
+#[tokio::test]
+async fn it_should_reject_unsubscribe_links_it_did_not_sign(ctx: &TestContext) {
+    let user = ctx.fixtures.create_user("user@example.com").await.unwrap();
+
+    let forged = UnsubscribeLinks::new(
+        "another-secret".to_string(),
+        ctx.config.email.link_base_url.clone(),
+    )
+    .link(user.id);
+    let path = forged
+        .strip_prefix(&ctx.config.email.link_base_url)
+        .unwrap();
+
+    ctx.client
+        .post(path, &json!({}))
+        .await
+        .unwrap()
+        .assert_status(StatusCode::BAD_REQUEST);
+    ctx.client
+        .post("/email/unsubscribe?token=not-a-token", &json!({}))
+        .await
+        .unwrap()
+        .assert_status(StatusCode::BAD_REQUEST);
+}
@@ -1,4 +1,6 @@
 // Main entry point for e2e tests
 // This file includes all the test modules
+// The suite runs against Postgres in a container, so it is skipped for SQLite builds
+#![cfg(not(feature = "sqlite"))]
 
 mod e2e;